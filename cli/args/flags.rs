@@ -32,6 +32,7 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::args::resolve_no_prompt;
 use crate::util::fs::canonicalize_path;
@@ -56,6 +57,7 @@ pub struct BenchFlags {
   pub filter: Option<String>,
   pub json: bool,
   pub no_run: bool,
+  pub output_path: Option<String>,
   pub watch: Option<WatchFlags>,
 }
 
@@ -84,6 +86,25 @@ pub struct CompileFlags {
   pub target: Option<String>,
   pub no_terminal: bool,
   pub include: Vec<String>,
+  pub permission_override: PermissionOverridePolicy,
+}
+
+/// Whether the users of a compiled executable may grant it more permissions
+/// than the ones embedded at compile time.
+#[derive(
+  Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionOverridePolicy {
+  /// The embedded permissions are final and are never prompted for.
+  Never,
+  /// Permissions that weren't embedded may be granted through a prompt.
+  #[default]
+  Prompt,
+  /// Additionally allows granting permissions through the
+  /// `DENO_STANDALONE_PERMISSIONS` environment variable, which holds a JSON
+  /// array of permission flags.
+  Allow,
 }
 
 impl CompileFlags {
@@ -115,7 +136,9 @@ pub struct CoverageFlags {
   pub output: Option<String>,
   pub include: Vec<String>,
   pub exclude: Vec<String>,
+  pub npm_packages: Vec<String>,
   pub r#type: CoverageType,
+  pub merge: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -152,9 +175,21 @@ pub struct EvalFlags {
   pub code: String,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FmtDiffFormat {
+  /// A human readable diff printed to the terminal.
+  #[default]
+  Pretty,
+  /// A unified diff which can be applied with `git apply`.
+  Patch,
+  /// A JSON list of the edits required to format each file.
+  Json,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FmtFlags {
   pub check: bool,
+  pub diff_format: FmtDiffFormat,
   pub files: FileFlags,
   pub use_tabs: Option<bool>,
   pub line_width: Option<NonZeroU32>,
@@ -180,6 +215,7 @@ pub struct InitFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InfoFlags {
   pub json: bool,
+  pub manifest: bool,
   pub file: Option<String>,
 }
 
@@ -303,6 +339,8 @@ pub struct WatchFlags {
   pub hmr: bool,
   pub no_clear_screen: bool,
   pub exclude: Vec<String>,
+  /// Milliseconds to wait for more file changes before restarting.
+  pub debounce: Option<u64>,
 }
 
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
@@ -311,12 +349,24 @@ pub struct WatchFlagsWithPaths {
   pub paths: Vec<String>,
   pub no_clear_screen: bool,
   pub exclude: Vec<String>,
+  /// Milliseconds to wait for more file changes before restarting.
+  pub debounce: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskListFormat {
+  Pretty,
+  Json,
+  /// A line with the name and description of each task, separated by a tab,
+  /// for the shell completions.
+  Completions,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  pub list: Option<TaskListFormat>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -343,6 +393,7 @@ pub struct TestFlags {
   pub watch: Option<WatchFlags>,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub output_path: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -352,6 +403,7 @@ pub struct UpgradeFlags {
   pub canary: bool,
   pub version: Option<String>,
   pub output: Option<String>,
+  pub side_by_side: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -475,6 +527,17 @@ pub enum CaData {
   Bytes(Vec<u8>),
 }
 
+/// The storage backend of the remote module cache, see `--cache-backend`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CacheBackend {
+  /// Stores everything in DENO_DIR.
+  Local,
+  /// Like `Local`, but also reads from the read-only DENO_SHARED_DIR.
+  Shared,
+  /// Keeps everything in memory for the current process only.
+  Memory,
+}
+
 #[derive(
   Clone, Default, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize,
 )]
@@ -500,9 +563,11 @@ pub struct Flags {
   /// the language server is configured with an explicit cache option.
   pub cache_path: Option<PathBuf>,
   pub cached_only: bool,
+  pub cache_backend: Option<CacheBackend>,
   pub type_check_mode: TypeCheckMode,
   pub config_flag: ConfigFlag,
   pub node_modules_dir: Option<bool>,
+  pub preserve_symlinks: bool,
   pub vendor: Option<bool>,
   pub enable_op_summary_metrics: bool,
   pub enable_testing_features: bool,
@@ -523,6 +588,7 @@ pub struct Flags {
   pub reload: bool,
   pub seed: Option<u64>,
   pub strace_ops: Option<Vec<String>>,
+  pub op_metrics: bool,
   pub unstable_config: UnstableConfig,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub v8_flags: Vec<String>,
@@ -553,6 +619,55 @@ pub struct PermissionFlags {
 }
 
 impl PermissionFlags {
+  /// Parses permission flags (ex. `--allow-net --allow-read=/tmp`), without
+  /// the leading binary name or subcommand.
+  pub fn from_args(
+    args: impl IntoIterator<Item = String>,
+  ) -> clap::error::Result<Self> {
+    let mut app = permission_args(Command::new("deno"));
+    let mut matches = app.try_get_matches_from_mut(
+      std::iter::once("deno".to_string()).chain(args),
+    )?;
+    let mut flags = Flags::default();
+    permission_args_parse(&mut flags, &mut matches);
+    Ok(flags.permissions)
+  }
+
+  /// Adds the permissions allowed or denied by `other` to these flags.
+  pub fn extend(&mut self, other: PermissionFlags) {
+    fn extend_list(list: &mut Option<Vec<String>>, other: Option<Vec<String>>) {
+      let Some(other) = other else {
+        return;
+      };
+      match list {
+        None => *list = Some(other),
+        // an empty list applies to everything already
+        Some(list) if list.is_empty() => {}
+        Some(list) if other.is_empty() => list.clear(),
+        Some(list) => list.extend(other),
+      }
+    }
+
+    self.allow_all |= other.allow_all;
+    extend_list(&mut self.allow_env, other.allow_env);
+    extend_list(&mut self.deny_env, other.deny_env);
+    self.allow_hrtime |= other.allow_hrtime;
+    self.deny_hrtime |= other.deny_hrtime;
+    extend_list(&mut self.allow_ffi, other.allow_ffi);
+    extend_list(&mut self.deny_ffi, other.deny_ffi);
+    extend_list(&mut self.allow_net, other.allow_net);
+    extend_list(&mut self.deny_net, other.deny_net);
+    extend_list(&mut self.allow_read, other.allow_read);
+    extend_list(&mut self.deny_read, other.deny_read);
+    extend_list(&mut self.allow_run, other.allow_run);
+    extend_list(&mut self.deny_run, other.deny_run);
+    extend_list(&mut self.allow_sys, other.allow_sys);
+    extend_list(&mut self.deny_sys, other.deny_sys);
+    extend_list(&mut self.allow_write, other.allow_write);
+    extend_list(&mut self.deny_write, other.deny_write);
+    self.no_prompt |= other.no_prompt;
+  }
+
   pub fn has_permission(&self) -> bool {
     self.allow_all
       || self.allow_env.is_some()
@@ -967,6 +1082,36 @@ impl Flags {
     self.permissions.allow_hrtime = true;
   }
 
+  /// Returns the interval to wait for more file changes before restarting
+  /// in watch mode, if it was overridden with `--watch-debounce`.
+  pub fn watch_debounce_interval(&self) -> Option<Duration> {
+    let debounce = match &self.subcommand {
+      DenoSubcommand::Run(RunFlags {
+        watch: Some(watch), ..
+      })
+      | DenoSubcommand::Serve(ServeFlags {
+        watch: Some(watch), ..
+      }) => watch.debounce,
+      DenoSubcommand::Bundle(BundleFlags {
+        watch: Some(watch), ..
+      })
+      | DenoSubcommand::Bench(BenchFlags {
+        watch: Some(watch), ..
+      })
+      | DenoSubcommand::Test(TestFlags {
+        watch: Some(watch), ..
+      })
+      | DenoSubcommand::Lint(LintFlags {
+        watch: Some(watch), ..
+      })
+      | DenoSubcommand::Fmt(FmtFlags {
+        watch: Some(watch), ..
+      }) => watch.debounce,
+      _ => None,
+    };
+    debounce.map(Duration::from_millis)
+  }
+
   pub fn resolve_watch_exclude_set(
     &self,
   ) -> Result<PathOrPatternSet, AnyError> {
@@ -1043,6 +1188,10 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
 
     <g>DENO_CERT</>            Load certificate authority from PEM encoded file
 
+    <g>DENO_CONSOLE_TIMESTAMPS</>
+                         Set to prefix every line printed by the global
+                         console with an ISO 8601 timestamp
+
     <g>DENO_DIR</>             Set the cache directory
 
     <g>DENO_INSTALL_ROOT</>    Set deno install's output directory
@@ -1056,6 +1205,10 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
                          History file is disabled when the value is empty
                          (defaults to $DENO_DIR/deno_history.txt)
 
+    <g>DENO_SHARED_DIR</>      Read-only cache directory (ex. a mounted CI cache)
+                         used for remote modules not found in $DENO_DIR
+                         (see --cache-backend)
+
     <g>DENO_NO_PACKAGE_JSON</> Disables auto-resolution of package.json
 
     <g>DENO_NO_PROMPT</>       Set to disable permission prompts on access
@@ -1064,6 +1217,9 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
     <g>DENO_NO_UPDATE_CHECK</> Set to disable checking if a newer Deno version is
                          available
 
+    <g>DENO_NO_VERSION_PIN</>  Set to ignore the Deno version pinned by the
+                         "deno" field of the config file
+
     <g>DENO_TLS_CA_STORE</>    Comma-separated list of order dependent certificate
                          stores. Possible values: "system", "mozilla".
                          Defaults to "mozilla".
@@ -1390,6 +1546,13 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
             .action(ArgAction::SetTrue)
             .help("UNSTABLE: Output benchmark result in JSON format"),
         )
+        .arg(
+          Arg::new("output")
+            .long("output")
+            .value_name("PATH")
+            .value_hint(ValueHint::FilePath)
+            .help("UNSTABLE: Stream benchmark events to PATH as JSON Lines, in addition to the console output"),
+        )
         .arg(
           Arg::new("ignore")
             .long("ignore")
@@ -1420,6 +1583,7 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
         )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
+        .arg(watch_debounce_arg())
         .arg(no_clear_screen_arg())
         .arg(script_arg().last(true))
         .arg(env_file_arg())
@@ -1453,6 +1617,7 @@ If no output file is given, the output is written to standard output:
         .arg(Arg::new("out_file").value_hint(ValueHint::FilePath))
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
+        .arg(watch_debounce_arg())
         .arg(no_clear_screen_arg())
         .arg(executable_ext_arg())
     })
@@ -1578,6 +1743,24 @@ supported in canary.
             "aarch64-apple-darwin",
           ]),
       )
+      .arg(
+        Arg::new("permission-override")
+          .long("permission-override")
+          .value_name("POLICY")
+          .help("Whether users of the executable may grant it more permissions")
+          .long_help(
+            "Controls whether users of the executable may grant it more
+permissions than the ones passed to 'deno compile':
+    never   Permissions that were not granted at compile time are denied
+            without prompting.
+    prompt  Permissions that were not granted at compile time may be granted
+            through a prompt (default).
+    allow   Additionally reads permission flags from the
+            DENO_STANDALONE_PERMISSIONS environment variable, as a JSON array
+            such as '[\"--allow-net\", \"--allow-read=/my dir\"]'.",
+          )
+          .value_parser(["never", "prompt", "allow"]),
+      )
       .arg(
         Arg::new("no-terminal")
           .long("no-terminal")
@@ -1636,6 +1819,15 @@ not match the exclude pattern:
 
   deno coverage --include=\"^file:\" --exclude=\"test\\.(ts|js)\" cov_profile
 
+Only include the source files of some npm packages:
+
+  deno coverage --npm-package=chalk --npm-package=@std/path cov_profile
+
+Merge the profiles of several runs, like the shards of a CI job, into a single
+profile that can be reported on later:
+
+  deno coverage --merge=cov_merged cov_shard_1/ cov_shard_2/
+
 Write a report using the lcov format:
 
   deno coverage --lcov --output=cov.lcov cov_profile/
@@ -1676,6 +1868,15 @@ Generate html reports from lcov:
             .default_value(r"test\.(js|mjs|ts|jsx|tsx)$")
             .help("Exclude source files from the report"),
         )
+        .arg(
+          Arg::new("npm-package")
+            .long("npm-package")
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .value_name("name")
+            .require_equals(true)
+            .help("Only include the source files of the given npm packages"),
+        )
         .arg(
           Arg::new("lcov")
             .long("lcov")
@@ -1710,6 +1911,17 @@ Generate html reports from lcov:
             .help("Output coverage report in detailed format in the terminal.")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("merge")
+            .long("merge")
+            .value_name("DIR")
+            .conflicts_with_all(["lcov", "html", "detailed"])
+            .require_equals(true)
+            .help(
+              "Merge the coverage profiles into the given directory instead of printing a report",
+            )
+            .value_hint(ValueHint::DirPath),
+        )
         .arg(
           Arg::new("files")
             .num_args(0..)
@@ -1894,9 +2106,19 @@ Ignore formatting code by preceding it with an ignore comment:
 
   // deno-fmt-ignore
 
+Ignore formatting a range of lines by surrounding them with ignore comments:
+
+  // deno-fmt-ignore-start
+  // deno-fmt-ignore-end
+
 Ignore formatting a file by adding an ignore comment at the top of the file:
 
-  // deno-fmt-ignore-file",
+  // deno-fmt-ignore-file
+
+Override the formatting options of a file by adding a comment at the top of the
+file:
+
+  // deno-fmt-options lineWidth=120 semiColons=false",
     )
     .defer(|cmd| {
       cmd
@@ -1908,6 +2130,19 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .help("Check if the source files are formatted")
             .num_args(0),
         )
+        .arg(
+          Arg::new("diff-format")
+            .long("diff-format")
+            .help("Set the output format of the diffs printed by --check")
+            .long_help(
+              "Set the output format of the diffs printed by --check.
+'pretty' prints a human readable diff (the default), 'patch' prints a
+unified diff which can be applied with `git apply` and 'json' prints a
+list of the edits required to format each file.",
+            )
+            .requires("check")
+            .value_parser(["pretty", "patch", "json"]),
+        )
         .arg(
           Arg::new("ext")
             .long("ext")
@@ -1936,6 +2171,7 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
         )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
+        .arg(watch_debounce_arg())
         .arg(no_clear_screen_arg())
         .arg(
           Arg::new("use-tabs")
@@ -2049,11 +2285,20 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
       .arg(config_arg())
       .arg(import_map_arg())
       .arg(node_modules_dir_arg())
+      .arg(preserve_symlinks_arg())
       .arg(vendor_arg())
       .arg(
         Arg::new("json")
           .long("json")
-          .help("UNSTABLE: Outputs the information in JSON format")
+          .help("Outputs the information in JSON format. The output of a file is versioned and described by https://deno.land/x/deno/cli/schemas/info.v1.json")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("manifest")
+          .long("manifest")
+          .help("UNSTABLE: Outputs a deterministic JSON manifest of the module graph, listing the hash, media type, dependencies and emit path of every module")
+          .requires("file")
+          .conflicts_with("json")
           .action(ArgAction::SetTrue),
       ))
 }
@@ -2381,6 +2626,7 @@ Ignore linting a file by adding an ignore comment at the top of the file:
         )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
+        .arg(watch_debounce_arg())
         .arg(no_clear_screen_arg())
     })
 }
@@ -2413,6 +2659,7 @@ fn run_subcommand() -> Command {
     .arg(check_arg(false))
     .arg(watch_arg(true))
     .arg(watch_exclude_arg())
+    .arg(watch_debounce_arg())
     .arg(hmr_arg(true))
     .arg(no_clear_screen_arg())
     .arg(executable_ext_arg())
@@ -2475,6 +2722,7 @@ fn serve_subcommand() -> Command {
     .arg(check_arg(false))
     .arg(watch_arg(true))
     .arg(watch_exclude_arg())
+    .arg(watch_debounce_arg())
     .arg(hmr_arg(true))
     .arg(no_clear_screen_arg())
     .arg(executable_ext_arg())
@@ -2509,7 +2757,11 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+List the available tasks as JSON:
+
+  deno task --list --json",
     )
     .defer(|cmd| {
       cmd
@@ -2523,6 +2775,27 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(
+          Arg::new("list")
+            .long("list")
+            .help("List the available tasks")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .help("Output the list of tasks in JSON format")
+            .requires("list")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("completions")
+            .long("completions")
+            .requires("list")
+            .conflicts_with("json")
+            .action(ArgAction::SetTrue)
+            .hide(true),
+        )
     })
 }
 
@@ -2575,7 +2848,7 @@ Directory arguments are expanded to all contained files matching the glob
     .arg(
       Arg::new("doc")
         .long("doc")
-        .help("Type-check code blocks in JSDoc and Markdown")
+        .help("Type-check code blocks in JSDoc and Markdown, and run those marked as `test`")
         .action(ArgAction::SetTrue),
     )
     .arg(
@@ -2650,6 +2923,7 @@ Directory arguments are expanded to all contained files matching the glob
         .conflicts_with("coverage"),
     )
     .arg(watch_exclude_arg())
+    .arg(watch_debounce_arg())
     .arg(no_clear_screen_arg())
     .arg(script_arg().last(true))
     .arg(
@@ -2659,6 +2933,13 @@ Directory arguments are expanded to all contained files matching the glob
         .value_hint(ValueHint::FilePath)
         .help("Write a JUnit XML test report to PATH. Use '-' to write to stdout which is the default when PATH is not provided.")
     )
+    .arg(
+      Arg::new("output")
+        .long("output")
+        .value_name("PATH")
+        .value_hint(ValueHint::FilePath)
+        .help("UNSTABLE: Stream test events to PATH as JSON Lines, in addition to the selected reporter")
+    )
     .arg(
       Arg::new("reporter")
         .long("reporter")
@@ -2695,7 +2976,13 @@ and is used to replace the current executable.
 If you want to not replace the current Deno executable but instead download an
 update to a different location, use the --output flag
 
-  deno upgrade --output $HOME/my_deno",
+  deno upgrade --output $HOME/my_deno
+
+To install a version next to the current one, in the Deno cache directory, use
+the --side-by-side flag. It is then used in the projects whose config file pins
+a matching version with the \"deno\" field
+
+  deno upgrade --side-by-side --version 1.40.5",
     )
     .hide(cfg!(not(feature = "upgrade")))
     .defer(|cmd| {
@@ -2731,6 +3018,13 @@ update to a different location, use the --output flag
             .help("Upgrade to canary builds")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("side-by-side")
+            .long("side-by-side")
+            .help("Install the version in the Deno cache directory instead of replacing the current exe")
+            .conflicts_with_all(["output", "canary"])
+            .action(ArgAction::SetTrue),
+        )
         .arg(ca_file_arg())
     })
 }
@@ -2780,6 +3074,7 @@ Remote modules and multiple modules may also be specified:
       .arg(import_map_arg())
       .arg(lock_arg())
       .arg(node_modules_dir_arg())
+      .arg(preserve_symlinks_arg())
       .arg(vendor_arg())
       .arg(reload_arg())
       .arg(ca_file_arg()))
@@ -2836,6 +3131,7 @@ fn compile_args_without_check_args(app: Command) -> Command {
     .arg(no_remote_arg())
     .arg(no_npm_arg())
     .arg(node_modules_dir_arg())
+    .arg(preserve_symlinks_arg())
     .arg(vendor_arg())
     .arg(config_arg())
     .arg(no_config_arg())
@@ -3219,11 +3515,13 @@ fn runtime_args(
   };
   app
     .arg(cached_only_arg())
+    .arg(cache_backend_arg())
     .arg(location_arg())
     .arg(v8_flags_arg())
     .arg(seed_arg())
     .arg(enable_testing_features_arg())
     .arg(strace_ops_arg())
+    .arg(op_metrics_arg())
 }
 
 fn inspect_args(app: Command) -> Command {
@@ -3331,6 +3629,21 @@ fn cached_only_arg() -> Arg {
     .help("Require that remote dependencies are already cached")
 }
 
+fn cache_backend_arg() -> Arg {
+  Arg::new("cache-backend")
+    .long("cache-backend")
+    .value_name("BACKEND")
+    .value_parser(["local", "shared", "memory"])
+    .help("Storage backend of the remote module cache")
+    .long_help(
+      "Storage backend of the remote module cache. \"local\" stores \
+      everything in DENO_DIR, \"shared\" also reads from the read-only \
+      DENO_SHARED_DIR and \"memory\" keeps downloads for the current run \
+      only. Defaults to \"shared\" when DENO_SHARED_DIR is set, otherwise to \
+      \"local\".",
+    )
+}
+
 /// Used for subcommands that operate on executable scripts only.
 /// `deno fmt` has its own `--ext` arg because its possible values differ.
 /// If --ext is not provided and the script doesn't have a file extension,
@@ -3382,6 +3695,14 @@ fn strace_ops_arg() -> Arg {
     .hide(true)
 }
 
+fn op_metrics_arg() -> Arg {
+  Arg::new("op-metrics")
+    .long("op-metrics")
+    .help("Record timing histograms for every op")
+    .action(ArgAction::SetTrue)
+    .hide(true)
+}
+
 fn v8_flags_arg() -> Arg {
   Arg::new("v8-flags")
     .long("v8-flags")
@@ -3481,6 +3802,15 @@ fn watch_exclude_arg() -> Arg {
     .value_hint(ValueHint::AnyPath)
 }
 
+fn watch_debounce_arg() -> Arg {
+  Arg::new("watch-debounce")
+    .long("watch-debounce")
+    .help("Milliseconds to wait for more file changes before restarting in watch mode [default: 200]")
+    .value_name("MS")
+    .value_parser(value_parser!(u64))
+    .require_equals(true)
+}
+
 fn no_check_arg() -> Arg {
   Arg::new("no-check")
     .num_args(0..=1)
@@ -3619,6 +3949,13 @@ fn node_modules_dir_arg() -> Arg {
     .help("Enables or disables the use of a local node_modules folder for npm packages")
 }
 
+fn preserve_symlinks_arg() -> Arg {
+  Arg::new("preserve-symlinks")
+    .long("preserve-symlinks")
+    .action(ArgAction::SetTrue)
+    .help("Resolve npm packages and CommonJS modules from their symlinked paths in node_modules instead of their real paths")
+}
+
 fn vendor_arg() -> Arg {
   Arg::new("vendor")
     .long("vendor")
@@ -3685,12 +4022,14 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
 
   let no_run = matches.get_flag("no-run");
+  let output_path = matches.remove_one::<String>("output");
 
   flags.subcommand = DenoSubcommand::Bench(BenchFlags {
     files: FileFlags { include, ignore },
     filter,
     json,
     no_run,
+    output_path,
     watch: watch_arg_parse(matches),
   });
 }
@@ -3749,6 +4088,14 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
+  let permission_override = match matches
+    .remove_one::<String>("permission-override")
+    .as_deref()
+  {
+    Some("never") => PermissionOverridePolicy::Never,
+    Some("allow") => PermissionOverridePolicy::Allow,
+    _ => PermissionOverridePolicy::Prompt,
+  };
   ext_arg_parse(flags, matches);
 
   flags.subcommand = DenoSubcommand::Compile(CompileFlags {
@@ -3758,9 +4105,69 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     target,
     no_terminal,
     include,
+    permission_override,
   });
 }
 
+const BASH_TASK_COMPLETIONS: &str = r#"
+_deno_tasks() {
+    if [[ ${COMP_CWORD} -eq 2 && "${COMP_WORDS[1]}" == "task" && "${COMP_WORDS[2]}" != -* ]]; then
+        local tasks
+        tasks="$(deno task --list --completions 2>/dev/null | cut -f1)"
+        if [[ -n "${tasks}" ]]; then
+            COMPREPLY=( $(compgen -W "${tasks}" -- "${COMP_WORDS[2]}") )
+            return 0
+        fi
+    fi
+    _deno "$@"
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _deno_tasks -o nosort -o bashdefault -o default deno
+else
+    complete -F _deno_tasks -o bashdefault -o default deno
+fi
+"#;
+
+const FISH_TASK_COMPLETIONS: &str = r#"complete -c deno -n "__fish_seen_subcommand_from task; and test (count (commandline -opc)) -eq 2" -f -a "(deno task --list --completions 2>/dev/null)"
+"#;
+
+const POWERSHELL_TASK_CASE: &str = "\n        'deno;task' {";
+
+const POWERSHELL_TASK_COMPLETIONS: &str = r#"
+            deno task --list --completions 2>$null | ForEach-Object {
+                $name, $description = $_ -split "`t", 2
+                if (-not $description) { $description = $name }
+                [CompletionResult]::new($name, $name, [CompletionResultType]::ParameterValue, $description)
+            }"#;
+
+/// The end of the zsh script, which is replaced to complete tasks first.
+const ZSH_ENTRY_POINT: &str = r#"if [ "$funcstack[1]" = "_deno" ]; then
+    _deno "$@"
+else
+    compdef _deno deno
+fi
+"#;
+
+const ZSH_TASK_COMPLETIONS: &str = r#"_deno_tasks() {
+    if (( CURRENT == 3 )) && [[ ${words[2]} == task ]]; then
+        local -a tasks
+        local line
+        for line in ${(f)"$(deno task --list --completions 2>/dev/null)"}; do
+            tasks+=("${${line%%$'\t'*}//:/\\:}:${line#*$'\t'}")
+        done
+        (( ${#tasks} )) && _describe 'task' tasks && return
+    fi
+    _deno "$@"
+}
+
+if [ "$funcstack[1]" = "_deno" ]; then
+    _deno_tasks "$@"
+else
+    compdef _deno_tasks deno
+fi
+"#;
+
 fn completions_parse(
   flags: &mut Flags,
   matches: &mut ArgMatches,
@@ -3776,11 +4183,35 @@ fn completions_parse(
   let mut buf: Vec<u8> = vec![];
   let name = "deno";
 
+  // Task names come from the configuration file, so the scripts complete
+  // them by calling `deno task --list --completions`.
   match matches.get_one::<String>("shell").unwrap().as_str() {
-    "bash" => generate(Bash, &mut app, name, &mut buf),
-    "fish" => generate(Fish, &mut app, name, &mut buf),
-    "powershell" => generate(PowerShell, &mut app, name, &mut buf),
-    "zsh" => generate(Zsh, &mut app, name, &mut buf),
+    "bash" => {
+      generate(Bash, &mut app, name, &mut buf);
+      buf.extend_from_slice(BASH_TASK_COMPLETIONS.as_bytes());
+    }
+    "fish" => {
+      generate(Fish, &mut app, name, &mut buf);
+      buf.extend_from_slice(FISH_TASK_COMPLETIONS.as_bytes());
+    }
+    "powershell" => {
+      generate(PowerShell, &mut app, name, &mut buf);
+      let script = String::from_utf8(buf).unwrap();
+      buf = script
+        .replacen(
+          POWERSHELL_TASK_CASE,
+          &format!("{POWERSHELL_TASK_CASE}{POWERSHELL_TASK_COMPLETIONS}"),
+          1,
+        )
+        .into_bytes();
+    }
+    "zsh" => {
+      generate(Zsh, &mut app, name, &mut buf);
+      let script = String::from_utf8(buf).unwrap();
+      buf = script
+        .replacen(ZSH_ENTRY_POINT, ZSH_TASK_COMPLETIONS, 1)
+        .into_bytes();
+    }
     "fig" => generate(Fig, &mut app, name, &mut buf),
     _ => unreachable!(),
   }
@@ -3816,7 +4247,12 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   } else {
     CoverageType::Summary
   };
+  let npm_packages = match matches.remove_many::<String>("npm-package") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
   let output = matches.remove_one::<String>("output");
+  let merge = matches.remove_one::<String>("merge");
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -3825,7 +4261,9 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     output,
     include,
     exclude,
+    npm_packages,
     r#type,
+    merge,
   });
 }
 
@@ -3928,8 +4366,19 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let prose_wrap = matches.remove_one::<String>("prose-wrap");
   let no_semicolons = matches.remove_one::<bool>("no-semicolons");
 
+  let diff_format = match matches.remove_one::<String>("diff-format") {
+    Some(format) => match format.as_str() {
+      "pretty" => FmtDiffFormat::Pretty,
+      "patch" => FmtDiffFormat::Patch,
+      "json" => FmtDiffFormat::Json,
+      _ => unreachable!(),
+    },
+    None => FmtDiffFormat::Pretty,
+  };
+
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
+    diff_format,
     files: FileFlags { include, ignore },
     use_tabs,
     line_width,
@@ -3958,9 +4407,11 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   let json = matches.get_flag("json");
+  let manifest = matches.get_flag("manifest");
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    manifest,
   });
 }
 
@@ -4181,9 +4632,19 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     .map(ConfigFlag::Path)
     .unwrap_or(ConfigFlag::Discover);
 
+  let list = matches.get_flag("list").then(|| {
+    if matches.get_flag("json") {
+      TaskListFormat::Json
+    } else if matches.get_flag("completions") {
+      TaskListFormat::Completions
+    } else {
+      TaskListFormat::Pretty
+    }
+  });
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    list,
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -4294,6 +4755,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
 
   let junit_path = matches.remove_one::<String>("junit-path");
+  let output_path = matches.remove_one::<String>("output");
 
   let reporter =
     if let Some(reporter) = matches.remove_one::<String>("reporter") {
@@ -4326,6 +4788,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     watch: watch_arg_parse(matches),
     reporter,
     junit_path,
+    output_path,
   });
 }
 
@@ -4341,12 +4804,14 @@ fn upgrade_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let canary = matches.get_flag("canary");
   let version = matches.remove_one::<String>("version");
   let output = matches.remove_one::<String>("output");
+  let side_by_side = matches.get_flag("side-by-side");
   flags.subcommand = DenoSubcommand::Upgrade(UpgradeFlags {
     dry_run,
     force,
     canary,
     version,
     output,
+    side_by_side,
   });
 }
 
@@ -4508,6 +4973,7 @@ fn runtime_args_parse(
 ) {
   compile_args_parse(flags, matches);
   cached_only_arg_parse(flags, matches);
+  cache_backend_arg_parse(flags, matches);
   if include_perms {
     permission_args_parse(flags, matches);
   }
@@ -4520,6 +4986,7 @@ fn runtime_args_parse(
   enable_testing_features_arg_parse(flags, matches);
   env_file_arg_parse(flags, matches);
   strace_ops_parse(flags, matches);
+  flags.op_metrics = matches.get_flag("op-metrics");
 }
 
 fn inspect_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -4599,6 +5066,18 @@ fn cached_only_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   }
 }
 
+fn cache_backend_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.cache_backend =
+    matches
+      .remove_one::<String>("cache-backend")
+      .map(|backend| match backend.as_str() {
+        "local" => CacheBackend::Local,
+        "shared" => CacheBackend::Shared,
+        "memory" => CacheBackend::Memory,
+        _ => unreachable!(),
+      });
+}
+
 fn ext_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.ext = matches.remove_one::<String>("ext");
 }
@@ -4699,6 +5178,7 @@ fn node_modules_and_vendor_dir_arg_parse(
   matches: &mut ArgMatches,
 ) {
   flags.node_modules_dir = matches.remove_one::<bool>("node-modules-dir");
+  flags.preserve_symlinks = matches.get_flag("preserve-symlinks");
   flags.vendor = matches.remove_one::<bool>("vendor");
 }
 
@@ -4721,6 +5201,7 @@ fn watch_arg_parse(matches: &mut ArgMatches) -> Option<WatchFlags> {
         .remove_many::<String>("watch-exclude")
         .map(|f| f.collect::<Vec<String>>())
         .unwrap_or_default(),
+      debounce: matches.remove_one::<u64>("watch-debounce"),
     })
   } else {
     None
@@ -4739,6 +5220,7 @@ fn watch_arg_parse_with_paths(
         .remove_many::<String>("watch-exclude")
         .map(|f| f.collect::<Vec<String>>())
         .unwrap_or_default(),
+      debounce: matches.remove_one::<u64>("watch-debounce"),
     });
   }
 
@@ -4752,6 +5234,7 @@ fn watch_arg_parse_with_paths(
         .remove_many::<String>("watch-exclude")
         .map(|f| f.collect::<Vec<String>>())
         .unwrap_or_default(),
+      debounce: matches.remove_one::<u64>("watch-debounce"),
     })
 }
 
@@ -4824,6 +5307,7 @@ mod tests {
           canary: false,
           version: None,
           output: None,
+          side_by_side: false,
         }),
         ..Flags::default()
       }
@@ -4842,10 +5326,45 @@ mod tests {
           canary: false,
           version: None,
           output: Some(String::from("example.txt")),
+          side_by_side: false,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn upgrade_side_by_side() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "upgrade",
+      "--side-by-side",
+      "--version",
+      "1.40.5"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          version: Some(String::from("1.40.5")),
+          output: None,
+          side_by_side: true,
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "upgrade",
+      "--side-by-side",
+      "--output",
+      "example.txt"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -4893,6 +5412,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: false,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -4918,6 +5438,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -4943,6 +5464,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -4968,6 +5490,7 @@ mod tests {
             paths: vec![String::from("foo.txt")],
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -4995,6 +5518,7 @@ mod tests {
             paths: vec![String::from("file1"), String::from("file2")],
             no_clear_screen: false,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5024,6 +5548,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5032,6 +5557,49 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_watch_with_debounce() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--watch-debounce=50",
+      "script.ts"
+    ]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags.watch_debounce_interval(),
+      Some(Duration::from_millis(50))
+    );
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![],
+            debounce: Some(50),
+          }),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--watch-debounce=fast",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_watch_with_excluded_paths() {
     let r = flags_from_vec(svec!(
@@ -5053,6 +5621,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: false,
             exclude: vec![String::from("foo")],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5078,6 +5647,7 @@ mod tests {
             paths: vec![String::from("foo")],
             no_clear_screen: false,
             exclude: vec![String::from("bar")],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5104,6 +5674,7 @@ mod tests {
             paths: vec![],
             no_clear_screen: false,
             exclude: vec![String::from("foo"), String::from("bar")],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5130,6 +5701,7 @@ mod tests {
             paths: vec![String::from("foo"), String::from("bar")],
             no_clear_screen: false,
             exclude: vec![String::from("baz"), String::from("qux"),],
+            debounce: None,
           }),
         }),
         code_cache_enabled: true,
@@ -5540,6 +6112,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
             ignore: vec![],
@@ -5563,6 +6136,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: true,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5580,12 +6154,41 @@ mod tests {
       }
     );
 
+    let r =
+      flags_from_vec(svec!["deno", "fmt", "--check", "--diff-format", "patch"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          diff_format: FmtDiffFormat::Patch,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--diff-format", "json"]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec!["deno", "fmt"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5609,6 +6212,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5633,6 +6237,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5647,6 +6252,7 @@ mod tests {
             hmr: false,
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           })
         }),
         ext: Some("ts".to_string()),
@@ -5667,6 +6273,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: true,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec!["foo.ts".to_string()],
             ignore: vec!["bar.js".to_string()],
@@ -5690,6 +6297,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5721,6 +6329,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec!["foo.ts".to_string()],
             ignore: vec![],
@@ -5757,6 +6366,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5787,6 +6397,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Fmt(FmtFlags {
           check: false,
+          diff_format: FmtDiffFormat::Pretty,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -5884,6 +6495,7 @@ mod tests {
             hmr: false,
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           })
         }),
         ..Flags::default()
@@ -6152,6 +6764,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          manifest: false,
           file: Some("script.ts".to_string()),
         }),
         ..Flags::default()
@@ -6164,6 +6777,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          manifest: false,
           file: Some("script.ts".to_string()),
         }),
         reload: true,
@@ -6177,6 +6791,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
+          manifest: false,
           file: Some("script.ts".to_string()),
         }),
         ..Flags::default()
@@ -6189,6 +6804,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          manifest: false,
           file: None
         }),
         ..Flags::default()
@@ -6201,12 +6817,37 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
+          manifest: false,
           file: None
         }),
         ..Flags::default()
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "info", "--manifest", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          manifest: true,
+          file: Some("script.ts".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--manifest"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--manifest",
+      "--json",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec![
       "deno",
       "info",
@@ -6220,6 +6861,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          manifest: false,
           file: None
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
@@ -6453,6 +7095,37 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_op_metrics() {
+    let r = flags_from_vec(svec!["deno", "run", "--op-metrics", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        op_metrics: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_preserve_symlinks() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--preserve-symlinks", "main.js"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "main.js".to_string(),
+        )),
+        preserve_symlinks: true,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn repl_with_flags() {
     #[rustfmt::skip]
@@ -7218,6 +7891,7 @@ mod tests {
             hmr: false,
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -7261,6 +7935,7 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           file: Some("script.ts".to_string()),
           json: false,
+          manifest: false,
         }),
         import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
@@ -7587,6 +8262,22 @@ mod tests {
     }
   }
 
+  #[test]
+  fn completions_complete_tasks() {
+    for shell in ["bash", "fish", "powershell", "zsh"] {
+      let r = flags_from_vec(svec!["deno", "completions", shell]).unwrap();
+      let DenoSubcommand::Completions(CompletionsFlags { buf }) = r.subcommand
+      else {
+        unreachable!()
+      };
+      let script = String::from_utf8(buf.into_vec()).unwrap();
+      assert!(
+        script.contains("deno task --list --completions"),
+        "{shell} script doesn't complete tasks"
+      );
+    }
+  }
+
   #[test]
   fn run_with_args() {
     let r = flags_from_vec(svec![
@@ -7923,6 +8614,35 @@ mod tests {
     );
   }
 
+  #[test]
+  fn cache_backend() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--cache-backend=memory",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        cache_backend: Some(CacheBackend::Memory),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--cache-backend=remote",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn allow_net_allowlist_with_ports() {
     let r = flags_from_vec(svec![
@@ -8201,6 +8921,7 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -8287,6 +9008,7 @@ mod tests {
           coverage_dir: None,
           watch: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8324,6 +9046,7 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8365,6 +9088,7 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8475,6 +9199,26 @@ mod tests {
 
     let r = flags_from_vec(svec!["deno", "test", "--junit-path"]);
     assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "test", "--output", "results.jsonl"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          output_path: Some("results.jsonl".to_string()),
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--output"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -8500,6 +9244,7 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8534,6 +9279,7 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8567,6 +9313,7 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8603,9 +9350,11 @@ mod tests {
             hmr: false,
             no_clear_screen: true,
             exclude: vec![],
+            debounce: None,
           }),
           reporter: Default::default(),
           junit_path: None,
+          output_path: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8673,6 +9422,7 @@ mod tests {
           canary: false,
           version: None,
           output: None,
+          side_by_side: false,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -8716,6 +9466,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          manifest: false,
           file: Some("https://example.com".to_string()),
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
@@ -9033,7 +9784,8 @@ mod tests {
           args: vec![],
           target: None,
           no_terminal: false,
-          include: vec![]
+          include: vec![],
+          permission_override: PermissionOverridePolicy::Prompt,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -9055,7 +9807,8 @@ mod tests {
           args: svec!["foo", "bar", "-p", "8080"],
           target: None,
           no_terminal: true,
-          include: vec![]
+          include: vec![],
+          permission_override: PermissionOverridePolicy::Prompt,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -9081,6 +9834,77 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compile_with_permission_override() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--allow-read",
+      "--permission-override=allow",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          permission_override: PermissionOverridePolicy::Allow,
+        }),
+        permissions: PermissionFlags {
+          allow_read: Some(vec![]),
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--permission-override=sometimes",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn permission_flags_from_args_and_extend() {
+    let mut flags = PermissionFlags {
+      allow_read: Some(svec!["./data"]),
+      allow_net: Some(vec![]),
+      allow_env: Some(svec!["HOME"]),
+      ..Default::default()
+    };
+    let extra = PermissionFlags::from_args(svec![
+      "--allow-read=/tmp",
+      "--allow-net=deno.land",
+      "--allow-env",
+      "--allow-hrtime",
+      "--deny-write=/etc",
+    ])
+    .unwrap();
+    flags.extend(extra);
+    assert_eq!(
+      flags,
+      PermissionFlags {
+        allow_read: Some(svec!["./data", "/tmp"]),
+        allow_net: Some(vec![]),
+        allow_env: Some(vec![]),
+        allow_hrtime: true,
+        deny_write: Some(svec!["/etc"]),
+        ..Default::default()
+      }
+    );
+
+    assert!(PermissionFlags::from_args(svec!["--allow-foo"]).is_err());
+  }
+
   #[test]
   fn coverage() {
     let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
@@ -9122,12 +9946,47 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(String::from("foo.lcov")),
+          ..CoverageFlags::default()
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn coverage_with_merge_and_npm_packages() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--merge=merged",
+      "--npm-package=chalk",
+      "--npm-package=@std/path",
+      "shard1",
+      "shard2"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["shard1".to_string(), "shard2".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          npm_packages: svec!["chalk", "@std/path"],
+          merge: Some("merged".to_string()),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "coverage", "--merge=merged", "--lcov"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn coverage_with_default_files() {
     let r = flags_from_vec(svec!["deno", "coverage",]);
@@ -9258,6 +10117,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -9271,6 +10131,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         ..Flags::default()
       }
@@ -9283,6 +10144,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          list: None,
         }),
         ..Flags::default()
       }
@@ -9307,6 +10169,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -9323,6 +10186,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -9340,6 +10204,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -9356,6 +10221,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -9372,6 +10238,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -9390,6 +10257,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          list: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -9410,12 +10278,39 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          list: None,
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn task_subcommand_list() {
+    let r = flags_from_vec(svec!["deno", "task", "--list"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Task(TaskFlags {
+        cwd: None,
+        task: None,
+        list: Some(TaskListFormat::Pretty),
+      }),
+    );
+
+    let r = flags_from_vec(svec!["deno", "task", "--list", "--json"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Task(TaskFlags {
+        cwd: None,
+        task: None,
+        list: Some(TaskListFormat::Json),
+      }),
+    );
+
+    let r = flags_from_vec(svec!["deno", "task", "--json"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn task_subcommand_config() {
     let r = flags_from_vec(svec!["deno", "task", "--config", "deno.jsonc"]);
@@ -9425,6 +10320,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          list: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9441,6 +10337,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          list: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9469,6 +10366,8 @@ mod tests {
       "--no-run",
       "--filter",
       "- foo",
+      "--output",
+      "bench.jsonl",
       "--location",
       "https:foo",
       "--allow-net",
@@ -9485,6 +10384,7 @@ mod tests {
           filter: Some("- foo".to_string()),
           json: true,
           no_run: true,
+          output_path: Some("bench.jsonl".to_string()),
           files: FileFlags {
             include: vec!["dir1/".to_string(), "dir2/".to_string()],
             ignore: vec![],
@@ -9520,6 +10420,7 @@ mod tests {
           filter: None,
           json: false,
           no_run: false,
+          output_path: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
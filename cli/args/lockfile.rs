@@ -3,8 +3,10 @@
 use std::path::PathBuf;
 
 use deno_core::error::AnyError;
+use deno_core::serde_json;
 
 use crate::args::ConfigFile;
+use crate::util::checksum;
 use crate::Flags;
 
 use super::DenoSubcommand;
@@ -45,3 +47,88 @@ pub fn discover(
   let lockfile = Lockfile::new(filename, flags.lock_write)?;
   Ok(Some(lockfile))
 }
+
+/// Like `Lockfile::check_or_insert_remote()`, for sources which may not be
+/// valid UTF-8, such as the modules imported `with { type: "bytes" }`. Both
+/// hash the same bytes, so the checksums of UTF-8 sources don't change.
+pub fn check_or_insert_remote_bytes(
+  lockfile: &mut Lockfile,
+  specifier: &str,
+  source: &[u8],
+) -> bool {
+  if let Ok(source) = std::str::from_utf8(source) {
+    return lockfile.check_or_insert_remote(specifier, source);
+  }
+  if !(specifier.starts_with("http:") || specifier.starts_with("https:")) {
+    return true;
+  }
+  // deno_lockfile only hashes strings and keeps the checksums of the remote
+  // modules private, so they are updated through its serialized form
+  let checksum = checksum::gen(&[source]);
+  let mut content = serde_json::to_value(&lockfile.content).unwrap();
+  let remote = content["remote"].as_object_mut().unwrap();
+  if !lockfile.overwrite {
+    if let Some(expected) = remote.get(specifier) {
+      return expected.as_str() == Some(checksum.as_str());
+    }
+  }
+  remote.insert(specifier.to_string(), checksum.into());
+  lockfile.content = serde_json::from_value(content).unwrap();
+  lockfile.has_content_changed = true;
+  true
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn check_or_insert_remote_bytes_round_trip() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let filename = temp_dir.path().join("deno.lock");
+    let specifier = "https://deno.land/x/image.png";
+    let source = [0x89, b'P', b'N', b'G', 0xff];
+
+    let mut lockfile = Lockfile::new(filename.clone(), false).unwrap();
+    assert!(check_or_insert_remote_bytes(
+      &mut lockfile,
+      specifier,
+      &source
+    ));
+    assert!(check_or_insert_remote_bytes(
+      &mut lockfile,
+      "https://deno.land/x/mod.ts",
+      b"export {};"
+    ));
+    lockfile.write().unwrap();
+
+    let mut lockfile = Lockfile::new(filename.clone(), false).unwrap();
+    assert!(check_or_insert_remote_bytes(
+      &mut lockfile,
+      specifier,
+      &source
+    ));
+    assert!(!check_or_insert_remote_bytes(
+      &mut lockfile,
+      specifier,
+      &source[1..]
+    ));
+    // UTF-8 sources keep the checksums of `check_or_insert_remote()`
+    assert!(lockfile
+      .check_or_insert_remote("https://deno.land/x/mod.ts", "export {};"));
+    assert!(!lockfile.has_content_changed);
+    let text = std::fs::read_to_string(&filename).unwrap();
+    assert!(text.contains(&format!(
+      "\"{specifier}\": \"{}\"",
+      checksum::gen(&[source])
+    )));
+
+    // lock-write replaces checksums
+    let mut lockfile = Lockfile::new(filename, true).unwrap();
+    assert!(check_or_insert_remote_bytes(
+      &mut lockfile,
+      specifier,
+      &source[1..]
+    ));
+  }
+}
@@ -6,6 +6,7 @@ mod flags_net;
 mod import_map;
 mod lockfile;
 pub mod package_json;
+mod version_pin;
 
 pub use self::import_map::resolve_import_map;
 use self::package_json::PackageJsonDeps;
@@ -32,9 +33,12 @@ pub use deno_config::TsConfigType;
 pub use deno_config::TsTypeLib;
 pub use deno_config::WorkspaceConfig;
 pub use flags::*;
+pub use lockfile::check_or_insert_remote_bytes;
 pub use lockfile::Lockfile;
 pub use lockfile::LockfileError;
 pub use package_json::PackageJsonDepsProvider;
+pub use version_pin::run_pinned_version;
+pub use version_pin::side_by_side_exe_path;
 
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::bail;
@@ -113,6 +117,9 @@ pub static DENO_DISABLE_PEDANTIC_NODE_WARNINGS: Lazy<bool> = Lazy::new(|| {
 pub static DENO_FUTURE: Lazy<bool> =
   Lazy::new(|| std::env::var("DENO_FUTURE").ok().is_some());
 
+pub static DENO_CONSOLE_TIMESTAMPS: Lazy<bool> =
+  Lazy::new(|| std::env::var("DENO_CONSOLE_TIMESTAMPS").ok().is_some());
+
 pub fn jsr_url() -> &'static Url {
   static JSR_URL: Lazy<Url> = Lazy::new(|| {
     let env_var_name = "JSR_URL";
@@ -248,6 +255,7 @@ pub struct BenchOptions {
   pub filter: Option<String>,
   pub json: bool,
   pub no_run: bool,
+  pub output_path: Option<String>,
 }
 
 impl BenchOptions {
@@ -266,6 +274,7 @@ impl BenchOptions {
       filter: bench_flags.filter,
       json: bench_flags.json,
       no_run: bench_flags.no_run,
+      output_path: bench_flags.output_path,
     })
   }
 }
@@ -273,6 +282,7 @@ impl BenchOptions {
 #[derive(Clone, Debug)]
 pub struct FmtOptions {
   pub check: bool,
+  pub diff_format: FmtDiffFormat,
   pub options: FmtOptionsConfig,
   pub files: FilePatterns,
 }
@@ -287,6 +297,7 @@ impl FmtOptions {
   pub fn new_with_base(base: PathBuf) -> Self {
     Self {
       check: false,
+      diff_format: FmtDiffFormat::default(),
       options: FmtOptionsConfig::default(),
       files: FilePatterns::new_with_base(base),
     }
@@ -302,6 +313,10 @@ impl FmtOptions {
 
     Ok(Self {
       check: maybe_fmt_flags.as_ref().map(|f| f.check).unwrap_or(false),
+      diff_format: maybe_fmt_flags
+        .as_ref()
+        .map(|f| f.diff_format)
+        .unwrap_or_default(),
       options: resolve_fmt_options(
         maybe_fmt_flags.as_ref(),
         maybe_config_options,
@@ -369,6 +384,7 @@ pub struct TestOptions {
   pub trace_leaks: bool,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub output_path: Option<String>,
 }
 
 impl TestOptions {
@@ -397,6 +413,7 @@ impl TestOptions {
       trace_leaks: test_flags.trace_leaks,
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path,
+      output_path: test_flags.output_path,
     })
   }
 }
@@ -901,6 +918,10 @@ impl CliOptions {
     }
   }
 
+  pub fn cache_backend(&self) -> Option<CacheBackend> {
+    self.flags.cache_backend
+  }
+
   pub fn npm_system_info(&self) -> NpmSystemInfo {
     match self.sub_command() {
       DenoSubcommand::Compile(CompileFlags {
@@ -1055,6 +1076,10 @@ impl CliOptions {
     *DENO_FUTURE
   }
 
+  pub fn console_timestamps(&self) -> bool {
+    *DENO_CONSOLE_TIMESTAMPS
+  }
+
   pub fn resolve_main_module(&self) -> Result<ModuleSpecifier, AnyError> {
     match &self.flags.subcommand {
       DenoSubcommand::Bundle(bundle_flags) => {
@@ -1571,6 +1596,14 @@ impl CliOptions {
     &self.flags.strace_ops
   }
 
+  pub fn op_metrics(&self) -> bool {
+    self.flags.op_metrics
+  }
+
+  pub fn preserve_symlinks(&self) -> bool {
+    self.flags.preserve_symlinks
+  }
+
   pub fn take_binary_npm_command_name(&self) -> Option<String> {
     match self.sub_command() {
       DenoSubcommand::Run(flags) => {
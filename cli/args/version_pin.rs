@@ -0,0 +1,205 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_semver::Version;
+use deno_semver::VersionReq;
+
+use crate::args::has_flag_env_var;
+use crate::args::ConfigFile;
+use crate::cache::DenoDir;
+use crate::Flags;
+
+use super::DenoSubcommand;
+
+const NO_VERSION_PIN_ENV_VAR: &str = "DENO_NO_VERSION_PIN";
+
+/// The path of a version installed with `deno upgrade --side-by-side`.
+pub fn side_by_side_exe_path(deno_dir: &DenoDir, version: &str) -> PathBuf {
+  let exe_name = if cfg!(windows) { "deno.exe" } else { "deno" };
+  deno_dir.versions_folder_path().join(version).join(exe_name)
+}
+
+/// The versions installed with `deno upgrade --side-by-side`, from the most
+/// recent one.
+fn side_by_side_versions(deno_dir: &DenoDir) -> Vec<(Version, PathBuf)> {
+  let Ok(entries) = fs::read_dir(deno_dir.versions_folder_path()) else {
+    return Vec::new();
+  };
+  let mut versions = entries
+    .filter_map(|entry| {
+      let entry = entry.ok()?;
+      let version =
+        Version::parse_standard(entry.file_name().to_str()?).ok()?;
+      let exe_path = side_by_side_exe_path(deno_dir, &version.to_string());
+      exe_path.is_file().then_some((version, exe_path))
+    })
+    .collect::<Vec<_>>();
+  versions.sort_by(|a, b| b.0.cmp(&a.0));
+  versions
+}
+
+/// Reads the `"deno"` field of the config file, the range of the Deno
+/// versions the project is meant to run with (ex. `">=1.40 <2"`).
+///
+/// `deno_config` doesn't keep unknown properties, so this parses the
+/// config file again.
+fn pinned_version_req(
+  config_file: &ConfigFile,
+) -> Result<Option<VersionReq>, AnyError> {
+  let Ok(config_path) = config_file.specifier.to_file_path() else {
+    return Ok(None);
+  };
+  let text = fs::read_to_string(&config_path)
+    .with_context(|| format!("Failed reading '{}'.", config_path.display()))?;
+  let value = jsonc_parser::parse_to_serde_value(&text, &Default::default())?
+    .and_then(|mut value| value.get_mut("deno").map(|v| v.take()));
+  let Some(value) = value else {
+    return Ok(None);
+  };
+  let version_req = value
+    .as_str()
+    .and_then(|text| VersionReq::parse_from_npm(text).ok())
+    .filter(|version_req| version_req.tag().is_none());
+  match version_req {
+    Some(version_req) => Ok(Some(version_req)),
+    None => bail!(
+      "Invalid \"deno\" field in '{}', expected a version range (ex. \">=1.40 <2\") but got {}.",
+      config_path.display(),
+      value
+    ),
+  }
+}
+
+/// Runs the command with a version installed side by side when the config
+/// file pins one that doesn't match the current version. Returns the exit
+/// code of that version, or `None` to go on with the current one.
+///
+/// This is only called once from `main()`, so that neither the language
+/// server nor the restarts of a watcher switch versions.
+pub fn run_pinned_version(flags: &Flags) -> Result<Option<i32>, AnyError> {
+  if matches!(
+    flags.subcommand,
+    DenoSubcommand::Upgrade(_) | DenoSubcommand::Lsp
+  ) || flags.config_flag == deno_config::ConfigFlag::Disabled
+    || has_flag_env_var(NO_VERSION_PIN_ENV_VAR)
+  {
+    return Ok(None);
+  }
+  let initial_cwd =
+    env::current_dir().with_context(|| "Failed getting cwd.")?;
+  let Some(config_file) = ConfigFile::discover(
+    &flags.config_flag,
+    flags.config_path_args(&initial_cwd),
+    &initial_cwd,
+    None,
+    &Default::default(),
+  )?
+  else {
+    return Ok(None);
+  };
+  let Some(version_req) = pinned_version_req(&config_file)? else {
+    return Ok(None);
+  };
+  // canary builds are matched with the version they are built from
+  let current = Version::parse_standard(env!("CARGO_PKG_VERSION")).unwrap();
+  if version_req.matches(&current) {
+    return Ok(None);
+  }
+
+  let deno_dir = DenoDir::new(flags.cache_path.clone())?;
+  let Some((version, exe_path)) = side_by_side_versions(&deno_dir)
+    .into_iter()
+    .find(|(version, _)| version_req.matches(version))
+  else {
+    bail!(
+      concat!(
+        "The config file at '{}' requires Deno {}, but this is Deno {}.\n",
+        "Install a matching version with `deno upgrade --side-by-side --version <version>`, ",
+        "or set {}=1 to use this one."
+      ),
+      config_file.specifier,
+      version_req.version_text(),
+      current,
+      NO_VERSION_PIN_ENV_VAR,
+    );
+  };
+  log::debug!(
+    "Running Deno {} from {} as pinned by {}",
+    version,
+    exe_path.display(),
+    config_file.specifier
+  );
+
+  let mut command = Command::new(&exe_path);
+  // the pinned version runs the command as is, even if it supports pinning
+  command
+    .args(env::args_os().skip(1))
+    .env(NO_VERSION_PIN_ENV_VAR, "1");
+  #[cfg(unix)]
+  let status: std::io::Result<std::process::ExitStatus> =
+    Err(std::os::unix::process::CommandExt::exec(&mut command));
+  #[cfg(not(unix))]
+  let status = command.status();
+  let status = status.with_context(|| {
+    format!(
+      "Failed running Deno {} at '{}'.",
+      version,
+      exe_path.display()
+    )
+  })?;
+  Ok(Some(status.code().unwrap_or(1)))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_side_by_side_versions() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let deno_dir = DenoDir::new(Some(temp_dir.path().to_path_buf())).unwrap();
+    for version in ["1.40.5", "1.43.0", "not-a-version"] {
+      let exe_path = side_by_side_exe_path(&deno_dir, version);
+      fs::create_dir_all(exe_path.parent().unwrap()).unwrap();
+      fs::write(exe_path, "").unwrap();
+    }
+    // a version without an executable isn't installed
+    fs::create_dir_all(deno_dir.versions_folder_path().join("1.44.0")).unwrap();
+
+    let versions = side_by_side_versions(&deno_dir)
+      .into_iter()
+      .map(|(version, _)| version.to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(versions, vec!["1.43.0", "1.40.5"]);
+  }
+
+  #[test]
+  fn test_pinned_version_req() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deno.json");
+    let pinned_version_req = |text: &str| {
+      fs::write(&config_path, text).unwrap();
+      let config_file = ConfigFile::read(&config_path, &Default::default())?;
+      pinned_version_req(&config_file)
+    };
+
+    let version_req = pinned_version_req(r#"{ "deno": ">=1.40 <2" }"#)
+      .unwrap()
+      .unwrap();
+    assert!(version_req.matches(&Version::parse_standard("1.43.5").unwrap()));
+    assert!(!version_req.matches(&Version::parse_standard("1.39.0").unwrap()));
+    assert!(!version_req.matches(&Version::parse_standard("2.0.0").unwrap()));
+
+    assert!(pinned_version_req("{}").unwrap().is_none());
+    let err = pinned_version_req(r#"{ "deno": 1 }"#).unwrap_err();
+    assert!(err.to_string().contains("Invalid \"deno\" field"));
+    assert!(pinned_version_req(r#"{ "deno": "latest" }"#).is_err());
+  }
+}
@@ -0,0 +1,54 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Measures how long timers are delayed while many CPU-bound `node:crypto`
+// operations run in the background. The async crypto ops shouldn't starve
+// the event loop, however many of them are pending.
+let [total, count] = typeof Deno !== "undefined"
+  ? Deno.args
+  : [process.argv[2], process.argv[3]];
+
+total = total ? parseInt(total, 0) : 10;
+count = count ? parseInt(count, 10) : 256;
+
+function measureTimerDelay(signal) {
+  const delays = [];
+  return new Promise((resolve) => {
+    let last = performance.now();
+    const interval = setInterval(() => {
+      const now = performance.now();
+      delays.push(now - last - 1);
+      last = now;
+      if (signal.aborted) {
+        clearInterval(interval);
+        resolve(delays);
+      }
+    }, 1);
+  });
+}
+
+async function bench(crypto) {
+  const controller = new AbortController();
+  const delays = measureTimerDelay(controller.signal);
+  const start = performance.now();
+  await Promise.all(
+    Array.from({ length: count }, () =>
+      new Promise((resolve, reject) => {
+        crypto.pbkdf2("password", "salt", 10000, 64, "sha512", (err) => {
+          if (err) reject(err);
+          else resolve();
+        });
+      })),
+  );
+  const elapsed = performance.now() - start;
+  controller.abort();
+
+  const sorted = (await delays).sort((a, b) => a - b);
+  const p99 = sorted[Math.floor(sorted.length * 0.99)] ?? 0;
+  console.log(
+    `time ${Math.round(elapsed)} ms timer delay p99 ${p99.toFixed(2)} ms ` +
+      `max ${(sorted.at(-1) ?? 0).toFixed(2)} ms`,
+  );
+  if (--total) await bench(crypto);
+}
+
+import("node:crypto").then(bench);
@@ -0,0 +1,298 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_cache_dir::DenoCacheEnv;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::RealDenoCacheEnv;
+use crate::args::CacheBackend;
+
+/// Environment variable pointing to a read-only DENO_DIR (ex. a mounted CI
+/// cache) that the global http cache falls back to when an item is not
+/// found in the writable DENO_DIR.
+pub const DENO_SHARED_DIR_ENV_VAR: &str = "DENO_SHARED_DIR";
+
+/// The storage backend of the global http cache.
+#[derive(Debug, Clone)]
+pub enum CliDenoCacheEnv {
+  /// Stores everything in the local DENO_DIR.
+  Real(RealDenoCacheEnv),
+  /// Writes to the local DENO_DIR, but falls back to reading from a
+  /// read-only shared cache.
+  Layered(LayeredDenoCacheEnv),
+  /// Keeps everything in memory, which is useful for tests.
+  Memory(MemoryDenoCacheEnv),
+}
+
+impl Default for CliDenoCacheEnv {
+  fn default() -> Self {
+    Self::Real(RealDenoCacheEnv)
+  }
+}
+
+impl CliDenoCacheEnv {
+  /// Resolves the backend for a global http cache stored in `deps_folder`,
+  /// layering it over the `deps` folder of `DENO_SHARED_DIR` when set.
+  pub fn from_env(deps_folder: &Path) -> Self {
+    match std::env::var_os(DENO_SHARED_DIR_ENV_VAR) {
+      Some(shared_dir) if !shared_dir.is_empty() => {
+        Self::Layered(LayeredDenoCacheEnv::new(
+          deps_folder.to_path_buf(),
+          PathBuf::from(shared_dir).join("deps"),
+        ))
+      }
+      _ => Self::default(),
+    }
+  }
+
+  /// Resolves the `backend` selected with `--cache-backend` for a global http
+  /// cache stored in `deps_folder`, or the one of [`Self::from_env`] when no
+  /// backend is selected.
+  pub fn from_backend(
+    backend: Option<CacheBackend>,
+    deps_folder: &Path,
+  ) -> Result<Self, AnyError> {
+    match backend {
+      None => Ok(Self::from_env(deps_folder)),
+      Some(CacheBackend::Local) => Ok(Self::default()),
+      Some(CacheBackend::Shared) => match Self::from_env(deps_folder) {
+        env @ Self::Layered(_) => Ok(env),
+        _ => bail!(
+          "--cache-backend=shared requires the {} environment variable to be set",
+          DENO_SHARED_DIR_ENV_VAR
+        ),
+      },
+      Some(CacheBackend::Memory) => {
+        Ok(Self::Memory(MemoryDenoCacheEnv::default()))
+      }
+    }
+  }
+}
+
+impl DenoCacheEnv for CliDenoCacheEnv {
+  fn read_file_bytes(&self, path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    match self {
+      Self::Real(env) => env.read_file_bytes(path),
+      Self::Layered(env) => env.read_file_bytes(path),
+      Self::Memory(env) => env.read_file_bytes(path),
+    }
+  }
+
+  fn atomic_write_file(
+    &self,
+    path: &Path,
+    bytes: &[u8],
+  ) -> std::io::Result<()> {
+    match self {
+      Self::Real(env) => env.atomic_write_file(path, bytes),
+      Self::Layered(env) => env.atomic_write_file(path, bytes),
+      Self::Memory(env) => env.atomic_write_file(path, bytes),
+    }
+  }
+
+  fn modified(&self, path: &Path) -> std::io::Result<Option<SystemTime>> {
+    match self {
+      Self::Real(env) => env.modified(path),
+      Self::Layered(env) => env.modified(path),
+      Self::Memory(env) => env.modified(path),
+    }
+  }
+
+  fn is_file(&self, path: &Path) -> bool {
+    match self {
+      Self::Real(env) => env.is_file(path),
+      Self::Layered(env) => env.is_file(path),
+      Self::Memory(env) => env.is_file(path),
+    }
+  }
+
+  fn time_now(&self) -> SystemTime {
+    match self {
+      Self::Real(env) => env.time_now(),
+      Self::Layered(env) => env.time_now(),
+      Self::Memory(env) => env.time_now(),
+    }
+  }
+}
+
+/// A cache environment that writes to a local directory and falls back to
+/// reading from the same relative path in a read-only shared directory.
+#[derive(Debug, Clone)]
+pub struct LayeredDenoCacheEnv {
+  writable_root: PathBuf,
+  shared_root: PathBuf,
+  real: RealDenoCacheEnv,
+}
+
+impl LayeredDenoCacheEnv {
+  pub fn new(writable_root: PathBuf, shared_root: PathBuf) -> Self {
+    Self {
+      writable_root,
+      shared_root,
+      real: RealDenoCacheEnv,
+    }
+  }
+
+  fn shared_path(&self, path: &Path) -> Option<PathBuf> {
+    let relative_path = path.strip_prefix(&self.writable_root).ok()?;
+    Some(self.shared_root.join(relative_path))
+  }
+}
+
+impl DenoCacheEnv for LayeredDenoCacheEnv {
+  fn read_file_bytes(&self, path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    if let Some(bytes) = self.real.read_file_bytes(path)? {
+      return Ok(Some(bytes));
+    }
+    match self.shared_path(path) {
+      Some(shared_path) => self.real.read_file_bytes(&shared_path),
+      None => Ok(None),
+    }
+  }
+
+  fn atomic_write_file(
+    &self,
+    path: &Path,
+    bytes: &[u8],
+  ) -> std::io::Result<()> {
+    // the shared cache is read-only, so always write to the local cache
+    self.real.atomic_write_file(path, bytes)
+  }
+
+  fn modified(&self, path: &Path) -> std::io::Result<Option<SystemTime>> {
+    if let Some(modified) = self.real.modified(path)? {
+      return Ok(Some(modified));
+    }
+    match self.shared_path(path) {
+      Some(shared_path) => self.real.modified(&shared_path),
+      None => Ok(None),
+    }
+  }
+
+  fn is_file(&self, path: &Path) -> bool {
+    self.real.is_file(path)
+      || self
+        .shared_path(path)
+        .map(|shared_path| self.real.is_file(&shared_path))
+        .unwrap_or(false)
+  }
+
+  fn time_now(&self) -> SystemTime {
+    self.real.time_now()
+  }
+}
+
+/// A cache environment that stores everything in memory.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryDenoCacheEnv {
+  files: Arc<Mutex<HashMap<PathBuf, MemoryFile>>>,
+}
+
+#[derive(Debug)]
+struct MemoryFile {
+  bytes: Vec<u8>,
+  modified: SystemTime,
+}
+
+impl DenoCacheEnv for MemoryDenoCacheEnv {
+  fn read_file_bytes(&self, path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    Ok(self.files.lock().get(path).map(|file| file.bytes.clone()))
+  }
+
+  fn atomic_write_file(
+    &self,
+    path: &Path,
+    bytes: &[u8],
+  ) -> std::io::Result<()> {
+    let file = MemoryFile {
+      bytes: bytes.to_vec(),
+      modified: self.time_now(),
+    };
+    self.files.lock().insert(path.to_path_buf(), file);
+    Ok(())
+  }
+
+  fn modified(&self, path: &Path) -> std::io::Result<Option<SystemTime>> {
+    Ok(self.files.lock().get(path).map(|file| file.modified))
+  }
+
+  fn is_file(&self, path: &Path) -> bool {
+    self.files.lock().contains_key(path)
+  }
+
+  fn time_now(&self) -> SystemTime {
+    SystemTime::now()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use test_util::TempDir;
+
+  #[test]
+  fn layered_env_reads_shared_and_writes_local() {
+    let temp_dir = TempDir::new();
+    let local = temp_dir.path().join("local");
+    let shared = temp_dir.path().join("shared");
+    shared.join("https/deno.land").create_dir_all();
+    shared.join("https/deno.land/a").write("shared a");
+    shared.join("https/deno.land/b").write("shared b");
+    local.join("https/deno.land").create_dir_all();
+    local.join("https/deno.land/b").write("local b");
+
+    let env =
+      LayeredDenoCacheEnv::new(local.to_path_buf(), shared.to_path_buf());
+    let a = local.join("https/deno.land/a");
+    let b = local.join("https/deno.land/b");
+    let c = local.join("https/deno.land/c");
+    assert!(env.is_file(a.as_path()));
+    assert!(!env.is_file(c.as_path()));
+    assert_eq!(
+      env.read_file_bytes(a.as_path()).unwrap(),
+      Some(b"shared a".to_vec())
+    );
+    assert_eq!(
+      env.read_file_bytes(b.as_path()).unwrap(),
+      Some(b"local b".to_vec())
+    );
+    assert_eq!(env.read_file_bytes(c.as_path()).unwrap(), None);
+    assert!(env.modified(a.as_path()).unwrap().is_some());
+
+    env.atomic_write_file(c.as_path(), b"local c").unwrap();
+    assert_eq!(c.read_to_string(), "local c");
+    assert!(!shared.join("https/deno.land/c").exists());
+  }
+
+  #[test]
+  fn from_backend() {
+    let deps_folder = Path::new("/deps");
+    assert!(matches!(
+      CliDenoCacheEnv::from_backend(Some(CacheBackend::Local), deps_folder),
+      Ok(CliDenoCacheEnv::Real(_))
+    ));
+    assert!(matches!(
+      CliDenoCacheEnv::from_backend(Some(CacheBackend::Memory), deps_folder),
+      Ok(CliDenoCacheEnv::Memory(_))
+    ));
+  }
+
+  #[test]
+  fn memory_env() {
+    let env = CliDenoCacheEnv::Memory(MemoryDenoCacheEnv::default());
+    let path = Path::new("/deps/https/deno.land/a");
+    assert!(!env.is_file(path));
+    assert_eq!(env.read_file_bytes(path).unwrap(), None);
+    assert_eq!(env.modified(path).unwrap(), None);
+    env.atomic_write_file(path, b"a").unwrap();
+    assert!(env.is_file(path));
+    assert_eq!(env.read_file_bytes(path).unwrap(), Some(b"a".to_vec()));
+    assert!(env.modified(path).unwrap().is_some());
+  }
+}
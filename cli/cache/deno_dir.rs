@@ -136,6 +136,11 @@ impl DenoDir {
     self.root.join("latest.txt")
   }
 
+  /// Folder of the Deno versions installed with `deno upgrade --side-by-side`.
+  pub fn versions_folder_path(&self) -> PathBuf {
+    self.root.join("versions")
+  }
+
   /// Folder used for the npm cache.
   pub fn npm_folder_path(&self) -> PathBuf {
     self.root.join("npm")
@@ -25,6 +25,7 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 mod cache_db;
+mod cache_env;
 mod caches;
 mod check;
 mod code_cache;
@@ -38,6 +39,7 @@ mod module_info;
 mod node;
 mod parsed_source;
 
+pub use cache_env::CliDenoCacheEnv;
 pub use caches::Caches;
 pub use check::TypeCheckCache;
 pub use code_cache::CodeCache;
@@ -95,10 +97,9 @@ impl deno_cache_dir::DenoCacheEnv for RealDenoCacheEnv {
   }
 }
 
-pub type GlobalHttpCache = deno_cache_dir::GlobalHttpCache<RealDenoCacheEnv>;
-pub type LocalHttpCache = deno_cache_dir::LocalHttpCache<RealDenoCacheEnv>;
-pub type LocalLspHttpCache =
-  deno_cache_dir::LocalLspHttpCache<RealDenoCacheEnv>;
+pub type GlobalHttpCache = deno_cache_dir::GlobalHttpCache<CliDenoCacheEnv>;
+pub type LocalHttpCache = deno_cache_dir::LocalHttpCache<CliDenoCacheEnv>;
+pub type LocalLspHttpCache = deno_cache_dir::LocalLspHttpCache<CliDenoCacheEnv>;
 pub use deno_cache_dir::HttpCache;
 
 use self::module_info::ModuleInfoCacheSourceHash;
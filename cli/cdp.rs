@@ -436,6 +436,20 @@ pub type UnserializableValue = String;
 /// <https://chromedevtools.github.io/devtools-protocol/tot/Runtime/#type-UniqueDebuggerId>
 pub type UniqueDebuggerId = String;
 
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-getScriptSource>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetScriptSourceArgs {
+  pub script_id: ScriptId,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-getScriptSource>
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetScriptSourceResponse {
+  pub script_source: String,
+}
+
 /// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-setScriptSource>
 #[derive(Debug, Deserialize)]
 pub struct SetScriptSourceResponse {
@@ -459,6 +473,88 @@ pub struct ScriptParsed {
   pub url: String,
 }
 
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#type-BreakpointId>
+pub type BreakpointId = String;
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#type-CallFrameId>
+pub type CallFrameId = String;
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#type-Location>
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+  pub script_id: ScriptId,
+  pub line_number: u64,
+  pub column_number: Option<u64>,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#type-CallFrame>
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebuggerCallFrame {
+  pub call_frame_id: CallFrameId,
+  pub function_name: String,
+  pub location: Location,
+  pub url: String,
+  pub scope_chain: Vec<Scope>,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#type-Scope>
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scope {
+  #[serde(rename = "type")]
+  pub kind: String,
+  pub object: RemoteObject,
+  pub name: Option<String>,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#event-paused>
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paused {
+  pub call_frames: Vec<DebuggerCallFrame>,
+  pub reason: String,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-setBreakpointByUrl>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBreakpointByUrlArgs {
+  pub line_number: u64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub column_number: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub condition: Option<String>,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-setBreakpointByUrl>
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBreakpointByUrlResponse {
+  pub breakpoint_id: BreakpointId,
+  pub locations: Vec<Location>,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-removeBreakpoint>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveBreakpointArgs {
+  pub breakpoint_id: BreakpointId,
+}
+
+/// <https://chromedevtools.github.io/devtools-protocol/tot/Debugger/#method-evaluateOnCallFrame>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluateOnCallFrameArgs {
+  pub call_frame_id: CallFrameId,
+  pub expression: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub object_group: Option<String>,
+}
+
 /// <https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#type-CoverageRange>
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +9,7 @@ use crate::args::PackageJsonDepsProvider;
 use crate::args::StorageKeyResolver;
 use crate::args::TsConfigType;
 use crate::cache::Caches;
+use crate::cache::CliDenoCacheEnv;
 use crate::cache::CodeCache;
 use crate::cache::DenoDir;
 use crate::cache::DenoDirProvider;
@@ -258,10 +259,12 @@ impl CliFactory {
 
   pub fn global_http_cache(&self) -> Result<&Arc<GlobalHttpCache>, AnyError> {
     self.services.global_http_cache.get_or_try_init(|| {
-      Ok(Arc::new(GlobalHttpCache::new(
-        self.deno_dir()?.deps_folder_path(),
-        crate::cache::RealDenoCacheEnv,
-      )))
+      let deps_folder = self.deno_dir()?.deps_folder_path();
+      let cache_env = CliDenoCacheEnv::from_backend(
+        self.options.cache_backend(),
+        &deps_folder,
+      )?;
+      Ok(Arc::new(GlobalHttpCache::new(deps_folder, cache_env)))
     })
   }
 
@@ -418,6 +421,7 @@ impl CliFactory {
               None => canonicalize_path_maybe_not_exists(self.options.initial_cwd())?
                 .join("node_modules"),
             },
+            preserve_symlinks: self.options.preserve_symlinks(),
           })
         } else {
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
@@ -707,6 +711,7 @@ impl CliFactory {
       .get_or_try_init_async(async {
         Ok(Arc::new(ModuleLoadPreparer::new(
           self.options.clone(),
+          self.file_fetcher()?.clone(),
           self.maybe_lockfile().clone(),
           self.module_graph_builder().await?.clone(),
           self.text_only_progress_bar().clone(),
@@ -797,6 +802,7 @@ impl CliFactory {
           None
         },
         self.emitter()?.clone(),
+        self.file_fetcher()?.clone(),
         self.main_module_graph_container().await?.clone(),
         self.module_info_cache()?.clone(),
         self.module_load_preparer().await?.clone(),
@@ -873,10 +879,13 @@ impl CliFactory {
       enable_op_summary_metrics: self.options.enable_op_summary_metrics(),
       enable_testing_features: self.options.enable_testing_features(),
       has_node_modules_dir: self.options.has_node_modules_dir(),
+      preserve_symlinks: self.options.preserve_symlinks(),
+      console_timestamps: self.options.console_timestamps(),
       hmr: self.options.has_hmr(),
       inspect_brk: self.options.inspect_brk().is_some(),
       inspect_wait: self.options.inspect_wait().is_some(),
       strace_ops: self.options.strace_ops().clone(),
+      op_metrics: self.options.op_metrics(),
       is_inspecting: self.options.is_inspecting(),
       is_npm_main: self.options.is_npm_main(),
       location: self.options.location_flag().clone(),
@@ -888,6 +897,7 @@ impl CliFactory {
         .or(std::env::args().next()),
       node_debug: std::env::var("NODE_DEBUG").ok(),
       origin_data_folder_path: Some(self.deno_dir()?.origin_data_folder_path()),
+      deno_dir: Some(self.deno_dir()?.root.clone()),
       seed: self.options.seed(),
       unsafely_ignore_certificate_errors: self
         .options
@@ -743,8 +743,8 @@ async fn fetch_no_follow<'a>(
 #[allow(clippy::print_stderr)]
 #[cfg(test)]
 mod tests {
+  use crate::cache::CliDenoCacheEnv;
   use crate::cache::GlobalHttpCache;
-  use crate::cache::RealDenoCacheEnv;
   use crate::http_util::HttpClient;
   use crate::version;
 
@@ -779,7 +779,7 @@ mod tests {
     let location = temp_dir.path().join("deps").to_path_buf();
     let blob_store: Arc<BlobStore> = Default::default();
     let file_fetcher = FileFetcher::new(
-      Arc::new(GlobalHttpCache::new(location, RealDenoCacheEnv)),
+      Arc::new(GlobalHttpCache::new(location, CliDenoCacheEnv::default())),
       cache_setting,
       true,
       Arc::new(HttpClient::new(None, None)),
@@ -1032,7 +1032,7 @@ mod tests {
     let file_fetcher = FileFetcher::new(
       Arc::new(GlobalHttpCache::new(
         location,
-        crate::cache::RealDenoCacheEnv,
+        crate::cache::CliDenoCacheEnv::default(),
       )),
       CacheSetting::ReloadAll,
       true,
@@ -1064,7 +1064,7 @@ mod tests {
       let file_fetcher = FileFetcher::new(
         Arc::new(GlobalHttpCache::new(
           location.clone(),
-          crate::cache::RealDenoCacheEnv,
+          crate::cache::CliDenoCacheEnv::default(),
         )),
         CacheSetting::Use,
         true,
@@ -1101,7 +1101,7 @@ mod tests {
       let file_fetcher = FileFetcher::new(
         Arc::new(GlobalHttpCache::new(
           location,
-          crate::cache::RealDenoCacheEnv,
+          crate::cache::CliDenoCacheEnv::default(),
         )),
         CacheSetting::Use,
         true,
@@ -1240,7 +1240,7 @@ mod tests {
       let file_fetcher = FileFetcher::new(
         Arc::new(GlobalHttpCache::new(
           location.clone(),
-          crate::cache::RealDenoCacheEnv,
+          crate::cache::CliDenoCacheEnv::default(),
         )),
         CacheSetting::Use,
         true,
@@ -1280,7 +1280,7 @@ mod tests {
       let file_fetcher = FileFetcher::new(
         Arc::new(GlobalHttpCache::new(
           location,
-          crate::cache::RealDenoCacheEnv,
+          crate::cache::CliDenoCacheEnv::default(),
         )),
         CacheSetting::Use,
         true,
@@ -1406,7 +1406,7 @@ mod tests {
     let file_fetcher = FileFetcher::new(
       Arc::new(GlobalHttpCache::new(
         location,
-        crate::cache::RealDenoCacheEnv,
+        crate::cache::CliDenoCacheEnv::default(),
       )),
       CacheSetting::Use,
       false,
@@ -1432,7 +1432,10 @@ mod tests {
     let temp_dir = TempDir::new();
     let location = temp_dir.path().join("deps").to_path_buf();
     let file_fetcher_01 = FileFetcher::new(
-      Arc::new(GlobalHttpCache::new(location.clone(), RealDenoCacheEnv)),
+      Arc::new(GlobalHttpCache::new(
+        location.clone(),
+        CliDenoCacheEnv::default(),
+      )),
       CacheSetting::Only,
       true,
       Arc::new(HttpClient::new(None, None)),
@@ -1440,7 +1443,7 @@ mod tests {
       None,
     );
     let file_fetcher_02 = FileFetcher::new(
-      Arc::new(GlobalHttpCache::new(location, RealDenoCacheEnv)),
+      Arc::new(GlobalHttpCache::new(location, CliDenoCacheEnv::default())),
       CacheSetting::Use,
       true,
       Arc::new(HttpClient::new(None, None)),
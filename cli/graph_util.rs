@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::check_or_insert_remote_bytes;
 use crate::args::jsr_url;
+use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::Lockfile;
 use crate::args::DENO_DISABLE_PEDANTIC_NODE_WARNINGS;
@@ -10,6 +12,7 @@ use crate::cache::ModuleInfoCache;
 use crate::cache::ParsedSourceCache;
 use crate::colors;
 use crate::errors::get_error_class_name;
+use crate::file_fetcher::FetchOptions;
 use crate::file_fetcher::FileFetcher;
 use crate::npm::CliNpmResolver;
 use crate::resolver::CliGraphResolver;
@@ -78,6 +81,17 @@ pub fn graph_valid(
     )
     .errors()
     .flat_map(|error| {
+      // bytes and text imports are not part of the graph, the module loader
+      // loads them on its own
+      if let ModuleGraphError::ModuleError(
+        ModuleError::UnsupportedImportAttributeType { kind, .. },
+      ) = &error
+      {
+        if kind == "bytes" || kind == "text" {
+          return None;
+        }
+      }
+
       let is_root = match &error {
         ModuleGraphError::ResolutionError(_)
         | ModuleGraphError::TypesResolutionError(_) => false,
@@ -157,8 +171,8 @@ pub fn graph_lock_or_exit(graph: &ModuleGraph, lockfile: &mut Lockfile) {
   for module in graph.modules() {
     let source = match module {
       Module::Js(module) if module.media_type.is_declaration() => continue, // skip declaration files
-      Module::Js(module) => &module.source,
-      Module::Json(module) => &module.source,
+      Module::Js(module) => module.source.as_bytes(),
+      Module::Json(module) => module.source.as_bytes(),
       Module::Node(_) | Module::Npm(_) | Module::External(_) => continue,
     };
 
@@ -173,19 +187,63 @@ pub fn graph_lock_or_exit(graph: &ModuleGraph, lockfile: &mut Lockfile) {
       continue;
     }
 
-    if !lockfile.check_or_insert_remote(module.specifier().as_str(), source) {
-      let err = format!(
-        concat!(
-          "The source code is invalid, as it does not match the expected hash in the lock file.\n",
-          "  Specifier: {}\n",
-          "  Lock file: {}",
-        ),
-        module.specifier(),
-        lockfile.filename.display(),
-      );
-      log::error!("{} {}", colors::red("error:"), err);
-      std::process::exit(10);
+    lock_remote_or_exit(module.specifier(), source, lockfile);
+  }
+}
+
+/// Checks the remote modules imported `with { type: "bytes" }` or
+/// `with { type: "text" }` against the lockfile and exits on errors. They are
+/// not part of the graph, so their sources are read back from the cache.
+pub async fn lock_asset_modules_or_exit(
+  graph: &ModuleGraph,
+  file_fetcher: &FileFetcher,
+  permissions: &PermissionsContainer,
+  lockfile: &Mutex<Lockfile>,
+) -> Result<(), AnyError> {
+  for error in graph.module_errors() {
+    let ModuleError::UnsupportedImportAttributeType {
+      specifier, kind, ..
+    } = error
+    else {
+      continue;
+    };
+    if kind != "bytes" && kind != "text"
+      || !matches!(specifier.scheme(), "http" | "https")
+    {
+      continue;
     }
+    let file = file_fetcher
+      .fetch_with_options(FetchOptions {
+        specifier,
+        permissions,
+        maybe_accept: None,
+        maybe_cache_setting: Some(&CacheSetting::Use),
+      })
+      .await?;
+    lock_remote_or_exit(&file.specifier, &file.source, &mut lockfile.lock());
+  }
+  Ok(())
+}
+
+/// Checks the source of a remote module against the lockfile and exits on
+/// a mismatch.
+pub fn lock_remote_or_exit(
+  specifier: &ModuleSpecifier,
+  source: &[u8],
+  lockfile: &mut Lockfile,
+) {
+  if !check_or_insert_remote_bytes(lockfile, specifier.as_str(), source) {
+    let err = format!(
+      concat!(
+        "The source code is invalid, as it does not match the expected hash in the lock file.\n",
+        "  Specifier: {}\n",
+        "  Lock file: {}",
+      ),
+      specifier,
+      lockfile.filename.display(),
+    );
+    log::error!("{} {}", colors::red("error:"), err);
+    std::process::exit(10);
   }
 }
 
@@ -10,23 +10,28 @@ const {
   op_test_event_step_result_failed,
   op_test_event_step_result_ignored,
   op_test_event_step_result_ok,
+  op_test_env_changed,
   op_test_event_step_wait,
   op_test_get_origin,
 } = core.ops;
 const {
   ArrayPrototypeFilter,
+  ArrayPrototypeJoin,
   ArrayPrototypePush,
+  ArrayPrototypeSlice,
   DateNow,
   Error,
   Map,
   MapPrototypeGet,
   MapPrototypeSet,
   SafeArrayIterator,
+  StringPrototypeIncludes,
+  StringPrototypeSplit,
   SymbolToStringTag,
   TypeError,
 } = primordials;
 
-import { setExitHandler } from "ext:runtime/30_os.js";
+import { setEnvChangeHandler, setExitHandler } from "ext:runtime/30_os.js";
 
 /**
  * @typedef {{
@@ -40,6 +45,8 @@ import { setExitHandler } from "ext:runtime/30_os.js";
  *   sanitizeOps: boolean,
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
+ *   sanitizeChildProcesses: boolean,
+ *   sanitizeEnv: boolean,
  *   permissions: PermissionOptions,
  * }} TestDescription
  *
@@ -108,6 +115,28 @@ function assertExit(fn, isTest) {
   };
 }
 
+// Wrap test function so that changes to environment variables record where
+// they were made, to point at the culprit if the environment sanitizer fails.
+function traceEnvChanges(fn) {
+  return async function envSanitizer(...params) {
+    setEnvChangeHandler((key) => {
+      const lines = StringPrototypeSplit(new Error().stack, "\n");
+      const trace = ArrayPrototypeFilter(
+        ArrayPrototypeSlice(lines, 1),
+        (line) => !StringPrototypeIncludes(line, "ext:"),
+      );
+      op_test_env_changed(key, ArrayPrototypeJoin(trace, "\n"));
+    });
+
+    try {
+      const innerResult = await fn(...new SafeArrayIterator(params));
+      if (innerResult) return innerResult;
+    } finally {
+      setEnvChangeHandler(null);
+    }
+  };
+}
+
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
     try {
@@ -210,6 +239,7 @@ function testInner(
     sanitizeOps: true,
     sanitizeResources: true,
     sanitizeExit: true,
+    sanitizeEnv: false,
     permissions: null,
   };
 
@@ -284,6 +314,9 @@ function testInner(
   }
 
   testDesc = { ...testDesc, ...overrides };
+  // Child processes show up as ops, so opting out of the op sanitizer also
+  // opts out of this one unless it's enabled explicitly.
+  testDesc.sanitizeChildProcesses ??= testDesc.sanitizeOps;
 
   // Delete this prop in case the user passed it. It's used to detect steps.
   delete testDesc.parent;
@@ -303,6 +336,8 @@ function testInner(
     testDesc.only,
     testDesc.sanitizeOps,
     testDesc.sanitizeResources,
+    testDesc.sanitizeChildProcesses,
+    testDesc.sanitizeEnv,
     testDesc.location.fileName,
     testDesc.location.lineNumber,
     testDesc.location.columnNumber,
@@ -493,6 +528,9 @@ function wrapTest(desc) {
   if (desc.sanitizeExit) {
     testFn = assertExit(testFn, true);
   }
+  if (!("parent" in desc) && desc.sanitizeEnv) {
+    testFn = traceEnvChanges(testFn);
+  }
   if (!("parent" in desc) && desc.permissions) {
     testFn = withPermissions(testFn, desc.permissions);
   }
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::cache::CliDenoCacheEnv;
 use crate::cache::DenoDir;
 use crate::cache::GlobalHttpCache;
 use crate::cache::HttpCache;
@@ -100,10 +101,9 @@ impl LspCache {
     });
     let deno_dir = DenoDir::new(global_cache_path)
       .expect("should be infallible with absolute custom root");
-    let global = Arc::new(GlobalHttpCache::new(
-      deno_dir.deps_folder_path(),
-      crate::cache::RealDenoCacheEnv,
-    ));
+    let deps_folder = deno_dir.deps_folder_path();
+    let cache_env = CliDenoCacheEnv::from_env(&deps_folder);
+    let global = Arc::new(GlobalHttpCache::new(deps_folder, cache_env));
     Self {
       deno_dir,
       global,
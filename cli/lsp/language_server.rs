@@ -13,9 +13,11 @@ use deno_core::url;
 use deno_core::ModuleSpecifier;
 use deno_graph::GraphKind;
 use deno_graph::Resolution;
+use deno_npm::NpmSystemInfo;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_semver::jsr::JsrPackageReqReference;
+use deno_semver::package::PackageNv;
 use indexmap::IndexSet;
 use log::error;
 use serde::Deserialize;
@@ -26,6 +28,7 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::env;
 use std::fmt::Write as _;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc::unbounded_channel;
@@ -213,12 +216,14 @@ impl LanguageServer {
   }
 
   /// Similar to `deno cache` on the command line, where modules will be cached
-  /// in the Deno cache, including any of their dependencies.
+  /// in the Deno cache, including any of their dependencies. When `reload` is
+  /// set, the provided specifiers are re-fetched even if already cached.
   pub async fn cache(
     &self,
     specifiers: Vec<ModuleSpecifier>,
     referrer: ModuleSpecifier,
     force_global_cache: bool,
+    reload: bool,
   ) -> LspResult<Option<Value>> {
     async fn create_graph_for_caching(
       cli_options: CliOptions,
@@ -267,7 +272,12 @@ impl LanguageServer {
     // prepare the cache inside the lock
     let maybe_prepare_cache_result = {
       let mut inner = self.0.write().await; // ensure dropped
-      match inner.prepare_cache(specifiers, referrer, force_global_cache) {
+      match inner.prepare_cache(
+        specifiers,
+        referrer,
+        force_global_cache,
+        reload,
+      ) {
         Ok(maybe_cache_result) => maybe_cache_result,
         Err(err) => {
           lsp_warn!("Error preparing caching: {:#}", err);
@@ -332,10 +342,46 @@ impl LanguageServer {
     )
   }
 
+  pub async fn cache_status_request(&self) -> LspResult<Option<Value>> {
+    let status = self.0.read().await.cache_status();
+    Ok(Some(serde_json::to_value(status).map_err(|err| {
+      error!("Failed to serialize cache status response: {:#}", err);
+      LspError::internal_error()
+    })?))
+  }
+
   pub async fn performance_request(&self) -> LspResult<Option<Value>> {
     Ok(Some(self.0.read().await.get_performance()))
   }
 
+  /// Re-fetches the provided specifiers from their origin, bypassing anything
+  /// already stored in the cache. Their dependencies are still loaded from
+  /// the cache when they are in it.
+  pub async fn recache_request(
+    &self,
+    params: Option<Value>,
+  ) -> LspResult<Option<Value>> {
+    match params.map(serde_json::from_value::<lsp_custom::RecacheParams>) {
+      Some(Ok(params)) => {
+        let (specifiers, referrer) = {
+          let inner = self.0.read().await;
+          let specifiers = params
+            .specifiers
+            .iter()
+            .map(|s| inner.url_map.normalize_url(s, LspUrlKind::File))
+            .collect::<Vec<_>>();
+          let referrer = inner
+            .url_map
+            .normalize_url(&params.referrer, LspUrlKind::File);
+          (specifiers, referrer)
+        };
+        self.cache(specifiers, referrer, false, true).await
+      }
+      Some(Err(err)) => Err(LspError::invalid_params(err.to_string())),
+      None => Err(LspError::invalid_params("Missing parameters")),
+    }
+  }
+
   pub async fn task_definitions(&self) -> LspResult<Vec<TaskDefinition>> {
     self.0.read().await.task_definitions()
   }
@@ -927,7 +973,8 @@ impl Inner {
               let referrer = config_file.specifier.clone();
               self.task_queue.queue_task(Box::new(|ls: LanguageServer| {
                 spawn(async move {
-                  if let Err(err) = ls.cache(specifiers, referrer, false).await
+                  if let Err(err) =
+                    ls.cache(specifiers, referrer, false, false).await
                   {
                     lsp_warn!("{:#}", err);
                   }
@@ -1057,7 +1104,9 @@ impl Inner {
     }
     self.task_queue.queue_task(Box::new(|ls: LanguageServer| {
       spawn(async move {
-        if let Err(err) = ls.cache(vec![], specifier.clone(), false).await {
+        if let Err(err) =
+          ls.cache(vec![], specifier.clone(), false, false).await
+        {
           lsp_warn!("Failed to cache \"{}\" on save: {:#}", &specifier, err);
         }
       });
@@ -2803,7 +2852,7 @@ impl tower_lsp::LanguageServer for LanguageServer {
         serde_json::from_value(json!(params.arguments))
           .map_err(|err| LspError::invalid_params(err.to_string()))?;
       self
-        .cache(specifiers, referrer, options.force_global_cache)
+        .cache(specifiers, referrer, options.force_global_cache, false)
         .await
     } else if params.command == "deno.reloadImportRegistries" {
       self.0.write().await.reload_import_registries().await
@@ -3250,11 +3299,17 @@ impl Inner {
     specifiers: Vec<ModuleSpecifier>,
     referrer: ModuleSpecifier,
     force_global_cache: bool,
+    reload: bool,
   ) -> Result<Option<PrepareCacheResult>, AnyError> {
     let mark = self
       .performance
       .mark_with_args("lsp.cache", (&specifiers, &referrer));
     let config_data = self.config.tree.root_data();
+    let cache_blocklist = if reload {
+      specifiers.iter().map(|s| s.to_string()).collect()
+    } else {
+      vec![]
+    };
     let mut roots = if !specifiers.is_empty() {
       specifiers
     } else {
@@ -3275,6 +3330,7 @@ impl Inner {
     let cli_options = CliOptions::new(
       Flags {
         cache_path: Some(self.cache.deno_dir().root.clone()),
+        cache_blocklist,
         ca_stores: workspace_settings.certificate_stores.clone(),
         ca_data: workspace_settings.tls_certificate.clone().map(CaData::File),
         unsafely_ignore_certificate_errors: workspace_settings
@@ -3316,6 +3372,94 @@ impl Inner {
     json!({ "averages": averages })
   }
 
+  /// The cached remote modules and npm packages that this language server has
+  /// loaded, see [`lsp_custom::CacheStatusResponse`] for what is left out.
+  fn cache_status(&self) -> lsp_custom::CacheStatusResponse {
+    let mut remote_modules = self
+      .documents
+      .documents(DocumentsFilter::All)
+      .into_iter()
+      .filter(|d| matches!(d.specifier().scheme(), "http" | "https"))
+      .filter_map(|d| {
+        let uri = self
+          .url_map
+          .normalize_specifier(d.specifier())
+          .ok()?
+          .into_url();
+        Some(lsp_custom::CachedRemoteModule {
+          specifier: d.specifier().clone(),
+          uri,
+          media_type: d.media_type().to_string(),
+          size: d.content().len(),
+        })
+      })
+      .collect::<Vec<_>>();
+    remote_modules.sort_by(|a, b| a.specifier.cmp(&b.specifier));
+
+    let mut npm_packages = vec![];
+    if let Some(npm_resolver) = self.resolver.maybe_managed_npm_resolver() {
+      for package in npm_resolver.all_system_packages(&NpmSystemInfo::default())
+      {
+        let Ok(uri) =
+          Url::parse(&format!("deno:/npm/{}/package.json", package.id.nv))
+        else {
+          continue;
+        };
+        npm_packages.push(lsp_custom::CachedNpmPackage {
+          name: package.id.nv.name.clone(),
+          version: package.id.nv.version.to_string(),
+          uri,
+          size: npm_resolver.package_size(&package.id).ok(),
+        });
+      }
+    }
+    npm_packages.sort_by(|a, b| {
+      a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version))
+    });
+    npm_packages.dedup_by(|a, b| a.name == b.name && a.version == b.version);
+
+    lsp_custom::CacheStatusResponse {
+      remote_modules,
+      npm_packages,
+    }
+  }
+
+  /// Reads a file out of a resolved npm package for a virtual document URL in
+  /// the form of `deno:/npm/<name>@<version>/<path>`.
+  fn npm_package_virtual_document(&self, uri: &Url) -> Option<String> {
+    if uri.scheme() != "deno" {
+      return None;
+    }
+    let path = uri.path().strip_prefix("/npm/")?;
+    let path = percent_encoding::percent_decode_str(path)
+      .decode_utf8()
+      .ok()?;
+    // scoped packages contain an additional path segment
+    let nv_end = if path.starts_with('@') {
+      let scope_end = path.find('/')?;
+      scope_end + 1 + path[scope_end + 1..].find('/')?
+    } else {
+      path.find('/')?
+    };
+    let nv = PackageNv::from_str(&path[..nv_end]).ok()?;
+    let sub_path = Path::new(&path[nv_end + 1..]);
+    if !sub_path
+      .components()
+      .all(|c| matches!(c, std::path::Component::Normal(_)))
+    {
+      return None;
+    }
+    let npm_resolver = self.resolver.maybe_managed_npm_resolver()?;
+    let package = npm_resolver
+      .all_system_packages(&NpmSystemInfo::default())
+      .into_iter()
+      .find(|p| p.id.nv == nv)?;
+    let folder = npm_resolver
+      .resolve_pkg_folder_from_pkg_id(&package.id)
+      .ok()?;
+    std::fs::read_to_string(folder.join(sub_path)).ok()
+  }
+
   fn task_definitions(&self) -> LspResult<Vec<TaskDefinition>> {
     let mut result = vec![];
     for config_file in self.config.tree.config_files() {
@@ -3493,6 +3637,10 @@ impl Inner {
         .unwrap();
       }
 
+      Some(contents)
+    } else if let Some(contents) =
+      self.npm_package_virtual_document(&params.text_document.uri)
+    {
       Some(contents)
     } else {
       let asset_or_doc = self.get_maybe_asset_or_document(&specifier);
@@ -4,7 +4,9 @@ use deno_core::serde::Deserialize;
 use deno_core::serde::Serialize;
 use tower_lsp::lsp_types as lsp;
 
+pub const CACHE_STATUS_REQUEST: &str = "deno/cacheStatus";
 pub const PERFORMANCE_REQUEST: &str = "deno/performance";
+pub const RECACHE_REQUEST: &str = "deno/recache";
 pub const TASK_REQUEST: &str = "deno/taskDefinitions";
 pub const VIRTUAL_TEXT_DOCUMENT: &str = "deno/virtualTextDocument";
 pub const LATEST_DIAGNOSTIC_BATCH_INDEX: &str =
@@ -20,6 +22,51 @@ pub struct TaskDefinition {
   pub source_uri: lsp::Url,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedRemoteModule {
+  pub specifier: lsp::Url,
+  /// The URL the client can use to open the module as a read-only virtual
+  /// document via `deno/virtualTextDocument`.
+  pub uri: lsp::Url,
+  pub media_type: String,
+  pub size: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedNpmPackage {
+  pub name: String,
+  pub version: String,
+  /// A `deno:/npm/<name>@<version>/package.json` URL which can be opened as a
+  /// read-only virtual document via `deno/virtualTextDocument`.
+  pub uri: lsp::Url,
+  pub size: Option<u64>,
+}
+
+/// The response of `deno/cacheStatus`.
+///
+/// This is not a listing of the cache directory. It only covers what the
+/// language server has loaded: the remote modules among the open documents
+/// and the dependencies it has resolved for them, and the npm packages of the
+/// current npm resolution. Modules that are cached but not imported by any of
+/// those documents are not reported.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatusResponse {
+  /// The remote modules loaded by the language server, sorted by specifier.
+  pub remote_modules: Vec<CachedRemoteModule>,
+  /// The packages of the current npm resolution, sorted by name and version.
+  pub npm_packages: Vec<CachedNpmPackage>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecacheParams {
+  pub specifiers: Vec<lsp::Url>,
+  pub referrer: lsp::Url,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RegistryStateNotificationParams {
   pub origin: String,
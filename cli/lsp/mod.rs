@@ -51,10 +51,15 @@ pub async fn start() -> Result<(), AnyError> {
       token.clone(),
     )
   })
+  .custom_method(
+    lsp_custom::CACHE_STATUS_REQUEST,
+    LanguageServer::cache_status_request,
+  )
   .custom_method(
     lsp_custom::PERFORMANCE_REQUEST,
     LanguageServer::performance_request,
   )
+  .custom_method(lsp_custom::RECACHE_REQUEST, LanguageServer::recache_request)
   .custom_method(lsp_custom::TASK_REQUEST, LanguageServer::task_definitions)
   // TODO(nayeemrmn): Rename this to `deno/taskDefinitions` in vscode_deno and
   // remove this alias.
@@ -426,7 +426,7 @@ impl ModuleRegistry {
     // the http cache should always be the global one for registry completions
     let http_cache = Arc::new(GlobalHttpCache::new(
       location.clone(),
-      crate::cache::RealDenoCacheEnv,
+      crate::cache::CliDenoCacheEnv::default(),
     ));
     let mut file_fetcher = FileFetcher::new(
       http_cache.clone(),
@@ -348,6 +348,9 @@ pub fn main() {
         // initialize the V8 platform on a parent thread of all threads that will spawn
         // V8 isolates.
         let flags = resolve_flags_and_init(args)?;
+        if let Some(exit_code) = args::run_pinned_version(&flags)? {
+          return Ok(exit_code);
+        }
         run_subcommand(flags).await
       }
     }
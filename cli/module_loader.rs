@@ -8,6 +8,7 @@ use std::str;
 use std::sync::Arc;
 
 use crate::args::jsr_url;
+use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::DenoSubcommand;
 use crate::args::TsTypeLib;
@@ -16,10 +17,13 @@ use crate::cache::ModuleInfoCache;
 use crate::cache::ParsedSourceCache;
 use crate::emit::Emitter;
 use crate::factory::CliFactory;
+use crate::file_fetcher::FetchOptions;
+use crate::file_fetcher::FileFetcher;
 use crate::graph_container::MainModuleGraphContainer;
 use crate::graph_container::ModuleGraphContainer;
 use crate::graph_container::ModuleGraphUpdatePermit;
 use crate::graph_util::graph_lock_or_exit;
+use crate::graph_util::lock_asset_modules_or_exit;
 use crate::graph_util::CreateGraphOptions;
 use crate::graph_util::ModuleGraphBuilder;
 use crate::node;
@@ -113,6 +117,7 @@ pub async fn load_top_level_deps(factory: &CliFactory) -> Result<(), AnyError> {
 
 pub struct ModuleLoadPreparer {
   options: Arc<CliOptions>,
+  file_fetcher: Arc<FileFetcher>,
   lockfile: Option<Arc<Mutex<Lockfile>>>,
   module_graph_builder: Arc<ModuleGraphBuilder>,
   progress_bar: ProgressBar,
@@ -123,6 +128,7 @@ impl ModuleLoadPreparer {
   #[allow(clippy::too_many_arguments)]
   pub fn new(
     options: Arc<CliOptions>,
+    file_fetcher: Arc<FileFetcher>,
     lockfile: Option<Arc<Mutex<Lockfile>>>,
     module_graph_builder: Arc<ModuleGraphBuilder>,
     progress_bar: ProgressBar,
@@ -130,6 +136,7 @@ impl ModuleLoadPreparer {
   ) -> Self {
     Self {
       options,
+      file_fetcher,
       lockfile,
       module_graph_builder,
       progress_bar,
@@ -153,7 +160,9 @@ impl ModuleLoadPreparer {
     log::debug!("Preparing module load.");
     let _pb_clear_guard = self.progress_bar.clear_guard();
 
-    let mut cache = self.module_graph_builder.create_fetch_cacher(permissions);
+    let mut cache = self
+      .module_graph_builder
+      .create_fetch_cacher(permissions.clone());
     log::debug!("Building module graph.");
     let has_type_checked = !graph.roots.is_empty();
 
@@ -174,6 +183,13 @@ impl ModuleLoadPreparer {
 
     // If there is a lockfile...
     if let Some(lockfile) = &self.lockfile {
+      lock_asset_modules_or_exit(
+        graph,
+        &self.file_fetcher,
+        &permissions,
+        lockfile,
+      )
+      .await?;
       let mut lockfile = lockfile.lock();
       // validate the integrity of all the modules
       graph_lock_or_exit(graph, &mut lockfile);
@@ -218,6 +234,7 @@ struct SharedCliModuleLoaderState {
   is_repl: bool,
   code_cache: Option<Arc<CodeCache>>,
   emitter: Arc<Emitter>,
+  file_fetcher: Arc<FileFetcher>,
   main_module_graph_container: Arc<MainModuleGraphContainer>,
   module_info_cache: Arc<ModuleInfoCache>,
   module_load_preparer: Arc<ModuleLoadPreparer>,
@@ -237,6 +254,7 @@ impl CliModuleLoaderFactory {
     options: &CliOptions,
     code_cache: Option<Arc<CodeCache>>,
     emitter: Arc<Emitter>,
+    file_fetcher: Arc<FileFetcher>,
     main_module_graph_container: Arc<MainModuleGraphContainer>,
     module_info_cache: Arc<ModuleInfoCache>,
     module_load_preparer: Arc<ModuleLoadPreparer>,
@@ -257,6 +275,7 @@ impl CliModuleLoaderFactory {
         ),
         code_cache,
         emitter,
+        file_fetcher,
         main_module_graph_container,
         module_info_cache,
         module_load_preparer,
@@ -352,6 +371,13 @@ impl<TGraphContainer: ModuleGraphContainer>
     } else {
       &self.root_permissions
     };
+    if let RequestedModuleType::Other(module_type) = &requested_module_type {
+      if module_type == "bytes" || module_type == "text" {
+        return self
+          .load_asset_module(specifier, module_type.clone(), permissions)
+          .await;
+      }
+    }
     let code_source = if let Some(result) = self
       .shared
       .npm_module_loader
@@ -577,6 +603,35 @@ impl<TGraphContainer: ModuleGraphContainer>
     Ok(Some(timestamp))
   }
 
+  /// Loads a module imported `with { type: "bytes" }` or
+  /// `with { type: "text" }`. These are not part of the module graph, but
+  /// were fetched into the cache, and checked against the lockfile, when
+  /// preparing it.
+  async fn load_asset_module(
+    &self,
+    specifier: &ModuleSpecifier,
+    module_type: Cow<'static, str>,
+    permissions: &PermissionsContainer,
+  ) -> Result<ModuleSource, AnyError> {
+    let file = self
+      .shared
+      .file_fetcher
+      .fetch_with_options(FetchOptions {
+        specifier,
+        permissions,
+        maybe_accept: None,
+        maybe_cache_setting: Some(&CacheSetting::Use),
+      })
+      .await?;
+    Ok(ModuleSource::new_with_redirect(
+      ModuleType::Other(module_type),
+      ModuleSourceCode::Bytes(file.source.into()),
+      specifier,
+      &file.specifier,
+      None,
+    ))
+  }
+
   async fn load_prepared_module(
     &self,
     specifier: &ModuleSpecifier,
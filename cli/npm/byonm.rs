@@ -30,6 +30,9 @@ use super::InnerCliNpmResolverRef;
 pub struct CliNpmResolverByonmCreateOptions {
   pub fs: Arc<dyn FileSystem>,
   pub root_node_modules_dir: PathBuf,
+  /// Keep the symlinked paths of the packages in `node_modules` instead of
+  /// resolving them to their real location.
+  pub preserve_symlinks: bool,
 }
 
 pub fn create_byonm_npm_resolver(
@@ -38,6 +41,7 @@ pub fn create_byonm_npm_resolver(
   Arc::new(ByonmCliNpmResolver {
     fs: options.fs,
     root_node_modules_dir: options.root_node_modules_dir,
+    preserve_symlinks: options.preserve_symlinks,
   })
 }
 
@@ -45,6 +49,7 @@ pub fn create_byonm_npm_resolver(
 pub struct ByonmCliNpmResolver {
   fs: Arc<dyn FileSystem>,
   root_node_modules_dir: PathBuf,
+  preserve_symlinks: bool,
 }
 
 impl ByonmCliNpmResolver {
@@ -147,6 +152,9 @@ impl NpmResolver for ByonmCliNpmResolver {
     }
 
     let path = inner(&*self.fs, name, referrer, mode)?;
+    if self.preserve_symlinks {
+      return Ok(path);
+    }
     Ok(self.fs.realpath_sync(&path)?)
   }
 
@@ -182,6 +190,7 @@ impl CliNpmResolver for ByonmCliNpmResolver {
     Arc::new(Self {
       fs: self.fs.clone(),
       root_node_modules_dir: self.root_node_modules_dir.clone(),
+      preserve_symlinks: self.preserve_symlinks,
     })
   }
 
@@ -202,6 +211,7 @@ impl CliNpmResolver for ByonmCliNpmResolver {
       req: &PackageReq,
       fs: &dyn FileSystem,
       path: PathBuf,
+      preserve_symlinks: bool,
     ) -> Result<PathBuf, AnyError> {
       let package_json = PackageJson::load_skip_read_permission(fs, path)?;
       let deps = get_local_package_json_version_reqs(&package_json);
@@ -216,6 +226,9 @@ impl CliNpmResolver for ByonmCliNpmResolver {
               .unwrap()
               .join("node_modules")
               .join(key);
+            if preserve_symlinks {
+              return Ok(package_path);
+            }
             return Ok(canonicalize_path_maybe_not_exists_with_fs(
               &package_path,
               fs,
@@ -245,6 +258,7 @@ impl CliNpmResolver for ByonmCliNpmResolver {
             req,
             self.fs.as_ref(),
             package_json_path,
+            self.preserve_symlinks,
           );
         }
         current_path = dir_path;
@@ -259,6 +273,7 @@ impl CliNpmResolver for ByonmCliNpmResolver {
         .parent()
         .unwrap()
         .join("package.json"),
+      self.preserve_symlinks,
     )
   }
 
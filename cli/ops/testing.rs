@@ -2,10 +2,12 @@
 
 use crate::tools::test::TestContainer;
 use crate::tools::test::TestDescription;
+use crate::tools::test::TestEnvironmentTraces;
 use crate::tools::test::TestEvent;
 use crate::tools::test::TestEventSender;
 use crate::tools::test::TestFailure;
 use crate::tools::test::TestLocation;
+use crate::tools::test::TestResourceTimeline;
 use crate::tools::test::TestStepDescription;
 use crate::tools::test::TestStepResult;
 
@@ -31,6 +33,7 @@ deno_core::extension!(deno_test,
     op_register_test,
     op_register_test_step,
     op_test_get_origin,
+    op_test_env_changed,
     op_test_event_step_wait,
     op_test_event_step_result_ok,
     op_test_event_step_result_ignored,
@@ -113,6 +116,8 @@ fn op_register_test(
   only: bool,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  sanitize_child_processes: bool,
+  sanitize_env: bool,
   #[string] file_name: String,
   #[smi] line_number: u32,
   #[smi] column_number: u32,
@@ -133,6 +138,8 @@ fn op_register_test(
     only,
     sanitize_ops,
     sanitize_resources,
+    sanitize_child_processes,
+    sanitize_env,
     origin: origin.clone(),
     location: TestLocation {
       file_name,
@@ -152,6 +159,15 @@ fn op_test_get_origin(state: &mut OpState) -> String {
   state.borrow::<ModuleSpecifier>().to_string()
 }
 
+#[op2(fast)]
+fn op_test_env_changed(
+  state: &mut OpState,
+  #[string] name: String,
+  #[string] trace: String,
+) {
+  TestEnvironmentTraces::record(state, name, trace);
+}
+
 #[op2(fast)]
 #[smi]
 #[allow(clippy::too_many_arguments)]
@@ -182,6 +198,7 @@ fn op_register_test_step(
     root_id,
     root_name,
   };
+  TestResourceTimeline::register_step(state, id, &description.name);
   let sender = state.borrow_mut::<TestEventSender>();
   sender.send(TestEvent::StepRegister(description)).ok();
   Ok(id)
@@ -189,6 +206,7 @@ fn op_register_test_step(
 
 #[op2(fast)]
 fn op_test_event_step_wait(state: &mut OpState, #[smi] id: usize) {
+  TestResourceTimeline::step_started(state, id);
   let sender = state.borrow_mut::<TestEventSender>();
   sender.send(TestEvent::StepWait(id)).ok();
 }
@@ -199,6 +217,7 @@ fn op_test_event_step_result_ok(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
+  TestResourceTimeline::step_ended(state, id);
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(id, TestStepResult::Ok, duration))
@@ -211,6 +230,7 @@ fn op_test_event_step_result_ignored(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
+  TestResourceTimeline::step_ended(state, id);
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(id, TestStepResult::Ignored, duration))
@@ -224,6 +244,7 @@ fn op_test_event_step_result_failed(
   #[serde] failure: TestFailure,
   #[smi] duration: u64,
 ) {
+  TestResourceTimeline::step_ended(state, id);
   let sender = state.borrow_mut::<TestEventSender>();
   sender
     .send(TestEvent::StepResult(
@@ -37,6 +37,7 @@ use crate::args::CliOptions;
 use crate::args::CompileFlags;
 use crate::args::PackageJsonDepsProvider;
 use crate::args::PermissionFlags;
+use crate::args::PermissionOverridePolicy;
 use crate::args::UnstableConfig;
 use crate::cache::DenoDir;
 use crate::file_fetcher::FileFetcher;
@@ -135,6 +136,7 @@ pub struct Metadata {
   pub argv: Vec<String>,
   pub seed: Option<u64>,
   pub permissions: PermissionFlags,
+  pub permission_override: PermissionOverridePolicy,
   pub location: Option<Url>,
   pub v8_flags: Vec<String>,
   pub log_level: Option<Level>,
@@ -622,6 +624,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       seed: cli_options.seed(),
       location: cli_options.location_flag().clone(),
       permissions: cli_options.permission_flags().clone(),
+      permission_override: compile_flags.permission_override,
       v8_flags: cli_options.v8_flags().clone(),
       unsafely_ignore_certificate_errors: cli_options
         .unsafely_ignore_certificate_errors()
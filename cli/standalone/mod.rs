@@ -5,6 +5,8 @@ use crate::args::npm_pkg_req_ref_to_binary_command;
 use crate::args::CaData;
 use crate::args::CacheSetting;
 use crate::args::PackageJsonDepsProvider;
+use crate::args::PermissionFlags;
+use crate::args::PermissionOverridePolicy;
 use crate::args::StorageKeyResolver;
 use crate::cache::Caches;
 use crate::cache::DenoDirProvider;
@@ -35,6 +37,7 @@ use deno_core::error::generic_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
+use deno_core::serde_json;
 use deno_core::v8_set_flags;
 use deno_core::FeatureChecker;
 use deno_core::ModuleLoader;
@@ -70,6 +73,11 @@ use self::binary::load_npm_vfs;
 use self::binary::Metadata;
 use self::file_system::DenoCompileFileSystem;
 
+/// Additional permission flags for executables compiled with
+/// `--permission-override=allow`, as a JSON array of strings so that their
+/// values may contain whitespace.
+const STANDALONE_PERMISSIONS_ENV_VAR: &str = "DENO_STANDALONE_PERMISSIONS";
+
 struct SharedModuleLoaderState {
   eszip: eszip::EszipV2,
   mapped_specifier_resolver: MappedSpecifierResolver,
@@ -420,6 +428,7 @@ pub async fn run(
             CliNpmResolverByonmCreateOptions {
               fs: fs.clone(),
               root_node_modules_dir: node_modules_path,
+              preserve_symlinks: false,
             },
           ))
           .await?;
@@ -500,9 +509,26 @@ pub async fn run(
   };
 
   let permissions = {
+    let mut permission_flags = metadata.permissions;
+    match metadata.permission_override {
+      PermissionOverridePolicy::Never => {
+        permission_flags.no_prompt = true;
+      }
+      PermissionOverridePolicy::Prompt => {}
+      PermissionOverridePolicy::Allow => {
+        if let Ok(args) = std::env::var(STANDALONE_PERMISSIONS_ENV_VAR) {
+          let extra_flags = serde_json::from_str::<Vec<String>>(&args)
+            .map_err(AnyError::from)
+            .and_then(|args| Ok(PermissionFlags::from_args(args)?))
+            .with_context(|| {
+              format!("Failed to parse {STANDALONE_PERMISSIONS_ENV_VAR}")
+            })?;
+          permission_flags.extend(extra_flags);
+        }
+      }
+    }
     let maybe_cwd = std::env::current_dir().ok();
-    let mut permissions =
-      metadata.permissions.to_options(maybe_cwd.as_deref())?;
+    let mut permissions = permission_flags.to_options(maybe_cwd.as_deref())?;
     // if running with an npm vfs, grant read access to it
     if let Some(vfs_root) = maybe_vfs_root {
       match &mut permissions.allow_read {
@@ -556,10 +582,13 @@ pub async fn run(
       enable_op_summary_metrics: false,
       enable_testing_features: false,
       has_node_modules_dir,
+      preserve_symlinks: false,
+      console_timestamps: *crate::args::DENO_CONSOLE_TIMESTAMPS,
       hmr: false,
       inspect_brk: false,
       inspect_wait: false,
       strace_ops: None,
+      op_metrics: false,
       is_inspecting: false,
       is_npm_main: main_module.scheme() == "npm",
       skip_op_registration: true,
@@ -570,6 +599,7 @@ pub async fn run(
         .or(std::env::args().next()),
       node_debug: std::env::var("NODE_DEBUG").ok(),
       origin_data_folder_path: None,
+      deno_dir: None,
       seed: metadata.seed,
       unsafely_ignore_certificate_errors: metadata
         .unsafely_ignore_certificate_errors,
@@ -51,13 +51,16 @@ mod mitata;
 mod reporters;
 
 use reporters::BenchReporter;
+use reporters::CompoundReporter;
 use reporters::ConsoleReporter;
 use reporters::JsonReporter;
+use reporters::JsonlReporter;
 
 #[derive(Debug, Clone)]
 struct BenchSpecifierOptions {
   filter: TestFilter,
   json: bool,
+  output_path: Option<String>,
   log_level: Option<log::Level>,
 }
 
@@ -137,11 +140,18 @@ impl BenchReport {
 fn create_reporter(
   show_output: bool,
   json: bool,
-) -> Box<dyn BenchReporter + Send> {
-  if json {
-    return Box::new(JsonReporter::new());
+  output_path: Option<&str>,
+) -> Result<Box<dyn BenchReporter + Send>, AnyError> {
+  let reporter: Box<dyn BenchReporter + Send> = if json {
+    Box::new(JsonReporter::new())
+  } else {
+    Box::new(ConsoleReporter::new(show_output))
+  };
+  if let Some(output_path) = output_path {
+    let jsonl = Box::new(JsonlReporter::new(output_path)?);
+    return Ok(Box::new(CompoundReporter::new(vec![reporter, jsonl])));
   }
-  Box::new(ConsoleReporter::new(show_output))
+  Ok(reporter)
 }
 
 /// Run a single specifier as an executable bench module.
@@ -299,8 +309,11 @@ async fn bench_specifiers(
     spawn(async move {
       let mut used_only = false;
       let mut report = BenchReport::new();
-      let mut reporter =
-        create_reporter(log_level != Some(Level::Error), options.json);
+      let mut reporter = create_reporter(
+        log_level != Some(Level::Error),
+        options.json,
+        options.output_path.as_deref(),
+      )?;
       let mut benches = IndexMap::new();
 
       while let Some(event) = receiver.recv().await {
@@ -443,6 +456,7 @@ pub async fn run_benchmarks(
     BenchSpecifierOptions {
       filter: TestFilter::from_flag(&bench_options.filter),
       json: bench_options.json,
+      output_path: bench_options.output_path,
       log_level,
     },
   )
@@ -553,6 +567,7 @@ pub async fn run_benchmarks_with_watch(
           BenchSpecifierOptions {
             filter: TestFilter::from_flag(&bench_options.filter),
             json: bench_options.json,
+            output_path: bench_options.output_path,
             log_level,
           },
         )
@@ -1,7 +1,17 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+
+use console_static_text::ansi::strip_ansi_codes;
+use deno_core::anyhow::Context;
+use deno_core::serde_json;
+use deno_runtime::ops::os::rss;
 use serde::Serialize;
 
+use crate::util::fs::create_file;
+
 use super::*;
 
 pub trait BenchReporter {
@@ -97,6 +107,207 @@ impl BenchReporter for JsonReporter {
   fn report_uncaught_error(&mut self, _origin: &str, _error: Box<JsError>) {}
 }
 
+pub struct CompoundReporter(Vec<Box<dyn BenchReporter + Send>>);
+
+impl CompoundReporter {
+  pub fn new(reporters: Vec<Box<dyn BenchReporter + Send>>) -> Self {
+    Self(reporters)
+  }
+}
+
+impl BenchReporter for CompoundReporter {
+  fn report_group_summary(&mut self) {
+    for reporter in &mut self.0 {
+      reporter.report_group_summary();
+    }
+  }
+
+  fn report_plan(&mut self, plan: &BenchPlan) {
+    for reporter in &mut self.0 {
+      reporter.report_plan(plan);
+    }
+  }
+
+  fn report_end(&mut self, report: &BenchReport) {
+    for reporter in &mut self.0 {
+      reporter.report_end(report);
+    }
+  }
+
+  fn report_register(&mut self, desc: &BenchDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_register(desc);
+    }
+  }
+
+  fn report_wait(&mut self, desc: &BenchDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_wait(desc);
+    }
+  }
+
+  fn report_output(&mut self, output: &str) {
+    for reporter in &mut self.0 {
+      reporter.report_output(output);
+    }
+  }
+
+  fn report_result(&mut self, desc: &BenchDescription, result: &BenchResult) {
+    for reporter in &mut self.0 {
+      reporter.report_result(desc, result);
+    }
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    for reporter in &mut self.0 {
+      reporter.report_uncaught_error(origin, error.clone());
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum JsonlBenchEvent<'a> {
+  #[serde(rename_all = "camelCase")]
+  Plan {
+    origin: &'a str,
+    total: usize,
+    used_only: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Register {
+    id: usize,
+    name: &'a str,
+    origin: &'a str,
+    group: Option<&'a str>,
+    baseline: bool,
+  },
+  Wait {
+    id: usize,
+  },
+  Output {
+    text: &'a str,
+  },
+  Result {
+    id: usize,
+    rss: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<&'a BenchStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<String>,
+  },
+  UncaughtError {
+    origin: &'a str,
+    message: String,
+  },
+  End {
+    total: usize,
+    failed: usize,
+    rss: usize,
+  },
+}
+
+/// Streams every bench event as a line of JSON to a file, so that results
+/// can be archived and processed without parsing the console output.
+///
+/// Warmup runs are not reported.
+pub struct JsonlReporter {
+  writer: BufWriter<File>,
+}
+
+impl JsonlReporter {
+  pub fn new(output_path: &str) -> Result<Self, AnyError> {
+    let file = create_file(Path::new(output_path)).with_context(|| {
+      format!("Failed to create bench output file {output_path}")
+    })?;
+    Ok(Self {
+      writer: BufWriter::new(file),
+    })
+  }
+
+  fn write_event(&mut self, event: &JsonlBenchEvent) {
+    // flush every event so that the file is usable even if the process
+    // is killed midway
+    let result = serde_json::to_writer(&mut self.writer, event)
+      .map_err(std::io::Error::from)
+      .and_then(|_| self.writer.write_all(b"\n"))
+      .and_then(|_| self.writer.flush());
+    if let Err(err) = result {
+      log::warn!("Failed writing bench output file: {err}");
+    }
+  }
+}
+
+impl BenchReporter for JsonlReporter {
+  fn report_group_summary(&mut self) {}
+
+  fn report_plan(&mut self, plan: &BenchPlan) {
+    self.write_event(&JsonlBenchEvent::Plan {
+      origin: &plan.origin,
+      total: plan.total,
+      used_only: plan.used_only,
+    });
+  }
+
+  fn report_end(&mut self, report: &BenchReport) {
+    self.write_event(&JsonlBenchEvent::End {
+      total: report.total,
+      failed: report.failed,
+      rss: rss(),
+    });
+  }
+
+  fn report_register(&mut self, desc: &BenchDescription) {
+    if desc.warmup {
+      return;
+    }
+    self.write_event(&JsonlBenchEvent::Register {
+      id: desc.id,
+      name: &desc.name,
+      origin: &desc.origin,
+      group: desc.group.as_deref(),
+      baseline: desc.baseline,
+    });
+  }
+
+  fn report_wait(&mut self, desc: &BenchDescription) {
+    if desc.warmup {
+      return;
+    }
+    self.write_event(&JsonlBenchEvent::Wait { id: desc.id });
+  }
+
+  fn report_output(&mut self, output: &str) {
+    self.write_event(&JsonlBenchEvent::Output { text: output });
+  }
+
+  fn report_result(&mut self, desc: &BenchDescription, result: &BenchResult) {
+    if desc.warmup {
+      return;
+    }
+    let (stats, failure) = match result {
+      BenchResult::Ok(stats) => (Some(stats), None),
+      BenchResult::Failed(error) => (
+        None,
+        Some(strip_ansi_codes(&format_test_error(error)).into_owned()),
+      ),
+    };
+    self.write_event(&JsonlBenchEvent::Result {
+      id: desc.id,
+      rss: rss(),
+      stats,
+      failure,
+    });
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.write_event(&JsonlBenchEvent::UncaughtError {
+      origin,
+      message: strip_ansi_codes(&format_test_error(&error)).into_owned(),
+    });
+  }
+}
+
 pub struct ConsoleReporter {
   name: String,
   show_output: bool,
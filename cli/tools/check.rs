@@ -124,8 +124,45 @@ impl TypeChecker {
 
     let type_check_mode = options.type_check_mode;
     let ts_config = ts_config_result.ts_config;
-    let maybe_check_hash = match self.npm_resolver.check_state_hash() {
-      Some(npm_check_hash) => {
+    let cache = TypeCheckCache::new(self.caches.type_checking_cache_db());
+    let mut maybe_check_hash = None;
+    let mut root_check_hashes = Vec::new();
+    if let Some(npm_check_hash) = self.npm_resolver.check_state_hash() {
+      if self.cli_options.maybe_workspace_config().is_some()
+        && graph.roots.len() > 1
+      {
+        // in a workspace the roots are usually the entrypoints of different
+        // members, so track each of them separately in order to only check
+        // the members affected by a change
+        for root in &graph.roots {
+          let segment = graph.segment(std::slice::from_ref(root));
+          let hash = match get_check_hash(
+            &segment,
+            npm_check_hash,
+            type_check_mode,
+            &ts_config,
+          ) {
+            CheckHashResult::NoFiles => continue,
+            CheckHashResult::Hash(hash) => hash,
+          };
+          if !options.reload && cache.has_check_hash(hash) {
+            log::debug!("Already type checked {}.", root);
+            continue;
+          }
+          root_check_hashes.push(RootCheckHash {
+            root: root.clone(),
+            hash,
+            specifiers: segment
+              .specifiers()
+              .map(|(specifier, _)| specifier.to_string())
+              .collect(),
+          });
+        }
+        if root_check_hashes.is_empty() {
+          log::debug!("Already type checked.");
+          return Ok((graph.into(), Default::default()));
+        }
+      } else {
         match get_check_hash(
           &graph,
           npm_check_hash,
@@ -135,14 +172,12 @@ impl TypeChecker {
           CheckHashResult::NoFiles => {
             return Ok((graph.into(), Default::default()))
           }
-          CheckHashResult::Hash(hash) => Some(hash),
+          CheckHashResult::Hash(hash) => maybe_check_hash = Some(hash),
         }
       }
-      None => None, // we can't determine a check hash
-    };
+    } // otherwise we can't determine a check hash
 
     // do not type check if we know this is type checked
-    let cache = TypeCheckCache::new(self.caches.type_checking_cache_db());
     if !options.reload {
       if let Some(check_hash) = maybe_check_hash {
         if cache.has_check_hash(check_hash) {
@@ -152,7 +187,30 @@ impl TypeChecker {
       }
     }
 
-    for root in &graph.roots {
+    // add fast check to the graph before getting the roots
+    if options.build_fast_check_graph {
+      self.module_graph_builder.build_fast_check_graph(
+        &mut graph,
+        BuildFastCheckGraphOptions {
+          workspace_fast_check: false,
+        },
+      )?;
+    }
+
+    let graph = Arc::new(graph);
+    let check_graph = if root_check_hashes.is_empty()
+      || root_check_hashes.len() == graph.roots.len()
+    {
+      graph.clone()
+    } else {
+      let roots = root_check_hashes
+        .iter()
+        .map(|r| r.root.clone())
+        .collect::<Vec<_>>();
+      Arc::new(graph.segment(&roots))
+    };
+
+    for root in &check_graph.roots {
       let root_str = root.as_str();
       log::info!("{} {}", colors::green("Check"), root_str);
     }
@@ -164,7 +222,7 @@ impl TypeChecker {
     let maybe_tsbuildinfo = if options.reload {
       None
     } else {
-      cache.get_tsbuildinfo(&graph.roots[0])
+      cache.get_tsbuildinfo(&check_graph.roots[0])
     };
     // to make tsc build info work, we need to consistently hash modules, so that
     // tsc can better determine if an emit is still valid or not, so we provide
@@ -174,22 +232,11 @@ impl TypeChecker {
       .write_str(version::deno())
       .finish();
 
-    // add fast check to the graph before getting the roots
-    if options.build_fast_check_graph {
-      self.module_graph_builder.build_fast_check_graph(
-        &mut graph,
-        BuildFastCheckGraphOptions {
-          workspace_fast_check: false,
-        },
-      )?;
-    }
-
-    let root_names = get_tsc_roots(&graph, check_js);
-    let graph = Arc::new(graph);
+    let root_names = get_tsc_roots(&check_graph, check_js);
     let response = tsc::exec(tsc::Request {
       config: ts_config,
       debug: self.cli_options.log_level() == Some(log::Level::Debug),
-      graph: graph.clone(),
+      graph: check_graph.clone(),
       hash_data,
       maybe_npm: Some(tsc::RequestNpmState {
         node_resolver: self.node_resolver.clone(),
@@ -208,10 +255,10 @@ impl TypeChecker {
       }
     });
 
-    diagnostics.apply_fast_check_source_maps(&graph);
+    diagnostics.apply_fast_check_source_maps(&check_graph);
 
     if let Some(tsbuildinfo) = response.maybe_tsbuildinfo {
-      cache.set_tsbuildinfo(&graph.roots[0], &tsbuildinfo);
+      cache.set_tsbuildinfo(&check_graph.roots[0], &tsbuildinfo);
     }
 
     if diagnostics.is_empty() {
@@ -219,6 +266,16 @@ impl TypeChecker {
         cache.add_check_hash(check_hash);
       }
     }
+    // a root is type checked when none of the diagnostics are in its modules
+    for root_check_hash in &root_check_hashes {
+      let has_diagnostic = diagnostics.iter().any(|d| match &d.file_name {
+        Some(file_name) => root_check_hash.specifiers.contains(file_name),
+        None => true,
+      });
+      if !has_diagnostic {
+        cache.add_check_hash(root_check_hash.hash);
+      }
+    }
 
     log::debug!("{}", response.stats);
 
@@ -245,6 +302,13 @@ enum CheckHashResult {
   NoFiles,
 }
 
+/// The check hash of the part of the graph reachable from a single root.
+struct RootCheckHash {
+  root: ModuleSpecifier,
+  hash: u64,
+  specifiers: HashSet<String>,
+}
+
 /// Gets a hash of the inputs for type checking. This can then
 /// be used to tell
 fn get_check_hash(
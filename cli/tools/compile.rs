@@ -245,6 +245,7 @@ mod test {
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         no_terminal: false,
         include: vec![],
+        permission_override: Default::default(),
       },
       &std::env::current_dir().unwrap(),
     )
@@ -267,6 +268,7 @@ mod test {
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
         no_terminal: false,
+        permission_override: Default::default(),
       },
       &std::env::current_dir().unwrap(),
     )
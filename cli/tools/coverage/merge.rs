@@ -11,11 +11,13 @@ use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::iter::Peekable;
 
+#[cfg(test)]
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct ProcessCoverage {
   pub result: Vec<cdp::ScriptCoverage>,
 }
 
+#[cfg(test)]
 pub fn merge_processes(
   mut processes: Vec<ProcessCoverage>,
 ) -> Option<ProcessCoverage> {
@@ -7,8 +7,10 @@ use crate::args::Flags;
 use crate::cdp;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
+use crate::npm::InnerCliNpmResolverRef;
 use crate::tools::fmt::format_json;
 use crate::tools::test::is_supported_test_path;
+use crate::util::checksum;
 use crate::util::fs::FileCollector;
 use crate::util::text_encoding::source_map_from_code;
 
@@ -27,6 +29,9 @@ use deno_core::url::Url;
 use deno_core::LocalInspectorSession;
 use deno_core::ModuleCodeString;
 use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
@@ -40,7 +45,42 @@ mod merge;
 mod range_tree;
 mod reporter;
 mod util;
-use merge::ProcessCoverage;
+
+/// A coverage profile, as written to the coverage directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoverageProfile {
+  #[serde(flatten)]
+  coverage: cdp::ScriptCoverage,
+  /// The hash of the source of the script, which tells apart the versions of
+  /// a module that changed between runs. Older profiles don't have it.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  source_hash: Option<String>,
+}
+
+fn source_hash(source: &str) -> String {
+  checksum::gen(&[source.as_bytes()])
+}
+
+fn write_profile(
+  dir: &Path,
+  profile: &CoverageProfile,
+) -> Result<(), AnyError> {
+  let filename = format!("{}.json", Uuid::new_v4());
+  let filepath = dir.join(filename);
+
+  let mut out = BufWriter::new(File::create(&filepath)?);
+  let coverage = serde_json::to_string(profile)?;
+  let formatted_coverage =
+    format_json(&filepath, &coverage, &Default::default())
+      .ok()
+      .flatten()
+      .unwrap_or(coverage);
+
+  out.write_all(formatted_coverage.as_bytes())?;
+  out.flush()?;
+  Ok(())
+}
 
 pub struct CoverageCollector {
   pub dir: PathBuf,
@@ -76,19 +116,20 @@ impl crate::worker::CoverageCollector for CoverageCollector {
         continue;
       }
 
-      let filename = format!("{}.json", Uuid::new_v4());
-      let filepath = self.dir.join(filename);
-
-      let mut out = BufWriter::new(File::create(&filepath)?);
-      let coverage = serde_json::to_string(&script_coverage)?;
-      let formatted_coverage =
-        format_json(&filepath, &coverage, &Default::default())
-          .ok()
-          .flatten()
-          .unwrap_or(coverage);
-
-      out.write_all(formatted_coverage.as_bytes())?;
-      out.flush()?;
+      let source_hash = self
+        .get_script_source(cdp::GetScriptSourceArgs {
+          script_id: script_coverage.script_id.clone(),
+        })
+        .await
+        .ok()
+        .map(|response| source_hash(&response.script_source));
+      write_profile(
+        &self.dir,
+        &CoverageProfile {
+          coverage: script_coverage,
+          source_hash,
+        },
+      )?;
     }
 
     self.disable_debugger().await?;
@@ -149,6 +190,20 @@ impl CoverageCollector {
     Ok(return_object)
   }
 
+  async fn get_script_source(
+    &mut self,
+    parameters: cdp::GetScriptSourceArgs,
+  ) -> Result<cdp::GetScriptSourceResponse, AnyError> {
+    let return_value = self
+      .session
+      .post_message("Debugger.getScriptSource", Some(parameters))
+      .await?;
+
+    let return_object = serde_json::from_value(return_value)?;
+
+    Ok(return_object)
+  }
+
   async fn take_precise_coverage(
     &mut self,
   ) -> Result<cdp::TakePreciseCoverageResponse, AnyError> {
@@ -381,8 +436,8 @@ fn collect_coverages(
   cli_options: &CliOptions,
   files: FileFlags,
   initial_cwd: &Path,
-) -> Result<Vec<cdp::ScriptCoverage>, AnyError> {
-  let mut coverages: Vec<cdp::ScriptCoverage> = Vec::new();
+) -> Result<Vec<CoverageProfile>, AnyError> {
+  let mut coverages: Vec<CoverageProfile> = Vec::new();
   let file_patterns = FilePatterns {
     base: initial_cwd.to_path_buf(),
     include: Some({
@@ -419,17 +474,96 @@ fn collect_coverages(
     coverages.push(new_coverage);
   }
 
-  coverages.sort_by_key(|k| k.url.clone());
+  coverages.sort_by_key(|k| k.coverage.url.clone());
 
   Ok(coverages)
 }
 
+/// Merges the profiles of the same version of each module, which can come
+/// from multiple runs or shards. The result is sorted by url.
+fn merge_profiles(profiles: Vec<CoverageProfile>) -> Vec<CoverageProfile> {
+  let mut versions: BTreeMap<
+    (String, Option<String>),
+    Vec<cdp::ScriptCoverage>,
+  > = BTreeMap::new();
+  for profile in profiles {
+    versions
+      .entry((profile.coverage.url.clone(), profile.source_hash))
+      .or_default()
+      .push(profile.coverage);
+  }
+
+  versions
+    .into_iter()
+    .enumerate()
+    .map(|(script_id, ((_, source_hash), scripts))| {
+      let mut coverage = merge::merge_scripts(scripts).unwrap();
+      coverage.script_id = script_id.to_string();
+      CoverageProfile {
+        coverage,
+        source_hash,
+      }
+    })
+    .collect()
+}
+
+/// Picks the coverage of the version of a module that matches its current
+/// source. When none of them matches, like for the modules whose source is
+/// wrapped at runtime, the coverage of all the versions is merged.
+fn select_version(
+  mut versions: Vec<CoverageProfile>,
+  runtime_code: &str,
+) -> cdp::ScriptCoverage {
+  if versions.len() == 1 {
+    return versions.pop().unwrap().coverage;
+  }
+  let source_hash = source_hash(runtime_code);
+  match versions
+    .iter()
+    .position(|profile| profile.source_hash.as_ref() == Some(&source_hash))
+  {
+    Some(index) => versions.swap_remove(index).coverage,
+    None => merge::merge_scripts(
+      versions
+        .into_iter()
+        .map(|profile| profile.coverage)
+        .collect(),
+    )
+    .unwrap(),
+  }
+}
+
+/// The name of the npm package of a module, if it is in one.
+fn npm_package_name(
+  npm_resolver: &dyn CliNpmResolver,
+  specifier: &ModuleSpecifier,
+) -> Option<String> {
+  match npm_resolver.as_inner() {
+    InnerCliNpmResolverRef::Managed(npm_resolver) => npm_resolver
+      .resolve_pkg_id_from_specifier(specifier)
+      .ok()
+      .flatten()
+      .map(|id| id.nv.name),
+    InnerCliNpmResolverRef::Byonm(_) => {
+      let (_, path) = specifier.path().rsplit_once("/node_modules/")?;
+      let mut parts = path.split('/');
+      let name = parts.next()?;
+      if name.starts_with('@') {
+        Some(format!("{}/{}", name, parts.next()?))
+      } else {
+        Some(name.to_string())
+      }
+    }
+  }
+}
+
 fn filter_coverages(
-  coverages: Vec<cdp::ScriptCoverage>,
+  coverages: Vec<CoverageProfile>,
   include: Vec<String>,
   exclude: Vec<String>,
+  npm_packages: &[String],
   npm_resolver: &dyn CliNpmResolver,
-) -> Vec<cdp::ScriptCoverage> {
+) -> Vec<CoverageProfile> {
   let include: Vec<Regex> =
     include.iter().map(|e| Regex::new(e).unwrap()).collect();
 
@@ -439,22 +573,29 @@ fn filter_coverages(
   coverages
     .into_iter()
     .filter(|e| {
-      let is_internal = e.url.starts_with("ext:")
-        || e.url.ends_with("__anonymous__")
-        || e.url.ends_with("$deno$test.js")
-        || e.url.ends_with(".snap")
-        || is_supported_test_path(Path::new(e.url.as_str()))
-        || Url::parse(&e.url)
-          .ok()
-          .map(|url| npm_resolver.in_npm_package(&url))
-          .unwrap_or(false);
-
-      let is_included = include.iter().any(|p| p.is_match(&e.url));
-      let is_excluded = exclude.iter().any(|p| p.is_match(&e.url));
+      let url = &e.coverage.url;
+      let npm_package = Url::parse(url)
+        .ok()
+        .filter(|url| npm_resolver.in_npm_package(url))
+        .map(|url| npm_package_name(npm_resolver, &url));
+      let is_internal = url.starts_with("ext:")
+        || url.ends_with("__anonymous__")
+        || url.ends_with("$deno$test.js")
+        || url.ends_with(".snap")
+        || is_supported_test_path(Path::new(url.as_str()))
+        || match npm_package {
+          // the modules of npm packages are only included when their
+          // package is requested, and then only those are included
+          Some(name) => !name.is_some_and(|name| npm_packages.contains(&name)),
+          None => !npm_packages.is_empty(),
+        };
+
+      let is_included = include.iter().any(|p| p.is_match(url));
+      let is_excluded = exclude.iter().any(|p| p.is_match(url));
 
       (include.is_empty() || is_included) && !is_excluded && !is_internal
     })
-    .collect::<Vec<cdp::ScriptCoverage>>()
+    .collect::<Vec<CoverageProfile>>()
 }
 
 pub async fn cover_files(
@@ -489,23 +630,37 @@ pub async fn cover_files(
     script_coverages,
     coverage_flags.include,
     coverage_flags.exclude,
+    &coverage_flags.npm_packages,
     npm_resolver.as_ref(),
   );
   if script_coverages.is_empty() {
     return Err(generic_error("No covered files included in the report"));
   }
 
-  let proc_coverages: Vec<_> = script_coverages
-    .into_iter()
-    .map(|cov| ProcessCoverage { result: vec![cov] })
-    .collect();
+  let script_coverages = merge_profiles(script_coverages);
 
-  let script_coverages = if let Some(c) = merge::merge_processes(proc_coverages)
-  {
-    c.result
-  } else {
-    vec![]
-  };
+  if let Some(merge_dir) = coverage_flags.merge {
+    let merge_dir = cli_options.initial_cwd().join(merge_dir);
+    fs::create_dir_all(&merge_dir)?;
+    for profile in &script_coverages {
+      write_profile(&merge_dir, profile)?;
+    }
+    log::info!(
+      "Merged the coverage into {} profiles in {}",
+      script_coverages.len(),
+      merge_dir.display()
+    );
+    return Ok(());
+  }
+
+  let mut url_to_versions: BTreeMap<String, Vec<CoverageProfile>> =
+    BTreeMap::new();
+  for profile in script_coverages {
+    url_to_versions
+      .entry(profile.coverage.url.clone())
+      .or_default()
+      .push(profile);
+  }
 
   let mut reporter = reporter::create(coverage_flags.r#type);
 
@@ -519,11 +674,9 @@ pub async fn cover_files(
     None => None,
   };
 
-  for script_coverage in script_coverages {
-    let module_specifier = deno_core::resolve_url_or_path(
-      &script_coverage.url,
-      cli_options.initial_cwd(),
-    )?;
+  for (url, versions) in url_to_versions {
+    let module_specifier =
+      deno_core::resolve_url_or_path(&url, cli_options.initial_cwd())?;
 
     let maybe_file = if module_specifier.scheme() == "file" {
       file_fetcher.get_source(&module_specifier)
@@ -574,6 +727,7 @@ pub async fn cover_files(
       .map(|c| c.into())
       .unwrap_or_else(|| original_source.clone().into());
 
+    let script_coverage = select_version(versions, runtime_code.as_str());
     let source_map = source_map_from_code(&runtime_code);
     let coverage_report = generate_coverage_report(
       &script_coverage,
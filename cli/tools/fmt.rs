@@ -9,6 +9,7 @@
 
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::args::FmtDiffFormat;
 use crate::args::FmtFlags;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
@@ -16,6 +17,8 @@ use crate::args::ProseWrap;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::util::diff::diff;
+use crate::util::diff::line_edits;
+use crate::util::diff::unified_diff;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
@@ -29,10 +32,13 @@ use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use deno_core::unsync::spawn_blocking;
 use log::debug;
 use log::info;
 use log::warn;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
@@ -143,8 +149,15 @@ async fn format_files(
     &paths,
   ));
   if check {
-    check_source_files(paths, fmt_options.options, incremental_cache.clone())
-      .await?;
+    let initial_cwd = factory.cli_options().initial_cwd().to_path_buf();
+    check_source_files(
+      paths,
+      fmt_options.options,
+      fmt_options.diff_format,
+      initial_cwd,
+      incremental_cache.clone(),
+    )
+    .await?;
   } else {
     format_source_files(paths, fmt_options.options, incremental_cache.clone())
       .await?;
@@ -204,16 +217,20 @@ fn format_markdown(
         if matches!(extension, "json" | "jsonc") {
           let mut json_config = get_resolved_json_config(fmt_options);
           json_config.line_width = line_width;
-          dprint_plugin_json::format_text(&fake_filename, text, &json_config)
+          format_with_ignored_ranges(text, |text| {
+            dprint_plugin_json::format_text(&fake_filename, text, &json_config)
+          })
         } else {
           let mut codeblock_config =
             get_resolved_typescript_config(fmt_options);
           codeblock_config.line_width = line_width;
-          dprint_plugin_typescript::format_text(
-            &fake_filename,
-            text,
-            &codeblock_config,
-          )
+          format_with_ignored_ranges(text, |text| {
+            dprint_plugin_typescript::format_text(
+              &fake_filename,
+              text,
+              &codeblock_config,
+            )
+          })
         }
       } else {
         Ok(None)
@@ -235,18 +252,29 @@ pub fn format_json(
 }
 
 /// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, or IPYNB file.
+///
+/// The options can be overridden by a `deno-fmt-options` directive in the
+/// leading comments of the file, and the lines of JS/TS and JSON files
+/// between `deno-fmt-ignore-start` and `deno-fmt-ignore-end` comments are
+/// left as they are, like markdown does natively.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
 ) -> Result<Option<String>, AnyError> {
   let ext = get_extension(file_path).unwrap_or_default();
+  let is_markdown = matches!(
+    ext.as_str(),
+    "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown"
+  );
+  let fmt_options =
+    &resolve_file_fmt_options(file_text, is_markdown, fmt_options)?;
 
   match ext.as_str() {
-    "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown" => {
-      format_markdown(file_text, fmt_options)
-    }
-    "json" | "jsonc" => format_json(file_path, file_text, fmt_options),
+    _ if is_markdown => format_markdown(file_text, fmt_options),
+    "json" | "jsonc" => format_with_ignored_ranges(file_text, |file_text| {
+      format_json(file_path, file_text, fmt_options)
+    }),
     "ipynb" => dprint_plugin_jupyter::format_text(
       file_text,
       |file_path: &Path, file_text: String| {
@@ -255,7 +283,9 @@ pub fn format_file(
     ),
     _ => {
       let config = get_resolved_typescript_config(fmt_options);
-      dprint_plugin_typescript::format_text(file_path, file_text, &config)
+      format_with_ignored_ranges(file_text, |file_text| {
+        dprint_plugin_typescript::format_text(file_path, file_text, &config)
+      })
     }
   }
 }
@@ -264,19 +294,205 @@ pub fn format_parsed_source(
   parsed_source: &ParsedSource,
   fmt_options: &FmtOptionsConfig,
 ) -> Result<Option<String>, AnyError> {
+  let file_text = parsed_source.text_info().text_str();
+  if file_text.contains(OPTIONS_DIRECTIVE)
+    || file_text.contains(IGNORE_START_DIRECTIVE)
+  {
+    // the directives are applied to the text, so it has to be reparsed
+    let file_path = PathBuf::from(format!(
+      "deno_fmt_stdin{}",
+      parsed_source.media_type().as_ts_extension()
+    ));
+    return format_file(&file_path, file_text, fmt_options);
+  }
+
   dprint_plugin_typescript::format_parsed_source(
     parsed_source,
     &get_resolved_typescript_config(fmt_options),
   )
 }
 
+const OPTIONS_DIRECTIVE: &str = "deno-fmt-options";
+const IGNORE_START_DIRECTIVE: &str = "deno-fmt-ignore-start";
+const IGNORE_END_DIRECTIVE: &str = "deno-fmt-ignore-end";
+/// Stands for an ignored range in the text given to the formatter. It must
+/// not contain `deno-fmt-ignore` as a word, which would make the formatter
+/// ignore the following node.
+const IGNORED_RANGE_PLACEHOLDER: &str = "deno-fmt-ignored-range-";
+
+/// The text of a line made of a single line comment, or of a single HTML
+/// comment in markdown.
+fn line_comment_text(line: &str, is_markdown: bool) -> Option<&str> {
+  let line = line.trim();
+  let text = if is_markdown {
+    line.strip_prefix("<!--")?.strip_suffix("-->")?
+  } else {
+    line.strip_prefix("//")?
+  };
+  Some(text.trim())
+}
+
+/// The arguments of `directive` when the comment text is this directive,
+/// optionally followed by some arguments or an explanation.
+fn directive_args<'a>(
+  comment_text: &'a str,
+  directive: &str,
+) -> Option<&'a str> {
+  let rest = comment_text.strip_prefix(directive)?;
+  if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+    Some(rest.trim())
+  } else {
+    None
+  }
+}
+
+/// Applies the `deno-fmt-options` directive of the leading comments of a
+/// file, like `// deno-fmt-options lineWidth=120 semiColons=false`, to the
+/// options resolved from the config file and the flags.
+fn resolve_file_fmt_options<'a>(
+  file_text: &str,
+  is_markdown: bool,
+  fmt_options: &'a FmtOptionsConfig,
+) -> Result<Cow<'a, FmtOptionsConfig>, AnyError> {
+  let mut lines = file_text.lines().peekable();
+  if lines.peek().is_some_and(|line| line.starts_with("#!")) {
+    lines.next();
+  }
+  for line in lines {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let Some(comment_text) = line_comment_text(line, is_markdown) else {
+      break;
+    };
+    if let Some(args) = directive_args(comment_text, OPTIONS_DIRECTIVE) {
+      let overrides = parse_options_directive(args).map_err(|err| {
+        generic_error(format!("Invalid {OPTIONS_DIRECTIVE} directive: {err:#}"))
+      })?;
+      return Ok(Cow::Owned(FmtOptionsConfig {
+        use_tabs: overrides.use_tabs.or(fmt_options.use_tabs),
+        line_width: overrides.line_width.or(fmt_options.line_width),
+        indent_width: overrides.indent_width.or(fmt_options.indent_width),
+        single_quote: overrides.single_quote.or(fmt_options.single_quote),
+        prose_wrap: overrides.prose_wrap.or(fmt_options.prose_wrap),
+        semi_colons: overrides.semi_colons.or(fmt_options.semi_colons),
+      }));
+    }
+  }
+  Ok(Cow::Borrowed(fmt_options))
+}
+
+/// Parses the `key=value` arguments of a `deno-fmt-options` directive, with
+/// the keys and the values of the `fmt` options of the config file.
+fn parse_options_directive(args: &str) -> Result<FmtOptionsConfig, AnyError> {
+  let mut options = serde_json::Map::new();
+  for arg in args.split_whitespace() {
+    let Some((key, value)) = arg.split_once('=') else {
+      bail!("expected an option like \"lineWidth=120\", found \"{arg}\"");
+    };
+    let value = serde_json::from_str(value)
+      .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    options.insert(key.to_string(), value);
+  }
+  Ok(serde_json::from_value(serde_json::Value::Object(options))?)
+}
+
+/// Formats a JS/TS or JSON text with `format`, keeping its ignored ranges as
+/// they are. The formatters don't support these ranges, so each one is
+/// replaced by a placeholder comment before formatting and restored after.
+/// An unterminated range goes on until the end of the text.
+fn format_with_ignored_ranges(
+  file_text: &str,
+  format: impl FnOnce(&str) -> Result<Option<String>, AnyError>,
+) -> Result<Option<String>, AnyError> {
+  if !file_text.contains(IGNORE_START_DIRECTIVE) {
+    return format(file_text);
+  }
+
+  let mut ranges = Vec::new();
+  let mut range_start = None;
+  let mut offset = 0;
+  for (index, line) in file_text.split_inclusive('\n').enumerate() {
+    let comment_text = line_comment_text(line, false);
+    let line_end = offset + line.len();
+    match range_start {
+      None => {
+        if comment_text.is_some_and(|comment_text| {
+          directive_args(comment_text, IGNORE_START_DIRECTIVE).is_some()
+        }) {
+          range_start = Some((offset, index + 1));
+        }
+      }
+      Some((start, line)) => {
+        if comment_text.is_some_and(|comment_text| {
+          directive_args(comment_text, IGNORE_END_DIRECTIVE).is_some()
+        }) {
+          ranges.push((start..line_end, line));
+          range_start = None;
+        }
+      }
+    }
+    offset = line_end;
+  }
+  if let Some((start, line)) = range_start {
+    ranges.push((start..file_text.len(), line));
+  }
+
+  let mut text = String::with_capacity(file_text.len());
+  let mut offset = 0;
+  for (index, (range, _)) in ranges.iter().enumerate() {
+    let range_text = &file_text[range.clone()];
+    let indent_len = range_text.len() - range_text.trim_start().len();
+    text.push_str(&file_text[offset..range.start + indent_len]);
+    text.push_str(&format!("// {IGNORED_RANGE_PLACEHOLDER}{index}"));
+    if range_text.ends_with('\n') {
+      text.push('\n');
+    }
+    offset = range.end;
+  }
+  text.push_str(&file_text[offset..]);
+
+  let Some(formatted_text) = format(&text)? else {
+    return Ok(None);
+  };
+  let mut restored_text = String::with_capacity(formatted_text.len());
+  let mut restored_count = 0;
+  for line in formatted_text.split_inclusive('\n') {
+    let is_placeholder = restored_count < ranges.len()
+      && line_comment_text(line, false)
+        .and_then(|comment_text| {
+          comment_text.strip_prefix(IGNORED_RANGE_PLACEHOLDER)
+        })
+        .is_some_and(|index| index == restored_count.to_string());
+    if is_placeholder {
+      let range_text = &file_text[ranges[restored_count].0.clone()];
+      restored_text.push_str(range_text);
+      if !range_text.ends_with('\n') && line.ends_with('\n') {
+        restored_text.push('\n');
+      }
+      restored_count += 1;
+    } else {
+      restored_text.push_str(line);
+    }
+  }
+  if let Some((_, line)) = ranges.get(restored_count) {
+    bail!(
+      "The {IGNORE_START_DIRECTIVE} range on line {line} could not be left as it is. Make sure it only spans whole statements or entries.",
+    );
+  }
+  Ok((restored_text != file_text).then_some(restored_text))
+}
+
 async fn check_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
+  diff_format: FmtDiffFormat,
+  initial_cwd: PathBuf,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let not_formatted_files_count = Arc::new(AtomicUsize::new(0));
   let checked_files_count = Arc::new(AtomicUsize::new(0));
+  let json_edits = Arc::new(Mutex::new(Vec::new()));
 
   // prevent threads outputting at the same time
   let output_lock = Arc::new(Mutex::new(0));
@@ -284,6 +500,7 @@ async fn check_source_files(
   run_parallelized(paths, {
     let not_formatted_files_count = not_formatted_files_count.clone();
     let checked_files_count = checked_files_count.clone();
+    let json_edits = json_edits.clone();
     move |file_path| {
       checked_files_count.fetch_add(1, Ordering::Relaxed);
       let file_text = read_file_contents(&file_path)?.text;
@@ -296,11 +513,43 @@ async fn check_source_files(
       match format_file(&file_path, &file_text, &fmt_options) {
         Ok(Some(formatted_text)) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
-          let _g = output_lock.lock();
-          let diff = diff(&file_text, &formatted_text);
-          info!("");
-          info!("{} {}:", colors::bold("from"), file_path.display());
-          info!("{}", diff);
+          let display_path = file_path
+            .strip_prefix(&initial_cwd)
+            .unwrap_or(&file_path)
+            .to_string_lossy()
+            .to_string();
+          match diff_format {
+            FmtDiffFormat::Pretty => {
+              let _g = output_lock.lock();
+              let diff = diff(&file_text, &formatted_text);
+              info!("");
+              info!("{} {}:", colors::bold("from"), file_path.display());
+              info!("{}", diff);
+            }
+            FmtDiffFormat::Patch => {
+              let patch =
+                unified_diff(&display_path, &file_text, &formatted_text);
+              let _g = output_lock.lock();
+              let mut stdout = stdout().lock();
+              stdout.write_all(patch.as_bytes())?;
+              stdout.flush()?;
+            }
+            FmtDiffFormat::Json => {
+              let edits = line_edits(&file_text, &formatted_text)
+                .into_iter()
+                .map(|edit| {
+                  json!({
+                    "file": display_path,
+                    "range": {
+                      "start": { "line": edit.start_line, "character": 0 },
+                      "end": { "line": edit.end_line, "character": 0 },
+                    },
+                    "replacement": edit.new_text,
+                  })
+                });
+              json_edits.lock().extend(edits);
+            }
+          }
         }
         Ok(None) => {
           // When checking formatting, only update the incremental cache when
@@ -335,6 +584,21 @@ async fn check_source_files(
   })
   .await?;
 
+  if diff_format == FmtDiffFormat::Json {
+    let mut json_edits = std::mem::take(&mut *json_edits.lock());
+    // files are checked in parallel, so sort for deterministic output
+    json_edits.sort_by(|a, b| {
+      a["file"].as_str().cmp(&b["file"].as_str()).then_with(|| {
+        a["range"]["start"]["line"]
+          .as_u64()
+          .cmp(&b["range"]["start"]["line"].as_u64())
+      })
+    });
+    let mut stdout = stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &json_edits)?;
+    writeln!(stdout)?;
+  }
+
   let not_formatted_files_count =
     not_formatted_files_count.load(Ordering::Relaxed);
   let checked_files_count = checked_files_count.load(Ordering::Relaxed);
@@ -819,4 +1083,159 @@ mod test {
       "console.log(\"there's\");\nconsole.log('hi');\nconsole.log('bye');\n",
     );
   }
+
+  #[test]
+  fn test_ignored_ranges() {
+    let file_text = concat!(
+      "const a  =  1;\n",
+      "function f() {\n",
+      "  // deno-fmt-ignore-start\n",
+      "  const matrix = [\n",
+      "    1, 0,\n",
+      "    0, 1,\n",
+      "  ];\n",
+      "  // deno-fmt-ignore-end\n",
+      "      return matrix;\n",
+      "}\n",
+    );
+    let formatted_text =
+      format_file(&PathBuf::from("mod.ts"), file_text, &Default::default())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+      formatted_text,
+      concat!(
+        "const a = 1;\n",
+        "function f() {\n",
+        "  // deno-fmt-ignore-start\n",
+        "  const matrix = [\n",
+        "    1, 0,\n",
+        "    0, 1,\n",
+        "  ];\n",
+        "  // deno-fmt-ignore-end\n",
+        "  return matrix;\n",
+        "}\n",
+      ),
+    );
+
+    // an unterminated range goes on until the end of the file
+    let file_text = "const a  =  1;\n// deno-fmt-ignore-start\nconst b  =  2;";
+    let formatted_text =
+      format_file(&PathBuf::from("mod.ts"), file_text, &Default::default())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+      formatted_text,
+      "const a = 1;\n// deno-fmt-ignore-start\nconst b  =  2;\n"
+    );
+  }
+
+  #[test]
+  fn test_ignored_ranges_json() {
+    let file_text = concat!(
+      "{\n",
+      "  \"a\":   1,\n",
+      "  // deno-fmt-ignore-start\n",
+      "  \"b\":   [1,   2],\n",
+      "  // deno-fmt-ignore-end\n",
+      "  \"c\":   3\n",
+      "}\n",
+    );
+    let formatted_text =
+      format_file(&PathBuf::from("data.jsonc"), file_text, &Default::default())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+      formatted_text,
+      concat!(
+        "{\n",
+        "  \"a\": 1,\n",
+        "  // deno-fmt-ignore-start\n",
+        "  \"b\":   [1,   2],\n",
+        "  // deno-fmt-ignore-end\n",
+        "  \"c\": 3\n",
+        "}\n",
+      ),
+    );
+  }
+
+  #[test]
+  fn test_ignored_ranges_formatted() {
+    let file_text = "// deno-fmt-ignore-start\nconst a  =  1;\n// deno-fmt-ignore-end\nconst b = 2;\n";
+    let result =
+      format_file(&PathBuf::from("mod.ts"), file_text, &Default::default())
+        .unwrap();
+    assert_eq!(result, None);
+  }
+
+  #[test]
+  fn test_options_directive() {
+    let file_text = concat!(
+      "// deno-fmt-options lineWidth=20 semiColons=false\n",
+      "const value = [\"aaaa\", \"bbbb\", \"cccc\"];\n",
+    );
+    let formatted_text = format_file(
+      &PathBuf::from("mod.ts"),
+      file_text,
+      &FmtOptionsConfig {
+        single_quote: Some(true),
+        ..Default::default()
+      },
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      formatted_text,
+      concat!(
+        "// deno-fmt-options lineWidth=20 semiColons=false\n",
+        "const value = [\n",
+        "  'aaaa',\n",
+        "  'bbbb',\n",
+        "  'cccc',\n",
+        "]\n",
+      ),
+    );
+
+    let file_text = "<!-- deno-fmt-options proseWrap=always lineWidth=10 -->\n\nsome words to wrap\n";
+    let formatted_text =
+      format_file(&PathBuf::from("README.md"), file_text, &Default::default())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+      formatted_text,
+      "<!-- deno-fmt-options proseWrap=always lineWidth=10 -->\n\nsome words\nto wrap\n"
+    );
+
+    // only the leading comments are read
+    let file_text = "const a = 1;\n// deno-fmt-options lineWidth=20\n";
+    let result =
+      format_file(&PathBuf::from("mod.ts"), file_text, &Default::default())
+        .unwrap();
+    assert_eq!(result, None);
+  }
+
+  #[test]
+  fn test_options_directive_invalid() {
+    let err = format_file(
+      &PathBuf::from("mod.ts"),
+      "// deno-fmt-options lineWidth=wide\nconst a = 1;\n",
+      &Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Invalid deno-fmt-options directive: invalid type: string \"wide\", expected u32"
+    );
+
+    let err = format_file(
+      &PathBuf::from("mod.ts"),
+      "// deno-fmt-options lineWidth\nconst a = 1;\n",
+      &Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Invalid deno-fmt-options directive: expected an option like \"lineWidth=120\", found \"lineWidth\""
+    );
+  }
 }
@@ -1,10 +1,13 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
 
+use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
@@ -21,13 +24,17 @@ use deno_graph::Resolution;
 use deno_npm::resolution::NpmResolutionSnapshot;
 use deno_npm::NpmPackageId;
 use deno_npm::NpmResolutionPackage;
+use deno_runtime::module_manifest::ModuleManifest;
+use deno_runtime::module_manifest::ModuleManifestEntry;
 use deno_semver::npm::NpmPackageNvReference;
 use deno_semver::npm::NpmPackageReqReference;
 use deno_semver::package::PackageNv;
 use deno_terminal::colors;
+use serde::Serialize;
 
 use crate::args::Flags;
 use crate::args::InfoFlags;
+use crate::cache::EmitCache;
 use crate::display;
 use crate::factory::CliFactory;
 use crate::graph_util::graph_lock_or_exit;
@@ -35,6 +42,11 @@ use crate::npm::CliNpmResolver;
 use crate::npm::ManagedCliNpmResolver;
 use crate::util::checksum;
 
+/// The version of the `deno info --json` output format, which is described
+/// by `cli/schemas/info.v1.json`. It's bumped on any breaking change to the
+/// output, while new fields may be added without bumping it.
+const INFO_JSON_VERSION: u32 = 1;
+
 pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   let factory = CliFactory::from_flags(flags)?;
   let cli_options = factory.cli_options();
@@ -77,9 +89,14 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
       lockfile.write().context("Failed writing lockfile.")?;
     }
 
-    if info_flags.json {
+    if info_flags.manifest {
+      let manifest = create_module_manifest(&graph, factory.emit_cache()?);
+      display::write_json_to_stdout(&manifest)?;
+    } else if info_flags.json {
       let mut json_graph = json!(graph);
       add_npm_packages_to_json(&mut json_graph, npm_resolver.as_ref());
+      add_sizes_to_json(&mut json_graph, &graph, npm_resolver.as_ref());
+      add_version_to_json(&mut json_graph);
       display::write_json_to_stdout(&json_graph)?;
     } else {
       let mut output = String::new();
@@ -97,6 +114,60 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   Ok(())
 }
 
+fn create_module_manifest(
+  graph: &ModuleGraph,
+  emit_cache: &EmitCache,
+) -> ModuleManifest {
+  let roots = graph.roots.iter().map(|root| graph.resolve(root)).collect();
+  let mut manifest = ModuleManifest::new(roots);
+  for module in graph.modules() {
+    let mut dependencies = BTreeSet::new();
+    let (source, media_type) = match module {
+      Module::Js(module) => {
+        for dependency in module.dependencies.values() {
+          dependencies.extend(dependency.get_code().map(|s| graph.resolve(s)));
+          dependencies.extend(dependency.get_type().map(|s| graph.resolve(s)));
+        }
+        if let Some(types_dependency) = &module.maybe_types_dependency {
+          dependencies.extend(
+            types_dependency
+              .dependency
+              .maybe_specifier()
+              .map(|s| graph.resolve(s)),
+          );
+        }
+        (&module.source, module.media_type)
+      }
+      Module::Json(module) => (&module.source, module.media_type),
+      // these are not loaded from source like the other modules
+      Module::Npm(_) | Module::Node(_) | Module::External(_) => continue,
+    };
+    let specifier = module.specifier();
+    let needs_emit = matches!(
+      media_type,
+      MediaType::TypeScript
+        | MediaType::Mts
+        | MediaType::Cts
+        | MediaType::Jsx
+        | MediaType::Tsx
+    );
+    manifest.modules.insert(
+      specifier.clone(),
+      ModuleManifestEntry {
+        hash: checksum::gen(&[source.as_bytes()]),
+        media_type: media_type.to_string(),
+        dependencies: dependencies.into_iter().collect(),
+        emit_path: if needs_emit {
+          emit_cache.get_emit_filepath(specifier)
+        } else {
+          None
+        },
+      },
+    );
+  }
+  manifest
+}
+
 #[allow(clippy::print_stdout)]
 fn print_cache_info(
   factory: &CliFactory,
@@ -271,6 +342,130 @@ fn add_npm_packages_to_json(
   json.insert("npmPackages".to_string(), json_packages.into());
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPackageSize {
+  kind: &'static str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  modules: Option<usize>,
+  size: u64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  emit_size: Option<u64>,
+}
+
+/// Adds the size of the cached emit of each module and the aggregate sizes
+/// of the npm packages and remote packages of the graph.
+fn add_sizes_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+  npm_resolver: &dyn CliNpmResolver,
+) {
+  let mut emit_sizes = HashMap::new();
+  let mut package_sizes = BTreeMap::new();
+  for module in graph.modules() {
+    let (specifier, size, maybe_cache_info) = match module {
+      Module::Js(module) => {
+        (&module.specifier, module.size(), &module.maybe_cache_info)
+      }
+      Module::Json(module) => {
+        (&module.specifier, module.size(), &module.maybe_cache_info)
+      }
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => continue,
+    };
+    let maybe_emit_size = maybe_cache_info
+      .as_ref()
+      .and_then(|info| info.emit.as_ref())
+      .and_then(|emit| std::fs::metadata(emit).ok())
+      .map(|metadata| metadata.len());
+    if let Some(emit_size) = maybe_emit_size {
+      emit_sizes.insert(specifier.as_str(), emit_size);
+    }
+    if matches!(specifier.scheme(), "http" | "https") {
+      let package = package_sizes
+        .entry(remote_package_scope(specifier))
+        .or_insert_with(|| JsonPackageSize {
+          kind: "remote",
+          ..Default::default()
+        });
+      *package.modules.get_or_insert(0) += 1;
+      package.size += size as u64;
+      if let Some(emit_size) = maybe_emit_size {
+        *package.emit_size.get_or_insert(0) += emit_size;
+      }
+    }
+  }
+
+  if let Some(npm_resolver) = npm_resolver.as_managed() {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = NpmInfo::build(graph, npm_resolver, &npm_snapshot);
+    for (id, size) in npm_info.package_sizes {
+      package_sizes.insert(
+        id.as_serialized(),
+        JsonPackageSize {
+          kind: "npm",
+          size,
+          ..Default::default()
+        },
+      );
+    }
+  }
+
+  let json = json.as_object_mut().unwrap();
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  for module in modules.into_iter().flatten() {
+    let Some(module) = module.as_object_mut() else {
+      continue;
+    };
+    let maybe_emit_size = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|specifier| emit_sizes.get(specifier));
+    if let Some(emit_size) = maybe_emit_size {
+      module.insert("emitSize".to_string(), (*emit_size).into());
+    }
+  }
+  json.insert("packageSizes".to_string(), json!(package_sizes));
+}
+
+/// Returns the URL shared by the remote modules of a package, which is the
+/// URL up to the first path segment with a version (ex.
+/// `https://deno.land/std@0.200.0/`), the package version folder for JSR or
+/// the origin otherwise.
+fn remote_package_scope(specifier: &ModuleSpecifier) -> String {
+  let segments = specifier
+    .path_segments()
+    .map(|segments| segments.collect::<Vec<_>>())
+    .unwrap_or_default();
+  // the last segment is the file name
+  let folders = &segments[..segments.len().saturating_sub(1)];
+  let len = if specifier.host_str() == Some("jsr.io")
+    && folders.first().is_some_and(|s| s.starts_with('@'))
+  {
+    // @scope/name/version
+    folders.len().min(3)
+  } else {
+    folders
+      .iter()
+      .position(|s| s.get(1..).is_some_and(|s| s.contains('@')))
+      .map(|i| i + 1)
+      .unwrap_or(0)
+  };
+  let mut scope = format!("{}/", specifier.origin().ascii_serialization());
+  for folder in &folders[..len] {
+    scope.push_str(folder);
+    scope.push('/');
+  }
+  scope
+}
+
+fn add_version_to_json(json: &mut serde_json::Value) {
+  let json = json.as_object_mut().unwrap();
+  let mut versioned = serde_json::Map::with_capacity(json.len() + 1);
+  versioned.insert("version".to_string(), INFO_JSON_VERSION.into());
+  versioned.append(json);
+  *json = versioned;
+}
+
 struct TreeNode {
   text: String,
   children: Vec<TreeNode>,
@@ -735,3 +930,43 @@ fn maybe_size_to_text(maybe_size: Option<u64>) -> String {
   ))
   .to_string()
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_remote_package_scope() {
+    let cases = [
+      (
+        "https://deno.land/std@0.200.0/path/mod.ts",
+        "https://deno.land/std@0.200.0/",
+      ),
+      (
+        "https://deno.land/x/oak@v12.6.1/mod.ts",
+        "https://deno.land/x/oak@v12.6.1/",
+      ),
+      (
+        "https://esm.sh/@scope/name@1.0.0/es2022/name.mjs",
+        "https://esm.sh/@scope/name@1.0.0/",
+      ),
+      (
+        "https://jsr.io/@std/path/0.225.0/join.ts",
+        "https://jsr.io/@std/path/0.225.0/",
+      ),
+      (
+        "https://jsr.io/@std/path/meta.json",
+        "https://jsr.io/@std/path/",
+      ),
+      (
+        "http://localhost:4545/subdir/mod.ts",
+        "http://localhost:4545/",
+      ),
+      ("https://example.com/mod.ts", "https://example.com/"),
+    ];
+    for (specifier, expected) in cases {
+      let specifier = ModuleSpecifier::parse(specifier).unwrap();
+      assert_eq!(remote_package_scope(&specifier), expected, "{specifier}");
+    }
+  }
+}
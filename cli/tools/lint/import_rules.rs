@@ -0,0 +1,751 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Lint rules that check the imports between modules using the module graph,
+//! rather than looking at a single file:
+//!
+//! - `no-restricted-imports` bans specifiers, such as `npm:lodash`.
+//! - `no-restricted-paths` bans imports from one directory into another.
+//! - `no-import-cycles` reports modules that end up importing themselves.
+//!
+//! The rules are enabled with `lint.rules.include` and configured in the
+//! `"importRules"` object of the config file.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
+
+use deno_ast::diagnostics::Diagnostic;
+use deno_ast::diagnostics::DiagnosticLevel;
+use deno_ast::diagnostics::DiagnosticLocation;
+use deno_ast::diagnostics::DiagnosticSnippet;
+use deno_ast::diagnostics::DiagnosticSnippetHighlight;
+use deno_ast::diagnostics::DiagnosticSnippetHighlightStyle;
+use deno_ast::diagnostics::DiagnosticSourcePos;
+use deno_ast::diagnostics::DiagnosticSourceRange;
+use deno_ast::ModuleSpecifier;
+use deno_ast::SourceRange;
+use deno_ast::SourceTextInfo;
+use deno_config::glob::PathOrPatternSet;
+use deno_config::ConfigFile;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_graph::source::LoadFuture;
+use deno_graph::source::LoadOptions;
+use deno_graph::source::LoadResponse;
+use deno_graph::source::Loader;
+use deno_graph::JsModule;
+use deno_graph::ModuleGraph;
+use serde::Deserialize;
+
+use crate::cache::FetchCacher;
+use crate::util::path::relative_specifier;
+
+pub const NO_RESTRICTED_IMPORTS_NAME: &str = "no-restricted-imports";
+pub const NO_RESTRICTED_PATHS_NAME: &str = "no-restricted-paths";
+pub const NO_IMPORT_CYCLES_NAME: &str = "no-import-cycles";
+
+pub const IMPORT_RULE_NAMES: [&str; 3] = [
+  NO_RESTRICTED_IMPORTS_NAME,
+  NO_RESTRICTED_PATHS_NAME,
+  NO_IMPORT_CYCLES_NAME,
+];
+
+/// The import rules that were included in the lint rules config.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EnabledImportRules {
+  pub no_restricted_imports: bool,
+  pub no_restricted_paths: bool,
+  pub no_import_cycles: bool,
+}
+
+impl EnabledImportRules {
+  pub fn from_included(include: &[String]) -> Self {
+    let is_included = |name: &str| include.iter().any(|i| i == name);
+    Self {
+      no_restricted_imports: is_included(NO_RESTRICTED_IMPORTS_NAME),
+      no_restricted_paths: is_included(NO_RESTRICTED_PATHS_NAME),
+      no_import_cycles: is_included(NO_IMPORT_CYCLES_NAME),
+    }
+  }
+
+  pub fn any(&self) -> bool {
+    self.no_restricted_imports
+      || self.no_restricted_paths
+      || self.no_import_cycles
+  }
+
+  pub fn names(&self) -> Vec<&'static str> {
+    [
+      (self.no_restricted_imports, NO_RESTRICTED_IMPORTS_NAME),
+      (self.no_restricted_paths, NO_RESTRICTED_PATHS_NAME),
+      (self.no_import_cycles, NO_IMPORT_CYCLES_NAME),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect()
+  }
+}
+
+/// `importRules` config representation for serde
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct SerializedImportRulesConfig {
+  restricted_imports: Vec<SerializedRestrictedImport>,
+  restricted_paths: Vec<SerializedRestrictedPath>,
+  import_cycles: SerializedImportCyclesConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SerializedRestrictedImport {
+  specifier: String,
+  #[serde(default)]
+  message: Option<String>,
+  /// Modules that may still use the specifier.
+  #[serde(default)]
+  allow: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SerializedRestrictedPath {
+  target: String,
+  from: String,
+  #[serde(default)]
+  message: Option<String>,
+  /// Modules in `from` that `target` may still import.
+  #[serde(default)]
+  allow: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SerializedImportCyclesConfig {
+  /// Modules that are left out when looking for cycles.
+  allow: Vec<String>,
+}
+
+#[derive(Debug)]
+struct RestrictedImport {
+  /// The specifier as written in the config file.
+  text: String,
+  /// The specifier to match, which is a file URL for relative paths.
+  specifier: String,
+  message: Option<String>,
+  allow: PathOrPatternSet,
+}
+
+#[derive(Debug)]
+struct RestrictedPath {
+  target_text: String,
+  from_text: String,
+  target: PathOrPatternSet,
+  from: PathOrPatternSet,
+  message: Option<String>,
+  allow: PathOrPatternSet,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportRulesConfig {
+  restricted_imports: Vec<RestrictedImport>,
+  restricted_paths: Vec<RestrictedPath>,
+  import_cycles_allow: PathOrPatternSet,
+}
+
+impl ImportRulesConfig {
+  /// Reads the `"importRules"` object of the config file.
+  ///
+  /// `deno_config` doesn't keep unknown properties, so this parses the
+  /// config file again.
+  pub fn from_config_file(config_file: &ConfigFile) -> Result<Self, AnyError> {
+    let Ok(config_path) = config_file.specifier.to_file_path() else {
+      return Ok(Self::default());
+    };
+    let text = std::fs::read_to_string(&config_path).with_context(|| {
+      format!("Failed reading '{}'.", config_path.display())
+    })?;
+    let value = jsonc_parser::parse_to_serde_value(&text, &Default::default())?
+      .and_then(|mut value| value.get_mut("importRules").map(|v| v.take()));
+    match value {
+      Some(value) => Self::from_value(value, config_path.parent().unwrap()),
+      None => Ok(Self::default()),
+    }
+  }
+
+  fn from_value(
+    value: serde_json::Value,
+    config_dir: &Path,
+  ) -> Result<Self, AnyError> {
+    let serialized: SerializedImportRulesConfig = serde_json::from_value(value)
+      .context("Failed to parse \"importRules\" configuration")?;
+    let path_set = |entries: &[String]| {
+      PathOrPatternSet::from_include_relative_path_or_patterns(
+        config_dir, entries,
+      )
+    };
+    let config_dir_url = ModuleSpecifier::from_directory_path(config_dir)
+      .map_err(|_| {
+        deno_core::anyhow::anyhow!(
+          "Invalid config directory '{}'.",
+          config_dir.display()
+        )
+      })?;
+
+    let mut restricted_imports =
+      Vec::with_capacity(serialized.restricted_imports.len());
+    for entry in serialized.restricted_imports {
+      let specifier = if entry.specifier.starts_with("./")
+        || entry.specifier.starts_with("../")
+      {
+        config_dir_url.join(&entry.specifier)?.to_string()
+      } else {
+        entry.specifier.clone()
+      };
+      restricted_imports.push(RestrictedImport {
+        allow: path_set(&entry.allow)?,
+        text: entry.specifier,
+        specifier,
+        message: entry.message,
+      });
+    }
+
+    let mut restricted_paths =
+      Vec::with_capacity(serialized.restricted_paths.len());
+    for entry in serialized.restricted_paths {
+      restricted_paths.push(RestrictedPath {
+        target: path_set(&[entry.target.clone()])?,
+        from: path_set(&[entry.from.clone()])?,
+        allow: path_set(&entry.allow)?,
+        target_text: entry.target,
+        from_text: entry.from,
+        message: entry.message,
+      });
+    }
+
+    Ok(Self {
+      restricted_imports,
+      restricted_paths,
+      import_cycles_allow: path_set(&serialized.import_cycles.allow)?,
+    })
+  }
+}
+
+/// Loads the local modules for the import rules and treats everything else
+/// as external, since the rules only look at the imports of local modules.
+pub struct LocalModuleLoader(pub FetchCacher);
+
+impl Loader for LocalModuleLoader {
+  fn load(
+    &self,
+    specifier: &ModuleSpecifier,
+    options: LoadOptions,
+  ) -> LoadFuture {
+    if specifier.scheme() == "file" {
+      self.0.load(specifier, options)
+    } else {
+      Box::pin(deno_core::futures::future::ready(Ok(Some(
+        LoadResponse::External {
+          specifier: specifier.clone(),
+        },
+      ))))
+    }
+  }
+}
+
+pub struct ImportRuleDiagnostic {
+  code: &'static str,
+  specifier: ModuleSpecifier,
+  text_info: SourceTextInfo,
+  range: SourceRange,
+  message: String,
+  hint: String,
+  info: Vec<Cow<'static, str>>,
+}
+
+impl ImportRuleDiagnostic {
+  pub fn specifier(&self) -> &ModuleSpecifier {
+    &self.specifier
+  }
+
+  pub fn range(&self) -> (&SourceTextInfo, SourceRange) {
+    (&self.text_info, self.range)
+  }
+}
+
+impl Diagnostic for ImportRuleDiagnostic {
+  fn level(&self) -> DiagnosticLevel {
+    DiagnosticLevel::Error
+  }
+
+  fn code(&self) -> Cow<'_, str> {
+    Cow::Borrowed(self.code)
+  }
+
+  fn message(&self) -> Cow<'_, str> {
+    Cow::Borrowed(&self.message)
+  }
+
+  fn location(&self) -> DiagnosticLocation {
+    DiagnosticLocation::ModulePosition {
+      specifier: Cow::Borrowed(&self.specifier),
+      text_info: Cow::Borrowed(&self.text_info),
+      source_pos: DiagnosticSourcePos::SourcePos(self.range.start),
+    }
+  }
+
+  fn snippet(&self) -> Option<DiagnosticSnippet<'_>> {
+    Some(DiagnosticSnippet {
+      source: Cow::Borrowed(&self.text_info),
+      highlight: DiagnosticSnippetHighlight {
+        range: DiagnosticSourceRange {
+          start: DiagnosticSourcePos::SourcePos(self.range.start),
+          end: DiagnosticSourcePos::SourcePos(self.range.end),
+        },
+        style: DiagnosticSnippetHighlightStyle::Error,
+        description: None,
+      },
+    })
+  }
+
+  fn hint(&self) -> Option<Cow<'_, str>> {
+    Some(Cow::Borrowed(&self.hint))
+  }
+
+  fn snippet_fixed(&self) -> Option<DiagnosticSnippet<'_>> {
+    None
+  }
+
+  fn info(&self) -> Cow<'_, [Cow<'_, str>]> {
+    Cow::Borrowed(&self.info)
+  }
+
+  fn docs_url(&self) -> Option<Cow<'_, str>> {
+    None
+  }
+}
+
+/// An import of a module being linted.
+struct ImportRef<'a> {
+  module: &'a JsModule,
+  specifier_text: &'a str,
+  range: &'a deno_graph::Range,
+  resolved: Vec<&'a ModuleSpecifier>,
+  is_runtime: bool,
+}
+
+impl<'a> ImportRef<'a> {
+  fn diagnostic(
+    &self,
+    code: &'static str,
+    message: String,
+    hint: String,
+    info: Vec<Cow<'static, str>>,
+  ) -> ImportRuleDiagnostic {
+    let text_info = SourceTextInfo::new(self.module.source.clone());
+    let range = SourceRange::new(
+      self.range.start.as_source_pos(&text_info),
+      self.range.end.as_source_pos(&text_info),
+    );
+    ImportRuleDiagnostic {
+      code,
+      specifier: self.module.specifier.clone(),
+      text_info,
+      range,
+      message,
+      hint,
+      info,
+    }
+  }
+}
+
+fn module_imports(module: &JsModule) -> impl Iterator<Item = ImportRef<'_>> {
+  module
+    .dependencies
+    .iter()
+    .flat_map(move |(specifier_text, dep)| {
+      let resolved = [
+        dep.maybe_code.maybe_specifier(),
+        dep.maybe_type.maybe_specifier(),
+      ]
+      .into_iter()
+      .flatten()
+      .collect::<Vec<_>>();
+      dep
+        .imports
+        .iter()
+        // skip imports without a location, like a jsxImportSource from the
+        // compiler options
+        .filter(|import| import.range.start != import.range.end)
+        .map(move |import| ImportRef {
+          module,
+          specifier_text,
+          range: &import.range,
+          resolved: resolved.clone(),
+          is_runtime: import.kind.is_runtime() && !import.is_dynamic,
+        })
+    })
+}
+
+/// If `specifier` is `pattern` or a path, subpath or version of it.
+fn matches_specifier(pattern: &str, specifier: &str) -> bool {
+  match specifier.strip_prefix(pattern) {
+    Some(rest) => {
+      rest.is_empty()
+        || pattern.ends_with('/')
+        || rest.starts_with('/')
+        || rest.starts_with('@')
+    }
+    None => false,
+  }
+}
+
+fn file_path(specifier: &ModuleSpecifier) -> Option<std::path::PathBuf> {
+  if specifier.scheme() == "file" {
+    specifier.to_file_path().ok()
+  } else {
+    None
+  }
+}
+
+/// Collects the import rule diagnostics for the modules in `specifiers`.
+pub fn collect_import_rule_diagnostics(
+  enabled: EnabledImportRules,
+  config: &ImportRulesConfig,
+  graph: &ModuleGraph,
+  specifiers: &HashSet<ModuleSpecifier>,
+) -> Vec<ImportRuleDiagnostic> {
+  let mut diagnostics = Vec::new();
+  let modules = graph
+    .modules()
+    .filter_map(|m| m.js())
+    .filter(|m| specifiers.contains(&m.specifier))
+    .collect::<Vec<_>>();
+
+  for module in &modules {
+    let Some(module_path) = file_path(&module.specifier) else {
+      continue;
+    };
+    for import in module_imports(module) {
+      if enabled.no_restricted_imports {
+        check_restricted_import(
+          config,
+          &module_path,
+          &import,
+          &mut diagnostics,
+        );
+      }
+      if enabled.no_restricted_paths {
+        check_restricted_path(config, &module_path, &import, &mut diagnostics);
+      }
+    }
+  }
+
+  if enabled.no_import_cycles {
+    check_import_cycles(config, graph, &modules, &mut diagnostics);
+  }
+
+  diagnostics.sort_by(|a, b| {
+    a.specifier
+      .cmp(&b.specifier)
+      .then(a.range.start.cmp(&b.range.start))
+      .then(a.code.cmp(b.code))
+  });
+  diagnostics
+}
+
+fn check_restricted_import(
+  config: &ImportRulesConfig,
+  module_path: &Path,
+  import: &ImportRef,
+  diagnostics: &mut Vec<ImportRuleDiagnostic>,
+) {
+  let restriction = config.restricted_imports.iter().find(|r| {
+    (matches_specifier(&r.specifier, import.specifier_text)
+      || import
+        .resolved
+        .iter()
+        .any(|s| matches_specifier(&r.specifier, s.as_str())))
+      && !r.allow.matches_path(module_path)
+  });
+  if let Some(restriction) = restriction {
+    diagnostics.push(
+      import.diagnostic(
+        NO_RESTRICTED_IMPORTS_NAME,
+        if restriction.text == import.specifier_text {
+          format!("'{}' is a restricted import", import.specifier_text)
+        } else {
+          format!(
+            "'{}' is a restricted import because it matches '{}'",
+            import.specifier_text, restriction.text
+          )
+        },
+        restriction.message.clone().unwrap_or_else(|| {
+          "remove the import, or add this module to the restriction's 'allow' list in the config file".to_string()
+        }),
+        Vec::new(),
+      ),
+    );
+  }
+}
+
+fn check_restricted_path(
+  config: &ImportRulesConfig,
+  module_path: &Path,
+  import: &ImportRef,
+  diagnostics: &mut Vec<ImportRuleDiagnostic>,
+) {
+  let Some(imported_path) = import.resolved.iter().find_map(|s| file_path(s))
+  else {
+    return;
+  };
+  let restriction = config.restricted_paths.iter().find(|r| {
+    r.target.matches_path(module_path)
+      && r.from.matches_path(&imported_path)
+      && !r.allow.matches_path(&imported_path)
+  });
+  if let Some(restriction) = restriction {
+    diagnostics.push(import.diagnostic(
+      NO_RESTRICTED_PATHS_NAME,
+      format!(
+        "'{}' imports a module from a restricted path",
+        import.specifier_text
+      ),
+      restriction.message.clone().unwrap_or_else(|| {
+        "move the shared code to a module both paths may import, or add the imported module to the restriction's 'allow' list in the config file".to_string()
+      }),
+      vec![Cow::Owned(format!(
+        "modules in '{}' may not import modules in '{}'",
+        restriction.target_text, restriction.from_text
+      ))],
+    ));
+  }
+}
+
+fn check_import_cycles(
+  config: &ImportRulesConfig,
+  graph: &ModuleGraph,
+  modules: &[&JsModule],
+  diagnostics: &mut Vec<ImportRuleDiagnostic>,
+) {
+  // the runtime imports between the local modules of the graph
+  let nodes = graph
+    .modules()
+    .filter_map(|m| m.js())
+    .filter(|m| {
+      file_path(&m.specifier)
+        .map(|path| !config.import_cycles_allow.matches_path(&path))
+        .unwrap_or(false)
+    })
+    .collect::<Vec<_>>();
+  let indexes = nodes
+    .iter()
+    .enumerate()
+    .map(|(i, m)| (&m.specifier, i))
+    .collect::<HashMap<_, _>>();
+  let edges = nodes
+    .iter()
+    .map(|module| {
+      module_imports(module)
+        .filter(|import| import.is_runtime)
+        .filter_map(|import| {
+          let resolved = graph.resolve(import.resolved.first()?);
+          let index = *indexes.get(&resolved)?;
+          Some((index, import))
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+  let components = strongly_connected_components(
+    &edges
+      .iter()
+      .map(|edges| edges.iter().map(|(i, _)| *i).collect::<Vec<_>>())
+      .collect::<Vec<_>>(),
+  );
+
+  for module in modules {
+    let Some(&index) = indexes.get(&module.specifier) else {
+      continue;
+    };
+    // report the first import that leads back to this module
+    let Some((next, import)) = edges[index]
+      .iter()
+      .find(|(next, _)| components[*next] == components[index])
+    else {
+      continue;
+    };
+    let cycle = shortest_path(&edges, &components, *next, index)
+      .into_iter()
+      .map(|i| {
+        relative_specifier(&module.specifier, &nodes[i].specifier)
+          .unwrap_or_else(|| nodes[i].specifier.to_string())
+      })
+      .collect::<Vec<_>>();
+    diagnostics.push(import.diagnostic(
+      NO_IMPORT_CYCLES_NAME,
+      format!("'{}' creates an import cycle", import.specifier_text),
+      "move the code both modules need into a separate module, or add one of the modules to 'importRules.importCycles.allow' in the config file".to_string(),
+      vec![Cow::Owned(format!(
+        "import cycle: {} -> {}",
+        relative_specifier(&module.specifier, &module.specifier)
+          .unwrap_or_else(|| module.specifier.to_string()),
+        cycle.join(" -> ")
+      ))],
+    ));
+  }
+}
+
+/// Returns the component of each node, where a node is in a cycle when it
+/// shares its component with the node of one of its edges.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<usize> {
+  // Kosaraju's algorithm, iterative to not overflow the stack on deep graphs
+  let len = edges.len();
+  let mut visited = vec![false; len];
+  let mut order = Vec::with_capacity(len);
+  for start in 0..len {
+    if visited[start] {
+      continue;
+    }
+    visited[start] = true;
+    let mut stack = vec![(start, 0)];
+    while let Some((node, edge_index)) = stack.last_mut() {
+      if let Some(&next) = edges[*node].get(*edge_index) {
+        *edge_index += 1;
+        if !visited[next] {
+          visited[next] = true;
+          stack.push((next, 0));
+        }
+      } else {
+        order.push(*node);
+        stack.pop();
+      }
+    }
+  }
+
+  let mut reverse_edges = vec![Vec::new(); len];
+  for (node, edges) in edges.iter().enumerate() {
+    for &next in edges {
+      reverse_edges[next].push(node);
+    }
+  }
+  let mut components = vec![usize::MAX; len];
+  for (component, &start) in order.iter().rev().enumerate() {
+    if components[start] != usize::MAX {
+      continue;
+    }
+    components[start] = component;
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+      for &next in &reverse_edges[node] {
+        if components[next] == usize::MAX {
+          components[next] = component;
+          stack.push(next);
+        }
+      }
+    }
+  }
+  components
+}
+
+/// The shortest path from `from` to `to` within their component, including
+/// both nodes.
+fn shortest_path(
+  edges: &[Vec<(usize, ImportRef)>],
+  components: &[usize],
+  from: usize,
+  to: usize,
+) -> Vec<usize> {
+  let mut previous = HashMap::from([(from, from)]);
+  let mut queue = VecDeque::from([from]);
+  while let Some(node) = queue.pop_front() {
+    if node == to {
+      break;
+    }
+    for (next, _) in &edges[node] {
+      if components[*next] == components[from] && !previous.contains_key(next) {
+        previous.insert(*next, node);
+        queue.push_back(*next);
+      }
+    }
+  }
+  let mut path = vec![to];
+  let mut node = to;
+  while node != from {
+    node = previous[&node];
+    path.push(node);
+  }
+  path.reverse();
+  path
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn matches_specifiers() {
+    assert!(matches_specifier("npm:lodash", "npm:lodash"));
+    assert!(matches_specifier("npm:lodash", "npm:lodash@4"));
+    assert!(matches_specifier("npm:lodash", "npm:lodash/fp"));
+    assert!(!matches_specifier("npm:lodash", "npm:lodash-es"));
+    assert!(matches_specifier("npm:@scope", "npm:@scope/pkg"));
+    assert!(matches_specifier(
+      "file:///a/legacy/",
+      "file:///a/legacy/b.ts"
+    ));
+    assert!(!matches_specifier(
+      "file:///a/legacy",
+      "file:///a/legacy2.ts"
+    ));
+  }
+
+  #[test]
+  fn components() {
+    // 0 -> 1 -> 2 -> 0, 2 -> 3, 3 -> 3, 4
+    let edges = vec![vec![1], vec![2], vec![0, 3], vec![3], vec![]];
+    let components = strongly_connected_components(&edges);
+    assert_eq!(components[0], components[1]);
+    assert_eq!(components[1], components[2]);
+    assert_ne!(components[2], components[3]);
+    assert_ne!(components[3], components[4]);
+  }
+
+  #[test]
+  fn parses_config() {
+    let config = ImportRulesConfig::from_value(
+      serde_json::json!({
+        "restrictedImports": [{
+          "specifier": "./legacy/",
+          "allow": ["./scripts/"],
+        }],
+        "restrictedPaths": [{
+          "target": "./src/domain/",
+          "from": "./src/infra/",
+        }],
+      }),
+      Path::new(if cfg!(windows) {
+        "C:\\project"
+      } else {
+        "/project"
+      }),
+    )
+    .unwrap();
+    assert!(config.restricted_imports[0].specifier.ends_with("/legacy/"));
+    assert!(config.restricted_imports[0]
+      .specifier
+      .starts_with("file:///"));
+    assert_eq!(config.restricted_paths[0].from_text, "./src/infra/");
+
+    let err = ImportRulesConfig::from_value(
+      serde_json::json!({ "restrictedImport": [] }),
+      Path::new("/project"),
+    )
+    .unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Failed to parse \"importRules\" configuration"
+    );
+  }
+}
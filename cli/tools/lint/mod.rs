@@ -16,6 +16,7 @@ use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_graph::FastCheckDiagnostic;
+use deno_graph::GraphKind;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_lint::linter::LintFileOptions;
 use deno_lint::linter::Linter;
@@ -51,6 +52,7 @@ use crate::util::fs::FileCollector;
 use crate::util::path::is_script_ext;
 use crate::util::sync::AtomicFlag;
 
+pub mod import_rules;
 pub mod no_slow_types;
 
 static STDIN_FILE_NAME: &str = "$deno$stdin.ts";
@@ -216,6 +218,51 @@ async fn lint_files(
     }
   }
 
+  if lint_rules.import_rules.any() {
+    let config = match maybe_config_file {
+      Some(config_file) => {
+        import_rules::ImportRulesConfig::from_config_file(config_file)?
+      }
+      None => Default::default(),
+    };
+    let enabled = lint_rules.import_rules;
+    let has_error = has_error.clone();
+    let reporter_lock = reporter_lock.clone();
+    let module_graph_builder = factory.module_graph_builder().await?.clone();
+    let module_graph_creator = factory.module_graph_creator().await?.clone();
+    let path_urls = paths
+      .iter()
+      .filter_map(|p| ModuleSpecifier::from_file_path(p).ok())
+      .collect::<Vec<_>>();
+    futures.push(deno_core::unsync::spawn(async move {
+      let mut loader = import_rules::LocalModuleLoader(
+        module_graph_builder.create_graph_loader(),
+      );
+      let graph = module_graph_creator
+        .create_graph_with_loader(
+          GraphKind::All,
+          path_urls.clone(),
+          &mut loader,
+        )
+        .await?;
+      let diagnostics = import_rules::collect_import_rule_diagnostics(
+        enabled,
+        &config,
+        &graph,
+        &path_urls.into_iter().collect(),
+      );
+      if !diagnostics.is_empty() {
+        has_error.raise();
+        let mut reporter = reporter_lock.lock();
+        for diagnostic in &diagnostics {
+          reporter
+            .visit_diagnostic(LintOrCliDiagnostic::ImportRule(diagnostic));
+        }
+      }
+      Ok(())
+    }));
+  }
+
   futures.push({
     let has_error = has_error.clone();
     let linter = create_linter(lint_rules.rules);
@@ -525,6 +572,7 @@ fn handle_lint_result(
 pub enum LintOrCliDiagnostic<'a> {
   Lint(&'a LintDiagnostic),
   FastCheck(&'a FastCheckDiagnostic),
+  ImportRule(&'a import_rules::ImportRuleDiagnostic),
 }
 
 impl<'a> LintOrCliDiagnostic<'a> {
@@ -532,6 +580,7 @@ impl<'a> LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => &d.specifier,
       LintOrCliDiagnostic::FastCheck(d) => d.specifier(),
+      LintOrCliDiagnostic::ImportRule(d) => d.specifier(),
     }
   }
 
@@ -541,6 +590,7 @@ impl<'a> LintOrCliDiagnostic<'a> {
       LintOrCliDiagnostic::FastCheck(d) => {
         d.range().map(|r| (&r.text_info, r.range))
       }
+      LintOrCliDiagnostic::ImportRule(d) => Some(d.range()),
     }
   }
 }
@@ -550,6 +600,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.level(),
       LintOrCliDiagnostic::FastCheck(d) => d.level(),
+      LintOrCliDiagnostic::ImportRule(d) => d.level(),
     }
   }
 
@@ -557,6 +608,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.code(),
       LintOrCliDiagnostic::FastCheck(_) => Cow::Borrowed("no-slow-types"),
+      LintOrCliDiagnostic::ImportRule(d) => d.code(),
     }
   }
 
@@ -564,6 +616,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.message(),
       LintOrCliDiagnostic::FastCheck(d) => d.message(),
+      LintOrCliDiagnostic::ImportRule(d) => d.message(),
     }
   }
 
@@ -571,6 +624,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.location(),
       LintOrCliDiagnostic::FastCheck(d) => d.location(),
+      LintOrCliDiagnostic::ImportRule(d) => d.location(),
     }
   }
 
@@ -578,6 +632,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.snippet(),
       LintOrCliDiagnostic::FastCheck(d) => d.snippet(),
+      LintOrCliDiagnostic::ImportRule(d) => d.snippet(),
     }
   }
 
@@ -585,6 +640,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.hint(),
       LintOrCliDiagnostic::FastCheck(d) => d.hint(),
+      LintOrCliDiagnostic::ImportRule(d) => d.hint(),
     }
   }
 
@@ -594,6 +650,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.snippet_fixed(),
       LintOrCliDiagnostic::FastCheck(d) => d.snippet_fixed(),
+      LintOrCliDiagnostic::ImportRule(d) => d.snippet_fixed(),
     }
   }
 
@@ -601,6 +658,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.info(),
       LintOrCliDiagnostic::FastCheck(d) => d.info(),
+      LintOrCliDiagnostic::ImportRule(d) => d.info(),
     }
   }
 
@@ -608,6 +666,7 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.docs_url(),
       LintOrCliDiagnostic::FastCheck(d) => d.docs_url(),
+      LintOrCliDiagnostic::ImportRule(d) => d.docs_url(),
     }
   }
 }
@@ -863,6 +922,7 @@ pub struct ConfiguredRules {
   pub rules: Vec<&'static dyn LintRule>,
   // cli specific rules
   pub no_slow_types: bool,
+  pub import_rules: import_rules::EnabledImportRules,
 }
 
 impl Default for ConfiguredRules {
@@ -880,6 +940,7 @@ impl ConfiguredRules {
     if self.no_slow_types {
       names.push("no-slow-types");
     }
+    names.extend(self.import_rules.names());
     names
   }
 }
@@ -898,26 +959,30 @@ pub fn get_configured_rules(
       .as_ref()
       .map(|exclude| exclude.iter().any(|i| i == NO_SLOW_TYPES_NAME))
       .unwrap_or(false);
+  // the import rules are opt-in and only run when included
+  let import_rules = rules
+    .include
+    .as_deref()
+    .map(import_rules::EnabledImportRules::from_included)
+    .unwrap_or_default();
+  let is_cli_rule = |c: &String| {
+    c == NO_SLOW_TYPES_NAME || import_rules::IMPORT_RULE_NAMES.contains(&&**c)
+  };
   let rules = rules::get_filtered_rules(
     rules
       .tags
       .or_else(|| Some(get_default_tags(maybe_config_file))),
-    rules.exclude.map(|exclude| {
-      exclude
-        .into_iter()
-        .filter(|c| c != NO_SLOW_TYPES_NAME)
-        .collect()
-    }),
-    rules.include.map(|include| {
-      include
-        .into_iter()
-        .filter(|c| c != NO_SLOW_TYPES_NAME)
-        .collect()
-    }),
+    rules
+      .exclude
+      .map(|exclude| exclude.into_iter().filter(|c| !is_cli_rule(c)).collect()),
+    rules
+      .include
+      .map(|include| include.into_iter().filter(|c| !is_cli_rule(c)).collect()),
   );
   ConfiguredRules {
     rules,
     no_slow_types,
+    import_rules,
   }
 }
 
@@ -961,4 +1026,21 @@ mod test {
     recommended_rule_names.sort();
     assert_eq!(rule_names, recommended_rule_names);
   }
+
+  #[test]
+  fn import_rules_when_included() {
+    let rules_config = LintRulesConfig {
+      exclude: None,
+      include: Some(vec!["no-import-cycles".to_string()]),
+      tags: None,
+    };
+    let rules = get_configured_rules(rules_config, None);
+    assert!(rules.import_rules.no_import_cycles);
+    assert!(!rules.import_rules.no_restricted_imports);
+    assert!(!rules.rules.iter().any(|r| r.code() == "no-import-cycles"));
+    assert_eq!(
+      get_configured_rules(Default::default(), None).import_rules,
+      Default::default()
+    );
+  }
 }
@@ -0,0 +1,626 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The debugger commands of the REPL.
+//!
+//! The debugger has an inspector session of its own, served by a thread:
+//! when the code hits a breakpoint, V8 blocks the thread of the worker until
+//! execution resumes, so the commands that inspect and step the paused code
+//! have to be read and sent from another thread.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::io::BufRead;
+use std::io::Write;
+
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use deno_core::futures::channel::mpsc;
+use deno_core::futures::channel::mpsc::UnboundedReceiver;
+use deno_core::futures::channel::mpsc::UnboundedSender;
+use deno_core::futures::channel::oneshot;
+use deno_core::futures::executor::block_on;
+use deno_core::futures::future::select;
+use deno_core::futures::future::Either;
+use deno_core::futures::StreamExt;
+use deno_core::serde::de::DeserializeOwned;
+use deno_core::serde::Serialize;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::serde_json::Value;
+use deno_core::InspectorMsg;
+use deno_core::InspectorMsgKind;
+use deno_core::InspectorSessionProxy;
+
+use crate::cdp;
+use crate::colors;
+
+pub const HELP: &str = "\
+.break <file>:<line>[:<column>]  Pause when a module reaches the given line
+.breakpoints                     List the breakpoints
+.delete <number>                 Remove a breakpoint
+.debug <expression>              Evaluate an expression, pausing at breakpoints
+.help                            Print this help
+
+While paused, the debug> prompt evaluates expressions in the paused frame and
+accepts .continue (.c), .next (.n), .step (.s), .out (.o), .frames (.bt),
+.scope [<frame>] and .help.";
+
+const PAUSED_HELP: &str = "\
+.continue, .c     Resume execution
+.next, .n         Step over the current statement
+.step, .s         Step into the current function call
+.out, .o          Step out of the current function
+.frames, .bt      Print the call stack
+.scope [<frame>]  Print the variables in scope of a frame, the top one by default
+.help             Print this help
+
+Anything else is evaluated in the top frame.";
+
+/// A REPL line that drives the debugger instead of being evaluated.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DebuggerCommand {
+  Break(BreakpointLocation),
+  Breakpoints,
+  Delete(usize),
+  /// Evaluates the expression from the event loop. V8 can't pause in code
+  /// evaluated by an inspector session, which the REPL evaluates lines with.
+  Debug(String),
+  Help,
+}
+
+impl DebuggerCommand {
+  /// Returns `None` if `line` isn't a debugger command.
+  pub fn parse(line: &str) -> Option<Result<Self, AnyError>> {
+    let line = line.trim();
+    let (name, args) = match line.split_once(char::is_whitespace) {
+      Some((name, args)) => (name, args.trim()),
+      None => (line, ""),
+    };
+    let command = match name {
+      ".break" => BreakpointLocation::parse(args).map(Self::Break),
+      ".breakpoints" => Ok(Self::Breakpoints),
+      ".delete" => args.parse().map(Self::Delete).map_err(|_| {
+        generic_error(format!("Invalid breakpoint number \"{args}\""))
+      }),
+      ".debug" if args.is_empty() => {
+        Err(generic_error("Expected an expression to evaluate"))
+      }
+      ".debug" => Ok(Self::Debug(args.trim_end_matches(';').to_string())),
+      ".help" => Ok(Self::Help),
+      _ => return None,
+    };
+    Some(command)
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BreakpointLocation {
+  pub path: String,
+  /// 1-based.
+  pub line_number: u64,
+  /// 1-based.
+  pub column_number: Option<u64>,
+}
+
+impl BreakpointLocation {
+  /// Parses `<file>:<line>[:<column>]`. The file can contain colons, as in
+  /// URLs and Windows paths.
+  fn parse(location: &str) -> Result<Self, AnyError> {
+    let invalid = || {
+      generic_error(format!(
+        "Invalid location \"{location}\", expected <file>:<line>[:<column>]"
+      ))
+    };
+    let (rest, last) = location.rsplit_once(':').ok_or_else(invalid)?;
+    let last = last.parse::<u64>().map_err(|_| invalid())?;
+    let (path, line_number, column_number) = match rest
+      .rsplit_once(':')
+      .and_then(|(path, line)| Some((path, line.parse::<u64>().ok()?)))
+    {
+      Some((path, line_number)) => (path, line_number, Some(last)),
+      None => (rest, last, None),
+    };
+    if path.is_empty() || line_number == 0 || column_number == Some(0) {
+      return Err(invalid());
+    }
+    Ok(Self {
+      path: path.to_string(),
+      line_number,
+      column_number,
+    })
+  }
+}
+
+pub struct Breakpoint {
+  pub number: usize,
+  pub id: cdp::BreakpointId,
+  pub url: String,
+  pub location: BreakpointLocation,
+}
+
+impl std::fmt::Display for Breakpoint {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}", self.url, self.location.line_number)?;
+    if let Some(column_number) = self.location.column_number {
+      write!(f, ":{column_number}")?;
+    }
+    Ok(())
+  }
+}
+
+struct Request {
+  method: String,
+  params: Value,
+  response_tx: oneshot::Sender<Result<Value, AnyError>>,
+}
+
+/// The handle of the REPL to its debugger session.
+pub struct ReplDebugger {
+  request_tx: UnboundedSender<Request>,
+  breakpoints: Vec<Breakpoint>,
+  next_breakpoint_number: usize,
+}
+
+impl ReplDebugger {
+  /// Connects a session to the inspector. It only gets established once the
+  /// event loop polls the inspector.
+  pub fn new(session_sender: &UnboundedSender<InspectorSessionProxy>) -> Self {
+    // The 'outbound' channel carries messages sent to the session.
+    let (outbound_tx, outbound_rx) = mpsc::unbounded();
+    // The 'inbound' channel carries messages received from the session.
+    let (inbound_tx, inbound_rx) = mpsc::unbounded();
+    let _ = session_sender.unbounded_send(InspectorSessionProxy {
+      tx: outbound_tx,
+      rx: inbound_rx,
+    });
+
+    let (request_tx, request_rx) = mpsc::unbounded();
+    let session = DebuggerSession {
+      inbound_tx,
+      outbound_rx,
+      next_message_id: 0,
+      pending: HashMap::new(),
+    };
+    std::thread::spawn(move || session.run(request_rx));
+
+    Self {
+      request_tx,
+      breakpoints: Vec::new(),
+      next_breakpoint_number: 1,
+    }
+  }
+
+  /// Sends a message to the session. The event loop of the worker has to be
+  /// polled for the response to arrive.
+  pub fn post_message(
+    &self,
+    method: &str,
+    params: impl Serialize,
+  ) -> impl Future<Output = Result<Value, AnyError>> {
+    let (response_tx, response_rx) = oneshot::channel();
+    let sent = serde_json::to_value(params).map(|params| {
+      self.request_tx.unbounded_send(Request {
+        method: method.to_string(),
+        params,
+        response_tx,
+      })
+    });
+    async move {
+      let closed = || generic_error("The debugger session was closed");
+      sent?.map_err(|_| closed())?;
+      response_rx.await.map_err(|_| closed())?
+    }
+  }
+
+  pub fn add_breakpoint(
+    &mut self,
+    id: cdp::BreakpointId,
+    url: String,
+    location: BreakpointLocation,
+  ) -> &Breakpoint {
+    let number = self.next_breakpoint_number;
+    self.next_breakpoint_number += 1;
+    self.breakpoints.push(Breakpoint {
+      number,
+      id,
+      url,
+      location,
+    });
+    self.breakpoints.last().unwrap()
+  }
+
+  pub fn remove_breakpoint(&mut self, number: usize) -> Option<Breakpoint> {
+    let index = self.breakpoints.iter().position(|b| b.number == number)?;
+    Some(self.breakpoints.remove(index))
+  }
+
+  pub fn breakpoints(&self) -> &[Breakpoint] {
+    &self.breakpoints
+  }
+}
+
+enum Event {
+  Message(Option<InspectorMsg>),
+  Request(Option<Request>),
+}
+
+/// The end of the session living on the debugger thread.
+struct DebuggerSession {
+  inbound_tx: UnboundedSender<String>,
+  outbound_rx: UnboundedReceiver<InspectorMsg>,
+  next_message_id: i32,
+  /// The requests of the REPL waiting for a response.
+  pending: HashMap<i32, oneshot::Sender<Result<Value, AnyError>>>,
+}
+
+#[allow(clippy::print_stdout)]
+impl DebuggerSession {
+  fn run(mut self, mut request_rx: UnboundedReceiver<Request>) {
+    loop {
+      let event =
+        match block_on(select(self.outbound_rx.next(), request_rx.next())) {
+          Either::Left((message, _)) => Event::Message(message),
+          Either::Right((request, _)) => Event::Request(request),
+        };
+      match event {
+        Event::Message(Some(message)) => self.handle_message(message),
+        Event::Request(Some(request)) => {
+          let id = self.send(&request.method, request.params);
+          self.pending.insert(id, request.response_tx);
+        }
+        // the REPL is exiting
+        Event::Message(None) | Event::Request(None) => break,
+      }
+    }
+  }
+
+  fn send(&mut self, method: &str, params: Value) -> i32 {
+    self.next_message_id += 1;
+    let message = json!({
+      "id": self.next_message_id,
+      "method": method,
+      "params": params,
+    });
+    let _ = self.inbound_tx.unbounded_send(message.to_string());
+    self.next_message_id
+  }
+
+  fn respond(&mut self, id: i32, content: &str) {
+    if let Some(response_tx) = self.pending.remove(&id) {
+      let _ = response_tx.send(parse_response(content));
+    }
+  }
+
+  fn handle_message(&mut self, message: InspectorMsg) {
+    match message.kind {
+      InspectorMsgKind::Message(id) => self.respond(id, &message.content),
+      InspectorMsgKind::Notification => {
+        let Ok(notification) =
+          serde_json::from_str::<cdp::Notification>(&message.content)
+        else {
+          return;
+        };
+        if notification.method == "Debugger.paused" {
+          if let Ok(paused) =
+            serde_json::from_value::<cdp::Paused>(notification.params)
+          {
+            self.on_paused(paused);
+          }
+        }
+      }
+    }
+  }
+
+  /// Sends a message and waits for its response, which only works while
+  /// execution is paused: the worker isn't polling its event loop otherwise.
+  ///
+  /// Returns `Ok(None)` if execution resumed on its own, this happens when
+  /// V8 can't pause.
+  fn call<T: DeserializeOwned>(
+    &mut self,
+    method: &str,
+    params: impl Serialize,
+  ) -> Result<Option<T>, AnyError> {
+    let id = self.send(method, serde_json::to_value(params)?);
+    let mut resumed = false;
+    while let Some(message) = block_on(self.outbound_rx.next()) {
+      match message.kind {
+        InspectorMsgKind::Message(message_id) if message_id == id => {
+          if resumed {
+            return Ok(None);
+          }
+          let result = parse_response(&message.content)?;
+          return Ok(Some(serde_json::from_value(result)?));
+        }
+        InspectorMsgKind::Message(message_id) => {
+          self.respond(message_id, &message.content)
+        }
+        InspectorMsgKind::Notification => {
+          resumed |=
+            serde_json::from_str::<cdp::Notification>(&message.content)
+              .is_ok_and(|notification| {
+                notification.method == "Debugger.resumed"
+              });
+        }
+      }
+    }
+    Err(generic_error("The inspector session was closed"))
+  }
+
+  // NOTE: this competes with the editor for stdin if the code is paused
+  // while the REPL waits for a line, e.g. in a timer callback.
+  fn on_paused(&mut self, paused: cdp::Paused) {
+    let Some(frame) = paused.call_frames.first() else {
+      return;
+    };
+    match self.source_line(&frame.location) {
+      Ok(Some(source_line)) => {
+        println!("{} {}", colors::yellow("Paused at"), format_frame(frame));
+        println!("{source_line}");
+      }
+      Ok(None) => {
+        println!(
+          "{} the code evaluated at the prompt can't pause, use .debug to evaluate it",
+          colors::yellow("Breakpoint skipped:"),
+        );
+        return;
+      }
+      Err(err) => println!("{} {:#}", colors::red("error:"), err),
+    }
+
+    let stdin = std::io::stdin();
+    loop {
+      print!("debug> ");
+      let _ = std::io::stdout().flush();
+      let mut line = String::new();
+      if !matches!(stdin.lock().read_line(&mut line), Ok(n) if n > 0) {
+        return self.resume("Debugger.resume");
+      }
+      let line = line.trim();
+      let (name, args) = match line.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (line, ""),
+      };
+      let result = match name {
+        "" => continue,
+        ".continue" | ".c" => return self.resume("Debugger.resume"),
+        ".next" | ".n" => return self.resume("Debugger.stepOver"),
+        ".step" | ".s" => return self.resume("Debugger.stepInto"),
+        ".out" | ".o" => return self.resume("Debugger.stepOut"),
+        ".frames" | ".bt" => Ok(format_frames(&paused.call_frames)),
+        ".scope" => match args {
+          "" => self.scope(frame),
+          index => match index
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| paused.call_frames.get(index))
+          {
+            Some(frame) => self.scope(frame),
+            None => Err(generic_error(format!("Invalid frame \"{index}\""))),
+          },
+        },
+        ".help" => Ok(PAUSED_HELP.to_string()),
+        _ => self.evaluate(frame, line),
+      };
+      match result {
+        Ok(output) => println!("{output}"),
+        Err(err) => println!("{} {:#}", colors::red("error:"), err),
+      }
+    }
+  }
+
+  fn resume(&mut self, method: &str) {
+    if let Err(err) = self.call::<Value>(method, json!({})) {
+      println!("{} {:#}", colors::red("error:"), err);
+    }
+  }
+
+  /// Returns the line of the source at `location`, or `None` if execution
+  /// resumed in the meantime.
+  fn source_line(
+    &mut self,
+    location: &cdp::Location,
+  ) -> Result<Option<String>, AnyError> {
+    let Some(response) = self.call::<cdp::GetScriptSourceResponse>(
+      "Debugger.getScriptSource",
+      cdp::GetScriptSourceArgs {
+        script_id: location.script_id.clone(),
+      },
+    )?
+    else {
+      return Ok(None);
+    };
+    let line = response
+      .script_source
+      .lines()
+      .nth(location.line_number as usize)
+      .unwrap_or_default();
+    Ok(Some(format!(
+      "{} {}",
+      colors::gray(format!("{:>5} |", location.line_number + 1)),
+      line
+    )))
+  }
+
+  fn evaluate(
+    &mut self,
+    frame: &cdp::DebuggerCallFrame,
+    expression: &str,
+  ) -> Result<String, AnyError> {
+    let response = self
+      .call::<cdp::EvaluateResponse>(
+        "Debugger.evaluateOnCallFrame",
+        cdp::EvaluateOnCallFrameArgs {
+          call_frame_id: frame.call_frame_id.clone(),
+          expression: expression.to_string(),
+          object_group: None,
+        },
+      )?
+      .ok_or_else(|| generic_error("Execution isn't paused"))?;
+    Ok(match response.exception_details {
+      Some(exception_details) => {
+        let (message, description) =
+          exception_details.get_message_and_description();
+        format!("{message} {description}")
+      }
+      None => format_remote_object(&response.result),
+    })
+  }
+
+  /// Lists the variables of the scopes of `frame`, except the global one.
+  fn scope(
+    &mut self,
+    frame: &cdp::DebuggerCallFrame,
+  ) -> Result<String, AnyError> {
+    let mut output = String::new();
+    for scope in &frame.scope_chain {
+      let Some(object_id) = &scope.object.object_id else {
+        continue;
+      };
+      if scope.kind == "global" {
+        continue;
+      }
+      let response = self
+        .call::<cdp::GetPropertiesResponse>(
+          "Runtime.getProperties",
+          cdp::GetPropertiesArgs {
+            object_id: object_id.clone(),
+            own_properties: Some(true),
+            accessor_properties_only: None,
+            generate_preview: None,
+            non_indexed_properties_only: None,
+          },
+        )?
+        .ok_or_else(|| generic_error("Execution isn't paused"))?;
+      writeln!(output, "{}", colors::gray(format!("{} scope:", scope.kind)))
+        .unwrap();
+      for property in response.result {
+        let value = match &property.value {
+          Some(value) => format_remote_object(value),
+          None => "[Getter/Setter]".to_string(),
+        };
+        writeln!(output, "  {}: {}", property.name, value).unwrap();
+      }
+    }
+    Ok(output.trim_end().to_string())
+  }
+}
+
+fn parse_response(content: &str) -> Result<Value, AnyError> {
+  let mut response: Value = serde_json::from_str(content)?;
+  if let Some(error) = response.get("error") {
+    return Err(generic_error(
+      error["message"]
+        .as_str()
+        .unwrap_or("Unknown error")
+        .to_string(),
+    ));
+  }
+  Ok(response["result"].take())
+}
+
+fn format_remote_object(object: &cdp::RemoteObject) -> String {
+  if let Some(value) = &object.unserializable_value {
+    return value.clone();
+  }
+  match (&object.value, &object.description) {
+    (Some(value), _) => value.to_string(),
+    (None, Some(description)) => description.clone(),
+    (None, None) => object.kind.clone(),
+  }
+}
+
+fn format_frame(frame: &cdp::DebuggerCallFrame) -> String {
+  let name = match frame.function_name.as_str() {
+    "" => "<anonymous>",
+    name => name,
+  };
+  format!(
+    "{} ({}:{}:{})",
+    name,
+    frame.url,
+    frame.location.line_number + 1,
+    frame.location.column_number.unwrap_or(0) + 1
+  )
+}
+
+fn format_frames(frames: &[cdp::DebuggerCallFrame]) -> String {
+  frames
+    .iter()
+    .enumerate()
+    .map(|(index, frame)| format!("#{index} {}", format_frame(frame)))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn location(
+    path: &str,
+    line_number: u64,
+    column_number: Option<u64>,
+  ) -> BreakpointLocation {
+    BreakpointLocation {
+      path: path.to_string(),
+      line_number,
+      column_number,
+    }
+  }
+
+  #[test]
+  fn parse_commands() {
+    assert!(DebuggerCommand::parse("1 + 1").is_none());
+    assert!(DebuggerCommand::parse(".5 + 1").is_none());
+    assert!(DebuggerCommand::parse(".breaking").is_none());
+    assert_eq!(
+      DebuggerCommand::parse(".break ./main.ts:3")
+        .unwrap()
+        .unwrap(),
+      DebuggerCommand::Break(location("./main.ts", 3, None))
+    );
+    assert_eq!(
+      DebuggerCommand::parse("  .breakpoints ").unwrap().unwrap(),
+      DebuggerCommand::Breakpoints
+    );
+    assert_eq!(
+      DebuggerCommand::parse(".delete 2").unwrap().unwrap(),
+      DebuggerCommand::Delete(2)
+    );
+    assert!(DebuggerCommand::parse(".delete two").unwrap().is_err());
+    assert_eq!(
+      DebuggerCommand::parse(".debug  f(1, 2) ").unwrap().unwrap(),
+      DebuggerCommand::Debug("f(1, 2)".to_string())
+    );
+    assert!(DebuggerCommand::parse(".debug").unwrap().is_err());
+    assert_eq!(
+      DebuggerCommand::parse(".help").unwrap().unwrap(),
+      DebuggerCommand::Help
+    );
+  }
+
+  #[test]
+  fn parse_location() {
+    let parse = |s| BreakpointLocation::parse(s).ok();
+    assert_eq!(parse("main.js:1"), Some(location("main.js", 1, None)));
+    assert_eq!(
+      parse("main.js:12:5"),
+      Some(location("main.js", 12, Some(5)))
+    );
+    assert_eq!(
+      parse("C:\\src\\main.js:2"),
+      Some(location("C:\\src\\main.js", 2, None))
+    );
+    assert_eq!(
+      parse("http://localhost:4545/main.js:7:1"),
+      Some(location("http://localhost:4545/main.js", 7, Some(1)))
+    );
+    assert_eq!(parse("main.js"), None);
+    assert_eq!(parse("main.js:"), None);
+    assert_eq!(parse(":3"), None);
+    assert_eq!(parse("main.js:0"), None);
+    assert_eq!(parse("main.js:1:0"), None);
+  }
+}
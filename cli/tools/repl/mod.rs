@@ -19,6 +19,7 @@ use deno_runtime::WorkerExecutionMode;
 use rustyline::error::ReadlineError;
 
 mod channel;
+mod debugger;
 mod editor;
 mod session;
 
@@ -54,7 +55,10 @@ impl Repl {
         Ok(line) => {
           self.editor.set_should_exit_on_interrupt(false);
           self.editor.update_history(line.clone());
-          let output = self.session.evaluate_line_and_get_output(&line).await;
+          let output = match self.session.run_debugger_command(&line).await {
+            Some(output) => output,
+            None => self.session.evaluate_line_and_get_output(&line).await,
+          };
 
           // We check for close and break here instead of making it a loop condition to get
           // consistent behavior in when the user evaluates a call to close().
@@ -36,6 +36,7 @@ use deno_core::futures::channel::mpsc::UnboundedReceiver;
 use deno_core::futures::FutureExt;
 use deno_core::futures::StreamExt;
 use deno_core::serde_json;
+use deno_core::serde_json::json;
 use deno_core::serde_json::Value;
 use deno_core::unsync::spawn;
 use deno_core::url::Url;
@@ -46,6 +47,7 @@ use deno_graph::source::Resolver;
 use deno_graph::Position;
 use deno_graph::PositionRange;
 use deno_graph::SpecifierWithRange;
+use deno_runtime::fs_util::specifier_to_file_path;
 use deno_runtime::worker::MainWorker;
 use deno_semver::npm::NpmPackageReqReference;
 use once_cell::sync::Lazy;
@@ -53,6 +55,10 @@ use regex::Match;
 use regex::Regex;
 use tokio::sync::Mutex;
 
+use super::debugger;
+use super::debugger::DebuggerCommand;
+use super::debugger::ReplDebugger;
+
 fn comment_source_to_position_range(
   comment_start: SourcePos,
   m: &Match,
@@ -188,6 +194,8 @@ pub struct ReplSession {
   test_event_receiver: Option<TestEventReceiver>,
   jsx: ReplJsxState,
   experimental_decorators: bool,
+  /// Started by the first debugger command.
+  debugger: Option<ReplDebugger>,
 }
 
 impl ReplSession {
@@ -283,6 +291,7 @@ impl ReplSession {
         import_source: None,
       },
       experimental_decorators,
+      debugger: None,
     };
 
     // inject prelude
@@ -332,6 +341,162 @@ impl ReplSession {
       .await
   }
 
+  /// Connects the debugger session if this is the first debugger command.
+  async fn start_debugger(&mut self) -> Result<&mut ReplDebugger, AnyError> {
+    if self.debugger.is_none() {
+      let session_sender = self
+        .worker
+        .js_runtime
+        .inspector()
+        .borrow()
+        .get_session_sender();
+      let debugger = ReplDebugger::new(&session_sender);
+      self
+        .worker
+        .js_runtime
+        .with_event_loop_future(
+          debugger
+            .post_message("Debugger.enable", json!({}))
+            .boxed_local(),
+          PollEventLoopOptions {
+            pump_v8_message_loop: false,
+            ..Default::default()
+          },
+        )
+        .await?;
+      self.debugger = Some(debugger);
+    }
+    Ok(self.debugger.as_mut().unwrap())
+  }
+
+  async fn post_debugger_message(
+    &mut self,
+    method: &str,
+    params: impl serde::Serialize,
+  ) -> Result<Value, AnyError> {
+    let response = self
+      .start_debugger()
+      .await?
+      .post_message(method, params)
+      .boxed_local();
+    self
+      .worker
+      .js_runtime
+      .with_event_loop_future(
+        response,
+        PollEventLoopOptions {
+          pump_v8_message_loop: false,
+          ..Default::default()
+        },
+      )
+      .await
+  }
+
+  /// Runs `line` if it is a debugger command, see [DebuggerCommand].
+  pub async fn run_debugger_command(
+    &mut self,
+    line: &str,
+  ) -> Option<EvaluationOutput> {
+    let command = DebuggerCommand::parse(line)?;
+    Some(match command {
+      Ok(DebuggerCommand::Debug(expression)) => {
+        // this also makes `debugger` statements pause
+        if let Err(err) = self.start_debugger().await {
+          return Some(result_to_evaluation_output(Err(err)));
+        }
+        self
+          .evaluate_line_and_get_output(&format!(
+            "await Promise.resolve().then(async () => ({expression}))"
+          ))
+          .await
+      }
+      Ok(command) => {
+        result_to_evaluation_output(self.run_breakpoint_command(command).await)
+      }
+      Err(err) => result_to_evaluation_output(Err(err)),
+    })
+  }
+
+  async fn run_breakpoint_command(
+    &mut self,
+    command: DebuggerCommand,
+  ) -> Result<EvaluationOutput, AnyError> {
+    match command {
+      DebuggerCommand::Break(location) => {
+        let cwd = specifier_to_file_path(&self.referrer)?;
+        let url = deno_core::resolve_url_or_path(
+          &location.path,
+          cwd.parent().unwrap(),
+        )?;
+        let response = self
+          .post_debugger_message(
+            "Debugger.setBreakpointByUrl",
+            cdp::SetBreakpointByUrlArgs {
+              line_number: location.line_number - 1,
+              url: Some(url.to_string()),
+              column_number: location.column_number.map(|column| column - 1),
+              condition: None,
+            },
+          )
+          .await?;
+        let response: cdp::SetBreakpointByUrlResponse =
+          serde_json::from_value(response)?;
+        let debugger = self.debugger.as_mut().unwrap();
+        let breakpoint = debugger.add_breakpoint(
+          response.breakpoint_id,
+          url.to_string(),
+          location,
+        );
+        let mut output =
+          format!("Breakpoint {} set at {}", breakpoint.number, breakpoint);
+        if response.locations.is_empty() {
+          output.push_str(&format!(" {}", colors::gray("(not loaded yet)")));
+        }
+        Ok(EvaluationOutput::Value(output))
+      }
+      DebuggerCommand::Breakpoints => {
+        let breakpoints = self
+          .debugger
+          .as_ref()
+          .map(|debugger| debugger.breakpoints())
+          .unwrap_or_default();
+        if breakpoints.is_empty() {
+          return Ok(EvaluationOutput::Value("No breakpoints".to_string()));
+        }
+        let output = breakpoints
+          .iter()
+          .map(|breakpoint| format!("{}: {}", breakpoint.number, breakpoint))
+          .collect::<Vec<_>>()
+          .join("\n");
+        Ok(EvaluationOutput::Value(output))
+      }
+      DebuggerCommand::Delete(number) => {
+        let Some(breakpoint) = self
+          .debugger
+          .as_mut()
+          .and_then(|debugger| debugger.remove_breakpoint(number))
+        else {
+          return Err(generic_error(format!("No breakpoint {number}")));
+        };
+        self
+          .post_debugger_message(
+            "Debugger.removeBreakpoint",
+            cdp::RemoveBreakpointArgs {
+              breakpoint_id: breakpoint.id,
+            },
+          )
+          .await?;
+        Ok(EvaluationOutput::Value(format!(
+          "Breakpoint {number} deleted"
+        )))
+      }
+      DebuggerCommand::Help => {
+        Ok(EvaluationOutput::Value(debugger::HELP.to_string()))
+      }
+      DebuggerCommand::Debug(_) => unreachable!(),
+    }
+  }
+
   pub async fn run_event_loop(&mut self) -> Result<(), AnyError> {
     self.worker.run_event_loop(true).await
   }
@@ -2,11 +2,13 @@
 
 use crate::args::Flags;
 use crate::args::TaskFlags;
+use crate::args::TaskListFormat;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::npm::ManagedCliNpmResolver;
+use crate::util::display::write_json_to_stdout;
 use crate::util::fs::canonicalize_path;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
@@ -22,6 +24,7 @@ use deno_task_shell::ShellCommandContext;
 use indexmap::IndexMap;
 use lazy_regex::Lazy;
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -41,13 +44,26 @@ pub async fn execute_script(
     .and_then(|p| p.scripts.clone())
     .unwrap_or_default();
 
+  if let Some(format) = task_flags.list {
+    let tasks = list_tasks(&tasks_config, &package_json_scripts);
+    match format {
+      TaskListFormat::Pretty => {
+        print_available_tasks(&mut std::io::stdout(), &tasks)?
+      }
+      TaskListFormat::Json => write_json_to_stdout(&tasks)?,
+      TaskListFormat::Completions => {
+        print_task_completions(&mut std::io::stdout(), &tasks)?
+      }
+    }
+    return Ok(0);
+  }
+
   let task_name = match &task_flags.task {
     Some(task) => task,
     None => {
       print_available_tasks(
         &mut std::io::stdout(),
-        &tasks_config,
-        &package_json_scripts,
+        &list_tasks(&tasks_config, &package_json_scripts),
       )?;
       return Ok(1);
     }
@@ -158,8 +174,7 @@ pub async fn execute_script(
     if log::log_enabled!(log::Level::Error) {
       print_available_tasks(
         &mut std::io::stderr(),
-        &tasks_config,
-        &package_json_scripts,
+        &list_tasks(&tasks_config, &package_json_scripts),
       )?;
     }
     Ok(1)
@@ -259,61 +274,113 @@ fn real_env_vars() -> HashMap<String, String> {
     .collect::<HashMap<String, String>>()
 }
 
+#[derive(Serialize)]
+enum TaskSource {
+  #[serde(rename = "deno.json")]
+  DenoJson,
+  #[serde(rename = "package.json")]
+  PackageJson,
+}
+
+#[derive(Serialize)]
+struct TaskListItem<'a> {
+  name: &'a str,
+  command: &'a str,
+  /// The comments above the task in the configuration file.
+  description: Option<String>,
+  source: TaskSource,
+}
+
+/// Lists the tasks of the configuration file, followed by the scripts of the
+/// package.json that aren't overridden by a task.
+fn list_tasks<'a>(
+  tasks_config: &'a IndexMap<String, deno_config::Task>,
+  package_json_scripts: &'a IndexMap<String, String>,
+) -> Vec<TaskListItem<'a>> {
+  let tasks = tasks_config.iter().map(|(name, task)| {
+    let (command, description) = match task {
+      deno_config::Task::Definition(definition) => (definition, None),
+      deno_config::Task::Commented {
+        definition,
+        comments,
+      } => (
+        definition,
+        Some(comments.join("\n")).filter(|c| !c.is_empty()),
+      ),
+    };
+    TaskListItem {
+      name,
+      command,
+      description,
+      source: TaskSource::DenoJson,
+    }
+  });
+  let scripts = package_json_scripts
+    .iter()
+    .filter(|(name, _)| !tasks_config.contains_key(*name))
+    .map(|(name, command)| TaskListItem {
+      name,
+      command,
+      description: None,
+      source: TaskSource::PackageJson,
+    });
+  tasks.chain(scripts).collect()
+}
+
 fn print_available_tasks(
   writer: &mut dyn std::io::Write,
-  tasks_config: &IndexMap<String, deno_config::Task>,
-  package_json_scripts: &IndexMap<String, String>,
+  tasks: &[TaskListItem],
 ) -> Result<(), std::io::Error> {
   writeln!(writer, "{}", colors::green("Available tasks:"))?;
 
-  if tasks_config.is_empty() && package_json_scripts.is_empty() {
+  if tasks.is_empty() {
     writeln!(
       writer,
       "  {}",
       colors::red("No tasks found in configuration file")
     )?;
   } else {
-    for (is_deno, (key, task)) in tasks_config
-      .iter()
-      .map(|(k, t)| (true, (k, t.clone())))
-      .chain(
-        package_json_scripts
-          .iter()
-          .filter(|(key, _)| !tasks_config.contains_key(*key))
-          .map(|(k, v)| (false, (k, deno_config::Task::Definition(v.clone())))),
-      )
-    {
+    for task in tasks {
       writeln!(
         writer,
         "- {}{}",
-        colors::cyan(key),
-        if is_deno {
-          "".to_string()
-        } else {
-          format!(" {}", colors::italic_gray("(package.json)"))
+        colors::cyan(task.name),
+        match task.source {
+          TaskSource::DenoJson => "".to_string(),
+          TaskSource::PackageJson => {
+            format!(" {}", colors::italic_gray("(package.json)"))
+          }
         }
       )?;
-      let definition = match &task {
-        deno_config::Task::Definition(definition) => definition,
-        deno_config::Task::Commented { definition, .. } => definition,
-      };
-      if let deno_config::Task::Commented { comments, .. } = &task {
+      if let Some(description) = &task.description {
         let slash_slash = colors::italic_gray("//");
-        for comment in comments {
-          writeln!(
-            writer,
-            "    {slash_slash} {}",
-            colors::italic_gray(comment)
-          )?;
+        for line in description.split('\n') {
+          writeln!(writer, "    {slash_slash} {}", colors::italic_gray(line))?;
         }
       }
-      writeln!(writer, "    {definition}")?;
+      writeln!(writer, "    {}", task.command)?;
     }
   }
 
   Ok(())
 }
 
+fn print_task_completions(
+  writer: &mut dyn std::io::Write,
+  tasks: &[TaskListItem],
+) -> Result<(), std::io::Error> {
+  for task in tasks {
+    // shells show a single line next to the completion
+    let description = task
+      .description
+      .as_deref()
+      .and_then(|description| description.lines().next())
+      .unwrap_or_default();
+    writeln!(writer, "{}\t{}", task.name, description)?;
+  }
+  Ok(())
+}
+
 struct NpxCommand;
 
 impl ShellCommand for NpxCommand {
@@ -85,9 +85,11 @@ pub fn format_test_error(js_error: &JsError) -> String {
 
 pub fn format_sanitizer_diff(
   diff: RuntimeActivityDiff,
+  timeline: Option<&TestResourceTimeline>,
 ) -> (Vec<String>, Vec<String>) {
-  let (mut messages, trailers) = format_sanitizer_accum(diff.appeared, true);
-  let disappeared = format_sanitizer_accum(diff.disappeared, false);
+  let (mut messages, trailers) =
+    format_sanitizer_accum(diff.appeared, true, timeline);
+  let disappeared = format_sanitizer_accum(diff.disappeared, false, None);
   messages.extend(disappeared.0);
   messages.sort();
   let mut trailers = BTreeSet::from_iter(trailers);
@@ -95,26 +97,76 @@ pub fn format_sanitizer_diff(
   (messages, trailers.into_iter().collect::<Vec<_>>())
 }
 
+pub(super) fn format_environment_diff(
+  before: &TestEnvironmentState,
+  after: &TestEnvironmentState,
+  traces: &TestEnvironmentTraces,
+) -> Vec<String> {
+  let with_trace = |message: String, name: &str| match traces.get(name) {
+    Some(trace) => format!("{message} It was last changed here:\n{trace}"),
+    None => message,
+  };
+  let mut output = vec![];
+  for (key, value) in &after.vars {
+    let name = key.to_string_lossy();
+    match before.vars.get(key) {
+      None => output.push(with_trace(format!("The environment variable \"{name}\" was set during the test, but not restored. Remove it with `Deno.env.delete(\"{name}\")` before the test finishes."), &name)),
+      Some(before_value) if before_value != value => output.push(with_trace(format!("The environment variable \"{name}\" was changed during the test, but not restored. Set it back to its original value before the test finishes."), &name)),
+      Some(_) => {}
+    }
+  }
+  for key in before.vars.keys() {
+    if !after.vars.contains_key(key) {
+      let name = key.to_string_lossy();
+      output.push(with_trace(format!("The environment variable \"{name}\" was deleted during the test, but not restored. Set it back to its original value before the test finishes."), &name));
+    }
+  }
+  if before.cwd != after.cwd {
+    let format_cwd = |cwd: &Option<PathBuf>| match cwd {
+      Some(cwd) => format!("\"{}\"", cwd.display()),
+      None => "an unknown directory".to_string(),
+    };
+    output.push(format!(
+      "The current working directory was changed from {} to {} during the test, but not restored. Change it back with `Deno.chdir()` before the test finishes.",
+      format_cwd(&before.cwd),
+      format_cwd(&after.cwd),
+    ));
+  }
+  output
+}
+
 fn format_sanitizer_accum(
   activities: Vec<RuntimeActivity>,
   appeared: bool,
+  timeline: Option<&TestResourceTimeline>,
 ) -> (Vec<String>, Vec<String>) {
   // Aggregate the sanitizer information
   let mut accum = HashMap::new();
   for activity in activities {
+    let origin = match (&activity, timeline) {
+      (RuntimeActivity::Resource(rid, _, name), Some(timeline)) => {
+        timeline.origin(*rid, pretty_resource_name(name).1)
+      }
+      _ => None,
+    };
     let item = format_sanitizer_accum_item(activity);
-    accum.entry(item).or_insert(0).add_assign(1);
+    accum.entry((item, origin)).or_insert(0).add_assign(1);
   }
 
   let mut output = vec![];
   let mut needs_trace_leaks = false;
-  for ((item_type, item_name, trace), count) in accum.into_iter() {
+  for (((item_type, item_name, trace), origin), count) in accum.into_iter() {
     if item_type == RuntimeActivityType::Resource {
       let (name, action1, action2) = pretty_resource_name(&item_name);
       let hint = resource_close_hint(&item_name);
 
       let value = if appeared {
-        format!("{name} was {action1} during the test, but not {action2} during the test. {hint}")
+        let mut value = format!("{name} was {action1} during the test, but not {action2} during the test. {hint}");
+        if let Some(origin) = origin {
+          value += " ";
+          value += &origin;
+        }
+        value
       } else {
         format!("{name} was {action1} before the test started, but was {action2} during the test. \
           Do not close resources in a test that were not created during that test.")
@@ -219,6 +271,7 @@ fn pretty_resource_name(
 ) -> (Cow<'static, str>, &'static str, &'static str) {
   let (name, action1, action2) = match name {
     "fsFile" => ("A file", "opened", "closed"),
+    "readDir" => ("A directory iterator", "opened", "closed"),
     "fetchRequest" => ("A fetch request", "started", "finished"),
     "fetchRequestBody" => ("A fetch request body", "created", "closed"),
     "fetchResponse" => ("A fetch response body", "created", "consumed"),
@@ -323,7 +376,8 @@ pub const OP_DETAILS: phf::Map<&'static str, [&'static str; 2]> = phf_map! {
   "op_fs_make_temp_file_async" => ["create a temporary file", "awaiting the result of a `Deno.makeTempFile` call"],
   "op_fs_mkdir_async" => ["create a directory", "awaiting the result of a `Deno.mkdir` call"],
   "op_fs_open_async" => ["open a file", "awaiting the result of a `Deno.open` call"],
-  "op_fs_read_dir_async" => ["read a directory", "collecting all items in the async iterable returned from a `Deno.readDir` call"],
+  "op_fs_read_dir_next_async" => ["read a directory", "collecting all items in the async iterable returned from a `Deno.readDir` call"],
+  "op_fs_read_dir_open_async" => ["open a directory", "collecting all items in the async iterable returned from a `Deno.readDir` call"],
   "op_fs_read_file_async" => ["read a file", "awaiting the result of a `Deno.readFile` call"],
   "op_fs_read_file_text_async" => ["read a text file", "awaiting the result of a `Deno.readTextFile` call"],
   "op_fs_read_link_async" => ["read a symlink", "awaiting the result of a `Deno.readLink` call"],
@@ -380,7 +434,7 @@ mod tests {
     ($name:ident, $appeared:literal, [$($activity:expr),*], $expected:literal) => {
       #[test]
       fn $name() {
-        let (leaks, trailer_notes) = super::format_sanitizer_accum(vec![$($activity),*], $appeared);
+        let (leaks, trailer_notes) = super::format_sanitizer_accum(vec![$($activity),*], $appeared, None);
         let mut output = String::new();
         for leak in leaks {
           output += &format!(" - {leak}\n");
@@ -398,4 +452,83 @@ mod tests {
   leak_format_test!(op_unknown, true, [RuntimeActivity::AsyncOp(0, None, "op_unknown")], 
     " - An async call to op_unknown was started in this test, but never completed.\n\
     To get more details where leaks occurred, run again with the --trace-leaks flag.\n");
+
+  #[test]
+  fn resource_origin() {
+    use super::super::ResourceSample;
+    use super::super::TestResourceTimeline;
+    use super::super::TimelinePoint;
+    use std::time::Duration;
+
+    let sample = |point, ms, rids: &[u32]| ResourceSample {
+      point,
+      elapsed: Duration::from_millis(ms),
+      rids: rids.iter().copied().collect(),
+    };
+    let timeline = TestResourceTimeline {
+      samples: vec![
+        sample(TimelinePoint::TestStart, 0, &[0]),
+        sample(TimelinePoint::StepStart("read".into()), 1, &[0, 3]),
+        sample(TimelinePoint::StepEnd("read".into()), 5, &[0, 3, 4]),
+        sample(TimelinePoint::TestEnd, 6, &[0, 3, 4]),
+      ],
+      ..Default::default()
+    };
+    let leak = |rid| {
+      let activity = RuntimeActivity::Resource(rid, None, "fsFile".into());
+      super::format_sanitizer_accum(vec![activity], true, Some(&timeline)).0
+    };
+
+    assert_eq!(
+      leak(4),
+      vec!["A file was opened during the test, but not closed during the test. Close the file handle by calling `file.close()`. It was opened between the start of the step \"read\" and the end of the step \"read\", 1ms to 5ms into the test."]
+    );
+    assert_eq!(
+      leak(3),
+      vec!["A file was opened during the test, but not closed during the test. Close the file handle by calling `file.close()`. It was opened between the start of the test and the start of the step \"read\", 0ms to 1ms into the test."]
+    );
+    assert_eq!(
+      leak(7),
+      vec!["A file was opened during the test, but not closed during the test. Close the file handle by calling `file.close()`. It was opened after the end of the test, 6ms into the test."]
+    );
+  }
+
+  #[test]
+  fn environment_diff() {
+    use super::super::TestEnvironmentState;
+    use super::super::TestEnvironmentTraces;
+    use std::path::PathBuf;
+
+    let traces = TestEnvironmentTraces::default();
+
+    let before = TestEnvironmentState {
+      vars: [("A".into(), "1".into()), ("B".into(), "2".into())].into(),
+      cwd: Some(PathBuf::from("/a")),
+    };
+    assert!(
+      super::format_environment_diff(&before, &before, &traces).is_empty()
+    );
+
+    let after = TestEnvironmentState {
+      vars: [("A".into(), "3".into()), ("C".into(), "4".into())].into(),
+      cwd: Some(PathBuf::from("/b")),
+    };
+    assert_eq!(
+      super::format_environment_diff(&before, &after, &traces),
+      vec![
+        "The environment variable \"A\" was changed during the test, but not restored. Set it back to its original value before the test finishes.",
+        "The environment variable \"C\" was set during the test, but not restored. Remove it with `Deno.env.delete(\"C\")` before the test finishes.",
+        "The environment variable \"B\" was deleted during the test, but not restored. Set it back to its original value before the test finishes.",
+        "The current working directory was changed from \"/a\" to \"/b\" during the test, but not restored. Change it back with `Deno.chdir()` before the test finishes.",
+      ]
+    );
+
+    let traces = TestEnvironmentTraces(
+      [("C".into(), "    at file:///a.ts:1:10".into())].into(),
+    );
+    assert_eq!(
+      super::format_environment_diff(&before, &after, &traces)[1],
+      "The environment variable \"C\" was set during the test, but not restored. Remove it with `Deno.env.delete(\"C\")` before the test finishes. It was last changed here:\n    at file:///a.ts:1:10",
+    );
+  }
 }
@@ -23,7 +23,18 @@ use crate::util::path::matches_pattern_or_exact_path;
 use crate::worker::CliMainWorkerFactory;
 use crate::worker::CoverageCollector;
 
+use deno_ast::swc::ast::Decl;
+use deno_ast::swc::ast::ExportSpecifier;
+use deno_ast::swc::ast::Ident;
+use deno_ast::swc::ast::ImportSpecifier;
+use deno_ast::swc::ast::Module;
+use deno_ast::swc::ast::ModuleDecl;
+use deno_ast::swc::ast::ModuleExportName;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::Stmt;
 use deno_ast::swc::common::comments::CommentKind;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
 use deno_ast::MediaType;
 use deno_ast::SourceRangedForSpanned;
 use deno_config::glob::FilePatterns;
@@ -52,6 +63,7 @@ use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
 use deno_core::PollEventLoopOptions;
+use deno_core::ResourceId;
 use deno_runtime::deno_io::Stdio;
 use deno_runtime::deno_io::StdioPipe;
 use deno_runtime::fmt_errors::format_js_error;
@@ -68,17 +80,20 @@ use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use regex::Regex;
 use serde::Deserialize;
+use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ffi::OsString;
 use std::fmt::Write as _;
 use std::future::poll_fn;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -97,10 +112,12 @@ pub use channel::create_test_event_channel;
 pub use channel::TestEventReceiver;
 pub use channel::TestEventSender;
 pub use channel::TestEventWorkerSender;
+use fmt::format_environment_diff;
 use fmt::format_sanitizer_diff;
 pub use fmt::format_test_error;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
+use reporters::JsonlTestReporter;
 use reporters::JunitTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
@@ -201,7 +218,7 @@ impl TestFilter {
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct TestLocation {
   pub file_name: String,
@@ -265,6 +282,8 @@ pub struct TestDescription {
   pub location: TestLocation,
   pub sanitize_ops: bool,
   pub sanitize_resources: bool,
+  pub sanitize_child_processes: bool,
+  pub sanitize_env: bool,
 }
 
 /// May represent a failure of a test or test step.
@@ -511,6 +530,7 @@ struct TestSpecifiersOptions {
   specifier: TestSpecifierOptions,
   reporter: TestReporterConfig,
   junit_path: Option<String>,
+  output_path: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -542,7 +562,9 @@ impl TestSummary {
   }
 }
 
-fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
+fn get_test_reporter(
+  options: &TestSpecifiersOptions,
+) -> Result<Box<dyn TestReporter>, AnyError> {
   let parallel = options.concurrent_jobs.get() > 1;
   let reporter: Box<dyn TestReporter> = match &options.reporter {
     TestReporterConfig::Dot => {
@@ -564,15 +586,21 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
     )),
   };
 
+  let mut reporters = vec![reporter];
   if let Some(junit_path) = &options.junit_path {
-    let junit = Box::new(JunitTestReporter::new(
+    reporters.push(Box::new(JunitTestReporter::new(
       options.cwd.clone(),
       junit_path.to_string(),
-    ));
-    return Box::new(CompoundTestReporter::new(vec![reporter, junit]));
+    )));
+  }
+  if let Some(output_path) = &options.output_path {
+    reporters.push(Box::new(JsonlTestReporter::new(output_path)?));
   }
 
-  reporter
+  if reporters.len() == 1 {
+    return Ok(reporters.pop().unwrap());
+  }
+  Ok(Box::new(CompoundTestReporter::new(reporters)))
 }
 
 async fn configure_main_worker(
@@ -604,6 +632,13 @@ async fn configure_main_worker(
   }
   let res = worker.execute_side_module_possibly_with_npm().await;
   let mut worker = worker.into_main_worker();
+  if options.trace_leaks {
+    worker
+      .js_runtime
+      .op_state()
+      .borrow_mut()
+      .put(TestResourceTimeline::default());
+  }
   match res {
     Ok(()) => Ok(()),
     Err(error) => {
@@ -909,6 +944,12 @@ async fn run_tests_for_worker_inner(
 
     // We always capture stats, regardless of sanitization state
     let before = stats.clone().capture(&filter);
+    let before_environment =
+      desc.sanitize_env.then(TestEnvironmentState::capture);
+    if desc.sanitize_env {
+      state_rc.borrow_mut().put(TestEnvironmentTraces::default());
+    }
+    TestResourceTimeline::test_started(&mut state_rc.borrow_mut());
 
     let earlier = Instant::now();
     let call = worker.js_runtime.call(&function);
@@ -940,6 +981,8 @@ async fn run_tests_for_worker_inner(
       }
     };
 
+    TestResourceTimeline::test_ended(&mut state_rc.borrow_mut());
+
     // Check the result before we check for leaks
     let result = {
       let scope = &mut worker.js_runtime.handle_scope();
@@ -957,6 +1000,8 @@ async fn run_tests_for_worker_inner(
     }
 
     // Await activity stabilization
+    let mut leaks = vec![];
+    let mut trailer_notes = vec![];
     if let Some(diff) = wait_for_activity_to_stabilize(
       worker,
       &stats,
@@ -965,24 +1010,34 @@ async fn run_tests_for_worker_inner(
       before,
       desc.sanitize_ops,
       desc.sanitize_resources,
+      desc.sanitize_child_processes,
     )
     .await?
     {
-      let (formatted, trailer_notes) = format_sanitizer_diff(diff);
-      if !formatted.is_empty() {
-        let failure = TestFailure::Leaked(formatted, trailer_notes);
-        fail_fast_tracker.add_failure();
-        let elapsed = earlier.elapsed().as_millis();
-        send_test_event(
-          &state_rc,
-          TestEvent::Result(
-            desc.id,
-            TestResult::Failed(failure),
-            elapsed as u64,
-          ),
-        )?;
-        continue;
-      }
+      let state = state_rc.borrow();
+      (leaks, trailer_notes) =
+        format_sanitizer_diff(diff, state.try_borrow::<TestResourceTimeline>());
+    }
+    if let Some(before_environment) = before_environment {
+      let traces = state_rc
+        .borrow_mut()
+        .try_take::<TestEnvironmentTraces>()
+        .unwrap_or_default();
+      leaks.extend(format_environment_diff(
+        &before_environment,
+        &TestEnvironmentState::capture(),
+        &traces,
+      ));
+    }
+    if !leaks.is_empty() {
+      let failure = TestFailure::Leaked(leaks, trailer_notes);
+      fail_fast_tracker.add_failure();
+      let elapsed = earlier.elapsed().as_millis();
+      send_test_event(
+        &state_rc,
+        TestEvent::Result(desc.id, TestResult::Failed(failure), elapsed as u64),
+      )?;
+      continue;
     }
 
     let elapsed = earlier.elapsed().as_millis();
@@ -1050,6 +1105,172 @@ fn is_empty(
   map.is_empty()
 }
 
+/// Process-wide state that a test using the environment sanitizer must
+/// restore before it finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TestEnvironmentState {
+  vars: BTreeMap<OsString, OsString>,
+  cwd: Option<PathBuf>,
+}
+
+impl TestEnvironmentState {
+  fn capture() -> Self {
+    Self {
+      vars: std::env::vars_os().collect(),
+      cwd: std::env::current_dir().ok(),
+    }
+  }
+}
+
+/// Where the environment variables were last changed during a test using the
+/// environment sanitizer, by name.
+#[derive(Debug, Default)]
+pub struct TestEnvironmentTraces(HashMap<String, String>);
+
+impl TestEnvironmentTraces {
+  pub fn record(state: &mut OpState, name: String, trace: String) {
+    if let Some(traces) = state.try_borrow_mut::<Self>() {
+      traces.0.insert(name, trace);
+    }
+  }
+
+  fn get(&self, name: &str) -> Option<&str> {
+    self.0.get(name).map(String::as_str)
+  }
+}
+
+/// A point of a test at which the open resources are sampled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TimelinePoint {
+  TestStart,
+  StepStart(String),
+  StepEnd(String),
+  TestEnd,
+}
+
+impl std::fmt::Display for TimelinePoint {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::TestStart => write!(f, "the start of the test"),
+      Self::StepStart(name) => write!(f, "the start of the step \"{name}\""),
+      Self::StepEnd(name) => write!(f, "the end of the step \"{name}\""),
+      Self::TestEnd => write!(f, "the end of the test"),
+    }
+  }
+}
+
+#[derive(Debug)]
+struct ResourceSample {
+  point: TimelinePoint,
+  elapsed: Duration,
+  rids: HashSet<ResourceId>,
+}
+
+/// With `--trace-leaks`, the resources that are open when a test and each of
+/// its steps start and end. Resources have no stack trace like ops and
+/// timers do, this is what tells where a leaked one was created.
+#[derive(Debug, Default)]
+pub struct TestResourceTimeline {
+  step_names: HashMap<usize, String>,
+  start: Option<Instant>,
+  samples: Vec<ResourceSample>,
+}
+
+impl TestResourceTimeline {
+  pub fn register_step(state: &mut OpState, id: usize, name: &str) {
+    if let Some(timeline) = state.try_borrow_mut::<Self>() {
+      timeline.step_names.insert(id, name.to_string());
+    }
+  }
+
+  pub fn step_started(state: &mut OpState, id: usize) {
+    Self::sample_step(state, id, TimelinePoint::StepStart);
+  }
+
+  pub fn step_ended(state: &mut OpState, id: usize) {
+    Self::sample_step(state, id, TimelinePoint::StepEnd);
+  }
+
+  fn test_started(state: &mut OpState) {
+    Self::sample(state, TimelinePoint::TestStart);
+  }
+
+  fn test_ended(state: &mut OpState) {
+    Self::sample(state, TimelinePoint::TestEnd);
+  }
+
+  fn sample_step(
+    state: &mut OpState,
+    id: usize,
+    point: fn(String) -> TimelinePoint,
+  ) {
+    let Some(timeline) = state.try_borrow::<Self>() else {
+      return;
+    };
+    let name = timeline.step_names.get(&id).cloned().unwrap_or_default();
+    Self::sample(state, point(name));
+  }
+
+  fn sample(state: &mut OpState, point: TimelinePoint) {
+    if !state.has::<Self>() {
+      return;
+    }
+    let rids = state.resource_table.names().map(|(rid, _)| rid).collect();
+    let timeline = state.borrow_mut::<Self>();
+    if point == TimelinePoint::TestStart {
+      timeline.start = Some(Instant::now());
+      timeline.samples.clear();
+    }
+    let elapsed = timeline
+      .start
+      .map(|start| start.elapsed())
+      .unwrap_or_default();
+    timeline.samples.push(ResourceSample {
+      point,
+      elapsed,
+      rids,
+    });
+  }
+
+  /// Tells when a resource that appeared during the test was created, between
+  /// the last sample without it and the first one with it.
+  fn origin(&self, rid: ResourceId, action: &str) -> Option<String> {
+    match self.samples.iter().position(|s| s.rids.contains(&rid)) {
+      // It was already open when the test started.
+      Some(0) => None,
+      Some(i) => {
+        let (before, after) = (&self.samples[i - 1], &self.samples[i]);
+        Some(format!(
+          "It was {action} between {} and {}, {}ms to {}ms into the test.",
+          before.point,
+          after.point,
+          before.elapsed.as_millis(),
+          after.elapsed.as_millis(),
+        ))
+      }
+      None => {
+        let last = self.samples.last()?;
+        Some(format!(
+          "It was {action} after {}, {}ms into the test.",
+          last.point,
+          last.elapsed.as_millis(),
+        ))
+      }
+    }
+  }
+}
+
+/// Whether the activity belongs to a spawned child process, which the child
+/// process sanitizer checks even when op or resource sanitization is off.
+fn is_child_process_activity(activity: &RuntimeActivity) -> bool {
+  match activity {
+    RuntimeActivity::AsyncOp(_, _, name) => *name == "op_spawn_wait",
+    RuntimeActivity::Resource(_, _, name) => name == "child",
+    _ => false,
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn wait_for_activity_to_stabilize(
   worker: &mut MainWorker,
   stats: &RuntimeActivityStatsFactory,
@@ -1058,6 +1279,7 @@ async fn wait_for_activity_to_stabilize(
   before: RuntimeActivityStats,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  sanitize_child_processes: bool,
 ) -> Result<Option<RuntimeActivityDiff>, AnyError> {
   // First, check to see if there's any diff at all. If not, just continue.
   let after = stats.clone().capture(filter);
@@ -1082,21 +1304,36 @@ async fn wait_for_activity_to_stabilize(
     }
   }
 
+  let keep_child_process = |activity: &RuntimeActivity| {
+    sanitize_child_processes && is_child_process_activity(activity)
+  };
   if !sanitize_ops {
-    diff
-      .appeared
-      .retain(|activity| !matches!(activity, RuntimeActivity::AsyncOp(..)));
-    diff
-      .disappeared
-      .retain(|activity| !matches!(activity, RuntimeActivity::AsyncOp(..)));
+    diff.appeared.retain(|activity| {
+      !matches!(activity, RuntimeActivity::AsyncOp(..))
+        || keep_child_process(activity)
+    });
+    diff.disappeared.retain(|activity| {
+      !matches!(activity, RuntimeActivity::AsyncOp(..))
+        || keep_child_process(activity)
+    });
   }
   if !sanitize_resources {
+    diff.appeared.retain(|activity| {
+      !matches!(activity, RuntimeActivity::Resource(..))
+        || keep_child_process(activity)
+    });
+    diff.disappeared.retain(|activity| {
+      !matches!(activity, RuntimeActivity::Resource(..))
+        || keep_child_process(activity)
+    });
+  }
+  if !sanitize_child_processes {
     diff
       .appeared
-      .retain(|activity| !matches!(activity, RuntimeActivity::Resource(..)));
+      .retain(|activity| !is_child_process_activity(activity));
     diff
       .disappeared
-      .retain(|activity| !matches!(activity, RuntimeActivity::Resource(..)));
+      .retain(|activity| !is_child_process_activity(activity));
   }
 
   // Since we don't have an option to disable timer sanitization, we use sanitize_ops == false &&
@@ -1123,14 +1360,23 @@ async fn wait_for_activity_to_stabilize(
   })
 }
 
+/// A code block extracted from the documentation of a module.
+struct DocExample {
+  file: File,
+  /// Whether the block has the `test` attribute, in which case it's wrapped
+  /// in a `Deno.test` so that it's run besides being type checked.
+  is_test: bool,
+}
+
 fn extract_files_from_regex_blocks(
   specifier: &ModuleSpecifier,
   source: &str,
   media_type: MediaType,
   file_line_index: usize,
+  exports: &BTreeSet<String>,
   blocks_regex: &Regex,
   lines_regex: &Regex,
-) -> Result<Vec<File>, AnyError> {
+) -> Result<Vec<DocExample>, AnyError> {
   let files = blocks_regex
     .captures_iter(source)
     .filter_map(|block| {
@@ -1140,10 +1386,12 @@ fn extract_files_from_regex_blocks(
         .get(1)
         .map(|attributes| attributes.as_str().split(' ').collect());
 
+      let mut is_test = false;
       let file_media_type = if let Some(attributes) = maybe_attributes {
         if attributes.contains(&"ignore") {
           return None;
         }
+        is_test = attributes.contains(&"test");
 
         match attributes.first() {
           Some(&"js") => MediaType::JavaScript,
@@ -1183,22 +1431,41 @@ fn extract_files_from_regex_blocks(
         writeln!(file_source, "{}", text.as_str()).unwrap();
       }
 
-      let file_specifier = ModuleSpecifier::parse(&format!(
-        "{}${}-{}",
-        specifier,
+      let lines = format!(
+        "{}-{}",
         file_line_index + line_offset + 1,
         file_line_index + line_offset + line_count + 1,
-      ))
-      .unwrap();
+      );
+      let file_specifier =
+        ModuleSpecifier::parse(&format!("{}${}", specifier, lines)).unwrap();
       let file_specifier =
         mapped_specifier_for_tsc(&file_specifier, file_media_type)
           .map(|s| ModuleSpecifier::parse(&s).unwrap())
           .unwrap_or(file_specifier);
 
-      Some(File {
-        specifier: file_specifier,
-        maybe_headers: None,
-        source: file_source.into_bytes().into(),
+      let test_name = is_test.then(|| {
+        let file_name = specifier
+          .path_segments()
+          .and_then(|mut segments| segments.next_back())
+          .unwrap_or_default();
+        format!("{}${}", file_name, lines)
+      });
+      let file_source = prepare_example(
+        file_source,
+        &file_specifier,
+        file_media_type,
+        specifier,
+        exports,
+        test_name.as_deref(),
+      );
+
+      Some(DocExample {
+        file: File {
+          specifier: file_specifier,
+          maybe_headers: None,
+          source: file_source.into_bytes().into(),
+        },
+        is_test,
       })
     })
     .collect();
@@ -1206,11 +1473,187 @@ fn extract_files_from_regex_blocks(
   Ok(files)
 }
 
+/// Adds the imports an example depends on implicitly and wraps it in a test
+/// when `test_name` is given. The positions of the example's own code are
+/// kept where possible, so that diagnostics point at the lines of the block.
+///
+/// Examples that fail to parse are returned as is, so that type checking
+/// reports the error.
+fn prepare_example(
+  source: String,
+  specifier: &ModuleSpecifier,
+  media_type: MediaType,
+  documented_specifier: &ModuleSpecifier,
+  exports: &BTreeSet<String>,
+  test_name: Option<&str>,
+) -> String {
+  let Ok(parsed_source) = deno_ast::parse_module(deno_ast::ParseParams {
+    specifier: specifier.clone(),
+    text_info: deno_ast::SourceTextInfo::from_string(source.clone()),
+    media_type,
+    capture_tokens: false,
+    maybe_syntax: None,
+    scope_analysis: false,
+  }) else {
+    return source;
+  };
+  let text_start = parsed_source.text_info().range().start;
+
+  let mut bound_names = HashSet::new();
+  let mut imports = Vec::new();
+  let mut first_statement = None;
+  for item in &parsed_source.module().body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+        for specifier in &import.specifiers {
+          let local = match specifier {
+            ImportSpecifier::Named(named) => &named.local,
+            ImportSpecifier::Default(default) => &default.local,
+            ImportSpecifier::Namespace(namespace) => &namespace.local,
+          };
+          bound_names.insert(local.sym.to_string());
+        }
+        imports.push(import.range().as_byte_range(text_start));
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+        bound_names.extend(decl_names(&export.decl));
+      }
+      ModuleItem::Stmt(Stmt::Decl(decl)) => {
+        bound_names.extend(decl_names(decl));
+      }
+      _ => {}
+    }
+    if !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))) {
+      first_statement
+        .get_or_insert_with(|| item.range().as_byte_range(text_start).start);
+    }
+  }
+
+  // Imports the exports of the documented module that the example refers to
+  // without binding them itself, on the first line so no line moves.
+  let mut used_names = UsedNames::default();
+  parsed_source.module().visit_with(&mut used_names);
+  let inferred_names = exports
+    .iter()
+    .filter(|name| used_names.0.contains(*name) && !bound_names.contains(*name))
+    .map(String::as_str)
+    .collect::<Vec<_>>();
+  let mut prepared = String::new();
+  if !inferred_names.is_empty() {
+    write!(
+      prepared,
+      "import {{ {} }} from \"{}\"; ",
+      inferred_names.join(", "),
+      documented_specifier
+    )
+    .unwrap();
+  }
+
+  let Some(test_name) = test_name else {
+    prepared.push_str(&source);
+    return prepared;
+  };
+
+  // The imports stay at the top level, which only keeps the positions when
+  // they come before the rest of the code. Otherwise they're hoisted, as they
+  // would be when evaluating the module anyway.
+  let body_start = imports.last().map(|range| range.end).unwrap_or(0);
+  let body = if first_statement.is_some_and(|start| start < body_start) {
+    let mut body = String::new();
+    let mut position = 0;
+    for range in &imports {
+      writeln!(prepared, "{}", &source[range.clone()]).unwrap();
+      body.push_str(&source[position..range.start]);
+      position = range.end;
+    }
+    body.push_str(&source[position..]);
+    body
+  } else {
+    prepared.push_str(&source[..body_start]);
+    source[body_start..].to_string()
+  };
+  write!(
+    prepared,
+    "Deno.test({}, async () => {{{}\n}});\n",
+    deno_core::serde_json::to_string(test_name).unwrap(),
+    body
+  )
+  .unwrap();
+  prepared
+}
+
+/// Collects the names of the identifiers referred to in a module.
+#[derive(Default)]
+struct UsedNames(HashSet<String>);
+
+impl Visit for UsedNames {
+  fn visit_ident(&mut self, ident: &Ident) {
+    self.0.insert(ident.sym.to_string());
+  }
+}
+
+/// The names bound by a declaration, not counting the ones of destructuring
+/// patterns.
+fn decl_names(decl: &Decl) -> Vec<String> {
+  match decl {
+    Decl::Class(class) => vec![class.ident.sym.to_string()],
+    Decl::Fn(function) => vec![function.ident.sym.to_string()],
+    Decl::Var(var) => var
+      .decls
+      .iter()
+      .filter_map(|declarator| declarator.name.as_ident())
+      .map(|ident| ident.id.sym.to_string())
+      .collect(),
+    Decl::Using(using) => using
+      .decls
+      .iter()
+      .filter_map(|declarator| declarator.name.as_ident())
+      .map(|ident| ident.id.sym.to_string())
+      .collect(),
+    Decl::TsInterface(interface) => vec![interface.id.sym.to_string()],
+    Decl::TsTypeAlias(alias) => vec![alias.id.sym.to_string()],
+    Decl::TsEnum(ts_enum) => vec![ts_enum.id.sym.to_string()],
+    Decl::TsModule(_) => vec![],
+  }
+}
+
+/// The names exported by a module, which examples in its documentation can
+/// use without importing them. Default and star exports aren't included.
+fn exported_names(module: &Module) -> BTreeSet<String> {
+  let mut names = BTreeSet::new();
+  for item in &module.body {
+    let ModuleItem::ModuleDecl(decl) = item else {
+      continue;
+    };
+    match decl {
+      ModuleDecl::ExportDecl(export) => names.extend(decl_names(&export.decl)),
+      ModuleDecl::ExportNamed(export) => {
+        for specifier in &export.specifiers {
+          let name = match specifier {
+            ExportSpecifier::Named(named) => {
+              named.exported.as_ref().unwrap_or(&named.orig)
+            }
+            ExportSpecifier::Namespace(namespace) => &namespace.name,
+            ExportSpecifier::Default(_) => continue,
+          };
+          if let ModuleExportName::Ident(ident) = name {
+            if &*ident.sym != "default" {
+              names.insert(ident.sym.to_string());
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+  names
+}
+
 fn extract_files_from_source_comments(
   specifier: &ModuleSpecifier,
   source: Arc<str>,
   media_type: MediaType,
-) -> Result<Vec<File>, AnyError> {
+) -> Result<Vec<DocExample>, AnyError> {
   let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
     specifier: specifier.clone(),
     text_info: deno_ast::SourceTextInfo::new(source),
@@ -1220,6 +1663,7 @@ fn extract_files_from_source_comments(
     scope_analysis: false,
   })?;
   let comments = parsed_source.comments().get_vec();
+  let exports = exported_names(parsed_source.module());
   let blocks_regex = lazy_regex::regex!(r"```([^\r\n]*)\r?\n([\S\s]*?)```");
   let lines_regex = lazy_regex::regex!(r"(?:\* ?)(?:\# ?)?(.*)");
 
@@ -1238,6 +1682,7 @@ fn extract_files_from_source_comments(
         &comment.text,
         media_type,
         parsed_source.text_info().line_index(comment.start()),
+        &exports,
         blocks_regex,
         lines_regex,
       )
@@ -1252,7 +1697,7 @@ fn extract_files_from_fenced_blocks(
   specifier: &ModuleSpecifier,
   source: &str,
   media_type: MediaType,
-) -> Result<Vec<File>, AnyError> {
+) -> Result<Vec<DocExample>, AnyError> {
   // The pattern matches code blocks as well as anything in HTML comment syntax,
   // but it stores the latter without any capturing groups. This way, a simple
   // check can be done to see if a block is inside a comment (and skip typechecking)
@@ -1266,6 +1711,7 @@ fn extract_files_from_fenced_blocks(
     source,
     media_type,
     /* file line index */ 0,
+    &BTreeSet::new(),
     blocks_regex,
     lines_regex,
   )
@@ -1274,7 +1720,7 @@ fn extract_files_from_fenced_blocks(
 async fn fetch_inline_files(
   file_fetcher: &FileFetcher,
   specifiers: Vec<ModuleSpecifier>,
-) -> Result<Vec<File>, AnyError> {
+) -> Result<Vec<DocExample>, AnyError> {
   let mut files = Vec::new();
   for specifier in specifiers {
     let fetch_permissions = PermissionsContainer::allow_all();
@@ -1304,11 +1750,14 @@ async fn fetch_inline_files(
 }
 
 /// Type check a collection of module and document specifiers.
+///
+/// Returns the specifiers of the documentation examples that are run as
+/// tests, see [DocExample::is_test].
 pub async fn check_specifiers(
   file_fetcher: &FileFetcher,
   main_graph_container: &Arc<MainModuleGraphContainer>,
   specifiers: Vec<(ModuleSpecifier, TestMode)>,
-) -> Result<(), AnyError> {
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
   let inline_files = fetch_inline_files(
     file_fetcher,
     specifiers
@@ -1335,12 +1784,19 @@ pub async fn check_specifiers(
     })
     .collect::<Vec<_>>();
 
+  let mut example_specifiers = Vec::new();
   if !inline_files.is_empty() {
-    module_specifiers
-      .extend(inline_files.iter().map(|file| file.specifier.clone()));
+    module_specifiers.extend(
+      inline_files
+        .iter()
+        .map(|example| example.file.specifier.clone()),
+    );
 
-    for file in inline_files {
-      file_fetcher.insert_memory_files(file);
+    for example in inline_files {
+      if example.is_test {
+        example_specifiers.push(example.file.specifier.clone());
+      }
+      file_fetcher.insert_memory_files(example.file);
     }
   }
 
@@ -1348,7 +1804,7 @@ pub async fn check_specifiers(
     .check_specifiers(&module_specifiers)
     .await?;
 
-  Ok(())
+  Ok(example_specifiers)
 }
 
 static HAS_TEST_RUN_SIGINT_HANDLER: AtomicBool = AtomicBool::new(false);
@@ -1379,7 +1835,7 @@ async fn test_specifiers(
     cancel_sender.send(TestEvent::Sigint).ok();
   });
   HAS_TEST_RUN_SIGINT_HANDLER.store(true, Ordering::Relaxed);
-  let reporter = get_test_reporter(&options);
+  let reporter = get_test_reporter(&options)?;
   let fail_fast_tracker = FailFastTracker::new(options.fail_fast);
 
   let join_handles = specifiers.into_iter().map(move |specifier| {
@@ -1715,7 +2171,7 @@ pub async fn run_tests(
 
   let main_graph_container = factory.main_module_graph_container().await?;
 
-  check_specifiers(
+  let example_specifiers = check_specifiers(
     file_fetcher,
     main_graph_container,
     specifiers_with_mode.clone(),
@@ -1738,6 +2194,7 @@ pub async fn run_tests(
         TestMode::Documentation => None,
         _ => Some(s),
       })
+      .chain(example_specifiers)
       .collect(),
     TestSpecifiersOptions {
       cwd: Url::from_directory_path(cli_options.initial_cwd()).map_err(
@@ -1754,6 +2211,7 @@ pub async fn run_tests(
       filter: test_options.filter.is_some(),
       reporter: test_options.reporter,
       junit_path: test_options.junit_path,
+      output_path: test_options.output_path,
       specifier: TestSpecifierOptions {
         filter: TestFilter::from_flag(&test_options.filter),
         shuffle: test_options.shuffle,
@@ -1869,7 +2327,7 @@ pub async fn run_tests_with_watch(
 
         let main_graph_container =
           factory.main_module_graph_container().await?;
-        check_specifiers(
+        let example_specifiers = check_specifiers(
           file_fetcher,
           main_graph_container,
           specifiers_with_mode.clone(),
@@ -1889,6 +2347,7 @@ pub async fn run_tests_with_watch(
               TestMode::Documentation => None,
               _ => Some(s),
             })
+            .chain(example_specifiers)
             .collect(),
           TestSpecifiersOptions {
             cwd: Url::from_directory_path(cli_options.initial_cwd()).map_err(
@@ -1905,6 +2364,7 @@ pub async fn run_tests_with_watch(
             filter: test_options.filter.is_some(),
             reporter: test_options.reporter,
             junit_path: test_options.junit_path,
+            output_path: test_options.output_path,
             specifier: TestSpecifierOptions {
               filter: TestFilter::from_flag(&test_options.filter),
               shuffle: test_options.shuffle,
@@ -1965,6 +2425,89 @@ mod inner_test {
 
   use super::*;
 
+  fn prepare(source: &str, test_name: Option<&str>) -> String {
+    let exports = ["Foo", "check", "unused"]
+      .into_iter()
+      .map(String::from)
+      .collect();
+    prepare_example(
+      source.to_string(),
+      &ModuleSpecifier::parse("file:///mod.ts$1-4.ts").unwrap(),
+      MediaType::TypeScript,
+      &ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      &exports,
+      test_name,
+    )
+  }
+
+  #[test]
+  fn test_prepare_example_infers_imports() {
+    assert_eq!(
+      prepare("const value: Foo = check();\n", None),
+      "import { Foo, check } from \"file:///mod.ts\"; const value: Foo = check();\n"
+    );
+    // names the example binds itself are left alone
+    let source =
+      "import { check } from \"./mod.ts\";\nfunction Foo() {}\ncheck(Foo);\n";
+    assert_eq!(prepare(source, None), source);
+    // invalid examples are reported by type checking
+    assert_eq!(prepare("check(;\n", Some("test")), "check(;\n");
+  }
+
+  #[test]
+  fn test_prepare_example_wraps_tests() {
+    assert_eq!(
+      prepare(
+        "import { check } from \"./mod.ts\";\nconsole.log(check());\n",
+        Some("mod.ts$1-4")
+      ),
+      "import { check } from \"./mod.ts\";Deno.test(\"mod.ts$1-4\", async () => {\nconsole.log(check());\n\n});\n"
+    );
+    assert_eq!(
+      prepare("await check();\n", Some("test")),
+      "import { check } from \"file:///mod.ts\"; Deno.test(\"test\", async () => {await check();\n\n});\n"
+    );
+    // imports after other code are hoisted
+    assert_eq!(
+      prepare(
+        "console.log(1);\nimport { a } from \"./a.ts\";\n",
+        Some("test")
+      ),
+      "import { a } from \"./a.ts\";\nDeno.test(\"test\", async () => {console.log(1);\n\n\n});\n"
+    );
+  }
+
+  #[test]
+  fn test_exported_names() {
+    let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      text_info: deno_ast::SourceTextInfo::from_string(
+        r#"export function a() {}
+export const b = 1, { c } = {};
+export interface D {}
+export { e as f, g as default };
+export * from "./h.ts";
+export * as ns from "./h.ts";
+export default class I {}
+const e = 1, g = 2;
+"#
+        .to_string(),
+      ),
+      media_type: MediaType::TypeScript,
+      capture_tokens: false,
+      maybe_syntax: None,
+      scope_analysis: false,
+    })
+    .unwrap();
+    assert_eq!(
+      exported_names(parsed_source.module()),
+      ["D", "a", "b", "f", "ns"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    );
+  }
+
   #[test]
   fn test_is_supported_test_ext() {
     assert!(!is_supported_test_ext(Path::new("tests/subdir/redirects")));
@@ -0,0 +1,298 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::fs::File;
+use std::io::BufWriter;
+
+use console_static_text::ansi::strip_ansi_codes;
+use deno_core::serde_json;
+use deno_runtime::fmt_errors::format_js_error;
+use deno_runtime::ops::os::rss;
+use serde::Serialize;
+
+use crate::util::fs::create_file;
+
+use super::*;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonlFailure {
+  message: String,
+  detail: String,
+}
+
+impl From<&TestFailure> for JsonlFailure {
+  fn from(failure: &TestFailure) -> Self {
+    Self {
+      message: strip_ansi_codes(&failure.overview()).into_owned(),
+      detail: strip_ansi_codes(&failure.detail()).into_owned(),
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum JsonlTestEvent<'a> {
+  #[serde(rename_all = "camelCase")]
+  Plan {
+    origin: &'a str,
+    total: usize,
+    filtered_out: usize,
+    used_only: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Register {
+    id: usize,
+    name: &'a str,
+    origin: &'a str,
+    location: &'a TestLocation,
+    ignore: bool,
+    only: bool,
+  },
+  Wait {
+    id: usize,
+  },
+  Output {
+    text: Cow<'a, str>,
+  },
+  #[serde(rename_all = "camelCase")]
+  Result {
+    id: usize,
+    status: &'static str,
+    elapsed: u64,
+    rss: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<JsonlFailure>,
+  },
+  #[serde(rename_all = "camelCase")]
+  StepRegister {
+    id: usize,
+    parent_id: usize,
+    root_id: usize,
+    name: &'a str,
+    origin: &'a str,
+    location: &'a TestLocation,
+    level: usize,
+  },
+  StepWait {
+    id: usize,
+  },
+  #[serde(rename_all = "camelCase")]
+  StepResult {
+    id: usize,
+    status: &'static str,
+    elapsed: u64,
+    rss: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<JsonlFailure>,
+  },
+  UncaughtError {
+    origin: &'a str,
+    message: String,
+  },
+  #[serde(rename_all = "camelCase")]
+  Summary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    passed_steps: usize,
+    failed_steps: usize,
+    ignored_steps: usize,
+    elapsed: u128,
+    rss: usize,
+  },
+  Sigint {
+    pending: Vec<usize>,
+  },
+}
+
+/// A test reporter that streams every event as a line of JSON to a file, so
+/// that results can be archived and processed without parsing the console
+/// output.
+pub struct JsonlTestReporter {
+  writer: BufWriter<File>,
+  summary: TestSummary,
+}
+
+impl JsonlTestReporter {
+  pub fn new(output_path: &str) -> Result<Self, AnyError> {
+    let file = create_file(Path::new(output_path)).with_context(|| {
+      format!("Failed to create test output file {output_path}")
+    })?;
+    Ok(Self {
+      writer: BufWriter::new(file),
+      summary: TestSummary::new(),
+    })
+  }
+
+  fn write_event(&mut self, event: &JsonlTestEvent) {
+    // flush every event so that the file is usable even if the process
+    // is killed midway
+    let result = serde_json::to_writer(&mut self.writer, event)
+      .map_err(std::io::Error::from)
+      .and_then(|_| self.writer.write_all(b"\n"))
+      .and_then(|_| self.writer.flush());
+    if let Err(err) = result {
+      log::warn!("Failed writing test output file: {err}");
+    }
+  }
+}
+
+impl TestReporter for JsonlTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    self.write_event(&JsonlTestEvent::Register {
+      id: description.id,
+      name: &description.name,
+      origin: &description.origin,
+      location: &description.location,
+      ignore: description.ignore,
+      only: description.only,
+    });
+  }
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    self.write_event(&JsonlTestEvent::Plan {
+      origin: &plan.origin,
+      total: plan.total,
+      filtered_out: plan.filtered_out,
+      used_only: plan.used_only,
+    });
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    self.write_event(&JsonlTestEvent::Wait { id: description.id });
+  }
+
+  fn report_output(&mut self, output: &[u8]) {
+    self.write_event(&JsonlTestEvent::Output {
+      text: String::from_utf8_lossy(output),
+    });
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let (status, failure) = match result {
+      TestResult::Ok => {
+        self.summary.passed += 1;
+        ("ok", None)
+      }
+      TestResult::Ignored => {
+        self.summary.ignored += 1;
+        ("ignored", None)
+      }
+      TestResult::Failed(failure) => {
+        self.summary.failed += 1;
+        ("failed", Some(failure.into()))
+      }
+      TestResult::Cancelled => {
+        self.summary.failed += 1;
+        ("cancelled", None)
+      }
+    };
+    self.write_event(&JsonlTestEvent::Result {
+      id: description.id,
+      status,
+      elapsed,
+      rss: rss(),
+      failure,
+    });
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.write_event(&JsonlTestEvent::UncaughtError {
+      origin,
+      message: strip_ansi_codes(&format_js_error(&error)).into_owned(),
+    });
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonlTestEvent::StepRegister {
+      id: description.id,
+      parent_id: description.parent_id,
+      root_id: description.root_id,
+      name: &description.name,
+      origin: &description.origin,
+      location: &description.location,
+      level: description.level,
+    });
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonlTestEvent::StepWait { id: description.id });
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let (status, failure) = match result {
+      TestStepResult::Ok => {
+        self.summary.passed_steps += 1;
+        ("ok", None)
+      }
+      TestStepResult::Ignored => {
+        self.summary.ignored_steps += 1;
+        ("ignored", None)
+      }
+      TestStepResult::Failed(failure) => {
+        self.summary.failed_steps += 1;
+        ("failed", Some(failure.into()))
+      }
+    };
+    self.write_event(&JsonlTestEvent::StepResult {
+      id: desc.id,
+      status,
+      elapsed,
+      rss: rss(),
+      failure,
+    });
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self.write_event(&JsonlTestEvent::Summary {
+      passed: self.summary.passed,
+      failed: self.summary.failed,
+      ignored: self.summary.ignored,
+      passed_steps: self.summary.passed_steps,
+      failed_steps: self.summary.failed_steps,
+      ignored_steps: self.summary.ignored_steps,
+      elapsed: elapsed.as_millis(),
+      rss: rss(),
+    });
+  }
+
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let mut pending = tests_pending.iter().copied().collect::<Vec<_>>();
+    pending.sort();
+    self.write_event(&JsonlTestEvent::Sigint { pending });
+  }
+
+  fn report_completed(&mut self) {}
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    self.writer.flush()?;
+    Ok(())
+  }
+}
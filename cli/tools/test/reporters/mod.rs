@@ -5,12 +5,14 @@ use super::*;
 mod common;
 mod compound;
 mod dot;
+mod jsonl;
 mod junit;
 mod pretty;
 mod tap;
 
 pub use compound::CompoundTestReporter;
 pub use dot::DotTestReporter;
+pub use jsonl::JsonlTestReporter;
 pub use junit::JunitTestReporter;
 pub use pretty::PrettyTestReporter;
 pub use tap::TapTestReporter;
@@ -2,6 +2,7 @@
 
 //! This module provides feature to upgrade deno executable
 
+use crate::args::side_by_side_exe_path;
 use crate::args::Flags;
 use crate::args::UpgradeFlags;
 use crate::colors;
@@ -379,7 +380,9 @@ pub async fn upgrade(
   let output_exe_path =
     full_path_output_flag.as_ref().unwrap_or(&current_exe_path);
 
-  let permissions = if let Ok(metadata) = fs::metadata(output_exe_path) {
+  let permissions = if upgrade_flags.side_by_side {
+    fs::metadata(&current_exe_path)?.permissions()
+  } else if let Ok(metadata) = fs::metadata(output_exe_path) {
     let permissions = metadata.permissions();
     if permissions.readonly() {
       bail!(
@@ -428,6 +431,7 @@ pub async fn upgrade(
 
       if !upgrade_flags.force
         && full_path_output_flag.is_none()
+        && !upgrade_flags.side_by_side
         && current_is_passed
       {
         log::info!("Version {} is already installed", crate::version::deno());
@@ -462,6 +466,7 @@ pub async fn upgrade(
 
       if !upgrade_flags.force
         && full_path_output_flag.is_none()
+        && !upgrade_flags.side_by_side
         && current_is_most_recent
       {
         log::info!(
@@ -480,6 +485,21 @@ pub async fn upgrade(
     }
   };
 
+  let side_by_side_exe_path = if upgrade_flags.side_by_side {
+    let deno_dir = factory.deno_dir()?;
+    let exe_path = side_by_side_exe_path(deno_dir, &install_version);
+    if !upgrade_flags.force && exe_path.exists() {
+      log::info!(
+        "Version {} is already installed side by side",
+        install_version
+      );
+      return Ok(());
+    }
+    Some(exe_path)
+  } else {
+    None
+  };
+
   let download_url = if upgrade_flags.canary {
     format!(
       "https://dl.deno.land/canary/{}/{}",
@@ -516,8 +536,13 @@ pub async fn upgrade(
       print_release_notes(version::deno(), &install_version);
     }
   } else {
-    let output_exe_path =
-      full_path_output_flag.as_ref().unwrap_or(&current_exe_path);
+    let output_exe_path = side_by_side_exe_path
+      .as_ref()
+      .or(full_path_output_flag.as_ref())
+      .unwrap_or(&current_exe_path);
+    if let Some(exe_path) = &side_by_side_exe_path {
+      fs::create_dir_all(exe_path.parent().unwrap())?;
+    }
     let output_result = if *output_exe_path == current_exe_path {
       replace_exe(&new_exe_path, output_exe_path)
     } else {
@@ -545,7 +570,11 @@ pub async fn upgrade(
         return Err(err.into());
       }
     }
-    log::info!("Upgraded successfully");
+    if side_by_side_exe_path.is_some() {
+      log::info!("Installed successfully to {}", output_exe_path.display());
+    } else {
+      log::info!("Upgraded successfully");
+    }
     if !upgrade_flags.canary {
       print_release_notes(version::deno(), &install_version);
     }
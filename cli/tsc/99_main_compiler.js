@@ -38,14 +38,19 @@ delete Object.prototype.__proto__;
     "Kv",
     "KvListIterator",
     "KvU64",
+    "NetworkInterfaceEvent",
+    "NetworkInterfaceWatcher",
+    "SyncChannel",
     "UnsafeCallback",
     "UnsafePointer",
     "UnsafePointerView",
     "UnsafeFnPointer",
     "UnixConnectOptions",
     "UnixListenOptions",
+    "createAcme",
     "createHttpClient",
     "dlopen",
+    "dlopenStatic",
     "flock",
     "flockSync",
     "funlock",
@@ -53,7 +58,10 @@ delete Object.prototype.__proto__;
     "listen",
     "listenDatagram",
     "openKv",
+    "runtimeMetrics",
+    "spawnPipeline",
     "umask",
+    "watchNetworkInterfaces",
   ]);
   const unstableMsgSuggestion =
     "If not, try changing the 'lib' compiler option to include 'deno.unstable' " +
@@ -294,6 +294,10 @@ impl Diagnostics {
     self.0.is_empty()
   }
 
+  pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+    self.0.iter()
+  }
+
   /// Modifies all the diagnostics to have their display positions
   /// modified to point at the original source.
   pub fn apply_fast_check_source_maps(&mut self, graph: &ModuleGraph) {
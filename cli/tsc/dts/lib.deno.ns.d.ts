@@ -832,6 +832,24 @@ declare namespace Deno {
      *
      * @default {true} */
     sanitizeExit?: boolean;
+    /** Ensure the test case does not leave child processes running, for
+     * example ones started with {@linkcode Deno.Command.spawn} whose status
+     * was never awaited.
+     *
+     * Defaults to the value of `sanitizeOps`, so test cases which disable
+     * the op sanitizer have to enable this one explicitly.
+     *
+     * @default {true} */
+    sanitizeChildProcesses?: boolean;
+    /** Ensure the test case restores the environment variables and the
+     * current working directory it changed, for example via
+     * {@linkcode Deno.env.set} or {@linkcode Deno.chdir}.
+     *
+     * The environment is shared by the whole process, so test files running
+     * concurrently with `--parallel` can also trigger this sanitizer.
+     *
+     * @default {false} */
+    sanitizeEnv?: boolean;
     /** Specifies the permissions that should be used to run the test.
      *
      * Set this to "inherit" to keep the calling runtime permissions, set this
@@ -1466,6 +1484,26 @@ declare namespace Deno {
    */
   export function exit(code?: number): never;
 
+  /** The exit code for the Deno process.
+   *
+   * If no exit code has been set, then Deno will exit with return code of
+   * `0`. It is used by {@linkcode Deno.exit} when it is called without a code,
+   * and when the process exits after the event loop ran to completion.
+   *
+   * Setting a value that's not an integer number throws.
+   *
+   * This is not available in worker contexts.
+   *
+   * ```ts
+   * console.log(Deno.exitCode); //-> 0
+   * Deno.exitCode = 1;
+   * console.log(Deno.exitCode); //-> 1
+   * ```
+   *
+   * @category Runtime
+   */
+  export var exitCode: number;
+
   /** An interface containing methods to interact with the process environment
    * variables.
    *
@@ -4118,6 +4156,52 @@ declare namespace Deno {
    */
   export function resources(): ResourceMap;
 
+  /**
+   * Groups resources so that they are all disposed together, typically at the
+   * end of a request handler with `await using`.
+   *
+   * Add resources with {@linkcode ResourceScope.use}, and pass
+   * {@linkcode ResourceScope.signal} to the operations that should be
+   * cancelled with the scope. Disposing the scope aborts the signal, then
+   * disposes the resources, the most recently added first.
+   *
+   * ```ts
+   * Deno.serve(async (req) => {
+   *   await using scope = new Deno.ResourceScope();
+   *   const file = scope.use(await Deno.open("./data.txt"));
+   *   const upstream = await fetch("https://example.com", {
+   *     signal: scope.signal,
+   *   });
+   *   // `file` is closed and `fetch()` cancelled when the handler returns
+   *   return new Response(await upstream.text());
+   * });
+   * ```
+   *
+   * @category Runtime
+   */
+  export class ResourceScope implements AsyncDisposable {
+    constructor();
+    /** Aborted when the scope is disposed. */
+    readonly signal: AbortSignal;
+    /** Whether the scope was disposed. */
+    readonly disposed: boolean;
+    /** Adds a disposable value, or a resource id to close, to the scope and
+     * returns it unchanged. `null` and `undefined` are ignored.
+     *
+     * Throws a `ReferenceError` if the scope was disposed. */
+    use<T extends AsyncDisposable | Disposable | number | null | undefined>(
+      value: T,
+    ): T;
+    /** Adds a callback called when the scope is disposed.
+     *
+     * Throws a `ReferenceError` if the scope was disposed. */
+    defer(onDispose: () => void | Promise<void>): void;
+    /** Disposes the scope. If disposing some of its resources fails, the
+     * others are still disposed and the error is rethrown, or an
+     * `AggregateError` of all the errors is. */
+    [Symbol.asyncDispose](): Promise<void>;
+  }
+
   /**
    * Additional information for FsEvent objects with the "other" kind.
    *
@@ -4599,8 +4683,11 @@ declare namespace Deno {
      *
      * If options `stdout` or `stderr` are not set to `"piped"`, accessing the
      * corresponding field on {@linkcode Deno.CommandOutput} will throw a `TypeError`.
+     *
+     * Use `options.maxBytes` to bound the memory taken by a chatty child, see
+     * {@linkcode Deno.CommandOutputOptions}.
      */
-    output(): Promise<CommandOutput>;
+    output(options?: CommandOutputOptions): Promise<CommandOutput>;
     /**
      * Synchronously executes the {@linkcode Deno.Command}, waiting for it to
      * finish and collecting all of its output.
@@ -4633,7 +4720,20 @@ declare namespace Deno {
 
     /** Waits for the child to exit completely, returning all its output and
      * status. */
-    output(): Promise<CommandOutput>;
+    output(options?: CommandOutputOptions): Promise<CommandOutput>;
+    /** Reads `stdout` (or `stderr`) of the child line by line. Lines are
+     * yielded without their `"\n"` or `"\r\n"` terminator.
+     *
+     * The stream is locked while it is read this way.
+     *
+     * ```ts
+     * const child = new Deno.Command("ls", { stdout: "piped" }).spawn();
+     * for await (const line of child.lines()) {
+     *   console.log(line);
+     * }
+     * ```
+     */
+    lines(options?: CommandLinesOptions): AsyncIterableIterator<string>;
     /** Kills the process with given {@linkcode Deno.Signal}.
      *
      * Defaults to `SIGTERM` if no signal is provided.
@@ -4729,6 +4829,43 @@ declare namespace Deno {
     signal: Signal | null;
   }
 
+  /**
+   * Options which can be passed to {@linkcode Deno.Command.output} and
+   * {@linkcode Deno.ChildProcess.output}.
+   *
+   * @category Sub Process
+   */
+  export interface CommandOutputOptions {
+    /** The maximum number of bytes collected from each of `stdout` and
+     * `stderr`. When the child writes more, it is killed and the promise
+     * rejects with a `RangeError`, unless `truncate` is set.
+     *
+     * Defaults to no limit. */
+    maxBytes?: number;
+    /** Keep the first `maxBytes` bytes of a stream that goes over the limit
+     * and discard the rest, instead of rejecting. The child keeps running and
+     * {@linkcode Deno.CommandOutput.truncated} is set.
+     *
+     * @default {false} */
+    truncate?: boolean;
+  }
+
+  /**
+   * Options which can be passed to {@linkcode Deno.ChildProcess.lines}.
+   *
+   * @category Sub Process
+   */
+  export interface CommandLinesOptions {
+    /** The stream to read lines from. It must be `"piped"`.
+     *
+     * @default {"stdout"} */
+    stream?: "stdout" | "stderr";
+    /** The encoding of the output, as a WHATWG encoding label.
+     *
+     * @default {"utf-8"} */
+    encoding?: string;
+  }
+
   /**
    * The interface returned from calling {@linkcode Deno.Command.output} or
    * {@linkcode Deno.Command.outputSync} which represents the result of spawning the
@@ -4741,6 +4878,9 @@ declare namespace Deno {
     readonly stdout: Uint8Array;
     /** The buffered output from the child process' `stderr`. */
     readonly stderr: Uint8Array;
+    /** Whether `stdout` or `stderr` was cut at
+     * {@linkcode Deno.CommandOutputOptions.maxBytes}. */
+    readonly truncated: boolean;
   }
 
   /** Option which can be specified when performing {@linkcode Deno.inspect}.
@@ -4771,6 +4911,16 @@ declare namespace Deno {
      *
      * @default {100} */
     iterableLimit?: number;
+    /** The maximum number of properties to print for each object. The
+     * remaining properties are summarized as `... n more properties`.
+     *
+     * @default {Infinity} */
+    propertyLimit?: number;
+    /** The output length after which nested objects are no longer expanded,
+     * which keeps inspecting huge object graphs from blocking the process.
+     *
+     * @default {134217728} */
+    outputLimit?: number;
     /** Show a Proxy's target and handler.
      *
      * @default {false} */
@@ -4783,10 +4933,12 @@ declare namespace Deno {
      *
      * @default {false} */
     trailingComma?: boolean;
-    /** Evaluate the result of calling getters.
+    /** Evaluate the result of calling getters. Set to `"get"` to only
+     * evaluate getters without a corresponding setter, or to `"set"` to only
+     * evaluate getters with a corresponding setter.
      *
      * @default {false} */
-    getters?: boolean;
+    getters?: boolean | "get" | "set";
     /** Show an object's non-enumerable properties.
      *
      * @default {false} */
@@ -4825,6 +4977,22 @@ declare namespace Deno {
    * console.log(inStringFormat);  // prints "x=10, y=hello"
    * ```
    *
+   * The custom inspect function receives `inspect` and the current inspect
+   * options. Passing those options along when inspecting nested values keeps
+   * the depth, colors and output limits of the outer call:
+   *
+   * ```ts
+   * class Wrapper {
+   *   constructor(public inner: unknown) {}
+   *   [Symbol.for("Deno.customInspect")](
+   *     inspect: typeof Deno.inspect,
+   *     options: Deno.InspectOptions,
+   *   ) {
+   *     return `Wrapper(${inspect(this.inner, options)})`;
+   *   }
+   * }
+   * ```
+   *
    * A depth can be specified by using the `depth` option:
    *
    * ```ts
@@ -5741,6 +5909,17 @@ declare namespace Deno {
      * The unit is seconds, with a default of 120.
      * Set to `0` to disable timeouts. */
     idleTimeout?: number;
+    /** The most bytes that may be waiting to be sent to the client, as
+     * reported by `socket.bufferedAmount`. A `send()` that would exceed it
+     * throws a `QuotaExceededError` `DOMException` instead of queueing the
+     * message, and a `"drain"` event is dispatched on the socket once the
+     * queue is empty again. Blobs are dropped instead of throwing, since they
+     * are only sent once they have been read.
+     *
+     * This allows servers to detect clients that can't keep up.
+     *
+     * The default is `0`, which means no limit. */
+    maxBufferedAmount?: number;
   }
 
   /**
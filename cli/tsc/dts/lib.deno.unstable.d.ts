@@ -829,6 +829,33 @@ declare namespace Deno {
     symbols: S,
   ): DynamicLibrary<S>;
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Opens a native library that was linked into the running binary and
+   * registered by its embedder under `name`, instead of loading a shared
+   * library from the file system. The symbols are declared and used exactly
+   * like with {@linkcode Deno.dlopen}.
+   *
+   * Throws if no library was registered under `name`.
+   *
+   * Requires `allow-ffi` permission.
+   *
+   * ```ts
+   * const lib = Deno.dlopenStatic("math", {
+   *   "add": { parameters: ["isize", "isize"], result: "isize" },
+   * } as const);
+   *
+   * const result = lib.symbols.add(35, 34); // 69
+   * ```
+   *
+   * @tags allow-ffi, unstable
+   * @category FFI
+   */
+  export function dlopenStatic<const S extends ForeignLibraryInterface>(
+    name: string,
+    symbols: S,
+  ): DynamicLibrary<S>;
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    *  Creates a presentable WebGPU surface from given window and
@@ -1150,6 +1177,437 @@ declare namespace Deno {
     reusePort?: boolean;
   }
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Resolves the certificate and key to use for the server name (SNI) sent by
+   * a client. It is either a callback, or a map of server names to key
+   * pairs where `*.example.com` matches any direct subdomain of
+   * `example.com`.
+   *
+   * The callback may return `undefined` to use the key pair passed directly
+   * to {@linkcode Deno.listenTls}, if any. Successfully resolved key pairs are
+   * cached for the lifetime of the listener, while failed lookups are retried
+   * on the next handshake for that server name.
+   *
+   * @category Network
+   * @tags unstable
+   */
+  export type TlsCertResolver =
+    | ((
+      serverName: string,
+    ) =>
+      | TlsCertifiedKeyPem
+      | undefined
+      | Promise<TlsCertifiedKeyPem | undefined>)
+    | Record<string, TlsCertifiedKeyPem>;
+
+  /**
+   * @category Network
+   * @tags unstable
+   */
+  export interface ListenTlsOptions {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Selects the certificate for each connection from the server name sent
+     * by the client, so that a single listener can serve many domains.
+     *
+     * ```ts
+     * const listener = Deno.listenTls({
+     *   port: 443,
+     *   certResolver: async (serverName) => await loadCertificate(serverName),
+     * });
+     * ```
+     */
+    certResolver?: TlsCertResolver;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Listen announces on the local transport address over TLS, resolving the
+   * certificate from the server name of each connection.
+   *
+   * Requires `allow-net` permission.
+   *
+   * @tags allow-net, unstable
+   * @category Network
+   */
+  export function listenTls(
+    options: ListenTlsOptions & { certResolver: TlsCertResolver },
+  ): TlsListener;
+
+  /**
+   * @category HTTP Server
+   * @tags unstable
+   */
+  export interface ServeOptions {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Serves HTTPS with the certificate resolved from the server name of each
+     * connection, see {@linkcode Deno.TlsCertResolver}.
+     */
+    certResolver?: TlsCertResolver;
+
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Also serves HTTP/3 over QUIC on the UDP port of the same number, with
+     * the `cert` and `key` of the HTTPS server. The HTTPS responses advertise
+     * it in an `Alt-Svc` header, unless they set their own.
+     *
+     * Requires the `--unstable-http` flag.
+     *
+     * @default {false} */
+    h3?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options of {@linkcode Deno.createAcme}.
+   *
+   * @category Network
+   * @tags unstable
+   */
+  export interface AcmeOptions {
+    /** The domains to provision certificates for, one per domain. */
+    domains: string[];
+    /** The directory in which the ACME account and the certificates are
+     * stored. Requires `allow-read` and `allow-write` permissions on it.
+     *
+     * @default {"$DENO_DIR/acme/<host of directoryUrl>"} */
+    storageDir?: string;
+    /** Must be `true` to agree to the terms of service of the ACME server. */
+    termsOfServiceAgreed: boolean;
+    /** The contact email of the ACME account. */
+    email?: string;
+    /** The directory URL of the ACME server.
+     *
+     * @default {"https://acme-v02.api.letsencrypt.org/directory"} */
+    directoryUrl?: string;
+    /** How many days before expiration a certificate is renewed.
+     *
+     * @default {30} */
+    renewBeforeDays?: number;
+    /** Additional root certificates trusted when connecting to the ACME
+     * server, in PEM format. */
+    caCerts?: string[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Provisions certificates from an ACME server, see
+   * {@linkcode Deno.createAcme}.
+   *
+   * @category Network
+   * @tags unstable
+   */
+  export interface Acme {
+    /** The domains certificates are provisioned for. */
+    readonly domains: readonly string[];
+    /** A resolver to pass to {@linkcode Deno.listenTls} or
+     * {@linkcode Deno.serve}. Certificates are issued on the first handshake
+     * for a domain, and renewed in the background. */
+    readonly certResolver: TlsCertResolver;
+    /** Responds to the HTTP-01 challenge requests of the ACME server, which
+     * must be served over HTTP on port 80. Returns `null` for any other
+     * request. */
+    handleHttpChallenge(request: Request): Response | null;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Provisions certificates from an ACME server (ie: Let's Encrypt) using
+   * HTTP-01 challenges, so that a server can serve HTTPS without a reverse
+   * proxy.
+   *
+   * ```ts
+   * const acme = Deno.createAcme({
+   *   domains: ["example.com"],
+   *   storageDir: "./certs",
+   *   termsOfServiceAgreed: true,
+   * });
+   * Deno.serve({ port: 80 }, (req) =>
+   *   acme.handleHttpChallenge(req) ??
+   *     Response.redirect(req.url.replace("http:", "https:")));
+   * Deno.serve(
+   *   { port: 443, certResolver: acme.certResolver },
+   *   () => new Response("Hello, world!"),
+   * );
+   * ```
+   *
+   * Requires `allow-net` permission to the ACME server, and `allow-read` and
+   * `allow-write` permissions to the storage directory.
+   *
+   * @tags allow-net, allow-read, allow-write, unstable
+   * @category Network
+   */
+  export function createAcme(options: AcmeOptions): Acme;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The combined status of a pipeline started with
+   * {@linkcode Deno.spawnPipeline}.
+   *
+   * @category Sub Process
+   * @tags unstable
+   */
+  export interface PipelineStatus {
+    /** `true` if every command of the pipeline exited successfully. */
+    success: boolean;
+    /** The status of each command, in pipeline order. */
+    statuses: CommandStatus[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The processes of a pipeline started with {@linkcode Deno.spawnPipeline}.
+   *
+   * @category Sub Process
+   * @tags unstable
+   */
+  export interface ChildProcessPipeline {
+    /** The spawned processes, in pipeline order. */
+    readonly children: ChildProcess[];
+    /** Resolves once every process of the pipeline has exited. */
+    readonly status: Promise<PipelineStatus>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Spawns the commands as a pipeline, like `a | b | c` in a shell. The
+   * stdout of each command is connected to the stdin of the next one with
+   * an OS pipe, so the data never passes through JavaScript.
+   *
+   * The `stdin` option of the first command and the `stdout` option of the
+   * last command are honored and default to `"inherit"`, like
+   * {@linkcode Deno.Command.spawn}. The `stderr` option is honored for every
+   * command.
+   *
+   * ```ts
+   * const { children, status } = Deno.spawnPipeline([
+   *   new Deno.Command("cat", { args: ["log.txt"] }),
+   *   new Deno.Command("grep", { args: ["error"] }),
+   *   new Deno.Command("wc", { args: ["-l"], stdout: "piped" }),
+   * ]);
+   * const output = await children[2].output();
+   * console.log(new TextDecoder().decode(output.stdout));
+   * console.log((await status).success);
+   * ```
+   *
+   * Requires `allow-run` permission for every command.
+   *
+   * @tags allow-run, unstable
+   * @category Sub Process
+   */
+  export function spawnPipeline(commands: Command[]): ChildProcessPipeline;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A change of the network interfaces, yielded by a
+   * {@linkcode Deno.NetworkInterfaceWatcher}. An address event has the
+   * address in the shape returned by {@linkcode Deno.networkInterfaces}, and
+   * a link event has the name of the interface that went up or down.
+   *
+   * @category Network
+   * @tags unstable
+   */
+  export type NetworkInterfaceEvent =
+    | {
+      kind: "addressadded" | "addressremoved";
+      interface: NetworkInterfaceInfo;
+    }
+    | {
+      kind: "linkup" | "linkdown";
+      name: string;
+    };
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Returned by {@linkcode Deno.watchNetworkInterfaces}. It is an async
+   * iterator yielding the changes of the network interfaces, until `.close()`
+   * is called.
+   *
+   * @category Network
+   * @tags unstable
+   */
+  export interface NetworkInterfaceWatcher
+    extends AsyncIterable<NetworkInterfaceEvent>, Disposable {
+    /** Stops watching the network interfaces and closes the watcher. */
+    close(): void;
+    /** Make the watcher block the event loop from finishing.
+     *
+     * Note: the watcher blocks the event loop from finishing by default. This
+     * method is only meaningful after `.unref()` is called. */
+    ref(): void;
+    /** Make the watcher not block the event loop from finishing. */
+    unref(): void;
+    [Symbol.asyncIterator](): AsyncIterableIterator<NetworkInterfaceEvent>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Watches the network interfaces for addresses being added or removed and
+   * links going up or down, so that long-running programs can react to the
+   * changes of connectivity instead of polling
+   * {@linkcode Deno.networkInterfaces}. Like with it, only the interfaces that
+   * have an address are reported.
+   *
+   * The notifications come from netlink on Linux, a routing socket on macOS
+   * and the BSDs, and the IP Helper API on Windows.
+   *
+   * ```ts
+   * using watcher = Deno.watchNetworkInterfaces();
+   * for await (const event of watcher) {
+   *   if (event.kind === "addressadded") {
+   *     console.log(event.interface.name, event.interface.address);
+   *   }
+   * }
+   * ```
+   *
+   * Requires `allow-sys` permission.
+   *
+   * @tags allow-sys, unstable
+   * @category Network
+   */
+  export function watchNetworkInterfaces(): NetworkInterfaceWatcher;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The timings of an op, as reported by {@linkcode Deno.runtimeMetrics}.
+   *
+   * @category Runtime
+   * @tags unstable
+   */
+  export interface OpTiming {
+    /** The number of completed calls. */
+    count: number;
+    /** The number of calls that threw. */
+    errors: number;
+    totalUs: number;
+    maxUs: number;
+    /** The number of calls per duration bucket. The buckets are bounded by
+     * 1, 2, 5, 10, 20, 50... microseconds up to 10 seconds, and the last one
+     * counts the slower calls. */
+    histogram: number[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The metrics returned by {@linkcode Deno.runtimeMetrics}.
+   *
+   * @category Runtime
+   * @tags unstable
+   */
+  export interface RuntimeMetrics {
+    /** The timings of every op called at least once, keyed by op name. */
+    ops: Record<string, OpTiming>;
+    /** The bytes read from and written to resources through the generic
+     * read and write ops, keyed by resource name. */
+    resources: Record<string, { bytesRead: number; bytesWritten: number }>;
+    modules: {
+      /** The number of modules loaded, including the dynamic imports. */
+      loaded: number;
+      dynamic: number;
+      failed: number;
+    };
+    /** How late the event loop was to wake up a timer, sampled every 10ms
+     * over the last ~10 seconds. */
+    eventLoop: {
+      samples: number;
+      meanUs: number;
+      p50Us: number;
+      p90Us: number;
+      p99Us: number;
+      maxUs: number;
+    };
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Returns metrics about the current worker, collected since it started:
+   * the timings of every op, the bytes moved through resources, the module
+   * loads and the event loop lag. The collection is enabled by the
+   * `--unstable-metrics` flag, and is cheap enough to leave on in
+   * production.
+   *
+   * ```ts
+   * const { ops, eventLoop } = Deno.runtimeMetrics();
+   * console.log(ops["op_read"]?.count, eventLoop.p99Us);
+   * ```
+   *
+   * @category Runtime
+   * @tags unstable
+   */
+  export function runtimeMetrics(): RuntimeMetrics;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A fixed-capacity channel of binary messages, which can be shared by
+   * workers to exchange messages without the overhead of `postMessage()`.
+   *
+   * The channel is backed by a `SharedArrayBuffer`. Post its
+   * {@linkcode SyncChannel.buffer} to a worker and attach to the same channel
+   * with `new Deno.SyncChannel(buffer)` there. Any number of workers can send
+   * and receive messages, which are received in the order they were sent.
+   *
+   * Sending to a full channel and receiving from an empty channel block the
+   * current thread until the channel changes or the timeout expires. A
+   * blocked worker can still be terminated.
+   *
+   * ```ts
+   * const channel = new Deno.SyncChannel(16, 1024);
+   * const worker = new Worker(import.meta.resolve("./worker.ts"), {
+   *   type: "module",
+   * });
+   * worker.postMessage(channel.buffer);
+   *
+   * // in worker.ts
+   * self.onmessage = (e) => {
+   *   const channel = new Deno.SyncChannel(e.data);
+   *   let message;
+   *   while ((message = channel.recv()) !== null) {
+   *     // ...
+   *   }
+   * };
+   * ```
+   *
+   * @category Workers
+   * @tags unstable
+   */
+  export class SyncChannel {
+    /** Creates a channel that holds up to `capacity` messages of at most
+     * `messageSize` bytes each. */
+    constructor(capacity: number, messageSize: number);
+    /** Attaches to the channel of the given buffer, which must be the
+     * {@linkcode SyncChannel.buffer} of a channel. */
+    constructor(buffer: SharedArrayBuffer);
+
+    /** The buffer that backs the channel. */
+    readonly buffer: SharedArrayBuffer;
+    /** The maximum number of messages in the channel. */
+    readonly capacity: number;
+    /** The maximum size of a message in bytes. */
+    readonly messageSize: number;
+    /** Whether the channel was closed. */
+    readonly closed: boolean;
+
+    /** Sends a copy of `data`, waiting for up to `timeout` milliseconds while
+     * the channel is full. Returns `false` if the timeout expired.
+     *
+     * Throws a {@linkcode Deno.errors.BrokenPipe} error if the channel is
+     * closed. */
+    send(data: Uint8Array, timeout?: number): boolean;
+    /** Sends a copy of `data` if the channel isn't full, without waiting. */
+    trySend(data: Uint8Array): boolean;
+    /** Receives a message, waiting for up to `timeout` milliseconds while the
+     * channel is empty. Returns `null` if the timeout expired, or if the
+     * channel is closed and every message was received. */
+    recv(timeout?: number): Uint8Array | null;
+    /** Receives a message if the channel isn't empty, without waiting. */
+    tryRecv(): Uint8Array | null;
+    /** Closes the channel. The messages that were already sent can still be
+     * received, and waiting senders and receivers wake up. */
+    close(): void;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * Unstable options which can be set when opening a datagram listener via
@@ -1255,6 +1713,44 @@ declare namespace Deno {
    */
   export function funlockSync(rid: number): void;
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A directory entry returned by {@linkcode Deno.readDir} with the `stat`
+   * option.
+   *
+   * @category File System
+   * @tags unstable
+   */
+  export interface DirEntryWithStat extends DirEntry {
+    /** The information about the entry itself, like {@linkcode Deno.lstat}
+     * would return. Symlinks are not followed. */
+    stat: FileInfo;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Reads the directory given by `path` and returns an async iterable of its
+   * entries, each with the information returned by {@linkcode Deno.lstat}.
+   * The information is read along with the entries, which is cheaper than
+   * calling `Deno.lstat` for each of them. Entries that are removed while
+   * the directory is being read are skipped.
+   *
+   * ```ts
+   * for await (const entry of Deno.readDir("/", { stat: true })) {
+   *   console.log(entry.name, entry.stat.size);
+   * }
+   * ```
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read, unstable
+   * @category File System
+   */
+  export function readDir(
+    path: string | URL,
+    options: { stat: true },
+  ): AsyncIterable<DirEntryWithStat>;
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * Open a new {@linkcode Deno.Kv} connection to persist data.
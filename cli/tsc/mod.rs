@@ -460,6 +460,15 @@ pub fn as_ts_script_kind(media_type: MediaType) -> i32 {
 pub const MISSING_DEPENDENCY_SPECIFIER: &str =
   "internal:///missing_dependency.d.ts";
 
+/// Declarations for modules imported `with { type: "bytes" }`.
+const BYTES_MODULE_SPECIFIER: &str = "internal:///bytes_module.d.ts";
+const BYTES_MODULE_SOURCE: &str =
+  "declare const bytes: Uint8Array;\nexport default bytes;\n";
+/// Declarations for modules imported `with { type: "text" }`.
+const TEXT_MODULE_SPECIFIER: &str = "internal:///text_module.d.ts";
+const TEXT_MODULE_SOURCE: &str =
+  "declare const text: string;\nexport default text;\n";
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct LoadResponse {
@@ -496,6 +505,14 @@ fn op_load_inner(
   // handle the request for that module here.
   } else if load_specifier == MISSING_DEPENDENCY_SPECIFIER {
     None
+  } else if load_specifier == BYTES_MODULE_SPECIFIER {
+    media_type = MediaType::Dts;
+    hash = get_maybe_hash(Some(BYTES_MODULE_SOURCE), state.hash_data);
+    Some(Cow::Borrowed(BYTES_MODULE_SOURCE))
+  } else if load_specifier == TEXT_MODULE_SPECIFIER {
+    media_type = MediaType::Dts;
+    hash = get_maybe_hash(Some(TEXT_MODULE_SOURCE), state.hash_data);
+    Some(Cow::Borrowed(TEXT_MODULE_SOURCE))
   } else if let Some(name) = load_specifier.strip_prefix("asset:///") {
     let maybe_source = get_lazily_loaded_asset(name);
     hash = get_maybe_hash(maybe_source, state.hash_data);
@@ -637,10 +654,26 @@ fn op_resolve_inner(
     }
 
     let graph = &state.graph;
-    let resolved_dep = graph
+    let maybe_dependency = graph
       .get(&referrer)
       .and_then(|m| m.js())
-      .and_then(|m| m.dependencies_prefer_fast_check().get(&specifier))
+      .and_then(|m| m.dependencies_prefer_fast_check().get(&specifier));
+
+    // bytes and text modules are not part of the graph, so they get their
+    // own declarations
+    let maybe_attribute_type =
+      maybe_dependency.and_then(|d| d.maybe_attribute_type.as_deref());
+    let maybe_declarations = match maybe_attribute_type {
+      Some("bytes") => Some(BYTES_MODULE_SPECIFIER),
+      Some("text") => Some(TEXT_MODULE_SPECIFIER),
+      _ => None,
+    };
+    if let Some(declarations) = maybe_declarations {
+      resolved.push((declarations.to_string(), ".d.ts".to_string()));
+      continue;
+    }
+
+    let resolved_dep = maybe_dependency
       .and_then(|d| d.maybe_type.ok().or_else(|| d.maybe_code.ok()));
 
     let maybe_result = match resolved_dep {
@@ -3,6 +3,7 @@
 use crate::colors;
 use dissimilar::diff as difference;
 use dissimilar::Chunk;
+use std::collections::HashMap;
 use std::fmt::Write as _;
 
 /// Print diff of the same file_path, before and after formatting.
@@ -145,6 +146,184 @@ impl DiffBuilder {
   }
 }
 
+/// A replacement of the whole lines in `start_line..end_line` (zero-based,
+/// end exclusive) of the original text with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEdit {
+  pub start_line: usize,
+  pub end_line: usize,
+  pub new_text: String,
+}
+
+/// Computes the line based edits necessary to turn `orig_text` into
+/// `edit_text`.
+pub fn line_edits(orig_text: &str, edit_text: &str) -> Vec<LineEdit> {
+  let orig_lines = split_lines(orig_text);
+  let edit_lines = split_lines(edit_text);
+
+  // Encode every distinct line as a single char so the character based
+  // diffing algorithm can be used to diff lines.
+  fn encode<'a>(
+    lines: &[&'a str],
+    line_ids: &mut HashMap<&'a str, u32>,
+  ) -> String {
+    lines
+      .iter()
+      .map(|line| {
+        let next_id = line_ids.len() as u32;
+        let id = *line_ids.entry(*line).or_insert(next_id);
+        // skip over the surrogate range, which are not valid chars
+        let id = if id >= 0xD800 { id + 0x800 } else { id };
+        char::from_u32(id).unwrap()
+      })
+      .collect()
+  }
+  let mut line_ids = HashMap::new();
+  let orig_encoded = encode(&orig_lines, &mut line_ids);
+  let edit_encoded = encode(&edit_lines, &mut line_ids);
+
+  let mut edits = Vec::new();
+  let mut orig_line = 0;
+  let mut edit_line = 0;
+  let mut current: Option<(usize, usize, usize)> = None;
+  for chunk in difference(&orig_encoded, &edit_encoded) {
+    match chunk {
+      Chunk::Equal(s) => {
+        if let Some((start, end, edit_start)) = current.take() {
+          edits.push(LineEdit {
+            start_line: start,
+            end_line: end,
+            new_text: edit_lines[edit_start..edit_line].concat(),
+          });
+        }
+        let len = s.chars().count();
+        orig_line += len;
+        edit_line += len;
+      }
+      Chunk::Delete(s) => {
+        let len = s.chars().count();
+        let entry = current.get_or_insert((orig_line, orig_line, edit_line));
+        orig_line += len;
+        entry.1 = orig_line;
+      }
+      Chunk::Insert(s) => {
+        current.get_or_insert((orig_line, orig_line, edit_line));
+        edit_line += s.chars().count();
+      }
+    }
+  }
+  if let Some((start, end, edit_start)) = current {
+    edits.push(LineEdit {
+      start_line: start,
+      end_line: end,
+      new_text: edit_lines[edit_start..edit_line].concat(),
+    });
+  }
+  edits
+}
+
+/// Creates a unified diff of the same file path, before and after formatting,
+/// which can be applied with `git apply`.
+pub fn unified_diff(
+  file_path: &str,
+  orig_text: &str,
+  edit_text: &str,
+) -> String {
+  const CONTEXT_LINES: usize = 3;
+
+  let edits = line_edits(orig_text, edit_text);
+  if edits.is_empty() {
+    return String::new();
+  }
+  let orig_lines = split_lines(orig_text);
+  let file_path = file_path.replace('\\', "/");
+  let file_path = file_path.trim_start_matches('/');
+  let mut output = String::new();
+  writeln!(output, "diff --git a/{file_path} b/{file_path}").unwrap();
+  writeln!(output, "--- a/{file_path}").unwrap();
+  writeln!(output, "+++ b/{file_path}").unwrap();
+
+  // group edits that are close to each other into the same hunk
+  let mut hunks: Vec<Vec<&LineEdit>> = Vec::new();
+  for edit in &edits {
+    match hunks.last_mut() {
+      Some(hunk)
+        if edit.start_line
+          <= hunk.last().unwrap().end_line + CONTEXT_LINES * 2 =>
+      {
+        hunk.push(edit)
+      }
+      _ => hunks.push(vec![edit]),
+    }
+  }
+
+  // the difference in line count between the original and edited text of
+  // the hunks written so far
+  let mut line_offset: isize = 0;
+  for hunk in hunks {
+    let first = hunk.first().unwrap();
+    let last = hunk.last().unwrap();
+    let start = first.start_line.saturating_sub(CONTEXT_LINES);
+    let end = std::cmp::min(last.end_line + CONTEXT_LINES, orig_lines.len());
+    let mut body = String::new();
+    let mut orig_count = 0;
+    let mut edit_count = 0;
+    let mut line = start;
+    for edit in hunk {
+      for orig_line in &orig_lines[line..edit.start_line] {
+        write_patch_line(&mut body, ' ', orig_line);
+        orig_count += 1;
+        edit_count += 1;
+      }
+      for orig_line in &orig_lines[edit.start_line..edit.end_line] {
+        write_patch_line(&mut body, '-', orig_line);
+        orig_count += 1;
+      }
+      for edit_line in split_lines(&edit.new_text) {
+        write_patch_line(&mut body, '+', edit_line);
+        edit_count += 1;
+      }
+      line = edit.end_line;
+    }
+    for orig_line in &orig_lines[line..end] {
+      write_patch_line(&mut body, ' ', orig_line);
+      orig_count += 1;
+      edit_count += 1;
+    }
+    let orig_start = if orig_count == 0 { start } else { start + 1 };
+    let edit_start = if edit_count == 0 {
+      (start as isize + line_offset) as usize
+    } else {
+      (start as isize + line_offset + 1) as usize
+    };
+    writeln!(
+      output,
+      "@@ -{},{} +{},{} @@",
+      orig_start, orig_count, edit_start, edit_count
+    )
+    .unwrap();
+    output.push_str(&body);
+    line_offset += edit_count as isize - orig_count as isize;
+  }
+  output
+}
+
+/// Splits the text into lines where each line keeps its line ending.
+fn split_lines(text: &str) -> Vec<&str> {
+  text.split_inclusive('\n').collect()
+}
+
+fn write_patch_line(output: &mut String, prefix: char, line: &str) {
+  output.push(prefix);
+  if let Some(line) = line.strip_suffix('\n') {
+    output.push_str(line);
+    output.push('\n');
+  } else {
+    output.push_str(line);
+    output.push_str("\n\\ No newline at end of file\n");
+  }
+}
+
 fn fmt_add() -> String {
   colors::green_bold("+").to_string()
 }
@@ -219,6 +398,73 @@ mod tests {
     run_test("test\n", "test\r\n", " | Text differed by line endings.\n");
   }
 
+  #[test]
+  fn test_line_edits() {
+    assert_eq!(line_edits("a\nb\nc\n", "a\nb\nc\n"), vec![]);
+    assert_eq!(
+      line_edits("a\nb\nc\nd\ne\n", "a\nB\nc\nd\ne\nf\n"),
+      vec![
+        LineEdit {
+          start_line: 1,
+          end_line: 2,
+          new_text: "B\n".to_string(),
+        },
+        LineEdit {
+          start_line: 5,
+          end_line: 5,
+          new_text: "f\n".to_string(),
+        },
+      ]
+    );
+    assert_eq!(
+      line_edits("a\nb\n", "b\n"),
+      vec![LineEdit {
+        start_line: 0,
+        end_line: 1,
+        new_text: String::new(),
+      }]
+    );
+  }
+
+  #[test]
+  fn test_unified_diff() {
+    assert_eq!(unified_diff("mod.ts", "a\n", "a\n"), "");
+    assert_eq!(
+      unified_diff(
+        "src/mod.ts",
+        "1\n2\n3\n4\nconsole.log('a')\n6\n7\n8\n9\n",
+        "1\n2\n3\n4\nconsole.log(\"a\");\n6\n7\n8\n9\n",
+      ),
+      concat!(
+        "diff --git a/src/mod.ts b/src/mod.ts\n",
+        "--- a/src/mod.ts\n",
+        "+++ b/src/mod.ts\n",
+        "@@ -2,7 +2,7 @@\n",
+        " 2\n",
+        " 3\n",
+        " 4\n",
+        "-console.log('a')\n",
+        "+console.log(\"a\");\n",
+        " 6\n",
+        " 7\n",
+        " 8\n",
+      ),
+    );
+    assert_eq!(
+      unified_diff("mod.ts", "a", "a\nb\n"),
+      concat!(
+        "diff --git a/mod.ts b/mod.ts\n",
+        "--- a/mod.ts\n",
+        "+++ b/mod.ts\n",
+        "@@ -1,1 +1,2 @@\n",
+        "-a\n",
+        "\\ No newline at end of file\n",
+        "+a\n",
+        "+b\n",
+      ),
+    );
+  }
+
   fn run_test(diff_text1: &str, diff_text2: &str, expected_output: &str) {
     assert_eq!(
       test_util::strip_ansi_codes(&diff(diff_text1, diff_text2,)),
@@ -39,16 +39,20 @@ struct DebouncedReceiver {
   // lose items if a `recv()` never completes
   received_items: HashSet<PathBuf>,
   receiver: UnboundedReceiver<Vec<PathBuf>>,
+  interval: Duration,
 }
 
 impl DebouncedReceiver {
-  fn new_with_sender() -> (Arc<mpsc::UnboundedSender<Vec<PathBuf>>>, Self) {
+  fn new_with_sender(
+    interval: Duration,
+  ) -> (Arc<mpsc::UnboundedSender<Vec<PathBuf>>>, Self) {
     let (sender, receiver) = mpsc::unbounded_channel();
     (
       Arc::new(sender),
       Self {
         receiver,
         received_items: HashSet::new(),
+        interval,
       },
     )
   }
@@ -65,7 +69,7 @@ impl DebouncedReceiver {
         items = self.receiver.recv() => {
           self.received_items.extend(items?);
         }
-        _ = sleep(DEBOUNCE_INTERVAL) => {
+        _ = sleep(self.interval) => {
           return Some(self.received_items.drain().collect());
         }
       }
@@ -253,7 +257,9 @@ where
   let (restart_tx, mut restart_rx) = tokio::sync::mpsc::unbounded_channel();
   let (changed_paths_tx, changed_paths_rx) = tokio::sync::broadcast::channel(4);
   let (watcher_sender, mut watcher_receiver) =
-    DebouncedReceiver::new_with_sender();
+    DebouncedReceiver::new_with_sender(
+      flags.watch_debounce_interval().unwrap_or(DEBOUNCE_INTERVAL),
+    );
 
   let PrintConfig {
     banner,
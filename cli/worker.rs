@@ -106,16 +106,20 @@ pub struct CliMainWorkerOptions {
   pub enable_op_summary_metrics: bool,
   pub enable_testing_features: bool,
   pub has_node_modules_dir: bool,
+  pub preserve_symlinks: bool,
+  pub console_timestamps: bool,
   pub hmr: bool,
   pub inspect_brk: bool,
   pub inspect_wait: bool,
   pub strace_ops: Option<Vec<String>>,
+  pub op_metrics: bool,
   pub is_inspecting: bool,
   pub is_npm_main: bool,
   pub location: Option<Url>,
   pub argv0: Option<String>,
   pub node_debug: Option<String>,
   pub origin_data_folder_path: Option<PathBuf>,
+  pub deno_dir: Option<PathBuf>,
   pub seed: Option<u64>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub unstable: bool,
@@ -611,6 +615,9 @@ impl CliMainWorkerFactory {
         user_agent: version::get_user_agent().to_string(),
         inspect: shared.options.is_inspecting,
         has_node_modules_dir: shared.options.has_node_modules_dir,
+        preserve_symlinks: shared.options.preserve_symlinks,
+        console_timestamps: shared.options.console_timestamps,
+        deno_dir: shared.options.deno_dir.clone(),
         argv0: shared.options.argv0.clone(),
         node_debug: shared.options.node_debug.clone(),
         node_ipc_fd: shared.node_ipc,
@@ -637,12 +644,14 @@ impl CliMainWorkerFactory {
       should_break_on_first_statement: shared.options.inspect_brk,
       should_wait_for_inspector_session: shared.options.inspect_wait,
       strace_ops: shared.options.strace_ops.clone(),
+      op_metrics: shared.options.op_metrics,
       module_loader,
       fs: shared.fs.clone(),
       npm_resolver: Some(shared.npm_resolver.clone().into_npm_resolver()),
       get_error_class_fn: Some(&errors::get_error_class_name),
       cache_storage_dir,
       origin_storage_dir,
+      web_storage_provider: None,
       blob_store: shared.blob_store.clone(),
       broadcast_channel: shared.broadcast_channel.clone(),
       shared_array_buffer_store: Some(shared.shared_array_buffer_store.clone()),
@@ -651,6 +660,7 @@ impl CliMainWorkerFactory {
       ),
       stdio,
       feature_checker,
+      ffi_static_libraries: Default::default(),
       skip_op_registration: shared.options.skip_op_registration,
       v8_code_cache: shared.code_cache.clone(),
     };
@@ -823,6 +833,9 @@ fn create_web_worker_callback(
         user_agent: version::get_user_agent().to_string(),
         inspect: shared.options.is_inspecting,
         has_node_modules_dir: shared.options.has_node_modules_dir,
+        preserve_symlinks: shared.options.preserve_symlinks,
+        console_timestamps: shared.options.console_timestamps,
+        deno_dir: shared.options.deno_dir.clone(),
         argv0: shared.options.argv0.clone(),
         node_debug: shared.options.node_debug.clone(),
         node_ipc_fd: None,
@@ -859,7 +872,9 @@ fn create_web_worker_callback(
       stdio: stdio.clone(),
       cache_storage_dir,
       feature_checker,
+      ffi_static_libraries: Default::default(),
       strace_ops: shared.options.strace_ops.clone(),
+      op_metrics: shared.options.op_metrics,
       close_on_idle: args.close_on_idle,
       maybe_worker_metadata: args.maybe_worker_metadata,
     };
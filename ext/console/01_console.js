@@ -375,6 +375,8 @@ function getUserOptions(ctx, isCrossContext) {
     sorted: ctx.sorted,
     getters: ctx.getters,
     numericSeparator: ctx.numericSeparator,
+    propertyLimit: ctx.propertyLimit,
+    outputLimit: ctx.outputLimit,
     ...ctx.userOptions,
   };
 
@@ -915,10 +917,11 @@ function formatRaw(ctx, value, recurseTimes, typedArray, proxyDetails) {
 
   ArrayPrototypePush(ctx.seen, value);
   ctx.currentDepth = recurseTimes;
+  const propertyLimit = MathMax(0, ctx.propertyLimit ?? Infinity);
   let output;
   try {
     output = formatter(ctx, value, recurseTimes);
-    for (i = 0; i < keys.length; i++) {
+    for (i = 0; i < MathMin(propertyLimit, keys.length); i++) {
       ArrayPrototypePush(
         output,
         formatProperty(ctx, value, recurseTimes, keys[i], extrasType),
@@ -967,6 +970,14 @@ function formatRaw(ctx, value, recurseTimes, typedArray, proxyDetails) {
     }
   }
 
+  const remainingKeys = keys.length - propertyLimit;
+  if (remainingKeys > 0) {
+    ArrayPrototypePush(
+      output,
+      `... ${remainingKeys} more propert${remainingKeys > 1 ? "ies" : "y"}`,
+    );
+  }
+
   const res = reduceToSingleString(
     ctx,
     output,
@@ -983,10 +994,10 @@ function formatRaw(ctx, value, recurseTimes, typedArray, proxyDetails) {
   // minimum. Otherwise the recursive algorithm might continue inspecting the
   // object even though the maximum string size (~2 ** 28 on 32 bit systems and
   // ~2 ** 30 on 64 bit systems) exceeded. The actual output is not limited at
-  // exactly 2 ** 27 but a bit higher. This depends on the object shape.
-  // This limit also makes sure that huge objects don't block the event loop
-  // significantly.
-  if (newLength > 2 ** 27) {
+  // exactly `outputLimit` (2 ** 27 by default) but a bit higher. This depends
+  // on the object shape. This limit also makes sure that huge objects don't
+  // block the event loop significantly.
+  if (newLength > (ctx.outputLimit ?? DEFAULT_OUTPUT_LIMIT)) {
     ctx.depth = -1;
   }
   return res;
@@ -2305,6 +2316,8 @@ function cliTable(head, columns) {
 // single quote in this array. That would give preference to single quotes.
 // However, we prefer double quotes as the default.
 
+const DEFAULT_OUTPUT_LIMIT = 2 ** 27;
+
 const denoInspectDefaultOptions = {
   indentationLvl: 0,
   currentDepth: 0,
@@ -2332,6 +2345,8 @@ const denoInspectDefaultOptions = {
   // API.
   quotes: ['"', "'", "`"],
   iterableLimit: 100, // similar to node's maxArrayLength, but doesn't only apply to arrays
+  propertyLimit: Infinity, // maximum number of properties printed per object
+  outputLimit: DEFAULT_OUTPUT_LIMIT, // output length after which objects aren't expanded
   trailingComma: false,
 
   inspect,
@@ -2747,12 +2762,14 @@ function parseCss(cssString) {
         css.color = value;
       }
     } else if (key == "font-weight") {
-      if (value == "bold") {
-        css.fontWeight = value;
+      if (
+        value == "bold" || value == "bolder" || NumberParseInt(value) >= 600
+      ) {
+        css.fontWeight = "bold";
       }
     } else if (key == "font-style") {
       if (
-        ArrayPrototypeIncludes(["italic", "oblique", "oblique 14deg"], value)
+        value == "italic" || StringPrototypeStartsWith(value, "oblique")
       ) {
         css.fontStyle = "italic";
       }
@@ -2945,6 +2962,8 @@ function inspectArgs(args, inspectOptions = {}) {
   if (ctx.colors) ctx.stylize = createStylizeWithColor(styles, colors);
   if (ctx.maxArrayLength === null) ctx.maxArrayLength = Infinity;
   if (ctx.maxStringLength === null) ctx.maxStringLength = Infinity;
+  if (ctx.propertyLimit === null) ctx.propertyLimit = Infinity;
+  if (ctx.outputLimit === null) ctx.outputLimit = Infinity;
 
   const noColor = !ctx.colors;
   const first = args[0];
@@ -3069,12 +3088,38 @@ function getConsoleInspectOptions(noColor) {
   };
 }
 
+/**
+ * Prefixes every line of the printed messages with an ISO 8601 timestamp.
+ * Messages that don't end with a newline (ex. CSI sequences) pass through
+ * untouched.
+ */
+function printWithTimestamps(printFunc) {
+  return (msg, level) => {
+    if (!StringPrototypeEndsWith(msg, "\n")) {
+      return printFunc(msg, level);
+    }
+    const timestamp = `[${DatePrototypeToISOString(new Date())}] `;
+    const lines = StringPrototypeReplaceAll(
+      StringPrototypeSlice(msg, 0, -1),
+      "\n",
+      `\n${timestamp}`,
+    );
+    return printFunc(`${timestamp}${lines}\n`, level);
+  };
+}
+
 class Console {
   #printFunc = null;
   [isConsoleInstance] = false;
 
-  constructor(printFunc) {
-    this.#printFunc = printFunc;
+  /**
+   * @param printFunc {(msg: string, level: number) => void}
+   * @param options {{ timestamps?: boolean }}
+   */
+  constructor(printFunc, options = {}) {
+    this.#printFunc = options.timestamps
+      ? printWithTimestamps(printFunc)
+      : printFunc;
     this.indentLevel = 0;
     this[isConsoleInstance] = true;
 
@@ -3204,7 +3249,7 @@ class Console {
 
   table = (data = undefined, properties = undefined) => {
     if (properties !== undefined && !ArrayIsArray(properties)) {
-      throw new Error(
+      throw new TypeError(
         "The 'properties' argument must be of type Array. " +
           "Received type " + typeof properties,
       );
@@ -3228,6 +3273,12 @@ class Console {
     const valuesKey = "Values";
     const indexKey = isSetObject || isMapObject ? "(iter idx)" : "(idx)";
 
+    // Like Node.js, the selected columns only apply to the rows of arrays and
+    // objects: sets and maps always print their values (and keys).
+    if (isSetObject || isMapObject) {
+      properties = undefined;
+    }
+
     if (isSetObject) {
       resultData = [...new SafeSetIterator(data)];
     } else if (isMapObject) {
@@ -3269,8 +3320,8 @@ class Console {
         const keys = properties || ObjectKeys(valueObj);
         for (let i = 0; i < keys.length; ++i) {
           const k = keys[i];
-          if (!primitive && ReflectHas(valueObj, k)) {
-            if (!(ReflectHas(objectValues, k))) {
+          if (!primitive && ObjectHasOwn(valueObj, k)) {
+            if (!ObjectHasOwn(objectValues, k)) {
               objectValues[k] = ArrayPrototypeFill(new Array(numRows), "");
             }
             objectValues[k][idx] = stringifyValue(valueObj[k]);
@@ -3407,6 +3458,8 @@ function inspect(
   if (ctx.colors) ctx.stylize = createStylizeWithColor(styles, colors);
   if (ctx.maxArrayLength === null) ctx.maxArrayLength = Infinity;
   if (ctx.maxStringLength === null) ctx.maxStringLength = Infinity;
+  if (ctx.propertyLimit === null) ctx.propertyLimit = Infinity;
+  if (ctx.outputLimit === null) ctx.outputLimit = Infinity;
   return formatValue(ctx, value, 0);
 }
 
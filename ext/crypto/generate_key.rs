@@ -15,6 +15,7 @@ use rsa::RsaPrivateKey;
 use serde::Deserialize;
 
 use crate::shared::*;
+use crate::SecretBuffer;
 
 // Allowlist for RSA public exponents.
 static PUB_EXPONENT_1: Lazy<BigUint> =
@@ -65,7 +66,7 @@ pub async fn op_crypto_generate_key(
 fn generate_key_rsa(
   modulus_length: u32,
   public_exponent: &[u8],
-) -> Result<Vec<u8>, AnyError> {
+) -> Result<SecretBuffer, AnyError> {
   let exponent = BigUint::from_bytes_be(public_exponent);
   if exponent != *PUB_EXPONENT_1 && exponent != *PUB_EXPONENT_2 {
     return Err(operation_error("Bad public exponent"));
@@ -81,16 +82,18 @@ fn generate_key_rsa(
     .to_pkcs1_der()
     .map_err(|_| operation_error("Failed to serialize RSA key"))?;
 
-  Ok(private_key.as_bytes().to_vec())
+  Ok(private_key.as_bytes().into())
 }
 
-fn generate_key_ec_p521() -> Vec<u8> {
+fn generate_key_ec_p521() -> SecretBuffer {
   let mut rng = OsRng;
   let key = p521::SecretKey::random(&mut rng);
-  key.to_nonzero_scalar().to_bytes().to_vec()
+  key.to_nonzero_scalar().to_bytes().as_slice().into()
 }
 
-fn generate_key_ec(named_curve: EcNamedCurve) -> Result<Vec<u8>, AnyError> {
+fn generate_key_ec(
+  named_curve: EcNamedCurve,
+) -> Result<SecretBuffer, AnyError> {
   let curve = match named_curve {
     EcNamedCurve::P256 => &ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING,
     EcNamedCurve::P384 => &ring::signature::ECDSA_P384_SHA384_FIXED_SIGNING,
@@ -102,15 +105,15 @@ fn generate_key_ec(named_curve: EcNamedCurve) -> Result<Vec<u8>, AnyError> {
   let pkcs8 = EcdsaKeyPair::generate_pkcs8(curve, &rng)
     .map_err(|_| operation_error("Failed to generate EC key"))?;
 
-  Ok(pkcs8.as_ref().to_vec())
+  Ok(pkcs8.as_ref().into())
 }
 
-fn generate_key_aes(length: usize) -> Result<Vec<u8>, AnyError> {
+fn generate_key_aes(length: usize) -> Result<SecretBuffer, AnyError> {
   if length % 8 != 0 || length > 256 {
     return Err(operation_error("Invalid AES key length"));
   }
 
-  let mut key = vec![0u8; length / 8];
+  let mut key = SecretBuffer::zeroed(length / 8);
   let rng = ring::rand::SystemRandom::new();
   rng
     .fill(&mut key)
@@ -122,7 +125,7 @@ fn generate_key_aes(length: usize) -> Result<Vec<u8>, AnyError> {
 fn generate_key_hmac(
   hash: ShaHash,
   length: Option<usize>,
-) -> Result<Vec<u8>, AnyError> {
+) -> Result<SecretBuffer, AnyError> {
   let hash = match hash {
     ShaHash::Sha1 => &ring::hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
     ShaHash::Sha256 => &ring::hmac::HMAC_SHA256,
@@ -146,7 +149,7 @@ fn generate_key_hmac(
   };
 
   let rng = ring::rand::SystemRandom::new();
-  let mut key = vec![0u8; length];
+  let mut key = SecretBuffer::zeroed(length);
   rng
     .fill(&mut key)
     .map_err(|_| operation_error("Failed to generate key"))?;
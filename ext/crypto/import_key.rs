@@ -16,6 +16,7 @@ use spki::der::Decode;
 
 use crate::key::CryptoNamedCurve;
 use crate::shared::*;
+use crate::SecretBuffer;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -118,9 +119,10 @@ const BASE64_URL_SAFE_FORGIVING:
 
 macro_rules! jwt_b64_int_or_err {
   ($name:ident, $b64:expr, $err:expr) => {
-    let bytes = BASE64_URL_SAFE_FORGIVING
+    let bytes: SecretBuffer = BASE64_URL_SAFE_FORGIVING
       .decode($b64)
-      .map_err(|_| data_error($err))?;
+      .map_err(|_| data_error($err))?
+      .into();
     let $name = UintRef::new(&bytes).map_err(|_| data_error($err))?;
   };
 }
@@ -194,7 +196,7 @@ fn import_key_rsa_jwk(
       let modulus_length = private_key.modulus.as_bytes().len() * 8;
 
       Ok(ImportKeyResult::Rsa {
-        raw_data: RustRawKeyData::Private(data.into()),
+        raw_data: RustRawKeyData::Private(SecretBuffer::from(data).into()),
         modulus_length,
         public_exponent,
       })
@@ -584,7 +586,9 @@ fn import_key_ec_jwk(
       );
 
       Ok(ImportKeyResult::Ec {
-        raw_data: RustRawKeyData::Private(pkcs8_der.as_bytes().to_vec().into()),
+        raw_data: RustRawKeyData::Private(
+          SecretBuffer::from(pkcs8_der.as_bytes()).into(),
+        ),
       })
     }
     _ => unreachable!(),
@@ -812,7 +816,7 @@ fn import_key_aes(key_data: KeyData) -> Result<ImportKeyResult, AnyError> {
         .decode(k)
         .map_err(|_| data_error("invalid key data"))?;
       ImportKeyResult::Hmac {
-        raw_data: RustRawKeyData::Secret(data.into()),
+        raw_data: RustRawKeyData::Secret(SecretBuffer::from(data).into()),
       }
     }
     _ => return Err(unsupported_format()),
@@ -826,7 +830,7 @@ fn import_key_hmac(key_data: KeyData) -> Result<ImportKeyResult, AnyError> {
         .decode(k)
         .map_err(|_| data_error("invalid key data"))?;
       ImportKeyResult::Hmac {
-        raw_data: RustRawKeyData::Secret(data.into()),
+        raw_data: RustRawKeyData::Secret(SecretBuffer::from(data).into()),
       }
     }
     _ => return Err(unsupported_format()),
@@ -62,6 +62,7 @@ mod export_key;
 mod generate_key;
 mod import_key;
 mod key;
+mod secret;
 mod shared;
 mod x25519;
 
@@ -74,6 +75,7 @@ use crate::key::Algorithm;
 use crate::key::CryptoHash;
 use crate::key::CryptoNamedCurve;
 use crate::key::HkdfOutput;
+pub use crate::secret::SecretBuffer;
 use crate::shared::V8RawKeyData;
 
 deno_core::extension!(deno_crypto,
@@ -462,7 +464,7 @@ pub async fn op_crypto_derive_bits(
         let iterations =
           NonZeroU32::new(args.iterations.ok_or_else(not_supported)?).unwrap();
         let secret = args.key.data;
-        let mut out = vec![0; args.length / 8];
+        let mut out = SecretBuffer::zeroed(args.length / 8);
         pbkdf2::derive(algorithm, iterations, salt, &secret, &mut out);
         Ok(out.into())
       }
@@ -515,7 +517,10 @@ pub async fn op_crypto_derive_bits(
             );
 
             // raw serialized x-coordinate of the computed point
-            Ok(shared_secret.raw_secret_bytes().to_vec().into())
+            Ok(
+              SecretBuffer::from(shared_secret.raw_secret_bytes().as_slice())
+                .into(),
+            )
           }
           CryptoNamedCurve::P384 => {
             let secret_key = p384::SecretKey::from_pkcs8_der(&args.key.data)
@@ -556,7 +561,10 @@ pub async fn op_crypto_derive_bits(
             );
 
             // raw serialized x-coordinate of the computed point
-            Ok(shared_secret.raw_secret_bytes().to_vec().into())
+            Ok(
+              SecretBuffer::from(shared_secret.raw_secret_bytes().as_slice())
+                .into(),
+            )
           }
         }
       }
@@ -587,7 +595,7 @@ pub async fn op_crypto_derive_bits(
             "The length provided for HKDF is too large",
           )
         })?;
-        let mut r = vec![0u8; length];
+        let mut r = SecretBuffer::zeroed(length);
         okm.fill(&mut r)?;
         Ok(r.into())
       }
@@ -704,7 +712,7 @@ pub fn op_crypto_unwrap_key(
         operation_error("decryption error - integrity check failed")
       })?;
 
-      Ok(unwrapped_key.into())
+      Ok(SecretBuffer::from(unwrapped_key).into())
     }
     _ => Err(type_error("Unsupported algorithm")),
   }
@@ -0,0 +1,186 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::fmt;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use deno_core::StringOrBuffer;
+use deno_core::ToJsBuffer;
+use zeroize::Zeroize;
+
+/// Key material or a derived secret owned by Rust. The bytes are zeroed when
+/// the buffer is dropped, and `Debug` only shows their length.
+///
+/// Buffers owned by V8 can't be zeroed, so ops borrow those as `&[u8]` for
+/// as long as they need them. Whatever an op copies out of them, or returns
+/// to JS, goes through a `SecretBuffer`.
+#[derive(Clone, Default)]
+pub struct SecretBuffer(Vec<u8>);
+
+impl SecretBuffer {
+  pub fn zeroed(len: usize) -> Self {
+    Self(vec![0; len])
+  }
+
+  /// Appends `data`. The bytes are moved to a zeroed allocation when they
+  /// don't fit, instead of letting the `Vec` reallocate and leave a copy of
+  /// them behind.
+  pub fn extend_from_slice(&mut self, data: &[u8]) {
+    let len = self.0.len() + data.len();
+    if len > self.0.capacity() {
+      let mut grown = Vec::with_capacity(len.max(self.0.capacity() * 2));
+      grown.extend_from_slice(&self.0);
+      // the old allocation is zeroed when it's dropped here
+      drop(std::mem::replace(self, Self(grown)));
+    }
+    self.0.extend_from_slice(data);
+  }
+
+  /// Removes the first `len` bytes.
+  pub fn advance(&mut self, len: usize) {
+    self.0.drain(..len);
+  }
+}
+
+impl Drop for SecretBuffer {
+  fn drop(&mut self) {
+    // this also zeroes the spare capacity, which holds what `advance` moved
+    self.0.zeroize();
+  }
+}
+
+impl fmt::Debug for SecretBuffer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "SecretBuffer({} bytes)", self.0.len())
+  }
+}
+
+impl Deref for SecretBuffer {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl AsRef<[u8]> for SecretBuffer {
+  fn as_ref(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl DerefMut for SecretBuffer {
+  fn deref_mut(&mut self) -> &mut [u8] {
+    &mut self.0
+  }
+}
+
+impl From<Vec<u8>> for SecretBuffer {
+  fn from(data: Vec<u8>) -> Self {
+    Self(data)
+  }
+}
+
+impl From<&[u8]> for SecretBuffer {
+  fn from(data: &[u8]) -> Self {
+    Self(data.to_vec())
+  }
+}
+
+/// Strings passed to ops are copied out of V8, so they are taken over
+/// without another copy. Buffers are still owned by V8 and are copied.
+impl From<StringOrBuffer> for SecretBuffer {
+  fn from(data: StringOrBuffer) -> Self {
+    match data {
+      StringOrBuffer::Buffer(buffer) => Self::from(&*buffer),
+      StringOrBuffer::String(string) => Self(string.into_bytes()),
+    }
+  }
+}
+
+/// Hands the bytes over to JS, without copying them. From then on they are
+/// only freed by the garbage collector.
+impl From<SecretBuffer> for ToJsBuffer {
+  fn from(mut secret: SecretBuffer) -> Self {
+    let data = if secret.0.len() == secret.0.capacity() {
+      std::mem::take(&mut secret.0)
+    } else {
+      // the buffer would be shrunk on the way, leaving the bytes behind in
+      // the old allocation
+      secret.0.to_vec()
+    };
+    data.into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extend_from_slice() {
+    let mut secret = SecretBuffer::from(Vec::with_capacity(4));
+    secret.extend_from_slice(&[1, 2]);
+    let ptr = secret.as_ptr();
+    // fits in the allocation
+    secret.extend_from_slice(&[3, 4]);
+    assert_eq!(secret.as_ptr(), ptr);
+    assert_eq!(&*secret, &[1, 2, 3, 4]);
+
+    // moved to a new allocation of twice the size, rather than reallocated
+    // by the `Vec`
+    secret.extend_from_slice(&[5]);
+    assert_ne!(secret.as_ptr(), ptr);
+    assert_eq!(secret.0.capacity(), 8);
+    assert_eq!(&*secret, &[1, 2, 3, 4, 5]);
+
+    // or to one that fits the data, if that's more
+    secret.extend_from_slice(&[0; 20]);
+    assert_eq!(secret.0.capacity(), 25);
+    assert_eq!(secret.len(), 25);
+  }
+
+  #[test]
+  fn advance() {
+    let mut secret = SecretBuffer::from(vec![1, 2, 3, 4, 5]);
+    let ptr = secret.as_ptr();
+    secret.advance(2);
+    assert_eq!(&*secret, &[3, 4, 5]);
+    // the allocation is kept, so that dropping the buffer zeroes it
+    assert_eq!(secret.as_ptr(), ptr);
+    assert_eq!(secret.0.capacity(), 5);
+    secret.advance(3);
+    assert!(secret.is_empty());
+  }
+
+  #[test]
+  fn from() {
+    let data = vec![1, 2, 3];
+    let ptr = data.as_ptr();
+    let secret = SecretBuffer::from(data);
+    assert_eq!(secret.as_ptr(), ptr);
+
+    let secret = SecretBuffer::from(&[1, 2, 3][..]);
+    assert_eq!(&*secret, &[1, 2, 3]);
+
+    let string = "secret".to_string();
+    let ptr = string.as_ptr();
+    let secret = SecretBuffer::from(StringOrBuffer::String(string));
+    assert_eq!(secret.as_ptr(), ptr);
+    assert_eq!(&*secret, b"secret");
+
+    let buffer = ToJsBuffer::from(SecretBuffer::from(vec![1, 2, 3]));
+    assert!(format!("{buffer:?}").contains("[1, 2, 3]"));
+    let mut secret = SecretBuffer::from(Vec::with_capacity(8));
+    secret.extend_from_slice(&[4, 5]);
+    let buffer = ToJsBuffer::from(secret);
+    assert!(format!("{buffer:?}").contains("[4, 5]"));
+  }
+
+  #[test]
+  fn debug_is_redacted() {
+    let secret = SecretBuffer::from(b"hunter2".to_vec());
+    assert_eq!(format!("{secret:?}"), "SecretBuffer(7 bytes)");
+    assert!(!format!("{secret:#?}").contains("104"));
+  }
+}
@@ -12,6 +12,7 @@
 /// <reference lib="esnext" />
 
 import { core, primordials } from "ext:core/mod.js";
+import { op_fetch_tee } from "ext:core/ops";
 const {
   isAnyArrayBuffer,
   isArrayBuffer,
@@ -24,6 +25,7 @@ const {
   DataViewPrototypeGetByteOffset,
   JSONParse,
   ObjectDefineProperties,
+  PromisePrototypeCatch,
   ObjectPrototypeIsPrototypeOf,
   TypedArrayPrototypeGetBuffer,
   TypedArrayPrototypeGetByteLength,
@@ -50,10 +52,12 @@ import { BlobPrototype } from "ext:deno_web/09_file.js";
 import {
   createProxy,
   errorReadableStream,
+  getReadableStreamResourceBacking,
   isReadableStreamDisturbed,
   readableStreamClose,
   readableStreamCollectIntoUint8Array,
   readableStreamDisturb,
+  readableStreamForRid,
   ReadableStreamPrototype,
   readableStreamTee,
   readableStreamThrowIfErrored,
@@ -196,7 +200,7 @@ class InnerBody {
    * @returns {InnerBody}
    */
   clone() {
-    const { 0: out1, 1: out2 } = readableStreamTee(this.stream, true);
+    const { 0: out1, 1: out2 } = teeStream(this.stream);
     this.streamOrStatic = out1;
     const second = new InnerBody(out2);
     second.source = core.deserialize(core.serialize(this.source));
@@ -227,6 +231,32 @@ class InnerBody {
   }
 }
 
+/**
+ * Tees a resource-backed stream in Rust, so that the resource is only read
+ * once, and other streams in JS.
+ * @param {ReadableStream<Uint8Array>} stream
+ * @returns {[ReadableStream<Uint8Array>, ReadableStream<Uint8Array>]}
+ */
+function teeStream(stream) {
+  const resourceBacking = getReadableStreamResourceBacking(stream);
+  if (!resourceBacking?.autoClose) {
+    return readableStreamTee(stream, true);
+  }
+  const { 0: rid1, 1: rid2 } = op_fetch_tee(resourceBacking.rid);
+  const out1 = readableStreamForRid(rid1);
+  const out2 = readableStreamForRid(rid2);
+  // the branches take over the resource, but the stream can still be errored,
+  // like when the fetch is aborted
+  const reader = stream.getReader();
+  PromisePrototypeCatch(reader.closed, (err) => {
+    errorReadableStream(out1, err);
+    errorReadableStream(out2, err);
+    core.tryClose(rid1);
+    core.tryClose(rid2);
+  });
+  return [out1, out2];
+}
+
 /**
  * @param {any} prototype
  * @param {symbol} bodySymbol
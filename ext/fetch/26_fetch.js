@@ -172,6 +172,7 @@ async function mainFetch(req, recursive, terminator) {
     if (terminator.aborted) return abortedNetworkError();
     throw err;
   } finally {
+    terminator[abortSignal.remove](onAbort);
     if (cancelHandleRid !== null) {
       core.tryClose(cancelHandleRid);
     }
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 mod fs_fetch_handler;
+mod tee;
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -83,6 +84,10 @@ pub struct Options {
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub client_cert_chain_and_key: TlsKeys,
   pub file_fetch_handler: Rc<dyn FetchHandler>,
+  /// When set, the chunks of a teed response body, like the body of a cloned
+  /// response, that one branch read but the other didn't yet are buffered in
+  /// a temporary file past this many bytes, instead of in memory.
+  pub tee_memory_limit: Option<usize>,
 }
 
 impl Options {
@@ -104,6 +109,7 @@ impl Default for Options {
       unsafely_ignore_certificate_errors: None,
       client_cert_chain_and_key: TlsKeys::Null,
       file_fetch_handler: Rc::new(DefaultFileFetchHandler),
+      tee_memory_limit: None,
     }
   }
 }
@@ -115,6 +121,7 @@ deno_core::extension!(deno_fetch,
     op_fetch<FP>,
     op_fetch_send,
     op_fetch_response_upgrade,
+    tee::op_fetch_tee,
     op_utf8_to_byte_string,
     op_fetch_custom_client<FP>,
   ],
@@ -213,6 +220,7 @@ pub fn create_client_from_options(
         .unwrap_or_default(),
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
+      tcp_keepalive: None,
       http1: true,
       http2: true,
     },
@@ -865,6 +873,7 @@ where
           _ => Some(None),
         },
       ),
+      tcp_keepalive: None,
       http1: args.http1,
       http2: args.http2,
     },
@@ -885,6 +894,8 @@ pub struct CreateHttpClientOptions {
   pub client_cert_chain_and_key: Option<TlsKey>,
   pub pool_max_idle_per_host: Option<usize>,
   pub pool_idle_timeout: Option<Option<u64>>,
+  /// The interval of the TCP keep-alive probes, in milliseconds.
+  pub tcp_keepalive: Option<u64>,
   pub http1: bool,
   pub http2: bool,
 }
@@ -899,6 +910,7 @@ impl Default for CreateHttpClientOptions {
       client_cert_chain_and_key: None,
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
+      tcp_keepalive: None,
       http1: true,
       http2: true,
     }
@@ -954,6 +966,11 @@ pub fn create_http_client(
     );
   }
 
+  if let Some(tcp_keepalive) = options.tcp_keepalive {
+    builder =
+      builder.tcp_keepalive(std::time::Duration::from_millis(tcp_keepalive));
+  }
+
   match (options.http1, options.http2) {
     (true, false) => builder = builder.http1_only(),
     (false, true) => builder = builder.http2_prior_knowledge(),
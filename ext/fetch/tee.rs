@@ -0,0 +1,279 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Teeing of resource-backed streams, like the bodies of cloned responses.
+//!
+//! The source is only read once. Every chunk is returned to the branch that
+//! read it, and buffered for the other branch until it reads it too. The
+//! buffers spill to a temporary file past [`Options::tee_memory_limit`].
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::cmp::min;
+use std::collections::VecDeque;
+use std::io::SeekFrom;
+use std::rc::Rc;
+
+use bytes::Bytes;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::AsyncRefCell;
+use deno_core::AsyncResult;
+use deno_core::BufView;
+use deno_core::CancelHandle;
+use deno_core::CancelTryFuture;
+use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
+use deno_core::ResourceId;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWriteExt;
+
+use crate::Options;
+
+/// The part of a buffer that was written to a temporary file.
+struct SpillFile {
+  file: tokio::fs::File,
+  read_pos: u64,
+  write_pos: u64,
+}
+
+/// The chunks of the source that the other branch read but this one didn't.
+#[derive(Default)]
+struct TeeBuffer {
+  chunks: VecDeque<Bytes>,
+  /// The number of bytes of `chunks`.
+  len: usize,
+  /// The chunks that come after `chunks`, once they exceeded the memory
+  /// limit.
+  spill: Option<SpillFile>,
+}
+
+impl TeeBuffer {
+  fn has_spilled(&self) -> bool {
+    self
+      .spill
+      .as_ref()
+      .is_some_and(|spill| spill.read_pos < spill.write_pos)
+  }
+
+  async fn push(
+    &mut self,
+    chunk: Bytes,
+    memory_limit: Option<usize>,
+  ) -> Result<(), AnyError> {
+    let exceeds_limit =
+      memory_limit.is_some_and(|limit| self.len + chunk.len() > limit);
+    // chunks can't go to memory while some are in the file, which keeps
+    // them in order
+    if !exceeds_limit && !self.has_spilled() {
+      self.len += chunk.len();
+      self.chunks.push_back(chunk);
+      return Ok(());
+    }
+
+    let spill = match &mut self.spill {
+      Some(spill) => spill,
+      None => self.spill.insert(SpillFile {
+        file: tokio::fs::File::from_std(tempfile::tempfile()?),
+        read_pos: 0,
+        write_pos: 0,
+      }),
+    };
+    spill.file.seek(SeekFrom::Start(spill.write_pos)).await?;
+    spill.file.write_all(&chunk).await?;
+    spill.write_pos += chunk.len() as u64;
+    Ok(())
+  }
+
+  async fn pop(&mut self, limit: usize) -> Result<Option<BufView>, AnyError> {
+    if let Some(chunk) = self.chunks.front_mut() {
+      let chunk = if chunk.len() > limit {
+        chunk.split_to(limit)
+      } else {
+        self.chunks.pop_front().unwrap()
+      };
+      self.len -= chunk.len();
+      return Ok(Some(chunk.into()));
+    }
+
+    let Some(spill) = &mut self.spill else {
+      return Ok(None);
+    };
+    if spill.read_pos == spill.write_pos {
+      return Ok(None);
+    }
+    let len = min(limit as u64, spill.write_pos - spill.read_pos) as usize;
+    let mut chunk = vec![0; len];
+    spill.file.seek(SeekFrom::Start(spill.read_pos)).await?;
+    spill.file.read_exact(&mut chunk).await?;
+    spill.read_pos += len as u64;
+    if spill.read_pos == spill.write_pos {
+      // reuse the file for the next chunks that exceed the limit
+      spill.file.set_len(0).await?;
+      spill.read_pos = 0;
+      spill.write_pos = 0;
+    }
+    Ok(Some(chunk.into()))
+  }
+}
+
+struct Tee {
+  source: Rc<dyn Resource>,
+  /// Held by the branch that reads the source.
+  reading: AsyncRefCell<()>,
+  buffers: [AsyncRefCell<TeeBuffer>; 2],
+  closed: [Cell<bool>; 2],
+  done: Cell<bool>,
+  /// The error of the source, for the branch that didn't read it.
+  error: RefCell<Option<String>>,
+  memory_limit: Option<usize>,
+}
+
+impl Tee {
+  fn finished(&self) -> Result<BufView, AnyError> {
+    match &*self.error.borrow() {
+      Some(error) => Err(type_error(error.clone())),
+      None => Ok(BufView::empty()),
+    }
+  }
+}
+
+pub struct TeeBranchResource {
+  tee: Rc<Tee>,
+  index: usize,
+  cancel: CancelHandle,
+}
+
+impl TeeBranchResource {
+  async fn read_chunk(
+    self: Rc<Self>,
+    limit: usize,
+  ) -> Result<BufView, AnyError> {
+    let tee = &self.tee;
+    let buffer = RcRef::map(tee, |tee| &tee.buffers[self.index]);
+    if let Some(chunk) = buffer.borrow_mut().await.pop(limit).await? {
+      return Ok(chunk);
+    }
+
+    let _reading = RcRef::map(tee, |tee| &tee.reading).borrow_mut().await;
+    // the other branch may have read the source while this one waited
+    if let Some(chunk) = buffer.borrow_mut().await.pop(limit).await? {
+      return Ok(chunk);
+    }
+    if tee.done.get() {
+      return tee.finished();
+    }
+
+    match tee.source.clone().read(limit).await {
+      Ok(chunk) if chunk.is_empty() => {
+        tee.done.set(true);
+        Ok(chunk)
+      }
+      Ok(chunk) => {
+        let other = 1 - self.index;
+        if !tee.closed[other].get() {
+          let mut buffer = RcRef::map(tee, |tee| &tee.buffers[other])
+            .borrow_mut()
+            .await;
+          buffer
+            .push(Bytes::copy_from_slice(&chunk), tee.memory_limit)
+            .await?;
+        }
+        Ok(chunk)
+      }
+      Err(err) => {
+        tee.done.set(true);
+        tee.error.replace(Some(err.to_string()));
+        Err(err)
+      }
+    }
+  }
+}
+
+impl Resource for TeeBranchResource {
+  fn name(&self) -> Cow<str> {
+    "fetchTeeBranch".into()
+  }
+
+  fn read(self: Rc<Self>, limit: usize) -> AsyncResult<BufView> {
+    let cancel = RcRef::map(&self, |r| &r.cancel);
+    Box::pin(self.clone().read_chunk(limit).try_or_cancel(cancel))
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+    let tee = &self.tee;
+    tee.closed[self.index].set(true);
+    let buffer = RcRef::map(tee, |tee| &tee.buffers[self.index]);
+    if let Some(mut buffer) = buffer.try_borrow_mut() {
+      *buffer = TeeBuffer::default();
+    }
+    if tee.closed.iter().all(Cell::get) {
+      tee.source.clone().close();
+    }
+  }
+}
+
+/// Tees the resource-backed stream `rid` into two branches, which take over
+/// the resource.
+#[op2]
+#[serde]
+pub fn op_fetch_tee(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<(ResourceId, ResourceId), AnyError> {
+  let source = state.resource_table.take_any(rid)?;
+  let memory_limit = state.borrow::<Options>().tee_memory_limit;
+  let tee = Rc::new(Tee {
+    source,
+    reading: AsyncRefCell::default(),
+    buffers: Default::default(),
+    closed: Default::default(),
+    done: Cell::new(false),
+    error: RefCell::new(None),
+    memory_limit,
+  });
+  let mut add_branch = |index| {
+    state.resource_table.add(TeeBranchResource {
+      tee: tee.clone(),
+      index,
+      cancel: CancelHandle::default(),
+    })
+  };
+  Ok((add_branch(0), add_branch(1)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn buffer_spills_past_memory_limit() {
+    let mut buffer = TeeBuffer::default();
+    for chunk in [&b"abc"[..], b"def", b"gh", b"i"] {
+      buffer
+        .push(Bytes::copy_from_slice(chunk), Some(4))
+        .await
+        .unwrap();
+    }
+    assert_eq!(buffer.len, 3);
+    assert!(buffer.has_spilled());
+
+    let mut read = Vec::new();
+    while let Some(chunk) = buffer.pop(2).await.unwrap() {
+      read.extend_from_slice(&chunk);
+    }
+    assert_eq!(read, b"abcdefghi");
+    assert!(!buffer.has_spilled());
+
+    // the file is reused once it was read
+    buffer
+      .push(Bytes::from_static(b"jkl"), Some(0))
+      .await
+      .unwrap();
+    assert_eq!(&*buffer.pop(8).await.unwrap().unwrap(), b"jkl");
+  }
+}
@@ -15,6 +15,7 @@ import {
   op_ffi_get_buf,
   op_ffi_get_static,
   op_ffi_load,
+  op_ffi_load_static,
   op_ffi_ptr_create,
   op_ffi_ptr_equals,
   op_ffi_ptr_of,
@@ -477,8 +478,10 @@ class DynamicLibrary {
   #rid;
   symbols = {};
 
-  constructor(path, symbols) {
-    ({ 0: this.#rid, 1: this.symbols } = op_ffi_load({ path, symbols }));
+  constructor(path, symbols, isStatic = false) {
+    ({ 0: this.#rid, 1: this.symbols } = isStatic
+      ? op_ffi_load_static({ name: path, symbols })
+      : op_ffi_load({ path, symbols }));
     for (const symbol in symbols) {
       if (!ObjectHasOwn(symbols, symbol)) {
         continue;
@@ -616,8 +619,13 @@ function dlopen(path, symbols) {
   return new DynamicLibrary(pathFromURL(path), symbols);
 }
 
+function dlopenStatic(name, symbols) {
+  return new DynamicLibrary(name, symbols, true);
+}
+
 export {
   dlopen,
+  dlopenStatic,
   UnsafeCallback,
   UnsafeFnPointer,
   UnsafePointer,
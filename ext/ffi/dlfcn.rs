@@ -20,9 +20,10 @@ use std::collections::HashMap;
 use std::ffi::c_void;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 
 pub struct DynamicLibraryResource {
-  lib: Library,
+  lib: LibraryHandle,
   pub symbols: HashMap<String, Box<Symbol>>,
 }
 
@@ -38,17 +39,92 @@ impl Resource for DynamicLibraryResource {
 
 impl DynamicLibraryResource {
   pub fn get_static(&self, symbol: String) -> Result<*mut c_void, AnyError> {
-    // By default, Err returned by this function does not tell
-    // which symbol wasn't exported. So we'll modify the error
-    // message to include the name of symbol.
-    //
-    // SAFETY: The obtained T symbol is the size of a pointer.
-    match unsafe { self.lib.symbol::<*mut c_void>(&symbol) } {
-      Ok(value) => Ok(Ok(value)),
-      Err(err) => Err(generic_error(format!(
-        "Failed to register symbol {symbol}: {err}"
-      ))),
-    }?
+    self.lib.symbol(&symbol)
+  }
+}
+
+enum LibraryHandle {
+  Dynamic(Library),
+  Static(Arc<StaticLibrary>),
+}
+
+impl LibraryHandle {
+  fn symbol(&self, symbol: &str) -> Result<*mut c_void, AnyError> {
+    match self {
+      // By default, Err returned by this function does not tell
+      // which symbol wasn't exported. So we'll modify the error
+      // message to include the name of symbol.
+      Self::Dynamic(lib) => {
+        // SAFETY: The obtained T symbol is the size of a pointer.
+        match unsafe { lib.symbol::<*mut c_void>(symbol) } {
+          Ok(value) => Ok(value),
+          Err(err) => Err(generic_error(format!(
+            "Failed to register symbol {symbol}: {err}"
+          ))),
+        }
+      }
+      Self::Static(lib) => match lib.symbols.get(symbol) {
+        Some(address) => Ok(address.0),
+        None => Err(generic_error(format!(
+          "Failed to register symbol {symbol}: not found in static library"
+        ))),
+      },
+    }
+  }
+}
+
+/// A table of native symbols linked into the embedding binary, which can be
+/// opened with `Deno.dlopenStatic()` the same way `Deno.dlopen()` opens a
+/// shared library.
+#[derive(Default)]
+pub struct StaticLibrary {
+  symbols: HashMap<String, SymbolAddress>,
+}
+
+impl StaticLibrary {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers `address` as the symbol `name` of this library.
+  ///
+  /// # Safety
+  ///
+  /// `address` must point to a function or static that lives as long as the
+  /// runtime. Like with `Deno.dlopen()`, JS code is trusted to declare the
+  /// correct type for it.
+  pub unsafe fn with_symbol(
+    mut self,
+    name: impl Into<String>,
+    address: *const c_void,
+  ) -> Self {
+    self
+      .symbols
+      .insert(name.into(), SymbolAddress(address as *mut c_void));
+    self
+  }
+}
+
+struct SymbolAddress(*mut c_void);
+
+// SAFETY: Symbols linked into the binary are valid from any thread, and
+// `StaticLibrary::with_symbol` requires them to outlive the runtime.
+unsafe impl Send for SymbolAddress {}
+// SAFETY: See above, the address itself is never mutated.
+unsafe impl Sync for SymbolAddress {}
+
+/// The static libraries available to `Deno.dlopenStatic()`, by name.
+#[derive(Clone, Default)]
+pub struct StaticLibraries(Arc<HashMap<String, Arc<StaticLibrary>>>);
+
+impl StaticLibraries {
+  pub fn new(libraries: HashMap<String, StaticLibrary>) -> Self {
+    Self(Arc::new(
+      libraries
+        .into_iter()
+        .map(|(name, library)| (name, Arc::new(library)))
+        .collect(),
+    ))
   }
 }
 
@@ -150,13 +226,60 @@ where
       format_error(e, path),
     ))
   })?;
-  let mut resource = DynamicLibraryResource {
-    lib,
+  let resource = DynamicLibraryResource {
+    lib: LibraryHandle::Dynamic(lib),
+    symbols: HashMap::new(),
+  };
+  register_symbols(scope, state, resource, args.symbols)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FfiLoadStaticArgs {
+  name: String,
+  symbols: HashMap<String, ForeignSymbol>,
+}
+
+#[op2]
+pub fn op_ffi_load_static<'scope, FP>(
+  scope: &mut v8::HandleScope<'scope>,
+  state: &mut OpState,
+  #[serde] args: FfiLoadStaticArgs,
+) -> Result<v8::Local<'scope, v8::Value>, AnyError>
+where
+  FP: FfiPermissions + 'static,
+{
+  check_unstable(state, "Deno.dlopenStatic");
+  // Static libraries are registered by the embedder and don't touch the file
+  // system, but their symbols are as callable as the ones of `Deno.dlopen()`.
+  let permissions = state.borrow_mut::<FP>();
+  permissions.check_partial(None)?;
+
+  let lib = state
+    .borrow::<StaticLibraries>()
+    .0
+    .get(&args.name)
+    .cloned()
+    .ok_or_else(|| {
+      generic_error(format!("Static library {} is not registered", args.name))
+    })?;
+  let resource = DynamicLibraryResource {
+    lib: LibraryHandle::Static(lib),
     symbols: HashMap::new(),
   };
+  register_symbols(scope, state, resource, args.symbols)
+}
+
+// Creates the JS functions for the `symbols` of a freshly opened library and
+// returns them alongside the rid of the library.
+fn register_symbols<'scope>(
+  scope: &mut v8::HandleScope<'scope>,
+  state: &mut OpState,
+  mut resource: DynamicLibraryResource,
+  symbols: HashMap<String, ForeignSymbol>,
+) -> Result<v8::Local<'scope, v8::Value>, AnyError> {
   let obj = v8::Object::new(scope);
 
-  for (symbol_key, foreign_symbol) in args.symbols {
+  for (symbol_key, foreign_symbol) in symbols {
     match foreign_symbol {
       ForeignSymbol::ForeignStatic(_) => {
         // No-op: Statics will be handled separately and are not part of the Rust-side resource.
@@ -166,24 +289,19 @@ where
           Some(symbol) => symbol,
           None => &symbol_key,
         };
-        // By default, Err returned by this function does not tell
-        // which symbol wasn't exported. So we'll modify the error
-        // message to include the name of symbol.
-        let fn_ptr =
-          // SAFETY: The obtained T symbol is the size of a pointer.
-          match unsafe { resource.lib.symbol::<*const c_void>(symbol) } {
-            Ok(value) => Ok(value),
-            Err(err) => if foreign_fn.optional {
+        let fn_ptr = match resource.lib.symbol(symbol) {
+          Ok(value) => Ok(value),
+          Err(err) => {
+            if foreign_fn.optional {
               let null: v8::Local<v8::Value> = v8::null(scope).into();
               let func_key = v8::String::new(scope, &symbol_key).unwrap();
               obj.set(scope, func_key.into(), null);
               break 'register_symbol;
             } else {
-              Err(generic_error(format!(
-                "Failed to register symbol {symbol}: {err}"
-              )))
-            },
-          }?;
+              Err(err)
+            }
+          }
+        }?;
 
         let ptr = libffi::middle::CodePtr::from_ptr(fn_ptr as _);
         let cif = libffi::middle::Cif::new(
@@ -417,10 +535,21 @@ pub(crate) fn format_error(e: dlopen2::Error, path: String) -> String {
 
 #[cfg(test)]
 mod tests {
+  use super::op_ffi_load_static;
   use super::ForeignFunction;
   use super::ForeignSymbol;
+  use super::LibraryHandle;
+  use super::StaticLibraries;
+  use super::StaticLibrary;
   use crate::symbol::NativeType;
+  use crate::FfiPermissions;
+  use deno_core::error::custom_error;
+  use deno_core::error::AnyError;
+  use deno_core::JsRuntime;
+  use deno_core::RuntimeOptions;
   use serde_json::json;
+  use std::collections::HashMap;
+  use std::sync::Arc;
 
   #[cfg(target_os = "windows")]
   #[test]
@@ -466,6 +595,73 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_static_library_symbol() {
+    static ANSWER: u32 = 42;
+    // SAFETY: `ANSWER` lives for the whole program.
+    let library = unsafe {
+      StaticLibrary::new().with_symbol("answer", &ANSWER as *const u32 as _)
+    };
+    let lib = LibraryHandle::Static(Arc::new(library));
+    let address = lib.symbol("answer").unwrap();
+    assert_eq!(address as *const u32, &ANSWER as *const u32);
+    let err = lib.symbol("question").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Failed to register symbol question: not found in static library"
+    );
+  }
+
+  struct TestPermissions(bool);
+
+  impl FfiPermissions for TestPermissions {
+    fn check_partial(
+      &mut self,
+      _path: Option<&std::path::Path>,
+    ) -> Result<(), AnyError> {
+      if self.0 {
+        Ok(())
+      } else {
+        Err(custom_error("PermissionDenied", "Requires ffi access"))
+      }
+    }
+  }
+
+  fn load_static_library(allow_ffi: bool) -> Result<(), AnyError> {
+    deno_core::extension!(
+      test_ext,
+      ops = [op_ffi_load_static<TestPermissions>],
+      options = { allow_ffi: bool },
+      state = |state, options| {
+        state.put(TestPermissions(options.allow_ffi));
+        state.put(StaticLibraries::new(HashMap::from([(
+          "math".to_string(),
+          StaticLibrary::new(),
+        )])));
+      }
+    );
+
+    let mut feature_checker = deno_core::FeatureChecker::default();
+    feature_checker.enable_legacy_unstable();
+    let mut runtime = JsRuntime::new(RuntimeOptions {
+      extensions: vec![test_ext::init_ops(allow_ffi)],
+      feature_checker: Some(Arc::new(feature_checker)),
+      ..Default::default()
+    });
+    runtime.execute_script(
+      "load_static",
+      "Deno.core.ops.op_ffi_load_static({ name: 'math', symbols: {} })",
+    )?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_load_static_library_permissions() {
+    load_static_library(true).unwrap();
+    let err = load_static_library(false).unwrap_err();
+    assert!(err.to_string().contains("Requires ffi access"), "{err}");
+  }
+
   #[test]
   fn test_serialize_foreign_symbol_failures() {
     let error = serde_json::from_value::<ForeignSymbol>(json! {{
@@ -24,7 +24,10 @@ use callback::op_ffi_unsafe_callback_close;
 use callback::op_ffi_unsafe_callback_create;
 use callback::op_ffi_unsafe_callback_ref;
 use dlfcn::op_ffi_load;
+use dlfcn::op_ffi_load_static;
 use dlfcn::ForeignFunction;
+pub use dlfcn::StaticLibraries;
+pub use dlfcn::StaticLibrary;
 use r#static::op_ffi_get_static;
 use repr::*;
 use symbol::NativeType;
@@ -61,6 +64,7 @@ deno_core::extension!(deno_ffi,
   parameters = [P: FfiPermissions],
   ops = [
     op_ffi_load<P>,
+    op_ffi_load_static<P>,
     op_ffi_get_static,
     op_ffi_call_nonblocking,
     op_ffi_call_ptr<P>,
@@ -91,4 +95,10 @@ deno_core::extension!(deno_ffi,
     op_ffi_unsafe_callback_ref,
   ],
   esm = [ "00_ffi.js" ],
+  options = {
+    static_libraries: StaticLibraries,
+  },
+  state = |state, options| {
+    state.put(options.static_libraries);
+  },
 );
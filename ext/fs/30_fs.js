@@ -4,7 +4,6 @@ import { core, internals, primordials } from "ext:core/mod.js";
 const {
   isDate,
   internalRidSymbol,
-  createCancelHandle,
 } = core;
 import {
   op_fs_chdir,
@@ -45,7 +44,8 @@ import {
   op_fs_mkdir_sync,
   op_fs_open_async,
   op_fs_open_sync,
-  op_fs_read_dir_async,
+  op_fs_read_dir_next_async,
+  op_fs_read_dir_open_async,
   op_fs_read_dir_sync,
   op_fs_read_file_async,
   op_fs_read_file_sync,
@@ -213,15 +213,36 @@ function readDirSync(path) {
   ]();
 }
 
-function readDir(path) {
-  const array = op_fs_read_dir_async(
-    pathFromURL(path),
-  );
+// Number of entries read per op call by `Deno.readDir()`.
+const READ_DIR_BATCH_SIZE = 256;
+
+function readDir(path, options = undefined) {
+  const withStat = options?.stat ?? false;
   return {
     async *[SymbolAsyncIterator]() {
-      const dir = await array;
-      for (let i = 0; i < dir.length; ++i) {
-        yield dir[i];
+      const rid = await op_fs_read_dir_open_async(
+        pathFromURL(path),
+        withStat,
+      );
+      try {
+        while (true) {
+          const entries = await op_fs_read_dir_next_async(
+            rid,
+            READ_DIR_BATCH_SIZE,
+          );
+          if (entries.length === 0) {
+            return;
+          }
+          for (let i = 0; i < entries.length; ++i) {
+            const entry = entries[i];
+            if (withStat) {
+              entry.stat = parseFileInfo(entry.stat);
+            }
+            yield entry;
+          }
+        }
+      } finally {
+        core.tryClose(rid);
       }
     },
   };
@@ -835,28 +856,15 @@ function readFileSync(path) {
 }
 
 async function readFile(path, options) {
-  let cancelRid;
-  let abortHandler;
-  if (options?.signal) {
-    options.signal.throwIfAborted();
-    cancelRid = createCancelHandle();
-    abortHandler = () => core.tryClose(cancelRid);
-    options.signal[abortSignal.add](abortHandler);
-  }
-
+  const cancelHandle = abortSignal.linkCancelHandle(options?.signal);
   try {
     const read = await op_fs_read_file_async(
       pathFromURL(path),
-      cancelRid,
+      cancelHandle.rid,
     );
     return read;
   } finally {
-    if (options?.signal) {
-      options.signal[abortSignal.remove](abortHandler);
-
-      // always throw the abort error when aborted
-      options.signal.throwIfAborted();
-    }
+    cancelHandle.unlink();
   }
 }
 
@@ -865,28 +873,15 @@ function readTextFileSync(path) {
 }
 
 async function readTextFile(path, options) {
-  let cancelRid;
-  let abortHandler;
-  if (options?.signal) {
-    options.signal.throwIfAborted();
-    cancelRid = createCancelHandle();
-    abortHandler = () => core.tryClose(cancelRid);
-    options.signal[abortSignal.add](abortHandler);
-  }
-
+  const cancelHandle = abortSignal.linkCancelHandle(options?.signal);
   try {
     const read = await op_fs_read_file_text_async(
       pathFromURL(path),
-      cancelRid,
+      cancelHandle.rid,
     );
     return read;
   } finally {
-    if (options?.signal) {
-      options.signal[abortSignal.remove](abortHandler);
-
-      // always throw the abort error when aborted
-      options.signal.throwIfAborted();
-    }
+    cancelHandle.unlink();
   }
 }
 
@@ -911,14 +906,7 @@ async function writeFile(
   data,
   options = {},
 ) {
-  let cancelRid;
-  let abortHandler;
-  if (options.signal) {
-    options.signal.throwIfAborted();
-    cancelRid = createCancelHandle();
-    abortHandler = () => core.tryClose(cancelRid);
-    options.signal[abortSignal.add](abortHandler);
-  }
+  const cancelHandle = abortSignal.linkCancelHandle(options.signal);
   try {
     if (ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, data)) {
       const file = await open(path, {
@@ -939,16 +927,11 @@ async function writeFile(
         options.create ?? true,
         options.createNew ?? false,
         data,
-        cancelRid,
+        cancelHandle.rid,
       );
     }
   } finally {
-    if (options.signal) {
-      options.signal[abortSignal.remove](abortHandler);
-
-      // always throw the abort error when aborted
-      options.signal.throwIfAborted();
-    }
+    cancelHandle.unlink();
   }
 }
 
@@ -77,6 +77,12 @@ pub struct FsDirEntry {
   pub is_symlink: bool,
 }
 
+/// The entries of a directory being read by
+/// [`FileSystem::read_dir_iter_async`], along with their `lstat` information
+/// when it was requested. It is advanced on a blocking thread.
+pub type FsDirIter =
+  Box<dyn Iterator<Item = (FsDirEntry, Option<FsStat>)> + Send>;
+
 #[allow(clippy::disallowed_types)]
 pub type FileSystemRc = crate::sync::MaybeArc<dyn FileSystem>;
 
@@ -168,6 +174,35 @@ pub trait FileSystem: std::fmt::Debug + MaybeSend + MaybeSync {
 
   fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>>;
   async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>>;
+  /// Opens a directory so that its entries can be read one at a time.
+  /// Entries that can't be read are skipped.
+  fn read_dir_iter_sync(&self, path: &Path) -> FsResult<FsDirIter> {
+    let entries = self.read_dir_sync(path)?;
+    Ok(Box::new(entries.into_iter().map(|entry| (entry, None))))
+  }
+  /// Opens a directory so that its entries can be read in batches. Entries
+  /// that can't be read, or can't be stat'ed when `with_stat` is set, are
+  /// skipped.
+  async fn read_dir_iter_async(
+    &self,
+    path: PathBuf,
+    with_stat: bool,
+  ) -> FsResult<FsDirIter> {
+    let entries = self.read_dir_async(path.clone()).await?;
+    let mut result = Vec::with_capacity(entries.len());
+    for entry in entries {
+      let stat = if with_stat {
+        match self.lstat_async(path.join(&entry.name)).await {
+          Ok(stat) => Some(stat),
+          Err(_) => continue,
+        }
+      } else {
+        None
+      };
+      result.push((entry, stat));
+    }
+    Ok(Box::new(result.into_iter()))
+  }
 
   fn rename_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()>;
   async fn rename_async(
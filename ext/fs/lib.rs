@@ -12,6 +12,7 @@ pub use crate::interface::AccessCheckFn;
 pub use crate::interface::FileSystem;
 pub use crate::interface::FileSystemRc;
 pub use crate::interface::FsDirEntry;
+pub use crate::interface::FsDirIter;
 pub use crate::interface::FsFileType;
 pub use crate::interface::OpenOptions;
 pub use crate::std_fs::RealFs;
@@ -116,7 +117,8 @@ deno_core::extension!(deno_fs,
     op_fs_realpath_sync<P>,
     op_fs_realpath_async<P>,
     op_fs_read_dir_sync<P>,
-    op_fs_read_dir_async<P>,
+    op_fs_read_dir_open_async<P>,
+    op_fs_read_dir_next_async,
     op_fs_rename_sync<P>,
     op_fs_rename_async<P>,
     op_fs_link_sync<P>,
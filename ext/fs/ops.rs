@@ -13,6 +13,7 @@ use deno_core::error::custom_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::unsync::spawn_blocking;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
 use deno_core::JsBuffer;
@@ -31,6 +32,7 @@ use crate::check_unstable;
 use crate::interface::AccessCheckFn;
 use crate::interface::FileSystemRc;
 use crate::interface::FsDirEntry;
+use crate::interface::FsDirIter;
 use crate::interface::FsFileType;
 use crate::FsPermissions;
 use crate::OpenOptions;
@@ -574,12 +576,35 @@ where
   Ok(entries)
 }
 
+/// A directory opened by `Deno.readDir()`, whose entries are read in batches.
+pub struct ReadDirResource {
+  entries: RefCell<Option<FsDirIter>>,
+}
+
+impl deno_core::Resource for ReadDirResource {
+  fn name(&self) -> Cow<str> {
+    "readDir".into()
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadDirEntry {
+  name: String,
+  is_file: bool,
+  is_directory: bool,
+  is_symlink: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  stat: Option<SerializableStat>,
+}
+
 #[op2(async)]
-#[serde]
-pub async fn op_fs_read_dir_async<P>(
+#[smi]
+pub async fn op_fs_read_dir_open_async<P>(
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<Vec<FsDirEntry>, AnyError>
+  with_stat: bool,
+) -> Result<ResourceId, AnyError>
 where
   P: FsPermissions + 'static,
 {
@@ -587,6 +612,9 @@ where
 
   let fs = {
     let mut state = state.borrow_mut();
+    if with_stat {
+      check_unstable(&state, "Deno.readDir({ stat })");
+    }
     state
       .borrow_mut::<P>()
       .check_read(&path, "Deno.readDir()")?;
@@ -594,11 +622,46 @@ where
   };
 
   let entries = fs
-    .read_dir_async(path.clone())
+    .read_dir_iter_async(path.clone(), with_stat)
     .await
     .context_path("readdir", &path)?;
 
-  Ok(entries)
+  let rid = state.borrow_mut().resource_table.add(ReadDirResource {
+    entries: RefCell::new(Some(entries)),
+  });
+  Ok(rid)
+}
+
+/// Returns up to `batch_size` entries of a directory opened with
+/// `op_fs_read_dir_open_async`, or an empty list once all were read.
+#[op2(async)]
+#[serde]
+pub async fn op_fs_read_dir_next_async(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[smi] batch_size: u32,
+) -> Result<Vec<ReadDirEntry>, AnyError> {
+  let resource = state.borrow().resource_table.get::<ReadDirResource>(rid)?;
+  let Some(mut entries) = resource.entries.take() else {
+    return Err(type_error("Directory is already being read"));
+  };
+  let (entries, batch) = spawn_blocking(move || {
+    let batch = entries
+      .by_ref()
+      .take(batch_size as usize)
+      .map(|(entry, stat)| ReadDirEntry {
+        name: entry.name,
+        is_file: entry.is_file,
+        is_directory: entry.is_directory,
+        is_symlink: entry.is_symlink,
+        stat: stat.map(SerializableStat::from),
+      })
+      .collect::<Vec<_>>();
+    (entries, batch)
+  })
+  .await?;
+  resource.entries.replace(Some(entries));
+  Ok(batch)
 }
 
 #[op2(fast)]
@@ -21,6 +21,7 @@ use deno_io::StdFileResourceInner;
 
 use crate::interface::AccessCheckCb;
 use crate::interface::FsDirEntry;
+use crate::interface::FsDirIter;
 use crate::interface::FsFileType;
 use crate::FileSystem;
 use crate::OpenOptions;
@@ -186,6 +187,16 @@ impl FileSystem for RealFs {
   async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
     spawn_blocking(move || read_dir(&path)).await?
   }
+  fn read_dir_iter_sync(&self, path: &Path) -> FsResult<FsDirIter> {
+    read_dir_iter(path, false)
+  }
+  async fn read_dir_iter_async(
+    &self,
+    path: PathBuf,
+    with_stat: bool,
+  ) -> FsResult<FsDirIter> {
+    spawn_blocking(move || read_dir_iter(&path, with_stat)).await?
+  }
 
   fn rename_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
     fs::rename(oldpath, newpath).map_err(Into::into)
@@ -770,32 +781,55 @@ fn realpath(path: &Path) -> FsResult<PathBuf> {
 }
 
 fn read_dir(path: &Path) -> FsResult<Vec<FsDirEntry>> {
-  let entries = fs::read_dir(path)?
-    .filter_map(|entry| {
-      let entry = entry.ok()?;
-      let name = entry.file_name().into_string().ok()?;
-      let metadata = entry.file_type();
-      macro_rules! method_or_false {
-        ($method:ident) => {
-          if let Ok(metadata) = &metadata {
-            metadata.$method()
-          } else {
-            false
-          }
-        };
-      }
-      Some(FsDirEntry {
-        name,
-        is_file: method_or_false!(is_file),
-        is_directory: method_or_false!(is_dir),
-        is_symlink: method_or_false!(is_symlink),
-      })
-    })
+  let entries = read_dir_iter(path, false)?
+    .map(|(entry, _)| entry)
     .collect();
-
   Ok(entries)
 }
 
+fn read_dir_iter(path: &Path, with_stat: bool) -> FsResult<FsDirIter> {
+  let entries = fs::read_dir(path)?.filter_map(move |entry| {
+    let entry = entry.ok()?;
+    let name = entry.file_name().into_string().ok()?;
+    let stat = if with_stat {
+      Some(dir_entry_lstat(&entry).ok()?)
+    } else {
+      None
+    };
+    let metadata = entry.file_type();
+    macro_rules! method_or_false {
+      ($method:ident) => {
+        if let Ok(metadata) = &metadata {
+          metadata.$method()
+        } else {
+          false
+        }
+      };
+    }
+    let entry = FsDirEntry {
+      name,
+      is_file: method_or_false!(is_file),
+      is_directory: method_or_false!(is_dir),
+      is_symlink: method_or_false!(is_symlink),
+    };
+    Some((entry, stat))
+  });
+
+  Ok(Box::new(entries))
+}
+
+/// On unix the metadata of a directory entry is read relative to the
+/// directory, without resolving its path again.
+#[cfg(not(windows))]
+fn dir_entry_lstat(entry: &fs::DirEntry) -> FsResult<FsStat> {
+  Ok(FsStat::from_std(entry.metadata()?))
+}
+
+#[cfg(windows)]
+fn dir_entry_lstat(entry: &fs::DirEntry) -> FsResult<FsStat> {
+  lstat(&entry.path())
+}
+
 #[cfg(not(windows))]
 fn symlink(
   oldpath: &Path,
@@ -15,6 +15,7 @@ import {
   op_http_get_request_method_and_url,
   op_http_read_request_body,
   op_http_serve,
+  op_http_serve_h3,
   op_http_serve_on,
   op_http_set_promise_complete,
   op_http_set_response_body_bytes,
@@ -34,6 +35,7 @@ const {
   ObjectPrototypeIsPrototypeOf,
   PromisePrototypeCatch,
   PromisePrototypeThen,
+  SafePromiseAll,
   Symbol,
   TypeError,
   TypedArrayPrototypeGetSymbolToStringTag,
@@ -76,7 +78,11 @@ import {
   resourceForReadableStream,
 } from "ext:deno_web/06_streams.js";
 import { listen, listenOptionApiName, TcpConn } from "ext:deno_net/01_net.js";
-import { hasTlsKeyPairOptions, listenTls } from "ext:deno_net/02_tls.js";
+import {
+  hasTlsKeyPairOptions,
+  listenTls,
+  loadTlsKeyPair,
+} from "ext:deno_net/02_tls.js";
 import { SymbolAsyncDispose } from "ext:deno_web/00_infra.js";
 
 const _upgraded = Symbol("_upgraded");
@@ -319,7 +325,7 @@ class InnerRequest {
       this.#methodAndUri = op_http_get_request_method_and_url(this.#external);
     }
     return {
-      transport: "tcp",
+      transport: transport === "udp" ? "udp" : "tcp",
       hostname: this.#methodAndUri[3],
       port: this.#methodAndUri[4],
     };
@@ -562,6 +568,8 @@ type RawServeOptions = {
   reusePort?: boolean;
   key?: string;
   cert?: string;
+  certResolver?: Deno.TlsCertResolver;
+  h3?: boolean;
   onError?: (error: unknown) => Response | Promise<Response>;
   onListen?: (params: { hostname: string; port: number }) => void;
   handler?: RawHandler;
@@ -594,7 +602,8 @@ function serve(arg1, arg2) {
     options = {};
   }
 
-  const wantsHttps = hasTlsKeyPairOptions(options);
+  const wantsHttps = hasTlsKeyPairOptions(options) ||
+    options.certResolver !== undefined;
   const wantsUnix = ObjectHasOwn(options, "path");
   const signal = options.signal;
   const onError = options.onError ?? function (error) {
@@ -635,15 +644,28 @@ function serve(arg1, arg2) {
     );
   }
 
+  if (options.h3 && (!options.cert || !options.key)) {
+    throw new TypeError(
+      "Both cert and key must be provided to enable HTTP/3.",
+    );
+  }
+
   let listener;
   if (wantsHttps) {
-    if (!options.cert || !options.key) {
+    // A cert resolver may be used on its own, or with a fallback key pair
+    const hasCertOrKey = options.cert !== undefined ||
+      options.key !== undefined;
+    if (
+      (hasCertOrKey || options.certResolver === undefined) &&
+      (!options.cert || !options.key)
+    ) {
       throw new TypeError(
         "Both cert and key must be provided to enable HTTPS.",
       );
     }
     listenOpts.cert = options.cert;
     listenOpts.key = options.key;
+    listenOpts.certResolver = options.certResolver;
     listenOpts.alpnProtocols = ["h2", "http/1.1"];
     listener = listenTls(listenOpts);
     listenOpts.port = listener.addr.port;
@@ -667,6 +689,16 @@ function serve(arg1, arg2) {
     }
   };
 
+  if (options.h3) {
+    return serveHttp3OnListener(
+      listener,
+      loadTlsKeyPair("Deno.serve", options),
+      signal,
+      handler,
+      onError,
+      onListen,
+    );
+  }
   return serveHttpOnListener(listener, signal, handler, onError, onListen);
 }
 
@@ -686,6 +718,63 @@ function serveHttpOnListener(listener, signal, handler, onError, onListen) {
   return serveHttpOn(context, listener.addr, callback);
 }
 
+/**
+ * Serve HTTP/3 on the UDP port of a TLS listener, along with HTTP/1.1 and/or HTTP/2 on the
+ * listener itself.
+ */
+function serveHttp3OnListener(
+  listener,
+  keyPair,
+  signal,
+  handler,
+  onError,
+  onListen,
+) {
+  const { 0: args, 1: h3Args } = op_http_serve_h3(
+    listener[internalRidSymbol],
+    keyPair,
+  );
+  const context = new CallbackContext(signal, args, listener);
+  // The requests of the HTTP/3 server come from UDP addresses
+  const h3Context = new CallbackContext(signal, h3Args, {
+    addr: { ...listener.addr, transport: "udp" },
+  });
+
+  onListen(context.scheme);
+
+  const server = serveHttpOn(
+    context,
+    listener.addr,
+    mapToCallback(context, handler, onError),
+  );
+  const h3Server = serveHttpOn(
+    h3Context,
+    listener.addr,
+    mapToCallback(h3Context, handler, onError),
+  );
+  return {
+    addr: server.addr,
+    finished: PromisePrototypeThen(
+      SafePromiseAll([server.finished, h3Server.finished]),
+      () => {},
+    ),
+    async shutdown() {
+      await SafePromiseAll([server.shutdown(), h3Server.shutdown()]);
+    },
+    ref() {
+      server.ref();
+      h3Server.ref();
+    },
+    unref() {
+      server.unref();
+      h3Server.unref();
+    },
+    [SymbolAsyncDispose]() {
+      return this.shutdown();
+    },
+  };
+}
+
 /**
  * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary connection.
  */
@@ -17,11 +17,13 @@ import {
   fromInnerResponse,
   newInnerResponse,
 } from "ext:deno_fetch/23_response.js";
+import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { setEventTargetData } from "ext:deno_web/02_event.js";
 import {
   _eventLoop,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
+  _maxBufferedAmount,
   _protocol,
   _readyState,
   _rid,
@@ -93,6 +95,14 @@ function upgradeWebSocket(request, options = {}) {
   socket[_server] = true;
   socket[_idleTimeoutDuration] = options.idleTimeout ?? 120;
   socket[_idleTimeoutTimeout] = null;
+  if (options.maxBufferedAmount !== undefined) {
+    socket[_maxBufferedAmount] = webidl.converters["unsigned long"](
+      options.maxBufferedAmount,
+      "Failed to execute 'Deno.upgradeWebSocket'",
+      "'maxBufferedAmount' of 'options'",
+      { enforceRange: true },
+    );
+  }
 
   if (inner._wantsUpgrade) {
     return inner._wantsUpgrade("upgradeWebSocket", r, socket);
@@ -0,0 +1,227 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! An experimental HTTP/3 server. The requests of its QUIC connections go
+//! through the same [`HttpRecord`]s as the HTTP/1.1 and HTTP/2 ones, so that
+//! `Deno.serve()` handlers can't tell them apart.
+
+use crate::http_next::HttpLifetime;
+use crate::request_body::RequestBody;
+use crate::request_properties::HttpConnectionProperties;
+use crate::service::handle_request;
+use crate::service::HttpRecord;
+use crate::service::HttpServerState;
+use crate::service::SignallingRc;
+use bytes::Buf;
+use bytes::Bytes;
+use deno_core::error::AnyError;
+use deno_core::futures::future::poll_fn;
+use deno_core::futures::stream;
+use deno_core::futures::stream::LocalBoxStream;
+use deno_core::futures::StreamExt;
+use deno_core::unsync::spawn;
+use deno_core::BufView;
+use deno_core::CancelFuture;
+use deno_core::CancelTryFuture;
+use deno_net::raw::NetworkStreamType;
+use deno_tls::rustls;
+use deno_tls::TlsKey;
+use h3::server::RequestStream;
+use http::header::HeaderName;
+use http::header::CONNECTION;
+use http::header::CONTENT_LENGTH;
+use http::header::TRANSFER_ENCODING;
+use http::header::UPGRADE;
+use http_body_util::BodyExt;
+use hyper::body::SizeHint;
+use std::net::SocketAddr;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The ALPN protocol of HTTP/3.
+const ALPN_H3: &[u8] = b"h3";
+
+/// The connection-specific headers, which are malformed in HTTP/3 (RFC
+/// 9114, section 4.2).
+static CONNECTION_HEADERS: [HeaderName; 5] = [
+  CONNECTION,
+  TRANSFER_ENCODING,
+  UPGRADE,
+  HeaderName::from_static("keep-alive"),
+  HeaderName::from_static("proxy-connection"),
+];
+
+type RequestRecvStream = RequestStream<h3_quinn::RecvStream, BufView>;
+
+/// Binds a QUIC endpoint to `addr`, which is usually the address of a TLS
+/// listener using the same key.
+pub(crate) fn bind_endpoint(
+  addr: SocketAddr,
+  key: TlsKey,
+) -> Result<quinn::Endpoint, AnyError> {
+  let TlsKey(cert, key) = key;
+  // QUIC requires TLS 1.3
+  let mut tls_config = rustls::ServerConfig::builder()
+    .with_safe_default_cipher_suites()
+    .with_safe_default_kx_groups()
+    .with_protocol_versions(&[&rustls::version::TLS13])?
+    .with_no_client_auth()
+    .with_single_cert(cert, key)?;
+  tls_config.alpn_protocols = vec![ALPN_H3.to_vec()];
+  let server_config = quinn::ServerConfig::with_crypto(Arc::new(tls_config));
+  Ok(quinn::Endpoint::server(server_config, addr)?)
+}
+
+/// Accepts the connections of `endpoint` until the listen cancel handle of
+/// `lifetime` is cancelled. The connections in progress are then shut down
+/// gracefully, after their requests in flight.
+pub(crate) async fn serve_endpoint(
+  endpoint: quinn::Endpoint,
+  lifetime: HttpLifetime,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+) -> Result<(), AnyError> {
+  let local_port = endpoint.local_addr()?.port();
+  loop {
+    let connecting = match endpoint
+      .accept()
+      .or_cancel(lifetime.listen_cancel_handle.clone())
+      .await
+    {
+      Ok(Some(connecting)) => connecting,
+      Ok(None) => return Ok(()),
+      Err(err) => {
+        // Refuse the new connections, the endpoint lives on until the
+        // current ones are closed
+        endpoint.set_server_config(None);
+        return Err(err.into());
+      }
+    };
+    spawn(
+      serve_connection(connecting, local_port, lifetime.clone(), tx.clone())
+        .try_or_cancel(lifetime.connection_cancel_handle.clone()),
+    );
+  }
+}
+
+async fn serve_connection(
+  connecting: quinn::Connecting,
+  local_port: u16,
+  lifetime: HttpLifetime,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+) -> Result<(), AnyError> {
+  let HttpLifetime {
+    connection_cancel_handle,
+    listen_cancel_handle,
+    server_state,
+  } = lifetime;
+
+  let connection = connecting.await?;
+  let peer_address = connection.remote_address();
+  let request_info = HttpConnectionProperties {
+    peer_address: peer_address.ip().to_string().into(),
+    peer_port: Some(peer_address.port()),
+    local_port: Some(local_port),
+    stream_type: NetworkStreamType::Tls,
+  };
+  let mut connection =
+    h3::server::Connection::new(h3_quinn::Connection::new(connection)).await?;
+
+  // Dropping the connection closes it, so it must outlive the requests
+  let requests_in_flight = SignallingRc::new(());
+  loop {
+    let (request, stream) = match connection
+      .accept()
+      .or_cancel(listen_cancel_handle.clone())
+      .await
+    {
+      Ok(Ok(Some(request))) => request,
+      Ok(Ok(None)) => return Ok(()),
+      Ok(Err(err)) => return Err(err.into()),
+      Err(_) => break,
+    };
+    let request_info = request_info.clone();
+    let server_state = server_state.clone();
+    let tx = tx.clone();
+    let requests_in_flight = requests_in_flight.clone();
+    spawn(
+      async move {
+        let res =
+          serve_request(request, stream, request_info, server_state, tx).await;
+        drop(requests_in_flight);
+        res
+      }
+      .try_or_cancel(connection_cancel_handle.clone()),
+    );
+  }
+
+  // The server no longer has to wait for this connection, only for its
+  // requests in flight
+  drop(server_state);
+  connection.shutdown(0).await?;
+  poll_fn(|cx| requests_in_flight.poll_complete(cx)).await;
+  Ok(())
+}
+
+async fn serve_request(
+  request: http::Request<()>,
+  stream: RequestStream<h3_quinn::BidiStream<BufView>, BufView>,
+  request_info: HttpConnectionProperties,
+  server_state: SignallingRc<HttpServerState>,
+  tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
+) -> Result<(), AnyError> {
+  let (mut send_stream, recv_stream) = stream.split();
+  let size_hint = request
+    .headers()
+    .get(CONTENT_LENGTH)
+    .and_then(|value| value.to_str().ok()?.parse().ok())
+    .map(SizeHint::with_exact)
+    .unwrap_or_default();
+  let body = RequestBody::Stream(request_body(recv_stream), size_hint);
+  let request = request.map(|()| body);
+
+  let response =
+    handle_request(request, request_info, server_state, tx).await?;
+  let (mut parts, mut body) = response.into_parts();
+  for name in &CONNECTION_HEADERS {
+    parts.headers.remove(name);
+  }
+  send_stream
+    .send_response(http::Response::from_parts(parts, ()))
+    .await?;
+
+  while let Some(frame) = body.frame().await {
+    match frame?.into_data() {
+      Ok(data) => send_stream.send_data(data).await?,
+      Err(frame) => {
+        if let Ok(trailers) = frame.into_trailers() {
+          send_stream.send_trailers(trailers).await?;
+          break;
+        }
+      }
+    }
+  }
+  send_stream.finish().await?;
+  Ok(())
+}
+
+/// The data frames of an HTTP/3 request, without the empty ones. The body
+/// ends after an error.
+fn request_body(
+  recv_stream: RequestRecvStream,
+) -> LocalBoxStream<'static, Result<Bytes, AnyError>> {
+  stream::unfold(Some(recv_stream), |recv_stream| async move {
+    let mut recv_stream = recv_stream?;
+    loop {
+      match recv_stream.recv_data().await {
+        Ok(Some(mut data)) => {
+          if data.has_remaining() {
+            let data = data.copy_to_bytes(data.remaining());
+            break Some((Ok(data), Some(recv_stream)));
+          }
+        }
+        Ok(None) => break None,
+        Err(err) => break Some((Err(err.into()), None)),
+      }
+    }
+  })
+  .boxed_local()
+}
@@ -1,8 +1,10 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use crate::compressible::is_content_compressible;
 use crate::extract_network_stream;
+use crate::http3;
 use crate::network_buffered_stream::NetworkStreamPrefixCheck;
 use crate::request_body::HttpRequestBody;
+use crate::request_properties::listener_properties;
 use crate::request_properties::HttpConnectionProperties;
 use crate::request_properties::HttpListenProperties;
 use crate::request_properties::HttpPropertyExtractor;
@@ -19,6 +21,8 @@ use crate::service::SignallingRc;
 use crate::websocket_upgrade::WebSocketUpgrade;
 use crate::LocalExecutor;
 use cache_control::CacheControl;
+use deno_core::error::bad_resource;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::external;
 use deno_core::futures::future::poll_fn;
@@ -41,8 +45,14 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_net::ops_tls::TlsListener;
 use deno_net::ops_tls::TlsStream;
+use deno_net::raw::NetworkListenerResource;
 use deno_net::raw::NetworkStream;
+use deno_net::raw::NetworkStreamAddress;
+use deno_net::raw::NetworkStreamType;
+use deno_tls::TlsKeys;
+use deno_tls::TlsKeysHolder;
 use deno_websocket::ws_create_server_stream;
 use hyper::body::Incoming;
 use hyper::header::HeaderMap;
@@ -90,11 +100,8 @@ static USE_WRITEV: Lazy<bool> = Lazy::new(|| {
   false
 });
 
-// NOTE(bartlomieju): currently we don't have any unstable HTTP features,
-// but let's keep this const here, because:
-//   a) we still need to support `--unstable-http` flag to not break user's CLI;
-//   b) we might add more unstable features in the future.
-#[allow(dead_code)]
+// NOTE(bartlomieju): we still need to support `--unstable-http` flag to not break user's CLI,
+// and it's now also needed for the experimental HTTP/3 listener.
 pub const UNSTABLE_FEATURE_NAME: &str = "http";
 
 /// All HTTP/2 connections start with this byte string.
@@ -432,8 +439,8 @@ pub fn op_http_read_request_body(
   let http =
     // SAFETY: op is called with external.
     unsafe { clone_external!(external, "op_http_read_request_body") };
-  let rid = if let Some(incoming) = http.take_request_body() {
-    let body_resource = Rc::new(HttpRequestBody::new(incoming));
+  let rid = if let Some(body) = http.take_request_body() {
+    let body_resource = Rc::new(HttpRequestBody::new(body));
     state.borrow_mut().resource_table.add_rc(body_resource)
   } else {
     // This should not be possible, but rather than panicking we'll return an invalid
@@ -908,10 +915,10 @@ where
 }
 
 #[derive(Clone)]
-struct HttpLifetime {
-  connection_cancel_handle: Rc<CancelHandle>,
-  listen_cancel_handle: Rc<CancelHandle>,
-  server_state: SignallingRc<HttpServerState>,
+pub(crate) struct HttpLifetime {
+  pub(crate) connection_cancel_handle: Rc<CancelHandle>,
+  pub(crate) listen_cancel_handle: Rc<CancelHandle>,
+  pub(crate) server_state: SignallingRc<HttpServerState>,
 }
 
 struct HttpJoinHandle {
@@ -976,6 +983,17 @@ pub fn op_http_serve<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] listener_rid: ResourceId,
 ) -> Result<(ResourceId, &'static str, String), AnyError>
+where
+  HTTP: HttpPropertyExtractor,
+{
+  serve_listener::<HTTP>(&state, listener_rid, None)
+}
+
+fn serve_listener<HTTP>(
+  state: &Rc<RefCell<OpState>>,
+  listener_rid: ResourceId,
+  alt_svc: Option<HeaderValue>,
+) -> Result<(ResourceId, &'static str, String), AnyError>
 where
   HTTP: HttpPropertyExtractor,
 {
@@ -986,6 +1004,9 @@ where
 
   let (tx, rx) = tokio::sync::mpsc::channel(10);
   let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  if let Some(alt_svc) = alt_svc {
+    resource.server_state.set_alt_svc(alt_svc);
+  }
   let listen_cancel_clone = resource.listen_cancel_handle();
 
   let lifetime = resource.lifetime();
@@ -1019,6 +1040,75 @@ where
   ))
 }
 
+/// Serves HTTP/3 on the UDP counterpart of a TLS listener, along with HTTP/1.1 and HTTP/2 on the
+/// listener itself. These advertise the HTTP/3 endpoint with an `Alt-Svc` header, as browsers
+/// only try QUIC after finding it there. Returns a server for each of them.
+#[allow(clippy::type_complexity)]
+#[op2]
+#[serde]
+pub fn op_http_serve_h3<HTTP>(
+  state: Rc<RefCell<OpState>>,
+  #[smi] listener_rid: ResourceId,
+  #[cppgc] keys: &TlsKeysHolder,
+) -> Result<
+  (
+    (ResourceId, &'static str, String),
+    (ResourceId, &'static str, String),
+  ),
+  AnyError,
+>
+where
+  HTTP: HttpPropertyExtractor,
+{
+  state
+    .borrow()
+    .feature_checker
+    .check_or_exit(UNSTABLE_FEATURE_NAME, "Deno.serve({ h3: true })");
+
+  let TlsKeys::Static(key) = keys.take() else {
+    return Err(type_error("HTTP/3 requires a static certificate and key"));
+  };
+
+  // The permission to listen on this address was checked for the listener
+  let local_addr = {
+    let listener = state
+      .borrow()
+      .resource_table
+      .get::<NetworkListenerResource<TlsListener>>(listener_rid)?;
+    RcRef::map(&listener, |this| &this.listener)
+      .try_borrow()
+      .ok_or_else(|| bad_resource("Listener is currently in use"))?
+      .local_addr()?
+  };
+  let endpoint = http3::bind_endpoint(local_addr, key)?;
+  let listen_properties = listener_properties(
+    NetworkStreamType::Tls,
+    NetworkStreamAddress::Ip(local_addr),
+  )?;
+
+  let alt_svc =
+    HeaderValue::try_from(format!("h3=\":{}\"; ma=86400", local_addr.port()))?;
+  let server = serve_listener::<HTTP>(&state, listener_rid, Some(alt_svc))?;
+
+  let (tx, rx) = tokio::sync::mpsc::channel(10);
+  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  let handle = spawn(http3::serve_endpoint(endpoint, resource.lifetime(), tx));
+
+  // Set the handle after we start the future
+  *RcRef::map(&resource, |this| &this.join_handle)
+    .try_borrow_mut()
+    .unwrap() = Some(handle);
+
+  Ok((
+    server,
+    (
+      state.borrow_mut().resource_table.add_rc(resource),
+      listen_properties.scheme,
+      listen_properties.fallback_host,
+    ),
+  ))
+}
+
 #[op2]
 #[serde]
 pub fn op_http_serve_on<HTTP>(
@@ -79,6 +79,7 @@ use crate::reader_stream::ShutdownHandle;
 
 pub mod compressible;
 mod fly_accept_encoding;
+mod http3;
 mod http_next;
 mod network_buffered_stream;
 mod reader_stream;
@@ -115,6 +116,7 @@ deno_core::extension!(
     http_next::op_http_read_request_body,
     http_next::op_http_serve_on<HTTP>,
     http_next::op_http_serve<HTTP>,
+    http_next::op_http_serve_h3<HTTP>,
     http_next::op_http_set_promise_complete,
     http_next::op_http_set_response_body_bytes,
     http_next::op_http_set_response_body_resource,
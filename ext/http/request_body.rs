@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use bytes::Bytes;
 use deno_core::error::AnyError;
+use deno_core::futures::stream::LocalBoxStream;
 use deno_core::futures::stream::Peekable;
 use deno_core::futures::Stream;
 use deno_core::futures::StreamExt;
@@ -18,8 +19,21 @@ use std::rc::Rc;
 use std::task::ready;
 use std::task::Poll;
 
-/// Converts a hyper incoming body stream into a stream of [`Bytes`] that we can use to read in V8.
-struct ReadFuture(Incoming);
+/// The body of a request, either a hyper one or the data of an HTTP/3 request stream along with
+/// its size hint.
+pub enum RequestBody {
+  Incoming(Incoming),
+  Stream(LocalBoxStream<'static, Result<Bytes, AnyError>>, SizeHint),
+}
+
+impl From<Incoming> for RequestBody {
+  fn from(value: Incoming) -> Self {
+    RequestBody::Incoming(value)
+  }
+}
+
+/// Converts a request body into a stream of [`Bytes`] that we can use to read in V8.
+struct ReadFuture(RequestBody);
 
 impl Stream for ReadFuture {
   type Item = Result<Bytes, AnyError>;
@@ -28,10 +42,13 @@ impl Stream for ReadFuture {
     self: Pin<&mut Self>,
     cx: &mut std::task::Context<'_>,
   ) -> Poll<Option<Self::Item>> {
+    let body = match &mut self.get_mut().0 {
+      RequestBody::Incoming(body) => body,
+      RequestBody::Stream(stream, _) => return stream.poll_next_unpin(cx),
+    };
     // Loop until we receive a non-empty frame from Hyper
-    let this = self.get_mut();
     loop {
-      let res = ready!(Pin::new(&mut this.0).poll_frame(cx));
+      let res = ready!(Pin::new(&mut *body).poll_frame(cx));
       break match res {
         Some(Ok(frame)) => {
           if let Ok(data) = frame.into_data() {
@@ -53,8 +70,11 @@ impl Stream for ReadFuture {
 pub struct HttpRequestBody(AsyncRefCell<Peekable<ReadFuture>>, SizeHint);
 
 impl HttpRequestBody {
-  pub fn new(body: Incoming) -> Self {
-    let size_hint = body.size_hint();
+  pub fn new(body: RequestBody) -> Self {
+    let size_hint = match &body {
+      RequestBody::Incoming(body) => body.size_hint(),
+      RequestBody::Stream(_, size_hint) => size_hint.clone(),
+    };
     Self(AsyncRefCell::new(ReadFuture(body).peekable()), size_hint)
   }
 
@@ -1,4 +1,5 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use crate::request_body::RequestBody;
 use crate::request_properties::HttpConnectionProperties;
 use crate::response_body::ResponseBytesInner;
 use crate::response_body::ResponseStreamResult;
@@ -10,9 +11,10 @@ use deno_core::ResourceId;
 use http::request::Parts;
 use hyper::body::Body;
 use hyper::body::Frame;
-use hyper::body::Incoming;
 use hyper::body::SizeHint;
 use hyper::header::HeaderMap;
+use hyper::header::HeaderValue;
+use hyper::header::ALT_SVC;
 use hyper::upgrade::OnUpgrade;
 
 use scopeguard::guard;
@@ -29,7 +31,6 @@ use std::task::Context;
 use std::task::Poll;
 use std::task::Waker;
 
-pub type Request = hyper::Request<Incoming>;
 pub type Response = hyper::Response<HttpRecordResponse>;
 
 #[cfg(feature = "__http_tracing")]
@@ -73,6 +74,7 @@ pub(crate) use http_trace;
 
 pub(crate) struct HttpServerStateInner {
   pool: Vec<(Rc<HttpRecord>, HeaderMap)>,
+  alt_svc: Option<HeaderValue>,
 }
 
 /// A signalling version of `Rc` that allows one to poll for when all other references
@@ -136,8 +138,15 @@ impl HttpServerState {
   pub fn new() -> SignallingRc<Self> {
     SignallingRc::new(Self(RefCell::new(HttpServerStateInner {
       pool: Vec::new(),
+      alt_svc: None,
     })))
   }
+
+  /// Advertises an alternative service, such as an HTTP/3 endpoint, in the responses that don't
+  /// set their own `Alt-Svc` header.
+  pub fn set_alt_svc(&self, alt_svc: HeaderValue) {
+    self.borrow_mut().alt_svc = Some(alt_svc);
+  }
 }
 
 impl std::ops::Deref for HttpServerState {
@@ -149,16 +158,10 @@ impl std::ops::Deref for HttpServerState {
 }
 
 enum RequestBodyState {
-  Incoming(Incoming),
+  Incoming(RequestBody),
   Resource(#[allow(dead_code)] HttpRequestBodyAutocloser),
 }
 
-impl From<Incoming> for RequestBodyState {
-  fn from(value: Incoming) -> Self {
-    RequestBodyState::Incoming(value)
-  }
-}
-
 /// Ensures that the request body closes itself when no longer needed.
 pub struct HttpRequestBodyAutocloser(ResourceId, Rc<RefCell<OpState>>);
 
@@ -177,7 +180,7 @@ impl Drop for HttpRequestBodyAutocloser {
 }
 
 pub(crate) async fn handle_request(
-  request: Request,
+  request: http::Request<impl Into<RequestBody>>,
   request_info: HttpConnectionProperties,
   server_state: SignallingRc<HttpServerState>, // Keep server alive for duration of this future.
   tx: tokio::sync::mpsc::Sender<Rc<HttpRecord>>,
@@ -238,12 +241,12 @@ impl Drop for HttpRecord {
 
 impl HttpRecord {
   fn new(
-    request: Request,
+    request: http::Request<impl Into<RequestBody>>,
     request_info: HttpConnectionProperties,
     server_state: SignallingRc<HttpServerState>,
   ) -> Rc<Self> {
     let (request_parts, request_body) = request.into_parts();
-    let request_body = Some(request_body.into());
+    let request_body = Some(RequestBodyState::Incoming(request_body.into()));
     let (mut response_parts, _) = http::Response::new(()).into_parts();
     let record =
       if let Some((record, headers)) = server_state.borrow_mut().pool.pop() {
@@ -354,8 +357,8 @@ impl HttpRecord {
       .ok_or_else(|| AnyError::msg("upgrade unavailable"))
   }
 
-  /// Take the Hyper body from this record.
-  pub fn take_request_body(&self) -> Option<Incoming> {
+  /// Take the request body from this record.
+  pub fn take_request_body(&self) -> Option<RequestBody> {
     let body_holder = &mut self.self_mut().request_body;
     let body = body_holder.take();
     match body {
@@ -444,7 +447,13 @@ impl HttpRecord {
 
   /// Take the response.
   fn into_response(self: Rc<Self>) -> Response {
-    let parts = self.self_mut().response_parts.take().unwrap();
+    let mut parts = self.self_mut().response_parts.take().unwrap();
+    if let Some(alt_svc) = &self.self_ref().server_state.borrow().alt_svc {
+      parts
+        .headers
+        .entry(ALT_SVC)
+        .or_insert_with(|| alt_svc.clone());
+    }
     let body = HttpRecordResponse(ManuallyDrop::new(self));
     Response::from_parts(parts, body)
   }
@@ -597,8 +606,14 @@ mod tests {
   use crate::response_body::Compression;
   use crate::response_body::ResponseBytesInner;
   use bytes::Buf;
+  use bytes::Bytes;
+  use deno_core::futures::stream;
+  use deno_core::futures::StreamExt;
+  use deno_core::futures::TryFutureExt;
+  use deno_core::Resource;
   use deno_net::raw::NetworkStreamType;
   use hyper::body::Body;
+  use hyper::body::Incoming;
   use hyper::service::service_fn;
   use hyper::service::HttpService;
   use hyper_util::rt::TokioIo;
@@ -689,4 +704,44 @@ mod tests {
     assert_eq!(server_state_check.strong_count(), 1);
     Ok(())
   }
+
+  #[tokio::test]
+  async fn test_handle_request_stream_body_alt_svc() -> Result<(), AnyError> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+    let server_state = HttpServerState::new();
+    server_state.set_alt_svc(HeaderValue::from_static("h3=\":443\"; ma=86400"));
+    let server_state_check = server_state.clone();
+    let request_info = HttpConnectionProperties {
+      peer_address: "".into(),
+      peer_port: None,
+      local_port: None,
+      stream_type: NetworkStreamType::Tls,
+    };
+    let body = RequestBody::Stream(
+      stream::iter([Ok(Bytes::from_static(b"hello"))]).boxed_local(),
+      SizeHint::with_exact(5),
+    );
+    let request = http::Request::builder().uri("/").body(body)?;
+
+    let (response, ()) = tokio::try_join!(
+      handle_request(request, request_info, server_state, tx)
+        .map_err(AnyError::from),
+      async move {
+        let record = rx.recv().await.unwrap();
+        let body = crate::request_body::HttpRequestBody::new(
+          record.take_request_body().unwrap(),
+        );
+        assert_eq!(body.size_hint(), (5, Some(5)));
+        let body = Rc::new(body);
+        assert_eq!(&*body.clone().read(64).await?, b"hello");
+        assert!(body.read(64).await?.is_empty());
+        record.complete();
+        Ok(())
+      },
+    )?;
+    assert_eq!(response.headers()[ALT_SVC], "h3=\":443\"; ma=86400");
+    drop(response);
+    assert_eq!(server_state_check.strong_count(), 1);
+    Ok(())
+  }
 }
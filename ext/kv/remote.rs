@@ -142,6 +142,7 @@ impl<P: RemoteDbHandlerPermissions + 'static> DatabaseHandler
           .unwrap(),
         pool_max_idle_per_host: None,
         pool_idle_timeout: None,
+        tcp_keepalive: None,
         http1: false,
         http2: true,
       },
@@ -5,7 +5,6 @@ const {
   BadResourcePrototype,
   InterruptedPrototype,
   internalRidSymbol,
-  createCancelHandle,
 } = core;
 import {
   op_dns_resolve,
@@ -63,29 +62,16 @@ function shutdown(rid) {
 }
 
 async function resolveDns(query, recordType, options) {
-  let cancelRid;
-  let abortHandler;
-  if (options?.signal) {
-    options.signal.throwIfAborted();
-    cancelRid = createCancelHandle();
-    abortHandler = () => core.tryClose(cancelRid);
-    options.signal[abortSignal.add](abortHandler);
-  }
-
+  const cancelHandle = abortSignal.linkCancelHandle(options?.signal);
   try {
     return await op_dns_resolve({
-      cancelRid,
+      cancelRid: cancelHandle.rid,
       query,
       recordType,
       options,
     });
   } finally {
-    if (options?.signal) {
-      options.signal[abortSignal.remove](abortHandler);
-
-      // always throw the abort error when aborted
-      options.signal.throwIfAborted();
-    }
+    cancelHandle.unlink();
   }
 }
 
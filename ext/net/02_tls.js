@@ -7,6 +7,7 @@ import {
   op_net_connect_tls,
   op_net_listen_tls,
   op_tls_cert_resolver_create,
+  op_tls_cert_resolver_evict,
   op_tls_cert_resolver_poll,
   op_tls_cert_resolver_resolve,
   op_tls_cert_resolver_resolve_error,
@@ -19,6 +20,9 @@ import {
 const {
   Number,
   ObjectDefineProperty,
+  ObjectHasOwn,
+  StringPrototypeIndexOf,
+  StringPrototypeSlice,
   TypeError,
   SymbolFor,
 } = primordials;
@@ -255,17 +259,23 @@ function listenTls({
   transport = "tcp",
   alpnProtocols = undefined,
   reusePort = false,
+  certResolver = undefined,
 }) {
   if (transport !== "tcp") {
     throw new TypeError(`Unsupported transport: '${transport}'`);
   }
 
-  if (!hasTlsKeyPairOptions(arguments[0])) {
-    throw new TypeError(
-      "A key and certificate are required for `Deno.listenTls`",
-    );
+  let keyPair;
+  if (certResolver !== undefined) {
+    keyPair = createCertResolver(certResolver, arguments[0]);
+  } else {
+    if (!hasTlsKeyPairOptions(arguments[0])) {
+      throw new TypeError(
+        "A key and certificate are required for `Deno.listenTls`",
+      );
+    }
+    keyPair = loadTlsKeyPair("Deno.listenTls", arguments[0]);
   }
-  const keyPair = loadTlsKeyPair("Deno.listenTls", arguments[0]);
   const { 0: rid, 1: localAddr } = op_net_listen_tls(
     { hostname, port: Number(port) },
     { alpnProtocols, reusePort },
@@ -295,12 +305,15 @@ async function startTls(
 const resolverSymbol = SymbolFor("unstableSniResolver");
 const serverNameSymbol = SymbolFor("unstableServerName");
 
-function createTlsKeyResolver(callback) {
+function createTlsKeyResolver(callback, onLookup = undefined) {
   const { 0: resolver, 1: lookup } = op_tls_cert_resolver_create();
+  const onClose = onLookup === undefined ? undefined : onLookup(lookup);
   (async () => {
     while (true) {
       const sni = await op_tls_cert_resolver_poll(lookup);
       if (typeof sni !== "string") {
+        // the listener using the resolver was closed
+        onClose?.();
         break;
       }
       try {
@@ -319,11 +332,78 @@ function createTlsKeyResolver(callback) {
   return resolver;
 }
 
+/**
+ * A `certResolver` may have a function under this symbol, which is called
+ * with an `evict(serverName)` function for every listener using it. Evicting
+ * a server name makes the listener resolve its key pair again on the next
+ * handshake, ie: after a certificate was renewed. The function may return
+ * a callback, which is called once the listener is closed.
+ */
+const certResolverEvictSymbol = Symbol("certResolverEvict");
+
+/**
+ * Looks up the key pair for a server name in a map of server names to key
+ * pairs. Matches the exact name first, then a wildcard entry for the parent
+ * domain (ie: `*.example.com` for `www.example.com`).
+ */
+function lookupCertMap(certs, serverName) {
+  if (ObjectHasOwn(certs, serverName)) {
+    return certs[serverName];
+  }
+  const dot = StringPrototypeIndexOf(serverName, ".");
+  if (dot !== -1) {
+    const wildcard = "*" + StringPrototypeSlice(serverName, dot);
+    if (ObjectHasOwn(certs, wildcard)) {
+      return certs[wildcard];
+    }
+  }
+  return undefined;
+}
+
+/**
+ * Creates a key resolver for the `certResolver` option of `Deno.listenTls`,
+ * which is either a callback or a map keyed by server name. The key pair
+ * passed directly to `Deno.listenTls`, if any, is used for server names that
+ * aren't resolved to a key pair.
+ */
+function createCertResolver(certResolver, options) {
+  let lookup;
+  if (typeof certResolver === "function") {
+    lookup = certResolver;
+  } else if (certResolver !== null && typeof certResolver === "object") {
+    lookup = (serverName) => lookupCertMap(certResolver, serverName);
+  } else {
+    throw new TypeError(
+      "`certResolver` must be a function or an object for `Deno.listenTls`",
+    );
+  }
+  const fallback = hasTlsKeyPairOptions(options) ? options : undefined;
+  const onEvict = certResolver[certResolverEvictSymbol];
+  return createTlsKeyResolver(
+    async (serverName) => {
+      const key = await lookup(serverName);
+      if (key !== undefined && key !== null) {
+        return key;
+      }
+      if (fallback === undefined) {
+        throw new TypeError(`No certificate for server name '${serverName}'`);
+      }
+      return fallback;
+    },
+    onEvict === undefined ? undefined : (keyLookup) =>
+      onEvict((serverName) =>
+        op_tls_cert_resolver_evict(keyLookup, serverName)
+      ),
+  );
+}
+
 internals.resolverSymbol = resolverSymbol;
+internals.certResolverEvictSymbol = certResolverEvictSymbol;
 internals.serverNameSymbol = serverNameSymbol;
 internals.createTlsKeyResolver = createTlsKeyResolver;
 
 export {
+  certResolverEvictSymbol,
   connectTls,
   hasTlsKeyPairOptions,
   listenTls,
@@ -91,6 +91,7 @@ deno_core::extension!(deno_net,
     ops_tls::op_tls_cert_resolver_poll,
     ops_tls::op_tls_cert_resolver_resolve,
     ops_tls::op_tls_cert_resolver_resolve_error,
+    ops_tls::op_tls_cert_resolver_evict,
     ops_tls::op_tls_start<P>,
     ops_tls::op_net_connect_tls<P>,
     ops_tls::op_net_listen_tls<P>,
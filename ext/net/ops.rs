@@ -672,7 +672,7 @@ pub fn op_set_keepalive_inner(
   resource.set_keepalive(keepalive)
 }
 
-fn rdata_to_return_record(
+pub fn rdata_to_return_record(
   ty: RecordType,
 ) -> impl Fn(&RData) -> Result<Option<DnsReturnRecord>, AnyError> {
   use RecordType::*;
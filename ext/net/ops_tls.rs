@@ -271,6 +271,14 @@ pub async fn op_tls_cert_resolver_poll(
   lookup.poll().await
 }
 
+#[op2(fast)]
+pub fn op_tls_cert_resolver_evict(
+  #[cppgc] lookup: &TlsKeyLookup,
+  #[string] sni: &str,
+) {
+  lookup.evict(sni);
+}
+
 #[op2(fast)]
 pub fn op_tls_cert_resolver_resolve(
   #[cppgc] lookup: &TlsKeyLookup,
@@ -534,11 +542,16 @@ where
         server_config_provider: None,
       })
     }
-    TlsKeys::Resolver(resolver) => Ok(TlsListener {
-      tcp_listener,
-      tls_config: None,
-      server_config_provider: Some(resolver.into_server_config_provider(alpn)),
-    }),
+    TlsKeys::Resolver(resolver) => {
+      super::check_unstable(state, "Deno.listenTls({ certResolver })");
+      Ok(TlsListener {
+        tcp_listener,
+        tls_config: None,
+        server_config_provider: Some(
+          resolver.into_server_config_provider(alpn),
+        ),
+      })
+    }
   }
   .map_err(|e| {
     custom_error("InvalidData", "Error creating TLS certificate").context(e)
@@ -51,6 +51,11 @@ use crate::global::global_object_middleware;
 use crate::global::global_template_middleware;
 
 pub trait NodePermissions {
+  fn check_net(
+    &mut self,
+    host: (&str, Option<u16>),
+    api_name: &str,
+  ) -> Result<(), AnyError>;
   fn check_net_url(
     &mut self,
     url: &Url,
@@ -76,6 +81,13 @@ pub trait NodePermissions {
 pub(crate) struct AllowAllNodePermissions;
 
 impl NodePermissions for AllowAllNodePermissions {
+  fn check_net(
+    &mut self,
+    _host: (&str, Option<u16>),
+    _api_name: &str,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
   fn check_net_url(
     &mut self,
     _url: &Url,
@@ -187,70 +199,109 @@ deno_core::extension!(deno_node,
   parameters = [P: NodePermissions],
   ops = [
     ops::crypto::op_node_create_decipheriv,
-    ops::crypto::op_node_cipheriv_encrypt,
+    ops::crypto::op_node_cipheriv_update,
+    ops::crypto::op_node_cipheriv_update_stream,
     ops::crypto::op_node_cipheriv_final,
+    ops::crypto::op_node_cipheriv_pipe,
     ops::crypto::op_node_cipheriv_set_aad,
     ops::crypto::op_node_decipheriv_set_aad,
+    ops::crypto::op_node_decipheriv_set_auth_tag,
     ops::crypto::op_node_create_cipheriv,
     ops::crypto::op_node_create_hash,
     ops::crypto::op_node_get_hashes,
-    ops::crypto::op_node_decipheriv_decrypt,
+    ops::crypto::op_node_get_ciphers,
+    ops::crypto::op_node_get_curves,
+    ops::crypto::op_node_decipheriv_update,
+    ops::crypto::op_node_decipheriv_update_stream,
     ops::crypto::op_node_decipheriv_final,
+    ops::crypto::op_node_decipheriv_pipe,
     ops::crypto::op_node_hash_update,
     ops::crypto::op_node_hash_update_str,
+    ops::crypto::op_node_hash_update_stream,
     ops::crypto::op_node_hash_digest,
     ops::crypto::op_node_hash_digest_hex,
     ops::crypto::op_node_hash_clone,
+    ops::crypto::op_node_create_hmac,
+    ops::crypto::op_node_hmac_update,
+    ops::crypto::op_node_hmac_update_stream,
+    ops::crypto::op_node_hmac_digest,
     ops::crypto::op_node_private_encrypt,
     ops::crypto::op_node_private_decrypt,
     ops::crypto::op_node_public_encrypt,
+    ops::crypto::op_node_public_decrypt,
     ops::crypto::op_node_check_prime,
     ops::crypto::op_node_check_prime_async,
     ops::crypto::op_node_check_prime_bytes,
     ops::crypto::op_node_check_prime_bytes_async,
     ops::crypto::op_node_gen_prime,
-    ops::crypto::op_node_gen_prime_async,
+    ops::crypto::op_node_generate_prime,
+    ops::crypto::op_node_generate_prime_async,
+    ops::crypto::op_node_mod_inv,
+    ops::crypto::op_node_mod_pow,
     ops::crypto::op_node_pbkdf2,
     ops::crypto::op_node_pbkdf2_async,
     ops::crypto::op_node_hkdf,
     ops::crypto::op_node_hkdf_async,
     ops::crypto::op_node_generate_secret,
     ops::crypto::op_node_generate_secret_async,
-    ops::crypto::op_node_sign,
-    ops::crypto::op_node_generate_rsa,
-    ops::crypto::op_node_generate_rsa_async,
-    ops::crypto::op_node_dsa_generate,
-    ops::crypto::op_node_dsa_generate_async,
-    ops::crypto::op_node_ec_generate,
-    ops::crypto::op_node_ec_generate_async,
-    ops::crypto::op_node_ed25519_generate,
-    ops::crypto::op_node_ed25519_generate_async,
-    ops::crypto::op_node_x25519_generate,
-    ops::crypto::op_node_x25519_generate_async,
+    ops::crypto::sign::op_node_sign,
+    ops::crypto::keygen::op_node_generate_rsa,
+    ops::crypto::keygen::op_node_generate_rsa_async,
+    ops::crypto::keygen::op_node_dsa_generate,
+    ops::crypto::keygen::op_node_dsa_generate_async,
+    ops::crypto::keygen::op_node_ec_generate,
+    ops::crypto::keygen::op_node_ec_generate_async,
+    ops::crypto::keygen::op_node_ed25519_generate,
+    ops::crypto::keygen::op_node_ed25519_generate_async,
+    ops::crypto::keygen::op_node_x25519_generate,
+    ops::crypto::keygen::op_node_x25519_generate_async,
     ops::crypto::op_node_dh_generate_group,
     ops::crypto::op_node_dh_generate_group_async,
     ops::crypto::op_node_dh_generate,
-    ops::crypto::op_node_dh_generate2,
-    ops::crypto::op_node_dh_compute_secret,
     ops::crypto::op_node_dh_generate_async,
-    ops::crypto::op_node_verify,
+    ops::crypto::op_node_dh_new,
+    ops::crypto::op_node_dh_new_group,
+    ops::crypto::op_node_dh_generate_keys,
+    ops::crypto::op_node_dh_compute_secret,
+    ops::crypto::op_node_dh_get_prime,
+    ops::crypto::op_node_dh_get_generator,
+    ops::crypto::op_node_dh_get_private_key,
+    ops::crypto::op_node_dh_get_public_key,
+    ops::crypto::op_node_dh_set_private_key,
+    ops::crypto::op_node_dh_set_public_key,
+    ops::crypto::sign::op_node_verify,
     ops::crypto::op_node_random_int,
+    ops::crypto::op_node_random_fill,
+    ops::crypto::op_node_random_fill_async,
+    ops::crypto::op_node_random_uuid,
     ops::crypto::op_node_scrypt_sync,
     ops::crypto::op_node_scrypt_async,
     ops::crypto::op_node_ecdh_generate_keys,
     ops::crypto::op_node_ecdh_compute_secret,
     ops::crypto::op_node_ecdh_compute_public_key,
     ops::crypto::op_node_ecdh_encode_pubkey,
-    ops::crypto::op_node_export_rsa_public_pem,
-    ops::crypto::op_node_export_rsa_spki_der,
+    ops::crypto::keys::op_node_create_private_key,
+    ops::crypto::keys::op_node_create_public_key,
+    ops::crypto::keys::op_node_create_private_key_from_jwk,
+    ops::crypto::keys::op_node_create_public_key_from_jwk,
+    ops::crypto::keys::op_node_create_public_key_from_private,
+    ops::crypto::keys::op_node_create_generated_key,
+    ops::crypto::keys::op_node_get_asymmetric_key_details,
+    ops::crypto::keys::op_node_export_private_key,
+    ops::crypto::keys::op_node_export_public_key,
     ops::crypto::x509::op_node_x509_parse,
     ops::crypto::x509::op_node_x509_ca,
     ops::crypto::x509::op_node_x509_check_email,
+    ops::crypto::x509::op_node_x509_check_host,
+    ops::crypto::x509::op_node_x509_check_ip,
     ops::crypto::x509::op_node_x509_fingerprint,
     ops::crypto::x509::op_node_x509_fingerprint256,
     ops::crypto::x509::op_node_x509_fingerprint512,
     ops::crypto::x509::op_node_x509_get_issuer,
+    ops::crypto::x509::op_node_x509_get_public_key,
+    ops::crypto::x509::op_node_x509_get_raw,
     ops::crypto::x509::op_node_x509_get_subject,
+    ops::crypto::x509::op_node_x509_get_subject_alt_name,
     ops::crypto::x509::op_node_x509_get_valid_from,
     ops::crypto::x509::op_node_x509_get_valid_to,
     ops::crypto::x509::op_node_x509_get_serial_number,
@@ -259,14 +310,35 @@ deno_core::extension!(deno_node,
     ops::fs::op_node_cp_sync<P>,
     ops::fs::op_node_cp<P>,
     ops::fs::op_node_statfs<P>,
+    ops::fs::op_node_fs_opendir_sync<P>,
+    ops::fs::op_node_fs_dir_read_sync,
+    ops::fs::op_node_fs_dir_read,
+    ops::fs::op_node_fs_realpath_sync<P>,
+    ops::fs::op_node_fs_realpath<P>,
     ops::winerror::op_node_sys_to_uv_error,
     ops::v8::op_v8_cached_data_version_tag,
     ops::v8::op_v8_get_heap_statistics,
+    ops::v8::op_v8_serializer_header,
+    ops::v8::op_v8_serialize_value,
+    ops::v8::op_v8_deserialize_value,
     ops::vm::op_vm_create_script,
     ops::vm::op_vm_create_context,
     ops::vm::op_vm_script_run_in_context,
     ops::vm::op_vm_script_run_in_this_context,
     ops::vm::op_vm_is_context,
+    ops::vm::op_vm_compile_function,
+    ops::blocklist::op_node_blocklist_new,
+    ops::blocklist::op_node_blocklist_add_address,
+    ops::blocklist::op_node_blocklist_add_range,
+    ops::blocklist::op_node_blocklist_add_subnet,
+    ops::blocklist::op_node_blocklist_check,
+    ops::dns::op_node_dns_system_servers,
+    ops::dns::op_node_dns_channel_new<P>,
+    ops::dns::op_node_dns_channel_cancel,
+    ops::dns::op_node_dns_query,
+    ops::dns::op_node_dns_reverse,
+    ops::blocklist::op_node_blocklist_get_rules,
+    ops::blocklist::op_node_socket_address_parse,
     ops::idna::op_node_idna_domain_to_ascii,
     ops::idna::op_node_idna_domain_to_unicode,
     ops::idna::op_node_idna_punycode_to_ascii,
@@ -279,17 +351,21 @@ deno_core::extension!(deno_node,
     ops::zlib::op_zlib_write,
     ops::zlib::op_zlib_write_async,
     ops::zlib::op_zlib_init,
+    ops::zlib::op_zlib_params,
     ops::zlib::op_zlib_reset,
     ops::zlib::brotli::op_brotli_compress,
     ops::zlib::brotli::op_brotli_compress_async,
-    ops::zlib::brotli::op_create_brotli_compress,
-    ops::zlib::brotli::op_brotli_compress_stream,
-    ops::zlib::brotli::op_brotli_compress_stream_end,
     ops::zlib::brotli::op_brotli_decompress,
     ops::zlib::brotli::op_brotli_decompress_async,
-    ops::zlib::brotli::op_create_brotli_decompress,
-    ops::zlib::brotli::op_brotli_decompress_stream,
-    ops::zlib::brotli::op_brotli_decompress_stream_end,
+    ops::zlib::brotli::op_brotli_stream_new,
+    ops::zlib::brotli::op_brotli_stream_init,
+    ops::zlib::brotli::op_brotli_stream_reset,
+    ops::zlib::brotli::op_brotli_stream_write,
+    ops::zlib::brotli::op_brotli_stream_write_async,
+    ops::zlib::brotli::op_brotli_stream_error,
+    ops::http::op_node_http_agent_new,
+    ops::http::op_node_http_agent_close,
+    ops::http::op_node_http_agent_stats,
     ops::http::op_node_http_request<P>,
     ops::http2::op_http2_connect,
     ops::http2::op_http2_poll_client_connection,
@@ -304,6 +380,14 @@ deno_core::extension!(deno_node,
     ops::http2::op_http2_accept,
     ops::http2::op_http2_listen,
     ops::http2::op_http2_send_response,
+    ops::http2::op_http2_server_send_data,
+    ops::http2::op_http2_server_send_trailers,
+    ops::http2::op_http2_server_reset_stream,
+    ops::http2::op_http2_server_push_stream,
+    ops::http2::op_http2_session_ping,
+    ops::http2::op_http2_session_settings,
+    ops::http2::op_http2_session_set_local_window_size,
+    ops::http2::op_http2_session_goaway,
     ops::os::op_node_os_get_priority<P>,
     ops::os::op_node_os_set_priority<P>,
     ops::os::op_node_os_username<P>,
@@ -337,11 +421,11 @@ deno_core::extension!(deno_node,
     ops::require::op_require_break_on_next_statement,
     ops::util::op_node_guess_handle_type,
     ops::worker_threads::op_worker_threads_filename<P>,
-    ops::crypto::op_node_create_private_key,
-    ops::crypto::op_node_create_public_key,
     ops::ipc::op_node_child_ipc_pipe,
     ops::ipc::op_node_ipc_write,
+    ops::ipc::op_node_ipc_write_advanced,
     ops::ipc::op_node_ipc_read,
+    ops::ipc::op_node_ipc_read_advanced,
     ops::process::op_node_process_kill,
     ops::process::op_process_abort,
   ],
@@ -444,6 +528,7 @@ deno_core::extension!(deno_node,
     "internal_binding/uv.ts",
     "internal/assert.mjs",
     "internal/async_hooks.ts",
+    "internal/blocklist.mjs",
     "internal/buffer.mjs",
     "internal/child_process.ts",
     "internal/cli_table.ts",
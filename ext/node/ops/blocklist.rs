@@ -0,0 +1,274 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Rule storage and address matching for `net.BlockList`.
+//!
+//! Addresses are compared as IPv6, with IPv4 addresses mapped to
+//! `::ffff:0:0/96`, so that an IPv4 rule also matches the IPv4-mapped form
+//! of the address and vice versa, like in Node.
+
+use std::cell::RefCell;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::v8;
+
+/// The prefix length of the IPv4-mapped IPv6 addresses.
+const IPV4_MAPPED_PREFIX: u8 = 96;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockListRule {
+  Address(IpAddr),
+  Range(IpAddr, IpAddr),
+  Subnet(IpAddr, u8),
+}
+
+impl BlockListRule {
+  fn matches(&self, address: u128) -> bool {
+    match *self {
+      Self::Address(rule) => to_u128(rule) == address,
+      Self::Range(start, end) => {
+        (to_u128(start)..=to_u128(end)).contains(&address)
+      }
+      Self::Subnet(network, prefix) => {
+        let prefix = match network {
+          IpAddr::V4(_) => prefix + IPV4_MAPPED_PREFIX,
+          IpAddr::V6(_) => prefix,
+        };
+        let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+        to_u128(network) & mask == address & mask
+      }
+    }
+  }
+}
+
+impl std::fmt::Display for BlockListRule {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Address(address) => {
+        write!(f, "Address: {} {}", family(address), address)
+      }
+      Self::Range(start, end) => {
+        write!(f, "Range: {} {}-{}", family(start), start, end)
+      }
+      Self::Subnet(network, prefix) => {
+        write!(f, "Subnet: {} {}/{}", family(network), network, prefix)
+      }
+    }
+  }
+}
+
+fn family(address: &IpAddr) -> &'static str {
+  match address {
+    IpAddr::V4(_) => "IPv4",
+    IpAddr::V6(_) => "IPv6",
+  }
+}
+
+fn to_u128(address: IpAddr) -> u128 {
+  match address {
+    IpAddr::V4(address) => address.to_ipv6_mapped().into(),
+    IpAddr::V6(address) => address.into(),
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct BlockList {
+  rules: RefCell<Vec<BlockListRule>>,
+}
+
+impl BlockList {
+  fn add(&self, rule: BlockListRule) {
+    self.rules.borrow_mut().push(rule);
+  }
+
+  fn check(&self, address: IpAddr) -> bool {
+    let address = to_u128(address);
+    self.rules.borrow().iter().any(|rule| rule.matches(address))
+  }
+
+  /// Returns the rules, most recently added first.
+  fn rules(&self) -> Vec<String> {
+    self
+      .rules
+      .borrow()
+      .iter()
+      .rev()
+      .map(|rule| rule.to_string())
+      .collect()
+  }
+}
+
+/// Parses `address` as an address of the given family, `4` or `6`.
+fn parse_address(address: &str, family: u8) -> Option<IpAddr> {
+  match family {
+    4 => address.parse::<Ipv4Addr>().ok().map(IpAddr::V4),
+    6 => address.parse::<Ipv6Addr>().ok().map(IpAddr::V6),
+    _ => None,
+  }
+}
+
+fn parse_address_or_throw(
+  address: &str,
+  family: u8,
+) -> Result<IpAddr, AnyError> {
+  parse_address(address, family)
+    .ok_or_else(|| type_error(format!("Invalid socket address: {address}")))
+}
+
+/// Returns the canonical form of `address`, or `None` if it isn't a valid
+/// address of the given family.
+#[op2]
+#[string]
+pub fn op_node_socket_address_parse(
+  #[string] address: &str,
+  #[smi] family: u8,
+) -> Option<String> {
+  parse_address(address, family).map(|address| address.to_string())
+}
+
+#[op2]
+pub fn op_node_blocklist_new<'s>(
+  scope: &'s mut v8::HandleScope,
+) -> v8::Local<'s, v8::Object> {
+  deno_core::cppgc::make_cppgc_object(scope, BlockList::default())
+}
+
+#[op2(fast)]
+pub fn op_node_blocklist_add_address(
+  #[cppgc] blocklist: &BlockList,
+  #[string] address: &str,
+  #[smi] family: u8,
+) -> Result<(), AnyError> {
+  let address = parse_address_or_throw(address, family)?;
+  blocklist.add(BlockListRule::Address(address));
+  Ok(())
+}
+
+/// Returns `false` without adding a rule when `start` comes after `end`.
+#[op2(fast)]
+pub fn op_node_blocklist_add_range(
+  #[cppgc] blocklist: &BlockList,
+  #[string] start: &str,
+  #[smi] start_family: u8,
+  #[string] end: &str,
+  #[smi] end_family: u8,
+) -> Result<bool, AnyError> {
+  let start = parse_address_or_throw(start, start_family)?;
+  let end = parse_address_or_throw(end, end_family)?;
+  if to_u128(start) > to_u128(end) {
+    return Ok(false);
+  }
+  blocklist.add(BlockListRule::Range(start, end));
+  Ok(true)
+}
+
+#[op2(fast)]
+pub fn op_node_blocklist_add_subnet(
+  #[cppgc] blocklist: &BlockList,
+  #[string] network: &str,
+  #[smi] family: u8,
+  #[smi] prefix: u8,
+) -> Result<(), AnyError> {
+  let network = parse_address_or_throw(network, family)?;
+  let max_prefix = match network {
+    IpAddr::V4(_) => 32,
+    IpAddr::V6(_) => 128,
+  };
+  if prefix > max_prefix {
+    return Err(type_error(format!("Invalid subnet prefix: {prefix}")));
+  }
+  blocklist.add(BlockListRule::Subnet(network, prefix));
+  Ok(())
+}
+
+#[op2(fast)]
+pub fn op_node_blocklist_check(
+  #[cppgc] blocklist: &BlockList,
+  #[string] address: &str,
+  #[smi] family: u8,
+) -> Result<bool, AnyError> {
+  let address = parse_address_or_throw(address, family)?;
+  Ok(blocklist.check(address))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_blocklist_get_rules(
+  #[cppgc] blocklist: &BlockList,
+) -> Vec<String> {
+  blocklist.rules()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ip(address: &str) -> IpAddr {
+    address.parse().unwrap()
+  }
+
+  #[test]
+  fn check() {
+    let blocklist = BlockList::default();
+    blocklist.add(BlockListRule::Address(ip("123.123.123.123")));
+    blocklist.add(BlockListRule::Range(ip("10.0.0.1"), ip("10.0.0.10")));
+    blocklist.add(BlockListRule::Subnet(ip("8592:757c:efae:4e45::"), 64));
+    blocklist.add(BlockListRule::Subnet(ip("192.168.0.0"), 16));
+
+    assert!(blocklist.check(ip("123.123.123.123")));
+    assert!(!blocklist.check(ip("123.123.123.124")));
+    assert!(blocklist.check(ip("10.0.0.1")));
+    assert!(blocklist.check(ip("10.0.0.10")));
+    assert!(!blocklist.check(ip("10.0.0.11")));
+    assert!(blocklist.check(ip("8592:757c:efae:4e45:f::")));
+    assert!(!blocklist.check(ip("8592:757c:efae:4e46::")));
+    assert!(blocklist.check(ip("192.168.255.255")));
+    assert!(!blocklist.check(ip("192.169.0.0")));
+    // IPv4-mapped IPv6 addresses match IPv4 rules
+    assert!(blocklist.check(ip("::ffff:123.123.123.123")));
+    assert!(blocklist.check(ip("::ffff:10.0.0.5")));
+    assert!(blocklist.check(ip("::ffff:192.168.1.1")));
+  }
+
+  #[test]
+  fn subnet_prefix_bounds() {
+    let blocklist = BlockList::default();
+    blocklist.add(BlockListRule::Subnet(ip("1.2.3.4"), 32));
+    assert!(blocklist.check(ip("1.2.3.4")));
+    assert!(!blocklist.check(ip("1.2.3.5")));
+
+    let blocklist = BlockList::default();
+    blocklist.add(BlockListRule::Subnet(ip("::"), 0));
+    assert!(blocklist.check(ip("ffff::1")));
+    assert!(blocklist.check(ip("1.2.3.4")));
+  }
+
+  #[test]
+  fn rules() {
+    let blocklist = BlockList::default();
+    blocklist.add(BlockListRule::Address(ip("1.1.1.1")));
+    blocklist.add(BlockListRule::Range(ip("10.0.0.1"), ip("10.0.0.10")));
+    blocklist.add(BlockListRule::Subnet(ip("8592:757c:efae:4e45::"), 64));
+    assert_eq!(
+      blocklist.rules(),
+      vec![
+        "Subnet: IPv6 8592:757c:efae:4e45::/64",
+        "Range: IPv4 10.0.0.1-10.0.0.10",
+        "Address: IPv4 1.1.1.1",
+      ]
+    );
+  }
+
+  #[test]
+  fn parse() {
+    assert_eq!(parse_address("1.2.3.4", 4), Some(ip("1.2.3.4")));
+    assert_eq!(parse_address("1.2.3.4", 6), None);
+    assert_eq!(parse_address("::1", 4), None);
+    assert_eq!(parse_address("0:0:0:0:0:0:0:1", 6), Some(ip("::1")));
+    assert_eq!(parse_address("1.2.3.4", 5), None);
+  }
+}
@@ -0,0 +1,236 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The BLAKE2b-512 and BLAKE2s-256 hash functions of RFC 7693, unkeyed.
+
+const SIGMA: [[usize; 16]; 10] = [
+  [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+  [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+  [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+  [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+  [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+  [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+  [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+  [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+  [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+  [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Both variants only differ by their word size and constants.
+macro_rules! blake2 {
+  (
+    $name:ident,
+    $word:ty,
+    rounds: $rounds:expr,
+    rotations: [$r1:expr, $r2:expr, $r3:expr, $r4:expr],
+    iv: $iv:expr,
+    output_length: $output_length:expr $(,)?
+  ) => {
+    #[derive(Clone)]
+    pub struct $name {
+      h: [$word; 8],
+      /// The number of bytes compressed so far.
+      counter: u128,
+      block: [u8; Self::BLOCK_SIZE],
+      /// The number of bytes in `block`. A full block is only compressed once
+      /// more input arrives, since the last block is compressed differently.
+      block_length: usize,
+    }
+
+    impl Default for $name {
+      fn default() -> Self {
+        let mut h = $iv;
+        h[0] ^= 0x01010000 ^ $output_length as $word;
+        Self {
+          h,
+          counter: 0,
+          block: [0; Self::BLOCK_SIZE],
+          block_length: 0,
+        }
+      }
+    }
+
+    impl $name {
+      const WORD_SIZE: usize = std::mem::size_of::<$word>();
+      const BLOCK_SIZE: usize = 16 * Self::WORD_SIZE;
+
+      pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+          if self.block_length == Self::BLOCK_SIZE {
+            self.counter += Self::BLOCK_SIZE as u128;
+            self.compress(false);
+            self.block_length = 0;
+          }
+          let length = data.len().min(Self::BLOCK_SIZE - self.block_length);
+          self.block[self.block_length..self.block_length + length]
+            .copy_from_slice(&data[..length]);
+          self.block_length += length;
+          data = &data[length..];
+        }
+      }
+
+      pub fn finalize(mut self) -> Box<[u8]> {
+        self.counter += self.block_length as u128;
+        self.block[self.block_length..].fill(0);
+        self.compress(true);
+        self
+          .h
+          .iter()
+          .flat_map(|word| word.to_le_bytes())
+          .take($output_length)
+          .collect()
+      }
+
+      fn compress(&mut self, last: bool) {
+        let mut m: [$word; 16] = [0; 16];
+        for (word, bytes) in
+          m.iter_mut().zip(self.block.chunks_exact(Self::WORD_SIZE))
+        {
+          *word = <$word>::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let mut v: [$word; 16] = [0; 16];
+        v[..8].copy_from_slice(&self.h);
+        v[8..].copy_from_slice(&$iv);
+        v[12] ^= self.counter as $word;
+        v[13] ^= (self.counter >> (8 * Self::WORD_SIZE)) as $word;
+        if last {
+          v[14] = !v[14];
+        }
+
+        let g = |v: &mut [$word; 16],
+                 a: usize,
+                 b: usize,
+                 c: usize,
+                 d: usize,
+                 x: $word,
+                 y: $word| {
+          v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+          v[d] = (v[d] ^ v[a]).rotate_right($r1);
+          v[c] = v[c].wrapping_add(v[d]);
+          v[b] = (v[b] ^ v[c]).rotate_right($r2);
+          v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+          v[d] = (v[d] ^ v[a]).rotate_right($r3);
+          v[c] = v[c].wrapping_add(v[d]);
+          v[b] = (v[b] ^ v[c]).rotate_right($r4);
+        };
+        for round in 0..$rounds {
+          let s = &SIGMA[round % 10];
+          g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+          g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+          g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+          g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+          g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+          g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+          g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+          g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+          self.h[i] ^= v[i] ^ v[i + 8];
+        }
+      }
+    }
+  };
+}
+
+blake2!(
+  Blake2b512,
+  u64,
+  rounds: 12,
+  rotations: [32, 24, 16, 63],
+  iv: [
+    0x6a09e667f3bcc908u64,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+  ],
+  output_length: 64,
+);
+
+blake2!(
+  Blake2s256,
+  u32,
+  rounds: 10,
+  rotations: [16, 12, 8, 7],
+  iv: [
+    0x6a09e667u32,
+    0xbb67ae85,
+    0x3c6ef372,
+    0xa54ff53a,
+    0x510e527f,
+    0x9b05688c,
+    0x1f83d9ab,
+    0x5be0cd19,
+  ],
+  output_length: 32,
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn blake2b512() {
+    let digest = |data: &[u8]| {
+      let mut hash = Blake2b512::default();
+      hash.update(data);
+      faster_hex::hex_string(&hash.finalize())
+    };
+    assert_eq!(
+      digest(b""),
+      "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+       d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+    );
+    assert_eq!(
+      digest(b"abc"),
+      "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+       7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+  }
+
+  #[test]
+  fn blake2s256() {
+    let digest = |data: &[u8]| {
+      let mut hash = Blake2s256::default();
+      hash.update(data);
+      faster_hex::hex_string(&hash.finalize())
+    };
+    assert_eq!(
+      digest(b""),
+      "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+    );
+    assert_eq!(
+      digest(b"abc"),
+      "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+    );
+  }
+
+  #[test]
+  fn block_boundaries() {
+    // inputs ending exactly at a block boundary are the tricky ones
+    for (length, blake2b, blake2s) in [
+      (63, "808dcde1a5aaffbb", "1040f8a15e0307bc"),
+      (64, "1459d88321ab96e6", "2fac0f29ee5f196d"),
+      (65, "1e0a9960eed1f9a0", "505fb1553d1bb5d4"),
+      (128, "a60f05571d05c7cf", "dbe9d41b42d8e74b"),
+      (129, "d0c51da7c07a55df", "f829fda044352e9f"),
+    ] {
+      let data = vec![0x5a; length];
+      let mut hash = Blake2b512::default();
+      for chunk in data.chunks(13) {
+        hash.update(chunk);
+      }
+      assert!(faster_hex::hex_string(&hash.finalize()).starts_with(blake2b));
+
+      let mut hash = Blake2s256::default();
+      for chunk in data.chunks(13) {
+        hash.update(chunk);
+      }
+      assert!(faster_hex::hex_string(&hash.finalize()).starts_with(blake2s));
+    }
+  }
+}
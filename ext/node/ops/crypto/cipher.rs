@@ -1,13 +1,23 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::typenum::U16;
+use aes::cipher::BlockCipher;
 use aes::cipher::BlockDecryptMut;
+use aes::cipher::BlockEncrypt;
 use aes::cipher::BlockEncryptMut;
+use aes::cipher::BlockSizeUser;
+use aes::cipher::InnerIvInit;
 use aes::cipher::KeyIvInit;
+use aes::cipher::StreamCipher;
+use aes::Block;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::Resource;
+use deno_crypto::SecretBuffer;
 use digest::KeyInit;
+use ghash::universal_hash::UniversalHash;
+use ghash::GHash;
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -15,18 +25,40 @@ use std::rc::Rc;
 
 type Tag = Option<Vec<u8>>;
 
-type Aes128Gcm = aead_gcm_stream::AesGcm<aes::Aes128>;
-type Aes256Gcm = aead_gcm_stream::AesGcm<aes::Aes256>;
+/// The ciphers implemented by [CipherContext] and [DecipherContext], as
+/// listed by `crypto.getCiphers()`.
+pub const CIPHERS: &[&str] = &[
+  "aes-128-cbc",
+  "aes-128-ccm",
+  "aes-128-ecb",
+  "aes-128-gcm",
+  "aes-192-cbc",
+  "aes-192-ccm",
+  "aes-192-ecb",
+  "aes-192-gcm",
+  "aes-256-cbc",
+  "aes-256-ccm",
+  "aes-256-ecb",
+  "aes-256-gcm",
+  "aes128",
+  "aes192",
+  "aes256",
+];
 
 enum Cipher {
   Aes128Cbc(Box<cbc::Encryptor<aes::Aes128>>),
   Aes128Ecb(Box<ecb::Encryptor<aes::Aes128>>),
   Aes192Ecb(Box<ecb::Encryptor<aes::Aes192>>),
   Aes256Ecb(Box<ecb::Encryptor<aes::Aes256>>),
-  Aes128Gcm(Box<Aes128Gcm>),
-  Aes256Gcm(Box<Aes256Gcm>),
+  Aes128Gcm(Box<Gcm<aes::Aes128>>),
+  Aes192Gcm(Box<Gcm<aes::Aes192>>),
+  Aes256Gcm(Box<Gcm<aes::Aes256>>),
+  Aes128Ccm(Box<Ccm<aes::Aes128>>),
+  Aes192Ccm(Box<Ccm<aes::Aes192>>),
+  Aes256Ccm(Box<Ccm<aes::Aes256>>),
+  Aes192Cbc(Box<cbc::Encryptor<aes::Aes192>>),
   Aes256Cbc(Box<cbc::Encryptor<aes::Aes256>>),
-  // TODO(kt3k): add more algorithms Aes192Cbc, etc.
+  // TODO(kt3k): add more algorithms Aes128Ctr, etc.
 }
 
 enum Decipher {
@@ -34,72 +66,183 @@ enum Decipher {
   Aes128Ecb(Box<ecb::Decryptor<aes::Aes128>>),
   Aes192Ecb(Box<ecb::Decryptor<aes::Aes192>>),
   Aes256Ecb(Box<ecb::Decryptor<aes::Aes256>>),
-  Aes128Gcm(Box<Aes128Gcm>),
-  Aes256Gcm(Box<Aes256Gcm>),
+  Aes128Gcm(Box<Gcm<aes::Aes128>>),
+  Aes192Gcm(Box<Gcm<aes::Aes192>>),
+  Aes256Gcm(Box<Gcm<aes::Aes256>>),
+  Aes128Ccm(Box<Ccm<aes::Aes128>>),
+  Aes192Ccm(Box<Ccm<aes::Aes192>>),
+  Aes256Ccm(Box<Ccm<aes::Aes256>>),
+  Aes192Cbc(Box<cbc::Decryptor<aes::Aes192>>),
   Aes256Cbc(Box<cbc::Decryptor<aes::Aes256>>),
-  // TODO(kt3k): add more algorithms Aes192Cbc, Aes128GCM, etc.
+  // TODO(kt3k): add more algorithms Aes128Ctr, etc.
 }
 
 pub struct CipherContext {
   cipher: Rc<RefCell<Cipher>>,
+  /// The input that doesn't fill a block yet, in block modes.
+  pending: RefCell<SecretBuffer>,
 }
 
 pub struct DecipherContext {
   decipher: Rc<RefCell<Decipher>>,
+  /// The input that isn't decrypted yet, in block modes. It's never empty
+  /// after an update with input, since the last block may be padded.
+  pending: RefCell<Vec<u8>>,
+  auth_tag: RefCell<Vec<u8>>,
 }
 
 impl CipherContext {
-  pub fn new(algorithm: &str, key: &[u8], iv: &[u8]) -> Result<Self, AnyError> {
+  pub fn new(
+    algorithm: &str,
+    key: &[u8],
+    iv: &[u8],
+    auth_tag_length: Option<usize>,
+  ) -> Result<Self, AnyError> {
     Ok(Self {
-      cipher: Rc::new(RefCell::new(Cipher::new(algorithm, key, iv)?)),
+      cipher: Rc::new(RefCell::new(Cipher::new(
+        algorithm,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      pending: RefCell::new(SecretBuffer::default()),
     })
   }
 
-  pub fn set_aad(&self, aad: &[u8]) {
-    self.cipher.borrow_mut().set_aad(aad);
+  /// Sets the additional authenticated data. CCM needs the length of the
+  /// plaintext along with it.
+  pub fn set_aad(
+    &self,
+    aad: &[u8],
+    plaintext_length: Option<usize>,
+  ) -> Result<(), AnyError> {
+    self.cipher.borrow_mut().set_aad(aad, plaintext_length)
   }
 
-  pub fn encrypt(&self, input: &[u8], output: &mut [u8]) {
-    self.cipher.borrow_mut().encrypt(input, output);
+  /// Encrypts the input and returns the ciphertext that is ready, which in
+  /// block modes is only the whole blocks so far.
+  pub fn update(&self, input: &[u8]) -> Result<Vec<u8>, AnyError> {
+    let mut cipher = self.cipher.borrow_mut();
+    if !cipher.is_block_mode() {
+      let mut output = vec![0; input.len()];
+      cipher.encrypt(input, &mut output)?;
+      return Ok(output);
+    }
+
+    let mut pending = self.pending.borrow_mut();
+    pending.extend_from_slice(input);
+    let len = pending.len() - pending.len() % 16;
+    let mut output = vec![0; len];
+    cipher.encrypt(&pending[..len], &mut output)?;
+    pending.advance(len);
+    Ok(output)
   }
 
-  pub fn r#final(
-    self,
-    input: &[u8],
-    output: &mut [u8],
-  ) -> Result<Tag, AnyError> {
-    Rc::try_unwrap(self.cipher)
+  /// Returns the rest of the ciphertext, padded in block modes, and the
+  /// auth tag in authenticated modes.
+  pub fn finish(self) -> Result<(Vec<u8>, Tag), AnyError> {
+    let cipher = Rc::try_unwrap(self.cipher)
       .map_err(|_| type_error("Cipher context is already in use"))?
-      .into_inner()
-      .r#final(input, output)
+      .into_inner();
+    if !cipher.is_block_mode() {
+      let tag = cipher.r#final(&[], &mut [])?;
+      return Ok((Vec::new(), tag));
+    }
+
+    let mut output = vec![0; 16];
+    let tag = cipher.r#final(&self.pending.into_inner(), &mut output)?;
+    Ok((output, tag))
   }
 }
 
 impl DecipherContext {
-  pub fn new(algorithm: &str, key: &[u8], iv: &[u8]) -> Result<Self, AnyError> {
+  pub fn new(
+    algorithm: &str,
+    key: &[u8],
+    iv: &[u8],
+    auth_tag_length: Option<usize>,
+  ) -> Result<Self, AnyError> {
     Ok(Self {
-      decipher: Rc::new(RefCell::new(Decipher::new(algorithm, key, iv)?)),
+      decipher: Rc::new(RefCell::new(Decipher::new(
+        algorithm,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      pending: RefCell::new(Vec::new()),
+      auth_tag: RefCell::new(Vec::new()),
     })
   }
 
-  pub fn set_aad(&self, aad: &[u8]) {
-    self.decipher.borrow_mut().set_aad(aad);
+  /// Sets the additional authenticated data. CCM needs the length of the
+  /// plaintext along with it.
+  pub fn set_aad(
+    &self,
+    aad: &[u8],
+    plaintext_length: Option<usize>,
+  ) -> Result<(), AnyError> {
+    self.decipher.borrow_mut().set_aad(aad, plaintext_length)
   }
 
-  pub fn decrypt(&self, input: &[u8], output: &mut [u8]) {
-    self.decipher.borrow_mut().decrypt(input, output);
+  /// Sets the tag that the data is authenticated with in [`Self::finish`].
+  pub fn set_auth_tag(&self, auth_tag: &[u8]) -> Result<(), AnyError> {
+    if !self
+      .decipher
+      .borrow()
+      .is_valid_auth_tag_length(auth_tag.len())
+    {
+      return Err(invalid_auth_tag_length(auth_tag.len()));
+    }
+    self.auth_tag.replace(auth_tag.to_vec());
+    Ok(())
   }
 
-  pub fn r#final(
-    self,
-    input: &[u8],
-    output: &mut [u8],
-    auth_tag: &[u8],
-  ) -> Result<(), AnyError> {
-    Rc::try_unwrap(self.decipher)
+  /// Whether the plaintext is only authentic once [`Self::finish`] checked
+  /// the auth tag.
+  pub fn is_authenticated(&self) -> bool {
+    !self.decipher.borrow().is_block_mode()
+  }
+
+  /// Decrypts the input and returns the plaintext that is ready. Block
+  /// modes hold back the last block, since it may be the padded one.
+  pub fn update(&self, input: &[u8]) -> Result<Vec<u8>, AnyError> {
+    let mut decipher = self.decipher.borrow_mut();
+    if !decipher.is_block_mode() {
+      let mut output = vec![0; input.len()];
+      decipher.decrypt(input, &mut output)?;
+      return Ok(output);
+    }
+
+    let mut pending = self.pending.borrow_mut();
+    pending.extend_from_slice(input);
+    let len = pending.len().saturating_sub(1);
+    let len = len - len % 16;
+    let mut output = vec![0; len];
+    decipher.decrypt(&pending[..len], &mut output)?;
+    pending.drain(..len);
+    Ok(output)
+  }
+
+  /// Returns the rest of the plaintext, unpadded in block modes. Fails in
+  /// authenticated modes if the data doesn't match the auth tag.
+  pub fn finish(self) -> Result<Vec<u8>, AnyError> {
+    let decipher = Rc::try_unwrap(self.decipher)
       .map_err(|_| type_error("Decipher context is already in use"))?
-      .into_inner()
-      .r#final(input, output, auth_tag)
+      .into_inner();
+    let auth_tag = self.auth_tag.into_inner();
+    if !decipher.is_block_mode() {
+      decipher.r#final(&[], &mut [], &auth_tag)?;
+      return Ok(Vec::new());
+    }
+
+    let pending = self.pending.into_inner();
+    if pending.len() != 16 {
+      return Err(type_error("Wrong final block length"));
+    }
+    let mut output = vec![0; 16];
+    let len = decipher.r#final(&pending, &mut output, &auth_tag)?;
+    output.truncate(len);
+    Ok(output)
   }
 }
 
@@ -115,56 +258,481 @@ impl Resource for DecipherContext {
   }
 }
 
-impl Cipher {
+/// Creates a CBC mode cipher, with the errors of Node.js for the wrong key and
+/// IV lengths.
+fn new_cbc<C: KeyIvInit>(key: &[u8], iv: &[u8]) -> Result<Box<C>, AnyError> {
+  if iv.len() != C::iv_size() {
+    return Err(type_error("Invalid initialization vector"));
+  }
+  C::new_from_slices(key, iv)
+    .map(Box::new)
+    .map_err(|_| type_error("Invalid key length"))
+}
+
+/// Creates an ECB mode cipher, with the error of Node.js for the wrong key
+/// length.
+fn new_ecb<C: KeyInit>(key: &[u8]) -> Result<Box<C>, AnyError> {
+  C::new_from_slice(key)
+    .map(Box::new)
+    .map_err(|_| type_error("Invalid key length"))
+}
+
+fn invalid_auth_tag_length(length: usize) -> AnyError {
+  type_error(format!("Invalid authentication tag length: {length}"))
+}
+
+fn authenticate(tag: &[u8], auth_tag: &[u8]) -> Result<(), AnyError> {
+  let matches = tag.get(..auth_tag.len()).is_some_and(|tag| {
+    ring::constant_time::verify_slices_are_equal(tag, auth_tag).is_ok()
+  });
+  if !auth_tag.is_empty() && matches {
+    Ok(())
+  } else {
+    Err(type_error("Failed to authenticate data"))
+  }
+}
+
+/// The most data that GCM encrypts with one IV, 2^39 - 256 bits (NIST SP
+/// 800-38D, section 5.2.1.1). The 32-bit block counter would wrap around
+/// past it.
+const GCM_MAX_DATA_LENGTH: u64 = (1 << 36) - 32;
+
+/// AES in GCM mode. Unlike the `aes-gcm` crate, it takes the data in chunks
+/// of any length.
+struct Gcm<Aes>
+where
+  Aes: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt,
+{
+  ctr: ctr::Ctr32BE<Aes>,
+  ghash: GHash,
+  /// The encrypted first counter block, which the tag is masked with.
+  tag_mask: Block,
+  /// The end of the input to GHASH that doesn't fill a block yet.
+  pending: Vec<u8>,
+  aad_length: u64,
+  data_length: u64,
+  /// Whether data was passed, after which no AAD can be set.
+  started: bool,
+  /// The length that tags are truncated to, if it was given.
+  tag_length: Option<usize>,
+}
+
+impl<Aes> Gcm<Aes>
+where
+  Aes: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
   fn new(
-    algorithm_name: &str,
     key: &[u8],
     iv: &[u8],
+    tag_length: Option<usize>,
   ) -> Result<Self, AnyError> {
-    use Cipher::*;
-    Ok(match algorithm_name {
-      "aes-128-cbc" => {
-        Aes128Cbc(Box::new(cbc::Encryptor::new(key.into(), iv.into())))
+    if iv.is_empty() {
+      return Err(type_error("Invalid initialization vector"));
+    }
+    if let Some(length) = tag_length {
+      if !Self::is_valid_tag_length(length) {
+        return Err(invalid_auth_tag_length(length));
       }
-      "aes-128-ecb" => Aes128Ecb(Box::new(ecb::Encryptor::new(key.into()))),
-      "aes-192-ecb" => Aes192Ecb(Box::new(ecb::Encryptor::new(key.into()))),
-      "aes-256-ecb" => Aes256Ecb(Box::new(ecb::Encryptor::new(key.into()))),
-      "aes-128-gcm" => {
-        let mut cipher =
-          aead_gcm_stream::AesGcm::<aes::Aes128>::new(key.into());
-        cipher.init(iv.try_into()?);
+    }
+    let cipher =
+      Aes::new_from_slice(key).map_err(|_| type_error("Invalid key length"))?;
+
+    let mut h = Block::default();
+    cipher.encrypt_block(&mut h);
+    let mut j0 = Block::default();
+    if iv.len() == 12 {
+      j0[..12].copy_from_slice(iv);
+      j0[15] = 1;
+    } else {
+      let mut ghash = GHash::new(&h);
+      ghash.update_padded(iv);
+      let mut lengths = Block::default();
+      lengths[8..].copy_from_slice(&(iv.len() as u64 * 8).to_be_bytes());
+      ghash.update(&[lengths]);
+      j0 = ghash.finalize();
+    }
+    let mut tag_mask = j0;
+    cipher.encrypt_block(&mut tag_mask);
+
+    // the data is encrypted starting from the counter block after J0
+    let mut counter = j0;
+    let next = u32::from_be_bytes(j0[12..].try_into().unwrap()).wrapping_add(1);
+    counter[12..].copy_from_slice(&next.to_be_bytes());
 
-        Aes128Gcm(Box::new(cipher))
+    Ok(Self {
+      ctr: ctr::Ctr32BE::from_core(ctr::CtrCore::inner_iv_init(
+        cipher, &counter,
+      )),
+      ghash: GHash::new(&h),
+      tag_mask,
+      pending: Vec::new(),
+      aad_length: 0,
+      data_length: 0,
+      started: false,
+      tag_length,
+    })
+  }
+
+  fn is_valid_tag_length(length: usize) -> bool {
+    matches!(length, 4 | 8 | 12..=16)
+  }
+
+  fn update_ghash(&mut self, mut input: &[u8]) {
+    if !self.pending.is_empty() {
+      let length = (16 - self.pending.len()).min(input.len());
+      self.pending.extend_from_slice(&input[..length]);
+      input = &input[length..];
+      if self.pending.len() < 16 {
+        return;
       }
-      "aes-256-gcm" => {
-        let mut cipher =
-          aead_gcm_stream::AesGcm::<aes::Aes256>::new(key.into());
-        cipher.init(iv.try_into()?);
+      self.ghash.update(&[Block::clone_from_slice(&self.pending)]);
+      self.pending.clear();
+    }
+    let mut blocks = input.chunks_exact(16);
+    for block in &mut blocks {
+      self.ghash.update(&[Block::clone_from_slice(block)]);
+    }
+    self.pending.extend_from_slice(blocks.remainder());
+  }
 
-        Aes256Gcm(Box::new(cipher))
+  /// Pads the AAD or the data to a whole block.
+  fn pad_ghash(&mut self) {
+    self.ghash.update_padded(&self.pending);
+    self.pending.clear();
+  }
+
+  fn start(&mut self) {
+    if !self.started {
+      self.pad_ghash();
+      self.started = true;
+    }
+  }
+
+  fn set_aad(&mut self, aad: &[u8]) -> Result<(), AnyError> {
+    if self.started {
+      return Err(type_error("AAD must be set before the data"));
+    }
+    self.update_ghash(aad);
+    self.aad_length += aad.len() as u64;
+    Ok(())
+  }
+
+  fn add_data_length(&mut self, length: usize) -> Result<(), AnyError> {
+    let data_length = self.data_length + length as u64;
+    if data_length > GCM_MAX_DATA_LENGTH {
+      return Err(type_error(
+        "GCM data exceeds the maximum length of 2^36 - 32 bytes",
+      ));
+    }
+    self.data_length = data_length;
+    Ok(())
+  }
+
+  fn encrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
+    self.add_data_length(input.len())?;
+    self.start();
+    let output = &mut output[..input.len()];
+    output.copy_from_slice(input);
+    self.ctr.apply_keystream(output);
+    self.update_ghash(output);
+    Ok(())
+  }
+
+  fn decrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
+    self.add_data_length(input.len())?;
+    self.start();
+    self.update_ghash(input);
+    let output = &mut output[..input.len()];
+    output.copy_from_slice(input);
+    self.ctr.apply_keystream(output);
+    Ok(())
+  }
+
+  /// Returns the untruncated tag.
+  fn finish(mut self) -> Block {
+    self.start();
+    self.pad_ghash();
+    let mut lengths = Block::default();
+    lengths[..8].copy_from_slice(&(self.aad_length * 8).to_be_bytes());
+    lengths[8..].copy_from_slice(&(self.data_length * 8).to_be_bytes());
+    self.ghash.update(&[lengths]);
+    let mut tag = self.ghash.finalize();
+    tag
+      .iter_mut()
+      .zip(self.tag_mask)
+      .for_each(|(tag, mask)| *tag ^= mask);
+    tag
+  }
+}
+
+/// AES in CCM mode. The length of the data is part of the first block of the
+/// CBC-MAC, so the data has to be passed at once.
+struct Ccm<Aes> {
+  cipher: Aes,
+  nonce: Vec<u8>,
+  tag_length: usize,
+  aad: Vec<u8>,
+  /// The length of the data, if it was given with the AAD.
+  data_length: Option<usize>,
+  /// The CBC-MAC of the data, once it was passed.
+  mac: Option<Block>,
+}
+
+impl<Aes> Ccm<Aes>
+where
+  Aes: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+  fn new(
+    algorithm_name: &str,
+    key: &[u8],
+    nonce: &[u8],
+    tag_length: Option<usize>,
+  ) -> Result<Self, AnyError> {
+    let tag_length = tag_length.ok_or_else(|| {
+      type_error(format!("authTagLength required for {algorithm_name}"))
+    })?;
+    if !Self::is_valid_tag_length(tag_length) {
+      return Err(invalid_auth_tag_length(tag_length));
+    }
+    if !(7..=13).contains(&nonce.len()) {
+      return Err(type_error("Invalid initialization vector"));
+    }
+    Ok(Self {
+      cipher: Aes::new_from_slice(key)
+        .map_err(|_| type_error("Invalid key length"))?,
+      nonce: nonce.to_vec(),
+      tag_length,
+      aad: Vec::new(),
+      data_length: None,
+      mac: None,
+    })
+  }
+
+  fn is_valid_tag_length(length: usize) -> bool {
+    matches!(length, 4 | 6 | 8 | 10 | 12 | 14 | 16)
+  }
+
+  /// Returns the block with the nonce, followed by `counter` in the bytes
+  /// that are left.
+  fn counter_block(&self, counter: u64) -> Block {
+    let counter_size = 15 - self.nonce.len();
+    let mut block = Block::default();
+    block[0] = counter_size as u8 - 1;
+    block[1..16 - counter_size].copy_from_slice(&self.nonce);
+    let counter = counter.to_be_bytes();
+    let length = counter_size.min(8);
+    block[16 - length..].copy_from_slice(&counter[8 - length..]);
+    block
+  }
+
+  fn cbc_mac(&self, data: &[u8]) -> Result<Block, AnyError> {
+    let counter_size = 15 - self.nonce.len();
+    if counter_size < 8 && (data.len() as u64) >> (counter_size * 8) != 0 {
+      return Err(type_error("Data is too long for the nonce"));
+    }
+    let mut mac = self.counter_block(data.len() as u64);
+    mac[0] = u8::from(!self.aad.is_empty()) << 6
+      | (((self.tag_length - 2) / 2) as u8) << 3
+      | (counter_size - 1) as u8;
+    self.cipher.encrypt_block(&mut mac);
+
+    let mut header = Vec::new();
+    if !self.aad.is_empty() {
+      let length = self.aad.len() as u64;
+      if length < 0xff00 {
+        header.extend_from_slice(&(length as u16).to_be_bytes());
+      } else if length <= u32::MAX as u64 {
+        header.extend_from_slice(&[0xff, 0xfe]);
+        header.extend_from_slice(&(length as u32).to_be_bytes());
+      } else {
+        header.extend_from_slice(&[0xff, 0xff]);
+        header.extend_from_slice(&length.to_be_bytes());
       }
-      "aes256" | "aes-256-cbc" => {
-        Aes256Cbc(Box::new(cbc::Encryptor::new(key.into(), iv.into())))
+      header.extend_from_slice(&self.aad);
+    }
+    // the AAD and the data are each padded with zeros to whole blocks
+    for input in [&header[..], data] {
+      for block in input.chunks(16) {
+        mac
+          .iter_mut()
+          .zip(block)
+          .for_each(|(mac, byte)| *mac ^= byte);
+        self.cipher.encrypt_block(&mut mac);
+      }
+    }
+    Ok(mac)
+  }
+
+  fn apply_keystream(&self, data: &mut [u8]) {
+    for (i, block) in data.chunks_mut(16).enumerate() {
+      let mut keystream = self.counter_block(i as u64 + 1);
+      self.cipher.encrypt_block(&mut keystream);
+      block
+        .iter_mut()
+        .zip(keystream)
+        .for_each(|(byte, key)| *byte ^= key);
+    }
+  }
+
+  fn set_aad(
+    &mut self,
+    aad: &[u8],
+    data_length: Option<usize>,
+  ) -> Result<(), AnyError> {
+    if self.mac.is_some() {
+      return Err(type_error("AAD must be set before the data"));
+    }
+    if !aad.is_empty() && data_length.is_none() {
+      return Err(type_error(
+        "options.plaintextLength required for CCM mode with AAD",
+      ));
+    }
+    self.aad = aad.to_vec();
+    self.data_length = data_length;
+    Ok(())
+  }
+
+  fn check_data(&self, data: &[u8]) -> Result<(), AnyError> {
+    if self.mac.is_some() {
+      return Err(type_error("CCM mode only supports a single update"));
+    }
+    if self.data_length.is_some_and(|length| length != data.len()) {
+      return Err(type_error(
+        "Data length does not match options.plaintextLength",
+      ));
+    }
+    Ok(())
+  }
+
+  fn encrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
+    self.check_data(input)?;
+    self.mac = Some(self.cbc_mac(input)?);
+    let output = &mut output[..input.len()];
+    output.copy_from_slice(input);
+    self.apply_keystream(output);
+    Ok(())
+  }
+
+  fn decrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
+    self.check_data(input)?;
+    let output = &mut output[..input.len()];
+    output.copy_from_slice(input);
+    self.apply_keystream(output);
+    self.mac = Some(self.cbc_mac(output)?);
+    Ok(())
+  }
+
+  /// Returns the tag, of the length given when creating the cipher.
+  fn finish(self) -> Result<Vec<u8>, AnyError> {
+    let mut tag = match self.mac {
+      Some(mac) => mac,
+      None => {
+        self.check_data(&[])?;
+        self.cbc_mac(&[])?
       }
+    };
+    let mut mask = self.counter_block(0);
+    self.cipher.encrypt_block(&mut mask);
+    tag
+      .iter_mut()
+      .zip(mask)
+      .for_each(|(tag, mask)| *tag ^= mask);
+    Ok(tag[..self.tag_length].to_vec())
+  }
+}
+
+impl Cipher {
+  fn new(
+    algorithm_name: &str,
+    key: &[u8],
+    iv: &[u8],
+    auth_tag_length: Option<usize>,
+  ) -> Result<Self, AnyError> {
+    use Cipher::*;
+    Ok(match algorithm_name {
+      "aes128" | "aes-128-cbc" => Aes128Cbc(new_cbc(key, iv)?),
+      "aes-128-ecb" => Aes128Ecb(new_ecb(key)?),
+      "aes-192-ecb" => Aes192Ecb(new_ecb(key)?),
+      "aes-256-ecb" => Aes256Ecb(new_ecb(key)?),
+      "aes-128-gcm" => Aes128Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-192-gcm" => Aes192Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-256-gcm" => Aes256Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-128-ccm" => Aes128Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes-192-ccm" => Aes192Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes-256-ccm" => Aes256Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes192" | "aes-192-cbc" => Aes192Cbc(new_cbc(key, iv)?),
+      "aes256" | "aes-256-cbc" => Aes256Cbc(new_cbc(key, iv)?),
       _ => return Err(type_error(format!("Unknown cipher {algorithm_name}"))),
     })
   }
 
-  fn set_aad(&mut self, aad: &[u8]) {
+  fn set_aad(
+    &mut self,
+    aad: &[u8],
+    plaintext_length: Option<usize>,
+  ) -> Result<(), AnyError> {
     use Cipher::*;
     match self {
-      Aes128Gcm(cipher) => {
-        cipher.set_aad(aad);
-      }
-      Aes256Gcm(cipher) => {
-        cipher.set_aad(aad);
-      }
-      _ => {}
+      Aes128Gcm(cipher) => cipher.set_aad(aad),
+      Aes192Gcm(cipher) => cipher.set_aad(aad),
+      Aes256Gcm(cipher) => cipher.set_aad(aad),
+      Aes128Ccm(cipher) => cipher.set_aad(aad, plaintext_length),
+      Aes192Ccm(cipher) => cipher.set_aad(aad, plaintext_length),
+      Aes256Ccm(cipher) => cipher.set_aad(aad, plaintext_length),
+      _ => Ok(()),
     }
   }
 
+  fn is_block_mode(&self) -> bool {
+    use Cipher::*;
+    !matches!(
+      self,
+      Aes128Gcm(_)
+        | Aes192Gcm(_)
+        | Aes256Gcm(_)
+        | Aes128Ccm(_)
+        | Aes192Ccm(_)
+        | Aes256Ccm(_)
+    )
+  }
+
   /// encrypt encrypts the data in the middle of the input.
-  fn encrypt(&mut self, input: &[u8], output: &mut [u8]) {
+  fn encrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
     use Cipher::*;
     match self {
       Aes128Cbc(encryptor) => {
@@ -191,13 +759,17 @@ impl Cipher {
           encryptor.encrypt_block_b2b_mut(input.into(), output.into());
         }
       }
-      Aes128Gcm(cipher) => {
-        output[..input.len()].copy_from_slice(input);
-        cipher.encrypt(output);
-      }
-      Aes256Gcm(cipher) => {
-        output[..input.len()].copy_from_slice(input);
-        cipher.encrypt(output);
+      Aes128Gcm(cipher) => cipher.encrypt(input, output)?,
+      Aes192Gcm(cipher) => cipher.encrypt(input, output)?,
+      Aes256Gcm(cipher) => cipher.encrypt(input, output)?,
+      Aes128Ccm(cipher) => cipher.encrypt(input, output)?,
+      Aes192Ccm(cipher) => cipher.encrypt(input, output)?,
+      Aes256Ccm(cipher) => cipher.encrypt(input, output)?,
+      Aes192Cbc(encryptor) => {
+        assert!(input.len() % 16 == 0);
+        for (input, output) in input.chunks(16).zip(output.chunks_mut(16)) {
+          encryptor.encrypt_block_b2b_mut(input.into(), output.into());
+        }
       }
       Aes256Cbc(encryptor) => {
         assert!(input.len() % 16 == 0);
@@ -206,6 +778,7 @@ impl Cipher {
         }
       }
     }
+    Ok(())
   }
 
   /// r#final encrypts the last block of the input data.
@@ -237,8 +810,27 @@ impl Cipher {
           .map_err(|_| type_error("Cannot pad the input data"))?;
         Ok(None)
       }
-      Aes128Gcm(cipher) => Ok(Some(cipher.finish().to_vec())),
-      Aes256Gcm(cipher) => Ok(Some(cipher.finish().to_vec())),
+      Aes128Gcm(cipher) => {
+        let length = cipher.tag_length.unwrap_or(16);
+        Ok(Some(cipher.finish()[..length].to_vec()))
+      }
+      Aes192Gcm(cipher) => {
+        let length = cipher.tag_length.unwrap_or(16);
+        Ok(Some(cipher.finish()[..length].to_vec()))
+      }
+      Aes256Gcm(cipher) => {
+        let length = cipher.tag_length.unwrap_or(16);
+        Ok(Some(cipher.finish()[..length].to_vec()))
+      }
+      Aes128Ccm(cipher) => Ok(Some(cipher.finish()?)),
+      Aes192Ccm(cipher) => Ok(Some(cipher.finish()?)),
+      Aes256Ccm(cipher) => Ok(Some(cipher.finish()?)),
+      Aes192Cbc(encryptor) => {
+        let _ = (*encryptor)
+          .encrypt_padded_b2b_mut::<Pkcs7>(input, output)
+          .map_err(|_| type_error("Cannot pad the input data"))?;
+        Ok(None)
+      }
       Aes256Cbc(encryptor) => {
         let _ = (*encryptor)
           .encrypt_padded_b2b_mut::<Pkcs7>(input, output)
@@ -254,51 +846,101 @@ impl Decipher {
     algorithm_name: &str,
     key: &[u8],
     iv: &[u8],
+    auth_tag_length: Option<usize>,
   ) -> Result<Self, AnyError> {
     use Decipher::*;
     Ok(match algorithm_name {
-      "aes-128-cbc" => {
-        Aes128Cbc(Box::new(cbc::Decryptor::new(key.into(), iv.into())))
-      }
-      "aes-128-ecb" => Aes128Ecb(Box::new(ecb::Decryptor::new(key.into()))),
-      "aes-192-ecb" => Aes192Ecb(Box::new(ecb::Decryptor::new(key.into()))),
-      "aes-256-ecb" => Aes256Ecb(Box::new(ecb::Decryptor::new(key.into()))),
-      "aes-128-gcm" => {
-        let mut decipher =
-          aead_gcm_stream::AesGcm::<aes::Aes128>::new(key.into());
-        decipher.init(iv.try_into()?);
-
-        Aes128Gcm(Box::new(decipher))
-      }
-      "aes-256-gcm" => {
-        let mut decipher =
-          aead_gcm_stream::AesGcm::<aes::Aes256>::new(key.into());
-        decipher.init(iv.try_into()?);
-
-        Aes256Gcm(Box::new(decipher))
-      }
-      "aes256" | "aes-256-cbc" => {
-        Aes256Cbc(Box::new(cbc::Decryptor::new(key.into(), iv.into())))
-      }
+      "aes128" | "aes-128-cbc" => Aes128Cbc(new_cbc(key, iv)?),
+      "aes-128-ecb" => Aes128Ecb(new_ecb(key)?),
+      "aes-192-ecb" => Aes192Ecb(new_ecb(key)?),
+      "aes-256-ecb" => Aes256Ecb(new_ecb(key)?),
+      "aes-128-gcm" => Aes128Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-192-gcm" => Aes192Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-256-gcm" => Aes256Gcm(Box::new(Gcm::new(key, iv, auth_tag_length)?)),
+      "aes-128-ccm" => Aes128Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes-192-ccm" => Aes192Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes-256-ccm" => Aes256Ccm(Box::new(Ccm::new(
+        algorithm_name,
+        key,
+        iv,
+        auth_tag_length,
+      )?)),
+      "aes192" | "aes-192-cbc" => Aes192Cbc(new_cbc(key, iv)?),
+      "aes256" | "aes-256-cbc" => Aes256Cbc(new_cbc(key, iv)?),
       _ => return Err(type_error(format!("Unknown cipher {algorithm_name}"))),
     })
   }
 
-  fn set_aad(&mut self, aad: &[u8]) {
+  fn set_aad(
+    &mut self,
+    aad: &[u8],
+    plaintext_length: Option<usize>,
+  ) -> Result<(), AnyError> {
     use Decipher::*;
     match self {
-      Aes128Gcm(decipher) => {
-        decipher.set_aad(aad);
-      }
-      Aes256Gcm(decipher) => {
-        decipher.set_aad(aad);
-      }
-      _ => {}
+      Aes128Gcm(decipher) => decipher.set_aad(aad),
+      Aes192Gcm(decipher) => decipher.set_aad(aad),
+      Aes256Gcm(decipher) => decipher.set_aad(aad),
+      Aes128Ccm(decipher) => decipher.set_aad(aad, plaintext_length),
+      Aes192Ccm(decipher) => decipher.set_aad(aad, plaintext_length),
+      Aes256Ccm(decipher) => decipher.set_aad(aad, plaintext_length),
+      _ => Ok(()),
     }
   }
 
+  /// Whether the decipher takes auth tags of the length, which is the
+  /// length given when creating it if there was one.
+  fn is_valid_auth_tag_length(&self, length: usize) -> bool {
+    use Decipher::*;
+    match self {
+      Aes128Gcm(decipher) => decipher.tag_length.map_or(
+        Gcm::<aes::Aes128>::is_valid_tag_length(length),
+        |tag_length| tag_length == length,
+      ),
+      Aes192Gcm(decipher) => decipher.tag_length.map_or(
+        Gcm::<aes::Aes192>::is_valid_tag_length(length),
+        |tag_length| tag_length == length,
+      ),
+      Aes256Gcm(decipher) => decipher.tag_length.map_or(
+        Gcm::<aes::Aes256>::is_valid_tag_length(length),
+        |tag_length| tag_length == length,
+      ),
+      Aes128Ccm(decipher) => decipher.tag_length == length,
+      Aes192Ccm(decipher) => decipher.tag_length == length,
+      Aes256Ccm(decipher) => decipher.tag_length == length,
+      _ => false,
+    }
+  }
+
+  fn is_block_mode(&self) -> bool {
+    use Decipher::*;
+    !matches!(
+      self,
+      Aes128Gcm(_)
+        | Aes192Gcm(_)
+        | Aes256Gcm(_)
+        | Aes128Ccm(_)
+        | Aes192Ccm(_)
+        | Aes256Ccm(_)
+    )
+  }
+
   /// decrypt decrypts the data in the middle of the input.
-  fn decrypt(&mut self, input: &[u8], output: &mut [u8]) {
+  fn decrypt(
+    &mut self,
+    input: &[u8],
+    output: &mut [u8],
+  ) -> Result<(), AnyError> {
     use Decipher::*;
     match self {
       Aes128Cbc(decryptor) => {
@@ -325,13 +967,17 @@ impl Decipher {
           decryptor.decrypt_block_b2b_mut(input.into(), output.into());
         }
       }
-      Aes128Gcm(decipher) => {
-        output[..input.len()].copy_from_slice(input);
-        decipher.decrypt(output);
-      }
-      Aes256Gcm(decipher) => {
-        output[..input.len()].copy_from_slice(input);
-        decipher.decrypt(output);
+      Aes128Gcm(decipher) => decipher.decrypt(input, output)?,
+      Aes192Gcm(decipher) => decipher.decrypt(input, output)?,
+      Aes256Gcm(decipher) => decipher.decrypt(input, output)?,
+      Aes128Ccm(decipher) => decipher.decrypt(input, output)?,
+      Aes192Ccm(decipher) => decipher.decrypt(input, output)?,
+      Aes256Ccm(decipher) => decipher.decrypt(input, output)?,
+      Aes192Cbc(decryptor) => {
+        assert!(input.len() % 16 == 0);
+        for (input, output) in input.chunks(16).zip(output.chunks_mut(16)) {
+          decryptor.decrypt_block_b2b_mut(input.into(), output.into());
+        }
       }
       Aes256Cbc(decryptor) => {
         assert!(input.len() % 16 == 0);
@@ -340,68 +986,264 @@ impl Decipher {
         }
       }
     }
+    Ok(())
   }
 
-  /// r#final decrypts the last block of the input data.
+  /// r#final decrypts the last block of the input data, and returns the
+  /// length of the plaintext written to the output.
   fn r#final(
     self,
     input: &[u8],
     output: &mut [u8],
     auth_tag: &[u8],
-  ) -> Result<(), AnyError> {
+  ) -> Result<usize, AnyError> {
     use Decipher::*;
     match self {
       Aes128Cbc(decryptor) => {
         assert!(input.len() == 16);
-        let _ = (*decryptor)
+        let plaintext = (*decryptor)
           .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
           .map_err(|_| type_error("Cannot unpad the input data"))?;
-        Ok(())
+        Ok(plaintext.len())
       }
       Aes128Ecb(decryptor) => {
         assert!(input.len() == 16);
-        let _ = (*decryptor)
+        let plaintext = (*decryptor)
           .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
           .map_err(|_| type_error("Cannot unpad the input data"))?;
-        Ok(())
+        Ok(plaintext.len())
       }
       Aes192Ecb(decryptor) => {
         assert!(input.len() == 16);
-        let _ = (*decryptor)
+        let plaintext = (*decryptor)
           .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
           .map_err(|_| type_error("Cannot unpad the input data"))?;
-        Ok(())
+        Ok(plaintext.len())
       }
       Aes256Ecb(decryptor) => {
         assert!(input.len() == 16);
-        let _ = (*decryptor)
+        let plaintext = (*decryptor)
           .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
           .map_err(|_| type_error("Cannot unpad the input data"))?;
-        Ok(())
+        Ok(plaintext.len())
       }
       Aes128Gcm(decipher) => {
-        let tag = decipher.finish();
-        if tag.as_slice() == auth_tag {
-          Ok(())
-        } else {
-          Err(type_error("Failed to authenticate data"))
-        }
+        authenticate(&decipher.finish(), auth_tag).map(|()| 0)
+      }
+      Aes192Gcm(decipher) => {
+        authenticate(&decipher.finish(), auth_tag).map(|()| 0)
       }
       Aes256Gcm(decipher) => {
-        let tag = decipher.finish();
-        if tag.as_slice() == auth_tag {
-          Ok(())
-        } else {
-          Err(type_error("Failed to authenticate data"))
-        }
+        authenticate(&decipher.finish(), auth_tag).map(|()| 0)
+      }
+      Aes128Ccm(decipher) => {
+        authenticate(&decipher.finish()?, auth_tag).map(|()| 0)
+      }
+      Aes192Ccm(decipher) => {
+        authenticate(&decipher.finish()?, auth_tag).map(|()| 0)
+      }
+      Aes256Ccm(decipher) => {
+        authenticate(&decipher.finish()?, auth_tag).map(|()| 0)
+      }
+      Aes192Cbc(decryptor) => {
+        assert!(input.len() == 16);
+        let plaintext = (*decryptor)
+          .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
+          .map_err(|_| type_error("Cannot unpad the input data"))?;
+        Ok(plaintext.len())
       }
       Aes256Cbc(decryptor) => {
         assert!(input.len() == 16);
-        let _ = (*decryptor)
+        let plaintext = (*decryptor)
           .decrypt_padded_b2b_mut::<Pkcs7>(input, output)
           .map_err(|_| type_error("Cannot unpad the input data"))?;
-        Ok(())
+        Ok(plaintext.len())
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn hex(data: &str) -> Vec<u8> {
+    data_encoding::HEXLOWER.decode(data.as_bytes()).unwrap()
+  }
+
+  #[test]
+  fn listed_ciphers_are_implemented() {
+    for name in CIPHERS {
+      let key_bits = if name.contains("128") {
+        128
+      } else if name.contains("192") {
+        192
+      } else {
+        256
+      };
+      let key = vec![0; key_bits / 8];
+      let authenticated = name.ends_with("gcm") || name.ends_with("ccm");
+      let (iv, tag_length) = if authenticated {
+        (vec![0; 12], Some(16))
+      } else {
+        (vec![0; 16], None)
+      };
+      assert!(Cipher::new(name, &key, &iv, tag_length).is_ok(), "{name}");
+      assert!(Decipher::new(name, &key, &iv, tag_length).is_ok(), "{name}");
+    }
+    assert!(Cipher::new("aes-128-ctr", &[0; 16], &[0; 16], None).is_err());
+  }
+
+  const GCM_KEY: &str = "feffe9928665731c6d6a8f9467308308";
+  const GCM_AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+  const GCM_PLAINTEXT: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+
+  // Test cases 4 and 6 of the GCM specification
+  #[test]
+  fn gcm_chunks() {
+    for (iv, ciphertext, tag) in [
+      (
+        "cafebabefacedbaddecaf888",
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+        "5bc94fbc3221a5db94fae95ae7121a47",
+      ),
+      (
+        "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+        "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca701e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5",
+        "619cc5aefffe0bfa462af43c1699d050",
+      ),
+    ] {
+      let aad = hex(GCM_AAD);
+      let plaintext = hex(GCM_PLAINTEXT);
+      let mut cipher =
+        Cipher::new("aes-128-gcm", &hex(GCM_KEY), &hex(iv), Some(12)).unwrap();
+      cipher.set_aad(&aad[..5], None).unwrap();
+      cipher.set_aad(&aad[5..], None).unwrap();
+      let mut output = vec![0; plaintext.len()];
+      for (input, output) in plaintext.chunks(7).zip(output.chunks_mut(7)) {
+        cipher.encrypt(input, output).unwrap();
       }
+      assert!(cipher.set_aad(&aad, None).is_err());
+      let auth_tag = cipher.r#final(&[], &mut []).unwrap().unwrap();
+      assert_eq!(output, hex(ciphertext));
+      assert_eq!(auth_tag, hex(&tag[..24]));
+
+      let context =
+        DecipherContext::new("aes-128-gcm", &hex(GCM_KEY), &hex(iv), None)
+          .unwrap();
+      context.set_aad(&aad, None).unwrap();
+      context.set_auth_tag(&hex(tag)).unwrap();
+      assert_eq!(context.update(&hex(ciphertext)).unwrap(), plaintext);
+      assert_eq!(context.finish().unwrap(), b"");
+    }
+  }
+
+  #[test]
+  fn gcm_auth_tag() {
+    let iv = hex("cafebabefacedbaddecaf888");
+    let context =
+      DecipherContext::new("aes-128-gcm", &hex(GCM_KEY), &iv, Some(16))
+        .unwrap();
+    assert!(context.set_auth_tag(&[0; 12]).is_err());
+    context.set_auth_tag(&[0; 16]).unwrap();
+    context.update(&[1, 2, 3, 4]).unwrap();
+    assert!(context.finish().is_err());
+
+    assert!(
+      CipherContext::new("aes-128-gcm", &hex(GCM_KEY), &iv, Some(5)).is_err()
+    );
+  }
+
+  #[test]
+  fn gcm_max_data_length() {
+    let iv = hex("cafebabefacedbaddecaf888");
+    let mut gcm = Gcm::<aes::Aes128>::new(&hex(GCM_KEY), &iv, None).unwrap();
+    gcm.data_length = GCM_MAX_DATA_LENGTH - 16;
+    let mut output = [0; 17];
+    gcm.encrypt(&[0; 16], &mut output).unwrap();
+    assert!(gcm.encrypt(&[0; 1], &mut output).is_err());
+    assert!(gcm.decrypt(&[0; 1], &mut output).is_err());
+  }
+
+  #[test]
+  fn invalid_key_and_iv_lengths() {
+    fn assert_type_error<T>(result: Result<T, AnyError>, message: &str) {
+      let error = result.err().unwrap();
+      assert_eq!(
+        deno_core::error::get_custom_error_class(&error),
+        Some("TypeError")
+      );
+      assert_eq!(error.to_string(), message);
+    }
+
+    for name in ["aes-128-cbc", "aes-192-cbc", "aes-256-cbc"] {
+      let key_error = "Invalid key length";
+      assert_type_error(Cipher::new(name, &[0; 15], &[0; 16], None), key_error);
+      assert_type_error(
+        Decipher::new(name, &[0; 33], &[0; 16], None),
+        key_error,
+      );
+      let iv_error = "Invalid initialization vector";
+      assert_type_error(Cipher::new(name, &[0; 32], &[0; 12], None), iv_error);
+      assert_type_error(Decipher::new(name, &[0; 32], &[], None), iv_error);
+    }
+    for name in ["aes-128-ecb", "aes-192-ecb", "aes-256-ecb"] {
+      let key_error = "Invalid key length";
+      assert_type_error(Cipher::new(name, &[0; 20], &[], None), key_error);
+      assert_type_error(Decipher::new(name, &[], &[], None), key_error);
     }
   }
+
+  // Packet vector #1 of RFC 3610
+  #[test]
+  fn ccm() {
+    let key = hex("c0c1c2c3c4c5c6c7c8c9cacbcccdcecf");
+    let nonce = hex("00000003020100a0a1a2a3a4a5");
+    let aad = hex("0001020304050607");
+    let plaintext = hex("08090a0b0c0d0e0f101112131415161718191a1b1c1d1e");
+    let ciphertext = hex("588c979a61c663d2f066d0c2c0f989806d5f6b61dac384");
+    let tag = hex("17e8d12cfdf926e0");
+
+    assert!(CipherContext::new("aes-128-ccm", &key, &nonce, None).is_err());
+    let cipher =
+      CipherContext::new("aes-128-ccm", &key, &nonce, Some(8)).unwrap();
+    assert!(cipher.set_aad(&aad, None).is_err());
+    cipher.set_aad(&aad, Some(plaintext.len())).unwrap();
+    assert_eq!(cipher.update(&plaintext).unwrap(), ciphertext);
+    assert!(cipher.update(&[]).is_err());
+    assert_eq!(cipher.finish().unwrap(), (vec![], Some(tag.clone())));
+
+    let decipher =
+      DecipherContext::new("aes-128-ccm", &key, &nonce, Some(8)).unwrap();
+    decipher.set_aad(&aad, Some(plaintext.len())).unwrap();
+    decipher.set_auth_tag(&tag).unwrap();
+    assert_eq!(decipher.update(&ciphertext).unwrap(), plaintext);
+    assert_eq!(decipher.finish().unwrap(), b"");
+  }
+
+  #[test]
+  fn cbc_update_output() {
+    let key = [1; 16];
+    let iv = [2; 16];
+    let plaintext = [b'a'; 21];
+    let ciphertext =
+      hex("8191529c09e02d51535221189b1b303fd342edb9e95def0d2e16f0170d7254ea");
+
+    let cipher = CipherContext::new("aes-128-cbc", &key, &iv, None).unwrap();
+    assert_eq!(cipher.update(&plaintext[..10]).unwrap(), b"");
+    assert_eq!(cipher.update(&plaintext[10..]).unwrap(), ciphertext[..16]);
+    assert_eq!(cipher.finish().unwrap(), (ciphertext[16..].to_vec(), None));
+
+    let decipher =
+      DecipherContext::new("aes-128-cbc", &key, &iv, None).unwrap();
+    // the last block is held back until the end, for the padding
+    assert_eq!(decipher.update(&ciphertext[..16]).unwrap(), b"");
+    assert_eq!(decipher.update(&ciphertext[16..]).unwrap(), plaintext[..16]);
+    assert_eq!(decipher.finish().unwrap(), plaintext[16..]);
+
+    let decipher =
+      DecipherContext::new("aes-128-cbc", &key, &iv, None).unwrap();
+    decipher.update(&ciphertext[..20]).unwrap();
+    assert!(decipher.finish().is_err());
+  }
 }
@@ -1,9 +1,16 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use super::modular;
 use super::primes::Prime;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_crypto::SecretBuffer;
 use num_bigint_dig::BigUint;
 use num_bigint_dig::RandBigInt;
 use num_traits::FromPrimitive;
+use num_traits::One;
+use std::cell::RefCell;
+use zeroize::Zeroize;
 
 pub struct PublicKey(BigUint);
 
@@ -29,17 +36,25 @@ impl PrivateKey {
     generator: &BigUint,
     modulus: &BigUint,
   ) -> PublicKey {
-    let public_key = generator.modpow(&self.0, modulus);
+    let public_key = mod_pow(generator, &self.0, modulus);
     PublicKey(public_key)
   }
 
-  pub fn into_vec(self) -> Vec<u8> {
-    self.0.to_bytes_be()
+  pub fn into_secret_buffer(self) -> SecretBuffer {
+    self.0.to_bytes_be().into()
+  }
+}
+
+impl Drop for PrivateKey {
+  fn drop(&mut self) {
+    self.0.zeroize();
   }
 }
 
 /// Classic DH
 pub struct DiffieHellman {
+  pub prime: BigUint,
+  pub generator: BigUint,
   pub private_key: PrivateKey,
   pub public_key: PublicKey,
 }
@@ -52,11 +67,13 @@ impl DiffieHellman {
     let private_key = PrivateKey::new(G::EXPONENT_SIZE / 8);
 
     let generator = BigUint::from_usize(G::GENERATOR).unwrap();
-    let modulus = BigUint::from_slice(G::MODULUS);
+    let modulus = G::modulus();
 
     let public_key = private_key.compute_public_key(&generator, &modulus);
 
     Self {
+      prime: modulus,
+      generator,
       private_key,
       public_key,
     }
@@ -69,12 +86,162 @@ impl DiffieHellman {
     let public_key = private_key.compute_public_key(&generator, &prime);
 
     Self {
+      prime: prime.0,
+      generator,
       private_key,
       public_key,
     }
   }
 }
 
+/// Classic DH with a fixed prime and generator, for `crypto.DiffieHellman`.
+/// The keys are generated, or set, later on.
+pub struct DiffieHellmanContext {
+  prime: BigUint,
+  generator: BigUint,
+  /// Size of the private key in bits, for the well-known groups. Otherwise,
+  /// private keys can be anything below the prime.
+  exponent_size: Option<usize>,
+  private_key: RefCell<Option<BigUint>>,
+  public_key: RefCell<Option<BigUint>>,
+}
+
+impl DiffieHellmanContext {
+  pub fn new(prime: &[u8], generator: &[u8]) -> Self {
+    Self {
+      prime: BigUint::from_bytes_be(prime),
+      generator: BigUint::from_bytes_be(generator),
+      exponent_size: None,
+      private_key: RefCell::new(None),
+      public_key: RefCell::new(None),
+    }
+  }
+
+  pub fn group<G>() -> Self
+  where
+    G: DiffieHellmanGroup,
+  {
+    Self {
+      prime: G::modulus(),
+      generator: BigUint::from_usize(G::GENERATOR).unwrap(),
+      exponent_size: Some(G::EXPONENT_SIZE),
+      private_key: RefCell::new(None),
+      public_key: RefCell::new(None),
+    }
+  }
+
+  /// Computes the public key, and generates the private key first unless it
+  /// was set.
+  pub fn generate_keys(&self) -> Vec<u8> {
+    let mut private_key = self.private_key.borrow_mut();
+    let private_key = private_key.get_or_insert_with(|| {
+      let mut rng = rand::thread_rng();
+      match self.exponent_size {
+        Some(exponent_size) => rng.gen_biguint(exponent_size),
+        None => rng.gen_biguint_below(&self.prime),
+      }
+    });
+    let public_key = mod_pow(&self.generator, private_key, &self.prime);
+    let padded = self.pad(&public_key);
+    self.public_key.replace(Some(public_key));
+    padded
+  }
+
+  pub fn compute_secret(
+    &self,
+    their_public_key: &[u8],
+  ) -> Result<SecretBuffer, AnyError> {
+    let private_key = self.private_key.borrow();
+    let Some(private_key) = private_key.as_ref() else {
+      return Err(type_error(
+        "No private key - did you forget to generate one?",
+      ));
+    };
+    let their_public_key = BigUint::from_bytes_be(their_public_key);
+    if their_public_key <= BigUint::one()
+      || their_public_key >= &self.prime - BigUint::one()
+    {
+      return Err(type_error("Supplied key is invalid"));
+    }
+    let mut shared_secret =
+      mod_pow(&their_public_key, private_key, &self.prime);
+    let padded = self.pad(&shared_secret);
+    shared_secret.zeroize();
+    Ok(padded.into())
+  }
+
+  pub fn prime(&self) -> Vec<u8> {
+    self.prime.to_bytes_be()
+  }
+
+  pub fn generator(&self) -> Vec<u8> {
+    self.generator.to_bytes_be()
+  }
+
+  pub fn private_key(&self) -> Option<SecretBuffer> {
+    let private_key = self.private_key.borrow();
+    private_key.as_ref().map(|key| key.to_bytes_be().into())
+  }
+
+  pub fn public_key(&self) -> Option<Vec<u8>> {
+    self.public_key.borrow().as_ref().map(|key| self.pad(key))
+  }
+
+  pub fn set_private_key(&self, private_key: &[u8]) {
+    let replaced = self
+      .private_key
+      .replace(Some(BigUint::from_bytes_be(private_key)));
+    if let Some(mut replaced) = replaced {
+      replaced.zeroize();
+    }
+  }
+
+  pub fn set_public_key(&self, public_key: &[u8]) {
+    self
+      .public_key
+      .replace(Some(BigUint::from_bytes_be(public_key)));
+  }
+
+  /// Big-endian bytes of `value`, left-padded to the size of the prime, like
+  /// Node.js does for public keys and secrets.
+  fn pad(&self, value: &BigUint) -> Vec<u8> {
+    let bytes = SecretBuffer::from(value.to_bytes_be());
+    let size = (self.prime.bits() + 7) / 8;
+    // allocated at once, so that secrets aren't left behind by growing it
+    let mut padded = Vec::with_capacity(size.max(bytes.len()));
+    padded.resize(size.saturating_sub(bytes.len()), 0);
+    padded.extend_from_slice(&bytes);
+    padded
+  }
+}
+
+impl Drop for DiffieHellmanContext {
+  fn drop(&mut self) {
+    if let Some(private_key) = self.private_key.get_mut() {
+      private_key.zeroize();
+    }
+  }
+}
+
+/// Computes `base ^ exponent mod modulus` in constant time when the modulus
+/// allows it, which is the case of every prime but 2. The exponent is the
+/// private key, so its bytes are zeroized afterwards.
+fn mod_pow(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+  let exponent_bytes = SecretBuffer::from(exponent.to_bytes_be());
+  match modular::mod_pow(
+    &base.to_bytes_be(),
+    &exponent_bytes,
+    &modulus.to_bytes_be(),
+  ) {
+    Ok(result) => {
+      let result = SecretBuffer::from(result);
+      BigUint::from_bytes_be(&result)
+    }
+    // even or oversized moduli, neither of which is secret
+    Err(_) => base.modpow(exponent, modulus),
+  }
+}
+
 /// Well-known modp groups
 //
 /// More Modular Exponential (MODP) Diffie-Hellman groups for Internet Key Exchange (IKE)
@@ -87,6 +254,15 @@ pub trait DiffieHellmanGroup {
   const MODULUS: &'static [u32];
   /// Size of the exponent in bits
   const EXPONENT_SIZE: usize;
+
+  /// `MODULUS` lists the most significant digit first.
+  fn modulus() -> BigUint {
+    let bytes = Self::MODULUS
+      .iter()
+      .flat_map(|digit| digit.to_be_bytes())
+      .collect::<Vec<_>>();
+    BigUint::from_bytes_be(&bytes)
+  }
 }
 
 /// 1536-bit MODP Group
@@ -286,3 +462,69 @@ impl DiffieHellmanGroup for Modp8192 {
     0x60C980DD, 0x98EDD3DF, 0xFFFFFFFF, 0xFFFFFFFF,
   ];
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn modp_group_modulus() {
+    let modulus = Modp2048::modulus();
+    assert_eq!(modulus.bits(), 2048);
+    let bytes = modulus.to_bytes_be();
+    assert_eq!(&bytes[..8], &[0xFF; 8]);
+    assert_eq!(&bytes[8..12], &[0xC9, 0x0F, 0xDA, 0xA2]);
+  }
+
+  #[test]
+  fn mod_pow_known_vectors() {
+    let pow = |base: u32, exponent: u32, modulus: u32| {
+      mod_pow(
+        &BigUint::from(base),
+        &BigUint::from(exponent),
+        &BigUint::from(modulus),
+      )
+    };
+    // https://en.wikipedia.org/wiki/Diffie%E2%80%93Hellman_key_exchange
+    assert_eq!(pow(5, 6, 23), BigUint::from(8u32));
+    assert_eq!(pow(5, 15, 23), BigUint::from(19u32));
+    assert_eq!(pow(19, 6, 23), BigUint::from(2u32));
+    assert_eq!(pow(8, 15, 23), BigUint::from(2u32));
+    // even moduli fall back to num-bigint
+    assert_eq!(pow(3, 5, 16), BigUint::from(3u32));
+
+    let modulus = Modp2048::modulus();
+    let generator = BigUint::from(2u32);
+    let exponent = rand::thread_rng().gen_biguint(256);
+    assert_eq!(
+      mod_pow(&generator, &exponent, &modulus),
+      generator.modpow(&exponent, &modulus)
+    );
+  }
+
+  #[test]
+  fn compute_known_shared_secret() {
+    let alice = DiffieHellmanContext::new(&[23], &[5]);
+    let bob = DiffieHellmanContext::new(&[23], &[5]);
+    alice.set_private_key(&[6]);
+    bob.set_private_key(&[15]);
+    assert_eq!(alice.generate_keys(), vec![8]);
+    assert_eq!(bob.generate_keys(), vec![19]);
+    assert_eq!(*alice.compute_secret(&[19]).unwrap(), [2]);
+    assert_eq!(*bob.compute_secret(&[8]).unwrap(), [2]);
+  }
+
+  #[test]
+  fn compute_shared_secret() {
+    let alice = DiffieHellmanContext::group::<Modp1536>();
+    let bob = DiffieHellmanContext::group::<Modp1536>();
+    let alice_public_key = alice.generate_keys();
+    let bob_public_key = bob.generate_keys();
+    assert_eq!(alice_public_key.len(), 192);
+
+    let alice_secret = alice.compute_secret(&bob_public_key).unwrap();
+    let bob_secret = bob.compute_secret(&alice_public_key).unwrap();
+    assert_eq!(*alice_secret, *bob_secret);
+    assert!(alice.compute_secret(&[1]).is_err());
+  }
+}
@@ -4,10 +4,33 @@ use deno_core::error::AnyError;
 use deno_core::Resource;
 use digest::Digest;
 use digest::DynDigest;
+use digest::KeyInit;
+use digest::Mac;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use super::blake2::Blake2b512;
+use super::blake2::Blake2s256;
+use super::shake::Shake;
+
+/// The algorithms implemented by [Hash], as listed by `crypto.getHashes()`.
+/// [Hmac] implements all of them but BLAKE2 and SHAKE.
+pub const HASHES: &[&str] = &[
+  "blake2b512",
+  "blake2s256",
+  "md4",
+  "md5",
+  "ripemd160",
+  "sha1",
+  "sha224",
+  "sha256",
+  "sha384",
+  "sha512",
+  "shake128",
+  "shake256",
+];
+
 pub enum Hash {
   Md4(Box<md4::Md4>),
   Md5(Box<md5::Md5>),
@@ -17,6 +40,10 @@ pub enum Hash {
   Sha256(Box<sha2::Sha256>),
   Sha384(Box<sha2::Sha384>),
   Sha512(Box<sha2::Sha512>),
+  Blake2b512(Box<Blake2b512>),
+  Blake2s256(Box<Blake2s256>),
+  Shake128(Box<Shake>),
+  Shake256(Box<Shake>),
 }
 
 pub struct Context {
@@ -34,12 +61,17 @@ impl Context {
     self.hash.borrow_mut().update(data);
   }
 
-  pub fn digest(self) -> Result<Box<[u8]>, AnyError> {
+  /// `output_length` is only allowed to differ from the digest size of the
+  /// algorithm for the extendable-output functions.
+  pub fn digest(
+    self,
+    output_length: Option<usize>,
+  ) -> Result<Box<[u8]>, AnyError> {
     let hash = Rc::try_unwrap(self.hash)
       .map_err(|_| type_error("Hash context is already in use"))?;
 
     let hash = hash.into_inner();
-    Ok(hash.digest_and_drop())
+    hash.digest_and_drop(output_length)
   }
 }
 
@@ -70,6 +102,10 @@ impl Hash {
       "sha256" => Sha256(Default::default()),
       "sha384" => Sha384(Default::default()),
       "sha512" => Sha512(Default::default()),
+      "blake2b512" => Blake2b512(Default::default()),
+      "blake2s256" => Blake2s256(Default::default()),
+      "shake128" => Shake128(Box::new(Shake::shake128())),
+      "shake256" => Shake256(Box::new(Shake::shake256())),
       _ => return Err(type_error("unsupported algorithm")),
     })
   }
@@ -84,11 +120,24 @@ impl Hash {
       Sha256(context) => Digest::update(&mut **context, data),
       Sha384(context) => Digest::update(&mut **context, data),
       Sha512(context) => Digest::update(&mut **context, data),
+      Blake2b512(context) => context.update(data),
+      Blake2s256(context) => context.update(data),
+      Shake128(context) | Shake256(context) => context.update(data),
     };
   }
 
-  pub fn digest_and_drop(self) -> Box<[u8]> {
-    match self {
+  pub fn digest_and_drop(
+    self,
+    output_length: Option<usize>,
+  ) -> Result<Box<[u8]>, AnyError> {
+    if let Shake128(context) | Shake256(context) = self {
+      let length =
+        output_length.unwrap_or_else(|| context.default_output_length());
+      return Ok(context.finalize(length));
+    }
+
+    let name = self.name();
+    let digest = match self {
       Md4(context) => context.finalize(),
       Md5(context) => context.finalize(),
       Ripemd160(context) => context.finalize(),
@@ -97,20 +146,37 @@ impl Hash {
       Sha256(context) => context.finalize(),
       Sha384(context) => context.finalize(),
       Sha512(context) => context.finalize(),
+      Blake2b512(context) => context.finalize(),
+      Blake2s256(context) => context.finalize(),
+      Shake128(_) | Shake256(_) => unreachable!(),
+    };
+    match output_length {
+      Some(length) if length != digest.len() => Err(type_error(format!(
+        "Output length {length} is invalid for {name}, which does not support XOF"
+      ))),
+      _ => Ok(digest),
+    }
+  }
+
+  fn name(&self) -> &'static str {
+    match self {
+      Md4(_) => "md4",
+      Md5(_) => "md5",
+      Ripemd160(_) => "ripemd160",
+      Sha1(_) => "sha1",
+      Sha224(_) => "sha224",
+      Sha256(_) => "sha256",
+      Sha384(_) => "sha384",
+      Sha512(_) => "sha512",
+      Blake2b512(_) => "blake2b512",
+      Blake2s256(_) => "blake2s256",
+      Shake128(_) => "shake128",
+      Shake256(_) => "shake256",
     }
   }
 
   pub fn get_hashes() -> Vec<&'static str> {
-    vec![
-      "md4",
-      "md5",
-      "ripemd160",
-      "sha1",
-      "sha224",
-      "sha256",
-      "sha384",
-      "sha512",
-    ]
+    HASHES.to_vec()
   }
 }
 
@@ -125,6 +191,159 @@ impl Clone for Hash {
       Sha256(_) => Sha256(Default::default()),
       Sha384(_) => Sha384(Default::default()),
       Sha512(_) => Sha512(Default::default()),
+      Blake2b512(context) => Blake2b512(context.clone()),
+      Blake2s256(context) => Blake2s256(context.clone()),
+      Shake128(context) => Shake128(context.clone()),
+      Shake256(context) => Shake256(context.clone()),
     }
   }
 }
+
+pub enum Hmac {
+  Md4(Box<hmac::Hmac<md4::Md4>>),
+  Md5(Box<hmac::Hmac<md5::Md5>>),
+  Ripemd160(Box<hmac::Hmac<ripemd::Ripemd160>>),
+  Sha1(Box<hmac::Hmac<sha1::Sha1>>),
+  Sha224(Box<hmac::Hmac<sha2::Sha224>>),
+  Sha256(Box<hmac::Hmac<sha2::Sha256>>),
+  Sha384(Box<hmac::Hmac<sha2::Sha384>>),
+  Sha512(Box<hmac::Hmac<sha2::Sha512>>),
+}
+
+pub struct HmacContext {
+  pub hmac: RefCell<Hmac>,
+}
+
+impl HmacContext {
+  pub fn new(algorithm: &str, key: &[u8]) -> Result<Self, AnyError> {
+    Ok(Self {
+      hmac: RefCell::new(Hmac::new(algorithm, key)?),
+    })
+  }
+
+  pub fn update(&self, data: &[u8]) {
+    self.hmac.borrow_mut().update(data);
+  }
+
+  pub fn digest(self) -> Box<[u8]> {
+    self.hmac.into_inner().digest_and_drop()
+  }
+}
+
+impl Resource for HmacContext {
+  fn name(&self) -> Cow<str> {
+    "cryptoHmac".into()
+  }
+}
+
+impl Hmac {
+  pub fn new(algorithm_name: &str, key: &[u8]) -> Result<Self, AnyError> {
+    macro_rules! new_hmac {
+      ($variant:ident) => {
+        Self::$variant(Box::new(
+          KeyInit::new_from_slice(key).expect("HMAC accepts any key length"),
+        ))
+      };
+    }
+
+    Ok(match algorithm_name {
+      "md4" => new_hmac!(Md4),
+      "md5" => new_hmac!(Md5),
+      "ripemd160" => new_hmac!(Ripemd160),
+      "sha1" => new_hmac!(Sha1),
+      "sha224" => new_hmac!(Sha224),
+      "sha256" => new_hmac!(Sha256),
+      "sha384" => new_hmac!(Sha384),
+      "sha512" => new_hmac!(Sha512),
+      _ => return Err(type_error("unsupported algorithm")),
+    })
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    match self {
+      Self::Md4(context) => Mac::update(&mut **context, data),
+      Self::Md5(context) => Mac::update(&mut **context, data),
+      Self::Ripemd160(context) => Mac::update(&mut **context, data),
+      Self::Sha1(context) => Mac::update(&mut **context, data),
+      Self::Sha224(context) => Mac::update(&mut **context, data),
+      Self::Sha256(context) => Mac::update(&mut **context, data),
+      Self::Sha384(context) => Mac::update(&mut **context, data),
+      Self::Sha512(context) => Mac::update(&mut **context, data),
+    };
+  }
+
+  pub fn digest_and_drop(self) -> Box<[u8]> {
+    match self {
+      Self::Md4(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Md5(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Ripemd160(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Sha1(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Sha224(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Sha256(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Sha384(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+      Self::Sha512(context) => {
+        Mac::finalize(*context).into_bytes().to_vec().into()
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn listed_hashes_are_implemented() {
+    for name in HASHES {
+      assert!(Hash::new(name).is_ok(), "{name}");
+      let hmac = !name.starts_with("blake2") && !name.starts_with("shake");
+      assert_eq!(Hmac::new(name, b"key").is_ok(), hmac, "{name}");
+    }
+    assert!(Hash::new("sha3-256").is_err());
+  }
+
+  #[test]
+  fn output_length() {
+    let digest = |name, length| {
+      let mut hash = Hash::new(name).unwrap();
+      hash.update(b"abc");
+      hash.digest_and_drop(length).map(|digest| digest.len())
+    };
+    assert_eq!(digest("shake128", None).unwrap(), 16);
+    assert_eq!(digest("shake256", None).unwrap(), 32);
+    assert_eq!(digest("shake256", Some(0)).unwrap(), 0);
+    assert_eq!(digest("shake256", Some(100)).unwrap(), 100);
+    assert_eq!(digest("sha256", Some(32)).unwrap(), 32);
+    assert_eq!(
+      digest("sha256", Some(16)).unwrap_err().to_string(),
+      "Output length 16 is invalid for sha256, which does not support XOF"
+    );
+  }
+
+  #[test]
+  fn clone_keeps_state() {
+    let mut hash = Hash::new("blake2b512").unwrap();
+    hash.update(b"ab");
+    let mut copy = hash.clone();
+    hash.update(b"c");
+    copy.update(b"c");
+    assert_eq!(
+      hash.digest_and_drop(None).unwrap(),
+      copy.digest_and_drop(None).unwrap()
+    );
+  }
+}
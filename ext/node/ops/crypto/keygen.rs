@@ -0,0 +1,436 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Key pair generation for `crypto.generateKeyPair()`.
+//!
+//! The keys are generated in the form `keys.rs` keeps them in: PKCS#1 for
+//! RSA, PKCS#8 and SPKI for DSA, the private scalar and the uncompressed
+//! public point for EC, and the raw keys for Ed25519 and X25519.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::OpState;
+use deno_core::ToJsBuffer;
+use deno_crypto::SecretBuffer;
+use elliptic_curve::pkcs8::AssociatedOid;
+use elliptic_curve::pkcs8::EncodePrivateKey;
+use elliptic_curve::pkcs8::ObjectIdentifier;
+use elliptic_curve::sec1::FromEncodedPoint;
+use elliptic_curve::sec1::ModulusSize;
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::AffinePoint;
+use elliptic_curve::CurveArithmetic;
+use elliptic_curve::FieldBytesSize;
+use num_traits::FromPrimitive;
+use rand::thread_rng;
+use rand::Rng;
+use rsa::pkcs1::EncodeRsaPrivateKey;
+use rsa::pkcs1::EncodeRsaPublicKey;
+use rsa::RsaPrivateKey;
+use sec1::der::Encode;
+use spki::EncodePublicKey;
+use std::future::Future;
+
+use super::pool::CpuPool;
+
+// u-coordinate of the base point.
+const X25519_BASEPOINT_BYTES: [u8; 32] = [
+  9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+  0, 0, 0, 0, 0, 0,
+];
+
+fn generate_rsa(
+  modulus_length: usize,
+  public_exponent: usize,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  let mut rng = rand::thread_rng();
+  let private_key = RsaPrivateKey::new_with_exp(
+    &mut rng,
+    modulus_length,
+    &rsa::BigUint::from_usize(public_exponent).unwrap(),
+  )?;
+  let public_key = private_key.to_public_key();
+  let private_key_der =
+    SecretBuffer::from(private_key.to_pkcs1_der()?.as_bytes());
+  let public_key_der = public_key.to_pkcs1_der()?.to_vec();
+
+  Ok((private_key_der.into(), public_key_der.into()))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_generate_rsa(
+  #[number] modulus_length: usize,
+  #[number] public_exponent: usize,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  generate_rsa(modulus_length, public_exponent)
+}
+
+#[op2(async)]
+#[serde]
+pub fn op_node_generate_rsa_async(
+  state: &mut OpState,
+  #[number] modulus_length: usize,
+  #[number] public_exponent: usize,
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state)
+    .spawn(move || generate_rsa(modulus_length, public_exponent))
+}
+
+fn dsa_generate(
+  modulus_length: usize,
+  divisor_length: usize,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  let mut rng = rand::thread_rng();
+  use dsa::Components;
+  use dsa::KeySize;
+  use dsa::SigningKey;
+
+  let key_size = match (modulus_length, divisor_length) {
+    #[allow(deprecated)]
+    (1024, 160) => KeySize::DSA_1024_160,
+    (2048, 224) => KeySize::DSA_2048_224,
+    (2048, 256) => KeySize::DSA_2048_256,
+    (3072, 256) => KeySize::DSA_3072_256,
+    _ => return Err(type_error("Invalid modulus_length or divisor_length")),
+  };
+  let components = Components::generate(&mut rng, key_size);
+  let signing_key = SigningKey::generate(&mut rng, components);
+  let verifying_key = signing_key.verifying_key();
+
+  Ok((
+    SecretBuffer::from(
+      signing_key
+        .to_pkcs8_der()
+        .map_err(|_| type_error("Not valid pkcs8"))?
+        .as_bytes(),
+    )
+    .into(),
+    verifying_key
+      .to_public_key_der()
+      .map_err(|_| type_error("Not valid spki"))?
+      .to_vec()
+      .into(),
+  ))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_dsa_generate(
+  #[number] modulus_length: usize,
+  #[number] divisor_length: usize,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  dsa_generate(modulus_length, divisor_length)
+}
+
+#[op2(async)]
+#[serde]
+pub fn op_node_dsa_generate_async(
+  state: &mut OpState,
+  #[number] modulus_length: usize,
+  #[number] divisor_length: usize,
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state)
+    .spawn(move || dsa_generate(modulus_length, divisor_length))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcCurve {
+  P224,
+  P256,
+  P384,
+  P521,
+  Secp256k1,
+}
+
+/// Calls `$f::<C>($args)` with the curve type `C` of the `EcCurve` `$curve`.
+macro_rules! with_ec_curve {
+  ($curve:expr, $f:ident($($arg:expr),*)) => {
+    match $curve {
+      EcCurve::P224 => $f::<p224::NistP224>($($arg),*),
+      EcCurve::P256 => $f::<p256::NistP256>($($arg),*),
+      EcCurve::P384 => $f::<p384::NistP384>($($arg),*),
+      EcCurve::P521 => $f::<p521::NistP521>($($arg),*),
+      EcCurve::Secp256k1 => $f::<k256::Secp256k1>($($arg),*),
+    }
+  };
+}
+
+impl EcCurve {
+  const ALL: [Self; 5] = [
+    Self::P224,
+    Self::P256,
+    Self::P384,
+    Self::P521,
+    Self::Secp256k1,
+  ];
+
+  pub(super) fn from_name(named_curve: &str) -> Result<Self, AnyError> {
+    match named_curve {
+      "P-224" | "secp224r1" => Ok(Self::P224),
+      "P-256" | "prime256v1" | "secp256r1" => Ok(Self::P256),
+      "P-384" | "prime384v1" | "secp384r1" => Ok(Self::P384),
+      "P-521" | "secp521r1" => Ok(Self::P521),
+      "secp256k1" => Ok(Self::Secp256k1),
+      _ => Err(type_error("Unsupported named curve")),
+    }
+  }
+
+  pub(super) fn from_oid(oid: ObjectIdentifier) -> Result<Self, AnyError> {
+    Self::ALL
+      .into_iter()
+      .find(|curve| curve.oid() == oid)
+      .ok_or_else(|| type_error("Unsupported named curve"))
+  }
+
+  pub(super) fn from_jwk_name(crv: &str) -> Result<Self, AnyError> {
+    Self::ALL
+      .into_iter()
+      .find(|curve| curve.jwk_name().is_ok_and(|name| name == crv))
+      .ok_or_else(|| type_error(format!("Unsupported JWK EC curve: {}", crv)))
+  }
+
+  /// The name of the curve in OpenSSL, which node reports as `namedCurve`.
+  pub(super) fn name(self) -> &'static str {
+    match self {
+      Self::P224 => "secp224r1",
+      Self::P256 => "prime256v1",
+      Self::P384 => "secp384r1",
+      Self::P521 => "secp521r1",
+      Self::Secp256k1 => "secp256k1",
+    }
+  }
+
+  fn oid(self) -> ObjectIdentifier {
+    match self {
+      Self::P224 => p224::NistP224::OID,
+      Self::P256 => p256::NistP256::OID,
+      Self::P384 => p384::NistP384::OID,
+      Self::P521 => p521::NistP521::OID,
+      Self::Secp256k1 => k256::Secp256k1::OID,
+    }
+  }
+
+  /// The name of the curve in a JWK, as registered in RFC 7518 and RFC 8812.
+  pub(super) fn jwk_name(self) -> Result<&'static str, AnyError> {
+    match self {
+      Self::P224 => Err(type_error("Unsupported JWK EC curve: P-224")),
+      Self::P256 => Ok("P-256"),
+      Self::P384 => Ok("P-384"),
+      Self::P521 => Ok("P-521"),
+      Self::Secp256k1 => Ok("secp256k1"),
+    }
+  }
+
+  pub(super) fn generate(self) -> (SecretBuffer, Vec<u8>) {
+    with_ec_curve!(self, ec_generate_curve())
+  }
+
+  /// Checks the private scalar `key`, and pads it to the size of the curve.
+  pub(super) fn private_key(
+    self,
+    key: &[u8],
+  ) -> Result<SecretBuffer, AnyError> {
+    with_ec_curve!(self, ec_private_key(key))
+  }
+
+  pub(super) fn private_key_der(
+    self,
+    key: &[u8],
+    type_: &str,
+  ) -> Result<(&'static str, SecretBuffer), AnyError> {
+    with_ec_curve!(self, ec_private_key_der(key, type_))
+  }
+
+  /// Returns the uncompressed public point of the private scalar `key`.
+  pub(super) fn public_point(self, key: &[u8]) -> Result<Vec<u8>, AnyError> {
+    with_ec_curve!(self, ec_public_point(key))
+  }
+
+  /// Checks the point `key`, encoded in any form, and returns it uncompressed.
+  pub(super) fn uncompressed_point(
+    self,
+    key: &[u8],
+  ) -> Result<Vec<u8>, AnyError> {
+    with_ec_curve!(self, ec_uncompressed_point(key))
+  }
+
+  pub(super) fn public_key_der(self, key: &[u8]) -> Result<Vec<u8>, AnyError> {
+    with_ec_curve!(self, ec_public_key_der(key))
+  }
+}
+
+fn ec_generate_curve<C>() -> (SecretBuffer, Vec<u8>)
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::SecretKey::<C>::random(&mut thread_rng());
+  // TODO(@littledivy): Support public key point encoding.
+  // Default is uncompressed.
+  let public_key = key.public_key().to_encoded_point(false);
+  (
+    key.to_bytes().as_slice().into(),
+    public_key.as_bytes().to_vec(),
+  )
+}
+
+fn ec_private_key_der<C>(
+  key: &[u8],
+  type_: &str,
+) -> Result<(&'static str, SecretBuffer), AnyError>
+where
+  C: CurveArithmetic + AssociatedOid,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::SecretKey::<C>::from_slice(key)?;
+  match type_ {
+    "sec1" => {
+      // `SecretKey::to_sec1_der()` leaves out the curve, which node includes
+      let private_key = key.to_bytes();
+      let public_key = key.public_key().to_encoded_point(false);
+      let der = sec1::EcPrivateKey {
+        private_key: &private_key,
+        parameters: Some(sec1::EcParameters::NamedCurve(C::OID)),
+        public_key: Some(public_key.as_bytes()),
+      }
+      .to_der()?;
+      Ok(("EC PRIVATE KEY", der.into()))
+    }
+    "pkcs8" => Ok(("PRIVATE KEY", key.to_pkcs8_der()?.as_bytes().into())),
+    _ => Err(type_error(format!("Unsupported key type: {}", type_))),
+  }
+}
+
+fn ec_public_point<C>(key: &[u8]) -> Result<Vec<u8>, AnyError>
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::SecretKey::<C>::from_slice(key)?;
+  Ok(key.public_key().to_encoded_point(false).as_bytes().to_vec())
+}
+
+fn ec_private_key<C>(key: &[u8]) -> Result<SecretBuffer, AnyError>
+where
+  C: CurveArithmetic,
+{
+  let key = elliptic_curve::SecretKey::<C>::from_slice(key)?;
+  Ok(key.to_bytes().as_slice().into())
+}
+
+fn ec_uncompressed_point<C>(key: &[u8]) -> Result<Vec<u8>, AnyError>
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::PublicKey::<C>::from_sec1_bytes(key)?;
+  Ok(key.to_encoded_point(false).as_bytes().to_vec())
+}
+
+fn ec_public_key_der<C>(key: &[u8]) -> Result<Vec<u8>, AnyError>
+where
+  C: CurveArithmetic + AssociatedOid,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::PublicKey::<C>::from_sec1_bytes(key)?;
+  Ok(key.to_public_key_der()?.to_vec())
+}
+
+fn ec_generate(
+  named_curve: &str,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  let (private_key, public_key) = EcCurve::from_name(named_curve)?.generate();
+  Ok((private_key.into(), public_key.into()))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_ec_generate(
+  #[string] named_curve: &str,
+) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  ec_generate(named_curve)
+}
+
+#[op2(async)]
+#[serde]
+pub fn op_node_ec_generate_async(
+  state: &mut OpState,
+  #[string] named_curve: String,
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state).spawn(move || ec_generate(&named_curve))
+}
+
+pub(super) fn ed25519_public_key(seed: &[u8]) -> Result<Vec<u8>, AnyError> {
+  use ring::signature::Ed25519KeyPair;
+  use ring::signature::KeyPair;
+
+  let pair = Ed25519KeyPair::from_seed_unchecked(seed)
+    .map_err(|_| type_error("Invalid Ed25519 private key"))?;
+  Ok(pair.public_key().as_ref().to_vec())
+}
+
+fn ed25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  let mut rng = thread_rng();
+  let mut seed = SecretBuffer::zeroed(32);
+  rng.fill(&mut seed[..]);
+
+  let public_key = ed25519_public_key(&seed)?;
+  Ok((seed.into(), public_key.into()))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_ed25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError>
+{
+  ed25519_generate()
+}
+
+#[op2(async)]
+#[serde]
+pub fn op_node_ed25519_generate_async(
+  state: &mut OpState,
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state).spawn(ed25519_generate)
+}
+
+pub(super) fn x25519_public_key(pkey: &[u8]) -> Result<Vec<u8>, AnyError> {
+  let pkey: [u8; 32] = pkey
+    .try_into()
+    .map_err(|_| type_error("Invalid X25519 private key"))?;
+  // https://www.rfc-editor.org/rfc/rfc7748#section-6.1
+  // pubkey = x25519(a, 9) which is constant-time Montgomery ladder.
+  //   https://eprint.iacr.org/2014/140.pdf page 4
+  //   https://eprint.iacr.org/2017/212.pdf algorithm 8
+  // pubkey is in LE order.
+  Ok(x25519_dalek::x25519(pkey, X25519_BASEPOINT_BYTES).to_vec())
+}
+
+fn x25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  let mut pkey = SecretBuffer::zeroed(32);
+
+  let mut rng = thread_rng();
+  rng.fill(&mut pkey[..]);
+
+  let pubkey = x25519_public_key(&pkey)?;
+  Ok((pkey.into(), pubkey.into()))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_x25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
+  x25519_generate()
+}
+
+#[op2(async)]
+#[serde]
+pub fn op_node_x25519_generate_async(
+  state: &mut OpState,
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state).spawn(x25519_generate)
+}
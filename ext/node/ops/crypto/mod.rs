@@ -1,53 +1,61 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use deno_core::error::generic_error;
+use deno_core::error::range_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
-use deno_core::serde_v8::BigInt as V8BigInt;
 use deno_core::unsync::spawn_blocking;
+use deno_core::v8;
 use deno_core::JsBuffer;
 use deno_core::OpState;
 use deno_core::ResourceId;
 use deno_core::StringOrBuffer;
 use deno_core::ToJsBuffer;
+use deno_crypto::SecretBuffer;
 use elliptic_curve::sec1::ToEncodedPoint;
 use hkdf::Hkdf;
 use num_bigint::BigInt;
-use num_bigint_dig::BigUint;
-use num_traits::FromPrimitive;
 use once_cell::sync::Lazy;
 use rand::distributions::Distribution;
 use rand::distributions::Uniform;
-use rand::thread_rng;
 use rand::Rng;
-use rsa::pkcs1::DecodeRsaPrivateKey;
-use rsa::pkcs1::DecodeRsaPublicKey;
 use rsa::pkcs8;
 use rsa::pkcs8::der::asn1;
 use rsa::pkcs8::der::Decode;
 use rsa::pkcs8::der::Encode;
 use rsa::pkcs8::der::Reader;
+use std::cell::RefCell;
 use std::future::Future;
 use std::rc::Rc;
 
+use self::pool::CpuPool;
+
 use p224::NistP224;
 use p256::NistP256;
 use p384::NistP384;
+use p521::NistP521;
 use rsa::pkcs8::DecodePrivateKey;
 use rsa::pkcs8::DecodePublicKey;
-use rsa::signature::hazmat::PrehashSigner;
-use rsa::signature::hazmat::PrehashVerifier;
-use rsa::signature::SignatureEncoding;
+use rsa::traits::PublicKeyParts;
 use rsa::Oaep;
 use rsa::Pkcs1v15Encrypt;
+use rsa::Pkcs1v15Sign;
 use rsa::RsaPrivateKey;
 use rsa::RsaPublicKey;
-use spki::EncodePublicKey;
 
+mod blake2;
 mod cipher;
 mod dh;
 mod digest;
+mod encrypted_key;
+pub mod keygen;
+pub mod keys;
+mod modular;
+pub mod pool;
 mod primes;
+mod shake;
+pub mod sign;
+mod stream;
 pub mod x509;
 
 #[op2(fast)]
@@ -68,32 +76,24 @@ pub fn op_node_check_prime_bytes(
 }
 
 #[op2(async)]
-pub async fn op_node_check_prime_async(
+pub fn op_node_check_prime_async(
+  state: &mut OpState,
   #[bigint] num: i64,
   #[number] checks: usize,
-) -> Result<bool, AnyError> {
-  // TODO(@littledivy): use rayon for CPU-bound tasks
-  Ok(
-    spawn_blocking(move || {
-      primes::is_probably_prime(&BigInt::from(num), checks)
-    })
-    .await?,
-  )
+) -> impl Future<Output = Result<bool, AnyError>> {
+  CpuPool::from_state(state)
+    .spawn(move || Ok(primes::is_probably_prime(&BigInt::from(num), checks)))
 }
 
 #[op2(async)]
 pub fn op_node_check_prime_bytes_async(
+  state: &mut OpState,
   #[anybuffer] bytes: &[u8],
   #[number] checks: usize,
-) -> Result<impl Future<Output = Result<bool, AnyError>>, AnyError> {
+) -> impl Future<Output = Result<bool, AnyError>> {
   let candidate = BigInt::from_bytes_be(num_bigint::Sign::Plus, bytes);
-  // TODO(@littledivy): use rayon for CPU-bound tasks
-  Ok(async move {
-    Ok(
-      spawn_blocking(move || primes::is_probably_prime(&candidate, checks))
-        .await?,
-    )
-  })
+  CpuPool::from_state(state)
+    .spawn(move || Ok(primes::is_probably_prime(&candidate, checks)))
 }
 
 #[op2(fast)]
@@ -116,6 +116,18 @@ pub fn op_node_get_hashes() -> Vec<&'static str> {
   digest::Hash::get_hashes()
 }
 
+#[op2]
+#[serde]
+pub fn op_node_get_ciphers() -> Vec<&'static str> {
+  cipher::CIPHERS.to_vec()
+}
+
+#[op2]
+#[serde]
+pub fn op_node_get_curves() -> Vec<&'static str> {
+  CURVES.to_vec()
+}
+
 #[op2(fast)]
 pub fn op_node_hash_update(
   state: &mut OpState,
@@ -144,16 +156,35 @@ pub fn op_node_hash_update_str(
   true
 }
 
+/// `output_length` is negative when it isn't given.
+/// Updates the hash with the chunks of the resource `source_rid`, which is
+/// closed at its end if `auto_close` is set.
+#[op2(async)]
+pub fn op_node_hash_update_stream(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] source_rid: ResourceId,
+  auto_close: bool,
+) -> Result<impl Future<Output = Result<(), AnyError>>, AnyError> {
+  let context = state.resource_table.get::<digest::Context>(rid)?;
+  let source = stream::source(state, source_rid, auto_close)?;
+  Ok(source.pump(move |chunk| {
+    context.update(chunk);
+    Ok(())
+  }))
+}
+
 #[op2]
 #[serde]
 pub fn op_node_hash_digest(
   state: &mut OpState,
   #[smi] rid: ResourceId,
+  #[smi] output_length: i32,
 ) -> Result<ToJsBuffer, AnyError> {
   let context = state.resource_table.take::<digest::Context>(rid)?;
   let context = Rc::try_unwrap(context)
     .map_err(|_| type_error("Hash context is already in use"))?;
-  Ok(context.digest()?.into())
+  Ok(context.digest(usize::try_from(output_length).ok())?.into())
 }
 
 #[op2]
@@ -161,11 +192,12 @@ pub fn op_node_hash_digest(
 pub fn op_node_hash_digest_hex(
   state: &mut OpState,
   #[smi] rid: ResourceId,
+  #[smi] output_length: i32,
 ) -> Result<String, AnyError> {
   let context = state.resource_table.take::<digest::Context>(rid)?;
   let context = Rc::try_unwrap(context)
     .map_err(|_| type_error("Hash context is already in use"))?;
-  let digest = context.digest()?;
+  let digest = context.digest(usize::try_from(output_length).ok())?;
   Ok(faster_hex::hex_string(&digest))
 }
 
@@ -179,27 +211,110 @@ pub fn op_node_hash_clone(
   Ok(state.resource_table.add(context.as_ref().clone()))
 }
 
+#[op2(fast)]
+#[smi]
+pub fn op_node_create_hmac(
+  state: &mut OpState,
+  #[string] algorithm: &str,
+  #[buffer] key: &[u8],
+) -> u32 {
+  state
+    .resource_table
+    .add(match digest::HmacContext::new(algorithm, key) {
+      Ok(context) => context,
+      Err(_) => return 0,
+    })
+}
+
+#[op2(fast)]
+pub fn op_node_hmac_update(
+  state: &mut OpState,
+  #[smi] rid: u32,
+  #[buffer] data: &[u8],
+) -> bool {
+  let context = match state.resource_table.get::<digest::HmacContext>(rid) {
+    Ok(context) => context,
+    _ => return false,
+  };
+  context.update(data);
+  true
+}
+
+/// Like [op_node_hash_update_stream], for an hmac.
+#[op2(async)]
+pub fn op_node_hmac_update_stream(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] source_rid: ResourceId,
+  auto_close: bool,
+) -> Result<impl Future<Output = Result<(), AnyError>>, AnyError> {
+  let context = state.resource_table.get::<digest::HmacContext>(rid)?;
+  let source = stream::source(state, source_rid, auto_close)?;
+  Ok(source.pump(move |chunk| {
+    context.update(chunk);
+    Ok(())
+  }))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_hmac_digest(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<ToJsBuffer, AnyError> {
+  let context = state.resource_table.take::<digest::HmacContext>(rid)?;
+  let context = Rc::try_unwrap(context)
+    .map_err(|_| type_error("Hmac context is already in use"))?;
+  Ok(context.digest().into())
+}
+
+/// The OAEP padding for `hash`, the `oaepHash` option of node, and
+/// `oaepLabel`.
+fn oaep_padding(hash: &str, label: Option<&[u8]>) -> Result<Oaep, AnyError> {
+  let mut padding = match hash.to_ascii_lowercase().as_str() {
+    "sha1" => Oaep::new::<sha1::Sha1>(),
+    "sha256" => Oaep::new::<sha2::Sha256>(),
+    "sha512" => Oaep::new::<sha2::Sha512>(),
+    _ => return Err(type_error(format!("Invalid digest: {hash}"))),
+  };
+  // the rsa crate only takes labels that are strings
+  padding.label = label
+    .map(|label| {
+      String::from_utf8(label.to_vec())
+        .map_err(|_| type_error("OAEP labels must be valid UTF-8"))
+    })
+    .transpose()?;
+  Ok(padding)
+}
+
+/// Removes the PKCS#1 v1.5 padding of a signature, `0x00 0x01 0xff.. 0x00`
+/// with at least 8 `0xff` bytes.
+fn unpad_pkcs1v15_sign(em: &[u8]) -> Option<&[u8]> {
+  let padding = em.strip_prefix(&[0, 1])?;
+  let len = padding.iter().position(|byte| *byte != 0xff)?;
+  (len >= 8 && padding[len] == 0).then(|| &padding[len + 1..])
+}
+
 #[op2]
 #[serde]
 pub fn op_node_private_encrypt(
   #[serde] key: StringOrBuffer,
   #[serde] msg: StringOrBuffer,
   #[smi] padding: u32,
+  #[string] oaep_hash: &str,
+  #[buffer] oaep_label: Option<&[u8]>,
 ) -> Result<ToJsBuffer, AnyError> {
-  let key = RsaPrivateKey::from_pkcs8_pem((&key).try_into()?)?;
+  let key = SecretBuffer::from(key);
+  let key = RsaPrivateKey::from_pkcs8_pem(std::str::from_utf8(&key)?)?;
 
   let mut rng = rand::thread_rng();
   match padding {
-    1 => Ok(
-      key
-        .as_ref()
-        .encrypt(&mut rng, Pkcs1v15Encrypt, &msg)?
-        .into(),
-    ),
+    // like RSA_private_encrypt, which op_node_public_decrypt inverts
+    1 => Ok(key.sign(Pkcs1v15Sign::new_unprefixed(), &msg)?.into()),
     4 => Ok(
       key
         .as_ref()
-        .encrypt(&mut rng, Oaep::new::<sha1::Sha1>(), &msg)?
+        .encrypt(&mut rng, oaep_padding(oaep_hash, oaep_label)?, &msg)?
         .into(),
     ),
     _ => Err(type_error("Unknown padding")),
@@ -212,14 +327,18 @@ pub fn op_node_private_decrypt(
   #[serde] key: StringOrBuffer,
   #[serde] msg: StringOrBuffer,
   #[smi] padding: u32,
+  #[string] oaep_hash: &str,
+  #[buffer] oaep_label: Option<&[u8]>,
 ) -> Result<ToJsBuffer, AnyError> {
-  let key = RsaPrivateKey::from_pkcs8_pem((&key).try_into()?)?;
+  let key = SecretBuffer::from(key);
+  let key = RsaPrivateKey::from_pkcs8_pem(std::str::from_utf8(&key)?)?;
 
-  match padding {
-    1 => Ok(key.decrypt(Pkcs1v15Encrypt, &msg)?.into()),
-    4 => Ok(key.decrypt(Oaep::new::<sha1::Sha1>(), &msg)?.into()),
-    _ => Err(type_error("Unknown padding")),
-  }
+  let plaintext = match padding {
+    1 => key.decrypt(Pkcs1v15Encrypt, &msg)?,
+    4 => key.decrypt(oaep_padding(oaep_hash, oaep_label)?, &msg)?,
+    _ => return Err(type_error("Unknown padding")),
+  };
+  Ok(SecretBuffer::from(plaintext).into())
 }
 
 #[op2]
@@ -228,6 +347,8 @@ pub fn op_node_public_encrypt(
   #[serde] key: StringOrBuffer,
   #[serde] msg: StringOrBuffer,
   #[smi] padding: u32,
+  #[string] oaep_hash: &str,
+  #[buffer] oaep_label: Option<&[u8]>,
 ) -> Result<ToJsBuffer, AnyError> {
   let key = RsaPublicKey::from_public_key_pem((&key).try_into()?)?;
 
@@ -236,13 +357,42 @@ pub fn op_node_public_encrypt(
     1 => Ok(key.encrypt(&mut rng, Pkcs1v15Encrypt, &msg)?.into()),
     4 => Ok(
       key
-        .encrypt(&mut rng, Oaep::new::<sha1::Sha1>(), &msg)?
+        .encrypt(&mut rng, oaep_padding(oaep_hash, oaep_label)?, &msg)?
         .into(),
     ),
     _ => Err(type_error("Unknown padding")),
   }
 }
 
+/// Recovers the data passed to `op_node_private_encrypt`, like
+/// RSA_public_decrypt.
+#[op2]
+#[serde]
+pub fn op_node_public_decrypt(
+  #[serde] key: StringOrBuffer,
+  #[serde] msg: StringOrBuffer,
+  #[smi] padding: u32,
+) -> Result<ToJsBuffer, AnyError> {
+  let key = RsaPublicKey::from_public_key_pem((&key).try_into()?)?;
+  if padding != 1 {
+    return Err(type_error("Unknown padding"));
+  }
+
+  let size = key.size();
+  let input = rsa::BigUint::from_bytes_be(&msg);
+  if msg.len() != size || input >= *key.n() {
+    return Err(type_error("Data must be as long as the key modulus"));
+  }
+  let output = rsa::hazmat::rsa_encrypt(&key, &input)?.to_bytes_be();
+  let mut em = vec![0; size - output.len()];
+  em.extend_from_slice(&output);
+  let data =
+    unpad_pkcs1v15_sign(&em).ok_or_else(|| type_error("Invalid padding"))?;
+  Ok(data.to_vec().into())
+}
+
+/// Creates a cipher context. `auth_tag_length` is negative when it isn't
+/// given for an authenticated mode.
 #[op2(fast)]
 #[smi]
 pub fn op_node_create_cipheriv(
@@ -250,58 +400,75 @@ pub fn op_node_create_cipheriv(
   #[string] algorithm: &str,
   #[buffer] key: &[u8],
   #[buffer] iv: &[u8],
-) -> u32 {
-  state.resource_table.add(
-    match cipher::CipherContext::new(algorithm, key, iv) {
-      Ok(context) => context,
-      Err(_) => return 0,
-    },
-  )
+  #[smi] auth_tag_length: i32,
+) -> Result<u32, AnyError> {
+  let auth_tag_length = usize::try_from(auth_tag_length).ok();
+  let context =
+    cipher::CipherContext::new(algorithm, key, iv, auth_tag_length)?;
+  Ok(state.resource_table.add(context))
 }
 
+/// `plaintext_length` is negative when it isn't given.
 #[op2(fast)]
 pub fn op_node_cipheriv_set_aad(
   state: &mut OpState,
   #[smi] rid: u32,
   #[buffer] aad: &[u8],
-) -> bool {
-  let context = match state.resource_table.get::<cipher::CipherContext>(rid) {
-    Ok(context) => context,
-    Err(_) => return false,
-  };
-  context.set_aad(aad);
-  true
+  #[smi] plaintext_length: i32,
+) -> Result<(), AnyError> {
+  let context = state.resource_table.get::<cipher::CipherContext>(rid)?;
+  context.set_aad(aad, usize::try_from(plaintext_length).ok())
 }
 
-#[op2(fast)]
-pub fn op_node_cipheriv_encrypt(
+/// Returns the ciphertext that is ready after the input, which can be
+/// shorter or longer than it in block modes.
+#[op2]
+#[buffer]
+pub fn op_node_cipheriv_update(
   state: &mut OpState,
   #[smi] rid: u32,
   #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-) -> bool {
-  let context = match state.resource_table.get::<cipher::CipherContext>(rid) {
-    Ok(context) => context,
-    Err(_) => return false,
-  };
-  context.encrypt(input, output);
-  true
+) -> Result<Vec<u8>, AnyError> {
+  let context = state.resource_table.get::<cipher::CipherContext>(rid)?;
+  context.update(input)
+}
+
+/// Returns a resource reading the ciphertext of the chunks of the resource
+/// `source_rid`. The context can only be finished once it was read to its
+/// end, or closed.
+#[op2(fast)]
+#[smi]
+pub fn op_node_cipheriv_update_stream(
+  state: &mut OpState,
+  #[smi] rid: u32,
+  #[smi] source_rid: ResourceId,
+  auto_close: bool,
+) -> Result<ResourceId, AnyError> {
+  let context = state.resource_table.get::<cipher::CipherContext>(rid)?;
+  let source = stream::source(state, source_rid, auto_close)?;
+  let stream = stream::CipherStream::new(
+    source,
+    Box::new(move |chunk| context.update(chunk)),
+  );
+  Ok(state.resource_table.add(stream))
 }
 
+/// Returns the rest of the ciphertext and the auth tag, if any.
 #[op2]
 #[serde]
 pub fn op_node_cipheriv_final(
   state: &mut OpState,
   #[smi] rid: u32,
-  #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-) -> Result<Option<Vec<u8>>, AnyError> {
+) -> Result<(ToJsBuffer, Option<ToJsBuffer>), AnyError> {
   let context = state.resource_table.take::<cipher::CipherContext>(rid)?;
   let context = Rc::try_unwrap(context)
     .map_err(|_| type_error("Cipher context is already in use"))?;
-  context.r#final(input, output)
+  let (output, tag) = context.finish()?;
+  Ok((output.into(), tag.map(Into::into)))
 }
 
+/// Creates a decipher context. `auth_tag_length` is negative when it isn't
+/// given for an authenticated mode.
 #[op2(fast)]
 #[smi]
 pub fn op_node_create_decipheriv(
@@ -309,206 +476,180 @@ pub fn op_node_create_decipheriv(
   #[string] algorithm: &str,
   #[buffer] key: &[u8],
   #[buffer] iv: &[u8],
-) -> u32 {
-  state.resource_table.add(
-    match cipher::DecipherContext::new(algorithm, key, iv) {
-      Ok(context) => context,
-      Err(_) => return 0,
-    },
-  )
+  #[smi] auth_tag_length: i32,
+) -> Result<u32, AnyError> {
+  let auth_tag_length = usize::try_from(auth_tag_length).ok();
+  let context =
+    cipher::DecipherContext::new(algorithm, key, iv, auth_tag_length)?;
+  Ok(state.resource_table.add(context))
 }
 
+/// `plaintext_length` is negative when it isn't given.
 #[op2(fast)]
 pub fn op_node_decipheriv_set_aad(
   state: &mut OpState,
   #[smi] rid: u32,
   #[buffer] aad: &[u8],
-) -> bool {
-  let context = match state.resource_table.get::<cipher::DecipherContext>(rid) {
-    Ok(context) => context,
-    Err(_) => return false,
-  };
-  context.set_aad(aad);
-  true
+  #[smi] plaintext_length: i32,
+) -> Result<(), AnyError> {
+  let context = state.resource_table.get::<cipher::DecipherContext>(rid)?;
+  context.set_aad(aad, usize::try_from(plaintext_length).ok())
 }
 
 #[op2(fast)]
-pub fn op_node_decipheriv_decrypt(
+pub fn op_node_decipheriv_set_auth_tag(
+  state: &mut OpState,
+  #[smi] rid: u32,
+  #[buffer] auth_tag: &[u8],
+) -> Result<(), AnyError> {
+  let context = state.resource_table.get::<cipher::DecipherContext>(rid)?;
+  context.set_auth_tag(auth_tag)
+}
+
+/// Returns the plaintext that is ready after the input. Block modes hold
+/// back the last block until the end.
+#[op2]
+#[buffer]
+pub fn op_node_decipheriv_update(
   state: &mut OpState,
   #[smi] rid: u32,
   #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-) -> bool {
-  let context = match state.resource_table.get::<cipher::DecipherContext>(rid) {
-    Ok(context) => context,
-    Err(_) => return false,
-  };
-  context.decrypt(input, output);
-  true
+) -> Result<Vec<u8>, AnyError> {
+  let context = state.resource_table.get::<cipher::DecipherContext>(rid)?;
+  context.update(input)
 }
 
+/// Like [op_node_cipheriv_update_stream], for the plaintext.
 #[op2(fast)]
+#[smi]
+pub fn op_node_decipheriv_update_stream(
+  state: &mut OpState,
+  #[smi] rid: u32,
+  #[smi] source_rid: ResourceId,
+  auto_close: bool,
+) -> Result<ResourceId, AnyError> {
+  let context = state.resource_table.get::<cipher::DecipherContext>(rid)?;
+  let source = stream::source(state, source_rid, auto_close)?;
+  let stream = stream::CipherStream::new(
+    source,
+    Box::new(move |chunk| context.update(chunk)),
+  );
+  Ok(state.resource_table.add(stream))
+}
+
+/// Returns the rest of the plaintext, checking the auth tag in
+/// authenticated modes.
+#[op2]
+#[buffer]
 pub fn op_node_decipheriv_final(
   state: &mut OpState,
   #[smi] rid: u32,
-  #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-  #[buffer] auth_tag: &[u8],
-) -> Result<(), AnyError> {
+) -> Result<Vec<u8>, AnyError> {
   let context = state.resource_table.take::<cipher::DecipherContext>(rid)?;
   let context = Rc::try_unwrap(context)
     .map_err(|_| type_error("Cipher context is already in use"))?;
-  context.r#final(input, output, auth_tag)
+  context.finish()
 }
 
-#[op2]
+/// Size of the chunks read from the source resource when piping data
+/// through a cipher.
+const CIPHER_PIPE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The most plaintext [op_node_decipheriv_pipe] holds back in memory until
+/// the auth tag is checked, in authenticated modes.
+const AUTHENTICATED_PIPE_MAX_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Encrypts everything read from `src_rid` and writes it to `dst_rid`,
+/// without passing the data through JS. Finalizes the cipher context and
+/// returns the auth tag, if any.
+#[op2(async)]
 #[serde]
-pub fn op_node_sign(
-  #[buffer] digest: &[u8],
-  #[string] digest_type: &str,
-  #[serde] key: StringOrBuffer,
-  #[string] _type: &str,
-  #[string] format: &str,
-) -> Result<ToJsBuffer, AnyError> {
-  let (label, doc) =
-    pkcs8::SecretDocument::from_pem(std::str::from_utf8(&key).unwrap())?;
-
-  let oid;
-  let pkey = match format {
-    "pem" => match label {
-      "PRIVATE KEY" => {
-        let pk_info = pkcs8::PrivateKeyInfo::try_from(doc.as_bytes())?;
-        oid = pk_info.algorithm.oid;
-        pk_info.private_key
-      }
-      "RSA PRIVATE KEY" => {
-        oid = RSA_ENCRYPTION_OID;
-        doc.as_bytes()
-      }
-      "EC PRIVATE KEY" => {
-        let ec_pk = sec1::EcPrivateKey::from_der(doc.as_bytes())?;
-        match ec_pk.parameters {
-          Some(sec1::EcParameters::NamedCurve(o)) => {
-            oid = o;
-            ec_pk.private_key
-          }
-          // https://datatracker.ietf.org/doc/html/rfc5915#section-3
-          //
-          // Though the ASN.1 indicates that
-          // the parameters field is OPTIONAL, implementations that conform to
-          // this document MUST always include the parameters field.
-          _ => return Err(type_error("invalid ECPrivateKey params")),
-        }
-      }
-      _ => return Err(type_error("Invalid PEM label")),
-    },
-    _ => return Err(type_error("Unsupported key format")),
+pub async fn op_node_cipheriv_pipe(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: u32,
+  #[smi] src_rid: ResourceId,
+  #[smi] dst_rid: ResourceId,
+) -> Result<Option<Vec<u8>>, AnyError> {
+  let (context, src, dst) = {
+    let mut state = state.borrow_mut();
+    let context = state.resource_table.take::<cipher::CipherContext>(rid)?;
+    let src = state.resource_table.get_any(src_rid)?;
+    let dst = state.resource_table.get_any(dst_rid)?;
+    (context, src, dst)
   };
+  let context = Rc::try_unwrap(context)
+    .map_err(|_| type_error("Cipher context is already in use"))?;
 
-  match oid {
-    RSA_ENCRYPTION_OID => {
-      use rsa::pkcs1v15::SigningKey;
-      let key = RsaPrivateKey::from_pkcs1_der(pkey)?;
-      Ok(
-        match digest_type {
-          "sha224" => {
-            let signing_key = SigningKey::<sha2::Sha224>::new(key);
-            signing_key.sign_prehash(digest)?.to_vec()
-          }
-          "sha256" => {
-            let signing_key = SigningKey::<sha2::Sha256>::new(key);
-            signing_key.sign_prehash(digest)?.to_vec()
-          }
-          "sha384" => {
-            let signing_key = SigningKey::<sha2::Sha384>::new(key);
-            signing_key.sign_prehash(digest)?.to_vec()
-          }
-          "sha512" => {
-            let signing_key = SigningKey::<sha2::Sha512>::new(key);
-            signing_key.sign_prehash(digest)?.to_vec()
-          }
-          _ => {
-            return Err(type_error(format!(
-              "Unknown digest algorithm: {}",
-              digest_type
-            )))
-          }
-        }
-        .into(),
-      )
-    }
-    // signature structure encoding is DER by default for DSA and ECDSA.
-    //
-    // TODO(@littledivy): Validate public_key if present
-    ID_SECP256R1_OID => {
-      let key = p256::ecdsa::SigningKey::from_slice(pkey)?;
-      Ok(
-        key
-          .sign_prehash(digest)
-          .map(|sig: p256::ecdsa::Signature| sig.to_der().to_vec().into())?,
-      )
+  loop {
+    let chunk = src.clone().read(CIPHER_PIPE_CHUNK_SIZE).await?;
+    if chunk.is_empty() {
+      break;
     }
-    ID_SECP384R1_OID => {
-      let key = p384::ecdsa::SigningKey::from_slice(pkey)?;
-      Ok(
-        key
-          .sign_prehash(digest)
-          .map(|sig: p384::ecdsa::Signature| sig.to_der().to_vec().into())?,
-      )
+    let output = context.update(&chunk)?;
+    if !output.is_empty() {
+      dst.clone().write_all(output.into()).await?;
     }
-    _ => Err(type_error("Unsupported signing key")),
   }
+
+  let (output, tag) = context.finish()?;
+  if !output.is_empty() {
+    dst.write_all(output.into()).await?;
+  }
+  Ok(tag)
 }
 
-#[op2]
-pub fn op_node_verify(
-  #[buffer] digest: &[u8],
-  #[string] digest_type: &str,
-  #[serde] key: StringOrBuffer,
-  #[string] key_type: &str,
-  #[string] key_format: &str,
-  #[buffer] signature: &[u8],
-) -> Result<bool, AnyError> {
-  match key_type {
-    "rsa" => {
-      use rsa::pkcs1v15::VerifyingKey;
-      let key = match key_format {
-        "pem" => RsaPublicKey::from_public_key_pem((&key).try_into()?)
-          .map_err(|_| type_error("Invalid RSA public key"))?,
-        // TODO(kt3k): Support der and jwk formats
-        _ => {
-          return Err(type_error(format!(
-            "Unsupported key format: {}",
-            key_format
-          )))
-        }
-      };
-      Ok(match digest_type {
-        "sha224" => VerifyingKey::<sha2::Sha224>::new(key)
-          .verify_prehash(digest, &signature.try_into()?)
-          .is_ok(),
-        "sha256" => VerifyingKey::<sha2::Sha256>::new(key)
-          .verify_prehash(digest, &signature.try_into()?)
-          .is_ok(),
-        "sha384" => VerifyingKey::<sha2::Sha384>::new(key)
-          .verify_prehash(digest, &signature.try_into()?)
-          .is_ok(),
-        "sha512" => VerifyingKey::<sha2::Sha512>::new(key)
-          .verify_prehash(digest, &signature.try_into()?)
-          .is_ok(),
-        _ => {
-          return Err(type_error(format!(
-            "Unknown digest algorithm: {}",
-            digest_type
-          )))
-        }
-      })
+/// Decrypts everything read from `src_rid` and writes it to `dst_rid`,
+/// without passing the data through JS. Finalizes the decipher context,
+/// checking the auth tag for authenticated modes. Their plaintext is only
+/// written once the tag matched, so it is buffered in memory until then, up
+/// to [AUTHENTICATED_PIPE_MAX_LENGTH] bytes. Longer input is a `RangeError`.
+#[op2(async)]
+pub async fn op_node_decipheriv_pipe(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: u32,
+  #[smi] src_rid: ResourceId,
+  #[smi] dst_rid: ResourceId,
+) -> Result<(), AnyError> {
+  let (context, src, dst) = {
+    let mut state = state.borrow_mut();
+    let context = state.resource_table.take::<cipher::DecipherContext>(rid)?;
+    let src = state.resource_table.get_any(src_rid)?;
+    let dst = state.resource_table.get_any(dst_rid)?;
+    (context, src, dst)
+  };
+  let context = Rc::try_unwrap(context)
+    .map_err(|_| type_error("Cipher context is already in use"))?;
+  let mut unauthenticated = context.is_authenticated().then(Vec::new);
+
+  loop {
+    let chunk = src.clone().read(CIPHER_PIPE_CHUNK_SIZE).await?;
+    if chunk.is_empty() {
+      break;
+    }
+    let output = context.update(&chunk)?;
+    if let Some(unauthenticated) = &mut unauthenticated {
+      if unauthenticated.len() + output.len() > AUTHENTICATED_PIPE_MAX_LENGTH {
+        return Err(range_error(format!(
+          "Authenticated decipher input is too large to pipe, the limit is {} bytes. Use update() and final() instead",
+          AUTHENTICATED_PIPE_MAX_LENGTH
+        )));
+      }
+      unauthenticated.extend_from_slice(&output);
+    } else if !output.is_empty() {
+      dst.clone().write_all(output.into()).await?;
+    }
+  }
+
+  let output = context.finish()?;
+  if let Some(unauthenticated) = unauthenticated {
+    if !unauthenticated.is_empty() {
+      dst.clone().write_all(unauthenticated.into()).await?;
     }
-    _ => Err(type_error(format!(
-      "Verifying with {} keys is not supported yet",
-      key_type
-    ))),
   }
+  if !output.is_empty() {
+    dst.write_all(output.into()).await?;
+  }
+  Ok(())
 }
 
 fn pbkdf2_sync(
@@ -547,24 +688,26 @@ pub fn op_node_pbkdf2(
   #[string] digest: &str,
   #[buffer] derived_key: &mut [u8],
 ) -> bool {
+  let password = SecretBuffer::from(password);
   pbkdf2_sync(&password, &salt, iterations, digest, derived_key).is_ok()
 }
 
 #[op2(async)]
 #[serde]
-pub async fn op_node_pbkdf2_async(
+pub fn op_node_pbkdf2_async(
+  state: &mut OpState,
   #[serde] password: StringOrBuffer,
   #[serde] salt: StringOrBuffer,
   #[smi] iterations: u32,
   #[string] digest: String,
   #[number] keylen: usize,
-) -> Result<ToJsBuffer, AnyError> {
-  spawn_blocking(move || {
-    let mut derived_key = vec![0; keylen];
+) -> impl Future<Output = Result<ToJsBuffer, AnyError>> {
+  CpuPool::from_state(state).spawn(move || {
+    let password = SecretBuffer::from(password);
+    let mut derived_key = SecretBuffer::zeroed(keylen);
     pbkdf2_sync(&password, &salt, iterations, &digest, &mut derived_key)
       .map(|_| derived_key.into())
   })
-  .await?
 }
 
 #[op2(fast)]
@@ -576,7 +719,7 @@ pub fn op_node_generate_secret(#[buffer] buf: &mut [u8]) {
 #[serde]
 pub async fn op_node_generate_secret_async(#[smi] len: i32) -> ToJsBuffer {
   spawn_blocking(move || {
-    let mut buf = vec![0u8; len as usize];
+    let mut buf = SecretBuffer::zeroed(len as usize);
     rand::thread_rng().fill(&mut buf[..]);
     buf.into()
   })
@@ -635,236 +778,26 @@ pub async fn op_node_hkdf_async(
   #[number] okm_len: usize,
 ) -> Result<ToJsBuffer, AnyError> {
   spawn_blocking(move || {
-    let mut okm = vec![0u8; okm_len];
+    let mut okm = SecretBuffer::zeroed(okm_len);
     hkdf_sync(&hash, &ikm, &salt, &info, &mut okm)?;
     Ok(okm.into())
   })
   .await?
 }
 
-use rsa::pkcs1::EncodeRsaPrivateKey;
-use rsa::pkcs1::EncodeRsaPublicKey;
-
 use self::primes::Prime;
 
-fn generate_rsa(
-  modulus_length: usize,
-  public_exponent: usize,
+/// Encodes a generated key pair as PKCS#8 and SPKI, like node does.
+fn dh_key_pair(
+  dh: dh::DiffieHellman,
 ) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  let mut rng = rand::thread_rng();
-  let private_key = RsaPrivateKey::new_with_exp(
-    &mut rng,
-    modulus_length,
-    &rsa::BigUint::from_usize(public_exponent).unwrap(),
+  let (private_key, public_key) = keys::dh_key_pair_der(
+    &dh.prime.to_bytes_be(),
+    &dh.generator.to_bytes_be(),
+    &dh.private_key.into_secret_buffer(),
+    &dh.public_key.into_vec(),
   )?;
-  let public_key = private_key.to_public_key();
-  let private_key_der = private_key.to_pkcs1_der()?.as_bytes().to_vec();
-  let public_key_der = public_key.to_pkcs1_der()?.to_vec();
-
-  Ok((private_key_der.into(), public_key_der.into()))
-}
-
-#[op2]
-#[serde]
-pub fn op_node_generate_rsa(
-  #[number] modulus_length: usize,
-  #[number] public_exponent: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  generate_rsa(modulus_length, public_exponent)
-}
-
-#[op2(async)]
-#[serde]
-pub async fn op_node_generate_rsa_async(
-  #[number] modulus_length: usize,
-  #[number] public_exponent: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(move || generate_rsa(modulus_length, public_exponent)).await?
-}
-
-#[op2]
-#[string]
-pub fn op_node_export_rsa_public_pem(
-  #[buffer] pkcs1_der: &[u8],
-) -> Result<String, AnyError> {
-  let public_key = RsaPublicKey::from_pkcs1_der(pkcs1_der)?;
-  let export = public_key.to_public_key_pem(Default::default())?;
-  Ok(export)
-}
-
-#[op2]
-#[serde]
-pub fn op_node_export_rsa_spki_der(
-  #[buffer] pkcs1_der: &[u8],
-) -> Result<ToJsBuffer, AnyError> {
-  let public_key = RsaPublicKey::from_pkcs1_der(pkcs1_der)?;
-  let export = public_key.to_public_key_der()?.to_vec();
-  Ok(export.into())
-}
-
-fn dsa_generate(
-  modulus_length: usize,
-  divisor_length: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  let mut rng = rand::thread_rng();
-  use dsa::pkcs8::EncodePrivateKey;
-  use dsa::Components;
-  use dsa::KeySize;
-  use dsa::SigningKey;
-
-  let key_size = match (modulus_length, divisor_length) {
-    #[allow(deprecated)]
-    (1024, 160) => KeySize::DSA_1024_160,
-    (2048, 224) => KeySize::DSA_2048_224,
-    (2048, 256) => KeySize::DSA_2048_256,
-    (3072, 256) => KeySize::DSA_3072_256,
-    _ => return Err(type_error("Invalid modulus_length or divisor_length")),
-  };
-  let components = Components::generate(&mut rng, key_size);
-  let signing_key = SigningKey::generate(&mut rng, components);
-  let verifying_key = signing_key.verifying_key();
-
-  Ok((
-    signing_key
-      .to_pkcs8_der()
-      .map_err(|_| type_error("Not valid pkcs8"))?
-      .as_bytes()
-      .to_vec()
-      .into(),
-    verifying_key
-      .to_public_key_der()
-      .map_err(|_| type_error("Not valid spki"))?
-      .to_vec()
-      .into(),
-  ))
-}
-
-#[op2]
-#[serde]
-pub fn op_node_dsa_generate(
-  #[number] modulus_length: usize,
-  #[number] divisor_length: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  dsa_generate(modulus_length, divisor_length)
-}
-
-#[op2(async)]
-#[serde]
-pub async fn op_node_dsa_generate_async(
-  #[number] modulus_length: usize,
-  #[number] divisor_length: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(move || dsa_generate(modulus_length, divisor_length)).await?
-}
-
-fn ec_generate(
-  named_curve: &str,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  let mut rng = rand::thread_rng();
-  // TODO(@littledivy): Support public key point encoding.
-  // Default is uncompressed.
-  match named_curve {
-    "P-256" | "prime256v1" | "secp256r1" => {
-      let key = p256::SecretKey::random(&mut rng);
-      let public_key = key.public_key();
-
-      Ok((
-        key.to_bytes().to_vec().into(),
-        public_key.to_encoded_point(false).as_ref().to_vec().into(),
-      ))
-    }
-    "P-384" | "prime384v1" | "secp384r1" => {
-      let key = p384::SecretKey::random(&mut rng);
-      let public_key = key.public_key();
-
-      Ok((
-        key.to_bytes().to_vec().into(),
-        public_key.to_encoded_point(false).as_ref().to_vec().into(),
-      ))
-    }
-    _ => Err(type_error("Unsupported named curve")),
-  }
-}
-
-#[op2]
-#[serde]
-pub fn op_node_ec_generate(
-  #[string] named_curve: &str,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  ec_generate(named_curve)
-}
-
-#[op2(async)]
-#[serde]
-pub async fn op_node_ec_generate_async(
-  #[string] named_curve: String,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(move || ec_generate(&named_curve)).await?
-}
-
-fn ed25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  use ring::signature::Ed25519KeyPair;
-  use ring::signature::KeyPair;
-
-  let mut rng = thread_rng();
-  let mut seed = vec![0u8; 32];
-  rng.fill(seed.as_mut_slice());
-
-  let pair = Ed25519KeyPair::from_seed_unchecked(&seed)
-    .map_err(|_| type_error("Failed to generate Ed25519 key"))?;
-
-  let public_key = pair.public_key().as_ref().to_vec();
-  Ok((seed.into(), public_key.into()))
-}
-
-#[op2]
-#[serde]
-pub fn op_node_ed25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError>
-{
-  ed25519_generate()
-}
-
-#[op2(async)]
-#[serde]
-pub async fn op_node_ed25519_generate_async(
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(ed25519_generate).await?
-}
-
-fn x25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  // u-coordinate of the base point.
-  const X25519_BASEPOINT_BYTES: [u8; 32] = [
-    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0,
-  ];
-
-  let mut pkey = [0; 32];
-
-  let mut rng = thread_rng();
-  rng.fill(pkey.as_mut_slice());
-
-  let pkey_copy = pkey.to_vec();
-  // https://www.rfc-editor.org/rfc/rfc7748#section-6.1
-  // pubkey = x25519(a, 9) which is constant-time Montgomery ladder.
-  //   https://eprint.iacr.org/2014/140.pdf page 4
-  //   https://eprint.iacr.org/2017/212.pdf algorithm 8
-  // pubkey is in LE order.
-  let pubkey = x25519_dalek::x25519(pkey, X25519_BASEPOINT_BYTES);
-
-  Ok((pkey_copy.into(), pubkey.to_vec().into()))
-}
-
-#[op2]
-#[serde]
-pub fn op_node_x25519_generate() -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  x25519_generate()
-}
-
-#[op2(async)]
-#[serde]
-pub async fn op_node_x25519_generate_async(
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(x25519_generate).await?
+  Ok((private_key.into(), public_key.into()))
 }
 
 fn dh_generate_group(
@@ -880,10 +813,7 @@ fn dh_generate_group(
     _ => return Err(type_error("Unsupported group name")),
   };
 
-  Ok((
-    dh.private_key.into_vec().into(),
-    dh.public_key.into_vec().into(),
-  ))
+  dh_key_pair(dh)
 }
 
 #[op2]
@@ -896,10 +826,11 @@ pub fn op_node_dh_generate_group(
 
 #[op2(async)]
 #[serde]
-pub async fn op_node_dh_generate_group_async(
+pub fn op_node_dh_generate_group_async(
+  state: &mut OpState,
   #[string] group_name: String,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(move || dh_generate_group(&group_name)).await?
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state).spawn(move || dh_generate_group(&group_name))
 }
 
 fn dh_generate(
@@ -912,10 +843,7 @@ fn dh_generate(
     .unwrap_or_else(|| Prime::generate(prime_len));
   let dh = dh::DiffieHellman::new(prime, generator);
 
-  Ok((
-    dh.private_key.into_vec().into(),
-    dh.public_key.into_vec().into(),
-  ))
+  dh_key_pair(dh)
 }
 
 #[op2]
@@ -928,49 +856,124 @@ pub fn op_node_dh_generate(
   dh_generate(prime, prime_len, generator)
 }
 
-// TODO(lev): This duplication should be avoided.
+#[op2]
+pub fn op_node_dh_new<'s>(
+  scope: &'s mut v8::HandleScope,
+  #[buffer] prime: &[u8],
+  #[buffer] generator: &[u8],
+) -> v8::Local<'s, v8::Object> {
+  let dh = dh::DiffieHellmanContext::new(prime, generator);
+  deno_core::cppgc::make_cppgc_object(scope, dh)
+}
+
+#[op2]
+pub fn op_node_dh_new_group<'s>(
+  scope: &'s mut v8::HandleScope,
+  #[string] group_name: &str,
+) -> Result<v8::Local<'s, v8::Object>, AnyError> {
+  let dh = match group_name {
+    "modp5" => dh::DiffieHellmanContext::group::<dh::Modp1536>(),
+    "modp14" => dh::DiffieHellmanContext::group::<dh::Modp2048>(),
+    "modp15" => dh::DiffieHellmanContext::group::<dh::Modp3072>(),
+    "modp16" => dh::DiffieHellmanContext::group::<dh::Modp4096>(),
+    "modp17" => dh::DiffieHellmanContext::group::<dh::Modp6144>(),
+    "modp18" => dh::DiffieHellmanContext::group::<dh::Modp8192>(),
+    _ => return Err(type_error("Unsupported group name")),
+  };
+  Ok(deno_core::cppgc::make_cppgc_object(scope, dh))
+}
+
 #[op2]
 #[serde]
-pub fn op_node_dh_generate2(
-  #[buffer] prime: JsBuffer,
-  #[number] prime_len: usize,
-  #[number] generator: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  dh_generate(Some(prime).as_deref(), prime_len, generator)
+pub fn op_node_dh_generate_keys(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+) -> ToJsBuffer {
+  dh.generate_keys().into()
 }
 
 #[op2]
 #[serde]
 pub fn op_node_dh_compute_secret(
-  #[buffer] prime: JsBuffer,
-  #[buffer] private_key: JsBuffer,
-  #[buffer] their_public_key: JsBuffer,
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+  #[buffer] their_public_key: &[u8],
 ) -> Result<ToJsBuffer, AnyError> {
-  let pubkey: BigUint = BigUint::from_bytes_be(their_public_key.as_ref());
-  let privkey: BigUint = BigUint::from_bytes_be(private_key.as_ref());
-  let primei: BigUint = BigUint::from_bytes_be(prime.as_ref());
-  let shared_secret: BigUint = pubkey.modpow(&privkey, &primei);
+  Ok(dh.compute_secret(their_public_key)?.into())
+}
+
+#[op2]
+#[serde]
+pub fn op_node_dh_get_prime(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+) -> ToJsBuffer {
+  dh.prime().into()
+}
 
-  Ok(shared_secret.to_bytes_be().into())
+#[op2]
+#[serde]
+pub fn op_node_dh_get_generator(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+) -> ToJsBuffer {
+  dh.generator().into()
+}
+
+#[op2]
+#[serde]
+pub fn op_node_dh_get_private_key(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+) -> Option<ToJsBuffer> {
+  dh.private_key().map(Into::into)
+}
+
+#[op2]
+#[serde]
+pub fn op_node_dh_get_public_key(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+) -> Option<ToJsBuffer> {
+  dh.public_key().map(Into::into)
+}
+
+#[op2(fast)]
+pub fn op_node_dh_set_private_key(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+  #[buffer] private_key: &[u8],
+) {
+  dh.set_private_key(private_key);
+}
+
+#[op2(fast)]
+pub fn op_node_dh_set_public_key(
+  #[cppgc] dh: &dh::DiffieHellmanContext,
+  #[buffer] public_key: &[u8],
+) {
+  dh.set_public_key(public_key);
 }
 
 #[op2(async)]
 #[serde]
-pub async fn op_node_dh_generate_async(
+pub fn op_node_dh_generate_async(
+  state: &mut OpState,
   #[buffer] prime: Option<JsBuffer>,
   #[number] prime_len: usize,
   #[number] generator: usize,
-) -> Result<(ToJsBuffer, ToJsBuffer), AnyError> {
-  spawn_blocking(move || dh_generate(prime.as_deref(), prime_len, generator))
-    .await?
+) -> impl Future<Output = Result<(ToJsBuffer, ToJsBuffer), AnyError>> {
+  CpuPool::from_state(state)
+    .spawn(move || dh_generate(prime.as_deref(), prime_len, generator))
 }
 
+/// The largest range `crypto.randomInt()` accepts, `2^48 - 1`.
+const RANDOM_INT_MAX_RANGE: i64 = 0xFFFF_FFFF_FFFF;
+
+/// Returns a random integer in `min..max`. Both must be safe integers, which
+/// is checked in JS.
 #[op2(fast)]
-#[smi]
+#[number]
 pub fn op_node_random_int(
-  #[smi] min: i32,
-  #[smi] max: i32,
-) -> Result<i32, AnyError> {
+  #[number] min: i64,
+  #[number] max: i64,
+) -> Result<i64, AnyError> {
+  if min >= max || max - min > RANDOM_INT_MAX_RANGE {
+    return Err(range_error("Invalid randomInt() range"));
+  }
   let mut rng = rand::thread_rng();
   // Uniform distribution is required to avoid Modulo Bias
   // https://en.wikipedia.org/wiki/Fisher–Yates_shuffle#Modulo_bias
@@ -979,52 +982,126 @@ pub fn op_node_random_int(
   Ok(dist.sample(&mut rng))
 }
 
-#[allow(clippy::too_many_arguments)]
+fn random_fill(
+  buf: &mut [u8],
+  offset: usize,
+  size: usize,
+) -> Result<(), AnyError> {
+  let buf = offset
+    .checked_add(size)
+    .and_then(|end| buf.get_mut(offset..end))
+    .ok_or_else(|| {
+      range_error("The value of \"size + offset\" is out of range")
+    })?;
+  rand::thread_rng().fill(buf);
+  Ok(())
+}
+
+/// Fills `size` bytes of `buf` at `offset`. Unlike `op_node_generate_secret`
+/// this writes into the caller's buffer rather than a copy.
+#[op2(fast)]
+pub fn op_node_random_fill(
+  #[buffer] buf: &mut [u8],
+  #[number] offset: usize,
+  #[number] size: usize,
+) -> Result<(), AnyError> {
+  random_fill(buf, offset, size)
+}
+
+#[op2(async)]
+pub async fn op_node_random_fill_async(
+  #[buffer] mut buf: JsBuffer,
+  #[number] offset: usize,
+  #[number] size: usize,
+) -> Result<(), AnyError> {
+  spawn_blocking(move || random_fill(&mut buf, offset, size)).await?
+}
+
+/// Returns a version 4 UUID, or a version 7 one whose first 48 bits are the
+/// current Unix time in milliseconds. UUIDs created in the same millisecond
+/// are not guaranteed to be ordered.
+#[op2]
+#[string]
+pub fn op_node_random_uuid(v7: bool) -> String {
+  let mut bytes = [0u8; 16];
+  rand::thread_rng().fill(&mut bytes);
+  if v7 {
+    let millis = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map(|d| d.as_millis() as u64)
+      .unwrap_or(0);
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+  }
+  bytes[6] = (bytes[6] & 0x0f) | if v7 { 0x70 } else { 0x40 };
+  bytes[8] = (bytes[8] & 0x3f) | 0x80;
+  format_uuid(&bytes)
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+  let hex = data_encoding::HEXLOWER.encode(bytes);
+  format!(
+    "{}-{}-{}-{}-{}",
+    &hex[0..8],
+    &hex[8..12],
+    &hex[12..16],
+    &hex[16..20],
+    &hex[20..32]
+  )
+}
+
 fn scrypt(
   password: StringOrBuffer,
   salt: StringOrBuffer,
-  keylen: u32,
   cost: u32,
   block_size: u32,
   parallelization: u32,
-  _maxmem: u32,
+  maxmem: u64,
   output_buffer: &mut [u8],
 ) -> Result<(), AnyError> {
-  // Construct Params
+  let password = SecretBuffer::from(password);
+  if cost < 2 || !cost.is_power_of_two() {
+    return Err(type_error(
+      "Invalid scrypt parameter: N must be a power of 2",
+    ));
+  }
+  // Like OpenSSL, account for both the B and V blocks of the algorithm.
+  let mem = 128u64.checked_mul(block_size as u64).and_then(|r128| {
+    r128.checked_mul(cost as u64 + 2 + parallelization as u64)
+  });
+  if mem.map_or(true, |mem| mem > maxmem) {
+    return Err(range_error(
+      "Invalid scrypt parameter: memory limit exceeded",
+    ));
+  }
+  // The length of the params is only used for PHC strings, the derived key
+  // always fills the output buffer.
   let params = scrypt::Params::new(
-    cost as u8,
+    cost.trailing_zeros() as u8,
     block_size,
     parallelization,
-    keylen as usize,
+    scrypt::Params::RECOMMENDED_LEN,
   )
-  .unwrap();
-
-  // Call into scrypt
-  let res = scrypt::scrypt(&password, &salt, &params, output_buffer);
-  if res.is_ok() {
-    Ok(())
-  } else {
-    // TODO(lev): key derivation failed, so what?
-    Err(generic_error("scrypt key derivation failed"))
+  .map_err(|_| type_error("Invalid scrypt parameter"))?;
+  if output_buffer.is_empty() {
+    return Ok(());
   }
+  scrypt::scrypt(&password, &salt, &params, output_buffer)
+    .map_err(|_| generic_error("scrypt key derivation failed"))
 }
 
-#[allow(clippy::too_many_arguments)]
 #[op2]
 pub fn op_node_scrypt_sync(
   #[serde] password: StringOrBuffer,
   #[serde] salt: StringOrBuffer,
-  #[smi] keylen: u32,
   #[smi] cost: u32,
   #[smi] block_size: u32,
   #[smi] parallelization: u32,
-  #[smi] maxmem: u32,
+  #[number] maxmem: u64,
   #[anybuffer] output_buffer: &mut [u8],
 ) -> Result<(), AnyError> {
   scrypt(
     password,
     salt,
-    keylen,
     cost,
     block_size,
     parallelization,
@@ -1033,38 +1110,118 @@ pub fn op_node_scrypt_sync(
   )
 }
 
+#[allow(clippy::too_many_arguments)]
 #[op2(async)]
 #[serde]
-pub async fn op_node_scrypt_async(
+pub fn op_node_scrypt_async(
+  state: &mut OpState,
   #[serde] password: StringOrBuffer,
   #[serde] salt: StringOrBuffer,
-  #[smi] keylen: u32,
+  #[number] keylen: usize,
   #[smi] cost: u32,
   #[smi] block_size: u32,
   #[smi] parallelization: u32,
-  #[smi] maxmem: u32,
-) -> Result<ToJsBuffer, AnyError> {
-  spawn_blocking(move || {
-    let mut output_buffer = vec![0u8; keylen as usize];
-    let res = scrypt(
+  #[number] maxmem: u64,
+) -> impl Future<Output = Result<ToJsBuffer, AnyError>> {
+  CpuPool::from_state(state).spawn(move || {
+    let mut output_buffer = SecretBuffer::zeroed(keylen);
+    scrypt(
       password,
       salt,
-      keylen,
       cost,
       block_size,
       parallelization,
       maxmem,
       &mut output_buffer,
-    );
+    )?;
+    Ok(output_buffer.into())
+  })
+}
 
-    if res.is_ok() {
-      Ok(output_buffer.into())
-    } else {
-      // TODO(lev): rethrow the error?
-      Err(generic_error("scrypt failure"))
+/// The curves matched by `match_ec_curve!`, as listed by `crypto.getCurves()`.
+const CURVES: &[&str] = &[
+  "prime256v1",
+  "secp224r1",
+  "secp256k1",
+  "secp256r1",
+  "secp384r1",
+  "secp521r1",
+];
+
+/// Calls `$f::<C>($args)` with the curve `C` named `$curve`.
+macro_rules! match_ec_curve {
+  ($curve:expr, $f:ident($($arg:expr),*)) => {
+    match $curve {
+      "secp256k1" => $f::<k256::Secp256k1>($($arg),*),
+      "prime256v1" | "secp256r1" => $f::<NistP256>($($arg),*),
+      "secp384r1" => $f::<NistP384>($($arg),*),
+      "secp521r1" => $f::<NistP521>($($arg),*),
+      "secp224r1" => $f::<NistP224>($($arg),*),
+      curve => Err(type_error(format!("Unsupported curve: {}", curve))),
     }
-  })
-  .await?
+  };
+}
+
+/// Encodes a point as `uncompressed`, `compressed` or `hybrid`, which is
+/// uncompressed with the parity of y in its tag.
+fn encode_ec_point<C>(
+  public_key: &elliptic_curve::PublicKey<C>,
+  format: &str,
+) -> Vec<u8>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let mut encoded = public_key
+    .to_encoded_point(format == "compressed")
+    .as_bytes()
+    .to_vec();
+  if format == "hybrid" {
+    encoded[0] = 0x06 | (encoded[encoded.len() - 1] & 1);
+  }
+  encoded
+}
+
+fn decode_ec_point<C>(
+  encoded: &[u8],
+) -> Result<elliptic_curve::PublicKey<C>, AnyError>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let public_key = match encoded.first() {
+    // hybrid points are uncompressed points with another tag
+    Some(0x06 | 0x07) => {
+      let mut uncompressed = encoded.to_vec();
+      uncompressed[0] = 0x04;
+      let public_key =
+        elliptic_curve::PublicKey::<C>::from_sec1_bytes(&uncompressed);
+      // the tag has to match the parity of y
+      public_key.ok().filter(|public_key| {
+        encode_ec_point(public_key, "hybrid").as_slice() == encoded
+      })
+    }
+    _ => elliptic_curve::PublicKey::<C>::from_sec1_bytes(encoded).ok(),
+  };
+  public_key
+    .ok_or_else(|| type_error("Public key is not valid for specified curve"))
+}
+
+fn ecdh_encode_pubkey<C>(
+  pubkey: &[u8],
+  format: &str,
+) -> Result<Vec<u8>, AnyError>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  Ok(encode_ec_point(&decode_ec_point::<C>(pubkey)?, format))
 }
 
 #[op2]
@@ -1072,68 +1229,26 @@ pub async fn op_node_scrypt_async(
 pub fn op_node_ecdh_encode_pubkey(
   #[string] curve: &str,
   #[buffer] pubkey: &[u8],
-  compress: bool,
+  #[string] format: &str,
 ) -> Result<Vec<u8>, AnyError> {
-  use elliptic_curve::sec1::FromEncodedPoint;
-
-  match curve {
-    "secp256k1" => {
-      let pubkey =
-        elliptic_curve::PublicKey::<k256::Secp256k1>::from_encoded_point(
-          &elliptic_curve::sec1::EncodedPoint::<k256::Secp256k1>::from_bytes(
-            pubkey,
-          )?,
-        );
-      // CtOption does not expose its variants.
-      if pubkey.is_none().into() {
-        return Err(type_error("Invalid public key"));
-      }
-
-      let pubkey = pubkey.unwrap();
-
-      Ok(pubkey.to_encoded_point(compress).as_ref().to_vec())
-    }
-    "prime256v1" | "secp256r1" => {
-      let pubkey = elliptic_curve::PublicKey::<NistP256>::from_encoded_point(
-        &elliptic_curve::sec1::EncodedPoint::<NistP256>::from_bytes(pubkey)?,
-      );
-      // CtOption does not expose its variants.
-      if pubkey.is_none().into() {
-        return Err(type_error("Invalid public key"));
-      }
-
-      let pubkey = pubkey.unwrap();
-
-      Ok(pubkey.to_encoded_point(compress).as_ref().to_vec())
-    }
-    "secp384r1" => {
-      let pubkey = elliptic_curve::PublicKey::<NistP384>::from_encoded_point(
-        &elliptic_curve::sec1::EncodedPoint::<NistP384>::from_bytes(pubkey)?,
-      );
-      // CtOption does not expose its variants.
-      if pubkey.is_none().into() {
-        return Err(type_error("Invalid public key"));
-      }
-
-      let pubkey = pubkey.unwrap();
-
-      Ok(pubkey.to_encoded_point(compress).as_ref().to_vec())
-    }
-    "secp224r1" => {
-      let pubkey = elliptic_curve::PublicKey::<NistP224>::from_encoded_point(
-        &elliptic_curve::sec1::EncodedPoint::<NistP224>::from_bytes(pubkey)?,
-      );
-      // CtOption does not expose its variants.
-      if pubkey.is_none().into() {
-        return Err(type_error("Invalid public key"));
-      }
-
-      let pubkey = pubkey.unwrap();
+  match_ec_curve!(curve, ecdh_encode_pubkey(pubkey, format))
+}
 
-      Ok(pubkey.to_encoded_point(compress).as_ref().to_vec())
-    }
-    &_ => Err(type_error("Unsupported curve")),
-  }
+fn ecdh_generate_keys<C>(
+  pubbuf: &mut [u8],
+  privbuf: &mut [u8],
+  format: &str,
+) -> Result<(), AnyError>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let privkey = elliptic_curve::SecretKey::<C>::random(&mut rand::thread_rng());
+  pubbuf.copy_from_slice(&encode_ec_point(&privkey.public_key(), format));
+  privbuf.copy_from_slice(&privkey.to_bytes());
+  Ok(())
 }
 
 #[op2(fast)]
@@ -1143,44 +1258,29 @@ pub fn op_node_ecdh_generate_keys(
   #[buffer] privbuf: &mut [u8],
   #[string] format: &str,
 ) -> Result<(), AnyError> {
-  let mut rng = rand::thread_rng();
-  let compress = format == "compressed";
-  match curve {
-    "secp256k1" => {
-      let privkey =
-        elliptic_curve::SecretKey::<k256::Secp256k1>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_encoded_point(compress).as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-
-      Ok(())
-    }
-    "prime256v1" | "secp256r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP256>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_encoded_point(compress).as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-
-      Ok(())
-    }
-    "secp384r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP384>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_encoded_point(compress).as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-
-      Ok(())
-    }
-    "secp224r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP224>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_encoded_point(compress).as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-
-      Ok(())
-    }
-    &_ => Err(type_error(format!("Unsupported curve: {}", curve))),
-  }
+  match_ec_curve!(curve, ecdh_generate_keys(pubbuf, privbuf, format))
+}
+
+fn ecdh_compute_secret<C>(
+  this_priv: &[u8],
+  their_pub: &[u8],
+  secret: &mut [u8],
+) -> Result<(), AnyError>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let their_public_key = decode_ec_point::<C>(their_pub)?;
+  let this_private_key = elliptic_curve::SecretKey::<C>::from_slice(this_priv)
+    .map_err(|_| type_error("Invalid private key"))?;
+  let shared_secret = elliptic_curve::ecdh::diffie_hellman(
+    this_private_key.to_nonzero_scalar(),
+    their_public_key.as_affine(),
+  );
+  secret.copy_from_slice(shared_secret.raw_secret_bytes());
+  Ok(())
 }
 
 #[op2]
@@ -1190,76 +1290,28 @@ pub fn op_node_ecdh_compute_secret(
   #[buffer] their_pub: &mut [u8],
   #[buffer] secret: &mut [u8],
 ) -> Result<(), AnyError> {
-  match curve {
-    "secp256k1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<k256::Secp256k1>::from_sec1_bytes(
-          their_pub,
-        )
-        .expect("bad public key");
-      let this_private_key =
-        elliptic_curve::SecretKey::<k256::Secp256k1>::from_slice(
-          &this_priv.expect("must supply private key"),
-        )
-        .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    "prime256v1" | "secp256r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP256>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP256>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    "secp384r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP384>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP384>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    "secp224r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP224>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP224>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    &_ => todo!(),
-  }
+  let this_priv =
+    this_priv.ok_or_else(|| type_error("Private key is not set"))?;
+  match_ec_curve!(curve, ecdh_compute_secret(&this_priv, their_pub, secret))
+}
+
+fn ecdh_compute_public_key<C>(
+  privkey: &[u8],
+  pubkey: &mut [u8],
+) -> Result<(), AnyError>
+where
+  C: elliptic_curve::CurveArithmetic,
+  elliptic_curve::AffinePoint<C>:
+    elliptic_curve::sec1::FromEncodedPoint<C> + ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let this_private_key = elliptic_curve::SecretKey::<C>::from_slice(privkey)
+    .map_err(|_| type_error("Private key is not valid for specified curve"))?;
+  pubkey.copy_from_slice(&encode_ec_point(
+    &this_private_key.public_key(),
+    "uncompressed",
+  ));
+  Ok(())
 }
 
 #[op2(fast)]
@@ -1268,42 +1320,7 @@ pub fn op_node_ecdh_compute_public_key(
   #[buffer] privkey: &[u8],
   #[buffer] pubkey: &mut [u8],
 ) -> Result<(), AnyError> {
-  match curve {
-    "secp256k1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<k256::Secp256k1>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-
-      Ok(())
-    }
-    "prime256v1" | "secp256r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP256>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
-    }
-    "secp384r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP384>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
-    }
-    "secp224r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP224>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
-    }
-    &_ => todo!(),
-  }
+  match_ec_curve!(curve, ecdh_compute_public_key(privkey, pubkey))
 }
 
 #[inline]
@@ -1317,36 +1334,62 @@ pub fn op_node_gen_prime(#[number] size: usize) -> ToJsBuffer {
   gen_prime(size)
 }
 
+fn generate_prime(
+  size: usize,
+  safe: bool,
+  add: Option<&[u8]>,
+  rem: Option<&[u8]>,
+) -> Result<ToJsBuffer, AnyError> {
+  let options = primes::PrimeOptions {
+    safe,
+    add: add.map(num_bigint_dig::BigUint::from_bytes_be),
+    rem: rem.map(num_bigint_dig::BigUint::from_bytes_be),
+  };
+  let prime = primes::Prime::generate_with_options(size, &options)?;
+  Ok(prime.0.to_bytes_be().into())
+}
+
+#[op2]
+#[serde]
+pub fn op_node_generate_prime(
+  #[number] size: usize,
+  safe: bool,
+  #[buffer] add: Option<JsBuffer>,
+  #[buffer] rem: Option<JsBuffer>,
+) -> Result<ToJsBuffer, AnyError> {
+  generate_prime(size, safe, add.as_deref(), rem.as_deref())
+}
+
 #[op2(async)]
 #[serde]
-pub async fn op_node_gen_prime_async(
+pub fn op_node_generate_prime_async(
+  state: &mut OpState,
   #[number] size: usize,
+  safe: bool,
+  #[buffer] add: Option<JsBuffer>,
+  #[buffer] rem: Option<JsBuffer>,
+) -> impl Future<Output = Result<ToJsBuffer, AnyError>> {
+  CpuPool::from_state(state)
+    .spawn(move || generate_prime(size, safe, add.as_deref(), rem.as_deref()))
+}
+
+#[op2]
+#[serde]
+pub fn op_node_mod_pow(
+  #[buffer] base: &[u8],
+  #[buffer] exponent: &[u8],
+  #[buffer] modulus: &[u8],
+) -> Result<ToJsBuffer, AnyError> {
+  Ok(modular::mod_pow(base, exponent, modulus)?.into())
+}
+
+#[op2]
+#[serde]
+pub fn op_node_mod_inv(
+  #[buffer] value: &[u8],
+  #[buffer] modulus: &[u8],
 ) -> Result<ToJsBuffer, AnyError> {
-  Ok(spawn_blocking(move || gen_prime(size)).await?)
-}
-
-#[derive(serde::Serialize)]
-#[serde(tag = "type")]
-pub enum AsymmetricKeyDetails {
-  #[serde(rename = "rsa")]
-  #[serde(rename_all = "camelCase")]
-  Rsa {
-    modulus_length: usize,
-    public_exponent: V8BigInt,
-  },
-  #[serde(rename = "rsa-pss")]
-  #[serde(rename_all = "camelCase")]
-  RsaPss {
-    modulus_length: usize,
-    public_exponent: V8BigInt,
-    hash_algorithm: String,
-    salt_length: u32,
-  },
-  #[serde(rename = "ec")]
-  #[serde(rename_all = "camelCase")]
-  Ec { named_curve: String },
-  #[serde(rename = "dh")]
-  Dh,
+  Ok(modular::mod_inv(value, modulus)?.into())
 }
 
 // https://oidref.com/
@@ -1364,8 +1407,8 @@ pub const ID_SECP256R1_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
 pub const ID_SECP384R1_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.34");
-pub const ID_SECP521R1_OID: const_oid::ObjectIdentifier =
-  const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.35");
+pub const ID_SECP256K1_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.10");
 
 // Default HashAlgorithm for RSASSA-PSS-params (sha1)
 //
@@ -1485,220 +1528,56 @@ impl<'a> TryFrom<rsa::pkcs8::der::asn1::AnyRef<'a>>
   }
 }
 
-fn parse_private_key(
-  key: &[u8],
-  format: &str,
-  type_: &str,
-) -> Result<pkcs8::SecretDocument, AnyError> {
-  match format {
-    "pem" => {
-      let (_, doc) =
-        pkcs8::SecretDocument::from_pem(std::str::from_utf8(key).unwrap())?;
-      Ok(doc)
-    }
-    "der" => {
-      match type_ {
-        "pkcs8" => pkcs8::SecretDocument::from_pkcs8_der(key)
-          .map_err(|_| type_error("Invalid PKCS8 private key")),
-        "pkcs1" => pkcs8::SecretDocument::from_pkcs1_der(key)
-          .map_err(|_| type_error("Invalid PKCS1 private key")),
-        // TODO(@littledivy): sec1 type
-        _ => Err(type_error(format!("Unsupported key type: {}", type_))),
-      }
-    }
-    _ => Err(type_error(format!("Unsupported key format: {}", format))),
-  }
-}
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-#[op2]
-#[serde]
-pub fn op_node_create_private_key(
-  #[buffer] key: &[u8],
-  #[string] format: &str,
-  #[string] type_: &str,
-) -> Result<AsymmetricKeyDetails, AnyError> {
-  use rsa::pkcs1::der::Decode;
-
-  let doc = parse_private_key(key, format, type_)?;
-  let pk_info = pkcs8::PrivateKeyInfo::try_from(doc.as_bytes())?;
-
-  let alg = pk_info.algorithm.oid;
-
-  match alg {
-    RSA_ENCRYPTION_OID => {
-      let private_key =
-        rsa::pkcs1::RsaPrivateKey::from_der(pk_info.private_key)?;
-      let modulus_length = private_key.modulus.as_bytes().len() * 8;
-
-      Ok(AsymmetricKeyDetails::Rsa {
-        modulus_length,
-        public_exponent: BigInt::from_bytes_be(
-          num_bigint::Sign::Plus,
-          private_key.public_exponent.as_bytes(),
-        )
-        .into(),
-      })
-    }
-    DH_KEY_AGREEMENT_OID => Ok(AsymmetricKeyDetails::Dh),
-    RSASSA_PSS_OID => {
-      let params = PssPrivateKeyParameters::try_from(
-        pk_info
-          .algorithm
-          .parameters
-          .ok_or_else(|| type_error("Malformed parameters".to_string()))?,
-      )
-      .map_err(|_| type_error("Malformed parameters".to_string()))?;
-
-      let hash_alg = params.hash_algorithm;
-      let hash_algorithm = match hash_alg.oid {
-        ID_SHA1_OID => "sha1",
-        ID_SHA256_OID => "sha256",
-        ID_SHA384_OID => "sha384",
-        ID_SHA512_OID => "sha512",
-        _ => return Err(type_error("Unsupported hash algorithm")),
-      };
-
-      let private_key =
-        rsa::pkcs1::RsaPrivateKey::from_der(pk_info.private_key)?;
-      let modulus_length = private_key.modulus.as_bytes().len() * 8;
-      Ok(AsymmetricKeyDetails::RsaPss {
-        modulus_length,
-        public_exponent: BigInt::from_bytes_be(
-          num_bigint::Sign::Plus,
-          private_key.public_exponent.as_bytes(),
-        )
-        .into(),
-        hash_algorithm: hash_algorithm.to_string(),
-        salt_length: params.salt_length,
-      })
-    }
-    EC_OID => {
-      let named_curve = pk_info
-        .algorithm
-        .parameters_oid()
-        .map_err(|_| type_error("malformed parameters"))?;
-      let named_curve = match named_curve {
-        ID_SECP256R1_OID => "p256",
-        ID_SECP384R1_OID => "p384",
-        ID_SECP521R1_OID => "p521",
-        _ => return Err(type_error("Unsupported named curve")),
-      };
-
-      Ok(AsymmetricKeyDetails::Ec {
-        named_curve: named_curve.to_string(),
-      })
-    }
-    _ => Err(type_error("Unsupported algorithm")),
+  fn supported<C>() -> Result<&'static str, AnyError> {
+    Ok(std::any::type_name::<C>())
   }
-}
 
-fn parse_public_key(
-  key: &[u8],
-  format: &str,
-  type_: &str,
-) -> Result<pkcs8::Document, AnyError> {
-  match format {
-    "pem" => {
-      let (label, doc) =
-        pkcs8::Document::from_pem(std::str::from_utf8(key).unwrap())?;
-      if label != "PUBLIC KEY" {
-        return Err(type_error("Invalid PEM label"));
-      }
-      Ok(doc)
+  #[test]
+  fn listed_curves_are_supported() {
+    for curve in CURVES {
+      assert!(match_ec_curve!(*curve, supported()).is_ok(), "{curve}");
     }
-    "der" => match type_ {
-      "pkcs1" => pkcs8::Document::from_pkcs1_der(key)
-        .map_err(|_| type_error("Invalid PKCS1 public key")),
-      _ => Err(type_error(format!("Unsupported key type: {}", type_))),
-    },
-    _ => Err(type_error(format!("Unsupported key format: {}", format))),
+    assert!(match_ec_curve!("secp192r1", supported()).is_err());
   }
-}
 
-#[op2]
-#[serde]
-pub fn op_node_create_public_key(
-  #[buffer] key: &[u8],
-  #[string] format: &str,
-  #[string] type_: &str,
-) -> Result<AsymmetricKeyDetails, AnyError> {
-  let mut doc = None;
-
-  let pk_info = if type_ != "spki" {
-    doc.replace(parse_public_key(key, format, type_)?);
-    spki::SubjectPublicKeyInfoRef::try_from(doc.as_ref().unwrap().as_bytes())?
-  } else {
-    spki::SubjectPublicKeyInfoRef::try_from(key)?
-  };
-
-  let alg = pk_info.algorithm.oid;
-
-  match alg {
-    RSA_ENCRYPTION_OID => {
-      let public_key = rsa::pkcs1::RsaPublicKey::from_der(
-        pk_info.subject_public_key.raw_bytes(),
-      )?;
-      let modulus_length = public_key.modulus.as_bytes().len() * 8;
-
-      Ok(AsymmetricKeyDetails::Rsa {
-        modulus_length,
-        public_exponent: BigInt::from_bytes_be(
-          num_bigint::Sign::Plus,
-          public_key.public_exponent.as_bytes(),
-        )
-        .into(),
-      })
-    }
-    RSASSA_PSS_OID => {
-      let params = PssPrivateKeyParameters::try_from(
-        pk_info
-          .algorithm
-          .parameters
-          .ok_or_else(|| type_error("Malformed parameters".to_string()))?,
+  deno_core::extension!(modular_ops, ops = [op_node_mod_pow, op_node_mod_inv]);
+
+  #[test]
+  fn mod_ops_from_js() {
+    let mut runtime = deno_core::JsRuntime::new(deno_core::RuntimeOptions {
+      extensions: vec![modular_ops::init_ops()],
+      ..Default::default()
+    });
+    runtime
+      .execute_script(
+        "mod_ops_from_js",
+        r#"
+          const { op_node_mod_pow, op_node_mod_inv } = Deno.core.ops;
+          const assertBytes = (actual, expected) => {
+            if (actual.join() !== expected.join()) {
+              throw new Error(`Expected ${expected}, got ${actual}`);
+            }
+          };
+          const bytes = (...values) => new Uint8Array(values);
+          // 4 ^ 13 mod 497
+          assertBytes(op_node_mod_pow(bytes(4), bytes(13), bytes(1, 241)), [1, 189]);
+          assertBytes(op_node_mod_inv(bytes(3), bytes(11)), [4]);
+          assertBytes(op_node_mod_inv(bytes(3), bytes(16)), [11]);
+          for (const [value, modulus] of [[2, 4], [2, 0]]) {
+            let threw = false;
+            try {
+              op_node_mod_inv(bytes(value), bytes(modulus));
+            } catch {
+              threw = true;
+            }
+            if (!threw) throw new Error(`${value} mod ${modulus} inverted`);
+          }
+        "#,
       )
-      .map_err(|_| type_error("Malformed parameters".to_string()))?;
-
-      let hash_alg = params.hash_algorithm;
-      let hash_algorithm = match hash_alg.oid {
-        ID_SHA1_OID => "sha1",
-        ID_SHA256_OID => "sha256",
-        ID_SHA384_OID => "sha384",
-        ID_SHA512_OID => "sha512",
-        _ => return Err(type_error("Unsupported hash algorithm")),
-      };
-
-      let public_key = rsa::pkcs1::RsaPublicKey::from_der(
-        pk_info.subject_public_key.raw_bytes(),
-      )?;
-      let modulus_length = public_key.modulus.as_bytes().len() * 8;
-      Ok(AsymmetricKeyDetails::RsaPss {
-        modulus_length,
-        public_exponent: BigInt::from_bytes_be(
-          num_bigint::Sign::Plus,
-          public_key.public_exponent.as_bytes(),
-        )
-        .into(),
-        hash_algorithm: hash_algorithm.to_string(),
-        salt_length: params.salt_length,
-      })
-    }
-    EC_OID => {
-      let named_curve = pk_info
-        .algorithm
-        .parameters_oid()
-        .map_err(|_| type_error("malformed parameters"))?;
-      let named_curve = match named_curve {
-        ID_SECP256R1_OID => "p256",
-        ID_SECP384R1_OID => "p384",
-        ID_SECP521R1_OID => "p521",
-        _ => return Err(type_error("Unsupported named curve")),
-      };
-
-      Ok(AsymmetricKeyDetails::Ec {
-        named_curve: named_curve.to_string(),
-      })
-    }
-    DH_KEY_AGREEMENT_OID => Ok(AsymmetricKeyDetails::Dh),
-    _ => Err(type_error("Unsupported algorithm")),
+      .unwrap();
   }
 }
@@ -0,0 +1,144 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Constant-time modular arithmetic on big-endian byte arrays.
+//!
+//! Operands are widened to a fixed size picked from the length of the inputs,
+//! so the running time only depends on the size of the operands and never on
+//! their values.
+
+use crypto_bigint::modular::runtime_mod::DynResidue;
+use crypto_bigint::modular::runtime_mod::DynResidueParams;
+use crypto_bigint::Integer;
+use crypto_bigint::NonZero;
+use crypto_bigint::Uint;
+use crypto_bigint::U1024;
+use crypto_bigint::U2048;
+use crypto_bigint::U4096;
+use crypto_bigint::U8192;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+
+/// Calls `$f::<LIMBS>` with the smallest supported width that fits `$len`
+/// bytes.
+macro_rules! with_width {
+  ($len:expr, $f:ident($($arg:expr),*)) => {{
+    let len = $len;
+    if len <= U1024::BYTES {
+      $f::<{ U1024::LIMBS }>($($arg),*)
+    } else if len <= U2048::BYTES {
+      $f::<{ U2048::LIMBS }>($($arg),*)
+    } else if len <= U4096::BYTES {
+      $f::<{ U4096::LIMBS }>($($arg),*)
+    } else if len <= U8192::BYTES {
+      $f::<{ U8192::LIMBS }>($($arg),*)
+    } else {
+      Err(type_error("Operand is too large"))
+    }
+  }};
+}
+
+/// Computes `base ^ exponent mod modulus`. The modulus must be odd.
+///
+/// The result is left padded with zeros to the length of `modulus`.
+pub fn mod_pow(
+  base: &[u8],
+  exponent: &[u8],
+  modulus: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  let len = base.len().max(exponent.len()).max(modulus.len());
+  with_width!(len, mod_pow_sized(base, exponent, modulus))
+}
+
+/// Computes the multiplicative inverse of `value` modulo `modulus`, failing
+/// when it doesn't exist.
+///
+/// The result is left padded with zeros to the length of `modulus`.
+pub fn mod_inv(value: &[u8], modulus: &[u8]) -> Result<Vec<u8>, AnyError> {
+  let len = value.len().max(modulus.len());
+  with_width!(len, mod_inv_sized(value, modulus))
+}
+
+fn mod_pow_sized<const LIMBS: usize>(
+  base: &[u8],
+  exponent: &[u8],
+  modulus: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  let m = from_be_bytes::<LIMBS>(modulus);
+  if !bool::from(m.is_odd()) {
+    return Err(type_error("Modulus must be odd"));
+  }
+  let base = from_be_bytes::<LIMBS>(base).rem(&NonZero::new(m).unwrap());
+  let exponent = from_be_bytes::<LIMBS>(exponent);
+  let params = DynResidueParams::new(&m);
+  let result = DynResidue::new(&base, params).pow(&exponent).retrieve();
+  Ok(to_be_bytes(&result, modulus.len()))
+}
+
+fn mod_inv_sized<const LIMBS: usize>(
+  value: &[u8],
+  modulus: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  let m = Option::<NonZero<Uint<LIMBS>>>::from(NonZero::new(from_be_bytes::<
+    LIMBS,
+  >(modulus)))
+  .ok_or_else(|| type_error("Modulus must not be zero"))?;
+  let value = from_be_bytes::<LIMBS>(value).rem(&m);
+  let (result, exists) = value.inv_mod(&m);
+  if !bool::from(exists) {
+    return Err(type_error("Value is not invertible"));
+  }
+  Ok(to_be_bytes(&result, modulus.len()))
+}
+
+fn from_be_bytes<const LIMBS: usize>(bytes: &[u8]) -> Uint<LIMBS> {
+  let mut padded = vec![0; Uint::<LIMBS>::BYTES];
+  padded[Uint::<LIMBS>::BYTES - bytes.len()..].copy_from_slice(bytes);
+  Uint::from_be_slice(&padded)
+}
+
+fn to_be_bytes<const LIMBS: usize>(value: &Uint<LIMBS>, len: usize) -> Vec<u8> {
+  let bytes = value
+    .as_words()
+    .iter()
+    .rev()
+    .flat_map(|word| word.to_be_bytes())
+    .collect::<Vec<_>>();
+  bytes[bytes.len() - len..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_mod_pow() {
+    assert_eq!(mod_pow(&[4], &[13], &[1, 241]).unwrap(), vec![1, 189]);
+    assert_eq!(mod_pow(&[2], &[], &[7]).unwrap(), vec![1]);
+    // base larger than the modulus
+    assert_eq!(mod_pow(&[1, 0], &[2], &[0, 13]).unwrap(), vec![0, 3]);
+    assert!(mod_pow(&[2], &[3], &[8]).is_err());
+    assert!(mod_pow(&[2], &[3], &[]).is_err());
+    assert!(mod_pow(&[2], &[3], &[1; 2048]).is_err());
+  }
+
+  #[test]
+  fn test_mod_pow_wide() {
+    // 2^(8 * 300) mod (2^(8 * 300) + 1) == 2^(8 * 300)
+    let mut modulus = vec![0; 301];
+    modulus[0] = 1;
+    modulus[300] = 1;
+    let mut expected = vec![0; 301];
+    expected[0] = 1;
+    let exponent = (8u16 * 300).to_be_bytes();
+    assert_eq!(mod_pow(&[2], &exponent, &modulus).unwrap(), expected);
+  }
+
+  #[test]
+  fn test_mod_inv() {
+    assert_eq!(mod_inv(&[3], &[11]).unwrap(), vec![4]);
+    assert_eq!(mod_inv(&[3], &[16]).unwrap(), vec![11]);
+    assert_eq!(mod_inv(&[14], &[11]).unwrap(), vec![4]);
+    assert!(mod_inv(&[2], &[4]).is_err());
+    assert!(mod_inv(&[2], &[0]).is_err());
+  }
+}
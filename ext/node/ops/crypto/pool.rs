@@ -0,0 +1,108 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The thread pool the async crypto ops run their CPU-bound work on.
+//!
+//! `spawn_blocking` is meant for blocking I/O: its pool grows to hundreds of
+//! threads, so a burst of `pbkdf2()` or `generateKeyPair()` calls ends up
+//! with more busy threads than cores, and the thread running the event loop
+//! has to compete with all of them. This pool has one thread less than
+//! there are cores instead, and queues the work that doesn't fit.
+
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use deno_core::futures::channel::oneshot;
+use deno_core::OpState;
+use once_cell::sync::Lazy;
+
+static DEFAULT_POOL: Lazy<CpuPool> = Lazy::new(|| {
+  let num_threads = std::thread::available_parallelism()
+    .map(|n| n.get().saturating_sub(1).max(1))
+    .unwrap_or(1);
+  CpuPool::new(num_threads).expect("Failed to start the crypto thread pool")
+});
+
+/// A handle to a pool of threads for CPU-bound work, cheap to clone.
+///
+/// The ops use the pool put in their `OpState` if there is one, which lets
+/// embedders size it differently, and a pool shared by all the workers of
+/// the process otherwise.
+// The pool is shared with its threads, so this is an `Arc` even without the
+// `sync_fs` feature.
+#[allow(clippy::disallowed_types)]
+#[derive(Clone)]
+pub struct CpuPool(std::sync::Arc<rayon::ThreadPool>);
+
+impl CpuPool {
+  #[allow(clippy::disallowed_types)]
+  pub fn new(num_threads: usize) -> Result<Self, AnyError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(num_threads)
+      .thread_name(|index| format!("deno-crypto-{index}"))
+      .build()?;
+    Ok(Self(std::sync::Arc::new(pool)))
+  }
+
+  pub fn from_state(state: &OpState) -> Self {
+    state
+      .try_borrow::<Self>()
+      .cloned()
+      .unwrap_or_else(|| DEFAULT_POOL.clone())
+  }
+
+  /// Runs `f` on the pool. A panic in `f` is returned as an error, rather
+  /// than taking down the pool thread.
+  pub fn spawn<R: Send + 'static>(
+    &self,
+    f: impl FnOnce() -> Result<R, AnyError> + Send + 'static,
+  ) -> impl Future<Output = Result<R, AnyError>> {
+    let (sender, receiver) = oneshot::channel();
+    self.0.spawn(move || {
+      let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+      let _ = sender.send(result);
+    });
+    async move {
+      match receiver.await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err(generic_error("The crypto operation panicked")),
+        Err(_) => Err(generic_error("The crypto operation was cancelled")),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_core::futures::executor::block_on;
+  use deno_core::futures::future::join_all;
+
+  #[test]
+  fn spawn() {
+    let pool = CpuPool::new(2).unwrap();
+    let results =
+      block_on(join_all((0..8).map(|i| pool.spawn(move || Ok(i * i)))));
+    let results = results.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(results, vec![0, 1, 4, 9, 16, 25, 36, 49]);
+
+    let error = block_on(
+      pool.spawn(|| -> Result<(), AnyError> { Err(generic_error("failed")) }),
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "failed");
+  }
+
+  #[test]
+  fn spawn_panic() {
+    let pool = CpuPool::new(1).unwrap();
+    let error = block_on(pool.spawn(|| -> Result<(), AnyError> {
+      panic!("oops");
+    }))
+    .unwrap_err();
+    assert_eq!(error.to_string(), "The crypto operation panicked");
+    // the thread survived
+    assert_eq!(block_on(pool.spawn(|| Ok(1))).unwrap(), 1);
+  }
+}
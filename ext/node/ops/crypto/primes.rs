@@ -1,6 +1,11 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::error::range_error;
+use deno_core::error::AnyError;
 use num_bigint::BigInt;
+use num_bigint_dig::prime::probably_prime;
+use num_bigint_dig::BigUint;
+use num_bigint_dig::RandBigInt;
 use num_bigint_dig::RandPrime;
 use num_integer::Integer;
 use num_traits::One;
@@ -10,11 +15,75 @@ use std::ops::Deref;
 
 pub struct Prime(pub num_bigint_dig::BigUint);
 
+/// The options of `crypto.generatePrime`, as in `BN_generate_prime_ex`.
+#[derive(Default)]
+pub struct PrimeOptions {
+  /// Whether `(p - 1) / 2` must be prime as well.
+  pub safe: bool,
+  /// Makes `p % add == rem`, where `rem` defaults to 1, or 3 for safe primes.
+  pub add: Option<BigUint>,
+  pub rem: Option<BigUint>,
+}
+
 impl Prime {
   pub fn generate(n: usize) -> Self {
     let mut rng = rand::thread_rng();
     Self(rng.gen_prime(n))
   }
+
+  pub fn generate_with_options(
+    bits: usize,
+    options: &PrimeOptions,
+  ) -> Result<Self, AnyError> {
+    if bits < 2 || (bits == 2 && options.safe) {
+      return Err(range_error("invalid size"));
+    }
+    let constraint = match &options.add {
+      Some(add) => {
+        if add.is_zero() || add.bits() > bits {
+          return Err(range_error("invalid options.add"));
+        }
+        let rem = match &options.rem {
+          Some(rem) if rem >= add => {
+            return Err(range_error("invalid options.rem"));
+          }
+          Some(rem) => rem.clone(),
+          None => BigUint::from(if options.safe { 3u32 } else { 1 }),
+        };
+        Some((add, rem))
+      }
+      None => None,
+    };
+
+    let mut rng = rand::thread_rng();
+    loop {
+      let mut candidate =
+        rng.gen_biguint(bits) | (BigUint::one() << (bits - 1));
+      if let Some((add, rem)) = &constraint {
+        candidate = &candidate - (&candidate % *add) + rem;
+        if candidate.bits() != bits {
+          continue;
+        }
+      } else {
+        candidate |= BigUint::one();
+      }
+
+      // A single round rules out most candidates cheaply, the full test only
+      // runs on the ones likely to be picked.
+      if !probably_prime(&candidate, 1) {
+        continue;
+      }
+      let half = &candidate >> 1;
+      if options.safe && !probably_prime(&half, 1) {
+        continue;
+      }
+      if probably_prime(&candidate, 20)
+        && (!options.safe || probably_prime(&half, 20))
+      {
+        return Ok(Self(candidate));
+      }
+    }
+  }
 }
 
 impl From<&[u8]> for Prime {
@@ -304,6 +373,61 @@ mod tests {
     assert!(!is_probably_prime(&BigInt::parse_bytes(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890", 10).unwrap(), 16));
   }
 
+  #[test]
+  fn generate_with_options() {
+    let prime =
+      Prime::generate_with_options(64, &PrimeOptions::default()).unwrap();
+    assert_eq!(prime.bits(), 64);
+    assert!(probably_prime(&prime, 20));
+
+    let options = PrimeOptions {
+      safe: true,
+      ..Default::default()
+    };
+    let prime = Prime::generate_with_options(64, &options).unwrap();
+    assert_eq!(prime.bits(), 64);
+    assert!(probably_prime(&(&*prime >> 1), 20));
+
+    let add = BigUint::from(12u32);
+    let options = PrimeOptions {
+      safe: true,
+      add: Some(add.clone()),
+      rem: Some(BigUint::from(11u32)),
+    };
+    let prime = Prime::generate_with_options(32, &options).unwrap();
+    assert_eq!(&*prime % &add, BigUint::from(11u32));
+
+    let options = PrimeOptions {
+      safe: false,
+      add: Some(add.clone()),
+      rem: None,
+    };
+    let prime = Prime::generate_with_options(32, &options).unwrap();
+    assert_eq!(&*prime % &add, BigUint::one());
+  }
+
+  #[test]
+  fn generate_with_invalid_options() {
+    let error = |bits, options| {
+      Prime::generate_with_options(bits, &options)
+        .err()
+        .unwrap()
+        .to_string()
+    };
+    assert_eq!(error(1, PrimeOptions::default()), "invalid size");
+    let options = PrimeOptions {
+      add: Some(BigUint::from(256u32)),
+      ..Default::default()
+    };
+    assert_eq!(error(8, options), "invalid options.add");
+    let options = PrimeOptions {
+      add: Some(BigUint::from(12u32)),
+      rem: Some(BigUint::from(12u32)),
+      ..Default::default()
+    };
+    assert_eq!(error(8, options), "invalid options.rem");
+  }
+
   #[test]
   fn oeis_a014233() {
     // https://oeis.org/A014233
@@ -0,0 +1,191 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The SHAKE128 and SHAKE256 extendable-output functions of FIPS 202.
+
+const ROUND_CONSTANTS: [u64; 24] = [
+  0x0000000000000001,
+  0x0000000000008082,
+  0x800000000000808a,
+  0x8000000080008000,
+  0x000000000000808b,
+  0x0000000080000001,
+  0x8000000080008081,
+  0x8000000000008009,
+  0x000000000000008a,
+  0x0000000000000088,
+  0x0000000080008009,
+  0x000000008000000a,
+  0x000000008000808b,
+  0x800000000000008b,
+  0x8000000000008089,
+  0x8000000000008003,
+  0x8000000000008002,
+  0x8000000000000080,
+  0x000000000000800a,
+  0x800000008000000a,
+  0x8000000080008081,
+  0x8000000000008080,
+  0x0000000080000001,
+  0x8000000080008008,
+];
+
+/// The rotation of each lane in the rho step, indexed by `x + 5 * y`.
+const RHO: [u32; 25] = [
+  0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8,
+  18, 2, 61, 56, 14,
+];
+
+fn keccak_f(state: &mut [u64; 25]) {
+  for round_constant in ROUND_CONSTANTS {
+    // theta
+    let mut columns = [0u64; 5];
+    for (x, column) in columns.iter_mut().enumerate() {
+      *column =
+        state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+    }
+    for x in 0..5 {
+      let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+      for y in 0..5 {
+        state[x + 5 * y] ^= d;
+      }
+    }
+
+    // rho and pi
+    let mut lanes = [0u64; 25];
+    for x in 0..5 {
+      for y in 0..5 {
+        lanes[y + 5 * ((2 * x + 3 * y) % 5)] =
+          state[x + 5 * y].rotate_left(RHO[x + 5 * y]);
+      }
+    }
+
+    // chi
+    for y in 0..5 {
+      for x in 0..5 {
+        state[x + 5 * y] = lanes[x + 5 * y]
+          ^ (!lanes[(x + 1) % 5 + 5 * y] & lanes[(x + 2) % 5 + 5 * y]);
+      }
+    }
+
+    // iota
+    state[0] ^= round_constant;
+  }
+}
+
+#[derive(Clone)]
+pub struct Shake {
+  state: [u64; 25],
+  /// The number of bytes absorbed or squeezed per permutation.
+  rate: usize,
+  /// The position in the current block.
+  position: usize,
+}
+
+impl Shake {
+  pub fn shake128() -> Self {
+    Self::with_rate(168)
+  }
+
+  pub fn shake256() -> Self {
+    Self::with_rate(136)
+  }
+
+  fn with_rate(rate: usize) -> Self {
+    Self {
+      state: [0; 25],
+      rate,
+      position: 0,
+    }
+  }
+
+  /// The output length used when none is given, as in Node.js: the security
+  /// strength of the function.
+  pub fn default_output_length(&self) -> usize {
+    (200 - self.rate) / 2
+  }
+
+  fn xor_byte(&mut self, index: usize, byte: u8) {
+    self.state[index / 8] ^= (byte as u64) << (8 * (index % 8));
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    for byte in data {
+      self.xor_byte(self.position, *byte);
+      self.position += 1;
+      if self.position == self.rate {
+        keccak_f(&mut self.state);
+        self.position = 0;
+      }
+    }
+  }
+
+  pub fn finalize(mut self, output_length: usize) -> Box<[u8]> {
+    self.xor_byte(self.position, 0x1f);
+    self.xor_byte(self.rate - 1, 0x80);
+    keccak_f(&mut self.state);
+
+    let mut output = vec![0; output_length];
+    let mut position = 0;
+    for byte in output.iter_mut() {
+      if position == self.rate {
+        keccak_f(&mut self.state);
+        position = 0;
+      }
+      *byte = (self.state[position / 8] >> (8 * (position % 8))) as u8;
+      position += 1;
+    }
+    output.into_boxed_slice()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn digest(mut shake: Shake, data: &[u8], length: usize) -> String {
+    shake.update(data);
+    faster_hex::hex_string(&shake.finalize(length))
+  }
+
+  #[test]
+  fn shake128() {
+    assert_eq!(
+      digest(Shake::shake128(), b"", 32),
+      "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+    );
+    assert_eq!(
+      digest(Shake::shake128(), b"abc", 32),
+      "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"
+    );
+    assert_eq!(Shake::shake128().default_output_length(), 16);
+  }
+
+  #[test]
+  fn shake256() {
+    assert_eq!(
+      digest(Shake::shake256(), b"", 64),
+      "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f\
+       d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+    );
+    assert_eq!(
+      digest(Shake::shake256(), b"abc", 64),
+      "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739\
+       d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4"
+    );
+    assert_eq!(Shake::shake256().default_output_length(), 32);
+  }
+
+  #[test]
+  fn input_and_output_span_blocks() {
+    let data = vec![0x5a; 256];
+    let mut shake = Shake::shake128();
+    for chunk in data.chunks(13) {
+      shake.update(chunk);
+    }
+    let output = faster_hex::hex_string(&shake.finalize(200));
+    assert!(output.ends_with("8d314662bcf42899"));
+
+    let output = digest(Shake::shake256(), &data, 300);
+    assert!(output.ends_with("4a10b17f3803fe40"));
+  }
+}
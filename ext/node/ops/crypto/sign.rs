@@ -0,0 +1,606 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Signing and verification for `crypto.createSign()` and
+//! `crypto.createVerify()`.
+//!
+//! The ops take the digest of the data, computed with the hash contexts of
+//! `digest.rs`, and sign it with PKCS#1 v1.5 or PSS padding for RSA keys, or
+//! with ECDSA for P-256, P-384 and secp256k1 keys.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::StringOrBuffer;
+use deno_core::ToJsBuffer;
+use deno_crypto::SecretBuffer;
+use digest::Digest;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::der::Decode;
+use rsa::signature::hazmat::PrehashSigner;
+use rsa::signature::hazmat::PrehashVerifier;
+use rsa::traits::PublicKeyParts;
+use rsa::BigUint;
+use rsa::Pkcs1v15Sign;
+use rsa::Pss;
+use rsa::RsaPrivateKey;
+use rsa::RsaPublicKey;
+
+use super::EC_OID;
+use super::ID_SECP256K1_OID;
+use super::ID_SECP256R1_OID;
+use super::ID_SECP384R1_OID;
+use super::RSASSA_PSS_OID;
+use super::RSA_ENCRYPTION_OID;
+
+// https://nodejs.org/api/crypto.html#rsa_pkcs1_padding
+const RSA_PKCS1_PADDING: u32 = 1;
+const RSA_PKCS1_PSS_PADDING: u32 = 6;
+const RSA_PSS_SALTLEN_DIGEST: i32 = -1;
+/// `RSA_PSS_SALTLEN_MAX_SIGN` when signing, `RSA_PSS_SALTLEN_AUTO` when
+/// verifying.
+const RSA_PSS_SALTLEN_MAX: i32 = -2;
+
+#[derive(Clone, Copy)]
+enum DsaEncoding {
+  Der,
+  IeeeP1363,
+}
+
+impl DsaEncoding {
+  fn parse(dsa_encoding: &str) -> Result<Self, AnyError> {
+    match dsa_encoding {
+      "der" => Ok(Self::Der),
+      "ieee-p1363" => Ok(Self::IeeeP1363),
+      _ => Err(type_error(format!("Invalid dsaEncoding: {}", dsa_encoding))),
+    }
+  }
+}
+
+macro_rules! encode_ecdsa_signature {
+  ($signature:expr, $dsa_encoding:expr) => {
+    match $dsa_encoding {
+      DsaEncoding::Der => $signature.to_der().as_bytes().to_vec(),
+      DsaEncoding::IeeeP1363 => $signature.to_bytes().to_vec(),
+    }
+  };
+}
+
+/// Evaluates to `None` when the signature is malformed.
+macro_rules! decode_ecdsa_signature {
+  ($signature_type:ty, $signature:expr, $dsa_encoding:expr) => {
+    match $dsa_encoding {
+      DsaEncoding::Der => <$signature_type>::from_der($signature).ok(),
+      DsaEncoding::IeeeP1363 => <$signature_type>::from_slice($signature).ok(),
+    }
+  };
+}
+
+enum SigningKey {
+  Rsa(RsaPrivateKey),
+  RsaPss(RsaPrivateKey),
+  P256(p256::ecdsa::SigningKey),
+  P384(p384::ecdsa::SigningKey),
+  Secp256k1(k256::ecdsa::SigningKey),
+}
+
+enum VerifyingKey {
+  Rsa(RsaPublicKey),
+  RsaPss(RsaPublicKey),
+  P256(p256::ecdsa::VerifyingKey),
+  P384(p384::ecdsa::VerifyingKey),
+  Secp256k1(k256::ecdsa::VerifyingKey),
+}
+
+impl SigningKey {
+  fn from_pkcs8_der(der: &[u8]) -> Result<Self, AnyError> {
+    let pk_info = rsa::pkcs8::PrivateKeyInfo::try_from(der)?;
+    match pk_info.algorithm.oid {
+      RSA_ENCRYPTION_OID => Ok(Self::Rsa(RsaPrivateKey::from_pkcs1_der(
+        pk_info.private_key,
+      )?)),
+      RSASSA_PSS_OID => Ok(Self::RsaPss(RsaPrivateKey::from_pkcs1_der(
+        pk_info.private_key,
+      )?)),
+      EC_OID => {
+        let named_curve = pk_info
+          .algorithm
+          .parameters_oid()
+          .map_err(|_| type_error("malformed parameters"))?;
+        let ec_pk = sec1::EcPrivateKey::from_der(pk_info.private_key)?;
+        Self::from_ec_private_key(named_curve, ec_pk.private_key)
+      }
+      _ => Err(type_error("Unsupported signing key")),
+    }
+  }
+
+  fn from_sec1_der(der: &[u8]) -> Result<Self, AnyError> {
+    let ec_pk = sec1::EcPrivateKey::from_der(der)?;
+    match ec_pk.parameters {
+      Some(sec1::EcParameters::NamedCurve(named_curve)) => {
+        Self::from_ec_private_key(named_curve, ec_pk.private_key)
+      }
+      // https://datatracker.ietf.org/doc/html/rfc5915#section-3
+      //
+      // Though the ASN.1 indicates that
+      // the parameters field is OPTIONAL, implementations that conform to
+      // this document MUST always include the parameters field.
+      _ => Err(type_error("invalid ECPrivateKey params")),
+    }
+  }
+
+  fn from_ec_private_key(
+    named_curve: const_oid::ObjectIdentifier,
+    private_key: &[u8],
+  ) -> Result<Self, AnyError> {
+    match named_curve {
+      ID_SECP256R1_OID => Ok(Self::P256(p256::ecdsa::SigningKey::from_slice(
+        private_key,
+      )?)),
+      ID_SECP384R1_OID => Ok(Self::P384(p384::ecdsa::SigningKey::from_slice(
+        private_key,
+      )?)),
+      ID_SECP256K1_OID => Ok(Self::Secp256k1(
+        k256::ecdsa::SigningKey::from_slice(private_key)?,
+      )),
+      _ => Err(type_error("Unsupported named curve")),
+    }
+  }
+
+  /// Parses a private key of the given format, `pem` or `der`. `type_` is the
+  /// type of DER keys: `pkcs8`, `pkcs1` or `sec1`.
+  fn parse(key: &[u8], format: &str, type_: &str) -> Result<Self, AnyError> {
+    match format {
+      "pem" => {
+        let (label, doc) =
+          rsa::pkcs8::SecretDocument::from_pem(std::str::from_utf8(key)?)?;
+        Self::from_pem_document(label, doc.as_bytes())
+      }
+      "der" => match type_ {
+        "pkcs8" => Self::from_pkcs8_der(key),
+        "pkcs1" => Ok(Self::Rsa(RsaPrivateKey::from_pkcs1_der(key)?)),
+        "sec1" => Self::from_sec1_der(key),
+        _ => Err(type_error(format!("Unsupported key type: {}", type_))),
+      },
+      _ => Err(type_error(format!("Unsupported key format: {}", format))),
+    }
+  }
+
+  fn from_pem_document(label: &str, der: &[u8]) -> Result<Self, AnyError> {
+    match label {
+      "PRIVATE KEY" => Self::from_pkcs8_der(der),
+      "RSA PRIVATE KEY" => Ok(Self::Rsa(RsaPrivateKey::from_pkcs1_der(der)?)),
+      "EC PRIVATE KEY" => Self::from_sec1_der(der),
+      _ => Err(type_error("Invalid PEM label")),
+    }
+  }
+
+  fn verifying_key(&self) -> VerifyingKey {
+    match self {
+      Self::Rsa(key) => VerifyingKey::Rsa(key.to_public_key()),
+      Self::RsaPss(key) => VerifyingKey::RsaPss(key.to_public_key()),
+      Self::P256(key) => VerifyingKey::P256(*key.verifying_key()),
+      Self::P384(key) => VerifyingKey::P384(*key.verifying_key()),
+      Self::Secp256k1(key) => VerifyingKey::Secp256k1(*key.verifying_key()),
+    }
+  }
+
+  fn sign(
+    &self,
+    digest: &[u8],
+    digest_type: &str,
+    padding: u32,
+    salt_length: i32,
+    dsa_encoding: DsaEncoding,
+  ) -> Result<Vec<u8>, AnyError> {
+    match self {
+      Self::Rsa(key) if padding == RSA_PKCS1_PADDING => {
+        let scheme = pkcs1v15_scheme(digest_type)?;
+        Ok(key.sign(scheme, digest)?)
+      }
+      Self::Rsa(key) | Self::RsaPss(key) => {
+        check_rsa_padding(padding)?;
+        let salt_length = match salt_length {
+          RSA_PSS_SALTLEN_MAX => max_salt_length(key, digest.len())?,
+          salt_length => resolve_salt_length(salt_length, digest.len())?,
+        };
+        let scheme = pss_scheme(digest_type, salt_length)?;
+        Ok(key.sign_with_rng(&mut rand::thread_rng(), scheme, digest)?)
+      }
+      Self::P256(key) => {
+        let signature: p256::ecdsa::Signature = key.sign_prehash(digest)?;
+        Ok(encode_ecdsa_signature!(signature, dsa_encoding))
+      }
+      Self::P384(key) => {
+        let signature: p384::ecdsa::Signature = key.sign_prehash(digest)?;
+        Ok(encode_ecdsa_signature!(signature, dsa_encoding))
+      }
+      Self::Secp256k1(key) => {
+        let signature: k256::ecdsa::Signature = key.sign_prehash(digest)?;
+        Ok(encode_ecdsa_signature!(signature, dsa_encoding))
+      }
+    }
+  }
+}
+
+impl VerifyingKey {
+  fn from_spki_der(der: &[u8]) -> Result<Self, AnyError> {
+    let spki = spki::SubjectPublicKeyInfoRef::try_from(der)?;
+    let public_key = spki.subject_public_key.raw_bytes();
+    match spki.algorithm.oid {
+      RSA_ENCRYPTION_OID => {
+        Ok(Self::Rsa(RsaPublicKey::from_pkcs1_der(public_key)?))
+      }
+      RSASSA_PSS_OID => {
+        Ok(Self::RsaPss(RsaPublicKey::from_pkcs1_der(public_key)?))
+      }
+      EC_OID => {
+        let named_curve = spki
+          .algorithm
+          .parameters_oid()
+          .map_err(|_| type_error("malformed parameters"))?;
+        match named_curve {
+          ID_SECP256R1_OID => Ok(Self::P256(
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)?,
+          )),
+          ID_SECP384R1_OID => Ok(Self::P384(
+            p384::ecdsa::VerifyingKey::from_sec1_bytes(public_key)?,
+          )),
+          ID_SECP256K1_OID => Ok(Self::Secp256k1(
+            k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)?,
+          )),
+          _ => Err(type_error("Unsupported named curve")),
+        }
+      }
+      _ => Err(type_error("Unsupported verifying key")),
+    }
+  }
+
+  /// Parses a public key, or the public key of a private key, of the given
+  /// format, `pem` or `der`. `type_` is the type of DER keys: `spki`,
+  /// `pkcs1`, `pkcs8` or `sec1`.
+  fn parse(key: &[u8], format: &str, type_: &str) -> Result<Self, AnyError> {
+    match format {
+      "pem" => {
+        let (label, doc) =
+          rsa::pkcs8::SecretDocument::from_pem(std::str::from_utf8(key)?)?;
+        match label {
+          "PUBLIC KEY" => Self::from_spki_der(doc.as_bytes()),
+          "RSA PUBLIC KEY" => {
+            Ok(Self::Rsa(RsaPublicKey::from_pkcs1_der(doc.as_bytes())?))
+          }
+          label => Ok(
+            SigningKey::from_pem_document(label, doc.as_bytes())?
+              .verifying_key(),
+          ),
+        }
+      }
+      "der" => match type_ {
+        "spki" => Self::from_spki_der(key),
+        "pkcs1" => match RsaPublicKey::from_pkcs1_der(key) {
+          Ok(key) => Ok(Self::Rsa(key)),
+          Err(_) => Ok(Self::Rsa(
+            RsaPrivateKey::from_pkcs1_der(key)?.to_public_key(),
+          )),
+        },
+        _ => Ok(SigningKey::parse(key, format, type_)?.verifying_key()),
+      },
+      _ => Err(type_error(format!("Unsupported key format: {}", format))),
+    }
+  }
+
+  fn verify(
+    &self,
+    digest: &[u8],
+    digest_type: &str,
+    signature: &[u8],
+    padding: u32,
+    salt_length: i32,
+    dsa_encoding: DsaEncoding,
+  ) -> Result<bool, AnyError> {
+    match self {
+      Self::Rsa(key) if padding == RSA_PKCS1_PADDING => {
+        let scheme = pkcs1v15_scheme(digest_type)?;
+        Ok(key.verify(scheme, digest, signature).is_ok())
+      }
+      Self::Rsa(key) | Self::RsaPss(key) => {
+        check_rsa_padding(padding)?;
+        let salt_length = match salt_length {
+          RSA_PSS_SALTLEN_MAX => {
+            let Some(salt_length) =
+              recover_salt_length(key, digest_type, signature)?
+            else {
+              return Ok(false);
+            };
+            salt_length
+          }
+          salt_length => resolve_salt_length(salt_length, digest.len())?,
+        };
+        let scheme = pss_scheme(digest_type, salt_length)?;
+        Ok(key.verify(scheme, digest, signature).is_ok())
+      }
+      Self::P256(key) => {
+        let Some(signature) = decode_ecdsa_signature!(
+          p256::ecdsa::Signature,
+          signature,
+          dsa_encoding
+        ) else {
+          return Ok(false);
+        };
+        Ok(key.verify_prehash(digest, &signature).is_ok())
+      }
+      Self::P384(key) => {
+        let Some(signature) = decode_ecdsa_signature!(
+          p384::ecdsa::Signature,
+          signature,
+          dsa_encoding
+        ) else {
+          return Ok(false);
+        };
+        Ok(key.verify_prehash(digest, &signature).is_ok())
+      }
+      Self::Secp256k1(key) => {
+        let Some(signature) = decode_ecdsa_signature!(
+          k256::ecdsa::Signature,
+          signature,
+          dsa_encoding
+        ) else {
+          return Ok(false);
+        };
+        Ok(key.verify_prehash(digest, &signature).is_ok())
+      }
+    }
+  }
+}
+
+macro_rules! match_rsa_digest {
+  ($digest_type:expr, $digest:ident => $body:expr) => {
+    match $digest_type {
+      "sha1" => {
+        type $digest = sha1::Sha1;
+        $body
+      }
+      "sha224" => {
+        type $digest = sha2::Sha224;
+        $body
+      }
+      "sha256" => {
+        type $digest = sha2::Sha256;
+        $body
+      }
+      "sha384" => {
+        type $digest = sha2::Sha384;
+        $body
+      }
+      "sha512" => {
+        type $digest = sha2::Sha512;
+        $body
+      }
+      _ => {
+        return Err(type_error(format!(
+          "Unknown digest algorithm: {}",
+          $digest_type
+        )))
+      }
+    }
+  };
+}
+
+// The DigestInfo prefix of SHA-1 digests, as `sha-1` doesn't provide the OID.
+//
+// https://www.rfc-editor.org/rfc/rfc8017#section-9.2
+const SHA1_DIGEST_INFO_PREFIX: [u8; 15] = [
+  0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00,
+  0x04, 0x14,
+];
+
+fn pkcs1v15_scheme(digest_type: &str) -> Result<Pkcs1v15Sign, AnyError> {
+  match digest_type {
+    "sha1" => Ok(Pkcs1v15Sign {
+      hash_len: Some(20),
+      prefix: Box::new(SHA1_DIGEST_INFO_PREFIX),
+    }),
+    "sha224" => Ok(Pkcs1v15Sign::new::<sha2::Sha224>()),
+    "sha256" => Ok(Pkcs1v15Sign::new::<sha2::Sha256>()),
+    "sha384" => Ok(Pkcs1v15Sign::new::<sha2::Sha384>()),
+    "sha512" => Ok(Pkcs1v15Sign::new::<sha2::Sha512>()),
+    _ => Err(type_error(format!(
+      "Unknown digest algorithm: {}",
+      digest_type
+    ))),
+  }
+}
+
+/// A PSS scheme whose MGF1 hash is the digest of the data.
+fn pss_scheme(digest_type: &str, salt_length: usize) -> Result<Pss, AnyError> {
+  Ok(match_rsa_digest!(digest_type, D => Pss::new_with_salt::<D>(salt_length)))
+}
+
+fn check_rsa_padding(padding: u32) -> Result<(), AnyError> {
+  match padding {
+    RSA_PKCS1_PADDING | RSA_PKCS1_PSS_PADDING => Ok(()),
+    _ => Err(type_error(format!("Unsupported padding: {}", padding))),
+  }
+}
+
+fn resolve_salt_length(
+  salt_length: i32,
+  digest_length: usize,
+) -> Result<usize, AnyError> {
+  match salt_length {
+    RSA_PSS_SALTLEN_DIGEST => Ok(digest_length),
+    salt_length => usize::try_from(salt_length)
+      .map_err(|_| type_error(format!("Invalid salt length: {}", salt_length))),
+  }
+}
+
+/// The length of the encoded message of a PSS signature.
+/// https://www.rfc-editor.org/rfc/rfc8017#section-8.1.1
+fn pss_encoded_length(key: &impl PublicKeyParts) -> (usize, usize) {
+  let em_bits = key.n().bits() - 1;
+  ((em_bits + 7) / 8, em_bits)
+}
+
+fn max_salt_length(
+  key: &RsaPrivateKey,
+  digest_length: usize,
+) -> Result<usize, AnyError> {
+  let (em_len, _) = pss_encoded_length(key);
+  em_len
+    .checked_sub(digest_length + 2)
+    .ok_or_else(|| type_error("Key is too small for the digest"))
+}
+
+/// Recovers the salt length of a PSS signature, for `RSA_PSS_SALTLEN_AUTO`.
+/// Returns `None` when the signature isn't a PSS signature of `key`.
+///
+/// https://www.rfc-editor.org/rfc/rfc8017#section-9.1.2
+fn recover_salt_length(
+  key: &RsaPublicKey,
+  digest_type: &str,
+  signature: &[u8],
+) -> Result<Option<usize>, AnyError> {
+  fn recover<D: Digest>(key: &RsaPublicKey, signature: &[u8]) -> Option<usize> {
+    let signature = BigUint::from_bytes_be(signature);
+    if signature >= *key.n() {
+      return None;
+    }
+    let (em_len, em_bits) = pss_encoded_length(key);
+    let message = rsa::hazmat::rsa_encrypt(key, &signature).ok()?;
+    let message = message.to_bytes_be();
+    if message.len() > em_len {
+      return None;
+    }
+    let mut em = vec![0; em_len - message.len()];
+    em.extend_from_slice(&message);
+
+    let h_len = <D as Digest>::output_size();
+    if em_len < h_len + 2 || em[em_len - 1] != 0xbc {
+      return None;
+    }
+    let (db, h) = em[..em_len - 1].split_at_mut(em_len - h_len - 1);
+    for (counter, chunk) in db.chunks_mut(h_len).enumerate() {
+      let mask = D::new()
+        .chain_update(&*h)
+        .chain_update((counter as u32).to_be_bytes())
+        .finalize();
+      for (byte, mask) in chunk.iter_mut().zip(mask) {
+        *byte ^= mask;
+      }
+    }
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    let separator = db.iter().position(|byte| *byte != 0)?;
+    (db[separator] == 0x01).then(|| db.len() - separator - 1)
+  }
+
+  Ok(match_rsa_digest!(digest_type, D => recover::<D>(key, signature)))
+}
+
+/// Signs `digest`, the `digest_type` hash of the data. `padding` and
+/// `salt_length` only apply to RSA keys and `dsa_encoding` to EC keys.
+#[op2]
+#[serde]
+#[allow(clippy::too_many_arguments)]
+pub fn op_node_sign(
+  #[buffer] digest: &[u8],
+  #[string] digest_type: &str,
+  #[serde] key: StringOrBuffer,
+  #[string] type_: &str,
+  #[string] format: &str,
+  #[smi] padding: u32,
+  #[smi] salt_length: i32,
+  #[string] dsa_encoding: &str,
+) -> Result<ToJsBuffer, AnyError> {
+  let dsa_encoding = DsaEncoding::parse(dsa_encoding)?;
+  let key = SecretBuffer::from(key);
+  let key = SigningKey::parse(&key, format, type_)?;
+  let signature =
+    key.sign(digest, digest_type, padding, salt_length, dsa_encoding)?;
+  Ok(signature.into())
+}
+
+/// Verifies `signature` of `digest`, like [`op_node_sign`].
+#[op2]
+#[allow(clippy::too_many_arguments)]
+pub fn op_node_verify(
+  #[buffer] digest: &[u8],
+  #[string] digest_type: &str,
+  #[serde] key: StringOrBuffer,
+  #[string] type_: &str,
+  #[string] format: &str,
+  #[buffer] signature: &[u8],
+  #[smi] padding: u32,
+  #[smi] salt_length: i32,
+  #[string] dsa_encoding: &str,
+) -> Result<bool, AnyError> {
+  let dsa_encoding = DsaEncoding::parse(dsa_encoding)?;
+  let key = VerifyingKey::parse(&key, format, type_)?;
+  key.verify(
+    digest,
+    digest_type,
+    signature,
+    padding,
+    salt_length,
+    dsa_encoding,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn recover_pss_salt_length() {
+    let key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+    let key = SigningKey::Rsa(key);
+    let digest = sha2::Sha256::digest(b"data");
+    for salt_length in [0, 20, RSA_PSS_SALTLEN_DIGEST, RSA_PSS_SALTLEN_MAX] {
+      let signature = key
+        .sign(
+          &digest,
+          "sha256",
+          RSA_PKCS1_PSS_PADDING,
+          salt_length,
+          DsaEncoding::Der,
+        )
+        .unwrap();
+      let VerifyingKey::Rsa(public_key) = key.verifying_key() else {
+        unreachable!();
+      };
+      let expected = match salt_length {
+        RSA_PSS_SALTLEN_DIGEST => 32,
+        RSA_PSS_SALTLEN_MAX => 128 - 32 - 2,
+        salt_length => salt_length as usize,
+      };
+      assert_eq!(
+        recover_salt_length(&public_key, "sha256", &signature).unwrap(),
+        Some(expected)
+      );
+    }
+  }
+
+  #[test]
+  fn ecdsa_signature_encodings() {
+    let key = SigningKey::P256(p256::ecdsa::SigningKey::random(
+      &mut rand::thread_rng(),
+    ));
+    let digest = sha2::Sha256::digest(b"data");
+    for dsa_encoding in [DsaEncoding::Der, DsaEncoding::IeeeP1363] {
+      let signature = key
+        .sign(&digest, "sha256", RSA_PKCS1_PADDING, 0, dsa_encoding)
+        .unwrap();
+      let verifying_key = key.verifying_key();
+      assert!(verifying_key
+        .verify(
+          &digest,
+          "sha256",
+          &signature,
+          RSA_PKCS1_PADDING,
+          0,
+          dsa_encoding
+        )
+        .unwrap());
+    }
+  }
+}
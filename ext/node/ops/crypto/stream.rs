@@ -0,0 +1,200 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Feeding the crypto contexts from resources, for `updateFromStream()`.
+//!
+//! The chunks are read from the resource in Rust, so piping a large file
+//! through a hash or a cipher doesn't take an op call per chunk. A stream
+//! that isn't backed by a resource already is turned into one in JS, whose
+//! channel applies backpressure to the stream.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::AsyncResult;
+use deno_core::BufView;
+use deno_core::OpState;
+use deno_core::Resource;
+use deno_core::ResourceId;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The resource to read `rid` from, taken out of the table if it should be
+/// closed once read.
+pub fn source(
+  state: &mut OpState,
+  rid: ResourceId,
+  auto_close: bool,
+) -> Result<Source, AnyError> {
+  let resource = if auto_close {
+    state.resource_table.take_any(rid)?
+  } else {
+    state.resource_table.get_any(rid)?
+  };
+  Ok(Source {
+    resource,
+    auto_close,
+  })
+}
+
+pub struct Source {
+  resource: Rc<dyn Resource>,
+  auto_close: bool,
+}
+
+impl Source {
+  async fn read(&self, limit: usize) -> Result<BufView, AnyError> {
+    self.resource.clone().read(limit).await
+  }
+
+  /// Reads the source to its end, passing each chunk to `update`.
+  pub async fn pump(
+    self,
+    mut update: impl FnMut(&[u8]) -> Result<(), AnyError>,
+  ) -> Result<(), AnyError> {
+    let result = async {
+      loop {
+        let chunk = self.read(CHUNK_SIZE).await?;
+        if chunk.is_empty() {
+          return Ok(());
+        }
+        update(&chunk)?;
+      }
+    }
+    .await;
+    self.close();
+    result
+  }
+
+  fn close(&self) {
+    if self.auto_close {
+      self.resource.clone().close();
+    }
+  }
+}
+
+type Update = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, AnyError>>;
+
+/// The output of a cipher or decipher context updated with the chunks of a
+/// source, as a resource. The source is only read when the output is, so a
+/// slow reader of the output slows down the source too.
+pub struct CipherStream {
+  source: Source,
+  /// Dropped at the end of the source, which releases the context for
+  /// `final()`.
+  update: RefCell<Option<Update>>,
+  /// The output that didn't fit in the last read.
+  pending: RefCell<Vec<u8>>,
+}
+
+impl CipherStream {
+  pub fn new(source: Source, update: Update) -> Self {
+    Self {
+      source,
+      update: RefCell::new(Some(update)),
+      pending: RefCell::new(Vec::new()),
+    }
+  }
+
+  async fn read_output(&self, limit: usize) -> Result<BufView, AnyError> {
+    let limit = limit.max(1);
+    loop {
+      {
+        let mut pending = self.pending.borrow_mut();
+        if !pending.is_empty() {
+          let length = pending.len().min(limit);
+          let rest = pending.split_off(length);
+          return Ok(std::mem::replace(&mut *pending, rest).into());
+        }
+      }
+      if self.update.borrow().is_none() {
+        return Ok(BufView::empty());
+      }
+      let chunk = self.source.read(limit).await?;
+      if chunk.is_empty() {
+        self.update.borrow_mut().take();
+        self.source.close();
+        return Ok(BufView::empty());
+      }
+      let update = self.update.borrow();
+      let Some(update) = update.as_ref() else {
+        // closed while reading
+        return Ok(BufView::empty());
+      };
+      // block modes keep partial blocks back, so the output can be empty
+      *self.pending.borrow_mut() = update(&chunk)?;
+    }
+  }
+}
+
+impl Resource for CipherStream {
+  fn name(&self) -> Cow<str> {
+    "cipherStream".into()
+  }
+
+  fn read(self: Rc<Self>, limit: usize) -> AsyncResult<BufView> {
+    Box::pin(async move { self.read_output(limit).await })
+  }
+
+  fn close(self: Rc<Self>) {
+    if self.update.borrow_mut().take().is_some() {
+      self.source.close();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_core::futures::executor::block_on;
+
+  struct Chunks(RefCell<Vec<&'static [u8]>>);
+
+  impl Resource for Chunks {
+    fn read(self: Rc<Self>, _limit: usize) -> AsyncResult<BufView> {
+      let chunk = self.0.borrow_mut().pop().unwrap_or_default();
+      Box::pin(async move { Ok(BufView::from(chunk.to_vec())) })
+    }
+  }
+
+  fn chunks(chunks: &[&'static [u8]]) -> Source {
+    let mut chunks = chunks.to_vec();
+    chunks.reverse();
+    Source {
+      resource: Rc::new(Chunks(RefCell::new(chunks))),
+      auto_close: false,
+    }
+  }
+
+  #[test]
+  fn pump() {
+    let mut read = Vec::new();
+    block_on(chunks(&[b"abc", b"de"]).pump(|chunk| {
+      read.extend_from_slice(chunk);
+      Ok(())
+    }))
+    .unwrap();
+    assert_eq!(read, b"abcde");
+  }
+
+  #[test]
+  fn cipher_stream() {
+    // outputs each chunk twice
+    let stream = Rc::new(CipherStream::new(
+      chunks(&[b"abc", b"de"]),
+      Box::new(|chunk| Ok([chunk, chunk].concat())),
+    ));
+    let mut output = Vec::new();
+    loop {
+      let chunk = block_on(stream.read_output(4)).unwrap();
+      if chunk.is_empty() {
+        break;
+      }
+      assert!(chunk.len() <= 4);
+      output.extend_from_slice(&chunk);
+    }
+    assert_eq!(output, b"abcabcdede");
+    assert!(stream.update.borrow().is_none());
+  }
+}
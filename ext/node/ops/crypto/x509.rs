@@ -1,38 +1,48 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::net::IpAddr;
+
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::v8;
+use deno_core::ToJsBuffer;
 
 use x509_parser::der_parser::asn1_rs::Any;
 use x509_parser::der_parser::asn1_rs::Tag;
 use x509_parser::der_parser::oid::Oid;
 use x509_parser::extensions;
+use x509_parser::extensions::GeneralName;
 use x509_parser::pem;
 use x509_parser::prelude::*;
 
 use digest::Digest;
 
 pub(crate) struct Certificate {
-  _buf: Vec<u8>,
-  pem: Option<pem::Pem>,
+  /// The DER encoding of `cert`, which borrows from it.
+  der: Vec<u8>,
   cert: X509Certificate<'static>,
 }
 
 impl Certificate {
-  fn fingerprint<D: Digest>(&self) -> Option<String> {
-    self.pem.as_ref().map(|pem| {
-      let mut hasher = D::new();
-      hasher.update(&pem.contents);
-      let bytes = hasher.finalize();
-      // OpenSSL returns colon separated upper case hex values.
-      let mut hex = String::with_capacity(bytes.len() * 2);
-      for byte in bytes {
-        hex.push_str(&format!("{:02X}:", byte));
-      }
-      hex.pop();
-      hex
-    })
+  fn fingerprint<D: Digest>(&self) -> String {
+    let mut hasher = D::new();
+    hasher.update(&self.der);
+    let bytes = hasher.finalize();
+    // OpenSSL returns colon separated upper case hex values.
+    let mut hex = String::with_capacity(bytes.len() * 3);
+    for byte in bytes {
+      hex.push_str(&format!("{:02X}:", byte));
+    }
+    hex.pop();
+    hex
+  }
+
+  fn subject_alt_names(&self) -> &[GeneralName<'_>] {
+    match self.subject_alternative_name() {
+      Ok(Some(ext)) => &ext.value.general_names,
+      _ => &[],
+    }
   }
 }
 
@@ -49,25 +59,20 @@ pub fn op_node_x509_parse<'s>(
   scope: &'s mut v8::HandleScope,
   #[buffer] buf: &[u8],
 ) -> Result<v8::Local<'s, v8::Object>, AnyError> {
-  let pem = match pem::parse_x509_pem(buf) {
-    Ok((_, pem)) => Some(pem),
-    Err(_) => None,
+  let der = match pem::parse_x509_pem(buf) {
+    Ok((_, pem)) => pem.contents,
+    Err(_) => buf.to_vec(),
   };
 
-  let cert = pem
-    .as_ref()
-    .map(|pem| pem.parse_x509())
-    .unwrap_or_else(|| X509Certificate::from_der(buf).map(|(_, cert)| cert))?;
-
-  let cert = Certificate {
-    _buf: buf.to_vec(),
-    // SAFETY: Extending the lifetime of the certificate. Backing buffer is
-    // owned by the resource.
-    cert: unsafe { std::mem::transmute(cert) },
-    pem,
+  let (_, cert) = X509Certificate::from_der(&der)?;
+  // SAFETY: Extending the lifetime of the certificate. The heap allocation of
+  // `der` it borrows from is owned by the resource and never modified.
+  let cert = unsafe {
+    std::mem::transmute::<X509Certificate<'_>, X509Certificate<'static>>(cert)
   };
 
-  let obj = deno_core::cppgc::make_cppgc_object(scope, cert);
+  let obj =
+    deno_core::cppgc::make_cppgc_object(scope, Certificate { der, cert });
   Ok(obj)
 }
 
@@ -76,46 +81,186 @@ pub fn op_node_x509_ca(#[cppgc] cert: &Certificate) -> Result<bool, AnyError> {
   Ok(cert.is_ca())
 }
 
+/// `subject` is `default`, `always` or `never`. By default the subject is
+/// only checked if the certificate has no subject alternative name of the
+/// same type, like OpenSSL does.
+fn check_subject(subject: &str, has_alt_names: bool) -> bool {
+  match subject {
+    "always" => true,
+    "never" => false,
+    _ => !has_alt_names,
+  }
+}
+
 #[op2(fast)]
 pub fn op_node_x509_check_email(
   #[cppgc] cert: &Certificate,
   #[string] email: &str,
+  #[string] subject: &str,
 ) -> Result<bool, AnyError> {
-  let subject = cert.subject();
-  if subject
-    .iter_email()
-    .any(|e| e.as_str().unwrap_or("") == email)
-  {
-    return Ok(true);
+  let mut has_alt_names = false;
+  for name in cert.subject_alt_names() {
+    if let GeneralName::RFC822Name(n) = name {
+      if n.eq_ignore_ascii_case(email) {
+        return Ok(true);
+      }
+      has_alt_names = true;
+    }
   }
 
-  let subject_alt = cert
-    .extensions()
-    .iter()
-    .find(|e| e.oid == x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME)
-    .and_then(|e| match e.parsed_extension() {
-      extensions::ParsedExtension::SubjectAlternativeName(s) => Some(s),
-      _ => None,
-    });
+  Ok(
+    check_subject(subject, has_alt_names)
+      && cert
+        .subject()
+        .iter_email()
+        .any(|e| e.as_str().is_ok_and(|e| e.eq_ignore_ascii_case(email))),
+  )
+}
 
-  if let Some(subject_alt) = subject_alt {
-    for name in &subject_alt.general_names {
-      if let extensions::GeneralName::RFC822Name(n) = name {
-        if *n == email {
-          return Ok(true);
-        }
+#[derive(Clone, Copy)]
+struct HostCheckFlags {
+  wildcards: bool,
+  partial_wildcards: bool,
+  multi_label_wildcards: bool,
+  single_label_subdomains: bool,
+}
+
+/// Matches a DNS name of a certificate against `host`, following the rules of
+/// OpenSSL's `X509_check_host`. A `host` starting with a dot matches the
+/// subdomains of the rest.
+fn match_host(pattern: &str, host: &str, flags: HostCheckFlags) -> bool {
+  if pattern.is_empty() || host.is_empty() {
+    return false;
+  }
+  let pattern = pattern.to_ascii_lowercase();
+  let host = host.to_ascii_lowercase();
+
+  if let Some(parent) = host.strip_prefix('.') {
+    let Some(label) = pattern
+      .strip_suffix(parent)
+      .and_then(|rest| rest.strip_suffix('.'))
+    else {
+      return false;
+    };
+    return !label.is_empty()
+      && !label.contains('*')
+      && (!flags.single_label_subdomains || !label.contains('.'));
+  }
+
+  if pattern == host {
+    return true;
+  }
+  if !flags.wildcards {
+    return false;
+  }
+
+  // The wildcard may only be in the leftmost label, which must be followed by
+  // at least two more.
+  let Some((label, parent)) = pattern.split_once('.') else {
+    return false;
+  };
+  if !parent.contains('.') || parent.contains('*') {
+    return false;
+  }
+  let Some((prefix, suffix)) = label.split_once('*') else {
+    return false;
+  };
+  if suffix.contains('*') {
+    return false;
+  }
+  if label != "*" && (!flags.partial_wildcards || label.starts_with("xn--")) {
+    return false;
+  }
+
+  let Some(matched) = host
+    .strip_suffix(parent)
+    .and_then(|rest| rest.strip_suffix('.'))
+  else {
+    return false;
+  };
+  if !flags.multi_label_wildcards && matched.contains('.') {
+    return false;
+  }
+  matched.len() > prefix.len() + suffix.len()
+    && matched.starts_with(prefix)
+    && matched.ends_with(suffix)
+}
+
+/// Returns the name of the certificate that matches `host`, see
+/// [`check_subject`] for `subject`.
+#[op2]
+#[string]
+#[allow(clippy::too_many_arguments)]
+pub fn op_node_x509_check_host(
+  #[cppgc] cert: &Certificate,
+  #[string] host: &str,
+  #[string] subject: &str,
+  wildcards: bool,
+  partial_wildcards: bool,
+  multi_label_wildcards: bool,
+  single_label_subdomains: bool,
+) -> Result<Option<String>, AnyError> {
+  let flags = HostCheckFlags {
+    wildcards,
+    partial_wildcards,
+    multi_label_wildcards,
+    single_label_subdomains,
+  };
+
+  let mut has_alt_names = false;
+  for name in cert.subject_alt_names() {
+    if let GeneralName::DNSName(n) = name {
+      if match_host(n, host, flags) {
+        return Ok(Some(n.to_string()));
       }
+      has_alt_names = true;
     }
   }
 
-  Ok(false)
+  if !check_subject(subject, has_alt_names) {
+    return Ok(None);
+  }
+  Ok(
+    cert
+      .subject()
+      .iter_common_name()
+      .filter_map(|cn| cn.as_str().ok())
+      .find(|cn| match_host(cn, host, flags))
+      .map(|cn| cn.to_string()),
+  )
+}
+
+/// `ip` is validated in JS.
+#[op2(fast)]
+pub fn op_node_x509_check_ip(
+  #[cppgc] cert: &Certificate,
+  #[string] ip: &str,
+) -> Result<bool, AnyError> {
+  let ip = ip
+    .parse::<IpAddr>()
+    .map_err(|_| type_error("Invalid IP address"))?;
+
+  Ok(cert.subject_alt_names().iter().any(|name| match name {
+    GeneralName::IPAddress(bytes) => ip_from_bytes(bytes) == Some(ip),
+    _ => false,
+  }))
+}
+
+fn ip_from_bytes(bytes: &[u8]) -> Option<IpAddr> {
+  if let Ok(octets) = <[u8; 4]>::try_from(bytes) {
+    Some(IpAddr::from(octets))
+  } else if let Ok(octets) = <[u8; 16]>::try_from(bytes) {
+    Some(IpAddr::from(octets))
+  } else {
+    None
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_node_x509_fingerprint(
   #[cppgc] cert: &Certificate,
-) -> Result<Option<String>, AnyError> {
+) -> Result<String, AnyError> {
   Ok(cert.fingerprint::<sha1::Sha1>())
 }
 
@@ -123,7 +268,7 @@ pub fn op_node_x509_fingerprint(
 #[string]
 pub fn op_node_x509_fingerprint256(
   #[cppgc] cert: &Certificate,
-) -> Result<Option<String>, AnyError> {
+) -> Result<String, AnyError> {
   Ok(cert.fingerprint::<sha2::Sha256>())
 }
 
@@ -131,7 +276,7 @@ pub fn op_node_x509_fingerprint256(
 #[string]
 pub fn op_node_x509_fingerprint512(
   #[cppgc] cert: &Certificate,
-) -> Result<Option<String>, AnyError> {
+) -> Result<String, AnyError> {
   Ok(cert.fingerprint::<sha2::Sha512>())
 }
 
@@ -252,3 +397,210 @@ pub fn op_node_x509_key_usage(
 
   Ok(key_usage.map(|k| k.flags).unwrap_or(0))
 }
+
+#[op2]
+#[serde]
+pub fn op_node_x509_get_raw(
+  #[cppgc] cert: &Certificate,
+) -> Result<ToJsBuffer, AnyError> {
+  Ok(cert.der.clone().into())
+}
+
+/// Returns the DER encoded SPKI of the public key.
+#[op2]
+#[serde]
+pub fn op_node_x509_get_public_key(
+  #[cppgc] cert: &Certificate,
+) -> Result<ToJsBuffer, AnyError> {
+  Ok(cert.public_key().raw.to_vec().into())
+}
+
+/// Names are quoted the way Node.js does, if they could otherwise be
+/// misread when splitting the list at the commas.
+fn safe_alt_name(name: &str, utf8: bool) -> String {
+  let safe = name.chars().all(|c| match c {
+    '"' | '\\' | ',' | '\'' => false,
+    c if utf8 => c >= ' ' && c != '\x7f',
+    c => (' '..='~').contains(&c),
+  });
+  if safe {
+    name.to_string()
+  } else {
+    deno_core::serde_json::to_string(name).unwrap()
+  }
+}
+
+fn format_alt_name(name: &GeneralName) -> String {
+  match name {
+    GeneralName::DNSName(n) => format!("DNS:{}", safe_alt_name(n, false)),
+    GeneralName::RFC822Name(n) => format!("email:{}", safe_alt_name(n, false)),
+    GeneralName::URI(n) => format!("URI:{}", safe_alt_name(n, false)),
+    GeneralName::IPAddress(bytes) => match ip_from_bytes(bytes) {
+      Some(IpAddr::V4(ip)) => format!("IP Address:{}", ip),
+      Some(IpAddr::V6(ip)) => format!(
+        "IP Address:{}",
+        ip.segments()
+          .iter()
+          .map(|s| format!("{:X}", s))
+          .collect::<Vec<_>>()
+          .join(":")
+      ),
+      None => "IP Address:<invalid>".to_string(),
+    },
+    GeneralName::DirectoryName(n) => {
+      let n = x509name_to_string(n, oid_registry())
+        .unwrap_or_default()
+        .replace('\n', "/");
+      format!("DirName:{}", safe_alt_name(&n, true))
+    }
+    GeneralName::RegisteredID(oid) => format!("Registered ID:{}", oid),
+    GeneralName::OtherName(..) => "othername:<unsupported>".to_string(),
+    GeneralName::X400Address(_) => "X400Name:<unsupported>".to_string(),
+    GeneralName::EDIPartyName(_) => "EdiPartyName:<unsupported>".to_string(),
+  }
+}
+
+#[op2]
+#[string]
+pub fn op_node_x509_get_subject_alt_name(
+  #[cppgc] cert: &Certificate,
+) -> Result<Option<String>, AnyError> {
+  let names = cert.subject_alt_names();
+  if names.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some(
+    names
+      .iter()
+      .map(format_alt_name)
+      .collect::<Vec<_>>()
+      .join(", "),
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DEFAULT_FLAGS: HostCheckFlags = HostCheckFlags {
+    wildcards: true,
+    partial_wildcards: true,
+    multi_label_wildcards: false,
+    single_label_subdomains: false,
+  };
+
+  #[test]
+  fn match_host_exact() {
+    assert!(match_host("example.com", "example.com", DEFAULT_FLAGS));
+    assert!(match_host("Example.COM", "example.com", DEFAULT_FLAGS));
+    assert!(!match_host("example.com", "www.example.com", DEFAULT_FLAGS));
+    assert!(!match_host("", "", DEFAULT_FLAGS));
+  }
+
+  #[test]
+  fn match_host_wildcards() {
+    assert!(match_host(
+      "*.example.com",
+      "www.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(!match_host("*.example.com", "example.com", DEFAULT_FLAGS));
+    assert!(!match_host("*.example.com", ".example.com", DEFAULT_FLAGS));
+    assert!(!match_host(
+      "*.example.com",
+      "a.b.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(!match_host("*.com", "example.com", DEFAULT_FLAGS));
+    assert!(!match_host("www.*.com", "www.example.com", DEFAULT_FLAGS));
+    assert!(!match_host(
+      "**.example.com",
+      "www.example.com",
+      DEFAULT_FLAGS
+    ));
+
+    let flags = HostCheckFlags {
+      wildcards: false,
+      ..DEFAULT_FLAGS
+    };
+    assert!(!match_host("*.example.com", "www.example.com", flags));
+
+    let flags = HostCheckFlags {
+      multi_label_wildcards: true,
+      ..DEFAULT_FLAGS
+    };
+    assert!(match_host("*.example.com", "a.b.example.com", flags));
+  }
+
+  #[test]
+  fn match_host_partial_wildcards() {
+    assert!(match_host(
+      "w*.example.com",
+      "www.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(match_host(
+      "*w.example.com",
+      "ww.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(!match_host(
+      "w*.example.com",
+      "w.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(!match_host(
+      "w*.example.com",
+      "abc.example.com",
+      DEFAULT_FLAGS
+    ));
+    assert!(!match_host(
+      "xn--*.example.com",
+      "xn--a.example.com",
+      DEFAULT_FLAGS
+    ));
+
+    let flags = HostCheckFlags {
+      partial_wildcards: false,
+      ..DEFAULT_FLAGS
+    };
+    assert!(!match_host("w*.example.com", "www.example.com", flags));
+  }
+
+  #[test]
+  fn match_host_subdomains() {
+    assert!(match_host("www.example.com", ".example.com", DEFAULT_FLAGS));
+    assert!(match_host("a.b.example.com", ".example.com", DEFAULT_FLAGS));
+    assert!(!match_host("example.com", ".example.com", DEFAULT_FLAGS));
+    assert!(!match_host("*.example.com", ".example.com", DEFAULT_FLAGS));
+
+    let flags = HostCheckFlags {
+      single_label_subdomains: true,
+      ..DEFAULT_FLAGS
+    };
+    assert!(match_host("www.example.com", ".example.com", flags));
+    assert!(!match_host("a.b.example.com", ".example.com", flags));
+  }
+
+  #[test]
+  fn alt_names_are_quoted_when_unsafe() {
+    assert_eq!(
+      format_alt_name(&GeneralName::DNSName("example.com")),
+      "DNS:example.com"
+    );
+    assert_eq!(
+      format_alt_name(&GeneralName::DNSName("a.com, DNS:b.com")),
+      r#"DNS:"a.com, DNS:b.com""#
+    );
+    assert_eq!(
+      format_alt_name(&GeneralName::IPAddress(&[127, 0, 0, 1])),
+      "IP Address:127.0.0.1"
+    );
+    let mut v6 = [0u8; 16];
+    v6[15] = 1;
+    assert_eq!(
+      format_alt_name(&GeneralName::IPAddress(&v6)),
+      "IP Address:0:0:0:0:0:0:0:1"
+    );
+  }
+}
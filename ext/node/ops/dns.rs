@@ -0,0 +1,269 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The DNS channel behind `dns.Resolver`, the counterpart of a c-ares
+//! channel in Node. Answers have the records in the shape returned by
+//! `Deno.resolveDns()`, converted to the Node ones in
+//! `internal_binding/cares_wrap.ts`, and failures are reported with the
+//! c-ares error codes instead of being thrown.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::net::IpAddr;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Duration;
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
+use deno_core::OpState;
+use deno_core::Resource;
+use deno_core::ResourceId;
+use deno_net::ops::rdata_to_return_record;
+use deno_net::ops::DnsReturnRecord;
+use serde::Serialize;
+use trust_dns_proto::op::ResponseCode;
+use trust_dns_proto::rr::Name;
+use trust_dns_proto::rr::RecordType;
+use trust_dns_resolver::config::NameServerConfigGroup;
+use trust_dns_resolver::config::ResolverConfig;
+use trust_dns_resolver::error::ResolveError;
+use trust_dns_resolver::error::ResolveErrorKind;
+use trust_dns_resolver::system_conf;
+use trust_dns_resolver::TokioAsyncResolver;
+
+use crate::NodePermissions;
+
+pub struct DnsChannel {
+  resolver: TokioAsyncResolver,
+  /// Cancels the pending queries, replaced after every `cancel()` so that
+  /// the later queries go through.
+  cancel: RefCell<Rc<CancelHandle>>,
+}
+
+impl Resource for DnsChannel {
+  fn name(&self) -> Cow<str> {
+    "dnsChannel".into()
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsAnswer {
+  /// The c-ares error code of a failed query, like `ENOTFOUND`.
+  code: Option<&'static str>,
+  records: Vec<DnsReturnRecord>,
+  ttls: Vec<u32>,
+}
+
+impl DnsAnswer {
+  fn error(code: &'static str) -> Self {
+    Self {
+      code: Some(code),
+      records: vec![],
+      ttls: vec![],
+    }
+  }
+}
+
+fn error_code(err: &ResolveError) -> &'static str {
+  match err.kind() {
+    ResolveErrorKind::NoRecordsFound { response_code, .. } => {
+      match *response_code {
+        ResponseCode::NXDomain => "ENOTFOUND",
+        ResponseCode::ServFail => "ESERVFAIL",
+        ResponseCode::Refused => "EREFUSED",
+        ResponseCode::FormErr => "EFORMERR",
+        ResponseCode::NotImp => "ENOTIMP",
+        _ => "ENODATA",
+      }
+    }
+    ResolveErrorKind::Timeout => "ETIMEOUT",
+    ResolveErrorKind::NoConnections | ResolveErrorKind::Io(_) => "ECONNREFUSED",
+    _ => "EBADRESP",
+  }
+}
+
+/// The name servers of the system, used when none are set.
+#[op2]
+#[serde]
+pub fn op_node_dns_system_servers() -> Result<Vec<(String, u16)>, AnyError> {
+  let (config, _) = system_conf::read_system_conf()?;
+  let mut servers = config
+    .name_servers()
+    .iter()
+    .map(|ns| (ns.socket_addr.ip().to_string(), ns.socket_addr.port()))
+    .collect::<Vec<_>>();
+  // every server is listed once for UDP and once for TCP
+  servers.dedup();
+  Ok(servers)
+}
+
+/// Uses the name servers of the system when `servers` is null. `timeout` is
+/// in milliseconds, -1 for the default one. `tries` is the number of
+/// attempts per server.
+#[op2]
+#[smi]
+pub fn op_node_dns_channel_new<P>(
+  state: &mut OpState,
+  #[serde] servers: Option<Vec<(String, u16)>>,
+  timeout: i32,
+  #[smi] tries: u32,
+) -> Result<ResourceId, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let (config, mut opts) = if let Some(servers) = servers {
+    let mut group = NameServerConfigGroup::new();
+    for (ip, port) in servers {
+      let ip = IpAddr::from_str(&ip)
+        .map_err(|_| type_error(format!("Invalid IP address: {ip}")))?;
+      group.merge(NameServerConfigGroup::from_ips_clear(&[ip], port, true));
+    }
+    (
+      ResolverConfig::from_parts(None, vec![], group),
+      Default::default(),
+    )
+  } else {
+    system_conf::read_system_conf()?
+  };
+
+  let permissions = state.borrow_mut::<P>();
+  for ns in config.name_servers() {
+    let ip = ns.socket_addr.ip().to_string();
+    permissions.check_net((&ip, Some(ns.socket_addr.port())), "node:dns")?;
+  }
+
+  if timeout >= 0 {
+    opts.timeout = Duration::from_millis(timeout as u64);
+  }
+  opts.attempts = tries as usize;
+  // c-ares doesn't cache the answers, and neither does Node
+  opts.cache_size = 0;
+
+  let resolver = TokioAsyncResolver::tokio(config, opts)?;
+  Ok(state.resource_table.add(DnsChannel {
+    resolver,
+    cancel: Default::default(),
+  }))
+}
+
+/// Fails the pending queries of the channel with `ECANCELLED`.
+#[op2(fast)]
+pub fn op_node_dns_channel_cancel(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<(), AnyError> {
+  let channel = state.resource_table.get::<DnsChannel>(rid)?;
+  channel.cancel.replace(Default::default()).cancel();
+  Ok(())
+}
+
+async fn query(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  name: Name,
+  record_type: RecordType,
+) -> Result<DnsAnswer, AnyError> {
+  let channel = state.borrow().resource_table.get::<DnsChannel>(rid)?;
+  let cancel = channel.cancel.borrow().clone();
+  let lookup = match channel
+    .resolver
+    .lookup(name, record_type)
+    .or_cancel(cancel)
+    .await
+  {
+    Ok(Ok(lookup)) => lookup,
+    Ok(Err(err)) => return Ok(DnsAnswer::error(error_code(&err))),
+    Err(_) => return Ok(DnsAnswer::error("ECANCELLED")),
+  };
+
+  let to_record = rdata_to_return_record(record_type);
+  let mut answer = DnsAnswer {
+    code: None,
+    records: vec![],
+    ttls: vec![],
+  };
+  for record in lookup.record_iter() {
+    let Some(data) = record.data() else {
+      continue;
+    };
+    if let Some(data) = to_record(data)? {
+      answer.records.push(data);
+      answer.ttls.push(record.ttl());
+    }
+  }
+  Ok(answer)
+}
+
+#[op2(async)]
+#[serde]
+pub async fn op_node_dns_query(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[string] name: String,
+  #[string] record_type: String,
+) -> Result<DnsAnswer, AnyError> {
+  let record_type = RecordType::from_str(&record_type)
+    .map_err(|_| type_error(format!("Invalid record type: {record_type}")))?;
+  let Ok(name) = Name::from_str(&name) else {
+    return Ok(DnsAnswer::error("EBADNAME"));
+  };
+  query(state, rid, name, record_type).await
+}
+
+/// Resolves the host names of an IP address, with its PTR records.
+#[op2(async)]
+#[serde]
+pub async fn op_node_dns_reverse(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[string] ip: String,
+) -> Result<DnsAnswer, AnyError> {
+  let Ok(ip) = IpAddr::from_str(&ip) else {
+    return Ok(DnsAnswer::error("EINVAL"));
+  };
+  query(state, rid, Name::from(ip), RecordType::PTR).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use trust_dns_proto::op::Query;
+
+  fn no_records(response_code: ResponseCode) -> ResolveError {
+    ResolveErrorKind::NoRecordsFound {
+      query: Box::new(Query::new()),
+      soa: None,
+      negative_ttl: None,
+      response_code,
+      trusted: true,
+    }
+    .into()
+  }
+
+  #[test]
+  fn error_codes() {
+    assert_eq!(error_code(&no_records(ResponseCode::NXDomain)), "ENOTFOUND");
+    assert_eq!(error_code(&no_records(ResponseCode::NoError)), "ENODATA");
+    assert_eq!(error_code(&no_records(ResponseCode::ServFail)), "ESERVFAIL");
+    assert_eq!(error_code(&no_records(ResponseCode::Refused)), "EREFUSED");
+    assert_eq!(error_code(&ResolveErrorKind::Timeout.into()), "ETIMEOUT");
+    assert_eq!(
+      error_code(&ResolveErrorKind::NoConnections.into()),
+      "ECONNREFUSED"
+    );
+    assert_eq!(
+      error_code(&ResolveErrorKind::Message("bad").into()),
+      "EBADRESP"
+    );
+  }
+
+  #[test]
+  fn reverse_name() {
+    let ip = IpAddr::from_str("8.8.4.4").unwrap();
+    assert_eq!(Name::from(ip).to_string(), "4.4.8.8.in-addr.arpa.");
+  }
+}
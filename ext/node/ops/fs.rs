@@ -1,14 +1,28 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
+use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::FutureExt;
+use deno_core::normalize_path;
 use deno_core::op2;
+use deno_core::unsync::spawn_blocking;
 use deno_core::OpState;
+use deno_core::Resource;
+use deno_core::ResourceId;
 use deno_fs::FileSystemRc;
+use deno_fs::FsDirEntry;
+use deno_fs::FsDirIter;
+use deno_io::fs::FsStat;
+use serde::Deserialize;
 use serde::Serialize;
 
 use crate::NodePermissions;
@@ -29,12 +43,14 @@ where
   Ok(fs.lstat_sync(&path).is_ok())
 }
 
-#[op2(fast)]
+#[op2]
+#[serde]
 pub fn op_node_cp_sync<P>(
   state: &mut OpState,
   #[string] path: &str,
   #[string] new_path: &str,
-) -> Result<(), AnyError>
+  #[serde] options: CpOptions,
+) -> Result<Option<CpError>, AnyError>
 where
   P: NodePermissions + 'static,
 {
@@ -49,16 +65,17 @@ where
     .check_write_with_api_name(new_path, Some("node:fs.cpSync"))?;
 
   let fs = state.borrow::<FileSystemRc>();
-  fs.cp_sync(path, new_path)?;
-  Ok(())
+  Ok(cp_sync(fs, path, new_path, &options.normalize()).err())
 }
 
 #[op2(async)]
+#[serde]
 pub async fn op_node_cp<P>(
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[string] new_path: String,
-) -> Result<(), AnyError>
+  #[serde] options: CpOptions,
+) -> Result<Option<CpError>, AnyError>
 where
   P: NodePermissions + 'static,
 {
@@ -76,10 +93,804 @@ where
     state.borrow::<FileSystemRc>().clone()
   };
 
-  fs.cp_async(path, new_path).await?;
+  let options = options.normalize();
+  Ok(cp_async(&fs, &path, &new_path, &options).await.err())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpOptions {
+  dereference: bool,
+  error_on_exist: bool,
+  force: bool,
+  preserve_timestamps: bool,
+  recursive: bool,
+  verbatim_symlinks: bool,
+  /// The paths `options.filter` rejected, which is called in JS before the
+  /// copy starts.
+  #[serde(default)]
+  skip: HashSet<PathBuf>,
+}
+
+impl CpOptions {
+  /// The paths are compared normalized, as JS joins them with `path.join()`.
+  fn normalize(mut self) -> Self {
+    self.skip = self.skip.into_iter().map(normalize_path).collect();
+    self
+  }
+
+  fn is_skipped(&self, path: &Path) -> bool {
+    !self.skip.is_empty() && self.skip.contains(&normalize_path(path))
+  }
+}
+
+/// Why `fs.cp()` failed, which JS turns into a Node error.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpError {
+  /// The code of the Node error, like `ERR_FS_CP_EINVAL`, or `None` if a
+  /// system call failed.
+  kind: Option<&'static str>,
+  /// The code of the system error of a Node error, like `EINVAL`.
+  code: Option<&'static str>,
+  message: String,
+  syscall: &'static str,
+  path: String,
+}
+
+impl CpError {
+  fn node(
+    kind: &'static str,
+    code: &'static str,
+    message: String,
+    path: &Path,
+  ) -> Self {
+    Self {
+      kind: Some(kind),
+      code: Some(code),
+      message,
+      syscall: "cp",
+      path: path.to_string_lossy().into_owned(),
+    }
+  }
+
+  /// The message of a failed system call keeps the OS error number, which
+  /// `denoErrorToNodeError()` maps to its code.
+  fn fs<E: Into<AnyError>>(
+    syscall: &'static str,
+    path: &Path,
+  ) -> impl FnOnce(E) -> Self {
+    let path = path.to_string_lossy().into_owned();
+    move |err| Self {
+      kind: None,
+      code: None,
+      message: err.into().to_string(),
+      syscall,
+      path,
+    }
+  }
+}
+
+enum CpKind {
+  Dir,
+  File,
+  Symlink,
+}
+
+/// The checks of `fs.cp()` on the paths of an entry to copy, before
+/// anything was copied.
+fn cp_check_paths(
+  cwd: &Path,
+  src: &Path,
+  src_stat: &FsStat,
+  dest: &Path,
+  dest_stat: Option<&FsStat>,
+) -> Result<(), CpError> {
+  if let Some(dest_stat) = dest_stat {
+    if dest_stat.ino != 0
+      && dest_stat.dev != 0
+      && dest_stat.ino == src_stat.ino
+      && dest_stat.dev == src_stat.dev
+    {
+      return Err(CpError::node(
+        "ERR_FS_CP_EINVAL",
+        "EINVAL",
+        "src and dest cannot be the same".to_string(),
+        dest,
+      ));
+    }
+    if src_stat.is_directory && !dest_stat.is_directory {
+      return Err(CpError::node(
+        "ERR_FS_CP_DIR_TO_NON_DIR",
+        "EISDIR",
+        format!(
+          "cannot overwrite directory {} with non-directory {}",
+          src.display(),
+          dest.display()
+        ),
+        dest,
+      ));
+    }
+    if !src_stat.is_directory && dest_stat.is_directory {
+      return Err(CpError::node(
+        "ERR_FS_CP_NON_DIR_TO_DIR",
+        "ENOTDIR",
+        format!(
+          "cannot overwrite non-directory {} with directory {}",
+          src.display(),
+          dest.display()
+        ),
+        dest,
+      ));
+    }
+  }
+  if src_stat.is_directory && is_subdir(cwd, src, dest) {
+    return Err(CpError::node(
+      "ERR_FS_CP_EINVAL",
+      "EINVAL",
+      format!(
+        "cannot copy {} to a subdirectory of self {}",
+        src.display(),
+        dest.display()
+      ),
+      dest,
+    ));
+  }
+  Ok(())
+}
+
+/// What to do with an entry to copy, by its type.
+fn cp_kind(
+  src: &Path,
+  src_stat: &FsStat,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<CpKind, CpError> {
+  if src_stat.is_directory {
+    if !options.recursive {
+      return Err(CpError::node(
+        "ERR_FS_EISDIR",
+        "EISDIR",
+        format!("{} is a directory (not copied)", src.display()),
+        src,
+      ));
+    }
+    Ok(CpKind::Dir)
+  } else if src_stat.is_file
+    || src_stat.is_char_device
+    || src_stat.is_block_device
+  {
+    Ok(CpKind::File)
+  } else if src_stat.is_symlink {
+    Ok(CpKind::Symlink)
+  } else if src_stat.is_socket {
+    Err(CpError::node(
+      "ERR_FS_CP_SOCKET",
+      "EINVAL",
+      format!("cannot copy a socket file: {}", dest.display()),
+      dest,
+    ))
+  } else if src_stat.is_fifo {
+    Err(CpError::node(
+      "ERR_FS_CP_FIFO_PIPE",
+      "EINVAL",
+      format!("cannot copy a FIFO pipe: {}", dest.display()),
+      dest,
+    ))
+  } else {
+    Err(CpError::node(
+      "ERR_FS_CP_UNKNOWN",
+      "EINVAL",
+      format!("cannot copy an unknown file type: {}", dest.display()),
+      dest,
+    ))
+  }
+}
+
+/// The checks of `fs.cp()` before replacing the symlink `dest` with a
+/// symlink to `resolved_src`.
+fn cp_check_symlinks(
+  cwd: &Path,
+  resolved_src: &Path,
+  resolved_dest: &Path,
+  src_is_dir: bool,
+  dest: &Path,
+) -> Result<(), CpError> {
+  if is_subdir(cwd, resolved_src, resolved_dest) {
+    return Err(CpError::node(
+      "ERR_FS_CP_EINVAL",
+      "EINVAL",
+      format!(
+        "cannot copy {} to a subdirectory of self {}",
+        resolved_src.display(),
+        resolved_dest.display()
+      ),
+      dest,
+    ));
+  }
+  if src_is_dir && is_subdir(cwd, resolved_dest, resolved_src) {
+    return Err(CpError::node(
+      "ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY",
+      "EINVAL",
+      format!(
+        "cannot overwrite {} with {}",
+        resolved_dest.display(),
+        resolved_src.display()
+      ),
+      dest,
+    ));
+  }
   Ok(())
 }
 
+/// Whether `dest` is `src` or inside of it.
+fn is_subdir(cwd: &Path, src: &Path, dest: &Path) -> bool {
+  normalize_path(cwd.join(dest)).starts_with(normalize_path(cwd.join(src)))
+}
+
+/// The target of the symlink `link`, resolved against its directory unless
+/// it should be copied verbatim.
+fn resolve_link(
+  cwd: &Path,
+  link: &Path,
+  target: PathBuf,
+  verbatim: bool,
+) -> PathBuf {
+  if verbatim || target.is_absolute() {
+    return target;
+  }
+  let parent = link.parent().unwrap_or(Path::new(""));
+  normalize_path(cwd.join(parent).join(target))
+}
+
+fn timestamp(ms: Option<u64>) -> (i64, u32) {
+  let ms = ms.unwrap_or_default();
+  ((ms / 1000) as i64, ((ms % 1000) * 1_000_000) as u32)
+}
+
+fn cp_stat_sync(
+  fs: &FileSystemRc,
+  path: &Path,
+  dereference: bool,
+) -> Result<Option<FsStat>, CpError> {
+  let (syscall, stat) = if dereference {
+    ("stat", fs.stat_sync(path))
+  } else {
+    ("lstat", fs.lstat_sync(path))
+  };
+  match stat {
+    Ok(stat) => Ok(Some(stat)),
+    Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+    Err(err) => Err(CpError::fs(syscall, path)(err)),
+  }
+}
+
+/// Stats `src` and `dest` for copying `src`, or returns `None` if `src` is
+/// skipped.
+fn cp_paths_sync(
+  fs: &FileSystemRc,
+  cwd: &Path,
+  src: &Path,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<Option<(FsStat, Option<FsStat>)>, CpError> {
+  if options.is_skipped(src) {
+    return Ok(None);
+  }
+  let syscall = if options.dereference { "stat" } else { "lstat" };
+  let Some(src_stat) = cp_stat_sync(fs, src, options.dereference)? else {
+    return Err(CpError::fs(syscall, src)(std::io::Error::from(
+      ErrorKind::NotFound,
+    )));
+  };
+  let dest_stat = cp_stat_sync(fs, dest, options.dereference)?;
+  cp_check_paths(cwd, src, &src_stat, dest, dest_stat.as_ref())?;
+  Ok(Some((src_stat, dest_stat)))
+}
+
+/// The port of Node's `cpSyncFn()`.
+fn cp_sync(
+  fs: &FileSystemRc,
+  src: &Path,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<(), CpError> {
+  let cwd = fs.cwd().map_err(CpError::fs("cwd", src))?;
+  let Some((src_stat, dest_stat)) =
+    cp_paths_sync(fs, &cwd, src, dest, options)?
+  else {
+    return Ok(());
+  };
+
+  // `dest` can't be inside of `src` through a symlink either
+  let src_path = normalize_path(cwd.join(src));
+  let src_parent = src_path.parent().unwrap_or(&src_path);
+  let mut dest_parent = normalize_path(cwd.join(dest));
+  while let Some(parent) = dest_parent.parent().map(Path::to_path_buf) {
+    if parent == src_parent || parent.parent().is_none() {
+      break;
+    }
+    match fs.stat_sync(&parent) {
+      Ok(stat) if stat.ino == src_stat.ino && stat.dev == src_stat.dev => {
+        return Err(CpError::node(
+          "ERR_FS_CP_EINVAL",
+          "EINVAL",
+          format!(
+            "cannot copy {} to a subdirectory of self {}",
+            src.display(),
+            dest.display()
+          ),
+          dest,
+        ));
+      }
+      Ok(_) => {}
+      Err(err) if err.kind() == ErrorKind::NotFound => break,
+      Err(err) => return Err(CpError::fs("stat", &parent)(err)),
+    }
+    dest_parent = parent;
+  }
+
+  if let Some(parent) = dest.parent() {
+    if !parent.as_os_str().is_empty() && !fs.exists_sync(parent) {
+      fs.mkdir_sync(parent, true, 0o777)
+        .map_err(CpError::fs("mkdir", parent))?;
+    }
+  }
+  cp_entry_sync(fs, &cwd, src_stat, dest_stat, src, dest, options)
+}
+
+fn cp_entry_sync(
+  fs: &FileSystemRc,
+  cwd: &Path,
+  src_stat: FsStat,
+  dest_stat: Option<FsStat>,
+  src: &Path,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<(), CpError> {
+  match cp_kind(src, &src_stat, dest, options)? {
+    CpKind::Dir => {
+      if dest_stat.is_none() {
+        fs.mkdir_sync(dest, false, 0o777)
+          .map_err(CpError::fs("mkdir", dest))?;
+      }
+      let entries =
+        fs.read_dir_sync(src).map_err(CpError::fs("opendir", src))?;
+      for entry in entries {
+        let src = src.join(&entry.name);
+        let dest = dest.join(&entry.name);
+        if let Some((src_stat, dest_stat)) =
+          cp_paths_sync(fs, cwd, &src, &dest, options)?
+        {
+          cp_entry_sync(fs, cwd, src_stat, dest_stat, &src, &dest, options)?;
+        }
+      }
+      if dest_stat.is_none() {
+        cp_set_mode_sync(fs, dest, src_stat.mode)?;
+      }
+    }
+    CpKind::File => {
+      if dest_stat.is_some() {
+        if options.force {
+          fs.remove_sync(dest, false)
+            .map_err(CpError::fs("unlink", dest))?;
+        } else if options.error_on_exist {
+          return Err(CpError::node(
+            "ERR_FS_CP_EEXIST",
+            "EEXIST",
+            format!("{} already exists", dest.display()),
+            dest,
+          ));
+        } else {
+          return Ok(());
+        }
+      }
+      fs.copy_file_sync(src, dest)
+        .map_err(CpError::fs("copyfile", dest))?;
+      if options.preserve_timestamps {
+        if src_stat.mode & 0o200 == 0 {
+          cp_set_mode_sync(fs, dest, src_stat.mode | 0o200)?;
+        }
+        // the copy updated the access time of `src`
+        let stat = fs.stat_sync(src).map_err(CpError::fs("stat", src))?;
+        let (atime_secs, atime_nanos) = timestamp(stat.atime);
+        let (mtime_secs, mtime_nanos) = timestamp(stat.mtime);
+        fs.utime_sync(dest, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+          .map_err(CpError::fs("utime", dest))?;
+      }
+      cp_set_mode_sync(fs, dest, src_stat.mode)?;
+    }
+    CpKind::Symlink => {
+      let target = fs
+        .read_link_sync(src)
+        .map_err(CpError::fs("readlink", src))?;
+      let resolved_src =
+        resolve_link(cwd, src, target, options.verbatim_symlinks);
+      if dest_stat.is_some() {
+        match fs.read_link_sync(dest) {
+          Ok(target) => {
+            let resolved_dest = resolve_link(cwd, dest, target, false);
+            let src_is_dir = fs.is_dir_sync(src);
+            cp_check_symlinks(
+              cwd,
+              &resolved_src,
+              &resolved_dest,
+              src_is_dir,
+              dest,
+            )?;
+            fs.remove_sync(dest, false)
+              .map_err(CpError::fs("unlink", dest))?;
+          }
+          // not a symlink, which the symlink below fails to replace
+          Err(err) if err.kind() == ErrorKind::InvalidInput => {}
+          Err(err) => return Err(CpError::fs("readlink", dest)(err)),
+        }
+      }
+      fs.symlink_sync(&resolved_src, dest, None)
+        .map_err(CpError::fs("symlink", dest))?;
+    }
+  }
+  Ok(())
+}
+
+fn cp_set_mode_sync(
+  fs: &FileSystemRc,
+  path: &Path,
+  mode: u32,
+) -> Result<(), CpError> {
+  if cfg!(unix) {
+    fs.chmod_sync(path, mode & 0o7777)
+      .map_err(CpError::fs("chmod", path))?;
+  }
+  Ok(())
+}
+
+async fn cp_stat_async(
+  fs: &FileSystemRc,
+  path: &Path,
+  dereference: bool,
+) -> Result<Option<FsStat>, CpError> {
+  let (syscall, stat) = if dereference {
+    ("stat", fs.stat_async(path.to_path_buf()).await)
+  } else {
+    ("lstat", fs.lstat_async(path.to_path_buf()).await)
+  };
+  match stat {
+    Ok(stat) => Ok(Some(stat)),
+    Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+    Err(err) => Err(CpError::fs(syscall, path)(err)),
+  }
+}
+
+async fn cp_paths_async(
+  fs: &FileSystemRc,
+  cwd: &Path,
+  src: &Path,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<Option<(FsStat, Option<FsStat>)>, CpError> {
+  if options.is_skipped(src) {
+    return Ok(None);
+  }
+  let syscall = if options.dereference { "stat" } else { "lstat" };
+  let Some(src_stat) = cp_stat_async(fs, src, options.dereference).await?
+  else {
+    return Err(CpError::fs(syscall, src)(std::io::Error::from(
+      ErrorKind::NotFound,
+    )));
+  };
+  let dest_stat = cp_stat_async(fs, dest, options.dereference).await?;
+  cp_check_paths(cwd, src, &src_stat, dest, dest_stat.as_ref())?;
+  Ok(Some((src_stat, dest_stat)))
+}
+
+/// The async version of [`cp_sync`].
+async fn cp_async(
+  fs: &FileSystemRc,
+  src: &Path,
+  dest: &Path,
+  options: &CpOptions,
+) -> Result<(), CpError> {
+  let cwd = fs.cwd().map_err(CpError::fs("cwd", src))?;
+  let Some((src_stat, dest_stat)) =
+    cp_paths_async(fs, &cwd, src, dest, options).await?
+  else {
+    return Ok(());
+  };
+
+  let src_path = normalize_path(cwd.join(src));
+  let src_parent = src_path.parent().unwrap_or(&src_path);
+  let mut dest_parent = normalize_path(cwd.join(dest));
+  while let Some(parent) = dest_parent.parent().map(Path::to_path_buf) {
+    if parent == src_parent || parent.parent().is_none() {
+      break;
+    }
+    match fs.stat_async(parent.clone()).await {
+      Ok(stat) if stat.ino == src_stat.ino && stat.dev == src_stat.dev => {
+        return Err(CpError::node(
+          "ERR_FS_CP_EINVAL",
+          "EINVAL",
+          format!(
+            "cannot copy {} to a subdirectory of self {}",
+            src.display(),
+            dest.display()
+          ),
+          dest,
+        ));
+      }
+      Ok(_) => {}
+      Err(err) if err.kind() == ErrorKind::NotFound => break,
+      Err(err) => return Err(CpError::fs("stat", &parent)(err)),
+    }
+    dest_parent = parent;
+  }
+
+  if let Some(parent) = dest.parent() {
+    if !parent.as_os_str().is_empty()
+      && fs.stat_async(parent.to_path_buf()).await.is_err()
+    {
+      fs.mkdir_async(parent.to_path_buf(), true, 0o777)
+        .await
+        .map_err(CpError::fs("mkdir", parent))?;
+    }
+  }
+  cp_entry_async(fs, &cwd, src_stat, dest_stat, src, dest, options).await
+}
+
+fn cp_entry_async<'a>(
+  fs: &'a FileSystemRc,
+  cwd: &'a Path,
+  src_stat: FsStat,
+  dest_stat: Option<FsStat>,
+  src: &'a Path,
+  dest: &'a Path,
+  options: &'a CpOptions,
+) -> LocalBoxFuture<'a, Result<(), CpError>> {
+  async move {
+    match cp_kind(src, &src_stat, dest, options)? {
+      CpKind::Dir => {
+        if dest_stat.is_none() {
+          fs.mkdir_async(dest.to_path_buf(), false, 0o777)
+            .await
+            .map_err(CpError::fs("mkdir", dest))?;
+        }
+        let entries = fs
+          .read_dir_async(src.to_path_buf())
+          .await
+          .map_err(CpError::fs("opendir", src))?;
+        for entry in entries {
+          let src = src.join(&entry.name);
+          let dest = dest.join(&entry.name);
+          if let Some((src_stat, dest_stat)) =
+            cp_paths_async(fs, cwd, &src, &dest, options).await?
+          {
+            cp_entry_async(fs, cwd, src_stat, dest_stat, &src, &dest, options)
+              .await?;
+          }
+        }
+        if dest_stat.is_none() {
+          cp_set_mode_async(fs, dest, src_stat.mode).await?;
+        }
+      }
+      CpKind::File => {
+        if dest_stat.is_some() {
+          if options.force {
+            fs.remove_async(dest.to_path_buf(), false)
+              .await
+              .map_err(CpError::fs("unlink", dest))?;
+          } else if options.error_on_exist {
+            return Err(CpError::node(
+              "ERR_FS_CP_EEXIST",
+              "EEXIST",
+              format!("{} already exists", dest.display()),
+              dest,
+            ));
+          } else {
+            return Ok(());
+          }
+        }
+        fs.copy_file_async(src.to_path_buf(), dest.to_path_buf())
+          .await
+          .map_err(CpError::fs("copyfile", dest))?;
+        if options.preserve_timestamps {
+          if src_stat.mode & 0o200 == 0 {
+            cp_set_mode_async(fs, dest, src_stat.mode | 0o200).await?;
+          }
+          let stat = fs
+            .stat_async(src.to_path_buf())
+            .await
+            .map_err(CpError::fs("stat", src))?;
+          let (atime_secs, atime_nanos) = timestamp(stat.atime);
+          let (mtime_secs, mtime_nanos) = timestamp(stat.mtime);
+          fs.utime_async(
+            dest.to_path_buf(),
+            atime_secs,
+            atime_nanos,
+            mtime_secs,
+            mtime_nanos,
+          )
+          .await
+          .map_err(CpError::fs("utime", dest))?;
+        }
+        cp_set_mode_async(fs, dest, src_stat.mode).await?;
+      }
+      CpKind::Symlink => {
+        let target = fs
+          .read_link_async(src.to_path_buf())
+          .await
+          .map_err(CpError::fs("readlink", src))?;
+        let resolved_src =
+          resolve_link(cwd, src, target, options.verbatim_symlinks);
+        if dest_stat.is_some() {
+          match fs.read_link_async(dest.to_path_buf()).await {
+            Ok(target) => {
+              let resolved_dest = resolve_link(cwd, dest, target, false);
+              let src_is_dir = fs
+                .stat_async(src.to_path_buf())
+                .await
+                .map(|stat| stat.is_directory)
+                .unwrap_or(false);
+              cp_check_symlinks(
+                cwd,
+                &resolved_src,
+                &resolved_dest,
+                src_is_dir,
+                dest,
+              )?;
+              fs.remove_async(dest.to_path_buf(), false)
+                .await
+                .map_err(CpError::fs("unlink", dest))?;
+            }
+            Err(err) if err.kind() == ErrorKind::InvalidInput => {}
+            Err(err) => return Err(CpError::fs("readlink", dest)(err)),
+          }
+        }
+        fs.symlink_async(resolved_src, dest.to_path_buf(), None)
+          .await
+          .map_err(CpError::fs("symlink", dest))?;
+      }
+    }
+    Ok(())
+  }
+  .boxed_local()
+}
+
+async fn cp_set_mode_async(
+  fs: &FileSystemRc,
+  path: &Path,
+  mode: u32,
+) -> Result<(), CpError> {
+  if cfg!(unix) {
+    fs.chmod_async(path.to_path_buf(), mode & 0o7777)
+      .await
+      .map_err(CpError::fs("chmod", path))?;
+  }
+  Ok(())
+}
+
+/// A directory opened by `fs.opendir()`, whose entries are read in batches
+/// of `bufferSize`.
+struct DirResource {
+  entries: RefCell<Option<FsDirIter>>,
+}
+
+impl Resource for DirResource {
+  fn name(&self) -> Cow<str> {
+    "fsDir".into()
+  }
+}
+
+#[op2(fast)]
+#[smi]
+pub fn op_node_fs_opendir_sync<P>(
+  state: &mut OpState,
+  #[string] path: &str,
+) -> Result<ResourceId, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = Path::new(path);
+  state
+    .borrow_mut::<P>()
+    .check_read_with_api_name(path, Some("node:fs.opendir"))?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  let entries = fs.read_dir_iter_sync(path)?;
+  let rid = state.resource_table.add(DirResource {
+    entries: RefCell::new(Some(entries)),
+  });
+  Ok(rid)
+}
+
+#[op2]
+#[serde]
+pub fn op_node_fs_dir_read_sync(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] batch_size: u32,
+) -> Result<Vec<FsDirEntry>, AnyError> {
+  let resource = state.resource_table.get::<DirResource>(rid)?;
+  let mut entries = resource.entries.borrow_mut();
+  let Some(entries) = entries.as_mut() else {
+    return Err(type_error("Directory is already being read"));
+  };
+  let batch = entries
+    .by_ref()
+    .take(batch_size as usize)
+    .map(|(entry, _)| entry)
+    .collect();
+  Ok(batch)
+}
+
+/// Returns up to `batch_size` entries of a directory, or an empty list once
+/// all were read.
+#[op2(async)]
+#[serde]
+pub async fn op_node_fs_dir_read(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[smi] batch_size: u32,
+) -> Result<Vec<FsDirEntry>, AnyError> {
+  let resource = state.borrow().resource_table.get::<DirResource>(rid)?;
+  let Some(mut entries) = resource.entries.take() else {
+    return Err(type_error("Directory is already being read"));
+  };
+  let (entries, batch) = spawn_blocking(move || {
+    let batch = entries
+      .by_ref()
+      .take(batch_size as usize)
+      .map(|(entry, _)| entry)
+      .collect::<Vec<_>>();
+    (entries, batch)
+  })
+  .await?;
+  resource.entries.replace(Some(entries));
+  Ok(batch)
+}
+
+/// `fs.realpath.native()`, which resolves the path with the `realpath(3)` of
+/// the OS.
+#[op2]
+#[string]
+pub fn op_node_fs_realpath_sync<P>(
+  state: &mut OpState,
+  #[string] path: &str,
+) -> Result<String, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = Path::new(path);
+  state
+    .borrow_mut::<P>()
+    .check_read_with_api_name(path, Some("node:fs.realpath"))?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  let resolved = fs.realpath_sync(path)?;
+  Ok(resolved.to_string_lossy().into_owned())
+}
+
+#[op2(async)]
+#[string]
+pub async fn op_node_fs_realpath<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+) -> Result<String, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = PathBuf::from(path);
+  let fs = {
+    let mut state = state.borrow_mut();
+    state
+      .borrow_mut::<P>()
+      .check_read_with_api_name(&path, Some("node:fs.realpath"))?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  let resolved = fs.realpath_async(path).await?;
+  Ok(resolved.to_string_lossy().into_owned())
+}
+
 #[derive(Debug, Serialize)]
 pub struct StatFs {
   #[serde(rename = "type")]
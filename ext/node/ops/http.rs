@@ -1,26 +1,305 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+
+use deno_core::error::bad_resource_id;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
+use deno_core::futures::Stream;
 use deno_core::op2;
+use deno_core::serde::Deserialize;
+use deno_core::serde::Serialize;
 use deno_core::url::Url;
 use deno_core::ByteString;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
 use deno_core::OpState;
 use deno_core::ResourceId;
+use deno_fetch::create_http_client;
 use deno_fetch::get_or_create_client_from_state;
+use deno_fetch::CreateHttpClientOptions;
 use deno_fetch::FetchCancelHandle;
 use deno_fetch::FetchRequestResource;
 use deno_fetch::FetchReturn;
 use deno_fetch::HttpClientResource;
 use deno_fetch::ResourceToBodyAdapter;
+use pin_project_lite::pin_project;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use reqwest::header::CONTENT_LENGTH;
 use reqwest::Body;
+use reqwest::Client;
 use reqwest::Method;
+use reqwest::Response;
+use reqwest::ResponseBuilderExt;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+/// The connection pool of an `http.Agent`, which the requests made with the
+/// agent share.
+///
+/// Like in Node, at most `maxSockets` requests per origin and `maxTotalSockets`
+/// requests in total are in flight, the others wait for their turn in order.
+/// A request holds its slot until its response body was read, which is when
+/// its connection goes back to the pool of the client. `hyper` reuses the
+/// connection that was idle the shortest first, so the `"fifo"` scheduling
+/// isn't supported.
+struct HttpAgent {
+  client: Client,
+  max_sockets: usize,
+  // Semaphores are shared with the response bodies, which must be `Send`.
+  #[allow(clippy::disallowed_types)]
+  total: std::sync::Arc<Semaphore>,
+  origins: RefCell<HashMap<String, HttpAgentOrigin>>,
+}
+
+struct HttpAgentOrigin {
+  #[allow(clippy::disallowed_types)]
+  sockets: std::sync::Arc<Semaphore>,
+  pending: usize,
+  requests: u64,
+}
+
+/// The slot of a request in the pool.
+struct Slot {
+  _socket: OwnedSemaphorePermit,
+  _total: OwnedSemaphorePermit,
+}
+
+impl HttpAgent {
+  #[allow(clippy::disallowed_types)]
+  async fn acquire(self: Rc<Self>, origin: String) -> Result<Slot, AnyError> {
+    let sockets = {
+      let mut origins = self.origins.borrow_mut();
+      let origin =
+        origins
+          .entry(origin.clone())
+          .or_insert_with(|| HttpAgentOrigin {
+            sockets: std::sync::Arc::new(Semaphore::new(self.max_sockets)),
+            pending: 0,
+            requests: 0,
+          });
+      origin.pending += 1;
+      origin.requests += 1;
+      origin.sockets.clone()
+    };
+    // also counts a request that was cancelled while waiting out
+    let _pending = PendingGuard {
+      agent: self.clone(),
+      origin,
+    };
+    let socket = sockets.acquire_owned().await?;
+    let total = self.total.clone().acquire_owned().await?;
+    Ok(Slot {
+      _socket: socket,
+      _total: total,
+    })
+  }
+
+  fn stats(&self) -> HashMap<String, HttpAgentOriginStats> {
+    self
+      .origins
+      .borrow()
+      .iter()
+      .map(|(name, origin)| {
+        let stats = HttpAgentOriginStats {
+          active: self.max_sockets - origin.sockets.available_permits(),
+          pending: origin.pending,
+          requests: origin.requests,
+        };
+        (name.clone(), stats)
+      })
+      .collect()
+  }
+}
+
+struct PendingGuard {
+  agent: Rc<HttpAgent>,
+  origin: String,
+}
+
+impl Drop for PendingGuard {
+  fn drop(&mut self) {
+    if let Some(origin) = self.agent.origins.borrow_mut().get_mut(&self.origin)
+    {
+      origin.pending -= 1;
+    }
+  }
+}
+
+pin_project! {
+  /// A response body that gives its slot back once it was read.
+  struct SlotBody<S> {
+    #[pin]
+    inner: S,
+    slot: Option<Slot>,
+  }
+}
+
+impl<S, T, E> Stream for SlotBody<S>
+where
+  S: Stream<Item = Result<T, E>>,
+{
+  type Item = Result<T, E>;
+
+  fn poll_next(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    let this = self.project();
+    let poll = this.inner.poll_next(cx);
+    if matches!(poll, Poll::Ready(None | Some(Err(_)))) {
+      this.slot.take();
+    }
+    poll
+  }
+}
+
+/// Moves the slot into the body of `response`.
+fn hold_slot(mut response: Response, slot: Slot) -> Result<Response, AnyError> {
+  let headers = std::mem::take(response.headers_mut());
+  let extensions = std::mem::take(response.extensions_mut());
+  let mut builder = http_v02::Response::builder()
+    .status(response.status())
+    .version(response.version());
+  if let Some(builder_extensions) = builder.extensions_mut() {
+    // includes the remote address and the upgrade of the connection
+    *builder_extensions = extensions;
+  }
+  builder = builder.url(response.url().clone());
+  let body = SlotBody {
+    inner: response.bytes_stream(),
+    slot: Some(slot),
+  };
+  let mut response = builder.body(Body::wrap_stream(body))?;
+  *response.headers_mut() = headers;
+  Ok(response.into())
+}
+
+#[derive(Default)]
+struct HttpAgents {
+  next_id: u32,
+  agents: HashMap<u32, Rc<HttpAgent>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpAgentOptions {
+  keep_alive: bool,
+  keep_alive_msecs: u64,
+  /// `None` if unlimited.
+  max_sockets: Option<usize>,
+  /// `None` if unlimited.
+  max_free_sockets: Option<usize>,
+  /// `None` if unlimited.
+  max_total_sockets: Option<usize>,
+  timeout: Option<u64>,
+  #[serde(default)]
+  ca_certs: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct HttpAgentOriginStats {
+  /// The requests that hold a connection.
+  active: usize,
+  /// The requests waiting for a connection.
+  pending: usize,
+  /// All the requests made to the origin.
+  requests: u64,
+}
+
+/// Creates the connection pool of an `http.Agent`. The pool isn't a
+/// resource, so that the pool of the global agent doesn't trip the resource
+/// sanitizer of the tests making requests.
+#[op2]
+#[smi]
+pub fn op_node_http_agent_new(
+  state: &mut OpState,
+  #[serde] options: HttpAgentOptions,
+) -> Result<u32, AnyError> {
+  let fetch_options = state.borrow::<deno_fetch::Options>();
+  let client = create_http_client(
+    &fetch_options.user_agent,
+    CreateHttpClientOptions {
+      root_cert_store: fetch_options.root_cert_store()?,
+      ca_certs: options
+        .ca_certs
+        .into_iter()
+        .map(|cert| cert.into_bytes())
+        .collect(),
+      proxy: fetch_options.proxy.clone(),
+      unsafely_ignore_certificate_errors: fetch_options
+        .unsafely_ignore_certificate_errors
+        .clone(),
+      client_cert_chain_and_key: fetch_options
+        .client_cert_chain_and_key
+        .clone()
+        .try_into()
+        .unwrap_or_default(),
+      pool_max_idle_per_host: Some(if options.keep_alive {
+        options.max_free_sockets.unwrap_or(usize::MAX)
+      } else {
+        0
+      }),
+      pool_idle_timeout: options.timeout.map(Some),
+      tcp_keepalive: options.keep_alive.then_some(options.keep_alive_msecs),
+      http1: true,
+      http2: false,
+    },
+  )?;
+  let max = |limit: Option<usize>| {
+    limit
+      .unwrap_or(Semaphore::MAX_PERMITS)
+      .min(Semaphore::MAX_PERMITS)
+  };
+  #[allow(clippy::disallowed_types)]
+  let agent = HttpAgent {
+    client,
+    max_sockets: max(options.max_sockets),
+    total: std::sync::Arc::new(Semaphore::new(max(options.max_total_sockets))),
+    origins: Default::default(),
+  };
+
+  if !state.has::<HttpAgents>() {
+    state.put(HttpAgents::default());
+  }
+  let agents = state.borrow_mut::<HttpAgents>();
+  let id = agents.next_id;
+  agents.next_id += 1;
+  agents.agents.insert(id, Rc::new(agent));
+  Ok(id)
+}
+
+/// Drops the pool of an agent. The requests in flight keep their connection.
+#[op2(fast)]
+pub fn op_node_http_agent_close(state: &mut OpState, #[smi] id: u32) {
+  if let Some(agents) = state.try_borrow_mut::<HttpAgents>() {
+    agents.agents.remove(&id);
+  }
+}
+
+#[op2]
+#[serde]
+pub fn op_node_http_agent_stats(
+  state: &mut OpState,
+  #[smi] id: u32,
+) -> Result<HashMap<String, HttpAgentOriginStats>, AnyError> {
+  Ok(http_agent(state, id)?.stats())
+}
+
+fn http_agent(state: &OpState, id: u32) -> Result<Rc<HttpAgent>, AnyError> {
+  state
+    .try_borrow::<HttpAgents>()
+    .and_then(|agents| agents.agents.get(&id))
+    .cloned()
+    .ok_or_else(bad_resource_id)
+}
 
 #[op2]
 #[serde]
@@ -30,12 +309,16 @@ pub fn op_node_http_request<P>(
   #[string] url: String,
   #[serde] headers: Vec<(ByteString, ByteString)>,
   #[smi] client_rid: Option<u32>,
+  #[smi] agent_id: Option<u32>,
   #[smi] body: Option<ResourceId>,
 ) -> Result<FetchReturn, AnyError>
 where
   P: crate::NodePermissions + 'static,
 {
-  let client = if let Some(rid) = client_rid {
+  let agent = agent_id.map(|id| http_agent(state, id)).transpose()?;
+  let client = if let Some(agent) = &agent {
+    agent.client.clone()
+  } else if let Some(rid) = client_rid {
     let r = state.resource_table.get::<HttpClientResource>(rid)?;
     r.client.clone()
   } else {
@@ -44,6 +327,11 @@ where
 
   let method = Method::from_bytes(&method)?;
   let url = Url::parse(&url)?;
+  let origin = format!(
+    "{}:{}",
+    url.host_str().unwrap_or_default(),
+    url.port_or_known_default().unwrap_or_default()
+  );
 
   {
     let permissions = state.borrow_mut::<P>();
@@ -78,11 +366,22 @@ where
   let cancel_handle_ = cancel_handle.clone();
 
   let fut = async move {
-    request
-      .send()
-      .or_cancel(cancel_handle_)
-      .await
-      .map(|res| res.map_err(|err| type_error(err.to_string())))
+    async move {
+      let slot = match agent {
+        Some(agent) => Some(agent.acquire(origin).await?),
+        None => None,
+      };
+      let response = request
+        .send()
+        .await
+        .map_err(|err| type_error(err.to_string()))?;
+      match slot {
+        Some(slot) => hold_slot(response, slot),
+        None => Ok(response),
+      }
+    }
+    .or_cancel(cancel_handle_)
+    .await
   };
 
   let request_rid = state
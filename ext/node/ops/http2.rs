@@ -5,11 +5,15 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::task::Poll;
+use std::time::Instant;
 
 use bytes::Bytes;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::future::poll_fn;
+use deno_core::futures::FutureExt;
 use deno_core::op2;
+use deno_core::serde::Deserialize;
 use deno_core::serde::Serialize;
 use deno_core::AsyncRefCell;
 use deno_core::BufView;
@@ -32,8 +36,124 @@ use http_v02::Response;
 use http_v02::StatusCode;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
+use tokio::sync::mpsc;
 use url::Url;
 
+/// The settings of `http2.connect()` and `http2.createServer()`, those left
+/// out keep the defaults of h2.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Http2Settings {
+  header_table_size: Option<u32>,
+  max_concurrent_streams: Option<u32>,
+  initial_window_size: Option<u32>,
+  max_frame_size: Option<u32>,
+  max_header_list_size: Option<u32>,
+  enable_connect_protocol: Option<bool>,
+}
+
+impl Http2Settings {
+  fn client_builder(&self) -> h2::client::Builder {
+    let mut builder = h2::client::Builder::new();
+    // pushed streams aren't surfaced by `ClientHttp2Session` yet, so the
+    // server is told not to send them
+    builder.enable_push(false);
+    if let Some(size) = self.header_table_size {
+      builder.header_table_size(size);
+    }
+    if let Some(max) = self.max_concurrent_streams {
+      builder.max_concurrent_streams(max);
+    }
+    if let Some(size) = self.initial_window_size {
+      builder.initial_window_size(size);
+    }
+    if let Some(max) = self.max_frame_size {
+      builder.max_frame_size(max);
+    }
+    if let Some(max) = self.max_header_list_size {
+      builder.max_header_list_size(max);
+    }
+    builder
+  }
+
+  fn server_builder(&self) -> h2::server::Builder {
+    let mut builder = h2::server::Builder::new();
+    if let Some(max) = self.max_concurrent_streams {
+      builder.max_concurrent_streams(max);
+    }
+    if let Some(size) = self.initial_window_size {
+      builder.initial_window_size(size);
+    }
+    if let Some(max) = self.max_frame_size {
+      builder.max_frame_size(max);
+    }
+    if let Some(max) = self.max_header_list_size {
+      builder.max_header_list_size(max);
+    }
+    if self.enable_connect_protocol == Some(true) {
+      builder.enable_connect_protocol();
+    }
+    builder
+  }
+}
+
+/// A change to a connection, applied by the op polling it.
+enum SessionControl {
+  InitialWindowSize(u32),
+  TargetWindowSize(u32),
+  EnableConnectProtocol,
+  GoAway(h2::Reason),
+}
+
+/// The handles of a session usable while its connection is being polled.
+pub struct Http2SessionControl {
+  ping_pong: AsyncRefCell<Option<h2::PingPong>>,
+  tx: mpsc::UnboundedSender<SessionControl>,
+}
+
+impl Http2SessionControl {
+  fn new(
+    ping_pong: Option<h2::PingPong>,
+  ) -> (Rc<Self>, mpsc::UnboundedReceiver<SessionControl>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let control = Self {
+      ping_pong: AsyncRefCell::new(ping_pong),
+      tx,
+    };
+    (Rc::new(control), rx)
+  }
+
+  fn send(&self, control: SessionControl) {
+    // the connection is gone when nothing receives the changes anymore
+    let _ = self.tx.send(control);
+  }
+}
+
+fn session_control(
+  state: &OpState,
+  rid: ResourceId,
+) -> Result<Rc<Http2SessionControl>, AnyError> {
+  if let Ok(conn) = state.resource_table.get::<Http2ClientConn>(rid) {
+    return Ok(conn.control.clone());
+  }
+  let conn = state.resource_table.get::<Http2ServerConnection>(rid)?;
+  Ok(conn.control.clone())
+}
+
+fn header_map(
+  headers: Vec<(ByteString, ByteString)>,
+) -> Result<HeaderMap, AnyError> {
+  let mut map = HeaderMap::with_capacity(headers.len());
+  for (name, value) in headers {
+    let name = HeaderName::from_bytes(&name)
+      .map_err(|_| type_error(format!("Invalid header name: {name:?}")))?;
+    let value = HeaderValue::from_bytes(&value)
+      .map_err(|_| type_error(format!("Invalid value of header {name}")))?;
+    map.append(name, value);
+  }
+  Ok(map)
+}
+
 pub struct Http2Client {
   pub client: AsyncRefCell<h2::client::SendRequest<BufView>>,
   pub url: Url,
@@ -45,9 +165,10 @@ impl Resource for Http2Client {
   }
 }
 
-#[derive(Debug)]
 pub struct Http2ClientConn {
   pub conn: AsyncRefCell<h2::client::Connection<NetworkStream, BufView>>,
+  control: Rc<Http2SessionControl>,
+  control_rx: AsyncRefCell<mpsc::UnboundedReceiver<SessionControl>>,
   cancel_handle: CancelHandle,
 }
 
@@ -88,9 +209,10 @@ impl Resource for Http2ClientResponseBody {
   }
 }
 
-#[derive(Debug)]
 pub struct Http2ServerConnection {
   pub conn: AsyncRefCell<h2::server::Connection<NetworkStream, BufView>>,
+  control: Rc<Http2SessionControl>,
+  control_rx: AsyncRefCell<mpsc::UnboundedReceiver<SessionControl>>,
 }
 
 impl Resource for Http2ServerConnection {
@@ -99,8 +221,25 @@ impl Resource for Http2ServerConnection {
   }
 }
 
+enum ServerResponder {
+  Stream(h2::server::SendResponse<BufView>),
+  Pushed(h2::server::SendPushedResponse<BufView>),
+}
+
+/// The response of a stream accepted by the server, or of one it pushed.
 pub struct Http2ServerSendResponse {
-  pub send_response: AsyncRefCell<h2::server::SendResponse<BufView>>,
+  send_response: AsyncRefCell<ServerResponder>,
+  /// The body, once the headers of the response are sent.
+  stream: AsyncRefCell<Option<h2::SendStream<BufView>>>,
+}
+
+impl Http2ServerSendResponse {
+  fn new(send_response: ServerResponder) -> Self {
+    Self {
+      send_response: AsyncRefCell::new(send_response),
+      stream: AsyncRefCell::new(None),
+    }
+  }
 }
 
 impl Resource for Http2ServerSendResponse {
@@ -115,6 +254,7 @@ pub async fn op_http2_connect(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
   #[string] url: String,
+  #[serde] settings: Http2Settings,
 ) -> Result<(ResourceId, ResourceId), AnyError> {
   // No permission check necessary because we're using an existing connection
   let network_stream = {
@@ -124,8 +264,9 @@ pub async fn op_http2_connect(
 
   let url = Url::parse(&url)?;
 
-  let (client, conn) =
-    h2::client::Builder::new().handshake(network_stream).await?;
+  let (client, mut conn) =
+    settings.client_builder().handshake(network_stream).await?;
+  let (control, control_rx) = Http2SessionControl::new(conn.ping_pong());
   let mut state = state.borrow_mut();
   let client_rid = state.resource_table.add(Http2Client {
     client: AsyncRefCell::new(client),
@@ -133,6 +274,8 @@ pub async fn op_http2_connect(
   });
   let conn_rid = state.resource_table.add(Http2ClientConn {
     conn: AsyncRefCell::new(conn),
+    control,
+    control_rx: AsyncRefCell::new(control_rx),
     cancel_handle: CancelHandle::new(),
   });
   Ok((client_rid, conn_rid))
@@ -143,17 +286,21 @@ pub async fn op_http2_connect(
 pub async fn op_http2_listen(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
+  #[serde] settings: Http2Settings,
 ) -> Result<ResourceId, AnyError> {
   let stream =
     take_network_stream_resource(&mut state.borrow_mut().resource_table, rid)?;
 
-  let conn = h2::server::Builder::new().handshake(stream).await?;
+  let mut conn = settings.server_builder().handshake(stream).await?;
+  let (control, control_rx) = Http2SessionControl::new(conn.ping_pong());
   Ok(
     state
       .borrow_mut()
       .resource_table
       .add(Http2ServerConnection {
         conn: AsyncRefCell::new(conn),
+        control,
+        control_rx: AsyncRefCell::new(control_rx),
       }),
   )
 }
@@ -164,7 +311,7 @@ pub async fn op_http2_accept(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
 ) -> Result<
-  Option<(Vec<(ByteString, ByteString)>, ResourceId, ResourceId)>,
+  Option<(Vec<(ByteString, ByteString)>, ResourceId, ResourceId, u32)>,
   AnyError,
 > {
   let resource = state
@@ -172,8 +319,34 @@ pub async fn op_http2_accept(
     .resource_table
     .get::<Http2ServerConnection>(rid)?;
   let mut conn = RcRef::map(&resource, |r| &r.conn).borrow_mut().await;
-  if let Some(res) = conn.accept().await {
-    let (req, resp) = res?;
+  let mut control_rx =
+    RcRef::map(&resource, |r| &r.control_rx).borrow_mut().await;
+  // accepting the streams is what drives the connection, so the changes to
+  // it are applied here
+  let accepted = poll_fn(|cx| -> Poll<Result<_, AnyError>> {
+    while let Poll::Ready(Some(control)) = control_rx.poll_recv(cx) {
+      match control {
+        SessionControl::InitialWindowSize(size) => {
+          conn.set_initial_window_size(size)?
+        }
+        SessionControl::TargetWindowSize(size) => {
+          conn.set_target_window_size(size)
+        }
+        SessionControl::EnableConnectProtocol => {
+          conn.enable_connect_protocol()?
+        }
+        SessionControl::GoAway(h2::Reason::NO_ERROR) => {
+          conn.graceful_shutdown()
+        }
+        SessionControl::GoAway(reason) => conn.abrupt_shutdown(reason),
+      }
+    }
+    conn
+      .poll_accept(cx)
+      .map(|accepted| Ok(accepted.transpose()?))
+  })
+  .await?;
+  if let Some((req, resp)) = accepted {
     let (parts, body) = req.into_parts();
     let (trailers_tx, trailers_rx) = tokio::sync::oneshot::channel();
     let stm = state
@@ -212,48 +385,175 @@ pub async fn op_http2_accept(
       req_headers.push((key.as_str().into(), val.as_bytes().into()));
     }
 
+    let stream_id = resp.stream_id();
     let resp = state
       .borrow_mut()
       .resource_table
-      .add(Http2ServerSendResponse {
-        send_response: AsyncRefCell::new(resp),
-      });
+      .add(Http2ServerSendResponse::new(ServerResponder::Stream(resp)));
 
-    Ok(Some((req_headers, stm, resp)))
+    Ok(Some((req_headers, stm, resp, stream_id.into())))
   } else {
     Ok(None)
   }
 }
 
+/// Sends the headers of the response, the body and the trailers follow with
+/// `op_http2_server_send_data()` and `op_http2_server_send_trailers()`
+/// unless `end_stream` is set.
 #[op2(async)]
-#[serde]
 pub async fn op_http2_send_response(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
   #[smi] status: u16,
   #[serde] headers: Vec<(ByteString, ByteString)>,
-) -> Result<(ResourceId, u32), AnyError> {
+  end_stream: bool,
+) -> Result<(), AnyError> {
   let resource = state
     .borrow()
     .resource_table
     .get::<Http2ServerSendResponse>(rid)?;
-  let mut send_response = RcRef::map(resource, |r| &r.send_response)
+  let mut send_response = RcRef::map(&resource, |r| &r.send_response)
     .borrow_mut()
     .await;
   let mut response = Response::new(());
-  if let Ok(status) = StatusCode::from_u16(status) {
-    *response.status_mut() = status;
+  *response.status_mut() = StatusCode::from_u16(status)
+    .map_err(|_| type_error(format!("Invalid status code: {status}")))?;
+  *response.headers_mut() = header_map(headers)?;
+
+  let stream = match &mut *send_response {
+    ServerResponder::Stream(send_response) => {
+      send_response.send_response(response, end_stream)?
+    }
+    ServerResponder::Pushed(send_response) => {
+      send_response.send_response(response, end_stream)?
+    }
+  };
+  if !end_stream {
+    *RcRef::map(&resource, |r| &r.stream).borrow_mut().await = Some(stream);
   }
-  for (name, value) in headers {
-    response.headers_mut().append(
-      HeaderName::from_lowercase(&name).unwrap(),
-      HeaderValue::from_bytes(&value).unwrap(),
-    );
+  Ok(())
+}
+
+#[op2(async)]
+pub async fn op_http2_server_send_data(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] data: JsBuffer,
+  end_stream: bool,
+) -> Result<(), AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<Http2ServerSendResponse>(rid)?;
+  let mut stream = RcRef::map(&resource, |r| &r.stream).borrow_mut().await;
+  let Some(stream) = stream.as_mut() else {
+    return Err(type_error("The headers of the response aren't sent"));
+  };
+  stream.send_data(data.to_vec().into(), end_stream)?;
+  Ok(())
+}
+
+/// Ends the stream with the trailers, or with an empty DATA frame when
+/// there are none.
+#[op2(async)]
+pub async fn op_http2_server_send_trailers(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] trailers: Vec<(ByteString, ByteString)>,
+) -> Result<(), AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<Http2ServerSendResponse>(rid)?;
+  let mut stream = RcRef::map(&resource, |r| &r.stream).borrow_mut().await;
+  let Some(stream) = stream.as_mut() else {
+    return Err(type_error("The headers of the response aren't sent"));
+  };
+  if trailers.is_empty() {
+    stream.send_data(BufView::empty(), true)?;
+  } else {
+    stream.send_trailers(header_map(trailers)?)?;
   }
+  Ok(())
+}
 
-  let stream = send_response.send_response(response, false)?;
-  let stream_id = stream.stream_id();
+#[op2(async)]
+pub async fn op_http2_server_reset_stream(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[smi] code: u32,
+) -> Result<(), AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<Http2ServerSendResponse>(rid)?;
+  let reason = h2::Reason::from(code);
+  if let Some(stream) = RcRef::map(&resource, |r| &r.stream)
+    .borrow_mut()
+    .await
+    .as_mut()
+  {
+    stream.send_reset(reason);
+    return Ok(());
+  }
+  match &mut *RcRef::map(&resource, |r| &r.send_response)
+    .borrow_mut()
+    .await
+  {
+    ServerResponder::Stream(send_response) => send_response.send_reset(reason),
+    ServerResponder::Pushed(send_response) => send_response.send_reset(reason),
+  }
+  Ok(())
+}
 
+/// Promises a request of the client on the stream, whose response is sent
+/// like the one of an accepted stream.
+#[op2(async)]
+#[serde]
+pub async fn op_http2_server_push_stream(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] mut pseudo_headers: HashMap<String, String>,
+  #[serde] headers: Vec<(ByteString, ByteString)>,
+) -> Result<(ResourceId, u32), AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<Http2ServerSendResponse>(rid)?;
+  let mut send_response = RcRef::map(&resource, |r| &r.send_response)
+    .borrow_mut()
+    .await;
+  let ServerResponder::Stream(send_response) = &mut *send_response else {
+    return Err(type_error(
+      "A push stream cannot initiate another push stream",
+    ));
+  };
+
+  let method = pseudo_headers
+    .remove(":method")
+    .unwrap_or_else(|| "GET".to_string());
+  let scheme = pseudo_headers
+    .remove(":scheme")
+    .unwrap_or_else(|| "https".to_string());
+  let authority = pseudo_headers.remove(":authority").unwrap_or_default();
+  let path = pseudo_headers
+    .remove(":path")
+    .unwrap_or_else(|| "/".to_string());
+  let mut request = http_v02::Request::builder()
+    .method(method.as_str())
+    .uri(format!("{scheme}://{authority}{path}"))
+    .body(())?;
+  *request.headers_mut() = header_map(headers)?;
+
+  let pushed = send_response.push_request(request)?;
+  let stream_id = pushed.stream_id();
+  let rid =
+    state
+      .borrow_mut()
+      .resource_table
+      .add(Http2ServerSendResponse::new(ServerResponder::Pushed(
+        pushed,
+      )));
   Ok((rid, stream_id.into()))
 }
 
@@ -265,9 +565,28 @@ pub async fn op_http2_poll_client_connection(
   let resource = state.borrow().resource_table.get::<Http2ClientConn>(rid)?;
 
   let cancel_handle = RcRef::map(resource.clone(), |this| &this.cancel_handle);
-  let mut conn = RcRef::map(resource, |this| &this.conn).borrow_mut().await;
+  let mut conn = RcRef::map(&resource, |this| &this.conn).borrow_mut().await;
+  let mut control_rx = RcRef::map(&resource, |this| &this.control_rx)
+    .borrow_mut()
+    .await;
+  let poll = poll_fn(|cx| -> Poll<Result<(), AnyError>> {
+    while let Poll::Ready(Some(control)) = control_rx.poll_recv(cx) {
+      match control {
+        SessionControl::InitialWindowSize(size) => {
+          conn.set_initial_window_size(size)?
+        }
+        SessionControl::TargetWindowSize(size) => {
+          conn.set_target_window_size(size)
+        }
+        // the client sends its GOAWAY frame once it's closed and done
+        // with its streams, these are only meaningful for servers
+        SessionControl::EnableConnectProtocol | SessionControl::GoAway(_) => {}
+      }
+    }
+    conn.poll_unpin(cx).map_err(AnyError::from)
+  });
 
-  match (&mut *conn).or_cancel(cancel_handle).await {
+  match poll.or_cancel(cancel_handle).await {
     Ok(result) => result?,
     Err(_) => {
       // TODO(bartlomieju): probably need a better mechanism for closing the connection
@@ -506,6 +825,8 @@ pub async fn op_http2_client_get_response_body_chunk(
   loop {
     match poll_fn(|cx| poll_data_or_trailers(cx, &mut body)).await? {
       DataOrTrailers::Data(data) => {
+        // lets the peer send more once the chunk is read
+        body.flow_control().release_capacity(data.len())?;
         return Ok((Some(data.to_vec()), false));
       }
       DataOrTrailers::Trailers(trailers) => {
@@ -561,3 +882,62 @@ pub async fn op_http2_client_get_response_trailers(
     Ok(None)
   }
 }
+
+// The ops below take the rid of either a client or a server connection.
+
+/// Sends a PING frame, resolving with the round trip time in milliseconds.
+#[op2(async)]
+pub async fn op_http2_session_ping(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<f64, AnyError> {
+  let control = session_control(&state.borrow(), rid)?;
+  let mut ping_pong = RcRef::map(&control, |c| &c.ping_pong).borrow_mut().await;
+  let Some(ping_pong) = ping_pong.as_mut() else {
+    return Err(type_error("The session can't send pings"));
+  };
+  let start = Instant::now();
+  ping_pong.send_ping(h2::Ping::opaque())?;
+  poll_fn(|cx| ping_pong.poll_pong(cx)).await?;
+  Ok(start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// Only the initial window size and the extended CONNECT protocol can
+/// change once the connection is established.
+#[op2]
+pub fn op_http2_session_settings(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[serde] settings: Http2Settings,
+) -> Result<(), AnyError> {
+  let control = session_control(state, rid)?;
+  if let Some(size) = settings.initial_window_size {
+    control.send(SessionControl::InitialWindowSize(size));
+  }
+  if settings.enable_connect_protocol == Some(true) {
+    control.send(SessionControl::EnableConnectProtocol);
+  }
+  Ok(())
+}
+
+#[op2(fast)]
+pub fn op_http2_session_set_local_window_size(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] size: u32,
+) -> Result<(), AnyError> {
+  session_control(state, rid)?.send(SessionControl::TargetWindowSize(size));
+  Ok(())
+}
+
+/// Shuts the server connection down, letting the open streams finish for
+/// `NGHTTP2_NO_ERROR` and dropping them for the other codes.
+#[op2(fast)]
+pub fn op_http2_session_goaway(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] code: u32,
+) -> Result<(), AnyError> {
+  session_control(state, rid)?.send(SessionControl::GoAway(code.into()));
+  Ok(())
+}
@@ -19,17 +19,22 @@ mod impl_ {
   use std::task::Poll;
 
   use deno_core::error::bad_resource_id;
+  use deno_core::error::range_error;
   use deno_core::error::AnyError;
   use deno_core::op2;
   use deno_core::serde_json;
   use deno_core::AsyncRefCell;
   use deno_core::CancelFuture;
   use deno_core::CancelHandle;
+  use deno_core::JsBuffer;
   use deno_core::OpState;
   use deno_core::RcRef;
   use deno_core::ResourceId;
+  use deno_core::ToJsBuffer;
   use pin_project_lite::pin_project;
   use tokio::io::AsyncBufRead;
+  use tokio::io::AsyncBufReadExt;
+  use tokio::io::AsyncReadExt;
   use tokio::io::AsyncWriteExt;
   use tokio::io::BufReader;
 
@@ -43,20 +48,27 @@ mod impl_ {
   #[cfg(windows)]
   type NamedPipeClient = tokio::net::windows::named_pipe::NamedPipeClient;
 
-  // Open IPC pipe from bootstrap options.
+  // Open IPC pipe from bootstrap options, along with the serialization of
+  // its messages set by the parent process: "json" or "advanced".
   #[op2]
-  #[smi]
+  #[serde]
   pub fn op_node_child_ipc_pipe(
     state: &mut OpState,
-  ) -> Result<Option<ResourceId>, AnyError> {
+  ) -> Result<Option<(ResourceId, String)>, AnyError> {
     let fd = match state.try_borrow_mut::<crate::ChildPipeFd>() {
       Some(child_pipe_fd) => child_pipe_fd.0,
       None => return Ok(None),
     };
 
-    Ok(Some(
+    let serialization = std::env::var("NODE_CHANNEL_SERIALIZATION_MODE")
+      .unwrap_or_else(|_| "json".to_string());
+    // Remove so that child processes don't inherit this environment variable.
+    std::env::remove_var("NODE_CHANNEL_SERIALIZATION_MODE");
+
+    Ok(Some((
       state.resource_table.add(IpcJsonStreamResource::new(fd)?),
-    ))
+      serialization,
+    )))
   }
 
   #[op2(async)]
@@ -92,6 +104,41 @@ mod impl_ {
     Ok(msgs)
   }
 
+  #[op2(async)]
+  pub async fn op_node_ipc_write_advanced(
+    state: Rc<RefCell<OpState>>,
+    #[smi] rid: ResourceId,
+    #[buffer] frame: JsBuffer,
+  ) -> Result<(), AnyError> {
+    let stream = state
+      .borrow()
+      .resource_table
+      .get::<IpcJsonStreamResource>(rid)
+      .map_err(|_| bad_resource_id())?;
+    stream.write_frame(&frame).await?;
+    Ok(())
+  }
+
+  /// Reads a message of the advanced serialization, `null` once the pipe is
+  /// closed.
+  #[op2(async)]
+  #[serde]
+  pub async fn op_node_ipc_read_advanced(
+    state: Rc<RefCell<OpState>>,
+    #[smi] rid: ResourceId,
+  ) -> Result<Option<ToJsBuffer>, AnyError> {
+    let stream = state
+      .borrow()
+      .resource_table
+      .get::<IpcJsonStreamResource>(rid)
+      .map_err(|_| bad_resource_id())?;
+
+    let cancel = stream.cancel.clone();
+    let mut stream = RcRef::map(stream, |r| &r.read_half).borrow_mut().await;
+    let frame = stream.read_frame().or_cancel(cancel).await??;
+    Ok(frame.map(Into::into))
+  }
+
   pub struct IpcJsonStreamResource {
     read_half: AsyncRefCell<IpcJsonStream>,
     #[cfg(unix)]
@@ -179,6 +226,20 @@ mod impl_ {
       write_half.write_all(&buf).await?;
       Ok(())
     }
+
+    /// Writes a message of the advanced serialization, a value serialized by
+    /// v8 preceded by its length as a big endian `u32`.
+    async fn write_frame(self: Rc<Self>, frame: &[u8]) -> Result<(), AnyError> {
+      let len = u32::try_from(frame.len())
+        .map_err(|_| range_error("The IPC message is too large"))?;
+      let mut write_half =
+        RcRef::map(self, |r| &r.write_half).borrow_mut().await;
+      let mut buf = Vec::with_capacity(4 + frame.len());
+      buf.extend_from_slice(&len.to_be_bytes());
+      buf.extend_from_slice(frame);
+      write_half.write_all(&buf).await?;
+      Ok(())
+    }
   }
 
   #[inline]
@@ -252,6 +313,18 @@ mod impl_ {
 
       Ok(json)
     }
+
+    async fn read_frame(&mut self) -> Result<Option<Vec<u8>>, AnyError> {
+      if self.pipe.fill_buf().await?.is_empty() {
+        // EOF.
+        return Ok(None);
+      }
+
+      let len = self.pipe.read_u32().await?;
+      let mut frame = vec![0; len as usize];
+      self.pipe.read_exact(&mut frame).await?;
+      Ok(Some(frame))
+    }
   }
 
   pin_project! {
@@ -555,6 +628,34 @@ mod impl_ {
       Ok(())
     }
 
+    #[tokio::test]
+    async fn unix_ipc_advanced() -> Result<(), Box<dyn std::error::Error>> {
+      let (ipc, mut fd2) = pair().await;
+      let child = tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+        use tokio::io::AsyncWriteExt;
+
+        const EXPECTED: &[u8] = b"\0\0\0\x05hello";
+        let mut buf = [0u8; EXPECTED.len()];
+        let n = fd2.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..n], EXPECTED);
+        // A message split across writes, then the end of the pipe.
+        fd2.write_all(b"\0\0\0\x05wo").await?;
+        fd2.write_all(b"rld").await?;
+        Ok::<_, std::io::Error>(())
+      });
+
+      ipc.clone().write_frame(b"hello").await?;
+
+      let mut ipc = RcRef::map(ipc, |r| &r.read_half).borrow_mut().await;
+      assert_eq!(ipc.read_frame().await?.as_deref(), Some(&b"world"[..]));
+
+      child.await??;
+      assert_eq!(ipc.read_frame().await?, None);
+
+      Ok(())
+    }
+
     #[test]
     fn memchr() {
       let str = b"hello world";
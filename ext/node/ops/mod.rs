@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+pub mod blocklist;
 pub mod crypto;
+pub mod dns;
 pub mod fs;
 pub mod http;
 pub mod http2;
@@ -1,7 +1,12 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use deno_core::error::range_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::v8;
+use deno_core::v8::ValueDeserializerHelper;
+use deno_core::v8::ValueSerializerHelper;
+use deno_core::JsBuffer;
 
 #[op2(fast)]
 pub fn op_v8_cached_data_version_tag() -> u32 {
@@ -32,6 +37,273 @@ pub fn op_v8_get_heap_statistics(
   buffer[13] = stats.external_memory() as f64;
 }
 
+/// Calls `delegate[name](...args)`, the hooks of the `Serializer` and the
+/// `Deserializer` of `node:v8`. `None` when it threw.
+fn call_delegate<'s>(
+  scope: &mut v8::HandleScope<'s>,
+  delegate: v8::Local<v8::Object>,
+  name: &str,
+  args: &[v8::Local<v8::Value>],
+) -> Option<v8::Local<'s, v8::Value>> {
+  let key = v8::String::new(scope, name)?;
+  let func = delegate.get(scope, key.into())?;
+  let func = v8::Local::<v8::Function>::try_from(func).ok()?;
+  func.call(scope, delegate.into(), args)
+}
+
+struct SerializerDelegate<'a> {
+  delegate: v8::Local<'a, v8::Object>,
+}
+
+impl<'a> v8::ValueSerializerImpl for SerializerDelegate<'a> {
+  fn throw_data_clone_error<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    message: v8::Local<'s, v8::String>,
+  ) {
+    if let Some(error) = call_delegate(
+      scope,
+      self.delegate,
+      "getDataCloneError",
+      &[message.into()],
+    ) {
+      scope.throw_exception(error);
+    }
+  }
+
+  fn write_host_object<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    object: v8::Local<'s, v8::Object>,
+    value_serializer: &mut dyn v8::ValueSerializerHelper,
+  ) -> Option<bool> {
+    // the bytes written by `_writeHostObject()`
+    let bytes =
+      call_delegate(scope, self.delegate, "writeHostObject", &[object.into()])?;
+    let bytes = v8::Local::<v8::Uint8Array>::try_from(bytes).ok()?;
+    let mut buf = vec![0; bytes.byte_length()];
+    bytes.copy_contents(&mut buf);
+    value_serializer.write_raw_bytes(&buf);
+    Some(true)
+  }
+
+  fn get_shared_array_buffer_id<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    shared_array_buffer: v8::Local<'s, v8::SharedArrayBuffer>,
+  ) -> Option<u32> {
+    let Some(id) = call_delegate(
+      scope,
+      self.delegate,
+      "getSharedArrayBufferId",
+      &[shared_array_buffer.into()],
+    ) else {
+      // V8 bails out on the pending exception before using the id
+      return Some(0);
+    };
+    // without `_getSharedArrayBufferId()`, V8 throws a DataCloneError
+    if id.is_undefined() {
+      return None;
+    }
+    id.uint32_value(scope)
+  }
+
+  fn get_wasm_module_transfer_id(
+    &mut self,
+    scope: &mut v8::HandleScope<'_>,
+    _module: v8::Local<v8::WasmModuleObject>,
+  ) -> Option<u32> {
+    let message = v8::String::new(scope, "#<Module> could not be cloned.")?;
+    self.throw_data_clone_error(scope, message);
+    None
+  }
+}
+
+/// The header of the V8 wire format, with its latest version.
+#[op2]
+#[buffer]
+pub fn op_v8_serializer_header(scope: &mut v8::HandleScope) -> Vec<u8> {
+  let delegate = v8::Object::new(scope);
+  let mut serializer =
+    v8::ValueSerializer::new(scope, Box::new(SerializerDelegate { delegate }));
+  serializer.write_header();
+  serializer.release()
+}
+
+/// Serializes a value, without the header, for `Serializer.writeValue()`.
+/// `transfers` has pairs of transfer ids and array buffers.
+#[op2(reentrant)]
+#[buffer]
+pub fn op_v8_serialize_value(
+  scope: &mut v8::HandleScope,
+  value: v8::Local<v8::Value>,
+  delegate: v8::Local<v8::Object>,
+  transfers: v8::Local<v8::Array>,
+) -> Result<Vec<u8>, AnyError> {
+  let mut serializer =
+    v8::ValueSerializer::new(scope, Box::new(SerializerDelegate { delegate }));
+  for i in (0..transfers.length()).step_by(2) {
+    let id = transfers.get_index(scope, i).unwrap();
+    let id = id.uint32_value(scope).unwrap_or_default();
+    let buffer = transfers.get_index(scope, i + 1).unwrap();
+    let buffer = v8::Local::<v8::ArrayBuffer>::try_from(buffer)
+      .map_err(|_| type_error("Transferred value is not an ArrayBuffer"))?;
+    serializer.transfer_array_buffer(id, buffer);
+  }
+
+  let scope = &mut v8::TryCatch::new(scope);
+  let context = scope.get_current_context();
+  let ret = serializer.write_value(context, value);
+  if scope.has_caught() || scope.has_terminated() {
+    scope.rethrow();
+    // discarded, an exception was thrown
+    return Ok(vec![]);
+  }
+  match ret {
+    Some(true) => Ok(serializer.release()),
+    _ => Err(type_error("Failed to serialize value")),
+  }
+}
+
+struct DeserializerDelegate<'a> {
+  delegate: v8::Local<'a, v8::Object>,
+  transfers: v8::Local<'a, v8::Array>,
+  /// The address of the data read by V8, which starts with a copy of the
+  /// header followed by the input from `offset` on.
+  data: usize,
+  header_len: usize,
+  offset: usize,
+}
+
+impl<'a> DeserializerDelegate<'a> {
+  /// The offset in the input of the next byte read by V8.
+  fn position(
+    &self,
+    value_deserializer: &mut dyn v8::ValueDeserializerHelper,
+  ) -> Option<usize> {
+    let next = value_deserializer.read_raw_bytes(0)?.as_ptr() as usize;
+    Some(self.offset + next - self.data - self.header_len)
+  }
+}
+
+impl<'a> v8::ValueDeserializerImpl for DeserializerDelegate<'a> {
+  fn read_host_object<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    value_deserializer: &mut dyn v8::ValueDeserializerHelper,
+  ) -> Option<v8::Local<'s, v8::Object>> {
+    let position = self.position(value_deserializer)?;
+    let offset = v8::Number::new(scope, position as f64);
+    // `[object, end]` from `_readHostObject()`, which reads the input from
+    // `offset` up to `end` by itself
+    let result =
+      call_delegate(scope, self.delegate, "readHostObject", &[offset.into()])?;
+    let result = v8::Local::<v8::Array>::try_from(result).ok()?;
+    let object = result.get_index(scope, 0)?;
+    let object = v8::Local::<v8::Object>::try_from(object).ok()?;
+    let end = result.get_index(scope, 1)?.uint32_value(scope)? as usize;
+    value_deserializer.read_raw_bytes(end.checked_sub(position)?)?;
+    Some(object)
+  }
+
+  fn get_shared_array_buffer_from_id<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    transfer_id: u32,
+  ) -> Option<v8::Local<'s, v8::SharedArrayBuffer>> {
+    for i in (0..self.transfers.length()).step_by(2) {
+      let id = self.transfers.get_index(scope, i)?.uint32_value(scope)?;
+      let buffer = self.transfers.get_index(scope, i + 1)?;
+      if id == transfer_id {
+        if let Ok(buffer) = v8::Local::<v8::SharedArrayBuffer>::try_from(buffer)
+        {
+          return Some(buffer);
+        }
+      }
+    }
+    None
+  }
+
+  fn get_wasm_module_from_id<'s>(
+    &mut self,
+    _scope: &mut v8::HandleScope<'s>,
+    _clone_id: u32,
+  ) -> Option<v8::Local<'s, v8::WasmModuleObject>> {
+    None
+  }
+}
+
+/// Deserializes the value at `offset` of `buffer`, for
+/// `Deserializer.readValue()`, with the format of `version`, 0 when the
+/// input had no header. Returns the value and the offset after it.
+/// `transfers` has pairs of transfer ids and array buffers.
+#[op2(reentrant)]
+pub fn op_v8_deserialize_value<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  #[buffer] buffer: JsBuffer,
+  #[number] offset: usize,
+  version: u32,
+  delegate: v8::Local<v8::Object>,
+  transfers: v8::Local<v8::Array>,
+) -> Result<v8::Local<'a, v8::Value>, AnyError> {
+  let input = buffer
+    .get(offset..)
+    .ok_or_else(|| range_error("Offset is out of bounds"))?;
+  let mut data = vec![];
+  if version > 0 {
+    data.push(0xFF);
+    let mut version = version;
+    while version >= 0x80 {
+      data.push(version as u8 | 0x80);
+      version >>= 7;
+    }
+    data.push(version as u8);
+  }
+  let header_len = data.len();
+  data.extend_from_slice(input);
+
+  let mut deserializer = v8::ValueDeserializer::new(
+    scope,
+    Box::new(DeserializerDelegate {
+      delegate,
+      transfers,
+      data: data.as_ptr() as usize,
+      header_len,
+      offset,
+    }),
+    &data,
+  );
+  let scope = &mut v8::TryCatch::new(scope);
+  let context = scope.get_current_context();
+  if version > 0 && !deserializer.read_header(context).unwrap_or_default() {
+    if scope.rethrow().is_some() {
+      return Ok(v8::undefined(scope).into());
+    }
+    return Err(range_error("Unsupported wire format version"));
+  }
+  for i in (0..transfers.length()).step_by(2) {
+    let id = transfers.get_index(scope, i).unwrap();
+    let id = id.uint32_value(scope).unwrap_or_default();
+    let buffer = transfers.get_index(scope, i + 1).unwrap();
+    if let Ok(buffer) = v8::Local::<v8::ArrayBuffer>::try_from(buffer) {
+      deserializer.transfer_array_buffer(id, buffer);
+    }
+  }
+
+  let Some(value) = deserializer.read_value(context) else {
+    scope.rethrow();
+    // discarded, an exception was thrown
+    return Ok(v8::undefined(scope).into());
+  };
+  let next = deserializer
+    .read_raw_bytes(0)
+    .map(|next| next.as_ptr() as usize)
+    .unwrap_or(data.as_ptr() as usize + data.len());
+  let end = offset + next - data.as_ptr() as usize - header_len;
+  let end = v8::Number::new(scope, end as f64);
+  Ok(v8::Array::new_with_elements(scope, &[value, end.into()]).into())
+}
+
 pub const VM_CONTEXT_INDEX: usize = 0;
 
 fn make_context<'a>(
@@ -15,15 +15,17 @@ impl Script {
   fn new(
     scope: &mut v8::HandleScope,
     source: v8::Local<v8::String>,
+    options: &i::ScriptOptions,
   ) -> Result<Self, AnyError> {
     Ok(Self {
-      inner: i::ContextifyScript::new(scope, source)?,
+      inner: i::ContextifyScript::new(scope, source, options)?,
     })
   }
 
   fn run_in_this_context<'s>(
     &self,
     scope: &'s mut v8::HandleScope,
+    timeout: u32,
   ) -> Result<v8::Local<'s, v8::Value>, AnyError> {
     let context = scope.get_current_context();
 
@@ -31,7 +33,7 @@ impl Script {
     let mut scope = v8::EscapableHandleScope::new(context_scope);
     let result = self
       .inner
-      .eval_machine(&mut scope, context)
+      .eval_machine(&mut scope, context, timeout, None)
       .unwrap_or_else(|| v8::undefined(&mut scope).into());
     Ok(scope.escape(result))
   }
@@ -40,20 +42,22 @@ impl Script {
     &self,
     scope: &mut v8::HandleScope<'s>,
     sandbox: v8::Local<'s, v8::Value>,
+    timeout: u32,
   ) -> Result<v8::Local<'s, v8::Value>, AnyError> {
-    let context = if let Ok(sandbox_obj) = sandbox.try_into() {
+    let (context, microtask_queue) = if let Ok(sandbox_obj) = sandbox.try_into()
+    {
       let context = i::ContextifyContext::from_sandbox_obj(scope, sandbox_obj)
         .ok_or_else(|| type_error("Invalid sandbox object"))?;
-      context.context(scope)
+      (context.context(scope), context.microtask_queue())
     } else {
-      scope.get_current_context()
+      (scope.get_current_context(), None)
     };
 
     let context_scope = &mut v8::ContextScope::new(scope, context);
     let mut scope = v8::EscapableHandleScope::new(context_scope);
     let result = self
       .inner
-      .eval_machine(&mut scope, context)
+      .eval_machine(&mut scope, context, timeout, microtask_queue)
       .unwrap_or_else(|| v8::undefined(&mut scope).into());
     Ok(scope.escape(result))
   }
@@ -63,32 +67,45 @@ impl Script {
 pub fn op_vm_create_script<'a>(
   scope: &mut v8::HandleScope<'a>,
   source: v8::Local<'a, v8::String>,
+  #[string] filename: String,
+  line_offset: i32,
+  column_offset: i32,
 ) -> Result<v8::Local<'a, v8::Object>, AnyError> {
-  let script = Script::new(scope, source)?;
+  let options = i::ScriptOptions {
+    filename,
+    line_offset,
+    column_offset,
+  };
+  let script = Script::new(scope, source, &options)?;
   Ok(deno_core::cppgc::make_cppgc_object(scope, script))
 }
 
+/// `timeout` is in milliseconds, 0 for none.
 #[op2(reentrant)]
 pub fn op_vm_script_run_in_context<'a>(
   scope: &mut v8::HandleScope<'a>,
   #[cppgc] script: &Script,
   sandbox: v8::Local<'a, v8::Value>,
+  timeout: u32,
 ) -> Result<v8::Local<'a, v8::Value>, AnyError> {
-  script.run_in_context(scope, sandbox)
+  script.run_in_context(scope, sandbox, timeout)
 }
 
 #[op2(reentrant)]
 pub fn op_vm_script_run_in_this_context<'a>(
   scope: &'a mut v8::HandleScope,
   #[cppgc] script: &Script,
+  timeout: u32,
 ) -> Result<v8::Local<'a, v8::Value>, AnyError> {
-  script.run_in_this_context(scope)
+  script.run_in_this_context(scope, timeout)
 }
 
 #[op2]
 pub fn op_vm_create_context(
   scope: &mut v8::HandleScope,
   sandbox_obj: v8::Local<v8::Object>,
+  allow_code_gen_strings: bool,
+  own_microtask_queue: bool,
 ) {
   // Don't allow contextifying a sandbox multiple times.
   assert!(!i::ContextifyContext::is_contextify_context(
@@ -96,7 +113,67 @@ pub fn op_vm_create_context(
     sandbox_obj
   ));
 
-  i::ContextifyContext::attach(scope, sandbox_obj);
+  let options = i::ContextOptions {
+    allow_code_gen_strings,
+    own_microtask_queue,
+  };
+  i::ContextifyContext::attach(scope, sandbox_obj, &options);
+}
+
+/// Compiles a function with the body `source` in the context of `sandbox`,
+/// or the current one when it's undefined.
+#[op2(reentrant)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_vm_compile_function<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  source: v8::Local<'a, v8::String>,
+  #[string] filename: String,
+  line_offset: i32,
+  column_offset: i32,
+  #[serde] params: Vec<String>,
+  sandbox: v8::Local<'a, v8::Value>,
+  context_extensions: v8::Local<'a, v8::Array>,
+) -> Result<Option<v8::Local<'a, v8::Function>>, AnyError> {
+  let context = if let Ok(sandbox_obj) = sandbox.try_into() {
+    let context = i::ContextifyContext::from_sandbox_obj(scope, sandbox_obj)
+      .ok_or_else(|| type_error("Invalid sandbox object"))?;
+    context.context(scope)
+  } else {
+    scope.get_current_context()
+  };
+
+  let context_scope = &mut v8::ContextScope::new(scope, context);
+  let mut scope = v8::EscapableHandleScope::new(context_scope);
+  let options = i::ScriptOptions {
+    filename,
+    line_offset,
+    column_offset,
+  };
+  let origin = options.origin(&mut scope);
+  let source = v8::script_compiler::Source::new(source, Some(&origin));
+  let params = params
+    .iter()
+    .map(|param| v8::String::new(&mut scope, param).unwrap())
+    .collect::<Vec<_>>();
+  let mut extensions = Vec::with_capacity(context_extensions.length() as _);
+  for index in 0..context_extensions.length() {
+    let extension = context_extensions
+      .get_index(&mut scope, index)
+      .and_then(|extension| v8::Local::<v8::Object>::try_from(extension).ok())
+      .ok_or_else(|| type_error("Invalid context extension"))?;
+    extensions.push(extension);
+  }
+
+  // a syntax error is thrown in `scope`
+  let function = v8::script_compiler::compile_function(
+    &mut scope,
+    source,
+    &params,
+    &extensions,
+    v8::script_compiler::CompileOptions::NoCompileOptions,
+    v8::script_compiler::NoCacheReason::NoReason,
+  );
+  Ok(function.map(|function| scope.escape(function)))
 }
 
 #[op2]
@@ -130,9 +207,14 @@ mod tests {
     let scope = &mut v8::ContextScope::new(scope, context);
 
     let source = v8::String::new(scope, "1 + 2").unwrap();
-    let script = Script::new(scope, source).unwrap();
+    let options = i::ScriptOptions {
+      filename: "evalmachine.<anonymous>".to_string(),
+      line_offset: 0,
+      column_offset: 0,
+    };
+    let script = Script::new(scope, source, &options).unwrap();
 
-    let result = script.run_in_this_context(scope).unwrap();
+    let result = script.run_in_this_context(scope, 0).unwrap();
     assert!(result.is_number());
   }
 }
@@ -5,6 +5,9 @@ use deno_core::error::AnyError;
 use deno_core::v8;
 use deno_core::v8::MapFnTo;
 use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 pub const PRIVATE_SYMBOL_NAME: v8::OneByteConst =
   v8::String::create_external_onebyte_const(b"node:contextify:context");
@@ -19,8 +22,10 @@ impl ContextifyScript {
   pub fn new(
     scope: &mut v8::HandleScope,
     source_str: v8::Local<v8::String>,
+    options: &ScriptOptions,
   ) -> Result<Self, AnyError> {
-    let source = v8::script_compiler::Source::new(source_str, None);
+    let origin = options.origin(scope);
+    let source = v8::script_compiler::Source::new(source_str, Some(&origin));
 
     let unbound_script = v8::script_compiler::compile_unbound_script(
       scope,
@@ -33,18 +38,49 @@ impl ContextifyScript {
     Ok(Self { script })
   }
 
-  // TODO(littledivy): Support `options`
+  /// Runs the script, terminating it after `timeout` milliseconds when it
+  /// isn't 0. The microtasks of `microtask_queue` are run after the script,
+  /// within the same timeout.
   pub fn eval_machine<'s>(
     &self,
     scope: &mut v8::HandleScope<'s>,
     _context: v8::Local<v8::Context>,
+    timeout: u32,
+    microtask_queue: Option<&v8::MicrotaskQueue>,
   ) -> Option<v8::Local<'s, v8::Value>> {
     let tc_scope = &mut v8::TryCatch::new(scope);
 
     let unbound_script = v8::Local::new(tc_scope, self.script.clone());
     let script = unbound_script.bind_to_current_context(tc_scope);
 
+    let watchdog = (timeout > 0).then(|| Watchdog::start(tc_scope, timeout));
     let result = script.run(tc_scope);
+    if let (Some(_), Some(microtask_queue)) = (result, microtask_queue) {
+      microtask_queue.perform_checkpoint(tc_scope);
+    }
+    let timed_out = watchdog.map(Watchdog::stop).unwrap_or(false);
+
+    if timed_out {
+      tc_scope.cancel_terminate_execution();
+      let message = v8::String::new(
+        tc_scope,
+        &format!("Script execution timed out after {timeout}ms"),
+      )
+      .unwrap();
+      let exception = v8::Exception::error(tc_scope, message);
+      let code_key =
+        v8::String::new_external_onebyte_static(tc_scope, b"code").unwrap();
+      let code = v8::String::new_external_onebyte_static(
+        tc_scope,
+        b"ERR_SCRIPT_EXECUTION_TIMEOUT",
+      )
+      .unwrap();
+      let exception_obj: v8::Local<v8::Object> = exception.try_into().unwrap();
+      exception_obj.set(tc_scope, code_key.into(), code.into());
+      tc_scope.throw_exception(exception);
+      tc_scope.rethrow();
+      return None;
+    }
 
     if tc_scope.has_caught() {
       // If there was an exception thrown during script execution, re-throw it.
@@ -59,27 +95,100 @@ impl ContextifyScript {
   }
 }
 
-#[derive(Debug, Clone)]
+/// The options of `new vm.Script()`, also used by `vm.compileFunction()`.
+pub struct ScriptOptions {
+  pub filename: String,
+  pub line_offset: i32,
+  pub column_offset: i32,
+}
+
+impl ScriptOptions {
+  pub fn origin<'s>(
+    &self,
+    scope: &mut v8::HandleScope<'s>,
+  ) -> v8::ScriptOrigin<'s> {
+    let resource_name = v8::String::new(scope, &self.filename).unwrap();
+    let source_map_url = v8::undefined(scope);
+    v8::ScriptOrigin::new(
+      scope,
+      resource_name.into(),
+      self.line_offset,
+      self.column_offset,
+      false,
+      -1,
+      source_map_url.into(),
+      false,
+      false,
+      false,
+    )
+  }
+}
+
+/// Terminates the execution of the isolate if it isn't stopped in time.
+struct Watchdog {
+  stop: mpsc::Sender<()>,
+  thread: thread::JoinHandle<bool>,
+}
+
+impl Watchdog {
+  fn start(isolate: &v8::Isolate, timeout: u32) -> Self {
+    let handle = isolate.thread_safe_handle();
+    let (stop, stopped) = mpsc::channel();
+    let thread = thread::spawn(move || {
+      match stopped.recv_timeout(Duration::from_millis(timeout as u64)) {
+        Err(mpsc::RecvTimeoutError::Timeout) => handle.terminate_execution(),
+        _ => false,
+      }
+    });
+    Self { stop, thread }
+  }
+
+  /// Returns whether the execution was terminated.
+  fn stop(self) -> bool {
+    let _ = self.stop.send(());
+    self.thread.join().unwrap()
+  }
+}
+
+/// The options of `vm.createContext()`.
+pub struct ContextOptions {
+  pub allow_code_gen_strings: bool,
+  /// Gives the context its own microtask queue, run after every script
+  /// (`microtaskMode: "afterEvaluate"`).
+  pub own_microtask_queue: bool,
+}
+
+#[derive(Debug)]
 pub struct ContextifyContext {
   context: v8::Global<v8::Context>,
   sandbox: v8::Global<v8::Object>,
+  microtask_queue: Option<v8::UniqueRef<v8::MicrotaskQueue>>,
 }
 
 impl ContextifyContext {
   pub fn attach(
     scope: &mut v8::HandleScope,
     sandbox_obj: v8::Local<v8::Object>,
+    options: &ContextOptions,
   ) {
     let tmp = init_global_template(scope);
 
     let context = create_v8_context(scope, tmp, None);
-    Self::from_context(scope, context, sandbox_obj);
+    context.set_allow_generation_from_strings(options.allow_code_gen_strings);
+    let microtask_queue = options.own_microtask_queue.then(|| {
+      let microtask_queue =
+        v8::MicrotaskQueue::new(scope, v8::MicrotasksPolicy::Explicit);
+      context.set_microtask_queue(&microtask_queue);
+      microtask_queue
+    });
+    Self::from_context(scope, context, sandbox_obj, microtask_queue);
   }
 
   fn from_context(
     scope: &mut v8::HandleScope,
     v8_context: v8::Local<v8::Context>,
     sandbox_obj: v8::Local<v8::Object>,
+    microtask_queue: Option<v8::UniqueRef<v8::MicrotaskQueue>>,
   ) {
     let main_context = scope.get_current_context();
     let context_state = main_context
@@ -92,8 +201,14 @@ impl ContextifyContext {
 
     let context = v8::Global::new(scope, v8_context);
     let sandbox = v8::Global::new(scope, sandbox_obj);
-    let wrapper =
-      deno_core::cppgc::make_cppgc_object(scope, Self { context, sandbox });
+    let wrapper = deno_core::cppgc::make_cppgc_object(
+      scope,
+      Self {
+        context,
+        sandbox,
+        microtask_queue,
+      },
+    );
     let ptr = deno_core::cppgc::try_unwrap_cppgc_object::<Self>(wrapper.into())
       .unwrap();
 
@@ -143,6 +258,10 @@ impl ContextifyContext {
     v8::Local::new(scope, &self.context)
   }
 
+  pub fn microtask_queue(&self) -> Option<&v8::MicrotaskQueue> {
+    self.microtask_queue.as_deref()
+  }
+
   fn global_proxy<'s>(
     &self,
     scope: &mut v8::HandleScope<'s>,
@@ -1,18 +1,27 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use brotli::enc::encode::BrotliEncoderParameter;
 use brotli::ffi::compressor::*;
+use brotli::ffi::decompressor::ffi::interface::BrotliDecoderParameter;
 use brotli::ffi::decompressor::ffi::interface::BrotliDecoderResult;
 use brotli::ffi::decompressor::ffi::BrotliDecoderState;
 use brotli::ffi::decompressor::*;
 use brotli::Decompressor;
+use deno_core::error::bad_resource_id;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::serde::Serialize;
 use deno_core::JsBuffer;
 use deno_core::OpState;
 use deno_core::Resource;
 use deno_core::ToJsBuffer;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ffi::c_char;
+use std::ffi::CStr;
+use std::future::Future;
 use std::io::Read;
+use std::rc::Rc;
 
 fn encoder_mode(mode: u32) -> Result<BrotliEncoderMode, AnyError> {
   if mode > 6 {
@@ -111,228 +120,417 @@ pub async fn op_brotli_compress_async(
   .await?
 }
 
-struct BrotliCompressCtx {
-  inst: *mut BrotliEncoderState,
+fn brotli_decompress(buffer: &[u8]) -> Result<ToJsBuffer, AnyError> {
+  let mut output = Vec::with_capacity(4096);
+  let mut decompressor = Decompressor::new(buffer, buffer.len());
+  decompressor.read_to_end(&mut output)?;
+  Ok(output.into())
 }
 
-impl Resource for BrotliCompressCtx {}
+#[op2]
+#[serde]
+pub fn op_brotli_decompress(
+  #[buffer] buffer: &[u8],
+) -> Result<ToJsBuffer, AnyError> {
+  brotli_decompress(buffer)
+}
 
-impl Drop for BrotliCompressCtx {
-  fn drop(&mut self) {
-    // SAFETY: `self.inst` is the current brotli encoder instance.
-    // It is not used after the following call.
-    unsafe { BrotliEncoderDestroyInstance(self.inst) };
-  }
+#[op2(async)]
+#[serde]
+pub async fn op_brotli_decompress_async(
+  #[buffer] buffer: JsBuffer,
+) -> Result<ToJsBuffer, AnyError> {
+  tokio::task::spawn_blocking(move || brotli_decompress(&buffer)).await?
 }
 
-#[op2]
-#[smi]
-pub fn op_create_brotli_compress(
-  state: &mut OpState,
-  #[serde] params: Vec<(u8, i32)>,
-) -> u32 {
-  let inst =
-    // SAFETY: Creates a brotli encoder instance for default allocators.
-    unsafe { BrotliEncoderCreateInstance(None, None, std::ptr::null_mut()) };
-
-  for (key, value) in params {
-    // SAFETY: `key` can range from 0-255.
-    // Any valid u32 can be used for the `value`.
+// The modes of `binding.Brotli`, as in `zlib.constants`.
+const BROTLI_DECODE: i32 = 8;
+const BROTLI_ENCODE: i32 = 9;
+
+/// The value of the parameters `init()` doesn't set.
+const PARAM_UNSET: u32 = u32::MAX;
+
+fn encoder_param(key: usize) -> Option<BrotliEncoderParameter> {
+  Some(match key {
+    0 => BrotliEncoderParameter::BROTLI_PARAM_MODE,
+    1 => BrotliEncoderParameter::BROTLI_PARAM_QUALITY,
+    2 => BrotliEncoderParameter::BROTLI_PARAM_LGWIN,
+    3 => BrotliEncoderParameter::BROTLI_PARAM_LGBLOCK,
+    4 => BrotliEncoderParameter::BROTLI_PARAM_DISABLE_LITERAL_CONTEXT_MODELING,
+    5 => BrotliEncoderParameter::BROTLI_PARAM_SIZE_HINT,
+    6 => BrotliEncoderParameter::BROTLI_PARAM_LARGE_WINDOW,
+    // `BROTLI_PARAM_NPOSTFIX` and `BROTLI_PARAM_NDIRECT` aren't supported by
+    // the encoder
+    _ => return None,
+  })
+}
+
+fn decoder_param(key: usize) -> Option<BrotliDecoderParameter> {
+  Some(match key {
+    0 => BrotliDecoderParameter::BROTLI_DECODER_PARAM_DISABLE_RING_BUFFER_REALLOCATION,
+    1 => BrotliDecoderParameter::BROTLI_DECODER_PARAM_LARGE_WINDOW,
+    _ => return None,
+  })
+}
+
+fn operation(flush: i32) -> Result<BrotliEncoderOperation, AnyError> {
+  Ok(match flush {
+    0 => BrotliEncoderOperation::BROTLI_OPERATION_PROCESS,
+    1 => BrotliEncoderOperation::BROTLI_OPERATION_FLUSH,
+    2 => BrotliEncoderOperation::BROTLI_OPERATION_FINISH,
+    3 => BrotliEncoderOperation::BROTLI_OPERATION_EMIT_METADATA,
+    _ => return Err(type_error("Invalid flush value")),
+  })
+}
+
+#[derive(Clone, Copy)]
+enum Instance {
+  Encoder(*mut BrotliEncoderState),
+  Decoder(*mut BrotliDecoderState),
+}
+
+impl Instance {
+  fn new(encode: bool) -> Option<Self> {
+    // SAFETY: Creates a brotli instance for default allocators.
+    let instance = unsafe {
+      if encode {
+        Self::Encoder(BrotliEncoderCreateInstance(
+          None,
+          None,
+          std::ptr::null_mut(),
+        ))
+      } else {
+        Self::Decoder(CBrotliDecoderCreateInstance(
+          None,
+          None,
+          std::ptr::null_mut(),
+        ))
+      }
+    };
+    let created = match instance {
+      Self::Encoder(inst) => !inst.is_null(),
+      Self::Decoder(inst) => !inst.is_null(),
+    };
+    created.then_some(instance)
+  }
+
+  fn set_params(self, params: &[u32]) -> bool {
+    for (key, &value) in params.iter().enumerate() {
+      if value == PARAM_UNSET {
+        continue;
+      }
+      match self {
+        Self::Encoder(inst) => {
+          let Some(param) = encoder_param(key) else {
+            return false;
+          };
+          // SAFETY: `inst` is a live encoder instance.
+          if unsafe { BrotliEncoderSetParameter(inst, param, value) } == 0 {
+            return false;
+          }
+        }
+        Self::Decoder(inst) => {
+          let Some(param) = decoder_param(key) else {
+            return false;
+          };
+          // SAFETY: `inst` is a live decoder instance.
+          unsafe { CBrotliDecoderSetParameter(inst, param, value) };
+        }
+      }
+    }
+    true
+  }
+
+  fn destroy(self) {
+    // SAFETY: The instance is not used after the following call.
     unsafe {
-      BrotliEncoderSetParameter(inst, encoder_param(key), value as u32);
+      match self {
+        Self::Encoder(inst) => BrotliEncoderDestroyInstance(inst),
+        Self::Decoder(inst) => CBrotliDecoderDestroyInstance(inst),
+      }
     }
   }
+}
 
-  state.resource_table.add(BrotliCompressCtx { inst })
+/// The error of a write, reported to the `onerror` of the handle.
+#[derive(Serialize)]
+pub struct BrotliError {
+  message: &'static str,
+  code: String,
+  errno: i32,
 }
 
-fn encoder_param(param: u8) -> BrotliEncoderParameter {
-  // SAFETY: BrotliEncoderParam is valid for 0-255
-  unsafe { std::mem::transmute(param as u32) }
+struct BrotliStreamInner {
+  encode: bool,
+  /// `None` before `init()`.
+  instance: Option<Instance>,
+  /// Set again on `reset()`.
+  params: Vec<u32>,
+  error: Option<BrotliError>,
 }
 
-#[op2(fast)]
-#[number]
-pub fn op_brotli_compress_stream(
-  state: &mut OpState,
-  #[smi] rid: u32,
-  #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-) -> Result<usize, AnyError> {
-  let ctx = state.resource_table.get::<BrotliCompressCtx>(rid)?;
+impl BrotliStreamInner {
+  fn init(&mut self, params: &[u32]) -> bool {
+    if let Some(instance) = self.instance.take() {
+      instance.destroy();
+    }
+    self.error = None;
+    self.params = params.to_vec();
+    let Some(instance) = Instance::new(self.encode) else {
+      return false;
+    };
+    self.instance = Some(instance);
+    instance.set_params(params)
+  }
+
+  fn reset(&mut self) -> bool {
+    let params = std::mem::take(&mut self.params);
+    self.init(&params)
+  }
+
+  /// Returns the space left in `out` and the input left over. On failure,
+  /// the error is kept for `op_brotli_stream_error`.
+  fn write(
+    &mut self,
+    flush: i32,
+    input: &[u8],
+    out: &mut [u8],
+  ) -> Result<(bool, u32, u32), AnyError> {
+    let instance = self
+      .instance
+      .ok_or_else(|| type_error("write before init"))?;
+    let operation = operation(flush)?;
 
-  // SAFETY: TODO(littledivy)
-  unsafe {
     let mut available_in = input.len();
     let mut next_in = input.as_ptr();
-    let mut available_out = output.len();
-    let mut next_out = output.as_mut_ptr();
-
-    if BrotliEncoderCompressStream(
-      ctx.inst,
-      BrotliEncoderOperation::BROTLI_OPERATION_PROCESS,
-      &mut available_in,
-      &mut next_in,
-      &mut available_out,
-      &mut next_out,
-      std::ptr::null_mut(),
-    ) != 1
-    {
-      return Err(type_error("Failed to compress"));
+    let mut available_out = out.len();
+    let mut next_out = out.as_mut_ptr();
+
+    match instance {
+      Instance::Encoder(inst) => {
+        // SAFETY: `next_in` and `next_out` point to `available_in` and
+        // `available_out` bytes of `input` and `out`.
+        let result = unsafe {
+          BrotliEncoderCompressStream(
+            inst,
+            operation,
+            &mut available_in,
+            &mut next_in,
+            &mut available_out,
+            &mut next_out,
+            std::ptr::null_mut(),
+          )
+        };
+        if result != 1 {
+          self.error = Some(BrotliError {
+            message: "Compression failed",
+            code: "ERR_BROTLI_COMPRESSION_FAILED".to_string(),
+            errno: -1,
+          });
+        }
+      }
+      Instance::Decoder(inst) => {
+        // SAFETY: `next_in` and `next_out` point to `available_in` and
+        // `available_out` bytes of `input` and `out`.
+        let result = unsafe {
+          CBrotliDecoderDecompressStream(
+            inst,
+            &mut available_in,
+            &mut next_in,
+            &mut available_out,
+            &mut next_out,
+            std::ptr::null_mut(),
+          )
+        };
+        match result {
+          BrotliDecoderResult::BROTLI_DECODER_RESULT_ERROR => {
+            // SAFETY: `inst` is a live decoder instance, the error strings
+            // are static and nul terminated.
+            let (errno, name) = unsafe {
+              let code = CBrotliDecoderGetErrorCode(inst);
+              let name = CStr::from_ptr(
+                CBrotliDecoderErrorString(code).cast::<c_char>(),
+              );
+              (code as i32, name.to_string_lossy())
+            };
+            self.error = Some(BrotliError {
+              message: "Decompression failed",
+              // Node's brotli names the codes with a leading underscore
+              code: format!("ERR__{name}"),
+              errno,
+            });
+          }
+          BrotliDecoderResult::BROTLI_DECODER_RESULT_NEEDS_MORE_INPUT
+            if matches!(
+              operation,
+              BrotliEncoderOperation::BROTLI_OPERATION_FINISH
+            ) =>
+          {
+            self.error = Some(BrotliError {
+              message: "unexpected end of file",
+              code: "Z_BUF_ERROR".to_string(),
+              errno: zlib::Z_BUF_ERROR,
+            });
+          }
+          _ => {}
+        }
+      }
     }
 
-    // On progress, next_out is advanced and available_out is reduced.
-    Ok(output.len() - available_out)
+    Ok((
+      self.error.is_none(),
+      available_out as u32,
+      available_in as u32,
+    ))
   }
 }
 
-#[op2(fast)]
-#[number]
-pub fn op_brotli_compress_stream_end(
-  state: &mut OpState,
-  #[smi] rid: u32,
-  #[buffer] output: &mut [u8],
-) -> Result<usize, AnyError> {
-  let ctx = state.resource_table.get::<BrotliCompressCtx>(rid)?;
-
-  // SAFETY: TODO(littledivy)
-  unsafe {
-    let mut available_out = output.len();
-    let mut next_out = output.as_mut_ptr();
-    let mut total_out = 0;
-
-    if BrotliEncoderCompressStream(
-      ctx.inst,
-      BrotliEncoderOperation::BROTLI_OPERATION_FINISH,
-      &mut 0,
-      std::ptr::null_mut(),
-      &mut available_out,
-      &mut next_out,
-      &mut total_out,
-    ) != 1
-    {
-      return Err(type_error("Failed to compress"));
+impl Drop for BrotliStreamInner {
+  fn drop(&mut self) {
+    if let Some(instance) = self.instance.take() {
+      instance.destroy();
     }
-
-    // On finish, next_out is advanced and available_out is reduced.
-    Ok(output.len() - available_out)
   }
 }
 
-fn brotli_decompress(buffer: &[u8]) -> Result<ToJsBuffer, AnyError> {
-  let mut output = Vec::with_capacity(4096);
-  let mut decompressor = Decompressor::new(buffer, buffer.len());
-  decompressor.read_to_end(&mut output)?;
-  Ok(output.into())
+/// A streaming brotli context, what `BrotliCompress` and `BrotliDecompress`
+/// are built on like the zlib streams are on `Zlib`.
+struct BrotliStream {
+  inner: RefCell<BrotliStreamInner>,
 }
 
-#[op2]
-#[serde]
-pub fn op_brotli_decompress(
-  #[buffer] buffer: &[u8],
-) -> Result<ToJsBuffer, AnyError> {
-  brotli_decompress(buffer)
+impl Resource for BrotliStream {
+  fn name(&self) -> Cow<str> {
+    "brotliStream".into()
+  }
 }
 
-#[op2(async)]
-#[serde]
-pub async fn op_brotli_decompress_async(
-  #[buffer] buffer: JsBuffer,
-) -> Result<ToJsBuffer, AnyError> {
-  tokio::task::spawn_blocking(move || brotli_decompress(&buffer)).await?
+fn brotli_stream(
+  state: &OpState,
+  rid: u32,
+) -> Result<Rc<BrotliStream>, AnyError> {
+  state
+    .resource_table
+    .get::<BrotliStream>(rid)
+    .map_err(|_| bad_resource_id())
 }
 
-struct BrotliDecompressCtx {
-  inst: *mut BrotliDecoderState,
+#[allow(clippy::too_many_arguments)]
+fn write_stream(
+  stream: &BrotliStream,
+  flush: i32,
+  input: &[u8],
+  in_off: u32,
+  in_len: u32,
+  out: &mut [u8],
+  out_off: u32,
+  out_len: u32,
+) -> Result<(bool, u32, u32), AnyError> {
+  let input = input
+    .get(in_off as usize..in_off as usize + in_len as usize)
+    .ok_or_else(|| type_error("invalid input range"))?;
+  let out = out
+    .get_mut(out_off as usize..out_off as usize + out_len as usize)
+    .ok_or_else(|| type_error("invalid output range"))?;
+  stream.inner.borrow_mut().write(flush, input, out)
 }
 
-impl Resource for BrotliDecompressCtx {}
+#[op2(fast)]
+#[smi]
+pub fn op_brotli_stream_new(
+  state: &mut OpState,
+  #[smi] mode: i32,
+) -> Result<u32, AnyError> {
+  let encode = match mode {
+    BROTLI_ENCODE => true,
+    BROTLI_DECODE => false,
+    _ => return Err(type_error("Unknown mode")),
+  };
+  Ok(state.resource_table.add(BrotliStream {
+    inner: RefCell::new(BrotliStreamInner {
+      encode,
+      instance: None,
+      params: Vec::new(),
+      error: None,
+    }),
+  }))
+}
 
-impl Drop for BrotliDecompressCtx {
-  fn drop(&mut self) {
-    // SAFETY: TODO(littledivy)
-    unsafe { CBrotliDecoderDestroyInstance(self.inst) };
-  }
+/// `params` has a value for each parameter, `u32::MAX` for those left to
+/// their default.
+#[op2(fast)]
+pub fn op_brotli_stream_init(
+  state: &mut OpState,
+  #[smi] rid: u32,
+  #[buffer] params: &[u32],
+) -> Result<bool, AnyError> {
+  let stream = brotli_stream(state, rid)?;
+  let ok = stream.inner.borrow_mut().init(params);
+  Ok(ok)
 }
 
 #[op2(fast)]
-#[smi]
-pub fn op_create_brotli_decompress(state: &mut OpState) -> u32 {
-  let inst =
-    // SAFETY: TODO(littledivy)
-    unsafe { CBrotliDecoderCreateInstance(None, None, std::ptr::null_mut()) };
-  state.resource_table.add(BrotliDecompressCtx { inst })
+pub fn op_brotli_stream_reset(
+  state: &mut OpState,
+  #[smi] rid: u32,
+) -> Result<bool, AnyError> {
+  let stream = brotli_stream(state, rid)?;
+  let ok = stream.inner.borrow_mut().reset();
+  Ok(ok)
 }
 
+#[allow(clippy::too_many_arguments)]
 #[op2(fast)]
-#[number]
-pub fn op_brotli_decompress_stream(
+pub fn op_brotli_stream_write(
   state: &mut OpState,
   #[smi] rid: u32,
+  #[smi] flush: i32,
   #[buffer] input: &[u8],
-  #[buffer] output: &mut [u8],
-) -> Result<usize, AnyError> {
-  let ctx = state.resource_table.get::<BrotliDecompressCtx>(rid)?;
-
-  // SAFETY: TODO(littledivy)
-  unsafe {
-    let mut available_in = input.len();
-    let mut next_in = input.as_ptr();
-    let mut available_out = output.len();
-    let mut next_out = output.as_mut_ptr();
-
-    if matches!(
-      CBrotliDecoderDecompressStream(
-        ctx.inst,
-        &mut available_in,
-        &mut next_in,
-        &mut available_out,
-        &mut next_out,
-        std::ptr::null_mut(),
-      ),
-      BrotliDecoderResult::BROTLI_DECODER_RESULT_ERROR
-    ) {
-      let ec = CBrotliDecoderGetErrorCode(ctx.inst) as i32;
-      return Err(type_error(format!("Failed to decompress, error {ec}")));
-    }
+  #[smi] in_off: u32,
+  #[smi] in_len: u32,
+  #[buffer] out: &mut [u8],
+  #[smi] out_off: u32,
+  #[smi] out_len: u32,
+  #[buffer] result: &mut [u32],
+) -> Result<bool, AnyError> {
+  let stream = brotli_stream(state, rid)?;
+  let (ok, avail_out, avail_in) =
+    write_stream(&stream, flush, input, in_off, in_len, out, out_off, out_len)?;
+  result[0] = avail_out;
+  result[1] = avail_in;
+  Ok(ok)
+}
 
-    // On progress, next_out is advanced and available_out is reduced.
-    Ok(output.len() - available_out)
-  }
+#[allow(clippy::too_many_arguments)]
+#[op2(async)]
+#[serde]
+pub fn op_brotli_stream_write_async(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: u32,
+  #[smi] flush: i32,
+  #[buffer] input: &[u8],
+  #[smi] in_off: u32,
+  #[smi] in_len: u32,
+  #[buffer] out: &mut [u8],
+  #[smi] out_off: u32,
+  #[smi] out_len: u32,
+) -> Result<impl Future<Output = Result<(bool, u32, u32), AnyError>>, AnyError>
+{
+  let stream = brotli_stream(&state.borrow(), rid)?;
+  // The buffers are only borrowed for the call, so the chunk is processed
+  // right away and the result reported asynchronously.
+  let result =
+    write_stream(&stream, flush, input, in_off, in_len, out, out_off, out_len);
+  Ok(async move { result })
 }
 
-#[op2(fast)]
-#[number]
-pub fn op_brotli_decompress_stream_end(
+#[op2]
+#[serde]
+pub fn op_brotli_stream_error(
   state: &mut OpState,
   #[smi] rid: u32,
-  #[buffer] output: &mut [u8],
-) -> Result<usize, AnyError> {
-  let ctx = state.resource_table.get::<BrotliDecompressCtx>(rid)?;
-
-  // SAFETY: TODO(littledivy)
-  unsafe {
-    let mut available_out = output.len();
-    let mut next_out = output.as_mut_ptr();
-    let mut available_in = 0;
-    let mut next_in = [];
-    let mut total_out = 0;
-
-    if matches!(
-      CBrotliDecoderDecompressStream(
-        ctx.inst,
-        &mut available_in,
-        next_in.as_mut_ptr(),
-        &mut available_out,
-        &mut next_out,
-        &mut total_out,
-      ),
-      BrotliDecoderResult::BROTLI_DECODER_RESULT_ERROR
-    ) {
-      return Err(type_error("Failed to decompress"));
-    }
-
-    // On finish, next_out is advanced and available_out is reduced.
-    Ok(output.len() - available_out)
-  }
+) -> Result<Option<BrotliError>, AnyError> {
+  let stream = brotli_stream(state, rid)?;
+  let error = stream.inner.borrow_mut().error.take();
+  Ok(error)
 }
@@ -220,6 +220,48 @@ impl ZlibInner {
     Ok(())
   }
 
+  /// The dictionary of inflate streams is set once inflate asks for it,
+  /// except for raw ones that have no header to ask with.
+  fn set_dictionary(&mut self) {
+    let Some(dictionary) = &self.dictionary else {
+      return;
+    };
+    self.err = match self.mode {
+      Mode::Deflate | Mode::DeflateRaw => {
+        self.strm.deflate_set_dictionary(dictionary)
+      }
+      Mode::InflateRaw => self.strm.inflate_set_dictionary(dictionary),
+      _ => Z_OK,
+    };
+  }
+
+  fn set_params(&mut self, level: i32, strategy: i32) -> Result<(), AnyError> {
+    check(self.init_done, "params before init")?;
+    check(!self.write_in_progress, "write already in progress")?;
+
+    self.err = match self.mode {
+      Mode::Deflate | Mode::Gzip | Mode::DeflateRaw => {
+        // The buffers of the last write are gone, and the flush before the
+        // call left nothing to write to them anyway.
+        self.strm.avail_in = 0;
+        self.strm.next_in = std::ptr::null_mut();
+        self.strm.avail_out = 0;
+        self.strm.next_out = std::ptr::null_mut();
+        self.strm.deflate_params(level, strategy)
+      }
+      _ => Z_OK,
+    };
+    // Nothing pending was flushed, which is fine.
+    if self.err == Z_BUF_ERROR {
+      self.err = Z_OK;
+    }
+    if self.err == Z_OK {
+      self.level = level;
+      self.strategy = strategy;
+    }
+    Ok(())
+  }
+
   fn close(&mut self) -> Result<bool, AnyError> {
     if self.write_in_progress {
       self.pending_close = true;
@@ -236,6 +278,9 @@ impl ZlibInner {
 
   fn reset_stream(&mut self) -> Result<(), AnyError> {
     self.err = self.strm.reset(self.mode);
+    if self.err == Z_OK {
+      self.set_dictionary();
+    }
 
     Ok(())
   }
@@ -379,14 +424,34 @@ pub fn op_zlib_init(
   zlib.flush = Flush::None;
   zlib.err = Z_OK;
 
-  zlib.init_stream()?;
-
   zlib.dictionary = if !dictionary.is_empty() {
     Some(dictionary.to_vec())
   } else {
     None
   };
 
+  zlib.init_stream()?;
+  if zlib.err == Z_OK {
+    zlib.set_dictionary();
+  }
+
+  Ok(zlib.err)
+}
+
+#[op2(fast)]
+#[smi]
+pub fn op_zlib_params(
+  state: &mut OpState,
+  #[smi] handle: u32,
+  #[smi] level: i32,
+  #[smi] strategy: i32,
+) -> Result<i32, AnyError> {
+  let resource = zlib(state, handle)?;
+  let mut zlib = resource.inner.borrow_mut();
+
+  check((-1..=9).contains(&level), "invalid level")?;
+  zlib.set_params(level, strategy)?;
+
   Ok(zlib.err)
 }
 
@@ -463,4 +528,75 @@ mod tests {
       stream.close().unwrap();
     }
   }
+
+  fn roundtrip(deflate: Mode, inflate: Mode, dictionary: &[u8]) -> Vec<u8> {
+    let input = b"hello hello hello world";
+    let mut compressed = [0u8; 128];
+    let mut stream = ZlibInner {
+      mode: deflate,
+      level: Z_DEFAULT_COMPRESSION,
+      window_bits: 15,
+      mem_level: 8,
+      strategy: Z_DEFAULT_STRATEGY,
+      dictionary: Some(dictionary.to_vec()),
+      ..Default::default()
+    };
+    stream.init_stream().unwrap();
+    stream.set_dictionary();
+    assert_eq!(stream.err, Z_OK);
+    stream
+      .start_write(
+        input,
+        0,
+        input.len() as u32,
+        &mut compressed,
+        0,
+        128,
+        Flush::Finish,
+      )
+      .unwrap();
+    stream.do_write(Flush::Finish).unwrap();
+    assert_eq!(stream.err, Z_STREAM_END);
+    let compressed_len = 128 - stream.strm.avail_out as usize;
+    stream.close().unwrap();
+
+    let mut output = [0u8; 128];
+    let mut stream = ZlibInner {
+      mode: inflate,
+      window_bits: 15,
+      dictionary: Some(dictionary.to_vec()),
+      ..Default::default()
+    };
+    stream.init_stream().unwrap();
+    stream.set_dictionary();
+    stream
+      .start_write(
+        &compressed,
+        0,
+        compressed_len as u32,
+        &mut output,
+        0,
+        128,
+        Flush::Finish,
+      )
+      .unwrap();
+    stream.do_write(Flush::Finish).unwrap();
+    assert_eq!(stream.err, Z_STREAM_END);
+    let output_len = 128 - stream.strm.avail_out as usize;
+    stream.close().unwrap();
+    output[..output_len].to_vec()
+  }
+
+  #[test]
+  fn zlib_dictionary() {
+    let dictionary = b"hello world";
+    assert_eq!(
+      roundtrip(Mode::Deflate, Mode::Inflate, dictionary),
+      b"hello hello hello world"
+    );
+    assert_eq!(
+      roundtrip(Mode::DeflateRaw, Mode::InflateRaw, dictionary),
+      b"hello hello hello world"
+    );
+  }
 }
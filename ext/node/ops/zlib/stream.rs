@@ -132,4 +132,20 @@ impl StreamWrapper {
       )
     }
   }
+
+  pub fn deflate_set_dictionary(&mut self, dictionary: &[u8]) -> c_int {
+    // SAFETY: `self.strm` is an initialized `zlib::z_stream`.
+    unsafe {
+      zlib::deflateSetDictionary(
+        &mut self.strm,
+        dictionary.as_ptr() as *const _,
+        dictionary.len() as _,
+      )
+    }
+  }
+
+  pub fn deflate_params(&mut self, level: c_int, strategy: c_int) -> c_int {
+    // SAFETY: `self.strm` is an initialized `zlib::z_stream`.
+    unsafe { zlib::deflateParams(&mut self.strm, level, strategy) }
+  }
 }
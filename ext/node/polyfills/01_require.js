@@ -288,6 +288,7 @@ let hasBrokenOnInspectBrk = false;
 let hasInspectBrk = false;
 // Are we running with --node-modules-dir flag or byonm?
 let usesLocalNodeModulesDir = false;
+let preserveSymlinks = false;
 
 function stat(filename) {
   // TODO: required only on windows
@@ -378,6 +379,9 @@ function tryPackage(requestPath, exts, isMain, originalPath) {
 
 const realpathCache = new SafeMap();
 function toRealPath(requestPath) {
+  if (preserveSymlinks) {
+    return pathResolve(requestPath);
+  }
   const maybeCached = realpathCache.get(requestPath);
   if (maybeCached) {
     return maybeCached;
@@ -827,7 +831,7 @@ Module._resolveFilename = function (
     parentPath,
   );
   if (filename) {
-    return op_require_real_path(filename);
+    return toRealPath(filename);
   }
   const requireStack = [];
   for (let cursor = parent; cursor; cursor = moduleParentCache.get(cursor)) {
@@ -894,7 +898,7 @@ Module.prototype.load = function (filename) {
 
   // Canonicalize the path so it's not pointing to the symlinked directory
   // in `node_modules` directory of the referrer.
-  this.filename = op_require_real_path(filename);
+  this.filename = toRealPath(filename);
   this.paths = Module._nodeModulePaths(
     pathDirname(this.filename),
   );
@@ -1254,6 +1258,9 @@ internals.requireImpl = {
   setUsesLocalNodeModulesDir() {
     usesLocalNodeModulesDir = true;
   },
+  setPreserveSymlinks() {
+    preserveSymlinks = true;
+  },
   setInspectBrk() {
     hasInspectBrk = true;
   },
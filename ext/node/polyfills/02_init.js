@@ -13,6 +13,7 @@ let initialized = false;
 function initialize(args) {
   const {
     usesLocalNodeModulesDir,
+    preserveSymlinks,
     argv0,
     runningOnMainThread,
     workerId,
@@ -28,6 +29,9 @@ function initialize(args) {
     if (usesLocalNodeModulesDir) {
       requireImpl.setUsesLocalNodeModulesDir();
     }
+    if (preserveSymlinks) {
+      requireImpl.setPreserveSymlinks();
+    }
 
     // FIXME(bartlomieju): not nice to depend on `Deno` namespace here
     // but it's the only way to get `args` and `version` and this point.
@@ -4,18 +4,140 @@
 
 import { op_node_cp, op_node_cp_sync } from "ext:core/ops";
 
+import {
+  denoErrorToNodeError,
+  ERR_FS_CP_DIR_TO_NON_DIR,
+  ERR_FS_CP_EEXIST,
+  ERR_FS_CP_EINVAL,
+  ERR_FS_CP_FIFO_PIPE,
+  ERR_FS_CP_NON_DIR_TO_DIR,
+  ERR_FS_CP_SOCKET,
+  ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY,
+  ERR_FS_CP_UNKNOWN,
+  ERR_FS_EISDIR,
+} from "ext:deno_node/internal/errors.ts";
 import {
   getValidatedPath,
   validateCpOptions,
 } from "ext:deno_node/internal/fs/utils.mjs";
+import { os } from "ext:deno_node/internal_binding/constants.ts";
+import { isPromise } from "ext:deno_node/internal/util/types.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
+import { join } from "node:path";
+
+const cpErrors = {
+  ERR_FS_CP_DIR_TO_NON_DIR,
+  ERR_FS_CP_EEXIST,
+  ERR_FS_CP_EINVAL,
+  ERR_FS_CP_FIFO_PIPE,
+  ERR_FS_CP_NON_DIR_TO_DIR,
+  ERR_FS_CP_SOCKET,
+  ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY,
+  ERR_FS_CP_UNKNOWN,
+  ERR_FS_EISDIR,
+};
+
+function cpError({ kind, code, message, syscall, path }) {
+  if (kind === null) {
+    return denoErrorToNodeError(new Error(message), { syscall, path });
+  }
+  return new cpErrors[kind]({
+    message,
+    path,
+    syscall,
+    errno: os.errno[code],
+    code,
+  });
+}
+
+function cpOptions(options, skip) {
+  return {
+    dereference: options.dereference,
+    errorOnExist: options.errorOnExist,
+    force: options.force,
+    preserveTimestamps: options.preserveTimestamps,
+    recursive: options.recursive,
+    verbatimSymlinks: options.verbatimSymlinks,
+    skip,
+  };
+}
+
+// The copy itself is made in Rust, so `options.filter` is called before it
+// starts, with the paths the copy visits. The paths it rejects are skipped,
+// along with what's inside of them.
+function filterSync(src, dest, options, skip) {
+  const include = options.filter(src, dest);
+  if (isPromise(include)) {
+    throw new Error(
+      "Expected a boolean from the filter function, but got a promise. Use `fs.promises.cp` instead.",
+    );
+  }
+  if (!include) {
+    skip.push(src);
+    return;
+  }
+  if (!options.recursive) {
+    return;
+  }
+  let stat;
+  try {
+    stat = options.dereference ? Deno.statSync(src) : Deno.lstatSync(src);
+  } catch {
+    // the copy reports it
+    return;
+  }
+  if (stat.isDirectory) {
+    for (const { name } of Deno.readDirSync(src)) {
+      filterSync(join(src, name), join(dest, name), options, skip);
+    }
+  }
+}
+
+async function filter(src, dest, options, skip) {
+  if (!await options.filter(src, dest)) {
+    skip.push(src);
+    return;
+  }
+  if (!options.recursive) {
+    return;
+  }
+  let stat;
+  try {
+    stat = options.dereference ? await Deno.stat(src) : await Deno.lstat(src);
+  } catch {
+    return;
+  }
+  if (stat.isDirectory) {
+    for await (const { name } of Deno.readDir(src)) {
+      await filter(join(src, name), join(dest, name), options, skip);
+    }
+  }
+}
 
 export function cpSync(src, dest, options) {
-  validateCpOptions(options);
-  const srcPath = getValidatedPath(src, "src");
-  const destPath = getValidatedPath(dest, "dest");
+  options = validateCpOptions(options);
+  const srcPath = getValidatedPath(src, "src").toString();
+  const destPath = getValidatedPath(dest, "dest").toString();
 
-  op_node_cp_sync(srcPath, destPath);
+  const skip = [];
+  if (options.filter) {
+    filterSync(srcPath, destPath, options, skip);
+  }
+  const err = op_node_cp_sync(srcPath, destPath, cpOptions(options, skip));
+  if (err) {
+    throw cpError(err);
+  }
+}
+
+async function cpAsync(src, dest, options) {
+  const skip = [];
+  if (options.filter) {
+    await filter(src, dest, options, skip);
+  }
+  const err = await op_node_cp(src, dest, cpOptions(options, skip));
+  if (err) {
+    throw cpError(err);
+  }
 }
 
 export function cp(src, dest, options, callback) {
@@ -23,13 +145,14 @@ export function cp(src, dest, options, callback) {
     callback = options;
     options = {};
   }
-  validateCpOptions(options);
-  const srcPath = getValidatedPath(src, "src");
-  const destPath = getValidatedPath(dest, "dest");
+  options = validateCpOptions(options);
+  const srcPath = getValidatedPath(src, "src").toString();
+  const destPath = getValidatedPath(dest, "dest").toString();
 
-  op_node_cp(
+  cpAsync(
     srcPath,
     destPath,
+    options,
   ).then(
     (res) => callback(null, res),
     (err) => callback(err, null),
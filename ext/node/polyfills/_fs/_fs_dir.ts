@@ -3,21 +3,45 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import { core } from "ext:core/mod.js";
+import {
+  op_node_fs_dir_read,
+  op_node_fs_dir_read_sync,
+  op_node_fs_opendir_sync,
+} from "ext:core/ops";
 import Dirent from "ext:deno_node/_fs/_fs_dirent.ts";
-import { assert } from "ext:deno_node/_util/asserts.ts";
-import { ERR_MISSING_ARGS } from "ext:deno_node/internal/errors.ts";
+import {
+  denoErrorToNodeError,
+  ERR_DIR_CLOSED,
+  ERR_DIR_CONCURRENT_OPERATION,
+  ERR_MISSING_ARGS,
+} from "ext:deno_node/internal/errors.ts";
 import { TextDecoder } from "ext:deno_web/08_text_encoding.js";
 
+type Options = { bufferSize?: number };
+
 export default class Dir {
   #dirPath: string | Uint8Array;
-  #syncIterator!: Iterator<Deno.DirEntry, undefined> | null;
-  #asyncIterator!: AsyncIterator<Deno.DirEntry, undefined> | null;
+  #rid: number | undefined;
+  #bufferSize: number;
+  #entries: Deno.DirEntry[] = [];
+  #done = false;
+  #closed = false;
+  /** The async reads in flight, which run one after the other. */
+  #operations = 0;
+  #queue: Promise<unknown> = Promise.resolve();
 
-  constructor(path: string | Uint8Array) {
+  /**
+   * `rid` is the handle `fs.opendir()` opened, a directory constructed
+   * without one is opened on the first read.
+   */
+  constructor(path: string | Uint8Array, options?: Options, rid?: number) {
     if (!path) {
       throw new ERR_MISSING_ARGS("path");
     }
     this.#dirPath = path;
+    this.#bufferSize = options?.bufferSize ?? 32;
+    this.#rid = rid;
   }
 
   get path(): string {
@@ -27,69 +51,132 @@ export default class Dir {
     return this.#dirPath;
   }
 
+  #handle(): number {
+    if (this.#rid !== undefined) {
+      return this.#rid;
+    }
+    try {
+      return this.#rid = op_node_fs_opendir_sync(this.path);
+    } catch (err) {
+      throw denoErrorToNodeError(err as Error, {
+        syscall: "opendir",
+        path: this.path,
+      });
+    }
+  }
+
+  /** The handle is closed as soon as all the entries were read. */
+  #setEntries(entries: Deno.DirEntry[]) {
+    this.#entries = entries;
+    if (entries.length === 0) {
+      this.#done = true;
+      this.#closeHandle();
+    }
+  }
+
+  #closeHandle() {
+    if (this.#rid !== undefined) {
+      core.tryClose(this.#rid);
+      this.#rid = undefined;
+    }
+  }
+
+  #next(): Dirent | null {
+    const entry = this.#entries.shift();
+    return entry ? new Dirent(entry) : null;
+  }
+
+  async #read(): Promise<Dirent | null> {
+    if (this.#entries.length === 0 && !this.#done && !this.#closed) {
+      try {
+        const rid = this.#handle();
+        this.#setEntries(await op_node_fs_dir_read(rid, this.#bufferSize));
+      } catch (err) {
+        throw denoErrorToNodeError(err as Error, {
+          syscall: "scandir",
+          path: this.path,
+        });
+      }
+    }
+    return this.#next();
+  }
+
   // deno-lint-ignore no-explicit-any
   read(callback?: (...args: any[]) => void): Promise<Dirent | null> {
+    if (this.#closed) {
+      throw new ERR_DIR_CLOSED();
+    }
+    this.#operations++;
+    const promise = this.#queue.then(() => this.#read()).finally(() => {
+      this.#operations--;
+    });
+    this.#queue = promise.catch(() => {});
     return new Promise((resolve, reject) => {
-      if (!this.#asyncIterator) {
-        this.#asyncIterator = Deno.readDir(this.path)[Symbol.asyncIterator]();
-      }
-      assert(this.#asyncIterator);
-      this.#asyncIterator
-        .next()
-        .then((iteratorResult) => {
-          resolve(
-            iteratorResult.done ? null : new Dirent(iteratorResult.value),
-          );
-          if (callback) {
-            callback(
-              null,
-              iteratorResult.done ? null : new Dirent(iteratorResult.value),
-            );
-          }
-        }, (err) => {
-          if (callback) {
-            callback(err);
-          }
-          reject(err);
-        });
+      promise.then((dirent) => {
+        resolve(dirent);
+        if (callback) {
+          callback(null, dirent);
+        }
+      }, (err) => {
+        if (callback) {
+          callback(err);
+        }
+        reject(err);
+      });
     });
   }
 
   readSync(): Dirent | null {
-    if (!this.#syncIterator) {
-      this.#syncIterator = Deno.readDirSync(this.path)![Symbol.iterator]();
+    if (this.#closed) {
+      throw new ERR_DIR_CLOSED();
     }
-
-    const iteratorResult = this.#syncIterator.next();
-    if (iteratorResult.done) {
-      return null;
-    } else {
-      return new Dirent(iteratorResult.value);
+    if (this.#operations > 0) {
+      throw new ERR_DIR_CONCURRENT_OPERATION();
+    }
+    if (this.#entries.length === 0 && !this.#done) {
+      try {
+        const rid = this.#handle();
+        this.#setEntries(op_node_fs_dir_read_sync(rid, this.#bufferSize));
+      } catch (err) {
+        throw denoErrorToNodeError(err as Error, {
+          syscall: "scandir",
+          path: this.path,
+        });
+      }
     }
+    return this.#next();
   }
 
-  /**
-   * Unlike Node, Deno does not require managing resource ids for reading
-   * directories, and therefore does not need to close directories when
-   * finished reading.
-   */
   // deno-lint-ignore no-explicit-any
   close(callback?: (...args: any[]) => void): Promise<void> {
-    return new Promise((resolve) => {
+    return new Promise((resolve, reject) => {
+      let error = null;
+      try {
+        this.closeSync();
+      } catch (err) {
+        error = err;
+      }
       if (callback) {
-        callback(null);
+        callback(error);
+        resolve();
+      } else if (error) {
+        reject(error);
+      } else {
+        resolve();
       }
-      resolve();
     });
   }
 
-  /**
-   * Unlike Node, Deno does not require managing resource ids for reading
-   * directories, and therefore does not need to close directories when
-   * finished reading
-   */
   closeSync() {
-    //No op
+    if (this.#closed) {
+      throw new ERR_DIR_CLOSED();
+    }
+    if (this.#operations > 0) {
+      throw new ERR_DIR_CONCURRENT_OPERATION();
+    }
+    this.#closed = true;
+    this.#entries = [];
+    this.#closeHandle();
   }
 
   async *[Symbol.asyncIterator](): AsyncIterableIterator<Dirent> {
@@ -102,7 +189,9 @@ export default class Dir {
         yield dirent;
       }
     } finally {
-      await this.close();
+      if (!this.#closed) {
+        await this.close();
+      }
     }
   }
 }
@@ -3,6 +3,7 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import { op_node_fs_opendir_sync } from "ext:core/ops";
 import Dir from "ext:deno_node/_fs/_fs_dir.ts";
 import { Buffer } from "node:buffer";
 import {
@@ -16,9 +17,7 @@ import {
 } from "ext:deno_node/internal/validators.mjs";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
-/** These options aren't functionally used right now, as `Dir` doesn't yet support them.
- * However, these values are still validated.
- */
+/** `encoding` isn't used right now, but it is still validated. */
 type Options = {
   encoding?: string;
   bufferSize?: number;
@@ -29,6 +28,16 @@ function _validateFunction(callback: unknown): asserts callback is Callback {
   validateFunction(callback, "callback");
 }
 
+function openDir(path: string, bufferSize: number): Dir {
+  let rid;
+  try {
+    rid = op_node_fs_opendir_sync(path);
+  } catch (err) {
+    throw denoErrorToNodeError(err as Error, { syscall: "opendir", path });
+  }
+  return new Dir(path, { bufferSize }, rid);
+}
+
 /** @link https://nodejs.org/api/fs.html#fsopendirsyncpath-options */
 export function opendir(
   path: string | Buffer | URL,
@@ -48,12 +57,9 @@ export function opendir(
     });
     validateInteger(bufferSize, "options.bufferSize", 1, 4294967295);
 
-    /** Throws if path is invalid */
-    Deno.readDirSync(path);
-
-    dir = new Dir(path);
+    dir = openDir(path, bufferSize);
   } catch (error) {
-    err = denoErrorToNodeError(error as Error, { syscall: "opendir" });
+    err = error;
   }
   if (err) {
     callback(err);
@@ -81,12 +87,5 @@ export function opendirSync(
 
   validateInteger(bufferSize, "options.bufferSize", 1, 4294967295);
 
-  try {
-    /** Throws if path is invalid */
-    Deno.readDirSync(path);
-
-    return new Dir(path);
-  } catch (err) {
-    throw denoErrorToNodeError(err as Error, { syscall: "opendir" });
-  }
+  return openDir(path, bufferSize);
 }
@@ -3,37 +3,102 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import {
+  op_node_fs_realpath,
+  op_node_fs_realpath_sync,
+} from "ext:core/ops";
+import { Buffer } from "node:buffer";
+import { denoErrorToNodeError } from "ext:deno_node/internal/errors.ts";
+import {
+  getOptions,
+  getValidatedPath,
+} from "ext:deno_node/internal/fs/utils.mjs";
+import { validateFunction } from "ext:deno_node/internal/validators.mjs";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
-type Options = { encoding: string };
-type Callback = (err: Error | null, path?: string) => void;
+type Options = { encoding: string } | string;
+type Callback = (err: Error | null, path?: string | Buffer) => void;
 
-export function realpath(
-  path: string,
-  options?: Options | Callback,
-  callback?: Callback,
+function encode(path: string, options?: Options): string | Buffer {
+  const { encoding } = getOptions(options, { encoding: "utf8" });
+  if (encoding === "buffer") {
+    return Buffer.from(path);
+  }
+  if (encoding === "utf8" || encoding === "utf-8") {
+    return path;
+  }
+  return Buffer.from(path).toString(encoding);
+}
+
+function realpathWith(
+  path: string | Buffer | URL,
+  options: Options | Callback | undefined,
+  callback: Callback | undefined,
 ) {
   if (typeof options === "function") {
     callback = options;
+    options = undefined;
   }
-  if (!callback) {
-    throw new Error("No callback function supplied");
-  }
-  Deno.realPath(path).then(
-    (path) => callback!(null, path),
-    (err) => callback!(err),
+  validateFunction(callback, "callback");
+  const validated = getValidatedPath(path).toString();
+  op_node_fs_realpath(validated).then(
+    (resolved: string) => callback!(null, encode(resolved, options)),
+    (err: Error) =>
+      callback!(
+        denoErrorToNodeError(err, { syscall: "realpath", path: validated }),
+      ),
   );
 }
 
-realpath.native = realpath;
+function realpathSyncWith(
+  path: string | Buffer | URL,
+  options?: Options,
+): string | Buffer {
+  const validated = getValidatedPath(path).toString();
+  try {
+    return encode(op_node_fs_realpath_sync(validated), options);
+  } catch (err) {
+    throw denoErrorToNodeError(err as Error, {
+      syscall: "realpath",
+      path: validated,
+    });
+  }
+}
+
+// Both resolve the path with the OS's realpath(3), which is what Node's
+// `native` variants do. Symlinks are resolved the same way by the JS
+// variants, so they only differ in identity.
+export function realpath(
+  path: string | Buffer | URL,
+  options?: Options | Callback,
+  callback?: Callback,
+) {
+  realpathWith(path, options, callback);
+}
+
+realpath.native = function native(
+  path: string | Buffer | URL,
+  options?: Options | Callback,
+  callback?: Callback,
+) {
+  realpathWith(path, options, callback);
+};
 
 export const realpathPromise = promisify(realpath) as (
-  path: string,
+  path: string | Buffer | URL,
   options?: Options,
-) => Promise<string>;
+) => Promise<string | Buffer>;
 
-export function realpathSync(path: string): string {
-  return Deno.realPathSync(path);
+export function realpathSync(
+  path: string | Buffer | URL,
+  options?: Options,
+): string | Buffer {
+  return realpathSyncWith(path, options);
 }
 
-realpathSync.native = realpathSync;
+realpathSync.native = function native(
+  path: string | Buffer | URL,
+  options?: Options,
+): string | Buffer {
+  return realpathSyncWith(path, options);
+};
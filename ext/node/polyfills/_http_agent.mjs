@@ -4,6 +4,11 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import {
+  op_node_http_agent_close,
+  op_node_http_agent_new,
+  op_node_http_agent_stats,
+} from "ext:core/ops";
 import * as net from "node:net";
 import EventEmitter from "node:events";
 import { debuglog } from "ext:deno_node/internal/util/debuglog.ts";
@@ -25,6 +30,12 @@ import {
 const kOnKeylog = Symbol("onkeylog");
 const kRequestOptions = Symbol("requestOptions");
 const kRequestAsyncResource = Symbol("requestAsyncResource");
+const kPool = Symbol("pool");
+
+// The pools of the agents that were garbage collected without `destroy()`.
+const poolRegistry = new FinalizationRegistry((id) => {
+  op_node_http_agent_close(id);
+});
 // New Agent code.
 
 // The largest departure from the previous implementation is that
@@ -490,6 +501,11 @@ Agent.prototype.reuseSocket = function reuseSocket(socket, req) {
 };
 
 Agent.prototype.destroy = function destroy() {
+  if (this[kPool] !== undefined) {
+    op_node_http_agent_close(this[kPool]);
+    poolRegistry.unregister(this);
+    this[kPool] = undefined;
+  }
   const sets = [this.freeSockets, this.sockets];
   for (let s = 0; s < sets.length; s++) {
     const set = sets[s];
@@ -503,6 +519,49 @@ Agent.prototype.destroy = function destroy() {
   }
 };
 
+/**
+ * The statistics of the connection pool of the agent, by "host:port": how
+ * many requests hold a connection (`active`), how many wait for one
+ * (`pending`), and how many were made (`requests`).
+ */
+Agent.prototype.getPoolStats = function getPoolStats() {
+  if (this[kPool] === undefined) {
+    return {};
+  }
+  return op_node_http_agent_stats(this[kPool]);
+};
+
+/**
+ * The id of the connection pool the requests made with `agent` share, which
+ * is created on the first request. `undefined` if the agent doesn't reuse
+ * connections nor limits them, when each request gets its own client.
+ */
+export function getAgentPool(agent, caCerts) {
+  if (!(agent instanceof Agent)) {
+    return undefined;
+  }
+  if (
+    !agent.keepAlive && !Number.isFinite(agent.maxSockets) &&
+    !Number.isFinite(agent.maxTotalSockets)
+  ) {
+    return undefined;
+  }
+  if (agent[kPool] === undefined) {
+    const limit = (max) => Number.isFinite(max) ? max : null;
+    agent[kPool] = op_node_http_agent_new({
+      keepAlive: !!agent.keepAlive,
+      keepAliveMsecs: agent.keepAliveMsecs,
+      maxSockets: limit(agent.maxSockets),
+      maxFreeSockets: limit(agent.maxFreeSockets),
+      maxTotalSockets: limit(agent.maxTotalSockets),
+      timeout: agent.options.timeout ?? null,
+      caCerts: caCerts ?? [],
+    });
+    poolRegistry.register(agent, agent[kPool], agent);
+  }
+  return agent[kPool];
+}
+
 function setRequestSocket(agent, req, socket) {
   req.onSocket(socket);
   const agentTimeout = agent.options.timeout || 0;
@@ -522,7 +581,11 @@ function asyncResetHandle(socket) {
   }
 }
 
-export const globalAgent = new Agent();
+export const globalAgent = new Agent({
+  keepAlive: true,
+  scheduling: "lifo",
+  timeout: 5000,
+});
 export default {
   Agent,
   globalAgent,
@@ -256,7 +256,17 @@ export class ServerImpl extends EventEmitter {
     // TODO(kt3k): The default host should be "localhost"
     const hostname = this.options.host ?? "0.0.0.0";
 
-    this.listener = Deno.listenTls({ port, hostname, cert, key });
+    const alpnProtocols = Array.isArray(this.options.ALPNProtocols)
+      ? this.options.ALPNProtocols
+      : undefined;
+
+    this.listener = Deno.listenTls({
+      port,
+      hostname,
+      cert,
+      key,
+      alpnProtocols,
+    });
 
     callback?.call(this);
     this.#listen(this.listener);
@@ -266,11 +276,7 @@ export class ServerImpl extends EventEmitter {
   async #listen(listener: Deno.TlsListener) {
     while (!this.#closed) {
       try {
-        // Creates TCP handle and socket directly from Deno.TlsConn.
-        // This works as TLS socket. We don't use TLSSocket class for doing
-        // this because Deno.startTls only supports client side tcp connection.
-        const handle = new TCP(TCPConstants.SOCKET, await listener.accept());
-        const socket = new net.Socket({ handle });
+        const socket = this._createSocket(await listener.accept());
         this.emit("secureConnection", socket);
       } catch (e) {
         if (e instanceof Deno.errors.BadResource) {
@@ -281,6 +287,14 @@ export class ServerImpl extends EventEmitter {
     }
   }
 
+  _createSocket(conn: Deno.TlsConn) {
+    // Creates TCP handle and socket directly from Deno.TlsConn.
+    // This works as TLS socket. We don't use TLSSocket class for doing
+    // this because Deno.startTls only supports client side tcp connection.
+    const handle = new TCP(TCPConstants.SOCKET, conn);
+    return new net.Socket({ handle });
+  }
+
   close(cb?: (err?: Error) => void): this {
     if (this.listener) {
       this.listener.close();
@@ -11,6 +11,11 @@ import util from "node:util";
 import { ok as assert } from "node:assert";
 import { zlib as zlibConstants } from "ext:deno_node/internal_binding/constants.ts";
 import { nextTick } from "ext:deno_node/_next_tick.ts";
+import {
+  ERR_BROTLI_INVALID_PARAM,
+  ERR_INVALID_ARG_TYPE,
+  ERR_ZLIB_INITIALIZATION_FAILED,
+} from "ext:deno_node/internal/errors.ts";
 import {
   isAnyArrayBuffer,
   isArrayBufferView,
@@ -69,6 +74,14 @@ export const createUnzip = function (o) {
   return new Unzip(o);
 };
 
+export const createBrotliCompress = function (o) {
+  return new BrotliCompress(o);
+};
+
+export const createBrotliDecompress = function (o) {
+  return new BrotliDecompress(o);
+};
+
 // Convenience methods.
 // compress/decompress a string or buffer in one step.
 export const deflate = function (buffer, opts, callback) {
@@ -267,19 +280,87 @@ function isValidFlushFlag(flag) {
     flag === binding.Z_FINISH || flag === binding.Z_BLOCK;
 }
 
-// the Zlib class they all inherit from
+function checkChunkSize(opts) {
+  if (opts.chunkSize) {
+    if (
+      opts.chunkSize < zlibConstants.Z_MIN_CHUNK ||
+      opts.chunkSize > zlibConstants.Z_MAX_CHUNK
+    ) {
+      throw new Error("Invalid chunk size: " + opts.chunkSize);
+    }
+  }
+}
+
+// The flush flags the zlib and the brotli streams use by default, for the
+// regular writes, the last one and `flush()`.
+var zlibDefaultOpts = {
+  flush: binding.Z_NO_FLUSH,
+  finishFlush: binding.Z_FINISH,
+  fullFlush: binding.Z_FULL_FLUSH,
+};
+var brotliDefaultOpts = {
+  flush: zlibConstants.BROTLI_OPERATION_PROCESS,
+  finishFlush: zlibConstants.BROTLI_OPERATION_FINISH,
+  fullFlush: zlibConstants.BROTLI_OPERATION_FLUSH,
+};
+
+// the ZlibBase class the zlib and brotli streams inherit from
 // This thing manages the queue of requests, and returns
 // true or false if there is anything in the queue when
 // you call the .write() method.
 
-function Zlib(opts, mode) {
+function ZlibBase(opts, handle, defaultOpts) {
   var _this = this;
 
-  this._opts = opts = opts || {};
+  this._opts = opts;
   this._chunkSize = opts.chunkSize || zlibConstants.Z_DEFAULT_CHUNK;
 
   Transform.call(this, opts);
 
+  this._defaultFlushFlag = defaultOpts.flush;
+  this._defaultFullFlushFlag = defaultOpts.fullFlush;
+  this._flushFlag = opts.flush || defaultOpts.flush;
+  this._finishFlushFlag = typeof opts.finishFlush !== "undefined"
+    ? opts.finishFlush
+    : defaultOpts.finishFlush;
+
+  this._handle = handle;
+
+  var self = this;
+  this._hadError = false;
+  this._handle.onerror = function (message, errno, code) {
+    // there is no way to cleanly recover.
+    // continuing only obscures problems.
+    _close(self);
+    self._hadError = true;
+
+    var error = new Error(message);
+    error.errno = errno;
+    error.code = code ?? codes[errno];
+    self.emit("error", error);
+  };
+
+  this._buffer = Buffer.allocUnsafe(this._chunkSize);
+  this._offset = 0;
+
+  this.once("end", this.close);
+
+  Object.defineProperty(this, "_closed", {
+    get: function () {
+      return !_this._handle;
+    },
+    configurable: true,
+    enumerable: true,
+  });
+}
+
+util.inherits(ZlibBase, Transform);
+
+// the Zlib class the zlib streams inherit from
+
+function Zlib(opts, mode) {
+  opts = opts || {};
+
   if (opts.flush && !isValidFlushFlag(opts.flush)) {
     throw new Error("Invalid flush flag: " + opts.flush);
   }
@@ -287,19 +368,7 @@ function Zlib(opts, mode) {
     throw new Error("Invalid flush flag: " + opts.finishFlush);
   }
 
-  this._flushFlag = opts.flush || binding.Z_NO_FLUSH;
-  this._finishFlushFlag = typeof opts.finishFlush !== "undefined"
-    ? opts.finishFlush
-    : binding.Z_FINISH;
-
-  if (opts.chunkSize) {
-    if (
-      opts.chunkSize < zlibConstants.Z_MIN_CHUNK ||
-      opts.chunkSize > zlibConstants.Z_MAX_CHUNK
-    ) {
-      throw new Error("Invalid chunk size: " + opts.chunkSize);
-    }
-  }
+  checkChunkSize(opts);
 
   if (opts.windowBits) {
     if (
@@ -349,21 +418,7 @@ function Zlib(opts, mode) {
     }
   }
 
-  this._handle = new binding.Zlib(mode);
-
-  var self = this;
-  this._hadError = false;
-  this._handle.onerror = function (message, errno) {
-    // there is no way to cleanly recover.
-    // continuing only obscures problems.
-    _close(self);
-    self._hadError = true;
-
-    var error = new Error(message);
-    error.errno = errno;
-    error.code = codes[errno];
-    self.emit("error", error);
-  };
+  ZlibBase.call(this, opts, new binding.Zlib(mode), zlibDefaultOpts);
 
   var level = zlibConstants.Z_DEFAULT_COMPRESSION;
   if (typeof opts.level === "number") level = opts.level;
@@ -379,23 +434,11 @@ function Zlib(opts, mode) {
     dictionary,
   );
 
-  this._buffer = Buffer.allocUnsafe(this._chunkSize);
-  this._offset = 0;
   this._level = level;
   this._strategy = strategy;
-
-  this.once("end", this.close);
-
-  Object.defineProperty(this, "_closed", {
-    get: function () {
-      return !_this._handle;
-    },
-    configurable: true,
-    enumerable: true,
-  });
 }
 
-util.inherits(Zlib, Transform);
+util.inherits(Zlib, ZlibBase);
 
 Zlib.prototype.params = function (level, strategy, callback) {
   if (level < zlibConstants.Z_MIN_LEVEL || level > zlibConstants.Z_MAX_LEVEL) {
@@ -427,25 +470,99 @@ Zlib.prototype.params = function (level, strategy, callback) {
   }
 };
 
-Zlib.prototype.reset = function () {
+// the Brotli class the brotli streams inherit from
+
+function isValidBrotliFlushFlag(flag) {
+  return flag === zlibConstants.BROTLI_OPERATION_PROCESS ||
+    flag === zlibConstants.BROTLI_OPERATION_FLUSH ||
+    flag === zlibConstants.BROTLI_OPERATION_FINISH ||
+    flag === zlibConstants.BROTLI_OPERATION_EMIT_METADATA;
+}
+
+// The largest of the BROTLI_PARAM_* and BROTLI_DECODER_PARAM_* keys.
+var kMaxBrotliParam = zlibConstants.BROTLI_PARAM_NDIRECT;
+var brotliInitParamsArray = new Uint32Array(kMaxBrotliParam + 1);
+
+function Brotli(opts, mode) {
+  opts = opts || {};
+
+  if (opts.flush !== undefined && !isValidBrotliFlushFlag(opts.flush)) {
+    throw new Error("Invalid flush flag: " + opts.flush);
+  }
+  if (
+    opts.finishFlush !== undefined && !isValidBrotliFlushFlag(opts.finishFlush)
+  ) {
+    throw new Error("Invalid flush flag: " + opts.finishFlush);
+  }
+
+  checkChunkSize(opts);
+
+  brotliInitParamsArray.fill(-1);
+  if (opts.params) {
+    Object.keys(opts.params).forEach(function (origKey) {
+      var key = +origKey;
+      if (
+        Number.isNaN(key) || key < 0 || key > kMaxBrotliParam ||
+        (brotliInitParamsArray[key] | 0) !== -1
+      ) {
+        throw new ERR_BROTLI_INVALID_PARAM(origKey);
+      }
+
+      var value = opts.params[origKey];
+      if (typeof value !== "number" && typeof value !== "boolean") {
+        throw new ERR_INVALID_ARG_TYPE(
+          "options.params[key]",
+          "number",
+          opts.params[origKey],
+        );
+      }
+      brotliInitParamsArray[key] = value;
+    });
+  }
+
+  var handle = new binding.Brotli(mode);
+  if (!handle.init(brotliInitParamsArray)) {
+    handle.close();
+    throw new ERR_ZLIB_INITIALIZATION_FAILED();
+  }
+
+  ZlibBase.call(this, opts, handle, brotliDefaultOpts);
+}
+
+util.inherits(Brotli, ZlibBase);
+
+function BrotliCompress(opts) {
+  if (!(this instanceof BrotliCompress)) return new BrotliCompress(opts);
+  Brotli.call(this, opts, binding.BROTLI_ENCODE);
+}
+
+function BrotliDecompress(opts) {
+  if (!(this instanceof BrotliDecompress)) return new BrotliDecompress(opts);
+  Brotli.call(this, opts, binding.BROTLI_DECODE);
+}
+
+util.inherits(BrotliCompress, Brotli);
+util.inherits(BrotliDecompress, Brotli);
+
+ZlibBase.prototype.reset = function () {
   assert(this._handle, "zlib binding closed");
   return this._handle.reset();
 };
 
 // This is the _flush function called by the transform class,
 // internally, when the last chunk has been written.
-Zlib.prototype._flush = function (callback) {
+ZlibBase.prototype._flush = function (callback) {
   this._transform(Buffer.alloc(0), "", callback);
 };
 
-Zlib.prototype.flush = function (kind, callback) {
+ZlibBase.prototype.flush = function (kind, callback) {
   var _this2 = this;
 
   var ws = this._writableState;
 
   if (typeof kind === "function" || kind === undefined && !callback) {
     callback = kind;
-    kind = binding.Z_FULL_FLUSH;
+    kind = this._defaultFullFlushFlag;
   }
 
   if (ws.ended) {
@@ -464,7 +581,7 @@ Zlib.prototype.flush = function (kind, callback) {
   }
 };
 
-Zlib.prototype.close = function (callback) {
+ZlibBase.prototype.close = function (callback) {
   _close(this, callback);
   nextTick(emitCloseNT, this);
 };
@@ -483,7 +600,7 @@ function emitCloseNT(self) {
   self.emit("close");
 }
 
-Zlib.prototype._transform = function (chunk, encoding, cb) {
+ZlibBase.prototype._transform = function (chunk, encoding, cb) {
   var flushFlag;
   var ws = this._writableState;
   var ending = ws.ending || ws.ended;
@@ -506,14 +623,14 @@ Zlib.prototype._transform = function (chunk, encoding, cb) {
     // once we've flushed the last of the queue, stop flushing and
     // go back to the normal behavior.
     if (chunk.length >= ws.length) {
-      this._flushFlag = this._opts.flush || binding.Z_NO_FLUSH;
+      this._flushFlag = this._opts.flush || this._defaultFlushFlag;
     }
   }
 
   this._processChunk(chunk, flushFlag, cb);
 };
 
-Zlib.prototype._processChunk = function (chunk, flushFlag, cb) {
+ZlibBase.prototype._processChunk = function (chunk, flushFlag, cb) {
   var availInBefore = chunk && chunk.length;
   var availOutBefore = this._chunkSize - this._offset;
   var inOff = 0;
@@ -647,4 +764,14 @@ util.inherits(DeflateRaw, Zlib);
 util.inherits(InflateRaw, Zlib);
 util.inherits(Unzip, Zlib);
 
-export { Deflate, DeflateRaw, Gunzip, Gzip, Inflate, InflateRaw, Unzip };
+export {
+  BrotliCompress,
+  BrotliDecompress,
+  Deflate,
+  DeflateRaw,
+  Gunzip,
+  Gzip,
+  Inflate,
+  InflateRaw,
+  Unzip,
+};
@@ -43,11 +43,23 @@ export const DEFLATERAW = 5;
 export const INFLATERAW = 6;
 export const UNZIP = 7;
 
+// brotli modes
+export const BROTLI_DECODE = 8;
+export const BROTLI_ENCODE = 9;
+
+import { core } from "ext:core/mod.js";
 import {
+  op_brotli_stream_error,
+  op_brotli_stream_init,
+  op_brotli_stream_new,
+  op_brotli_stream_reset,
+  op_brotli_stream_write,
+  op_brotli_stream_write_async,
   op_zlib_close,
   op_zlib_close_if_pending,
   op_zlib_init,
   op_zlib_new,
+  op_zlib_params,
   op_zlib_reset,
   op_zlib_write,
   op_zlib_write_async,
@@ -163,8 +175,11 @@ class Zlib {
     }
   }
 
-  params() {
-    throw new Error("deflateParams Not supported");
+  params(level, strategy) {
+    const err = op_zlib_params(this.#handle, level, strategy);
+    if (err != Z_OK) {
+      this.#error("Failed to set parameters", err);
+    }
   }
 
   reset() {
@@ -180,4 +195,100 @@ class Zlib {
   }
 }
 
-export { Zlib };
+// The counterpart of `Zlib` for the brotli streams, with the same
+// interface. The flush values are the `BROTLI_OPERATION_*` constants.
+class Brotli {
+  #handle;
+
+  constructor(mode) {
+    this.#handle = op_brotli_stream_new(mode);
+  }
+
+  // `params` has a value for each parameter, -1 for those to leave as they
+  // are.
+  init(params) {
+    return op_brotli_stream_init(this.#handle, params);
+  }
+
+  close() {
+    core.tryClose(this.#handle);
+  }
+
+  writeSync(
+    flush,
+    input,
+    in_off,
+    in_len,
+    out,
+    out_off,
+    out_len,
+  ) {
+    const ok = op_brotli_stream_write(
+      this.#handle,
+      flush,
+      input,
+      in_off,
+      in_len,
+      out,
+      out_off,
+      out_len,
+      writeResult,
+    );
+
+    if (ok) {
+      return [writeResult[1], writeResult[0]];
+    }
+    this.#error();
+    return;
+  }
+
+  write(
+    flush,
+    input,
+    in_off,
+    in_len,
+    out,
+    out_off,
+    out_len,
+  ) {
+    op_brotli_stream_write_async(
+      this.#handle,
+      flush,
+      input,
+      in_off,
+      in_len,
+      out,
+      out_off,
+      out_len,
+    ).then(([ok, availOut, availIn]) => {
+      if (ok) {
+        this.callback(availIn, availOut);
+      } else {
+        this.#error();
+      }
+    });
+
+    return this;
+  }
+
+  params() {
+    // the brotli parameters are only set on init
+  }
+
+  reset() {
+    if (!op_brotli_stream_reset(this.#handle)) {
+      this.onerror(
+        "Failed to reset stream",
+        -1,
+        "ERR_ZLIB_INITIALIZATION_FAILED",
+      );
+    }
+  }
+
+  #error() {
+    const { message, errno, code } = op_brotli_stream_error(this.#handle);
+    this.onerror(message, errno, code);
+  }
+}
+
+export { Brotli, Zlib };
@@ -823,9 +823,22 @@ export function execFileSync(
 }
 
 function setupChildProcessIpcChannel() {
-  const fd = op_node_child_ipc_pipe();
-  if (typeof fd != "number" || fd < 0) return;
-  setupChannel(process, fd);
+  const pipe = op_node_child_ipc_pipe();
+  if (pipe === null) return;
+  const [rid, serialization] = pipe;
+  // Like in Node, the channel only keeps the child alive while there are
+  // listeners for it.
+  const control = setupChannel(process, rid, serialization, false);
+  process.on("newListener", (event: string) => {
+    if (event === "message" || event === "disconnect") {
+      control.refCounted();
+    }
+  });
+  process.on("removeListener", (event: string) => {
+    if (event === "message" || event === "disconnect") {
+      control.unrefCounted();
+    }
+  });
 }
 
 internals.__setupChildProcessIpcChannel = setupChildProcessIpcChannel;
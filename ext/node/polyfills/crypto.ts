@@ -22,6 +22,7 @@ import {
   randomFillSync,
   randomInt,
   randomUUID,
+  randomUUIDv7,
 } from "ext:deno_node/internal/crypto/random.ts";
 import type {
   CheckPrimeOptions,
@@ -367,6 +368,7 @@ export default {
   randomFillSync,
   randomInt,
   randomUUID,
+  randomUUIDv7,
   scrypt,
   scryptSync,
   secureHeapUsed,
@@ -506,6 +508,7 @@ export {
   randomFillSync,
   randomInt,
   randomUUID,
+  randomUUIDv7,
   scrypt,
   scryptSync,
   secureHeapUsed,
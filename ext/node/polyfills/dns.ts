@@ -93,7 +93,6 @@ import {
   QueryReqWrap,
 } from "ext:deno_node/internal_binding/cares_wrap.ts";
 import { domainToASCII } from "ext:deno_node/internal/idna.ts";
-import { notImplemented } from "ext:deno_node/_utils.ts";
 
 function onlookup(
   this: GetAddrInfoReqWrap,
@@ -291,7 +290,7 @@ Object.defineProperty(lookup, customPromisifyArgs, {
 
 function onresolve(
   this: QueryReqWrap,
-  err: number,
+  err: number | string,
   records: Records,
   ttls?: number[],
 ) {
@@ -332,10 +331,6 @@ function resolver(bindingName: keyof ChannelWrapQuery) {
     req.hostname = name;
     req.oncomplete = onresolve;
 
-    if (options && (options as ResolveOptions).ttl) {
-      notImplemented("dns.resolve* with ttl option");
-    }
-
     req.ttl = !!(options && (options as ResolveOptions).ttl);
 
     const err = this._handle[bindingName](req, domainToASCII(name));
@@ -43,7 +43,11 @@ import {
 import { ok as assert } from "node:assert";
 import { kOutHeaders } from "ext:deno_node/internal/http.ts";
 import { _checkIsHttpToken as checkIsHttpToken } from "ext:deno_node/_http_common.ts";
-import { Agent, globalAgent } from "ext:deno_node/_http_agent.mjs";
+import {
+  Agent,
+  getAgentPool,
+  globalAgent,
+} from "ext:deno_node/_http_agent.mjs";
 // import { chunkExpression as RE_TE_CHUNKED } from "ext:deno_node/_http_common.ts";
 import { urlToHttpOptions } from "ext:deno_node/internal/url.ts";
 import { kEmptyObject } from "ext:deno_node/internal/util.mjs";
@@ -598,8 +602,12 @@ class ClientRequest extends OutgoingMessage {
       }
     }
 
-    const client = this._getClient() ?? createHttpClient({ http2: false });
-    this._client = client;
+    // requests made with an agent share its pool, the others get a client
+    // of their own
+    const agentPool = getAgentPool(this.agent, this._getCaCerts());
+    if (agentPool === undefined) {
+      this._client = this._getClient() ?? createHttpClient({ http2: false });
+    }
 
     if (
       this.method === "POST" || this.method === "PATCH" || this.method === "PUT"
@@ -620,7 +628,8 @@ class ClientRequest extends OutgoingMessage {
       this.method,
       url,
       headers,
-      client[internalRidSymbol],
+      this._client?.[internalRidSymbol],
+      agentPool,
       this._bodyWriteRid,
     );
   }
@@ -639,6 +648,11 @@ class ClientRequest extends OutgoingMessage {
     return undefined;
   }
 
+  /** The extra CA certificates to trust, for `https`. */
+  _getCaCerts(): string[] | undefined {
+    return undefined;
+  }
+
   // TODO(bartlomieju): handle error
   onSocket(socket, _err) {
     nextTick(() => {
@@ -680,7 +694,7 @@ class ClientRequest extends OutgoingMessage {
           this._timeout.removeEventListener("abort", this._timeoutCb);
           webClearTimeout(this._timeout[timerId]);
         }
-        this._client.close();
+        this._client?.close();
         const incoming = new IncomingMessageForClient(this.socket);
         incoming.req = this;
         this.res = incoming;
@@ -6,6 +6,7 @@
 
 import { core, primordials } from "ext:core/mod.js";
 import {
+  op_http2_accept,
   op_http2_client_get_response,
   op_http2_client_get_response_body_chunk,
   op_http2_client_get_response_trailers,
@@ -14,12 +15,20 @@ import {
   op_http2_client_send_data,
   op_http2_client_send_trailers,
   op_http2_connect,
+  op_http2_listen,
   op_http2_poll_client_connection,
-  op_http_set_response_trailers,
+  op_http2_send_response,
+  op_http2_server_push_stream,
+  op_http2_server_reset_stream,
+  op_http2_server_send_data,
+  op_http2_server_send_trailers,
+  op_http2_session_goaway,
+  op_http2_session_ping,
+  op_http2_session_set_local_window_size,
+  op_http2_session_settings,
 } from "ext:core/ops";
 
 import { notImplemented, warnNotImplemented } from "ext:deno_node/_utils.ts";
-import { toInnerRequest } from "ext:deno_fetch/23_request.js";
 import { Readable } from "node:stream";
 import { EventEmitter } from "node:events";
 import { Buffer } from "node:buffer";
@@ -27,7 +36,11 @@ import { emitWarning } from "node:process";
 import Stream from "node:stream";
 import { connect as netConnect, Server, Socket, TCP } from "node:net";
 import { connect as tlsConnect } from "node:tls";
-import { TypedArray } from "ext:deno_node/internal/util/types.ts";
+import { ServerImpl as TLSServer } from "ext:deno_node/_tls_wrap.ts";
+import {
+  isArrayBufferView,
+  TypedArray,
+} from "ext:deno_node/internal/util/types.ts";
 import {
   kHandle,
   kMaybeDestroy,
@@ -36,7 +49,6 @@ import {
 } from "ext:deno_node/internal/stream_base_commons.ts";
 import { FileHandle } from "node:fs/promises";
 import { kStreamBaseField } from "ext:deno_node/internal_binding/stream_wrap.ts";
-import { serveHttpOnConnection } from "ext:deno_http/00_serve.ts";
 import { nextTick } from "ext:deno_node/_next_tick.ts";
 import { TextEncoder } from "ext:deno_web/08_text_encoding.js";
 import { Duplex } from "node:stream";
@@ -48,10 +60,16 @@ import {
   ERR_HTTP2_GOAWAY_SESSION,
   ERR_HTTP2_HEADERS_SENT,
   ERR_HTTP2_INFO_STATUS_NOT_ALLOWED,
+  ERR_HTTP2_INVALID_PACKED_SETTINGS_LENGTH,
   ERR_HTTP2_INVALID_PSEUDOHEADER,
   ERR_HTTP2_INVALID_SESSION,
+  ERR_HTTP2_INVALID_SETTING_VALUE,
   ERR_HTTP2_INVALID_STREAM,
+  ERR_HTTP2_NESTED_PUSH,
   ERR_HTTP2_NO_SOCKET_MANIPULATION,
+  ERR_HTTP2_PING_CANCEL,
+  ERR_HTTP2_PING_LENGTH,
+  ERR_HTTP2_PUSH_DISABLED,
   ERR_HTTP2_SESSION_ERROR,
   ERR_HTTP2_STATUS_INVALID,
   ERR_HTTP2_STREAM_CANCEL,
@@ -60,12 +78,19 @@ import {
   ERR_HTTP2_TRAILERS_ALREADY_SENT,
   ERR_HTTP2_TRAILERS_NOT_READY,
   ERR_HTTP2_UNSUPPORTED_PROTOCOL,
+  ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
   ERR_INVALID_HTTP_TOKEN,
   ERR_SOCKET_CLOSED,
   ERR_STREAM_WRITE_AFTER_END,
 } from "ext:deno_node/internal/errors.ts";
 import { _checkIsHttpToken } from "ext:deno_node/_http_common.ts";
+import {
+  validateFunction,
+  validateInt32,
+  validateNumber,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
 const {
   StringPrototypeTrim,
   FunctionPrototypeBind,
@@ -94,6 +119,10 @@ const kSentTrailers = Symbol("sent-trailers");
 const kState = Symbol("state");
 const kType = Symbol("type");
 const kTimeout = Symbol("timeout");
+const kLocalSettings = Symbol("local-settings");
+const kServer = Symbol("server");
+const kPushed = Symbol("pushed");
+const kWhenConnected = Symbol("whenConnected");
 
 const kDenoResponse = Symbol("kDenoResponse");
 const kDenoRid = Symbol("kDenoRid");
@@ -120,6 +149,8 @@ const SESSION_FLAGS_DESTROYED = 0x4;
 const ENCODER = new TextEncoder();
 type Http2Headers = Record<string, string | string[]>;
 
+const { internalRidSymbol } = core;
+
 const debugHttp2Enabled = false;
 function debugHttp2(...args) {
   if (debugHttp2Enabled) {
@@ -128,7 +159,7 @@ function debugHttp2(...args) {
 }
 
 export class Http2Session extends EventEmitter {
-  constructor(type, _options /* socket */) {
+  constructor(type, options /* socket */) {
     super();
 
     // TODO(bartlomieju): Handle sockets here
@@ -149,6 +180,7 @@ export class Http2Session extends EventEmitter {
     this[kAlpnProtocol] = undefined;
     this[kType] = type;
     this[kTimeout] = null;
+    this[kLocalSettings] = { ...getDefaultSettings(), ...options?.settings };
     // this[kProxySocket] = null;
     // this[kSocket] = socket;
     // this[kHandle] = undefined;
@@ -193,16 +225,70 @@ export class Http2Session extends EventEmitter {
     }
   }
 
-  setLocalWindowSize(_windowSize: number) {
-    notImplemented("Http2Session.setLocalWindowSize");
+  // Runs `fn` with the rid of the connection once the session is connected.
+  [kWhenConnected](fn: (rid: number) => void) {
+    if (this[kDenoConnRid] !== undefined) {
+      fn(this[kDenoConnRid]);
+    } else {
+      this.once("connect", () => fn(this[kDenoConnRid]));
+    }
+  }
+
+  setLocalWindowSize(windowSize: number) {
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    validateInt32(windowSize, "windowSize", 0);
+    this[kWhenConnected]((rid) =>
+      op_http2_session_set_local_window_size(rid, windowSize)
+    );
   }
 
   ping(
-    _payload: Buffer | TypedArray | DataView,
-    _callback: () => void,
+    payload: Buffer | TypedArray | DataView | undefined,
+    callback: (
+      err: Error | null,
+      duration: number,
+      payload: Buffer,
+    ) => void,
   ): boolean {
-    notImplemented("Http2Session.ping");
-    return false;
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    if (typeof payload === "function") {
+      callback = payload;
+      payload = undefined;
+    }
+    if (payload !== undefined) {
+      if (!isArrayBufferView(payload)) {
+        throw new ERR_INVALID_ARG_TYPE(
+          "payload",
+          ["ArrayBuffer", "Buffer", "TypedArray", "DataView"],
+          payload,
+        );
+      }
+      if (payload.byteLength !== 8) {
+        throw new ERR_HTTP2_PING_LENGTH();
+      }
+    }
+    validateFunction(callback, "callback");
+
+    // h2 sends a payload of its own, the one given is handed back as is
+    const pingPayload = payload === undefined
+      ? Buffer.alloc(8)
+      : Buffer.from(payload.buffer, payload.byteOffset, 8);
+    this[kWhenConnected]((rid) => {
+      op_http2_session_ping(rid).then(
+        (duration) => callback(null, duration, pingPayload),
+        (err) =>
+          callback(
+            this.destroyed ? new ERR_HTTP2_PING_CANCEL() : err,
+            0,
+            pingPayload,
+          ),
+      );
+    });
+    return true;
   }
 
   get socket(): Socket /*| TlsSocket*/ {
@@ -223,26 +309,65 @@ export class Http2Session extends EventEmitter {
   }
 
   get localSettings(): Record<string, unknown> {
-    notImplemented("Http2Session.localSettings");
-    return {};
+    return { ...this[kLocalSettings] };
   }
 
+  // h2 doesn't expose the settings of the peer, these are the defaults of
+  // the protocol.
   get remoteSettings(): Record<string, unknown> {
-    notImplemented("Http2Session.remoteSettings");
-    return {};
+    return getDefaultSettings();
   }
 
-  settings(_settings: Record<string, unknown>, _callback: () => void) {
-    notImplemented("Http2Session.settings");
+  settings(
+    settings: Record<string, unknown>,
+    callback?: (
+      err: Error | null,
+      settings: Record<string, unknown>,
+      duration: number,
+    ) => void,
+  ) {
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    validateObject(settings, "settings");
+    validateSettings(settings);
+    if (callback !== undefined) {
+      validateFunction(callback, "callback");
+    }
+
+    // Only the initial window size and the extended CONNECT protocol can
+    // change on an established connection, the settings are applied once
+    // they're queued as h2 doesn't report their acknowledgement.
+    this[kState].pendingAck++;
+    this[kWhenConnected]((rid) => {
+      this[kState].pendingAck--;
+      if (this.destroyed) {
+        return;
+      }
+      op_http2_session_settings(rid, settings);
+      this[kLocalSettings] = { ...this[kLocalSettings], ...settings };
+      this.emit("localSettings", this.localSettings);
+      callback?.(null, this.localSettings, 0);
+    });
   }
 
+  // The last stream ID and the opaque data can't be given with h2.
   goaway(
-    code?: number,
+    code: number = constants.NGHTTP2_NO_ERROR,
     lastStreamID?: number,
     opaqueData?: Buffer | TypedArray | DataView,
   ) {
-    // TODO(satyarohith): create goaway op and pass the args
-    debugHttp2(">>> goaway - ignored args", code, lastStreamID, opaqueData);
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    validateNumber(code, "code");
+    debugHttp2(">>> goaway - ignored args", lastStreamID, opaqueData);
+    this[kState].goawayCode = code;
+
+    if (this[kType] === constants.NGHTTP2_SESSION_SERVER) {
+      this[kWhenConnected]((rid) => op_http2_session_goaway(rid, code));
+      return;
+    }
     if (this[kDenoConnRid]) {
       core.tryClose(this[kDenoConnRid]);
     }
@@ -344,6 +469,17 @@ function closeSession(session: Http2Session, code?: number, error?: Error) {
     session[kDenoConnRid],
     session[kDenoClientRid],
   );
+  if (
+    session[kType] === constants.NGHTTP2_SESSION_SERVER &&
+    session[kDenoConnRid]
+  ) {
+    // the connection is driven by the op accepting its streams, which is
+    // told to shut it down
+    op_http2_session_goaway(
+      session[kDenoConnRid],
+      code ?? constants.NGHTTP2_NO_ERROR,
+    );
+  }
   if (session[kDenoConnRid]) {
     core.tryClose(session[kDenoConnRid]);
   }
@@ -355,8 +491,13 @@ function closeSession(session: Http2Session, code?: number, error?: Error) {
 }
 
 export class ServerHttp2Session extends Http2Session {
-  constructor() {
-    super(constants.NGHTTP2_SESSION_SERVER, {});
+  constructor(server?: Http2Server | Http2SecureServer, options = {}) {
+    super(constants.NGHTTP2_SESSION_SERVER, options);
+    this[kServer] = server;
+  }
+
+  get server(): Http2Server | Http2SecureServer | undefined {
+    return this[kServer];
   }
 
   altsvc(
@@ -395,6 +536,8 @@ export class ClientHttp2Session extends Http2Session {
     options: Record<string, unknown>,
   ) {
     super(constants.NGHTTP2_SESSION_CLIENT, options);
+    // pushed streams aren't supported, see `Http2Settings::client_builder()`
+    this[kLocalSettings].enablePush = false;
     this[kPendingRequestCalls] = null;
     this[kDenoClientRid] = undefined;
     this[kDenoConnRid] = undefined;
@@ -418,10 +561,15 @@ export class ClientHttp2Session extends Http2Session {
       debugHttp2(">>> before connect");
       const connRid_ = await connPromise;
       // console.log(">>>> awaited connRid", connRid_, url);
-      const [clientRid, connRid] = await op_http2_connect(connRid_, url);
+      const [clientRid, connRid] = await op_http2_connect(
+        connRid_,
+        url,
+        options.settings ?? {},
+      );
       debugHttp2(">>> after connect", clientRid, connRid);
       this[kDenoClientRid] = clientRid;
       this[kDenoConnRid] = connRid;
+      this[kState].flags |= SESSION_FLAGS_READY;
       (async () => {
         try {
           const promise = op_http2_poll_client_connection(
@@ -583,154 +731,6 @@ function getAuthority(headers) {
   return undefined;
 }
 
-export class Http2Stream extends EventEmitter {
-  #session: Http2Session;
-  #headers: Promise<Http2Headers>;
-  #controllerPromise: Promise<ReadableStreamDefaultController<Uint8Array>>;
-  #readerPromise: Promise<ReadableStream<Uint8Array>>;
-  #closed: boolean;
-  _response: Response;
-  // This is required to set the trailers on the response.
-  _request: Request;
-
-  constructor(
-    session: Http2Session,
-    headers: Promise<Http2Headers>,
-    controllerPromise: Promise<ReadableStreamDefaultController<Uint8Array>>,
-    readerPromise: Promise<ReadableStream<Uint8Array>>,
-  ) {
-    super();
-    this.#session = session;
-    this.#headers = headers;
-    this.#controllerPromise = controllerPromise;
-    this.#readerPromise = readerPromise;
-    this.#closed = false;
-    nextTick(() => {
-      (async () => {
-        const headers = await this.#headers;
-        this.emit("headers", headers);
-      })();
-      (async () => {
-        const reader = await this.#readerPromise;
-        if (reader) {
-          for await (const data of reader) {
-            this.emit("data", new Buffer(data));
-          }
-        }
-        this.emit("end");
-      })();
-    });
-  }
-
-  // TODO(mmastrac): Implement duplex
-  end() {
-    (async () => {
-      const controller = await this.#controllerPromise;
-      controller.close();
-    })();
-  }
-
-  write(buffer, callback?: () => void) {
-    (async () => {
-      const controller = await this.#controllerPromise;
-      if (typeof buffer === "string") {
-        controller.enqueue(ENCODER.encode(buffer));
-      } else {
-        controller.enqueue(buffer);
-      }
-      callback?.();
-    })();
-  }
-
-  setEncoding(_encoding) {}
-
-  resume() {
-  }
-
-  pause() {
-  }
-
-  get aborted(): boolean {
-    notImplemented("Http2Stream.aborted");
-    return false;
-  }
-
-  get bufferSize(): number {
-    notImplemented("Http2Stream.bufferSize");
-    return 0;
-  }
-
-  close(_code: number, _callback: () => void) {
-    this.#closed = true;
-    this.emit("close");
-  }
-
-  get closed(): boolean {
-    return this.#closed;
-  }
-
-  get destroyed(): boolean {
-    return false;
-  }
-
-  get endAfterHeaders(): boolean {
-    notImplemented("Http2Stream.endAfterHeaders");
-    return false;
-  }
-
-  get id(): number | undefined {
-    notImplemented("Http2Stream.id");
-    return undefined;
-  }
-
-  get pending(): boolean {
-    notImplemented("Http2Stream.pending");
-    return false;
-  }
-
-  priority(_options: Record<string, unknown>) {
-    notImplemented("Http2Stream.priority");
-  }
-
-  get rstCode(): number {
-    // notImplemented("Http2Stream.rstCode");
-    return 0;
-  }
-
-  get sentHeaders(): boolean {
-    notImplemented("Http2Stream.sentHeaders");
-    return false;
-  }
-
-  get sentInfoHeaders(): Record<string, unknown> {
-    notImplemented("Http2Stream.sentInfoHeaders");
-    return {};
-  }
-
-  get sentTrailers(): Record<string, unknown> {
-    notImplemented("Http2Stream.sentTrailers");
-    return {};
-  }
-
-  get session(): Http2Session {
-    return this.#session;
-  }
-
-  setTimeout(msecs: number, callback?: () => void) {
-    setStreamTimeout(this, msecs, callback);
-  }
-
-  get state(): Record<string, unknown> {
-    notImplemented("Http2Stream.state");
-    return {};
-  }
-
-  sendTrailers(headers: Record<string, unknown>) {
-    const request = toInnerRequest(this._request);
-    op_http_set_response_trailers(request.external, Object.entries(headers));
-  }
-}
-
 async function clientHttp2Request(
   session,
   sessionConnectPromise,
@@ -1300,75 +1300,227 @@ function callTimeout() {
   notImplemented("callTimeout");
 }
 
-export class ServerHttp2Stream extends Http2Stream {
-  _deferred: ReturnType<typeof Promise.withResolvers<Response>>;
-  #body: ReadableStream<Uint8Array>;
-  #waitForTrailers: boolean;
-  #headersSent: boolean;
+// Splits the headers into the pseudo-headers and the list of the others,
+// with one entry per value.
+function splitHeaders(
+  headers: Http2Headers,
+): [Record<string, string>, [string, string][]] {
+  const pseudoHeaders = {};
+  const headerList = [];
+  for (const [name, value] of Object.entries(headers)) {
+    if (value === undefined || value === null) {
+      continue;
+    }
+    const key = StringPrototypeToLowerCase(name);
+    if (key[0] === ":") {
+      pseudoHeaders[key] = String(value);
+    } else if (ArrayIsArray(value)) {
+      for (const item of value) {
+        headerList.push([key, String(item)]);
+      }
+    } else {
+      headerList.push([key, String(value)]);
+    }
+  }
+  return [pseudoHeaders, headerList];
+}
+
+function toHeaderObject(headerList: [string, string][]): Http2Headers {
+  const headers = { __proto__: null };
+  for (const [name, value] of headerList) {
+    const existing = headers[name];
+    if (existing === undefined) {
+      headers[name] = name === "set-cookie" ? [value] : value;
+    } else if (name === "set-cookie") {
+      existing.push(value);
+    } else if (name === "cookie") {
+      headers[name] = `${existing}; ${value}`;
+    } else {
+      headers[name] = `${existing}, ${value}`;
+    }
+  }
+  return headers;
+}
+
+export class ServerHttp2Stream extends Duplex {
+  #rid: number;
+  #bodyRid: number | undefined;
+  // The ops sending the frames of the stream, in order.
+  #sending: Promise<unknown> = Promise.resolve();
 
   constructor(
-    session: Http2Session,
-    headers: Promise<Http2Headers>,
-    controllerPromise: Promise<ReadableStreamDefaultController<Uint8Array>>,
-    reader: ReadableStream<Uint8Array>,
-    body: ReadableStream<Uint8Array>,
-    // This is required to set the trailers on the response.
-    req: Request,
+    session: ServerHttp2Session,
+    rid: number,
+    bodyRid: number | undefined,
+    id: number,
+    headers: Http2Headers,
   ) {
-    super(session, headers, controllerPromise, Promise.resolve(reader));
-    this._deferred = Promise.withResolvers<Response>();
-    this.#body = body;
-    this._request = req;
+    super({ allowHalfOpen: true, decodeStrings: false, autoDestroy: false });
+    this[kSession] = session;
+    this.#rid = rid;
+    this.#bodyRid = bodyRid;
+    this[kID] = id;
+    this[kTimeout] = null;
+    this[kState] = {
+      flags: STREAM_FLAGS_READY,
+      rstCode: constants.NGHTTP2_NO_ERROR,
+      writeQueueSize: 0,
+      trailersReady: false,
+      endAfterHeaders: false,
+      endSent: false,
+    };
+    this[kAuthority] = getAuthority(headers);
+    this[kProtocol] = headers[constants.HTTP2_HEADER_SCHEME];
+    if (headers[constants.HTTP2_HEADER_METHOD] === constants.HTTP2_METHOD_HEAD) {
+      this[kState].flags |= STREAM_FLAGS_HEAD_REQUEST;
+    }
+    session[kState].streams.set(id, this);
   }
 
-  additionalHeaders(_headers: Record<string, unknown>) {
-    notImplemented("ServerHttp2Stream.additionalHeaders");
+  #send<T>(op: () => Promise<T>): Promise<T> {
+    const promise = this.#sending.then(op);
+    this.#sending = promise.catch(() => {});
+    return promise;
   }
 
-  end(): void {
-    super.end();
-    if (this.#waitForTrailers) {
-      this.emit("wantTrailers");
+  [kUpdateTimer]() {
+    if (this.destroyed) {
+      return;
     }
+    if (this[kTimeout]) {
+      this[kTimeout].refresh();
+    }
+    if (this[kSession]) {
+      this[kSession][kUpdateTimer]();
+    }
+  }
+
+  get id(): number {
+    return this[kID];
+  }
+
+  get pending(): boolean {
+    return false;
+  }
+
+  get session(): Http2Session {
+    return this[kSession];
   }
 
   get headersSent(): boolean {
-    return this.#headersSent;
+    return !!(this[kState].flags & STREAM_FLAGS_HEADERS_SENT);
+  }
+
+  get headRequest(): boolean {
+    return !!(this[kState].flags & STREAM_FLAGS_HEAD_REQUEST);
+  }
+
+  get aborted(): boolean {
+    return !!(this[kState].flags & STREAM_FLAGS_ABORTED);
+  }
+
+  get closed(): boolean {
+    return !!(this[kState].flags & STREAM_FLAGS_CLOSED);
+  }
+
+  get rstCode(): number {
+    return this[kState].rstCode;
+  }
+
+  get endAfterHeaders(): boolean {
+    return this[kState].endAfterHeaders;
+  }
+
+  get bufferSize(): number {
+    return this[kState].writeQueueSize + this.writableLength;
+  }
+
+  get sentHeaders() {
+    return this[kSentHeaders];
+  }
+
+  get sentTrailers() {
+    return this[kSentTrailers];
+  }
+
+  get sentInfoHeaders() {
+    return this[kInfoHeaders];
+  }
+
+  get state(): Record<string, unknown> {
+    notImplemented("Http2Stream.state");
+    return {};
   }
 
   get pushAllowed(): boolean {
-    notImplemented("ServerHttp2Stream.pushAllowed");
-    return false;
+    return !this.destroyed && !this.closed && !this.headRequest &&
+      !this[kPushed];
   }
 
-  pushStream(
-    _headers: Record<string, unknown>,
-    _options: Record<string, unknown>,
-    _callback: () => unknown,
-  ) {
-    notImplemented("ServerHttp2Stream.pushStream");
+  priority(_options: Record<string, unknown>) {
+    notImplemented("Http2Stream.priority");
+  }
+
+  additionalHeaders(_headers: Record<string, unknown>) {
+    notImplemented("ServerHttp2Stream.additionalHeaders");
   }
 
   respond(
-    headers: Http2Headers,
-    options: Record<string, unknown>,
+    headers?: Http2Headers,
+    options?: Record<string, unknown>,
   ) {
-    this.#headersSent = true;
-    const response: ResponseInit = {};
-    if (headers) {
-      for (const [name, value] of Object.entries(headers)) {
-        if (name == constants.HTTP2_HEADER_STATUS) {
-          response.status = Number(value);
-        }
+    if (this.destroyed || this.closed) {
+      throw new ERR_HTTP2_INVALID_STREAM();
+    }
+    if (this.headersSent) {
+      throw new ERR_HTTP2_HEADERS_SENT();
+    }
+    this[kUpdateTimer]();
+
+    headers = ObjectAssign({ __proto__: null }, headers);
+    options = { ...options };
+    const state = this[kState];
+    state.flags |= STREAM_FLAGS_HEADERS_SENT;
+
+    const status = Number(
+      headers[constants.HTTP2_HEADER_STATUS] ?? constants.HTTP_STATUS_OK,
+    );
+    if (status < 200 || status > 599) {
+      if (status >= 100 && status < 200) {
+        throw new ERR_HTTP2_INFO_STATUS_NOT_ALLOWED();
       }
+      throw new ERR_HTTP2_STATUS_INVALID(status);
     }
-    if (options?.endStream) {
-      this._deferred.resolve(this._response = new Response("", response));
+    delete headers[constants.HTTP2_HEADER_STATUS];
+    if (options.sendDate !== false && headers.date === undefined) {
+      headers.date = new Date().toUTCString();
+    }
+    this[kSentHeaders] = headers;
+
+    const endStream = !!options.endStream ||
+      status === constants.HTTP_STATUS_NO_CONTENT ||
+      status === constants.HTTP_STATUS_NOT_MODIFIED ||
+      this.headRequest;
+    if (options.waitForTrailers) {
+      state.flags |= STREAM_FLAGS_HAS_TRAILERS;
+      // the trailers end the stream instead
+      state.endSent = false;
     } else {
-      this.#waitForTrailers = options?.waitForTrailers;
-      this._deferred.resolve(
-        this._response = new Response(this.#body, response),
-      );
+      state.endSent = endStream;
+    }
+
+    const [, headerList] = splitHeaders(headers);
+    const eos = endStream && !options.waitForTrailers;
+    this.#send(() =>
+      op_http2_send_response(this.#rid, status, headerList, eos)
+    ).then(() => {
+      if (eos) {
+        finishServerStream(this);
+      }
+    }, (err) => this.destroy(err));
+
+    if (endStream) {
+      this.end();
     }
   }
 
@@ -1387,6 +1539,218 @@ export class ServerHttp2Stream extends Http2Stream {
   ) {
     notImplemented("ServerHttp2Stream.respondWithFile");
   }
+
+  pushStream(
+    headers: Http2Headers,
+    options: Record<string, unknown> | undefined,
+    callback?: (
+      err: Error | null,
+      pushStream?: ServerHttp2Stream,
+      headers?: Http2Headers,
+    ) => void,
+  ) {
+    if (typeof options === "function") {
+      callback = options;
+      options = undefined;
+    }
+    validateFunction(callback, "callback");
+    if (this[kPushed]) {
+      throw new ERR_HTTP2_NESTED_PUSH();
+    }
+    if (!this.pushAllowed) {
+      throw new ERR_HTTP2_PUSH_DISABLED();
+    }
+    this[kUpdateTimer]();
+
+    headers = ObjectAssign({ __proto__: null }, headers);
+    if (headers[constants.HTTP2_HEADER_METHOD] === undefined) {
+      headers[constants.HTTP2_HEADER_METHOD] = constants.HTTP2_METHOD_GET;
+    }
+    if (getAuthority(headers) === undefined) {
+      headers[constants.HTTP2_HEADER_AUTHORITY] = this[kAuthority];
+    }
+    if (headers[constants.HTTP2_HEADER_SCHEME] === undefined) {
+      headers[constants.HTTP2_HEADER_SCHEME] = this[kProtocol] ?? "https";
+    }
+    if (headers[constants.HTTP2_HEADER_PATH] === undefined) {
+      headers[constants.HTTP2_HEADER_PATH] = "/";
+    }
+
+    const [pseudoHeaders, headerList] = splitHeaders(headers);
+    this.#send(() =>
+      op_http2_server_push_stream(this.#rid, pseudoHeaders, headerList)
+    ).then(([rid, id]) => {
+      const stream = new ServerHttp2Stream(
+        this[kSession],
+        rid,
+        undefined,
+        id,
+        headers,
+      );
+      stream[kPushed] = true;
+      stream[kSentHeaders] = headers;
+      callback(null, stream, headers);
+    }, (err) => {
+      if (String(err?.message).includes("disabled server push")) {
+        err = new ERR_HTTP2_PUSH_DISABLED();
+      }
+      callback(err);
+    });
+  }
+
+  // A write or the end of the stream before `respond()` sends the default
+  // headers.
+  _write(chunk, encoding, callback) {
+    if (!this.headersSent) {
+      this.respond();
+    }
+    this[kUpdateTimer]();
+    const data = typeof chunk === "string"
+      ? Buffer.from(chunk, encoding)
+      : chunk;
+    this.#send(() => op_http2_server_send_data(this.#rid, data, false))
+      .then(() => callback(), callback);
+  }
+
+  _final(callback) {
+    if (!this.headersSent) {
+      this.respond();
+    }
+    const state = this[kState];
+    if (state.flags & STREAM_FLAGS_HAS_TRAILERS) {
+      this.#send(() => Promise.resolve()).then(() => {
+        state.trailersReady = true;
+        if (!this.destroyed && !this.closed && !this.emit("wantTrailers")) {
+          this.sendTrailers({});
+        }
+        callback();
+      });
+      return;
+    }
+    if (state.endSent) {
+      callback();
+      return;
+    }
+    state.endSent = true;
+    this.#send(() =>
+      op_http2_server_send_data(this.#rid, new Uint8Array(), true)
+    ).then(() => {
+      callback();
+      finishServerStream(this);
+    }, callback);
+  }
+
+  _read() {
+    if (this.#bodyRid === undefined) {
+      this.push(null);
+      return;
+    }
+    const bodyRid = this.#bodyRid;
+    (async () => {
+      const [chunk] = await op_http2_client_get_response_body_chunk(bodyRid);
+      if (chunk === null) {
+        const trailerList = await op_http2_client_get_response_trailers(
+          bodyRid,
+        );
+        if (trailerList) {
+          this.emit(
+            "trailers",
+            toHeaderObject(trailerList),
+            constants.NGHTTP2_FLAG_END_STREAM,
+            trailerList.flat(),
+          );
+        }
+        this.#bodyRid = undefined;
+        core.tryClose(bodyRid);
+        this.push(null);
+        return;
+      }
+      this[kUpdateTimer]();
+      this.push(Buffer.from(chunk));
+    })().catch((err) => this.destroy(err));
+  }
+
+  sendTrailers(headers: Http2Headers) {
+    if (this.destroyed || this.closed) {
+      throw new ERR_HTTP2_INVALID_STREAM();
+    }
+    if (this[kSentTrailers]) {
+      throw new ERR_HTTP2_TRAILERS_ALREADY_SENT();
+    }
+    if (!this[kState].trailersReady) {
+      throw new ERR_HTTP2_TRAILERS_NOT_READY();
+    }
+
+    headers = ObjectAssign({ __proto__: null }, headers);
+    this[kSentTrailers] = headers;
+    this[kState].flags &= ~STREAM_FLAGS_HAS_TRAILERS;
+    this[kState].endSent = true;
+    const [, trailerList] = splitHeaders(headers);
+    this.#send(() => op_http2_server_send_trailers(this.#rid, trailerList))
+      .then(() => finishServerStream(this), (err) => this.destroy(err));
+  }
+
+  close(code: number = constants.NGHTTP2_NO_ERROR, callback?: () => void) {
+    if (this.closed) {
+      return;
+    }
+    validateNumber(code, "code");
+    if (callback !== undefined) {
+      this.once("close", callback);
+    }
+    const state = this[kState];
+    state.flags |= STREAM_FLAGS_CLOSED;
+    state.rstCode = code;
+    if (!this.writableEnded) {
+      state.flags |= STREAM_FLAGS_ABORTED;
+      this.emit("aborted");
+    }
+    this.#send(() => op_http2_server_reset_stream(this.#rid, code))
+      .catch(() => {})
+      .then(() => this.destroy());
+  }
+
+  _destroy(err, callback) {
+    const state = this[kState];
+    if (!this.closed) {
+      state.flags |= STREAM_FLAGS_CLOSED;
+      state.rstCode = err == null
+        ? constants.NGHTTP2_NO_ERROR
+        : constants.NGHTTP2_INTERNAL_ERROR;
+    }
+    // h2 resets the stream with `NGHTTP2_CANCEL` if it was still open
+    core.tryClose(this.#rid);
+    if (this.#bodyRid !== undefined) {
+      core.tryClose(this.#bodyRid);
+      this.#bodyRid = undefined;
+    }
+    clearTimeout(this[kTimeout]);
+
+    const session = this[kSession];
+    if (session !== undefined) {
+      session[kState].streams.delete(this[kID]);
+      this[kSession] = undefined;
+      session[kMaybeDestroy]();
+    }
+    callback(err);
+  }
+
+  setTimeout(msecs: number, callback?: () => void) {
+    setStreamTimeout.call(this, msecs, callback);
+  }
+
+  _onTimeout() {
+    this.emit("timeout");
+  }
+}
+
+// Closes a stream whose last frame has been sent.
+function finishServerStream(stream: ServerHttp2Stream) {
+  if (stream.destroyed) {
+    return;
+  }
+  stream[kState].flags |= STREAM_FLAGS_CLOSED;
+  nextTick(() => stream.destroy());
 }
 
 function setupCompat(ev) {
@@ -1451,6 +1815,7 @@ function initializeOptions(options) {
   options = { ...options };
   // assertIsObject(options.settings, 'options.settings');
   options.settings = { ...options.settings };
+  validateSettings(options.settings);
 
   // assertIsArray(options.remoteCustomSettings, 'options.remoteCustomSettings');
   if (options.remoteCustomSettings) {
@@ -1490,10 +1855,84 @@ function initializeOptions(options) {
   return options;
 }
 
+// Serves an HTTP/2 session on the connection with the rid `connRid`, its
+// streams are emitted on the session and on the server.
+async function serveHttp2Session(
+  server: Http2Server | Http2SecureServer,
+  connRid: number,
+) {
+  const options = server[kOptions];
+  const session = new ServerHttp2Session(server, options);
+  session.on("stream", (stream, headers, flags, rawHeaders) => {
+    server.emit("stream", stream, headers, flags, rawHeaders);
+  });
+  session.on("error", (err) => server.emit("sessionError", err, session));
+  server.emit("session", session);
+
+  let rid;
+  try {
+    rid = await op_http2_listen(connRid, options.settings);
+  } catch (err) {
+    session.destroy(err);
+    return;
+  }
+  session[kDenoConnRid] = rid;
+  session[kState].flags |= SESSION_FLAGS_READY;
+  session.emit("connect", session, {});
+
+  // The connection is driven by accepting its streams, which goes on until
+  // it's closed, also when the session is shutting it down.
+  while (true) {
+    let accepted;
+    try {
+      accepted = await op_http2_accept(rid);
+    } catch (err) {
+      // a peer going away without closing the session isn't an error
+      if (
+        session.destroyed ||
+        err instanceof Deno.errors.ConnectionReset ||
+        err instanceof Deno.errors.BrokenPipe
+      ) {
+        break;
+      }
+      session.destroy(err);
+      return;
+    }
+    if (accepted === null) {
+      break;
+    }
+
+    const [headerList, bodyRid, responseRid, id] = accepted;
+    if (session.destroyed) {
+      core.tryClose(bodyRid);
+      core.tryClose(responseRid);
+      continue;
+    }
+    const headers = toHeaderObject(headerList);
+    const stream = new ServerHttp2Stream(
+      session,
+      responseRid,
+      bodyRid,
+      id,
+      headers,
+    );
+    session.emit(
+      "stream",
+      stream,
+      headers,
+      constants.NGHTTP2_FLAG_END_HEADERS,
+      headerList.flat(),
+    );
+  }
+
+  session[kState].flags |= SESSION_FLAGS_CLOSED;
+  if (!session.destroyed) {
+    session.destroy();
+  }
+}
+
 export class Http2Server extends Server {
   #options: Record<string, unknown> = {};
-  #abortController;
-  #server;
   timeout = 0;
 
   constructor(
@@ -1503,59 +1942,11 @@ export class Http2Server extends Server {
     options = initializeOptions(options);
     super(options);
     this[kOptions] = options;
-    this.#abortController = new AbortController();
     this.on("newListener", setupCompat);
 
-    this.on(
-      "connection",
-      (conn: Deno.Conn) => {
-        try {
-          const session = new ServerHttp2Session();
-          this.emit("session", session);
-          this.#server = serveHttpOnConnection(
-            conn,
-            this.#abortController.signal,
-            async (req: Request) => {
-              try {
-                const controllerDeferred = Promise.withResolvers<
-                  ReadableStreamDefaultController<Uint8Array>
-                >();
-                const body = new ReadableStream({
-                  start(controller) {
-                    controllerDeferred.resolve(controller);
-                  },
-                });
-                const headers: Http2Headers = {};
-                for (const [name, value] of req.headers) {
-                  headers[name] = value;
-                }
-                headers[constants.HTTP2_HEADER_PATH] =
-                  new URL(req.url).pathname;
-                const stream = new ServerHttp2Stream(
-                  session,
-                  Promise.resolve(headers),
-                  controllerDeferred.promise,
-                  req.body,
-                  body,
-                  req,
-                );
-                this.emit("stream", stream, headers);
-                return await stream._deferred.promise;
-              } catch (e) {
-                console.log(">>> Error in serveHttpOnConnection", e);
-              }
-              return new Response("");
-            },
-            () => {
-              console.log(">>> error");
-            },
-            () => {},
-          );
-        } catch (e) {
-          console.log(">>> Error in Http2Server", e);
-        }
-      },
-    );
+    this.on("connection", (conn: Deno.Conn) => {
+      serveHttp2Session(this, conn[internalRidSymbol]);
+    });
     this.#options = options;
     if (typeof requestListener === "function") {
       this.on("request", requestListener);
@@ -1575,11 +1966,13 @@ export class Http2Server extends Server {
   }
 
   updateSettings(settings: Record<string, unknown>) {
+    validateObject(settings, "settings");
+    validateSettings(settings);
     this.#options.settings = { ...this.#options.settings, ...settings };
   }
 }
 
-export class Http2SecureServer extends Server {
+export class Http2SecureServer extends TLSServer {
   #options: Record<string, unknown> = {};
   timeout = 0;
 
@@ -1587,8 +1980,14 @@ export class Http2SecureServer extends Server {
     options: Record<string, unknown>,
     requestListener: () => unknown,
   ) {
-    super(options, function () {
-      notImplemented("connectionListener");
+    options = initializeOptions(options);
+    // `allowHTTP1` isn't supported, only HTTP/2 is negotiated
+    super({ ...options, ALPNProtocols: ["h2"] });
+    this[kOptions] = options;
+    this.on("newListener", setupCompat);
+
+    this.on("secureConnection", (conn: Deno.TlsConn) => {
+      serveHttp2Session(this, conn[internalRidSymbol]);
     });
     this.#options = options;
     if (typeof requestListener === "function") {
@@ -1596,8 +1995,9 @@ export class Http2SecureServer extends Server {
     }
   }
 
-  close(_callback?: () => unknown) {
-    notImplemented("Http2SecureServer.close");
+  // Serve the TLS connection directly, like `Http2Server` does for TCP.
+  _createSocket(conn: Deno.TlsConn) {
+    return conn;
   }
 
   setTimeout(msecs: number, callback?: () => unknown) {
@@ -1608,6 +2008,8 @@ export class Http2SecureServer extends Server {
   }
 
   updateSettings(settings: Record<string, unknown>) {
+    validateObject(settings, "settings");
+    validateSettings(settings);
     this.#options.settings = { ...this.#options.settings, ...settings };
   }
 }
@@ -1624,11 +2026,14 @@ export function createServer(
 }
 
 export function createSecureServer(
-  _options: Record<string, unknown>,
-  _onRequestHandler: () => unknown,
+  options: Record<string, unknown>,
+  onRequestHandler: () => unknown,
 ): Http2SecureServer {
-  notImplemented("http2.createSecureServer");
-  return new Http2SecureServer();
+  if (typeof options === "function") {
+    onRequestHandler = options;
+    options = {};
+  }
+  return new Http2SecureServer(options, onRequestHandler);
 }
 
 export function connect(
@@ -1644,6 +2049,10 @@ export function connect(
   }
 
   options = { ...options };
+  if (options.settings !== undefined) {
+    validateObject(options.settings, "options.settings");
+    validateSettings(options.settings);
+  }
 
   if (typeof authority === "string") {
     authority = new URL(authority);
@@ -2029,21 +2438,142 @@ export const constants = {
 //   constants.HTTP2_HEADER_X_CONTENT_TYPE_OPTIONS,
 // ]);
 
+const kMaxInt = 2 ** 32 - 1;
+// h2 rejects window sizes above the maximum of the protocol
+const kMaxWindowSize = 2 ** 31 - 1;
+const kMaxFrameSize = 2 ** 24 - 1;
+
+// The settings in the order of `getPackedSettings()`, with their ID and the
+// range of their values.
+const kSettings: [string, number, number, number][] = [
+  ["headerTableSize", 0x1, 0, kMaxInt],
+  ["maxConcurrentStreams", 0x3, 0, kMaxInt],
+  ["maxFrameSize", 0x5, 16384, kMaxFrameSize],
+  ["initialWindowSize", 0x4, 0, kMaxWindowSize],
+  ["maxHeaderListSize", 0x6, 0, kMaxInt],
+];
+const kBooleanSettings: [string, number][] = [
+  ["enablePush", 0x2],
+  ["enableConnectProtocol", 0x8],
+];
+
+function validateSettings(settings: Record<string, unknown>) {
+  for (const [name, , min, max] of kSettings) {
+    const value = settings[name];
+    if (
+      value !== undefined &&
+      (typeof value !== "number" || value < min || value > max)
+    ) {
+      throw new ERR_HTTP2_INVALID_SETTING_VALUE(name, value, min, max);
+    }
+  }
+  const { maxHeaderSize } = settings;
+  if (
+    maxHeaderSize !== undefined &&
+    (typeof maxHeaderSize !== "number" || maxHeaderSize < 0 ||
+      maxHeaderSize > kMaxInt)
+  ) {
+    throw new ERR_HTTP2_INVALID_SETTING_VALUE(
+      "maxHeaderSize",
+      maxHeaderSize,
+      0,
+      kMaxInt,
+    );
+  }
+  for (const [name] of kBooleanSettings) {
+    const value = settings[name];
+    if (value !== undefined && typeof value !== "boolean") {
+      throw new ERR_HTTP2_INVALID_SETTING_VALUE(name, value);
+    }
+  }
+}
+
 export function getDefaultSettings(): Record<string, unknown> {
-  notImplemented("http2.getDefaultSettings");
-  return {};
+  return {
+    headerTableSize: 4096,
+    enablePush: true,
+    initialWindowSize: 65535,
+    maxFrameSize: 16384,
+    maxConcurrentStreams: kMaxInt,
+    maxHeaderListSize: 65535,
+    maxHeaderSize: 65535,
+    enableConnectProtocol: false,
+  };
 }
 
-export function getPackedSettings(_settings: Record<string, unknown>): Buffer {
-  notImplemented("http2.getPackedSettings");
-  return {};
+export function getPackedSettings(
+  settings: Record<string, unknown> = {},
+): Buffer {
+  validateObject(settings, "settings");
+  validateSettings(settings);
+  settings = { ...settings };
+  if (settings.maxHeaderListSize === undefined) {
+    settings.maxHeaderListSize = settings.maxHeaderSize;
+  }
+
+  const entries: [number, number][] = [];
+  for (const [name, id] of kSettings) {
+    if (settings[name] !== undefined) {
+      entries.push([id, settings[name] as number]);
+    }
+  }
+  for (const [name, id] of kBooleanSettings) {
+    if (settings[name] !== undefined) {
+      entries.push([id, settings[name] ? 1 : 0]);
+    }
+  }
+
+  const buffer = Buffer.alloc(entries.length * 6);
+  entries.forEach(([id, value], i) => {
+    buffer.writeUInt16BE(id, i * 6);
+    buffer.writeUInt32BE(value, i * 6 + 2);
+  });
+  return buffer;
 }
 
 export function getUnpackedSettings(
-  _buffer: Buffer | TypedArray,
+  buffer: Buffer | TypedArray | DataView,
+  options: Record<string, unknown> = {},
 ): Record<string, unknown> {
-  notImplemented("http2.getUnpackedSettings");
-  return {};
+  if (!isArrayBufferView(buffer)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "buf",
+      ["Buffer", "TypedArray", "DataView"],
+      buffer,
+    );
+  }
+  if (buffer.byteLength % 6 !== 0) {
+    throw new ERR_HTTP2_INVALID_PACKED_SETTINGS_LENGTH();
+  }
+
+  const view = new DataView(
+    buffer.buffer,
+    buffer.byteOffset,
+    buffer.byteLength,
+  );
+  const numbers = new Map(kSettings.map(([name, id]) => [id, name]));
+  const booleans = new Map(kBooleanSettings.map(([name, id]) => [id, name]));
+  const settings: Record<string, unknown> = {};
+  for (let offset = 0; offset < view.byteLength; offset += 6) {
+    const id = view.getUint16(offset);
+    const value = view.getUint32(offset + 2);
+    if (numbers.has(id)) {
+      settings[numbers.get(id)!] = value;
+      if (id === 0x6) {
+        settings.maxHeaderSize = value;
+      }
+    } else if (booleans.has(id)) {
+      settings[booleans.get(id)!] = value !== 0;
+    } else {
+      settings.customSettings ??= {};
+      settings.customSettings[id] = value;
+    }
+  }
+
+  if (options?.validate) {
+    validateSettings(settings);
+  }
+  return settings;
 }
 
 export const sensitiveHeaders = Symbol("nodejs.http2.sensitiveHeaders");
@@ -2204,7 +2734,7 @@ class Http2ServerRequest extends Readable {
     stream[kRequest] = this;
 
     // Pause the stream..
-    stream.on("trailers", onStreamTrailers);
+    stream.on("trailers", onRequestTrailers);
     stream.on("end", onStreamEnd);
     stream.on("error", onStreamError);
     stream.on("aborted", onStreamAbortedRequest);
@@ -2270,16 +2800,15 @@ class Http2ServerRequest extends Readable {
     return this.socket;
   }
 
-  // _read(nread) {
-  //   const state = this[kState];
-  //   assert(!state.closed);
-  //   if (!state.didRead) {
-  //     state.didRead = true;
-  //     this[kStream].on("data", onStreamData);
-  //   } else {
-  //     nextTick(resumeStream, this[kStream]);
-  //   }
-  // }
+  _read(_nread) {
+    const state = this[kState];
+    if (!state.didRead) {
+      state.didRead = true;
+      this[kStream].on("data", onStreamData);
+    } else {
+      nextTick(resumeStream, this[kStream]);
+    }
+  }
 
   get method() {
     return this[kHeaders][constants.HTTP2_HEADER_METHOD];
@@ -2318,6 +2847,25 @@ class Http2ServerRequest extends Readable {
   }
 }
 
+function onStreamData(chunk) {
+  const request = this[kRequest];
+  if (request !== undefined && !request.push(chunk)) {
+    this.pause();
+  }
+}
+
+function resumeStream(stream) {
+  stream.resume();
+}
+
+function onRequestTrailers(trailers, _flags, rawTrailers) {
+  const request = this[kRequest];
+  if (request !== undefined) {
+    ObjectAssign(request[kTrailers], trailers);
+    request[kRawTrailers].push(...rawTrailers);
+  }
+}
+
 function onStreamEnd() {
   // Cause the request stream to end as well.
   const request = this[kRequest];
@@ -115,11 +115,19 @@ class HttpsClientRequest extends ClientRequest {
   override _encrypted: true;
   override defaultProtocol = "https:";
   override _getClient(): Deno.HttpClient | undefined {
+    const certs = this._getCaCerts();
+    if (certs === undefined) {
+      return undefined;
+    }
+    return createHttpClient({ caCerts: certs, http2: false });
+  }
+
+  override _getCaCerts(): string[] | undefined {
     if (caCerts === null) {
       return undefined;
     }
     if (caCerts !== undefined) {
-      return createHttpClient({ caCerts, http2: false });
+      return caCerts;
     }
     // const status = await Deno.permissions.query({
     //   name: "env",
@@ -136,7 +144,7 @@ class HttpsClientRequest extends ClientRequest {
     }
     const caCert = Deno.readTextFileSync(certFilename);
     caCerts = [caCert];
-    return createHttpClient({ caCerts, http2: false });
+    return caCerts;
   }
 }
 
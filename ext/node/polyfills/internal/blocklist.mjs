@@ -0,0 +1,209 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+// TODO(petamoriken): enable prefer-primordials for node polyfills
+// deno-lint-ignore-file prefer-primordials
+
+import {
+  op_node_blocklist_add_address,
+  op_node_blocklist_add_range,
+  op_node_blocklist_add_subnet,
+  op_node_blocklist_check,
+  op_node_blocklist_get_rules,
+  op_node_blocklist_new,
+  op_node_socket_address_parse,
+} from "ext:core/ops";
+
+import {
+  customInspectSymbol,
+  kEmptyObject,
+} from "ext:deno_node/internal/util.mjs";
+import { inspect } from "ext:deno_node/internal/util/inspect.mjs";
+import {
+  ERR_INVALID_ADDRESS,
+  ERR_INVALID_ARG_VALUE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateInt32,
+  validateObject,
+  validatePort,
+  validateString,
+  validateUint32,
+} from "ext:deno_node/internal/validators.mjs";
+
+const kHandle = Symbol("kHandle");
+const kDetail = Symbol("kDetail");
+
+const AF_INET = 4;
+const AF_INET6 = 6;
+
+function inspectOptions(options) {
+  return {
+    ...options,
+    depth: options.depth == null ? null : options.depth - 1,
+  };
+}
+
+export class SocketAddress {
+  static isSocketAddress(value) {
+    return value?.[kHandle] !== undefined;
+  }
+
+  constructor(options = kEmptyObject) {
+    validateObject(options, "options");
+    let { family = "ipv4" } = options;
+    const {
+      address = family === "ipv4" ? "127.0.0.1" : "::",
+      port = 0,
+      flowlabel = 0,
+    } = options;
+
+    let type;
+    if (typeof family?.toLowerCase === "function") {
+      family = family.toLowerCase();
+    }
+    switch (family) {
+      case "ipv4":
+        type = AF_INET;
+        break;
+      case "ipv6":
+        type = AF_INET6;
+        break;
+      default:
+        throw new ERR_INVALID_ARG_VALUE("options.family", options.family);
+    }
+
+    validateString(address, "options.address");
+    validatePort(port, "options.port");
+    validateUint32(flowlabel, "options.flowlabel", false);
+
+    const parsed = op_node_socket_address_parse(address, type);
+    if (parsed === null) {
+      throw new ERR_INVALID_ADDRESS();
+    }
+    this[kHandle] = { address: parsed, family: type };
+    this[kDetail] = {
+      address: parsed,
+      port: port | 0,
+      family,
+      flowlabel: flowlabel | 0,
+    };
+  }
+
+  get address() {
+    return this[kDetail].address;
+  }
+
+  get port() {
+    return this[kDetail].port;
+  }
+
+  get family() {
+    return this[kDetail].family;
+  }
+
+  get flowlabel() {
+    return this[kDetail].flowlabel;
+  }
+
+  [customInspectSymbol](depth, options) {
+    if (depth < 0) {
+      return this;
+    }
+    return `SocketAddress ${inspect(this.toJSON(), inspectOptions(options))}`;
+  }
+
+  toJSON() {
+    return {
+      address: this.address,
+      port: this.port,
+      family: this.family,
+      flowlabel: this.flowlabel,
+    };
+  }
+}
+
+export class BlockList {
+  constructor() {
+    this[kHandle] = op_node_blocklist_new();
+  }
+
+  [customInspectSymbol](depth, options) {
+    if (depth < 0) {
+      return this;
+    }
+    return `BlockList ${
+      inspect({ rules: this.rules }, inspectOptions(options))
+    }`;
+  }
+
+  addAddress(address, family = "ipv4") {
+    if (!SocketAddress.isSocketAddress(address)) {
+      validateString(address, "address");
+      validateString(family, "family");
+      address = new SocketAddress({ address, family });
+    }
+    const { address: ip, family: type } = address[kHandle];
+    op_node_blocklist_add_address(this[kHandle], ip, type);
+  }
+
+  addRange(start, end, family = "ipv4") {
+    if (!SocketAddress.isSocketAddress(start)) {
+      validateString(start, "start");
+      validateString(family, "family");
+      start = new SocketAddress({ address: start, family });
+    }
+    if (!SocketAddress.isSocketAddress(end)) {
+      validateString(end, "end");
+      validateString(family, "family");
+      end = new SocketAddress({ address: end, family });
+    }
+    const added = op_node_blocklist_add_range(
+      this[kHandle],
+      start[kHandle].address,
+      start[kHandle].family,
+      end[kHandle].address,
+      end[kHandle].family,
+    );
+    if (!added) {
+      throw new ERR_INVALID_ARG_VALUE("start", start, "must come before end");
+    }
+  }
+
+  addSubnet(network, prefix, family = "ipv4") {
+    if (!SocketAddress.isSocketAddress(network)) {
+      validateString(network, "network");
+      validateString(family, "family");
+      network = new SocketAddress({ address: network, family });
+    }
+    switch (network.family) {
+      case "ipv4":
+        validateInt32(prefix, "prefix", 0, 32);
+        break;
+      case "ipv6":
+        validateInt32(prefix, "prefix", 0, 128);
+        break;
+    }
+    const { address: ip, family: type } = network[kHandle];
+    op_node_blocklist_add_subnet(this[kHandle], ip, type, prefix);
+  }
+
+  check(address, family = "ipv4") {
+    if (!SocketAddress.isSocketAddress(address)) {
+      validateString(address, "address");
+      validateString(family, "family");
+      try {
+        address = new SocketAddress({ address, family });
+      } catch {
+        // Ignore the error. If it's not a valid address, return false.
+        return false;
+      }
+    }
+    const { address: ip, family: type } = address[kHandle];
+    return op_node_blocklist_check(this[kHandle], ip, type);
+  }
+
+  get rules() {
+    return op_node_blocklist_get_rules(this[kHandle]);
+  }
+}
@@ -7,7 +7,12 @@
 // deno-lint-ignore-file prefer-primordials
 
 import { core, internals } from "ext:core/mod.js";
-import { op_node_ipc_read, op_node_ipc_write } from "ext:core/ops";
+import {
+  op_node_ipc_read,
+  op_node_ipc_read_advanced,
+  op_node_ipc_write,
+  op_node_ipc_write_advanced,
+} from "ext:core/ops";
 import {
   ArrayIsArray,
   ArrayPrototypeFilter,
@@ -31,6 +36,9 @@ import {
   AbortError,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
+  ERR_IPC_CHANNEL_CLOSED,
+  ERR_IPC_DISCONNECTED,
+  ERR_MISSING_ARGS,
   ERR_UNKNOWN_SIGNAL,
 } from "ext:deno_node/internal/errors.ts";
 import { Buffer } from "node:buffer";
@@ -168,8 +176,16 @@ export class ChildProcess extends EventEmitter {
       cwd,
       shell = false,
       signal,
+      serialization = "json",
       windowsVerbatimArguments = false,
     } = options || {};
+    if (serialization !== "json" && serialization !== "advanced") {
+      throw new ERR_INVALID_ARG_VALUE(
+        "options.serialization",
+        serialization,
+        'must be "json" or "advanced"',
+      );
+    }
     const normalizedStdio = normalizeStdioOption(stdio);
     const [
       stdin = "pipe",
@@ -188,6 +204,9 @@ export class ChildProcess extends EventEmitter {
     const ipc = normalizedStdio.indexOf("ipc");
 
     const stringEnv = mapValues(env, (value) => value.toString());
+    if (ipc >= 0) {
+      stringEnv.NODE_CHANNEL_SERIALIZATION_MODE = serialization;
+    }
     try {
       this.#process = new Deno.Command(cmd, {
         args: cmdArgs,
@@ -258,7 +277,7 @@ export class ChildProcess extends EventEmitter {
 
       const pipeFd = internals.getPipeFd(this.#process);
       if (typeof pipeFd == "number") {
-        setupChannel(this, pipeFd);
+        setupChannel(this, pipeFd, serialization);
       }
 
       (async () => {
@@ -460,7 +479,8 @@ export interface ChildProcessOptions {
   signal?: AbortSignal;
 
   /**
-   * NOTE: This option is not yet implemented.
+   * The serialization of the messages sent over the IPC channel.
+   * Default: "json".
    */
   serialization?: "json" | "advanced";
 
@@ -1090,19 +1110,101 @@ function toDenoArgs(args: string[]): string[] {
   return denoArgs;
 }
 
-export function setupChannel(target, ipc) {
+// Node's internal messages, like the ones of `cluster`, are emitted as
+// "internalMessage" instead of "message".
+function isInternal(message) {
+  return message !== null && typeof message === "object" &&
+    typeof message.cmd === "string" && message.cmd.length > 5 &&
+    message.cmd.startsWith("NODE_");
+}
+
+// The `channel` of a process, the pending read of the IPC pipe keeps the
+// event loop alive while it's ref'ed.
+class Control {
+  #refed;
+  #refs = 0;
+  #refExplicitlySet = false;
+  #pending = null;
+
+  constructor(refed: boolean) {
+    this.#refed = refed;
+  }
+
+  // Counts the listeners of the messages, which ref the channel unless
+  // `ref()` or `unref()` were called.
+  refCounted() {
+    if (++this.#refs === 1 && !this.#refExplicitlySet) {
+      this.#setRefed(true);
+    }
+  }
+
+  unrefCounted() {
+    if (--this.#refs === 0 && !this.#refExplicitlySet) {
+      this.#setRefed(false);
+    }
+  }
+
+  ref() {
+    this.#refExplicitlySet = true;
+    this.#setRefed(true);
+  }
+
+  unref() {
+    this.#refExplicitlySet = true;
+    this.#setRefed(false);
+  }
+
+  track(promise) {
+    this.#pending = promise;
+    if (promise !== null && !this.#refed) {
+      core.unrefOpPromise(promise);
+    }
+  }
+
+  #setRefed(refed: boolean) {
+    if (this.#refed === refed) {
+      return;
+    }
+    this.#refed = refed;
+    if (this.#pending !== null) {
+      if (refed) {
+        core.refOpPromise(this.#pending);
+      } else {
+        core.unrefOpPromise(this.#pending);
+      }
+    }
+  }
+}
+
+export function setupChannel(
+  target,
+  ipc,
+  serialization = "json",
+  refed = true,
+) {
+  const advanced = serialization === "advanced";
+  const control = new Control(refed);
+
   async function readLoop() {
     try {
       while (true) {
         if (!target.connected || target.killed) {
           return;
         }
-        const msg = await op_node_ipc_read(ipc);
+        const promise = advanced
+          ? op_node_ipc_read_advanced(ipc)
+          : op_node_ipc_read(ipc);
+        control.track(promise);
+        let msg = await promise;
+        control.track(null);
         if (msg == null) {
           // Channel closed.
           target.disconnect();
           return;
         }
+        if (advanced) {
+          msg = core.deserialize(msg);
+        }
 
         process.nextTick(handleMessage, msg);
       }
@@ -1117,7 +1219,11 @@ export function setupChannel(target, ipc) {
   }
 
   function handleMessage(msg) {
-    target.emit("message", msg);
+    if (isInternal(msg)) {
+      target.emit("internalMessage", msg);
+    } else {
+      target.emit("message", msg);
+    }
   }
 
   target.send = function (message, handle, options, callback) {
@@ -1135,26 +1241,56 @@ export function setupChannel(target, ipc) {
     options = { swallowErrors: false, ...options };
 
     if (message === undefined) {
-      throw new TypeError("ERR_MISSING_ARGS", "message");
+      throw new ERR_MISSING_ARGS("message");
+    }
+    if (
+      typeof message !== "string" && typeof message !== "object" &&
+      typeof message !== "number" && typeof message !== "boolean"
+    ) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "message",
+        ["string", "object", "number", "boolean"],
+        message,
+      );
     }
 
     if (handle !== undefined) {
       notImplemented("ChildProcess.send with handle");
     }
 
-    op_node_ipc_write(ipc, message)
-      .then(() => {
-        if (callback) {
-          process.nextTick(callback, null);
-        }
-      });
+    if (!this.connected) {
+      const err = new ERR_IPC_CHANNEL_CLOSED();
+      if (typeof callback === "function") {
+        process.nextTick(callback, err);
+      } else {
+        process.nextTick(() => this.emit("error", err));
+      }
+      return false;
+    }
+
+    const write = advanced
+      ? op_node_ipc_write_advanced(ipc, core.serialize(message))
+      : op_node_ipc_write(ipc, message);
+    write.then(() => {
+      if (callback) {
+        process.nextTick(callback, null);
+      }
+    }, (err) => {
+      if (typeof callback === "function") {
+        process.nextTick(callback, err);
+      } else if (!options.swallowErrors) {
+        process.nextTick(() => this.emit("error", err));
+      }
+    });
+    return true;
   };
 
   target.connected = true;
+  target.channel = control;
 
   target.disconnect = function () {
     if (!this.connected) {
-      this.emit("error", new Error("IPC channel is already disconnected"));
+      this.emit("error", new ERR_IPC_DISCONNECTED());
       return;
     }
 
@@ -1168,6 +1304,7 @@ export function setupChannel(target, ipc) {
 
   // Start reading messages from the channel.
   readLoop();
+  return control;
 }
 
 export default {
@@ -4,90 +4,117 @@
 // deno-lint-ignore-file prefer-primordials
 
 import {
-  op_node_generate_secret,
-  op_node_generate_secret_async,
+  op_node_random_fill,
+  op_node_random_fill_async,
 } from "ext:core/ops";
 
-import {
-  MAX_SIZE as kMaxUint32,
-} from "ext:deno_node/internal/crypto/_randomBytes.ts";
-import { Buffer } from "node:buffer";
 import { isAnyArrayBuffer, isArrayBufferView } from "node:util/types";
-import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_OUT_OF_RANGE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateFunction,
+  validateNumber,
+} from "ext:deno_node/internal/validators.mjs";
 
-const kBufferMaxLength = 0x7fffffff;
+const kMaxInt32 = 2 ** 31 - 1;
 
-function assertOffset(offset, length) {
-  if (offset > kMaxUint32 || offset < 0) {
-    throw new TypeError("offset must be a uint32");
-  }
+// Offsets and sizes are in elements of `buf`, and returned in bytes.
+function assertOffset(offset, elementSize, length) {
+  validateNumber(offset, "offset");
+  offset *= elementSize;
 
-  if (offset > kBufferMaxLength || offset > length) {
-    throw new RangeError("offset out of range");
+  const maxLength = Math.min(length, kMaxInt32);
+  if (Number.isNaN(offset) || offset > maxLength || offset < 0) {
+    throw new ERR_OUT_OF_RANGE("offset", `>= 0 && <= ${maxLength}`, offset);
   }
+
+  return offset >>> 0;
 }
 
-function assertSize(size, offset, length) {
-  if (size > kMaxUint32 || size < 0) {
-    throw new TypeError("size must be a uint32");
+function assertSize(size, elementSize, offset, length) {
+  validateNumber(size, "size");
+  size *= elementSize;
+
+  if (Number.isNaN(size) || size > kMaxInt32 || size < 0) {
+    throw new ERR_OUT_OF_RANGE("size", `>= 0 && <= ${kMaxInt32}`, size);
+  }
+
+  if (size + offset > length) {
+    throw new ERR_OUT_OF_RANGE("size + offset", `<= ${length}`, size + offset);
   }
 
-  if (size + offset > length || size > kBufferMaxLength) {
-    throw new RangeError("buffer too small");
+  return size >>> 0;
+}
+
+function validateBuffer(buf) {
+  if (!isAnyArrayBuffer(buf) && !isArrayBufferView(buf)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "buf",
+      ["ArrayBuffer", "ArrayBufferView"],
+      buf,
+    );
   }
 }
 
+// A view of all the bytes of `buf`, which the ops write into.
+function asBytes(buf) {
+  return isAnyArrayBuffer(buf)
+    ? new Uint8Array(buf)
+    : new Uint8Array(buf.buffer, buf.byteOffset, buf.byteLength);
+}
+
 export default function randomFill(
   buf,
   offset,
   size,
   cb,
 ) {
+  validateBuffer(buf);
+
+  const elementSize = buf.BYTES_PER_ELEMENT || 1;
   if (typeof offset === "function") {
     cb = offset;
     offset = 0;
-    size = buf.length;
+    size = buf.byteLength;
   } else if (typeof size === "function") {
     cb = size;
-    size = buf.length - Number(offset);
+    offset = assertOffset(offset, elementSize, buf.byteLength);
+    size = buf.byteLength - offset;
+  } else {
+    validateFunction(cb, "callback");
+    offset = assertOffset(offset, elementSize, buf.byteLength);
+    if (size === undefined) {
+      size = buf.byteLength - offset;
+    } else {
+      size = assertSize(size, elementSize, offset, buf.byteLength);
+    }
   }
 
-  assertOffset(offset, buf.length);
-  assertSize(size, offset, buf.length);
-
-  op_node_generate_secret_async(Math.floor(size))
-    .then(
-      (randomData) => {
-        const randomBuf = Buffer.from(randomData.buffer);
-        randomBuf.copy(buf, offset, 0, size);
-        cb(null, buf);
-      },
-    );
+  op_node_random_fill_async(asBytes(buf), offset, size).then(
+    () => cb(null, buf),
+    (err) => cb(err),
+  );
 }
 
 export function randomFillSync(buf, offset = 0, size) {
-  if (!isAnyArrayBuffer(buf) && !isArrayBufferView(buf)) {
-    throw new ERR_INVALID_ARG_TYPE(
-      "buf",
-      ["ArrayBuffer", "ArrayBufferView"],
-      buf,
-    );
-  }
+  validateBuffer(buf);
 
-  assertOffset(offset, buf.byteLength);
+  const elementSize = buf.BYTES_PER_ELEMENT || 1;
+  offset = assertOffset(offset, elementSize, buf.byteLength);
 
   if (size === undefined) {
     size = buf.byteLength - offset;
   } else {
-    assertSize(size, offset, buf.byteLength);
+    size = assertSize(size, elementSize, offset, buf.byteLength);
   }
 
   if (size === 0) {
     return buf;
   }
 
-  const bytes = new Uint8Array(buf.buffer ? buf.buffer : buf, offset, size);
-  op_node_generate_secret(bytes);
+  op_node_random_fill(asBytes(buf), offset, size);
 
   return buf;
 }
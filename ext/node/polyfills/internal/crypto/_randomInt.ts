@@ -4,6 +4,14 @@
 // deno-lint-ignore-file prefer-primordials
 
 import { op_node_random_int } from "ext:core/ops";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_OUT_OF_RANGE,
+} from "ext:deno_node/internal/errors.ts";
+import { validateFunction } from "ext:deno_node/internal/validators.mjs";
+
+// The largest range of random integers, `2^48 - 1`.
+const RAND_MAX = 0xFFFF_FFFF_FFFF;
 
 export default function randomInt(max: number): number;
 export default function randomInt(min: number, max: number): number;
@@ -18,36 +26,46 @@ export default function randomInt(
 ): void;
 
 export default function randomInt(
-  max: number,
-  min?: ((err: Error | null, n?: number) => void) | number,
+  min: number,
+  max?: ((err: Error | null, n?: number) => void) | number,
   cb?: (err: Error | null, n?: number) => void,
 ): number | void {
-  if (typeof max === "number" && typeof min === "number") {
-    [max, min] = [min, max];
-  }
-  if (min === undefined) min = 0;
-  else if (typeof min === "function") {
-    cb = min;
+  // `randomInt(max)` and `randomInt(max, cb)`
+  const minNotSpecified = typeof max === "undefined" ||
+    typeof max === "function";
+  if (minNotSpecified) {
+    cb = max as ((err: Error | null, n?: number) => void) | undefined;
+    max = min;
     min = 0;
   }
-
-  if (
-    !Number.isSafeInteger(min) ||
-    typeof max === "number" && !Number.isSafeInteger(max)
-  ) {
-    throw new Error("max or min is not a Safe Number");
+  if (cb !== undefined) {
+    validateFunction(cb, "callback");
   }
 
-  if (max - min > Math.pow(2, 48)) {
-    throw new RangeError("max - min should be less than 2^48!");
+  if (!Number.isSafeInteger(min)) {
+    throw new ERR_INVALID_ARG_TYPE("min", "a safe integer", min);
+  }
+  if (!Number.isSafeInteger(max)) {
+    throw new ERR_INVALID_ARG_TYPE("max", "a safe integer", max);
+  }
+  max = max as number;
+  if (max <= min) {
+    throw new ERR_OUT_OF_RANGE(
+      "max",
+      `greater than the value of "min" (${min})`,
+      max,
+    );
   }
 
-  if (min >= max) {
-    throw new Error("Min is bigger than Max!");
+  const range = max - min;
+  if (!(range <= RAND_MAX)) {
+    throw new ERR_OUT_OF_RANGE(
+      `max${minNotSpecified ? "" : " - min"}`,
+      `<= ${RAND_MAX}`,
+      range,
+    );
   }
 
-  min = Math.ceil(min);
-  max = Math.floor(max);
   const result = op_node_random_int(min, max);
 
   if (cb) {
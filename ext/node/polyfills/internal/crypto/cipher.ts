@@ -9,16 +9,23 @@ const {
   encode,
 } = core;
 import {
-  op_node_cipheriv_encrypt,
   op_node_cipheriv_final,
+  op_node_cipheriv_pipe,
   op_node_cipheriv_set_aad,
+  op_node_cipheriv_update,
+  op_node_cipheriv_update_stream,
   op_node_create_cipheriv,
   op_node_create_decipheriv,
-  op_node_decipheriv_decrypt,
   op_node_decipheriv_final,
+  op_node_decipheriv_pipe,
   op_node_decipheriv_set_aad,
+  op_node_decipheriv_set_auth_tag,
+  op_node_decipheriv_update,
+  op_node_decipheriv_update_stream,
+  op_node_mod_pow,
   op_node_private_decrypt,
   op_node_private_encrypt,
+  op_node_public_decrypt,
   op_node_public_encrypt,
 } from "ext:core/ops";
 
@@ -26,7 +33,11 @@ import { Buffer } from "node:buffer";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import type { TransformOptions } from "ext:deno_node/_stream.d.ts";
 import { Transform } from "ext:deno_node/_stream.mjs";
+import { readableStreamForRid } from "ext:deno_web/06_streams.js";
+import { resourceForStream } from "ext:deno_node/internal/crypto/hash.ts";
 import {
+  createPrivateKey,
+  createPublicKey,
   getArrayBufferOrView,
   KeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
@@ -36,6 +47,9 @@ import type {
   Encoding,
 } from "ext:deno_node/internal/crypto/types.ts";
 import { getDefaultEncoding } from "ext:deno_node/internal/crypto/util.ts";
+import { crypto as constants } from "ext:deno_node/internal_binding/constants.ts";
+import { validateString } from "ext:deno_node/internal/validators.mjs";
+import { ERR_CRYPTO_INVALID_STATE } from "ext:deno_node/internal/errors.ts";
 import {
   isAnyArrayBuffer,
   isArrayBufferView,
@@ -48,8 +62,6 @@ export function isStringOrBuffer(val) {
     Buffer.isBuffer(val);
 }
 
-const NO_TAG = new Uint8Array();
-
 export type CipherCCMTypes =
   | "aes-128-ccm"
   | "aes-192-ccm"
@@ -150,22 +162,22 @@ function toU8(input: string | Uint8Array): Uint8Array {
   return typeof input === "string" ? encode(input) : input;
 }
 
+function toOutput(output: Uint8Array, encoding: string): Buffer | string {
+  const buf = Buffer.from(output.buffer);
+  return encoding === "buffer" ? buf : buf.toString(encoding);
+}
+
 export class Cipheriv extends Transform implements Cipher {
   /** CipherContext resource id */
   #context: number;
 
-  /** plaintext data cache */
-  #cache: BlockModeCache;
-
-  #needsBlockCache: boolean;
-
   #authTag?: Buffer;
 
   constructor(
     cipher: string,
     key: CipherKey,
     iv: BinaryLike | null,
-    options?: TransformOptions,
+    options?: TransformOptions & { authTagLength?: number },
   ) {
     super({
       transform(chunk, encoding, cb) {
@@ -178,40 +190,40 @@ export class Cipheriv extends Transform implements Cipher {
       },
       ...options,
     });
-    this.#cache = new BlockModeCache(false);
-    this.#context = op_node_create_cipheriv(cipher, toU8(key), toU8(iv));
-    this.#needsBlockCache =
-      !(cipher == "aes-128-gcm" || cipher == "aes-256-gcm");
-    if (this.#context == 0) {
-      throw new TypeError("Unknown cipher");
-    }
+    this.#context = op_node_create_cipheriv(
+      cipher,
+      toU8(key),
+      toU8(iv),
+      options?.authTagLength ?? -1,
+    );
   }
 
   final(encoding: string = getDefaultEncoding()): Buffer | string {
-    const buf = new Buffer(16);
-    const maybeTag = op_node_cipheriv_final(
-      this.#context,
-      this.#cache.cache,
-      buf,
-    );
+    const { 0: output, 1: maybeTag } = op_node_cipheriv_final(this.#context);
     if (maybeTag) {
       this.#authTag = Buffer.from(maybeTag);
-      return encoding === "buffer" ? Buffer.from([]) : "";
     }
-    return encoding === "buffer" ? buf : buf.toString(encoding);
+    return toOutput(output, encoding);
   }
 
   getAuthTag(): Buffer {
-    return this.#authTag!;
+    if (this.#authTag === undefined) {
+      throw new ERR_CRYPTO_INVALID_STATE("getAuthTag");
+    }
+    return this.#authTag;
   }
 
   setAAD(
     buffer: ArrayBufferView,
-    _options?: {
+    options?: {
       plaintextLength: number;
     },
   ): this {
-    op_node_cipheriv_set_aad(this.#context, buffer);
+    op_node_cipheriv_set_aad(
+      this.#context,
+      buffer,
+      options?.plaintextLength ?? -1,
+    );
     return this;
   }
 
@@ -227,69 +239,41 @@ export class Cipheriv extends Transform implements Cipher {
   ): Buffer | string {
     // TODO(kt3k): throw ERR_INVALID_ARG_TYPE if data is not string, Buffer, or ArrayBufferView
     let buf = data;
-    if (typeof data === "string" && typeof inputEncoding === "string") {
+    if (typeof data === "string") {
       buf = Buffer.from(data, inputEncoding);
     }
 
-    let output;
-    if (!this.#needsBlockCache) {
-      output = Buffer.allocUnsafe(buf.length);
-      op_node_cipheriv_encrypt(this.#context, buf, output);
-      return outputEncoding === "buffer"
-        ? output
-        : output.toString(outputEncoding);
-    }
-
-    this.#cache.add(buf);
-    const input = this.#cache.get();
-
-    if (input === null) {
-      output = Buffer.alloc(0);
-    } else {
-      output = Buffer.allocUnsafe(input.length);
-      op_node_cipheriv_encrypt(this.#context, input, output);
-    }
-    return outputEncoding === "buffer"
-      ? output
-      : output.toString(outputEncoding);
-  }
-}
-
-/** Caches data and output the chunk of multiple of 16.
- * Used by CBC, ECB modes of block ciphers */
-class BlockModeCache {
-  cache: Uint8Array;
-  // The last chunk can be padded when decrypting.
-  #lastChunkIsNonZero: boolean;
-
-  constructor(lastChunkIsNotZero = false) {
-    this.cache = new Uint8Array(0);
-    this.#lastChunkIsNonZero = lastChunkIsNotZero;
+    return toOutput(
+      op_node_cipheriv_update(this.#context, buf),
+      outputEncoding,
+    );
   }
 
-  add(data: Uint8Array) {
-    const cache = this.cache;
-    this.cache = new Uint8Array(cache.length + data.length);
-    this.cache.set(cache);
-    this.cache.set(data, cache.length);
+  /**
+   * Returns the ciphertext of the chunks of `stream`, as a stream that reads
+   * `stream` only as fast as it is read itself. The chunks are passed to the
+   * cipher in Rust, rather than one at a time to `update()`. `final()` can be
+   * called once the returned stream ended. Deno only.
+   */
+  updateFromStream(
+    stream: ReadableStream<Uint8Array>,
+  ): ReadableStream<Uint8Array> {
+    const { 0: rid, 1: autoClose } = resourceForStream(stream);
+    return readableStreamForRid(
+      op_node_cipheriv_update_stream(this.#context, rid, autoClose),
+    );
   }
 
-  /** Gets the chunk of the length of largest multiple of 16.
-   * Used for preparing data for encryption/decryption */
-  get(): Uint8Array | null {
-    let len = this.cache.length;
-    if (this.#lastChunkIsNonZero) {
-      // Reduces the available chunk length by 1 to keep the last chunk
-      len -= 1;
-    }
-    if (len < 16) {
-      return null;
+  /**
+   * Encrypts everything read from the file descriptor `srcFd` and writes the
+   * ciphertext to `dstFd`, without passing the chunks through JS. This
+   * finalizes the cipher like `final()`. Deno only.
+   */
+  async pipeFd(srcFd: number, dstFd: number): Promise<void> {
+    const maybeTag = await op_node_cipheriv_pipe(this.#context, srcFd, dstFd);
+    if (maybeTag) {
+      this.#authTag = Buffer.from(maybeTag);
     }
-
-    len = Math.floor(len / 16) * 16;
-    const out = this.cache.subarray(0, len);
-    this.cache = this.cache.subarray(len);
-    return out;
   }
 }
 
@@ -297,18 +281,11 @@ export class Decipheriv extends Transform implements Cipher {
   /** DecipherContext resource id */
   #context: number;
 
-  /** ciphertext data cache */
-  #cache: BlockModeCache;
-
-  #needsBlockCache: boolean;
-
-  #authTag?: BinaryLike;
-
   constructor(
     cipher: string,
     key: CipherKey,
     iv: BinaryLike | null,
-    options?: TransformOptions,
+    options?: TransformOptions & { authTagLength?: number },
   ) {
     super({
       transform(chunk, encoding, cb) {
@@ -321,44 +298,37 @@ export class Decipheriv extends Transform implements Cipher {
       },
       ...options,
     });
-    this.#cache = new BlockModeCache(true);
-    this.#context = op_node_create_decipheriv(cipher, toU8(key), toU8(iv));
-    this.#needsBlockCache =
-      !(cipher == "aes-128-gcm" || cipher == "aes-256-gcm");
-    if (this.#context == 0) {
-      throw new TypeError("Unknown cipher");
-    }
+    this.#context = op_node_create_decipheriv(
+      cipher,
+      toU8(key),
+      toU8(iv),
+      options?.authTagLength ?? -1,
+    );
   }
 
   final(encoding: string = getDefaultEncoding()): Buffer | string {
-    let buf = new Buffer(16);
-    op_node_decipheriv_final(
-      this.#context,
-      this.#cache.cache,
-      buf,
-      this.#authTag || NO_TAG,
-    );
-
-    if (!this.#needsBlockCache) {
-      return encoding === "buffer" ? Buffer.from([]) : "";
-    }
-
-    buf = buf.subarray(0, 16 - buf.at(-1)); // Padded in Pkcs7 mode
-    return encoding === "buffer" ? buf : buf.toString(encoding);
+    return toOutput(op_node_decipheriv_final(this.#context), encoding);
   }
 
   setAAD(
     buffer: ArrayBufferView,
-    _options?: {
+    options?: {
       plaintextLength: number;
     },
   ): this {
-    op_node_decipheriv_set_aad(this.#context, buffer);
+    op_node_decipheriv_set_aad(
+      this.#context,
+      buffer,
+      options?.plaintextLength ?? -1,
+    );
     return this;
   }
 
-  setAuthTag(buffer: BinaryLike, _encoding?: string): this {
-    this.#authTag = buffer;
+  setAuthTag(buffer: BinaryLike, encoding?: string): this {
+    op_node_decipheriv_set_auth_tag(
+      this.#context,
+      getArrayBufferOrView(buffer, "buffer", encoding),
+    );
     return this;
   }
 
@@ -373,30 +343,34 @@ export class Decipheriv extends Transform implements Cipher {
   ): Buffer | string {
     // TODO(kt3k): throw ERR_INVALID_ARG_TYPE if data is not string, Buffer, or ArrayBufferView
     let buf = data;
-    if (typeof data === "string" && typeof inputEncoding === "string") {
+    if (typeof data === "string") {
       buf = Buffer.from(data, inputEncoding);
     }
 
-    let output;
-    if (!this.#needsBlockCache) {
-      output = Buffer.allocUnsafe(buf.length);
-      op_node_decipheriv_decrypt(this.#context, buf, output);
-      return outputEncoding === "buffer"
-        ? output
-        : output.toString(outputEncoding);
-    }
+    return toOutput(
+      op_node_decipheriv_update(this.#context, buf),
+      outputEncoding,
+    );
+  }
 
-    this.#cache.add(buf);
-    const input = this.#cache.get();
-    if (input === null) {
-      output = Buffer.alloc(0);
-    } else {
-      output = new Buffer(input.length);
-      op_node_decipheriv_decrypt(this.#context, input, output);
-    }
-    return outputEncoding === "buffer"
-      ? output
-      : output.toString(outputEncoding);
+  /** Like `Cipheriv.prototype.updateFromStream()`. Deno only. */
+  updateFromStream(
+    stream: ReadableStream<Uint8Array>,
+  ): ReadableStream<Uint8Array> {
+    const { 0: rid, 1: autoClose } = resourceForStream(stream);
+    return readableStreamForRid(
+      op_node_decipheriv_update_stream(this.#context, rid, autoClose),
+    );
+  }
+
+  /**
+   * Like `Cipheriv.prototype.pipeFd()`. In authenticated modes, nothing is
+   * written to `dstFd` unless the data matches the auth tag, so the plaintext
+   * is held in memory until then. This rejects with a `RangeError` if it is
+   * longer than 64 MiB. Deno only.
+   */
+  async pipeFd(srcFd: number, dstFd: number): Promise<void> {
+    await op_node_decipheriv_pipe(this.#context, srcFd, dstFd);
   }
 }
 
@@ -407,8 +381,13 @@ export function privateEncrypt(
   const { data } = prepareKey(privateKey);
   const padding = privateKey.padding || 1;
 
+  const { oaepHash, oaepLabel } = getOaepOptions(privateKey);
+
   buffer = getArrayBufferOrView(buffer, "buffer");
-  return op_node_private_encrypt(data, buffer, padding);
+  if (padding === constants.RSA_NO_PADDING) {
+    return rsaWithoutPadding(privateKey, buffer, "d");
+  }
+  return op_node_private_encrypt(data, buffer, padding, oaepHash, oaepLabel);
 }
 
 export function privateDecrypt(
@@ -418,8 +397,13 @@ export function privateDecrypt(
   const { data } = prepareKey(privateKey);
   const padding = privateKey.padding || 1;
 
+  const { oaepHash, oaepLabel } = getOaepOptions(privateKey);
+
   buffer = getArrayBufferOrView(buffer, "buffer");
-  return op_node_private_decrypt(data, buffer, padding);
+  if (padding === constants.RSA_NO_PADDING) {
+    return rsaWithoutPadding(privateKey, buffer, "d");
+  }
+  return op_node_private_decrypt(data, buffer, padding, oaepHash, oaepLabel);
 }
 
 export function publicEncrypt(
@@ -429,8 +413,24 @@ export function publicEncrypt(
   const { data } = prepareKey(publicKey);
   const padding = publicKey.padding || 1;
 
+  const { oaepHash, oaepLabel } = getOaepOptions(publicKey);
+
   buffer = getArrayBufferOrView(buffer, "buffer");
-  return op_node_public_encrypt(data, buffer, padding);
+  if (padding === constants.RSA_NO_PADDING) {
+    return rsaWithoutPadding(publicKey, buffer, "e");
+  }
+  return op_node_public_encrypt(data, buffer, padding, oaepHash, oaepLabel);
+}
+
+function getOaepOptions(key) {
+  const { oaepHash = "sha1", oaepLabel } = key;
+  validateString(oaepHash, "key.oaepHash");
+  return {
+    oaepHash,
+    oaepLabel: oaepLabel === undefined
+      ? undefined
+      : getArrayBufferOrView(oaepLabel, "key.oaepLabel"),
+  };
 }
 
 export function prepareKey(key) {
@@ -451,8 +451,48 @@ export function prepareKey(key) {
   throw new TypeError("Invalid key type");
 }
 
-export function publicDecrypt() {
-  notImplemented("crypto.publicDecrypt");
+export function publicDecrypt(
+  publicKey: ArrayBufferView | string | KeyObject,
+  buffer: ArrayBufferView | string | KeyObject,
+): Buffer {
+  const { data } = prepareKey(publicKey);
+  const padding = publicKey.padding || 1;
+
+  buffer = getArrayBufferOrView(buffer, "buffer");
+  if (padding === constants.RSA_NO_PADDING) {
+    return rsaWithoutPadding(publicKey, buffer, "e");
+  }
+  return op_node_public_decrypt(data, buffer, padding);
+}
+
+/**
+ * Raw RSA, `buffer ^ exponent mod n`, for `RSA_NO_PADDING`. The exponent is
+ * `"d"` for the private key operations and `"e"` for the public ones.
+ */
+function rsaWithoutPadding(
+  key,
+  buffer: ArrayBufferView | ArrayBuffer,
+  exponent: "d" | "e",
+): Buffer {
+  const keyObject = exponent === "d"
+    ? createPrivateKey(key)
+    : createPublicKey(key);
+  const jwk = keyObject.export({ format: "jwk" });
+  if (jwk.kty !== "RSA" || jwk[exponent] === undefined) {
+    throw new TypeError("Invalid key type");
+  }
+  const modulus = Buffer.from(jwk.n, "base64url");
+  const data = ArrayBuffer.isView(buffer)
+    ? Buffer.from(buffer.buffer, buffer.byteOffset, buffer.byteLength)
+    : Buffer.from(buffer);
+  if (
+    data.byteLength !== modulus.byteLength || Buffer.compare(data, modulus) >= 0
+  ) {
+    throw new TypeError("Data must be as long as the key modulus");
+  }
+  return Buffer.from(
+    op_node_mod_pow(data, Buffer.from(jwk[exponent], "base64url"), modulus),
+  );
 }
 
 export default {
@@ -6,15 +6,24 @@
 
 import {
   op_node_create_hash,
+  op_node_create_hmac,
   op_node_get_hashes,
   op_node_hash_clone,
   op_node_hash_digest,
   op_node_hash_digest_hex,
   op_node_hash_update,
   op_node_hash_update_str,
+  op_node_hash_update_stream,
+  op_node_hmac_digest,
+  op_node_hmac_update,
+  op_node_hmac_update_stream,
 } from "ext:core/ops";
 
 import { TextEncoder } from "ext:deno_web/08_text_encoding.js";
+import {
+  getReadableStreamResourceBacking,
+  resourceForReadableStream,
+} from "ext:deno_web/06_streams.js";
 import { Buffer } from "node:buffer";
 import { Transform } from "node:stream";
 import {
@@ -22,7 +31,10 @@ import {
   forgivingBase64UrlEncode as encodeToBase64Url,
 } from "ext:deno_web/00_infra.js";
 import type { TransformOptions } from "ext:deno_node/_stream.d.ts";
-import { validateString } from "ext:deno_node/internal/validators.mjs";
+import {
+  validateString,
+  validateUint32,
+} from "ext:deno_node/internal/validators.mjs";
 import type {
   BinaryToTextEncoding,
   Encoding,
@@ -56,6 +68,48 @@ const coerceToBytes = (data: string | BufferSource): Uint8Array => {
   }
 };
 
+/**
+ * The resource to read `stream` from in Rust, and whether to close it once
+ * read. A stream that isn't backed by a resource gets one pumping it.
+ */
+export function resourceForStream(
+  stream: ReadableStream<Uint8Array>,
+): [number, boolean] {
+  const resourceBacking = getReadableStreamResourceBacking(stream);
+  if (resourceBacking) {
+    return [resourceBacking.rid, resourceBacking.autoClose];
+  }
+  return [resourceForReadableStream(stream), true];
+}
+
+function encodeDigest(
+  digest: Uint8Array,
+  encoding?: string,
+): Buffer | string {
+  if (encoding === undefined) {
+    return Buffer.from(digest);
+  }
+
+  // TODO(@littedivy): Fast paths for below encodings.
+  switch (encoding) {
+    case "binary":
+      return String.fromCharCode(...digest);
+    case "base64":
+      return encodeToBase64(digest);
+    case "base64url":
+      return encodeToBase64Url(digest);
+    case "buffer":
+      return Buffer.from(digest);
+    default:
+      return Buffer.from(digest).toString(encoding);
+  }
+}
+
+export interface HashOptions extends TransformOptions {
+  /** The output length in bytes of the XOF hash functions, like `shake256`. */
+  outputLength?: number;
+}
+
 /**
  * The Hash class is a utility for creating hash digests of data. It can be used in one of two ways:
  *
@@ -66,10 +120,11 @@ const coerceToBytes = (data: string | BufferSource): Uint8Array => {
  */
 export class Hash extends Transform {
   #context: number;
+  #outputLength: number;
 
   constructor(
     algorithm: string | number,
-    _opts?: TransformOptions,
+    opts?: HashOptions,
   ) {
     super({
       transform(chunk: string, _encoding: string, callback: () => void) {
@@ -82,6 +137,12 @@ export class Hash extends Transform {
       },
     });
 
+    const outputLength = opts?.outputLength;
+    if (outputLength !== undefined) {
+      validateUint32(outputLength, "options.outputLength");
+    }
+    this.#outputLength = outputLength ?? -1;
+
     if (typeof algorithm === "string") {
       this.#context = op_node_create_hash(
         algorithm.toLowerCase(),
@@ -96,8 +157,8 @@ export class Hash extends Transform {
     const context = this.#context;
   }
 
-  copy(): Hash {
-    return new Hash(op_node_hash_clone(this.#context));
+  copy(options?: HashOptions): Hash {
+    return new Hash(op_node_hash_clone(this.#context), options);
   }
 
   /**
@@ -113,6 +174,16 @@ export class Hash extends Transform {
     return this;
   }
 
+  /**
+   * Updates the hash content with the chunks of `stream`. They are read in
+   * Rust, rather than passed to `update()` one at a time. Deno only.
+   */
+  async updateFromStream(stream: ReadableStream<Uint8Array>): Promise<this> {
+    const { 0: rid, 1: autoClose } = resourceForStream(stream);
+    await op_node_hash_update_stream(this.#context, rid, autoClose);
+    return this;
+  }
+
   /**
    * Calculates the digest of all of the data.
    *
@@ -122,27 +193,13 @@ export class Hash extends Transform {
    */
   digest(encoding?: string): Buffer | string {
     if (encoding === "hex") {
-      return op_node_hash_digest_hex(this.#context);
-    }
-
-    const digest = op_node_hash_digest(this.#context);
-    if (encoding === undefined) {
-      return Buffer.from(digest);
+      return op_node_hash_digest_hex(this.#context, this.#outputLength);
     }
 
-    // TODO(@littedivy): Fast paths for below encodings.
-    switch (encoding) {
-      case "binary":
-        return String.fromCharCode(...digest);
-      case "base64":
-        return encodeToBase64(digest);
-      case "base64url":
-        return encodeToBase64Url(digest);
-      case "buffer":
-        return Buffer.from(digest);
-      default:
-        return Buffer.from(digest).toString(encoding);
-    }
+    return encodeDigest(
+      op_node_hash_digest(this.#context, this.#outputLength),
+      encoding,
+    );
   }
 }
 
@@ -157,11 +214,7 @@ export function Hmac(
 type Hmac = HmacImpl;
 
 class HmacImpl extends Transform {
-  #ipad: Uint8Array;
-  #opad: Uint8Array;
-  #ZEROES = Buffer.alloc(128);
-  #algorithm: string;
-  #hash: Hash;
+  #context: number;
 
   constructor(
     hmac: string,
@@ -188,44 +241,27 @@ class HmacImpl extends Transform {
       ? getKeyMaterial(key)
       : prepareSecretKey(key, options?.encoding) as Buffer;
 
-    const alg = hmac.toLowerCase();
-    this.#algorithm = alg;
-    const blockSize = (alg === "sha512" || alg === "sha384") ? 128 : 64;
-    const keySize = u8Key.length;
-
-    let bufKey: Buffer;
-
-    if (keySize > blockSize) {
-      const hash = new Hash(alg, options);
-      bufKey = hash.update(u8Key).digest() as Buffer;
-    } else {
-      bufKey = Buffer.concat([u8Key, this.#ZEROES], blockSize);
-    }
-
-    this.#ipad = Buffer.allocUnsafe(blockSize);
-    this.#opad = Buffer.allocUnsafe(blockSize);
-
-    for (let i = 0; i < blockSize; i++) {
-      this.#ipad[i] = bufKey[i] ^ 0x36;
-      this.#opad[i] = bufKey[i] ^ 0x5C;
+    this.#context = op_node_create_hmac(hmac.toLowerCase(), u8Key);
+    if (this.#context === 0) {
+      throw new TypeError(`Invalid digest: ${hmac}`);
     }
-
-    this.#hash = new Hash(alg);
-    this.#hash.update(this.#ipad);
   }
 
   digest(): Buffer;
   digest(encoding: BinaryToTextEncoding): string;
   digest(encoding?: BinaryToTextEncoding): Buffer | string {
-    const result = this.#hash.digest();
+    return encodeDigest(op_node_hmac_digest(this.#context), encoding);
+  }
 
-    return new Hash(this.#algorithm).update(this.#opad).update(result).digest(
-      encoding,
-    );
+  update(data: string | ArrayBuffer, _inputEncoding?: Encoding): this {
+    unwrapErr(op_node_hmac_update(this.#context, coerceToBytes(data)));
+    return this;
   }
 
-  update(data: string | ArrayBuffer, inputEncoding?: Encoding): this {
-    this.#hash.update(data, inputEncoding);
+  /** Like `Hash.prototype.updateFromStream()`. Deno only. */
+  async updateFromStream(stream: ReadableStream<Uint8Array>): Promise<this> {
+    const { 0: rid, 1: autoClose } = resourceForStream(stream);
+    await op_node_hmac_update_stream(this.#context, rid, autoClose);
     return this;
   }
 }
@@ -236,7 +272,7 @@ Hmac.prototype = HmacImpl.prototype;
  * Creates and returns a Hash object that can be used to generate hash digests
  * using the given `algorithm`. Optional `options` argument controls stream behavior.
  */
-export function createHash(algorithm: string, opts?: TransformOptions) {
+export function createHash(algorithm: string, opts?: HashOptions) {
   return new Hash(algorithm, opts);
 }
 
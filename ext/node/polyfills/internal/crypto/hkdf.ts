@@ -13,6 +13,7 @@ import {
 } from "ext:deno_node/internal/validators.mjs";
 import {
   ERR_CRYPTO_INVALID_DIGEST,
+  ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE,
   ERR_INVALID_ARG_TYPE,
   ERR_OUT_OF_RANGE,
   hideStackFrames,
@@ -22,7 +23,7 @@ import {
   validateByteSource,
 } from "ext:deno_node/internal/crypto/util.ts";
 import {
-  createSecretKey,
+  getKeyMaterial,
   isKeyObject,
   KeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
@@ -35,12 +36,12 @@ import {
 
 const validateParameters = hideStackFrames((hash, key, salt, info, length) => {
   validateString(hash, "digest");
-  key = new Uint8Array(prepareKey(key));
+  key = prepareKey(key);
   validateByteSource(salt, "salt");
   validateByteSource(info, "info");
 
-  salt = new Uint8Array(toBuf(salt));
-  info = new Uint8Array(toBuf(info));
+  salt = toBytes(toBuf(salt));
+  info = toBytes(toBuf(info));
 
   validateInteger(length, "length", 0, kMaxLength);
 
@@ -61,13 +62,26 @@ const validateParameters = hideStackFrames((hash, key, salt, info, length) => {
   };
 });
 
-function prepareKey(key: BinaryLike | KeyObject) {
+/** Returns the bytes of an ArrayBuffer or ArrayBufferView, without copying. */
+function toBytes(value: ArrayBufferLike | ArrayBufferView): Uint8Array {
+  if (isAnyArrayBuffer(value)) {
+    return new Uint8Array(value as ArrayBufferLike);
+  }
+  const view = value as ArrayBufferView;
+  return new Uint8Array(view.buffer, view.byteOffset, view.byteLength);
+}
+
+function prepareKey(key: BinaryLike | KeyObject): Uint8Array {
   if (isKeyObject(key)) {
-    return key;
+    const keyObject = key as KeyObject;
+    if (keyObject.type !== "secret") {
+      throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(keyObject.type, "secret");
+    }
+    return getKeyMaterial(keyObject);
   }
 
   if (isAnyArrayBuffer(key)) {
-    return createSecretKey(new Uint8Array(key as unknown as ArrayBufferLike));
+    return toBytes(key as unknown as ArrayBufferLike);
   }
 
   key = toBuf(key as string);
@@ -87,7 +101,7 @@ function prepareKey(key: BinaryLike | KeyObject) {
     );
   }
 
-  return createSecretKey(key);
+  return toBytes(key);
 }
 
 export function hkdf(
@@ -32,6 +32,7 @@ import { Buffer } from "node:buffer";
 import { KeyFormat, KeyType } from "ext:deno_node/internal/crypto/types.ts";
 
 import {
+  op_node_create_generated_key,
   op_node_dh_generate,
   op_node_dh_generate_async,
   op_node_dh_generate_group,
@@ -566,8 +567,8 @@ export function generateKeyPair(
   ) => void,
 ) {
   createJob(kAsync, type, options).then(([privateKey, publicKey]) => {
-    privateKey = new PrivateKeyObject(setOwnedKey(privateKey), { type });
-    publicKey = new PublicKeyObject(setOwnedKey(publicKey), { type });
+    privateKey = createKeyObject(type, options, privateKey, true);
+    publicKey = createKeyObject(type, options, publicKey, false);
 
     if (typeof options === "object" && options !== null) {
       const { publicKeyEncoding, privateKeyEncoding } = options as any;
@@ -768,8 +769,8 @@ export function generateKeyPairSync(
   | KeyPairSyncResult<string | Buffer, string | Buffer> {
   let [privateKey, publicKey] = createJob(kSync, type, options);
 
-  privateKey = new PrivateKeyObject(setOwnedKey(privateKey), { type });
-  publicKey = new PublicKeyObject(setOwnedKey(publicKey), { type });
+  privateKey = createKeyObject(type, options, privateKey, true);
+  publicKey = createKeyObject(type, options, publicKey, false);
 
   if (typeof options === "object" && options !== null) {
     const { publicKeyEncoding, privateKeyEncoding } = options as any;
@@ -789,6 +790,18 @@ export function generateKeyPairSync(
 const kSync = 0;
 const kAsync = 1;
 
+// Wraps a generated key. The named curve is needed for EC keys, which are
+// generated as the bare scalar and point.
+function createKeyObject(type, options, key, isPrivate) {
+  const handle = op_node_create_generated_key(
+    key,
+    isPrivate,
+    type,
+    type === "ec" ? options.namedCurve : "",
+  );
+  return isPrivate ? new PrivateKeyObject(handle) : new PublicKeyObject(handle);
+}
+
 function createJob(mode, type, options) {
   validateString(type, "type");
 
@@ -6,9 +6,13 @@
 
 import {
   op_node_create_private_key,
+  op_node_create_private_key_from_jwk,
   op_node_create_public_key,
-  op_node_export_rsa_public_pem,
-  op_node_export_rsa_spki_der,
+  op_node_create_public_key_from_jwk,
+  op_node_create_public_key_from_private,
+  op_node_export_private_key,
+  op_node_export_public_key,
+  op_node_get_asymmetric_key_details,
 } from "ext:core/ops";
 
 import {
@@ -17,6 +21,7 @@ import {
 } from "ext:deno_node/internal/crypto/constants.ts";
 import { isStringOrBuffer } from "ext:deno_node/internal/crypto/cipher.ts";
 import {
+  ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS,
   ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
@@ -41,6 +46,7 @@ import {
 import {
   validateObject,
   validateOneOf,
+  validateString,
 } from "ext:deno_node/internal/validators.mjs";
 import {
   forgivingBase64UrlEncode as encodeToBase64Url,
@@ -156,6 +162,7 @@ function copyBuffer(input: string | Buffer | ArrayBufferView) {
   );
 }
 
+// The material of secret keys. Asymmetric keys are held by their handles.
 const KEY_STORE = new WeakMap();
 
 export class KeyObject {
@@ -214,48 +221,100 @@ export interface JsonWebKeyInput {
   format: "jwk";
 }
 
-export function prepareAsymmetricKey(key) {
+// Parses the key input of `createPrivateKey()` and `createPublicKey()`.
+function getKeyInput(key) {
   if (isStringOrBuffer(key)) {
     return { format: "pem", data: getArrayBufferOrView(key, "key") };
-  } else if (isKeyObject(key)) {
-    return {
-      // Assumes that assymetric keys are stored as PEM.
-      format: "pem",
-      data: getKeyMaterial(key),
-    };
-  } else if (typeof key == "object") {
+  } else if (typeof key == "object" && key !== null) {
     const { key: data, encoding, format, type } = key;
+    if (format === "jwk") {
+      validateObject(data, "key.key");
+      return { format, data };
+    }
     if (!isStringOrBuffer(data)) {
       throw new TypeError("Invalid key type");
     }
 
+    let { passphrase } = key;
+    if (passphrase !== undefined) {
+      if (!isStringOrBuffer(passphrase)) {
+        throw new ERR_INVALID_ARG_VALUE("options.passphrase", passphrase);
+      }
+      passphrase = getArrayBufferOrView(
+        passphrase,
+        "key.passphrase",
+        encoding,
+      );
+    }
     return {
       data: getArrayBufferOrView(data, "key", encoding),
       format: format ?? "pem",
-      encoding,
       type,
+      passphrase,
     };
   }
 
   throw new TypeError("Invalid key type");
 }
 
+export function prepareAsymmetricKey(key) {
+  if (isKeyObject(key)) {
+    const type = key.type === "private" ? "pkcs8" : "spki";
+    return {
+      format: "der",
+      data: key.export({ format: "der", type }),
+      type,
+    };
+  } else if (typeof key == "object" && key !== null) {
+    if (isKeyObject(key.key)) {
+      return prepareAsymmetricKey(key.key);
+    }
+    // the ops only take unencrypted PEM and DER keys
+    if (key.format === "jwk") {
+      return prepareAsymmetricKey(
+        key.key?.d === undefined ? createPublicKey(key) : createPrivateKey(key),
+      );
+    }
+    if (key.passphrase !== undefined) {
+      return prepareAsymmetricKey(createPrivateKey(key));
+    }
+  }
+  const { data, format, type } = getKeyInput(key);
+  return { data, format, type };
+}
+
 export function createPrivateKey(
   key: PrivateKeyInput | string | Buffer | JsonWebKeyInput,
 ): PrivateKeyObject {
-  const { data, format, type } = prepareAsymmetricKey(key);
-  const details = op_node_create_private_key(data, format, type);
-  const handle = setOwnedKey(copyBuffer(data));
-  return new PrivateKeyObject(handle, details);
+  const { data, format, type, passphrase } = getKeyInput(key);
+  const handle = format === "jwk"
+    ? op_node_create_private_key_from_jwk(data)
+    : op_node_create_private_key(data, format, type ?? "", passphrase);
+  return new PrivateKeyObject(handle);
 }
 
 export function createPublicKey(
-  key: PublicKeyInput | string | Buffer | JsonWebKeyInput,
+  key: PublicKeyInput | string | Buffer | JsonWebKeyInput | KeyObject,
 ): PublicKeyObject {
-  const { data, format, type } = prepareAsymmetricKey(key);
-  const details = op_node_create_public_key(data, format, type);
-  const handle = setOwnedKey(copyBuffer(data));
-  return new PublicKeyObject(handle, details);
+  if (isCryptoKey(key)) {
+    key = key[kKeyObject];
+  } else if (isKeyObject(key?.key)) {
+    key = key.key;
+  }
+  if (isKeyObject(key)) {
+    if (key.type !== "private") {
+      throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(key.type, "private");
+    }
+    return new PublicKeyObject(
+      op_node_create_public_key_from_private(key[kHandle]),
+    );
+  }
+
+  const { data, format, type, passphrase } = getKeyInput(key);
+  const handle = format === "jwk"
+    ? op_node_create_public_key_from_jwk(data)
+    : op_node_create_public_key(data, format, type ?? "", passphrase);
+  return new PublicKeyObject(handle);
 }
 
 function getKeyTypes(allowKeyObject: boolean, bufferOnly = false) {
@@ -347,10 +406,12 @@ const kAsymmetricKeyType = Symbol("kAsymmetricKeyType");
 const kAsymmetricKeyDetails = Symbol("kAsymmetricKeyDetails");
 
 class AsymmetricKeyObject extends KeyObject {
-  constructor(type: KeyObjectType, handle: unknown, details: unknown) {
+  constructor(type: KeyObjectType, handle: unknown) {
     super(type, handle);
-    this[kAsymmetricKeyType] = details.type;
-    this[kAsymmetricKeyDetails] = { ...details };
+    const { type: asymmetricKeyType, ...details } =
+      op_node_get_asymmetric_key_details(handle);
+    this[kAsymmetricKeyType] = asymmetricKeyType;
+    this[kAsymmetricKeyDetails] = details;
   }
 
   get asymmetricKeyType() {
@@ -358,49 +419,66 @@ class AsymmetricKeyObject extends KeyObject {
   }
 
   get asymmetricKeyDetails() {
-    return this[kAsymmetricKeyDetails];
+    return { ...this[kAsymmetricKeyDetails] };
   }
 }
 
 export class PrivateKeyObject extends AsymmetricKeyObject {
-  constructor(handle: unknown, details: unknown) {
-    super("private", handle, details);
+  constructor(handle: unknown) {
+    super("private", handle);
   }
 
-  export(_options: unknown) {
-    notImplemented("crypto.PrivateKeyObject.prototype.export");
+  export(options: unknown) {
+    validateObject(options, "options");
+    const { format, type, cipher, passphrase } = options;
+    validateOneOf(format, "options.format", ["pem", "der", "jwk"]);
+    if (format === "jwk") {
+      return op_node_export_private_key(this[kHandle], format, "", "");
+    }
+    validateString(type, "options.type");
+
+    let key;
+    if (cipher != null) {
+      validateString(cipher, "options.cipher");
+      if (format === "der" && (type === "pkcs1" || type === "sec1")) {
+        throw new ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS(
+          type,
+          "does not support encryption",
+        );
+      }
+      if (!isStringOrBuffer(passphrase)) {
+        throw new ERR_INVALID_ARG_VALUE("options.passphrase", passphrase);
+      }
+      key = op_node_export_private_key(
+        this[kHandle],
+        format,
+        type,
+        cipher,
+        getArrayBufferOrView(passphrase, "options.passphrase"),
+      );
+    } else if (passphrase !== undefined) {
+      throw new ERR_INVALID_ARG_VALUE("options.cipher", cipher);
+    } else {
+      key = op_node_export_private_key(this[kHandle], format, type, "");
+    }
+    return format === "der" ? Buffer.from(key) : key;
   }
 }
 
 export class PublicKeyObject extends AsymmetricKeyObject {
-  constructor(handle: unknown, details: unknown) {
-    super("public", handle, details);
+  constructor(handle: unknown) {
+    super("public", handle);
   }
 
   export(options: unknown) {
-    const key = KEY_STORE.get(this[kHandle]);
-    switch (this.asymmetricKeyType) {
-      case "rsa":
-      case "rsa-pss": {
-        switch (options.format) {
-          case "pem":
-            return op_node_export_rsa_public_pem(key);
-          case "der": {
-            if (options.type == "pkcs1") {
-              return key;
-            } else {
-              return op_node_export_rsa_spki_der(key);
-            }
-          }
-          default:
-            throw new TypeError(`exporting ${options.type} is not implemented`);
-        }
-      }
-      default:
-        throw new TypeError(
-          `exporting ${this.asymmetricKeyType} is not implemented`,
-        );
+    validateObject(options, "options");
+    const { format, type } = options;
+    validateOneOf(format, "options.format", ["pem", "der", "jwk"]);
+    if (format !== "jwk") {
+      validateString(type, "options.type");
     }
+    const key = op_node_export_public_key(this[kHandle], format, type ?? "");
+    return format === "der" ? Buffer.from(key) : key;
   }
 }
 
@@ -10,15 +10,16 @@ import {
   op_node_check_prime_async,
   op_node_check_prime_bytes,
   op_node_check_prime_bytes_async,
-  op_node_gen_prime,
-  op_node_gen_prime_async,
+  op_node_generate_prime,
+  op_node_generate_prime_async,
+  op_node_random_uuid,
 } from "ext:core/ops";
 const {
   StringPrototypePadStart,
   StringPrototypeToString,
 } = primordials;
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
+import { Buffer } from "node:buffer";
 import randomBytes from "ext:deno_node/internal/crypto/_randomBytes.ts";
 import randomFill, {
   randomFillSync,
@@ -37,6 +38,7 @@ import {
 import {
   ERR_INVALID_ARG_TYPE,
   ERR_OUT_OF_RANGE,
+  NodeRangeError,
 } from "ext:deno_node/internal/errors.ts";
 
 export { default as randomBytes } from "ext:deno_node/internal/crypto/_randomBytes.ts";
@@ -172,13 +174,22 @@ export function generatePrime(
   }
   validateFunction(callback, "callback");
   const {
+    safe,
     bigint,
+    add,
+    rem,
   } = validateRandomPrimeJob(size, options);
-  op_node_gen_prime_async(size).then((prime: Uint8Array) =>
-    bigint ? arrayBufferToUnsignedBigInt(prime.buffer) : prime.buffer
-  ).then((prime: ArrayBuffer | bigint) => {
-    callback?.(null, prime);
-  });
+  op_node_generate_prime_async(size, safe, add, rem).then(
+    (prime: Uint8Array) => {
+      callback?.(
+        null,
+        bigint ? arrayBufferToUnsignedBigInt(prime.buffer) : prime.buffer,
+      );
+    },
+    (err: Error) => {
+      callback?.(err, undefined!);
+    },
+  );
 }
 
 export function generatePrimeSync(
@@ -186,80 +197,95 @@ export function generatePrimeSync(
   options: GeneratePrimeOptions = {},
 ): ArrayBuffer | bigint {
   const {
+    safe,
     bigint,
+    add,
+    rem,
   } = validateRandomPrimeJob(size, options);
 
-  const prime = op_node_gen_prime(size);
+  const prime = op_node_generate_prime(size, safe, add, rem);
   if (bigint) return arrayBufferToUnsignedBigInt(prime.buffer);
   return prime.buffer;
 }
 
+interface RandomPrimeJob {
+  safe: boolean;
+  bigint: boolean;
+  add?: Uint8Array;
+  rem?: Uint8Array;
+}
+
 function validateRandomPrimeJob(
   size: number,
   options: GeneratePrimeOptions,
-): GeneratePrimeOptions {
+): RandomPrimeJob {
   validateInt32(size, "size", 1);
   validateObject(options, "options");
 
-  let {
+  const {
     safe = false,
     bigint = false,
-    add,
-    rem,
   } = options!;
 
   validateBoolean(safe, "options.safe");
   validateBoolean(bigint, "options.bigint");
 
+  const add = largeNumberToBytes(options.add, "options.add");
+  const rem = largeNumberToBytes(options.rem, "options.rem");
+
   if (add !== undefined) {
-    if (typeof add === "bigint") {
-      add = unsignedBigIntToBuffer(add, "options.add");
-    } else if (!isAnyArrayBuffer(add) && !isArrayBufferView(add)) {
-      throw new ERR_INVALID_ARG_TYPE(
-        "options.add",
-        [
-          "ArrayBuffer",
-          "TypedArray",
-          "Buffer",
-          "DataView",
-          "bigint",
-        ],
-        add,
-      );
+    const addValue = bytesToUnsignedBigInt(add);
+    if (addValue === 0n || StringPrototypeToString(addValue, 2).length > size) {
+      throw new NodeRangeError("ERR_OUT_OF_RANGE", "invalid options.add");
     }
-  }
-
-  if (rem !== undefined) {
-    if (typeof rem === "bigint") {
-      rem = unsignedBigIntToBuffer(rem, "options.rem");
-    } else if (!isAnyArrayBuffer(rem) && !isArrayBufferView(rem)) {
-      throw new ERR_INVALID_ARG_TYPE(
-        "options.rem",
-        [
-          "ArrayBuffer",
-          "TypedArray",
-          "Buffer",
-          "DataView",
-          "bigint",
-        ],
-        rem,
-      );
+    if (rem !== undefined && bytesToUnsignedBigInt(rem) >= addValue) {
+      throw new NodeRangeError("ERR_OUT_OF_RANGE", "invalid options.rem");
     }
   }
 
-  // TODO(@littledivy): safe, add and rem options are not implemented.
-  if (safe || add || rem) {
-    notImplemented("safe, add and rem options are not implemented.");
-  }
-
   return {
     safe,
     bigint,
     add,
-    rem,
+    // Like in Node.js, `rem` only has an effect together with `add`.
+    rem: add === undefined ? undefined : rem,
   };
 }
 
+function largeNumberToBytes(
+  value: LargeNumberLike | undefined,
+  name: string,
+): Uint8Array | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+  if (typeof value === "bigint") {
+    return unsignedBigIntToBuffer(value, name);
+  }
+  if (isAnyArrayBuffer(value)) {
+    return new Uint8Array(value as ArrayBuffer);
+  }
+  if (isArrayBufferView(value)) {
+    const view = value as ArrayBufferView;
+    return new Uint8Array(view.buffer, view.byteOffset, view.byteLength);
+  }
+  throw new ERR_INVALID_ARG_TYPE(
+    name,
+    [
+      "ArrayBuffer",
+      "TypedArray",
+      "Buffer",
+      "DataView",
+      "bigint",
+    ],
+    value,
+  );
+}
+
+function bytesToUnsignedBigInt(bytes: Uint8Array): bigint {
+  return arrayBufferToUnsignedBigInt(bytes.slice().buffer);
+}
+
 /**
  * 48 is the ASCII code for '0', 97 is the ASCII code for 'a'.
  * @param {number} number An integer between 0 and 15.
@@ -297,7 +323,30 @@ function unsignedBigIntToBuffer(bigint: bigint, name: string) {
   return Buffer.from(padded, "hex");
 }
 
-export const randomUUID = () => globalThis.crypto.randomUUID();
+export interface RandomUUIDOptions {
+  /**
+   * Node.js caches random data to generate UUIDs faster. This is accepted
+   * for compatibility, but has no effect.
+   * @default false
+   */
+  disableEntropyCache?: boolean;
+}
+
+export function randomUUID(options?: RandomUUIDOptions): string {
+  if (options !== undefined) {
+    validateObject(options, "options");
+  }
+  const { disableEntropyCache = false } = options ?? {};
+  validateBoolean(disableEntropyCache, "options.disableEntropyCache");
+
+  return op_node_random_uuid(false);
+}
+
+/** Returns a version 7 UUID, which starts with the current time in
+ * milliseconds so that UUIDs sort roughly by creation time. */
+export function randomUUIDv7(): string {
+  return op_node_random_uuid(true);
+}
 
 export default {
   checkPrime,
@@ -305,6 +354,7 @@ export default {
   generatePrime,
   generatePrimeSync,
   randomUUID,
+  randomUUIDv7,
   randomInt,
   randomBytes,
   randomFill,
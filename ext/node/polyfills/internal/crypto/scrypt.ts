@@ -73,16 +73,7 @@ export function scryptSync(
   }
 
   const buf = Buffer.alloc(keylen);
-  op_node_scrypt_sync(
-    password,
-    salt,
-    keylen,
-    Math.log2(N),
-    r,
-    p,
-    maxmem,
-    buf.buffer,
-  );
+  op_node_scrypt_sync(password, salt, N, r, p, maxmem, buf);
 
   return buf;
 }
@@ -108,18 +99,11 @@ export function scrypt(
   }
 
   try {
-    op_node_scrypt_async(
-      password,
-      salt,
-      keylen,
-      Math.log2(N),
-      r,
-      p,
-      maxmem,
-    ).then(
+    op_node_scrypt_async(password, salt, keylen, N, r, p, maxmem).then(
       (buf: Uint8Array) => {
         cb(null, Buffer.from(buf.buffer));
       },
+      (err: unknown) => cb(err),
     );
   } catch (err: unknown) {
     return cb(err);
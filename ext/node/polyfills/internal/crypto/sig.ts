@@ -6,9 +6,10 @@
 
 import { op_node_sign, op_node_verify } from "ext:core/ops";
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import {
   validateFunction,
+  validateInt32,
+  validateOneOf,
   validateString,
 } from "ext:deno_node/internal/validators.mjs";
 import { Buffer } from "node:buffer";
@@ -22,13 +23,13 @@ import type {
   PublicKeyInput,
 } from "ext:deno_node/internal/crypto/types.ts";
 import {
+  isKeyObject,
   KeyObject,
   prepareAsymmetricKey,
 } from "ext:deno_node/internal/crypto/keys.ts";
 import { createHash, Hash } from "ext:deno_node/internal/crypto/hash.ts";
-import { KeyFormat, KeyType } from "ext:deno_node/internal/crypto/types.ts";
-import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
 import { ERR_CRYPTO_SIGN_KEY_REQUIRED } from "ext:deno_node/internal/errors.ts";
+import { crypto as constants } from "ext:deno_node/internal_binding/constants.ts";
 
 export type DSAEncoding = "der" | "ieee-p1363";
 
@@ -51,6 +52,31 @@ export interface VerifyKeyObjectInput extends SigningOptions {
 
 export type KeyLike = string | Buffer | KeyObject;
 
+function getSigningOptions(key: unknown) {
+  let padding = constants.RSA_PKCS1_PADDING;
+  let saltLength = constants.RSA_PSS_SALTLEN_AUTO;
+  let dsaEncoding: DSAEncoding = "der";
+  if (typeof key === "object" && key !== null && !isKeyObject(key)) {
+    const options = key as SigningOptions;
+    if (options.padding !== undefined) {
+      validateInt32(options.padding, "key.padding");
+      padding = options.padding;
+    }
+    if (options.saltLength !== undefined) {
+      validateInt32(options.saltLength, "key.saltLength");
+      saltLength = options.saltLength;
+    }
+    if (options.dsaEncoding !== undefined) {
+      validateOneOf(options.dsaEncoding, "key.dsaEncoding", [
+        "der",
+        "ieee-p1363",
+      ]);
+      dsaEncoding = options.dsaEncoding;
+    }
+  }
+  return { padding, saltLength, dsaEncoding };
+}
+
 export class SignImpl extends Writable {
   hash: Hash;
   #digestType: string;
@@ -80,12 +106,18 @@ export class SignImpl extends Writable {
     encoding?: BinaryToTextEncoding,
   ): Buffer | string {
     const { data, format, type } = prepareAsymmetricKey(privateKey);
+    const { padding, saltLength, dsaEncoding } = getSigningOptions(
+      privateKey,
+    );
     const ret = Buffer.from(op_node_sign(
       this.hash.digest(),
       this.#digestType,
       data!,
-      type,
+      type ?? "",
       format,
+      padding,
+      saltLength,
+      dsaEncoding,
     ));
     return encoding ? ret.toString(encoding) : ret;
   }
@@ -97,6 +129,12 @@ export class SignImpl extends Writable {
     this.hash.update(data, encoding);
     return this;
   }
+
+  /** Like `Hash.prototype.updateFromStream()`. Deno only. */
+  async updateFromStream(stream: ReadableStream<Uint8Array>): Promise<this> {
+    await this.hash.updateFromStream(stream);
+    return this;
+  }
 }
 
 export function Sign(algorithm: string, options?: WritableOptions) {
@@ -135,34 +173,29 @@ export class VerifyImpl extends Writable {
     return this;
   }
 
+  /** Like `Hash.prototype.updateFromStream()`. Deno only. */
+  async updateFromStream(stream: ReadableStream<Uint8Array>): Promise<this> {
+    await this.hash.updateFromStream(stream);
+    return this;
+  }
+
   verify(
     publicKey: BinaryLike | VerifyKeyObjectInput | VerifyPublicKeyInput,
     signature: BinaryLike,
     encoding?: BinaryToTextEncoding,
   ): boolean {
-    let keyData: BinaryLike;
-    let keyType: KeyType;
-    let keyFormat: KeyFormat;
-    if (typeof publicKey === "string" || isArrayBufferView(publicKey)) {
-      // if the key is BinaryLike, interpret it as a PEM encoded RSA key
-      // deno-lint-ignore no-explicit-any
-      keyData = publicKey as any;
-      keyType = "rsa";
-      keyFormat = "pem";
-    } else {
-      // TODO(kt3k): Add support for the case when publicKey is a KeyObject,
-      // CryptoKey, etc
-      notImplemented(
-        "crypto.Verify.prototype.verify with non BinaryLike input",
-      );
-    }
+    const { data, format, type } = prepareAsymmetricKey(publicKey);
+    const { padding, saltLength, dsaEncoding } = getSigningOptions(publicKey);
     return op_node_verify(
       this.hash.digest(),
       this.#digestType,
-      keyData!,
-      keyType,
-      keyFormat,
+      data!,
+      type ?? "",
+      format,
       Buffer.from(signature, encoding),
+      padding,
+      saltLength,
+      dsaEncoding,
     );
   }
 }
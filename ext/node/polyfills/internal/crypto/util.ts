@@ -4,6 +4,8 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import { op_node_get_ciphers, op_node_get_curves } from "ext:core/ops";
+
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { Buffer } from "node:buffer";
 import {
@@ -58,6 +60,13 @@ export const ellipticCurves: Array<EllipticCurve> = [
     publicKeySizeCompressed: 49,
     sharedSecretSize: 48,
   }, // NIST P-384 EC
+  {
+    name: "secp521r1",
+    privateKeySize: 66,
+    publicKeySize: 133,
+    publicKeySizeCompressed: 67,
+    sharedSecretSize: 66,
+  }, // NIST P-521 EC
   {
     name: "secp224r1",
     privateKeySize: 28,
@@ -67,26 +76,8 @@ export const ellipticCurves: Array<EllipticCurve> = [
   }, // NIST P-224 EC
 ];
 
-// deno-fmt-ignore
-const supportedCiphers = [
-  "aes-128-ecb",  "aes-192-ecb",
-  "aes-256-ecb",  "aes-128-cbc",
-  "aes-192-cbc",  "aes-256-cbc",
-  "aes128",       "aes192",
-  "aes256",       "aes-128-cfb",
-  "aes-192-cfb",  "aes-256-cfb",
-  "aes-128-cfb8", "aes-192-cfb8",
-  "aes-256-cfb8", "aes-128-cfb1",
-  "aes-192-cfb1", "aes-256-cfb1",
-  "aes-128-ofb",  "aes-192-ofb",
-  "aes-256-ofb",  "aes-128-ctr",
-  "aes-192-ctr",  "aes-256-ctr",
-  "aes-128-gcm",  "aes-192-gcm",
-  "aes-256-gcm"
-];
-
 export function getCiphers(): string[] {
-  return supportedCiphers;
+  return op_node_get_ciphers();
 }
 
 export function getCipherInfo(
@@ -126,7 +117,7 @@ export function getCipherInfo(
   //
   // TODO(@littledivy): write proper cipher info utility in Rust
   // in future refactors
-  const cipher = supportedCiphers.find((c) => c === nameOrNid);
+  const cipher = getCiphers().find((c) => c === nameOrNid);
   if (cipher === undefined) {
     return undefined;
   }
@@ -138,7 +129,8 @@ export function getCipherInfo(
       name: `${name}-${keyLength}-${mode}`,
       keyLength: parseInt(keyLength) / 8,
       mode,
-      ivLength: 16,
+      // the default nonce length of the authenticated modes
+      ivLength: mode === "gcm" || mode === "ccm" ? 12 : 16,
     };
   }
 
@@ -211,9 +203,8 @@ export const validateByteSource = hideStackFrames((val, name) => {
   );
 });
 
-const curveNames = ellipticCurves.map((x) => x.name);
 export function getCurves(): readonly string[] {
-  return curveNames;
+  return op_node_get_curves();
 }
 
 export interface SecureHeapUsage {
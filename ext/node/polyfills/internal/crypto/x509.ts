@@ -7,23 +7,39 @@
 import {
   op_node_x509_ca,
   op_node_x509_check_email,
+  op_node_x509_check_host,
+  op_node_x509_check_ip,
   op_node_x509_fingerprint,
   op_node_x509_fingerprint256,
   op_node_x509_fingerprint512,
   op_node_x509_get_issuer,
+  op_node_x509_get_public_key,
+  op_node_x509_get_raw,
   op_node_x509_get_serial_number,
   op_node_x509_get_subject,
+  op_node_x509_get_subject_alt_name,
   op_node_x509_get_valid_from,
   op_node_x509_get_valid_to,
   op_node_x509_key_usage,
   op_node_x509_parse,
 } from "ext:core/ops";
 
-import { KeyObject } from "ext:deno_node/internal/crypto/keys.ts";
+import {
+  createPublicKey,
+  KeyObject,
+} from "ext:deno_node/internal/crypto/keys.ts";
 import { Buffer } from "node:buffer";
-import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+} from "ext:deno_node/internal/errors.ts";
+import { isIP } from "ext:deno_node/internal/net.ts";
 import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
-import { validateString } from "ext:deno_node/internal/validators.mjs";
+import {
+  validateBoolean,
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { BinaryLike } from "ext:deno_node/internal/crypto/types.ts";
 
@@ -32,9 +48,9 @@ export type PeerCertificate = any;
 
 export interface X509CheckOptions {
   /**
-   * @default 'always'
+   * @default 'default'
    */
-  subject: "always" | "never";
+  subject: "default" | "always" | "never";
   /**
    * @default true
    */
@@ -53,6 +69,31 @@ export interface X509CheckOptions {
   singleLabelSubdomains: boolean;
 }
 
+function getFlags(options: Partial<X509CheckOptions> = {}) {
+  validateObject(options, "options");
+  const {
+    subject = "default",
+    wildcards = true,
+    partialWildcards = true,
+    multiLabelWildcards = false,
+    singleLabelSubdomains = false,
+  } = { ...options };
+  validateBoolean(wildcards, "options.wildcards");
+  validateBoolean(partialWildcards, "options.partialWildcards");
+  validateBoolean(multiLabelWildcards, "options.multiLabelWildcards");
+  validateBoolean(singleLabelSubdomains, "options.singleLabelSubdomains");
+  if (subject !== "default" && subject !== "always" && subject !== "never") {
+    throw new ERR_INVALID_ARG_VALUE("options.subject", subject);
+  }
+  return {
+    subject,
+    wildcards,
+    partialWildcards,
+    multiLabelWildcards,
+    singleLabelSubdomains,
+  };
+}
+
 export class X509Certificate {
   #handle: number;
 
@@ -78,20 +119,43 @@ export class X509Certificate {
 
   checkEmail(
     email: string,
-    _options?: Pick<X509CheckOptions, "subject">,
+    options?: Pick<X509CheckOptions, "subject">,
   ): string | undefined {
     validateString(email, "email");
-    if (op_node_x509_check_email(this.#handle, email)) {
+    if (email.includes("\0")) {
+      throw new ERR_INVALID_ARG_VALUE("email", email);
+    }
+    const { subject } = getFlags(options);
+    if (op_node_x509_check_email(this.#handle, email, subject)) {
       return email;
     }
   }
 
-  checkHost(_name: string, _options?: X509CheckOptions): string | undefined {
-    notImplemented("crypto.X509Certificate.prototype.checkHost");
+  checkHost(name: string, options?: X509CheckOptions): string | undefined {
+    validateString(name, "name");
+    if (name.includes("\0")) {
+      throw new ERR_INVALID_ARG_VALUE("name", name);
+    }
+    const flags = getFlags(options);
+    return op_node_x509_check_host(
+      this.#handle,
+      name,
+      flags.subject,
+      flags.wildcards,
+      flags.partialWildcards,
+      flags.multiLabelWildcards,
+      flags.singleLabelSubdomains,
+    ) ?? undefined;
   }
 
-  checkIP(_ip: string): string | undefined {
-    notImplemented("crypto.X509Certificate.prototype.checkIP");
+  checkIP(ip: string): string | undefined {
+    validateString(ip, "ip");
+    if (!isIP(ip)) {
+      throw new ERR_INVALID_ARG_VALUE("ip", ip);
+    }
+    if (op_node_x509_check_ip(this.#handle, ip)) {
+      return ip;
+    }
   }
 
   checkIssued(_otherCert: X509Certificate): boolean {
@@ -145,15 +209,15 @@ export class X509Certificate {
   }
 
   get publicKey(): KeyObject {
-    notImplemented("crypto.X509Certificate.prototype.publicKey");
-
-    return {} as KeyObject;
+    return createPublicKey({
+      key: Buffer.from(op_node_x509_get_public_key(this.#handle)),
+      format: "der",
+      type: "spki",
+    });
   }
 
   get raw(): Buffer {
-    notImplemented("crypto.X509Certificate.prototype.raw");
-
-    return {} as Buffer;
+    return Buffer.from(op_node_x509_get_raw(this.#handle));
   }
 
   get serialNumber(): string {
@@ -165,7 +229,7 @@ export class X509Certificate {
   }
 
   get subjectAltName(): string | undefined {
-    return undefined;
+    return op_node_x509_get_subject_alt_name(this.#handle) ?? undefined;
   }
 
   toJSON(): string {
@@ -177,7 +241,12 @@ export class X509Certificate {
   }
 
   toString(): string {
-    notImplemented("crypto.X509Certificate.prototype.toString");
+    const base64 = this.raw.toString("base64");
+    let pem = "-----BEGIN CERTIFICATE-----\n";
+    for (let i = 0; i < base64.length; i += 64) {
+      pem += base64.slice(i, i + 64) + "\n";
+    }
+    return pem + "-----END CERTIFICATE-----\n";
   }
 
   get validFrom(): string {
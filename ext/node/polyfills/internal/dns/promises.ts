@@ -205,7 +205,7 @@ export function lookup(
 
 function onresolve(
   this: QueryReqWrap,
-  err: number,
+  err: number | string,
   records: Records,
   ttls?: number[],
 ) {
@@ -518,6 +518,46 @@ export const ERR_FS_EISDIR = makeSystemErrorWithCode(
   "Path is a directory",
 );
 
+export const ERR_FS_CP_DIR_TO_NON_DIR = makeSystemErrorWithCode(
+  "ERR_FS_CP_DIR_TO_NON_DIR",
+  "Cannot overwrite directory with non-directory",
+);
+
+export const ERR_FS_CP_EEXIST = makeSystemErrorWithCode(
+  "ERR_FS_CP_EEXIST",
+  "Target already exists",
+);
+
+export const ERR_FS_CP_EINVAL = makeSystemErrorWithCode(
+  "ERR_FS_CP_EINVAL",
+  "Invalid src or dest",
+);
+
+export const ERR_FS_CP_FIFO_PIPE = makeSystemErrorWithCode(
+  "ERR_FS_CP_FIFO_PIPE",
+  "Cannot copy a FIFO pipe",
+);
+
+export const ERR_FS_CP_NON_DIR_TO_DIR = makeSystemErrorWithCode(
+  "ERR_FS_CP_NON_DIR_TO_DIR",
+  "Cannot overwrite non-directory with directory",
+);
+
+export const ERR_FS_CP_SOCKET = makeSystemErrorWithCode(
+  "ERR_FS_CP_SOCKET",
+  "Cannot copy a socket file",
+);
+
+export const ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY = makeSystemErrorWithCode(
+  "ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY",
+  "Cannot overwrite symlink in subdirectory of self",
+);
+
+export const ERR_FS_CP_UNKNOWN = makeSystemErrorWithCode(
+  "ERR_FS_CP_UNKNOWN",
+  "Cannot copy an unknown file type",
+);
+
 function createInvalidArgType(
   name: string,
   expected: string | string[],
@@ -2292,6 +2332,12 @@ export class ERR_HTTP2_STREAM_CANCEL extends NodeError {
   }
 }
 
+export class ERR_INVALID_ADDRESS extends NodeError {
+  constructor() {
+    super("ERR_INVALID_ADDRESS", "Invalid socket address");
+  }
+}
+
 export class ERR_INVALID_ADDRESS_FAMILY extends NodeRangeError {
   host: string;
   port: number;
@@ -2733,6 +2779,14 @@ export default {
   ERR_EVENT_RECURSION,
   ERR_FALSY_VALUE_REJECTION,
   ERR_FEATURE_UNAVAILABLE_ON_PLATFORM,
+  ERR_FS_CP_DIR_TO_NON_DIR,
+  ERR_FS_CP_EEXIST,
+  ERR_FS_CP_EINVAL,
+  ERR_FS_CP_FIFO_PIPE,
+  ERR_FS_CP_NON_DIR_TO_DIR,
+  ERR_FS_CP_SOCKET,
+  ERR_FS_CP_SYMLINK_TO_SUBDIRECTORY,
+  ERR_FS_CP_UNKNOWN,
   ERR_FS_EISDIR,
   ERR_FS_FILE_TOO_LARGE,
   ERR_FS_INVALID_SYMLINK_TYPE,
@@ -2796,6 +2850,7 @@ export default {
   ERR_INSPECTOR_NOT_CONNECTED,
   ERR_INSPECTOR_NOT_WORKER,
   ERR_INTERNAL_ASSERTION,
+  ERR_INVALID_ADDRESS,
   ERR_INVALID_ADDRESS_FAMILY,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_TYPE_RANGE,
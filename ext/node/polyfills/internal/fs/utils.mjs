@@ -9,6 +9,7 @@ import { Buffer } from "node:buffer";
 import {
   ERR_FS_EISDIR,
   ERR_FS_INVALID_SYMLINK_TYPE,
+  ERR_INCOMPATIBLE_OPTION_PAIR,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
   ERR_OUT_OF_RANGE,
@@ -807,6 +808,7 @@ const defaultCpOptions = {
   force: true,
   preserveTimestamps: false,
   recursive: false,
+  verbatimSymlinks: false,
 };
 
 const defaultRmOptions = {
@@ -833,6 +835,11 @@ export const validateCpOptions = hideStackFrames((options) => {
   validateBoolean(options.force, "options.force");
   validateBoolean(options.preserveTimestamps, "options.preserveTimestamps");
   validateBoolean(options.recursive, "options.recursive");
+  validateBoolean(options.verbatimSymlinks, "options.verbatimSymlinks");
+  options.mode = getValidMode(options.mode, "copyFile");
+  if (options.dereference === true && options.verbatimSymlinks === true) {
+    throw new ERR_INCOMPATIBLE_OPTION_PAIR("dereference", "verbatimSymlinks");
+  }
   if (options.filter !== undefined) {
     validateFunction(options.filter, "options.filter");
   }
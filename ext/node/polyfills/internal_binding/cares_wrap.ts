@@ -27,8 +27,16 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import { core } from "ext:core/mod.js";
+import {
+  op_node_dns_channel_cancel,
+  op_node_dns_channel_new,
+  op_node_dns_query,
+  op_node_dns_reverse,
+  op_node_dns_system_servers,
+} from "ext:core/ops";
 import type { ErrnoException } from "ext:deno_node/internal/errors.ts";
-import { isIPv4 } from "ext:deno_node/internal/net.ts";
+import { isIP, isIPv4 } from "ext:deno_node/internal/net.ts";
 import { codeMap } from "ext:deno_node/internal_binding/uv.ts";
 import {
   AsyncWrap,
@@ -134,7 +142,7 @@ export class QueryReqWrap extends AsyncWrap {
   resolve!: (records: any) => void;
   reject!: (err: ErrnoException | null) => void;
   oncomplete!: (
-    err: number,
+    err: number | string,
     // deno-lint-ignore no-explicit-any
     records: any,
     ttls?: number[],
@@ -182,10 +190,27 @@ function compressIPv6(address: string): string {
   return finalAddress;
 }
 
+type DnsRecord = Awaited<ReturnType<typeof Deno.resolveDns>>[number];
+
+interface DnsAnswer {
+  code: string | null;
+  records: DnsRecord[];
+  ttls: number[];
+}
+
+function toResult({ code, records, ttls }: DnsAnswer) {
+  return {
+    code: code ?? 0,
+    ret: records as Awaited<ReturnType<typeof Deno.resolveDns>>,
+    ttls,
+  };
+}
+
 export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
-  #servers: [string, number][] = [];
+  #servers: [string, number][] | null = null;
   #timeout: number;
   #tries: number;
+  #rid: number | null = null;
 
   constructor(timeout: number, tries: number) {
     super(providerType.DNSCHANNEL);
@@ -194,61 +219,27 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
     this.#tries = tries;
   }
 
-  async #query(query: string, recordType: Deno.RecordType) {
-    // TODO(@bartlomieju): TTL logic.
-
-    let code: number;
-    let ret: Awaited<ReturnType<typeof Deno.resolveDns>>;
-
-    if (this.#servers.length) {
-      for (const [ipAddr, port] of this.#servers) {
-        const resolveOptions = {
-          nameServer: {
-            ipAddr,
-            port,
-          },
-        };
-
-        ({ code, ret } = await this.#resolve(
-          query,
-          recordType,
-          resolveOptions,
-        ));
-
-        if (code === 0 || code === codeMap.get("EAI_NODATA")!) {
-          break;
-        }
-      }
-    } else {
-      ({ code, ret } = await this.#resolve(query, recordType));
+  // The native channel is only created by the first query, so that the net
+  // permissions of the name servers are checked when they are used.
+  #channel(): number {
+    if (this.#rid === null) {
+      this.#rid = op_node_dns_channel_new(
+        this.#servers,
+        this.#timeout,
+        this.#tries,
+      );
     }
 
-    return { code: code!, ret: ret! };
+    return this.#rid!;
   }
 
-  async #resolve(
+  #query(
     query: string,
     recordType: Deno.RecordType,
-    resolveOptions?: Deno.ResolveDnsOptions,
-  ): Promise<{
-    code: number;
-    ret: Awaited<ReturnType<typeof Deno.resolveDns>>;
-  }> {
-    let ret: Awaited<ReturnType<typeof Deno.resolveDns>> = [];
-    let code = 0;
-
-    try {
-      ret = await Deno.resolveDns(query, recordType, resolveOptions);
-    } catch (e) {
-      if (e instanceof Deno.errors.NotFound) {
-        code = codeMap.get("EAI_NODATA")!;
-      } else {
-        // TODO(cmorten): map errors to appropriate error codes.
-        code = codeMap.get("UNKNOWN")!;
-      }
-    }
-
-    return { code, ret };
+  ): Promise<ReturnType<typeof toResult>> {
+    return op_node_dns_query(this.#channel(), query, recordType).then(
+      toResult,
+    );
   }
 
   queryAny(req: QueryReqWrap, name: string): number {
@@ -257,16 +248,24 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
     //
     // Ideally we move to using the "ANY" / "*" DNS query in future
     // REF: https://github.com/denoland/deno/issues/14492
+    this.#channel();
+
     (async () => {
       const records: { type: Deno.RecordType; [key: string]: unknown }[] = [];
 
       await Promise.allSettled([
-        this.#query(name, "A").then(({ ret }) => {
-          ret.forEach((record) => records.push({ type: "A", address: record }));
+        this.#query(name, "A").then(({ ret, ttls }) => {
+          ret.forEach((record, i) =>
+            records.push({ type: "A", address: record, ttl: ttls[i] })
+          );
         }),
-        this.#query(name, "AAAA").then(({ ret }) => {
-          (ret as string[]).forEach((record) =>
-            records.push({ type: "AAAA", address: compressIPv6(record) })
+        this.#query(name, "AAAA").then(({ ret, ttls }) => {
+          (ret as string[]).forEach((record, i) =>
+            records.push({
+              type: "AAAA",
+              address: compressIPv6(record),
+              ttl: ttls[i],
+            })
           );
         }),
         this.#query(name, "CAA").then(({ ret }) => {
@@ -359,18 +358,18 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
   }
 
   queryA(req: QueryReqWrap, name: string): number {
-    this.#query(name, "A").then(({ code, ret }) => {
-      req.oncomplete(code, ret);
+    this.#query(name, "A").then(({ code, ret, ttls }) => {
+      req.oncomplete(code, ret, ttls);
     });
 
     return 0;
   }
 
   queryAaaa(req: QueryReqWrap, name: string): number {
-    this.#query(name, "AAAA").then(({ code, ret }) => {
+    this.#query(name, "AAAA").then(({ code, ret, ttls }) => {
       const records = (ret as string[]).map((record) => compressIPv6(record));
 
-      req.oncomplete(code, records);
+      req.oncomplete(code, records, ttls);
     });
 
     return 0;
@@ -503,21 +502,35 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
     return 0;
   }
 
-  getHostByAddr(_req: QueryReqWrap, _name: string): number {
-    // TODO(@bartlomieju): https://github.com/denoland/deno/issues/14432
-    notImplemented("cares.ChannelWrap.prototype.getHostByAddr");
+  getHostByAddr(req: QueryReqWrap, name: string): number {
+    if (!isIP(name)) {
+      return codeMap.get("EINVAL")!;
+    }
+
+    const answer: Promise<DnsAnswer> = op_node_dns_reverse(
+      this.#channel(),
+      name,
+    );
+    answer.then(toResult).then(({ code, ret }) => {
+      const records = (ret as string[]).map((record) => fqdnToHostname(record));
+
+      req.oncomplete(code, records);
+    });
+
+    return 0;
   }
 
   getServers(): [string, number][] {
-    return this.#servers;
+    return this.#servers ?? op_node_dns_system_servers();
   }
 
   setServers(servers: string | [number, string, number][]): number {
     if (typeof servers === "string") {
+      const parts = servers ? servers.split(",") : [];
       const tuples: [string, number][] = [];
 
-      for (let i = 0; i < servers.length; i += 2) {
-        tuples.push([servers[i], parseInt(servers[i + 1])]);
+      for (let i = 0; i < parts.length; i += 2) {
+        tuples.push([parts[i], parseInt(parts[i + 1])]);
       }
 
       this.#servers = tuples;
@@ -525,6 +538,12 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
       this.#servers = servers.map(([_ipVersion, ip, port]) => [ip, port]);
     }
 
+    // The channel is created again with the new servers by the next query.
+    if (this.#rid !== null) {
+      core.tryClose(this.#rid);
+      this.#rid = null;
+    }
+
     return 0;
   }
 
@@ -533,7 +552,9 @@ export class ChannelWrap extends AsyncWrap implements ChannelWrapQuery {
   }
 
   cancel() {
-    notImplemented("cares.ChannelWrap.prototype.cancel");
+    if (this.#rid !== null) {
+      op_node_dns_channel_cancel(this.#rid);
+    }
   }
 }
 
@@ -105,6 +105,10 @@ import type { DuplexOptions } from "ext:deno_node/_stream.d.ts";
 import type { BufferEncoding } from "ext:deno_node/_global.d.ts";
 import type { Abortable } from "ext:deno_node/_events.d.ts";
 import { channel } from "node:diagnostics_channel";
+import {
+  BlockList,
+  SocketAddress,
+} from "ext:deno_node/internal/blocklist.mjs";
 
 let debug = debuglog("net", (fn) => {
   debug = fn;
@@ -2472,11 +2476,12 @@ export function createServer(
   return new Server(options, connectionListener);
 }
 
-export { isIP, isIPv4, isIPv6 };
+export { BlockList, isIP, isIPv4, isIPv6, SocketAddress };
 
 export default {
   _createServerHandle,
   _normalizeArgs,
+  BlockList,
   isIP,
   isIPv4,
   isIPv6,
@@ -2485,5 +2490,6 @@ export default {
   createServer,
   Server,
   Socket,
+  SocketAddress,
   Stream,
 };
@@ -27,6 +27,7 @@ import { getOptionValue } from "ext:deno_node/internal/options.ts";
 import { assert } from "ext:deno_node/_util/asserts.ts";
 import { join } from "node:path";
 import { pathFromURL } from "ext:deno_web/00_infra.js";
+import { opNowNs } from "ext:deno_web/02_timers.js";
 import {
   arch as arch_,
   chdir,
@@ -225,9 +226,9 @@ export function emitWarning(
 }
 
 export function hrtime(time?: [number, number]): [number, number] {
-  const milli = performance.now();
-  const sec = Math.floor(milli / 1000);
-  const nano = Math.floor(milli * 1_000_000 - sec * 1_000_000_000);
+  const now = opNowNs();
+  const sec = Number(now / 1_000_000_000n);
+  const nano = Number(now % 1_000_000_000n);
   if (!time) {
     return [sec, nano];
   }
@@ -236,8 +237,7 @@ export function hrtime(time?: [number, number]): [number, number] {
 }
 
 hrtime.bigint = function (): bigint {
-  const [sec, nano] = hrtime();
-  return BigInt(sec) * 1_000_000_000n + BigInt(nano);
+  return opNowNs();
 };
 
 export function memoryUsage(): {
@@ -6,15 +6,27 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
-import { core } from "ext:core/mod.js";
 import {
   op_v8_cached_data_version_tag,
+  op_v8_deserialize_value,
   op_v8_get_heap_statistics,
+  op_v8_serialize_value,
+  op_v8_serializer_header,
 } from "ext:core/ops";
 
 import { Buffer } from "node:buffer";
 
 import { notImplemented } from "ext:deno_node/_utils.ts";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_RETURN_VALUE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  isAnyArrayBuffer,
+  isArrayBuffer,
+  isArrayBufferView,
+} from "ext:deno_node/internal/util/types.ts";
+import { inspect } from "ext:deno_node/internal/util/inspect.mjs";
 
 export function cachedDataVersionTag() {
   return op_v8_cached_data_version_tag();
@@ -72,29 +84,347 @@ export function writeHeapSnapshot() {
   notImplemented("v8.writeHeapSnapshot");
 }
 export function serialize(value) {
-  return Buffer.from(core.serialize(value));
+  const ser = new DefaultSerializer();
+  ser.writeHeader();
+  ser.writeValue(value);
+  return ser.releaseBuffer();
+}
+export function deserialize(buffer) {
+  const der = new DefaultDeserializer(buffer);
+  der.readHeader();
+  return der.readValue();
+}
+
+let header: Uint8Array | undefined;
+
+// The header of the wire format written by V8, with its latest version.
+function wireFormatHeader() {
+  header ??= op_v8_serializer_header();
+  return header;
 }
-export function deserialize(data) {
-  return core.deserialize(data);
+
+function writeVarint(value: bigint): Uint8Array {
+  const bytes = [];
+  while (value >= 0x80n) {
+    bytes.push(Number(value & 0x7fn) | 0x80);
+    value >>= 7n;
+  }
+  bytes.push(Number(value));
+  return new Uint8Array(bytes);
 }
+
 export class Serializer {
-  constructor() {
-    notImplemented("v8.Serializer.prototype.constructor");
+  #chunks: Uint8Array[] = [];
+  #transfers: (number | ArrayBuffer)[] = [];
+  // Called by the ops while a value is written.
+  #delegate = {
+    writeHostObject: (object: object) => {
+      if (typeof this._writeHostObject !== "function") {
+        throw new this._getDataCloneError(
+          `#<${object.constructor?.name ?? "Object"}> could not be cloned.`,
+        );
+      }
+      const chunks = this.#chunks;
+      this.#chunks = [];
+      try {
+        this._writeHostObject(object);
+        return Buffer.concat(this.#chunks);
+      } finally {
+        this.#chunks = chunks;
+      }
+    },
+    getSharedArrayBufferId: (sharedArrayBuffer: SharedArrayBuffer) => {
+      if (typeof this._getSharedArrayBufferId !== "function") {
+        return undefined;
+      }
+      return this._getSharedArrayBufferId(sharedArrayBuffer);
+    },
+    getDataCloneError: (message: string) => this._getDataCloneError(message),
+  };
+
+  writeHeader() {
+    this.#chunks.push(wireFormatHeader());
+  }
+
+  writeValue(value: unknown) {
+    this.#chunks.push(
+      op_v8_serialize_value(value, this.#delegate, this.#transfers),
+    );
+    return true;
+  }
+
+  releaseBuffer(): Buffer {
+    const buffer = Buffer.concat(this.#chunks);
+    this.#chunks = [];
+    return buffer;
+  }
+
+  transferArrayBuffer(id: number, arrayBuffer: ArrayBuffer) {
+    if (!isArrayBuffer(arrayBuffer)) {
+      throw new ERR_INVALID_ARG_TYPE("arrayBuffer", "ArrayBuffer", arrayBuffer);
+    }
+    this.#transfers.push(id >>> 0, arrayBuffer);
+  }
+
+  writeUint32(value: number) {
+    this.#chunks.push(writeVarint(BigInt(value >>> 0)));
+  }
+
+  writeUint64(hi: number, lo: number) {
+    this.#chunks.push(
+      writeVarint((BigInt(hi >>> 0) << 32n) | BigInt(lo >>> 0)),
+    );
   }
+
+  writeDouble(value: number) {
+    const bytes = new Uint8Array(8);
+    new DataView(bytes.buffer).setFloat64(0, Number(value), true);
+    this.#chunks.push(bytes);
+  }
+
+  writeRawBytes(source: ArrayBufferView) {
+    if (!isArrayBufferView(source)) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "source",
+        ["Buffer", "TypedArray", "DataView"],
+        source,
+      );
+    }
+    this.#chunks.push(
+      new Uint8Array(source.buffer, source.byteOffset, source.byteLength)
+        .slice(),
+    );
+  }
+
+  // NOTE: V8 always serializes the array buffer views itself here, the
+  // option isn't available to embedders through rusty_v8 yet. This means
+  // that `Buffer`s are deserialized as `Uint8Array`s.
+  _setTreatArrayBufferViewsAsHostObjects(_flag: boolean) {}
 }
+
+Serializer.prototype._getDataCloneError = Error;
+
 export class Deserializer {
-  constructor() {
-    notImplemented("v8.Deserializer.prototype.constructor");
+  buffer: ArrayBufferView;
+  #bytes: Uint8Array;
+  #offset = 0;
+  #version = 0;
+  #transfers: (number | ArrayBuffer | SharedArrayBuffer)[] = [];
+  // Called by the ops while a value is read.
+  #delegate = {
+    readHostObject: (offset: number) => {
+      if (typeof this._readHostObject !== "function") {
+        return undefined;
+      }
+      this.#offset = offset;
+      const object = this._readHostObject();
+      if (
+        object === null ||
+        (typeof object !== "object" && typeof object !== "function")
+      ) {
+        throw new ERR_INVALID_RETURN_VALUE(
+          "an object",
+          "_readHostObject",
+          object,
+        );
+      }
+      return [object, this.#offset];
+    },
+  };
+
+  constructor(buffer: ArrayBufferView) {
+    if (!isArrayBufferView(buffer)) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "buffer",
+        ["Buffer", "TypedArray", "DataView"],
+        buffer,
+      );
+    }
+    this.buffer = buffer;
+    this.#bytes = new Uint8Array(
+      buffer.buffer,
+      buffer.byteOffset,
+      buffer.byteLength,
+    );
+  }
+
+  readHeader() {
+    if (this.#bytes[this.#offset] === 0xff) {
+      this.#offset++;
+      const version = this.#readVarint("ReadHeader");
+      // the latest version fits in a single byte
+      if (version > BigInt(wireFormatHeader()[1])) {
+        throw new Error(
+          "Unable to deserialize cloned data due to invalid or unsupported version.",
+        );
+      }
+      this.#version = Number(version);
+    }
+    return true;
+  }
+
+  readValue() {
+    const { 0: value, 1: offset } = op_v8_deserialize_value(
+      this.#bytes,
+      this.#offset,
+      this.#version,
+      this.#delegate,
+      this.#transfers,
+    );
+    this.#offset = offset;
+    return value;
+  }
+
+  getWireFormatVersion() {
+    return this.#version;
+  }
+
+  transferArrayBuffer(
+    id: number,
+    arrayBuffer: ArrayBuffer | SharedArrayBuffer,
+  ) {
+    if (!isAnyArrayBuffer(arrayBuffer)) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "arrayBuffer",
+        ["ArrayBuffer", "SharedArrayBuffer"],
+        arrayBuffer,
+      );
+    }
+    this.#transfers.push(id >>> 0, arrayBuffer);
+  }
+
+  #readVarint(method: string): bigint {
+    let value = 0n;
+    let shift = 0n;
+    while (true) {
+      if (this.#offset >= this.#bytes.length) {
+        throw new Error(`${method}() failed`);
+      }
+      const byte = this.#bytes[this.#offset++];
+      value |= BigInt(byte & 0x7f) << shift;
+      shift += 7n;
+      if (byte < 0x80) {
+        return value;
+      }
+    }
+  }
+
+  readUint32(): number {
+    return Number(BigInt.asUintN(32, this.#readVarint("ReadUint32")));
+  }
+
+  readUint64(): [number, number] {
+    const value = BigInt.asUintN(64, this.#readVarint("ReadUint64"));
+    return [Number(value >> 32n), Number(value & 0xffffffffn)];
+  }
+
+  readDouble(): number {
+    if (this.#offset + 8 > this.#bytes.length) {
+      throw new Error("ReadDouble() failed");
+    }
+    const value = new DataView(
+      this.#bytes.buffer,
+      this.#bytes.byteOffset + this.#offset,
+      8,
+    ).getFloat64(0, true);
+    this.#offset += 8;
+    return value;
+  }
+
+  // The offset of the bytes in `this.buffer`.
+  _readRawBytes(length: number): number {
+    if (this.#offset + length > this.#bytes.length) {
+      throw new Error("ReadRawBytes() failed");
+    }
+    const offset = this.#offset;
+    this.#offset += length;
+    return offset;
+  }
+
+  readRawBytes(length: number): Buffer {
+    const offset = this._readRawBytes(length);
+    return Buffer.from(
+      this.buffer.buffer,
+      this.buffer.byteOffset + offset,
+      length,
+    );
   }
 }
-export class DefaultSerializer {
+
+const arrayBufferViewTypes = [
+  Int8Array,
+  Uint8Array,
+  Uint8ClampedArray,
+  Int16Array,
+  Uint16Array,
+  Int32Array,
+  Uint32Array,
+  Float32Array,
+  Float64Array,
+  DataView,
+  Buffer,
+  BigInt64Array,
+  BigUint64Array,
+];
+
+const arrayBufferViewTypeToIndex = new Map(
+  arrayBufferViewTypes.map((ctor, i) => [ctor.name, i]),
+);
+
+export class DefaultSerializer extends Serializer {
   constructor() {
-    notImplemented("v8.DefaultSerializer.prototype.constructor");
+    super();
+    this._setTreatArrayBufferViewsAsHostObjects(true);
+  }
+
+  // The format of the array buffer views of Node, for the host objects
+  // written by V8.
+  _writeHostObject(abView: ArrayBufferView) {
+    let i = 10; // Buffer
+    if (abView.constructor !== Buffer) {
+      i = arrayBufferViewTypeToIndex.get(
+        Object.prototype.toString.call(abView).slice(8, -1),
+      );
+      if (i === undefined) {
+        throw new this._getDataCloneError(
+          `Unserializable host object: ${inspect(abView)}`,
+        );
+      }
+    }
+    this.writeUint32(i);
+    this.writeUint32(abView.byteLength);
+    this.writeRawBytes(
+      new Uint8Array(abView.buffer, abView.byteOffset, abView.byteLength),
+    );
   }
 }
-export class DefaultDeserializer {
-  constructor() {
-    notImplemented("v8.DefaultDeserializer.prototype.constructor");
+
+export class DefaultDeserializer extends Deserializer {
+  // Reads the array buffer views written by Node.
+  _readHostObject() {
+    const typeIndex = this.readUint32();
+    const ctor = arrayBufferViewTypes[typeIndex];
+    const byteLength = this.readUint32();
+    const byteOffset = this._readRawBytes(byteLength);
+    if (ctor === Buffer) {
+      return Buffer.from(
+        this.buffer.buffer,
+        this.buffer.byteOffset + byteOffset,
+        byteLength,
+      );
+    }
+    const BYTES_PER_ELEMENT = ctor.BYTES_PER_ELEMENT || 1;
+    const offset = this.buffer.byteOffset + byteOffset;
+    if (offset % BYTES_PER_ELEMENT === 0) {
+      return new ctor(
+        this.buffer.buffer,
+        offset,
+        byteLength / BYTES_PER_ELEMENT,
+      );
+    }
+    // Copy to an aligned buffer first.
+    const copy = this.buffer.buffer.slice(offset, offset + byteLength);
+    return new ctor(copy, 0, byteLength / BYTES_PER_ELEMENT);
   }
 }
 export const promiseHooks = {
@@ -4,30 +4,99 @@
 
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import {
+  op_vm_compile_function,
   op_vm_create_context,
   op_vm_create_script,
   op_vm_is_context,
   op_vm_script_run_in_context,
   op_vm_script_run_in_this_context,
 } from "ext:core/ops";
+import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  validateArray,
+  validateBoolean,
+  validateInt32,
+  validateObject,
+  validateOneOf,
+  validateString,
+  validateStringArray,
+  validateUint32,
+} from "ext:deno_node/internal/validators.mjs";
+
+let defaultContextNameIndex = 1;
+
+function validateContext(contextifiedObject: any) {
+  if (!isContext(contextifiedObject)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "contextifiedObject",
+      "vm.Context",
+      contextifiedObject,
+    );
+  }
+}
+
+// The timeout of the run options, 0 for none.
+function getTimeout(options: any): number {
+  if (options === undefined) {
+    return 0;
+  }
+  validateObject(options, "options");
+
+  const { timeout, displayErrors = true, breakOnSigint = false } = options;
+  validateBoolean(displayErrors, "options.displayErrors");
+  validateBoolean(breakOnSigint, "options.breakOnSigint");
+  if (timeout === undefined) {
+    return 0;
+  }
+  validateUint32(timeout, "options.timeout", true);
+  return timeout;
+}
 
 export class Script {
   #inner;
 
-  constructor(code: string, _options = {}) {
-    this.#inner = op_vm_create_script(code);
+  constructor(code: string, options: any = {}) {
+    code = `${code}`;
+    if (typeof options === "string") {
+      options = { filename: options };
+    } else {
+      validateObject(options, "options");
+    }
+
+    const {
+      filename = "evalmachine.<anonymous>",
+      lineOffset = 0,
+      columnOffset = 0,
+    } = options;
+    validateString(filename, "options.filename");
+    validateInt32(lineOffset, "options.lineOffset");
+    validateInt32(columnOffset, "options.columnOffset");
+
+    this.#inner = op_vm_create_script(
+      code,
+      filename,
+      lineOffset,
+      columnOffset,
+    );
   }
 
-  runInThisContext(_options: any) {
-    return op_vm_script_run_in_this_context(this.#inner);
+  runInThisContext(options: any) {
+    const timeout = getTimeout(options);
+    return op_vm_script_run_in_this_context(this.#inner, timeout);
   }
 
-  runInContext(contextifiedObject: any, _options: any) {
-    return op_vm_script_run_in_context(this.#inner, contextifiedObject);
+  runInContext(contextifiedObject: any, options: any) {
+    validateContext(contextifiedObject);
+    const timeout = getTimeout(options);
+    return op_vm_script_run_in_context(
+      this.#inner,
+      contextifiedObject,
+      timeout,
+    );
   }
 
   runInNewContext(contextObject: any, options: any) {
-    const context = createContext(contextObject);
+    const context = createContext(contextObject, getContextOptions(options));
     return this.runInContext(context, options);
   }
 
@@ -36,12 +105,58 @@ export class Script {
   }
 }
 
-export function createContext(contextObject: any = {}, _options: any) {
+// The options of `vm.createContext()` in the options of
+// `vm.runInNewContext()`.
+function getContextOptions(options: any) {
+  if (!options) {
+    return {};
+  }
+  return {
+    name: options.contextName,
+    origin: options.contextOrigin,
+    codeGeneration: options.contextCodeGeneration,
+    microtaskMode: options.microtaskMode,
+  };
+}
+
+export function createContext(contextObject: any = {}, options: any = {}) {
   if (isContext(contextObject)) {
     return contextObject;
   }
 
-  op_vm_create_context(contextObject);
+  validateObject(options, "options");
+  const {
+    name = `VM Context ${defaultContextNameIndex++}`,
+    origin,
+    codeGeneration,
+    microtaskMode,
+  } = options;
+  // The name and the origin are only shown by the inspector.
+  validateString(name, "options.name");
+  if (origin !== undefined) {
+    validateString(origin, "options.origin");
+  }
+
+  let strings = true;
+  if (codeGeneration !== undefined) {
+    validateObject(codeGeneration, "options.codeGeneration");
+    const { wasm = true } = codeGeneration;
+    ({ strings = true } = codeGeneration);
+    validateBoolean(strings, "options.codeGeneration.strings");
+    // TODO: disallow the compilation of WebAssembly with `wasm: false`.
+    validateBoolean(wasm, "options.codeGeneration.wasm");
+  }
+
+  validateOneOf(microtaskMode, "options.microtaskMode", [
+    "afterEvaluate",
+    undefined,
+  ]);
+
+  op_vm_create_context(
+    contextObject,
+    strings,
+    microtaskMode === "afterEvaluate",
+  );
   return contextObject;
 }
 
@@ -52,9 +167,12 @@ export function createScript(code: string, options: any) {
 export function runInContext(
   code: string,
   contextifiedObject: any,
-  _options: any,
+  options: any,
 ) {
-  return createScript(code).runInContext(contextifiedObject);
+  if (typeof options === "string") {
+    options = { filename: options };
+  }
+  return createScript(code, options).runInContext(contextifiedObject, options);
 }
 
 export function runInNewContext(
@@ -62,16 +180,19 @@ export function runInNewContext(
   contextObject: any,
   options: any,
 ) {
-  if (options) {
-    console.warn("vm.runInNewContext options are currently not supported");
+  if (typeof options === "string") {
+    options = { filename: options };
   }
-  return createScript(code).runInNewContext(contextObject);
+  return createScript(code, options).runInNewContext(contextObject, options);
 }
 
 export function runInThisContext(
   code: string,
   options: any,
 ) {
+  if (typeof options === "string") {
+    options = { filename: options };
+  }
   return createScript(code, options).runInThisContext(options);
 }
 
@@ -79,8 +200,46 @@ export function isContext(maybeContext: any) {
   return op_vm_is_context(maybeContext);
 }
 
-export function compileFunction(_code: string, _params: any, _options: any) {
-  notImplemented("compileFunction");
+export function compileFunction(
+  code: string,
+  params: string[] = [],
+  options: any = {},
+) {
+  validateString(code, "code");
+  validateStringArray(params, "params");
+  validateObject(options, "options");
+
+  const {
+    filename = "",
+    lineOffset = 0,
+    columnOffset = 0,
+    parsingContext,
+    contextExtensions = [],
+  } = options;
+  validateString(filename, "options.filename");
+  validateInt32(lineOffset, "options.lineOffset");
+  validateInt32(columnOffset, "options.columnOffset");
+  if (parsingContext !== undefined && !isContext(parsingContext)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "options.parsingContext",
+      "Context",
+      parsingContext,
+    );
+  }
+  validateArray(contextExtensions, "options.contextExtensions");
+  contextExtensions.forEach((extension: any, i: number) => {
+    validateObject(extension, `options.contextExtensions[${i}]`);
+  });
+
+  return op_vm_compile_function(
+    code,
+    filename,
+    lineOffset,
+    columnOffset,
+    params,
+    parsingContext,
+    contextExtensions,
+  );
 }
 
 export function measureMemory(_options: any) {
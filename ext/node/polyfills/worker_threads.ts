@@ -27,12 +27,13 @@ import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { EventEmitter } from "node:events";
 import { BroadcastChannel } from "ext:deno_broadcast_channel/01_broadcast_channel.js";
-import process from "node:process";
+import process, { nextTick } from "node:process";
 
 const { JSONParse, JSONStringify, ObjectPrototypeIsPrototypeOf } = primordials;
 const {
+  ArrayPrototypeForEach,
   Error,
-  PromiseResolve,
+  Promise,
   Symbol,
   SymbolFor,
   SymbolIterator,
@@ -85,6 +86,12 @@ class NodeWorker extends EventEmitter {
   // indicating that the worker is no longer running, but there might
   // still be messages left to receive.
   #status = "RUNNING";
+  // Resolved with the exit code once "exit" is emitted.
+  #exited: Promise<number>;
+  #resolveExited!: (exitCode: number) => void;
+  // Resolved once all the messages of the worker are received.
+  #messagesReceived: Promise<void>;
+  #resolveMessagesReceived!: () => void;
 
   // https://nodejs.org/api/worker_threads.html#workerthreadid
   threadId = this.#id;
@@ -100,6 +107,12 @@ class NodeWorker extends EventEmitter {
 
   constructor(specifier: URL | string, options?: WorkerOptions) {
     super();
+    this.#exited = new Promise((resolve) => {
+      this.#resolveExited = resolve;
+    });
+    this.#messagesReceived = new Promise((resolve) => {
+      this.#resolveMessagesReceived = resolve;
+    });
 
     if (
       typeof specifier === "object" &&
@@ -205,7 +218,11 @@ class NodeWorker extends EventEmitter {
       switch (type) {
         case 1: { // TerminalError
           this.#status = "CLOSED";
-        } /* falls through */
+          this.#handleError(data);
+          // like an uncaught exception in Node
+          this.#exit(1);
+          return;
+        }
         case 2: { // Error
           this.#handleError(data);
           break;
@@ -213,6 +230,9 @@ class NodeWorker extends EventEmitter {
         case 3: { // Close
           debugWT(`Host got "close" message from worker: ${this.#name}`);
           this.#status = "CLOSED";
+          // the messages posted before closing come first
+          await this.#messagesReceived;
+          this.#exit(data);
           return;
         }
         default: {
@@ -230,21 +250,30 @@ class NodeWorker extends EventEmitter {
       }
       const data = await this.#messagePromise;
       if (this.#status === "TERMINATED" || data === null) {
-        return;
+        break;
       }
-      let message, _transferables;
+      let message, transferables;
       try {
         const v = deserializeJsMessageData(data);
         message = v[0];
-        _transferables = v[1];
+        transferables = v[1];
       } catch (err) {
         this.emit("messageerror", err);
-        return;
+        break;
       }
+      patchMessagePorts(transferables);
       this.emit("message", message);
     }
+    this.#resolveMessagesReceived();
   };
 
+  #exit(exitCode: number) {
+    nextTick(() => {
+      this.emit("exit", exitCode);
+      this.#resolveExited(exitCode);
+    });
+  }
+
   postMessage(message, transferOrOptions = {}) {
     const prefix = "Failed to execute 'postMessage' on 'MessagePort'";
     webidl.requiredArguments(arguments.length, 1, prefix);
@@ -276,13 +305,13 @@ class NodeWorker extends EventEmitter {
   }
 
   // https://nodejs.org/api/worker_threads.html#workerterminate
-  terminate() {
-    if (this.#status !== "TERMINATED") {
+  terminate(): Promise<number> {
+    if (this.#status === "RUNNING") {
       this.#status = "TERMINATED";
       op_host_terminate_worker(this.#id);
+      this.#exit(1);
     }
-    this.emit("exit", 0);
-    return PromiseResolve(0);
+    return this.#exited;
   }
 
   ref() {
@@ -357,7 +386,8 @@ internals.__initWorkerThreads = (
     parentPort = self as ParentPort;
     threadId = workerId;
     if (maybeWorkerMetadata) {
-      const { 0: metadata, 1: _ } = maybeWorkerMetadata;
+      const { 0: metadata, 1: transferables } = maybeWorkerMetadata;
+      patchMessagePorts(transferables);
       workerData = metadata.workerData;
       environmentData = metadata.environmentData;
       const env = metadata.env;
@@ -369,8 +399,6 @@ internals.__initWorkerThreads = (
     defaultExport.parentPort = parentPort;
     defaultExport.threadId = threadId;
 
-    workerData = patchMessagePortIfFound(workerData);
-
     parentPort.off = parentPort.removeListener = function (
       this: ParentPort,
       name,
@@ -387,9 +415,8 @@ internals.__initWorkerThreads = (
     ) {
       // deno-lint-ignore no-explicit-any
       const _listener = (ev: any) => {
-        let message = ev.data;
-        message = patchMessagePortIfFound(message);
-        return listener(message);
+        patchMessagePorts(ev.ports);
+        return listener(ev.data);
       };
       listeners.set(listener, _listener);
       this.addEventListener(name, _listener);
@@ -398,9 +425,12 @@ internals.__initWorkerThreads = (
 
     parentPort.once = function (this: ParentPort, name, listener) {
       // deno-lint-ignore no-explicit-any
-      const _listener = (ev: any) => listener(ev.data);
+      const _listener = (ev: any) => {
+        patchMessagePorts(ev.ports);
+        return listener(ev.data);
+      };
       listeners.set(listener, _listener);
-      this.addEventListener(name, _listener);
+      this.addEventListener(name, _listener, { once: true });
       return this;
     };
 
@@ -484,7 +514,13 @@ const listeners = new SafeWeakMap<
 function webMessagePortToNodeMessagePort(port: MessagePort) {
   port.on = port.addListener = function (this: MessagePort, name, listener) {
     // deno-lint-ignore no-explicit-any
-    const _listener = (ev: any) => listener(ev.data);
+    const _listener = (ev: any) => {
+      // the ports transferred along with the message
+      if (ev.ports) {
+        patchMessagePorts(ev.ports);
+      }
+      return listener(ev.data);
+    };
     if (name == "message") {
       if (port.onmessage === null) {
         port.onmessage = _listener;
@@ -534,19 +570,14 @@ function webMessagePortToNodeMessagePort(port: MessagePort) {
   return port;
 }
 
-// deno-lint-ignore no-explicit-any
-function patchMessagePortIfFound(data: any) {
-  if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, data)) {
-    data = webMessagePortToNodeMessagePort(data);
-  } else {
-    for (const obj in data as Record<string, unknown>) {
-      if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, data[obj])) {
-        data[obj] = webMessagePortToNodeMessagePort(data[obj] as MessagePort);
-        break;
-      }
+// Gives the Node API to the message ports transferred to this thread, which
+// are the same objects as the ones found in the message.
+function patchMessagePorts(transferables: unknown[]) {
+  ArrayPrototypeForEach(transferables, (transferable) => {
+    if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, transferable)) {
+      webMessagePortToNodeMessagePort(transferable as MessagePort);
     }
-  }
-  return data;
+  });
 }
 
 export {
@@ -2,7 +2,11 @@
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { zlib as constants } from "ext:deno_node/internal_binding/constants.ts";
 import {
+  BrotliCompress,
+  BrotliDecompress,
   codes,
+  createBrotliCompress,
+  createBrotliDecompress,
   createDeflate,
   createDeflateRaw,
   createGunzip,
@@ -37,8 +41,6 @@ import {
   brotliCompressSync,
   brotliDecompress,
   brotliDecompressSync,
-  createBrotliCompress,
-  createBrotliDecompress,
 } from "ext:deno_node/_brotli.js";
 
 export class Options {
@@ -60,16 +62,6 @@ export class BrotliOptions {
     notImplemented("BrotliOptions.prototype.constructor");
   }
 }
-export class BrotliCompress {
-  constructor() {
-    notImplemented("BrotliCompress.prototype.constructor");
-  }
-}
-export class BrotliDecompress {
-  constructor() {
-    notImplemented("BrotliDecompress.prototype.constructor");
-  }
-}
 export class ZlibBase {
   constructor() {
     notImplemented("ZlibBase.prototype.constructor");
@@ -123,8 +115,10 @@ export default {
 
 export {
   brotliCompress,
+  BrotliCompress,
   brotliCompressSync,
   brotliDecompress,
+  BrotliDecompress,
   brotliDecompressSync,
   codes,
   createBrotliCompress,
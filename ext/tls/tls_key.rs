@@ -28,6 +28,7 @@ use std::future::ready;
 use std::future::Future;
 use std::io::ErrorKind;
 use std::rc::Rc;
+use std::rc::Weak;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc;
@@ -155,16 +156,17 @@ impl Debug for TlsKeyResolver {
 
 pub fn new_resolver() -> (TlsKeyResolver, TlsKeyLookup) {
   let (resolution_tx, resolution_rx) = mpsc::unbounded_channel();
+  let inner = Rc::new(TlsKeyResolverInner {
+    resolution_tx,
+    cache: Default::default(),
+  });
+  let resolver = Rc::downgrade(&inner);
   (
-    TlsKeyResolver {
-      inner: Rc::new(TlsKeyResolverInner {
-        resolution_tx,
-        cache: Default::default(),
-      }),
-    },
+    TlsKeyResolver { inner },
     TlsKeyLookup {
       resolution_rx: RefCell::new(resolution_rx),
       pending: Default::default(),
+      resolver,
     },
   )
 }
@@ -198,7 +200,13 @@ impl TlsKeyResolver {
       let mut cache = inner.cache.borrow_mut();
       match cache.get(&sni) {
         None | Some(TlsKeyState::Resolving(..)) => {
-          cache.insert(sni, TlsKeyState::Resolved(res.clone()));
+          if res.is_ok() {
+            cache.insert(sni, TlsKeyState::Resolved(res.clone()));
+          } else {
+            // Failures are not cached so that a later handshake retries the
+            // lookup (ie: once a certificate has been issued for the host)
+            cache.remove(&sni);
+          }
         }
         Some(TlsKeyState::Resolved(..)) => {
           // Someone beat us to it
@@ -220,6 +228,8 @@ pub struct TlsKeyLookup {
   >,
   pending:
     RefCell<HashMap<String, broadcast::Sender<Result<TlsKey, ErrorType>>>>,
+  /// Weak so that dropping the resolver still closes the lookup queue.
+  resolver: Weak<TlsKeyResolverInner>,
 }
 
 impl TlsKeyLookup {
@@ -245,6 +255,18 @@ impl TlsKeyLookup {
       .unwrap()
       .send(res.map_err(Rc::new));
   }
+
+  /// Evict a resolved key from the resolver cache, so that the next handshake
+  /// for this host looks it up again (ie: after a certificate was renewed).
+  pub fn evict(&self, sni: &str) {
+    let Some(inner) = self.resolver.upgrade() else {
+      return;
+    };
+    let mut cache = inner.cache.borrow_mut();
+    if let Some(TlsKeyState::Resolved(..)) = cache.get(sni) {
+      cache.remove(sni);
+    }
+  }
 }
 
 #[cfg(test)]
@@ -318,4 +340,58 @@ pub mod tests {
 
     task.await.unwrap();
   }
+
+  #[tokio::test]
+  async fn test_resolve_error_not_cached() {
+    let (resolver, lookup) = new_resolver();
+    let task = spawn(async move {
+      let mut attempts = 0;
+      while let Some(sni) = lookup.poll().await {
+        attempts += 1;
+        if attempts == 1 {
+          lookup.resolve(sni, Err(anyhow!("Not issued yet")));
+        } else {
+          lookup.resolve(sni.clone(), Ok(tls_key_for_test(&sni)));
+        }
+      }
+      attempts
+    });
+
+    assert!(resolver.resolve("example.com".to_owned()).await.is_err());
+    let key = resolver.resolve("example.com".to_owned()).await.unwrap();
+    assert_eq!(tls_key_for_test("example.com"), key);
+    // The successful lookup is cached
+    let key = resolver.resolve("example.com".to_owned()).await.unwrap();
+    assert_eq!(tls_key_for_test("example.com"), key);
+    drop(resolver);
+
+    assert_eq!(task.await.unwrap(), 2);
+  }
+
+  #[tokio::test]
+  async fn test_resolve_evict() {
+    let (resolver, lookup) = new_resolver();
+    let lookup = Rc::new(lookup);
+    let task = spawn({
+      let lookup = lookup.clone();
+      async move {
+        let mut attempts = 0;
+        while let Some(sni) = lookup.poll().await {
+          attempts += 1;
+          lookup.resolve(sni.clone(), Ok(tls_key_for_test(&sni)));
+        }
+        attempts
+      }
+    });
+
+    resolver.resolve("example.com".to_owned()).await.unwrap();
+    resolver.resolve("example.com".to_owned()).await.unwrap();
+    lookup.evict("example.com");
+    resolver.resolve("example.com".to_owned()).await.unwrap();
+    drop(resolver);
+    // evicting once the resolver is gone is a no-op
+    lookup.evict("example.com");
+
+    assert_eq!(task.await.unwrap(), 2);
+  }
 }
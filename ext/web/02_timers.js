@@ -1,7 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 import { core, primordials } from "ext:core/mod.js";
-import { op_defer, op_now } from "ext:core/ops";
+import { op_defer, op_now, op_now_ns } from "ext:core/ops";
 const {
   Uint8Array,
   Uint32Array,
@@ -21,6 +21,11 @@ function opNow() {
   return (hr[0] * 1000 + hr[1] / 1e6);
 }
 
+/** Nanoseconds since the runtime started, as a bigint. */
+function opNowNs() {
+  return op_now_ns();
+}
+
 // ---------------------------------------------------------------------------
 
 function checkThis(thisArg) {
@@ -132,6 +137,7 @@ export {
   clearTimeout,
   defer,
   opNow,
+  opNowNs,
   refTimer,
   setImmediate,
   setInterval,
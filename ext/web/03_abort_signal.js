@@ -3,7 +3,7 @@
 // @ts-check
 /// <reference path="../../core/internal.d.ts" />
 
-import { primordials } from "ext:core/mod.js";
+import { core, primordials } from "ext:core/mod.js";
 const {
   ArrayPrototypeEvery,
   ArrayPrototypePush,
@@ -355,12 +355,45 @@ function createDependentAbortSignal(signals, prefix) {
   return resultSignal;
 }
 
+const noCancelHandle = {
+  rid: undefined,
+  unlink() {},
+};
+
+/**
+ * Creates a cancel handle for the ops that `signal` aborts, which is closed
+ * when it aborts, directly or through the signals it depends on. `unlink` has
+ * to be called once the ops settled: it closes the handle and throws the abort
+ * reason if the signal aborted meanwhile.
+ * @param {AbortSignal | undefined} signal
+ * @returns {{ rid: number | undefined, unlink: () => void }}
+ */
+function linkCancelHandle(signal) {
+  if (signal === undefined || signal === null) {
+    return noCancelHandle;
+  }
+  signal.throwIfAborted();
+  const rid = core.createCancelHandle();
+  const abortHandler = () => core.tryClose(rid);
+  signal[add](abortHandler);
+  return {
+    rid,
+    unlink() {
+      signal[remove](abortHandler);
+      core.tryClose(rid);
+      // always throw the abort error when aborted
+      signal.throwIfAborted();
+    },
+  };
+}
+
 export {
   AbortController,
   AbortSignal,
   AbortSignalPrototype,
   add,
   createDependentAbortSignal,
+  linkCancelHandle,
   newSignal,
   remove,
   signalAbort,
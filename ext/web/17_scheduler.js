@@ -0,0 +1,229 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// @ts-check
+/// <reference path="../../core/internal.d.ts" />
+
+import { core, primordials } from "ext:core/mod.js";
+const {
+  ArrayPrototypeIndexOf,
+  ArrayPrototypePush,
+  ArrayPrototypeShift,
+  ArrayPrototypeSplice,
+  ArrayPrototypeUnshift,
+  ObjectPrototypeIsPrototypeOf,
+  Promise,
+  Symbol,
+  SymbolFor,
+} = primordials;
+
+import * as webidl from "ext:deno_webidl/00_webidl.js";
+import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
+import { add, remove } from "./03_abort_signal.js";
+import { clearTimeout, setTimeout } from "./02_timers.js";
+
+const illegalConstructorKey = Symbol("illegalConstructorKey");
+
+// Ordered from highest to lowest priority.
+const TASK_PRIORITIES = ["user-blocking", "user-visible", "background"];
+
+webidl.converters.TaskPriority = webidl.createEnumConverter(
+  "TaskPriority",
+  TASK_PRIORITIES,
+);
+
+webidl.converters.SchedulerPostTaskOptions = webidl
+  .createDictionaryConverter(
+    "SchedulerPostTaskOptions",
+    [
+      {
+        key: "signal",
+        converter: webidl.converters.AbortSignal,
+      },
+      {
+        key: "priority",
+        converter: webidl.converters.TaskPriority,
+      },
+      {
+        key: "delay",
+        converter: webidl.converters["unsigned long long"],
+        defaultValue: 0,
+      },
+    ],
+  );
+
+/** One queue of pending tasks per priority. */
+const taskQueues = {
+  "user-blocking": [],
+  "user-visible": [],
+  "background": [],
+};
+let dispatchQueued = false;
+
+function nextTask() {
+  for (let i = 0; i < TASK_PRIORITIES.length; ++i) {
+    const queue = taskQueues[TASK_PRIORITIES[i]];
+    if (queue.length > 0) {
+      return ArrayPrototypeShift(queue);
+    }
+  }
+  return undefined;
+}
+
+function hasPendingTasks() {
+  for (let i = 0; i < TASK_PRIORITIES.length; ++i) {
+    if (taskQueues[TASK_PRIORITIES[i]].length > 0) {
+      return true;
+    }
+  }
+  return false;
+}
+
+// Only a single task is run per event loop turn, so that I/O and higher
+// priority tasks posted in the meantime get a chance to run in between.
+function queueDispatch() {
+  if (dispatchQueued) {
+    return;
+  }
+  dispatchQueued = true;
+  core.queueImmediate(dispatch);
+}
+
+function dispatch() {
+  dispatchQueued = false;
+  const task = nextTask();
+  if (task === undefined) {
+    return;
+  }
+  if (hasPendingTasks()) {
+    queueDispatch();
+  }
+  if (task.signal !== undefined) {
+    task.signal[remove](task.abortAlgorithm);
+  }
+  try {
+    task.resolve(task.callback());
+  } catch (error) {
+    task.reject(error);
+  }
+}
+
+function enqueueTask(task, front = false) {
+  const queue = taskQueues[task.priority];
+  if (front) {
+    ArrayPrototypeUnshift(queue, task);
+  } else {
+    ArrayPrototypePush(queue, task);
+  }
+  queueDispatch();
+}
+
+function dequeueTask(task) {
+  const queue = taskQueues[task.priority];
+  const index = ArrayPrototypeIndexOf(queue, task);
+  if (index !== -1) {
+    ArrayPrototypeSplice(queue, index, 1);
+  }
+}
+
+class Scheduler {
+  constructor(key = null) {
+    if (key !== illegalConstructorKey) {
+      webidl.illegalConstructor();
+    }
+    this[webidl.brand] = webidl.brand;
+  }
+
+  /**
+   * @param {() => any} callback
+   * @param {{ signal?: AbortSignal, priority?: string, delay?: number }} options
+   * @returns {Promise<any>}
+   */
+  postTask(callback, options = {}) {
+    webidl.assertBranded(this, SchedulerPrototype);
+    const prefix = "Failed to execute 'postTask' on 'Scheduler'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    callback = webidl.converters.Function(callback, prefix, "Argument 1");
+    options = webidl.converters.SchedulerPostTaskOptions(
+      options,
+      prefix,
+      "Argument 2",
+    );
+
+    const signal = options.signal;
+    return new Promise((resolve, reject) => {
+      if (signal?.aborted) {
+        reject(signal.reason);
+        return;
+      }
+
+      const task = {
+        callback,
+        resolve,
+        reject,
+        priority: options.priority ?? "user-visible",
+        signal,
+        abortAlgorithm: undefined,
+        timerId: null,
+      };
+      if (signal !== undefined) {
+        task.abortAlgorithm = () => {
+          if (task.timerId !== null) {
+            clearTimeout(task.timerId);
+            task.timerId = null;
+          } else {
+            dequeueTask(task);
+          }
+          reject(signal.reason);
+        };
+        signal[add](task.abortAlgorithm);
+      }
+
+      if (options.delay > 0) {
+        task.timerId = setTimeout(() => {
+          task.timerId = null;
+          enqueueTask(task);
+        }, options.delay);
+      } else {
+        enqueueTask(task);
+      }
+    });
+  }
+
+  /**
+   * Yields to the event loop. The returned promise resolves ahead of other
+   * pending `user-visible` tasks.
+   * @returns {Promise<void>}
+   */
+  yield() {
+    webidl.assertBranded(this, SchedulerPrototype);
+    return new Promise((resolve, reject) => {
+      enqueueTask({
+        callback: () => {},
+        resolve,
+        reject,
+        priority: "user-visible",
+        signal: undefined,
+        abortAlgorithm: undefined,
+        timerId: null,
+      }, true);
+    });
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(SchedulerPrototype, this),
+        keys: [],
+      }),
+      inspectOptions,
+    );
+  }
+}
+
+webidl.configureInterface(Scheduler);
+const SchedulerPrototype = Scheduler.prototype;
+
+const scheduler = new Scheduler(illegalConstructorKey);
+
+export { Scheduler, scheduler };
@@ -1265,3 +1265,53 @@ declare var ImageData: {
     settings?: ImageDataSettings,
   ): ImageData;
 };
+
+/** @category Platform */
+declare type TaskPriority = "user-blocking" | "user-visible" | "background";
+
+/** @category Platform */
+declare interface SchedulerPostTaskOptions {
+  /** A signal that aborts the task, rejecting the returned promise with the
+   * signal's reason if the task hasn't run yet. */
+  signal?: AbortSignal;
+  /** The priority of the task.
+   *
+   * @default {"user-visible"} */
+  priority?: TaskPriority;
+  /** The minimum amount of milliseconds to wait before queueing the task.
+   *
+   * @default {0} */
+  delay?: number;
+}
+
+/** Schedules tasks with a priority. Pending `user-blocking` tasks run before
+ * `user-visible` tasks, which run before `background` tasks. Priorities only
+ * order the tasks of the scheduler among each other, not against timers,
+ * I/O callbacks or other tasks of the event loop.
+ *
+ * ```ts
+ * await scheduler.postTask(() => cleanup(), { priority: "background" });
+ * ```
+ *
+ * @category Platform
+ */
+declare interface Scheduler {
+  /** Queues `callback` to run as a task with the given priority. The returned
+   * promise resolves with the value returned by `callback`. */
+  postTask<T>(
+    callback: () => T | PromiseLike<T>,
+    options?: SchedulerPostTaskOptions,
+  ): Promise<T>;
+  /** Yields to the event loop, resolving ahead of other pending
+   * `user-visible` tasks. */
+  yield(): Promise<void>;
+}
+
+/** @category Platform */
+declare var Scheduler: {
+  readonly prototype: Scheduler;
+  new (): never;
+};
+
+/** @category Platform */
+declare var scheduler: Scheduler;
@@ -55,6 +55,7 @@ pub use crate::message_port::Transferable;
 
 use crate::timers::op_defer;
 use crate::timers::op_now;
+use crate::timers::op_now_ns;
 use crate::timers::StartTime;
 pub use crate::timers::TimersPermission;
 
@@ -87,6 +88,7 @@ deno_core::extension!(deno_web,
     compression::op_compression_write,
     compression::op_compression_finish,
     op_now<P>,
+    op_now_ns<P>,
     op_defer,
     op_transfer_arraybuffer,
     stream_resource::op_readable_stream_resource_allocate,
@@ -117,6 +119,7 @@ deno_core::extension!(deno_web,
     "14_compression.js",
     "15_performance.js",
     "16_image_data.js",
+    "17_scheduler.js",
   ],
   options = {
     blob_store: Arc<BlobStore>,
@@ -12,12 +12,10 @@ pub trait TimersPermission {
 
 pub type StartTime = Instant;
 
-// Returns a milliseconds and nanoseconds subsec
-// since the start time of the deno runtime.
-// If the High precision flag is not set, the
-// nanoseconds are rounded on 2ms.
-#[op2(fast)]
-pub fn op_now<TP>(state: &mut OpState, #[buffer] buf: &mut [u8])
+/// Returns the time elapsed since the start time of the deno runtime. If the
+/// high precision permission is not granted, the nanoseconds are rounded on
+/// 2ms.
+fn elapsed<TP>(state: &mut OpState) -> (u64, u32)
 where
   TP: TimersPermission + 'static,
 {
@@ -33,6 +31,19 @@ where
     let reduced_time_precision = 2_000_000; // 2ms in nanoseconds
     subsec_nanos -= subsec_nanos % reduced_time_precision;
   }
+  (seconds, subsec_nanos)
+}
+
+// Returns a milliseconds and nanoseconds subsec
+// since the start time of the deno runtime.
+// If the High precision flag is not set, the
+// nanoseconds are rounded on 2ms.
+#[op2(fast)]
+pub fn op_now<TP>(state: &mut OpState, #[buffer] buf: &mut [u8])
+where
+  TP: TimersPermission + 'static,
+{
+  let (seconds, subsec_nanos) = elapsed::<TP>(state);
   if buf.len() < 8 {
     return;
   }
@@ -43,6 +54,21 @@ where
   buf[1] = subsec_nanos;
 }
 
+// Returns the nanoseconds since the start time of the deno runtime as a
+// bigint, so callers like `process.hrtime.bigint()` don't lose precision
+// going through a millisecond float.
+// If the High precision flag is not set, the
+// nanoseconds are rounded on 2ms.
+#[op2(fast)]
+#[bigint]
+pub fn op_now_ns<TP>(state: &mut OpState) -> u64
+where
+  TP: TimersPermission + 'static,
+{
+  let (seconds, subsec_nanos) = elapsed::<TP>(state);
+  seconds * 1_000_000_000 + subsec_nanos as u64
+}
+
 #[allow(clippy::unused_async)]
 #[op2(async(lazy), fast)]
 pub async fn op_defer() {}
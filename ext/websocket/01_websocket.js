@@ -20,6 +20,8 @@ import {
   op_ws_send_binary_ab,
   op_ws_send_ping,
   op_ws_send_text,
+  op_ws_set_max_buffered_amount,
+  op_ws_wait_drain,
 } from "ext:core/ops";
 const {
   ArrayBufferIsView,
@@ -116,6 +118,9 @@ const _server = Symbol("[[server]]");
 const _idleTimeoutDuration = Symbol("[[idleTimeout]]");
 const _idleTimeoutTimeout = Symbol("[[idleTimeoutTimeout]]");
 const _serverHandleIdleTimeout = Symbol("[[serverHandleIdleTimeout]]");
+const _maxBufferedAmount = Symbol("[[maxBufferedAmount]]");
+const _drainPending = Symbol("[[drainPending]]");
+const _waitForDrain = Symbol("[[waitForDrain]]");
 class WebSocket extends EventTarget {
   constructor(url, protocols = []) {
     super();
@@ -129,6 +134,8 @@ class WebSocket extends EventTarget {
     this[_binaryType] = "blob";
     this[_idleTimeoutDuration] = 0;
     this[_idleTimeoutTimeout] = undefined;
+    this[_maxBufferedAmount] = 0;
+    this[_drainPending] = false;
     const prefix = "Failed to construct 'WebSocket'";
     webidl.requiredArguments(arguments.length, 1, prefix);
     url = webidl.converters.USVString(url, prefix, "Argument 1");
@@ -326,23 +333,64 @@ class WebSocket extends EventTarget {
       throw new DOMException("readyState not OPEN", "InvalidStateError");
     }
 
+    let sent;
     if (ArrayBufferIsView(data)) {
-      op_ws_send_binary(this[_rid], data);
+      sent = op_ws_send_binary(this[_rid], data);
     } else if (isArrayBuffer(data)) {
-      op_ws_send_binary(this[_rid], new Uint8Array(data));
+      sent = op_ws_send_binary(this[_rid], new Uint8Array(data));
     } else if (ObjectPrototypeIsPrototypeOf(BlobPrototype, data)) {
+      // A Blob is only checked against the limit once it has been read, so
+      // when it doesn't fit it is dropped without throwing.
       PromisePrototypeThen(
         // deno-lint-ignore prefer-primordials
         data.slice().arrayBuffer(),
-        (ab) => op_ws_send_binary_ab(this[_rid], ab),
+        (ab) => {
+          if (!op_ws_send_binary_ab(this[_rid], ab)) {
+            this[_waitForDrain]();
+          }
+        },
       );
+      return;
     } else {
       const string = String(data);
-      op_ws_send_text(
+      sent = op_ws_send_text(
         this[_rid],
         string,
       );
     }
+
+    if (!sent) {
+      this[_waitForDrain]();
+      throw new DOMException(
+        `The WebSocket outbound queue is full (maxBufferedAmount is ${
+          this[_maxBufferedAmount]
+        } bytes)`,
+        "QuotaExceededError",
+      );
+    }
+  }
+
+  /** Dispatches a "drain" event once the outbound queue has been flushed
+   * after a message was rejected. */
+  [_waitForDrain]() {
+    if (this[_drainPending]) {
+      return;
+    }
+    this[_drainPending] = true;
+    PromisePrototypeThen(
+      op_ws_wait_drain(this[_rid]),
+      (drained) => {
+        this[_drainPending] = false;
+        if (drained && this[_readyState] === OPEN) {
+          const event = new Event("drain");
+          setIsTrusted(event, true);
+          dispatch(this, event);
+        }
+      },
+      () => {
+        this[_drainPending] = false;
+      },
+    );
   }
 
   close(code = undefined, reason = undefined) {
@@ -411,6 +459,9 @@ class WebSocket extends EventTarget {
 
   async [_eventLoop]() {
     const rid = this[_rid];
+    if (this[_maxBufferedAmount]) {
+      op_ws_set_max_buffered_amount(rid, this[_maxBufferedAmount]);
+    }
     while (this[_readyState] !== CLOSED) {
       const kind = await op_ws_next_event(rid);
 
@@ -608,6 +659,8 @@ function createWebSocketBranded() {
   socket[_binaryType] = "arraybuffer";
   socket[_idleTimeoutDuration] = 0;
   socket[_idleTimeoutTimeout] = undefined;
+  socket[_maxBufferedAmount] = 0;
+  socket[_drainPending] = false;
   return socket;
 }
 
@@ -615,6 +668,7 @@ export {
   _eventLoop,
   _idleTimeoutDuration,
   _idleTimeoutTimeout,
+  _maxBufferedAmount,
   _protocol,
   _readyState,
   _rid,
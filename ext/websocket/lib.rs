@@ -55,6 +55,7 @@ use tokio::io::AsyncWrite;
 use tokio::io::ReadHalf;
 use tokio::io::WriteHalf;
 use tokio::net::TcpStream;
+use tokio::sync::Notify;
 
 use fastwebsockets::CloseCode;
 use fastwebsockets::FragmentCollectorRead;
@@ -464,6 +465,10 @@ pub enum MessageKind {
 /// To avoid locks, we keep as much as we can inside of [`Cell`]s.
 pub struct ServerWebSocket {
   buffered: Cell<usize>,
+  /// The most bytes that may be queued for sending, or 0 for no limit.
+  max_buffered: Cell<usize>,
+  /// Notified when the outbound queue is flushed, fails or is closed.
+  drain: Notify,
   error: Cell<Option<String>>,
   errored: Cell<bool>,
   closed: Cell<bool>,
@@ -478,6 +483,8 @@ impl ServerWebSocket {
     let (ws_read, ws_write) = ws.split(tokio::io::split);
     Self {
       buffered: Cell::new(0),
+      max_buffered: Cell::new(0),
+      drain: Notify::new(),
       error: Cell::new(None),
       errored: Cell::new(false),
       closed: Cell::new(false),
@@ -498,6 +505,26 @@ impl ServerWebSocket {
     }
   }
 
+  /// Adds `len` bytes to the outbound queue, unless they would exceed the
+  /// limit.
+  fn try_buffer(&self, len: usize) -> bool {
+    let buffered = self.buffered.get() + len;
+    let max = self.max_buffered.get();
+    if max != 0 && buffered > max {
+      return false;
+    }
+    self.buffered.set(buffered);
+    true
+  }
+
+  fn release_buffered(&self, len: usize) {
+    let buffered = self.buffered.get() - len;
+    self.buffered.set(buffered);
+    if buffered == 0 {
+      self.drain.notify_waiters();
+    }
+  }
+
   /// Reserve a lock, but don't wait on it. This gets us our place in line.
   fn reserve_lock(
     self: &Rc<Self>,
@@ -526,6 +553,11 @@ impl Resource for ServerWebSocket {
   fn name(&self) -> Cow<str> {
     "serverWebSocket".into()
   }
+
+  fn close(self: Rc<Self>) {
+    self.closed.set(true);
+    self.drain.notify_waiters();
+  }
 }
 
 pub fn ws_create_server_stream(
@@ -548,22 +580,32 @@ pub fn ws_create_server_stream(
   Ok(rid)
 }
 
-fn send_binary(state: &mut OpState, rid: ResourceId, data: &[u8]) {
+/// Queues a frame, returning `false` if it doesn't fit in the outbound
+/// queue.
+fn send_frame(
+  state: &mut OpState,
+  rid: ResourceId,
+  opcode: OpCode,
+  data: Vec<u8>,
+) -> bool {
   let resource = state.resource_table.get::<ServerWebSocket>(rid).unwrap();
-  let data = data.to_vec();
   let len = data.len();
-  resource.buffered.set(resource.buffered.get() + len);
+  if !resource.try_buffer(len) {
+    return false;
+  }
   let lock = resource.reserve_lock();
   deno_core::unsync::spawn(async move {
     if let Err(err) = resource
-      .write_frame(lock, Frame::new(true, OpCode::Binary, None, data.into()))
+      .write_frame(lock, Frame::new(true, opcode, None, data.into()))
       .await
     {
       resource.set_error(Some(err.to_string()));
+      resource.drain.notify_waiters();
     } else {
-      resource.buffered.set(resource.buffered.get() - len);
+      resource.release_buffered(len);
     }
   });
+  true
 }
 
 #[op2]
@@ -571,8 +613,8 @@ pub fn op_ws_send_binary(
   state: &mut OpState,
   #[smi] rid: ResourceId,
   #[anybuffer] data: &[u8],
-) {
-  send_binary(state, rid, data)
+) -> bool {
+  send_frame(state, rid, OpCode::Binary, data.to_vec())
 }
 
 #[op2(fast)]
@@ -580,8 +622,8 @@ pub fn op_ws_send_binary_ab(
   state: &mut OpState,
   #[smi] rid: ResourceId,
   #[arraybuffer] data: &[u8],
-) {
-  send_binary(state, rid, data)
+) -> bool {
+  send_frame(state, rid, OpCode::Binary, data.to_vec())
 }
 
 #[op2(fast)]
@@ -589,24 +631,8 @@ pub fn op_ws_send_text(
   state: &mut OpState,
   #[smi] rid: ResourceId,
   #[string] data: String,
-) {
-  let resource = state.resource_table.get::<ServerWebSocket>(rid).unwrap();
-  let len = data.len();
-  resource.buffered.set(resource.buffered.get() + len);
-  let lock = resource.reserve_lock();
-  deno_core::unsync::spawn(async move {
-    if let Err(err) = resource
-      .write_frame(
-        lock,
-        Frame::new(true, OpCode::Text, None, data.into_bytes().into()),
-      )
-      .await
-    {
-      resource.set_error(Some(err.to_string()));
-    } else {
-      resource.buffered.set(resource.buffered.get() - len);
-    }
-  });
+) -> bool {
+  send_frame(state, rid, OpCode::Text, data.into_bytes())
 }
 
 /// Async version of send. Does not update buffered amount as we rely on the socket itself for backpressure.
@@ -663,6 +689,42 @@ pub fn op_ws_get_buffered_amount(
     .get() as u32
 }
 
+/// Limits the bytes queued by the sync send ops, see [`send_frame`]. 0 means
+/// no limit.
+#[op2(fast)]
+pub fn op_ws_set_max_buffered_amount(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  max_buffered_amount: u32,
+) -> Result<(), AnyError> {
+  let resource = state.resource_table.get::<ServerWebSocket>(rid)?;
+  resource.max_buffered.set(max_buffered_amount as usize);
+  Ok(())
+}
+
+/// Resolves once the outbound queue is empty. Returns `false` if the socket
+/// failed or was closed first.
+#[op2(async)]
+pub async fn op_ws_wait_drain(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<bool, AnyError> {
+  let resource = state
+    .borrow_mut()
+    .resource_table
+    .get::<ServerWebSocket>(rid)?;
+  loop {
+    let notified = resource.drain.notified();
+    if resource.errored.get() || resource.closed.get() {
+      return Ok(false);
+    }
+    if resource.buffered.get() == 0 {
+      return Ok(true);
+    }
+    notified.await;
+  }
+}
+
 #[op2(async)]
 pub async fn op_ws_send_ping(
   state: Rc<RefCell<OpState>>,
@@ -697,6 +759,7 @@ pub async fn op_ws_close(
     .unwrap_or_else(|| Frame::close_raw(vec![].into()));
 
   resource.closed.set(true);
+  resource.drain.notify_waiters();
   let lock = resource.reserve_lock();
   resource.write_frame(lock, frame).await?;
   Ok(())
@@ -833,6 +896,8 @@ deno_core::extension!(deno_websocket,
     op_ws_send_text_async,
     op_ws_send_ping,
     op_ws_get_buffered_amount,
+    op_ws_set_max_buffered_amount,
+    op_ws_wait_drain,
   ],
   esm = [ "01_websocket.js", "02_websocketstream.js" ],
   options = {
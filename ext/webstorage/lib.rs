@@ -1,23 +1,64 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-// NOTE to all: use **cached** prepared statements when interfacing with SQLite.
+mod sqlite;
 
 use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::OpState;
-use rusqlite::params;
-use rusqlite::Connection;
-use rusqlite::OptionalExtension;
 
 pub use rusqlite;
+pub use sqlite::SqliteWebStorage;
+pub use sqlite::SqliteWebStorageProvider;
+
+/// The default maximum size of a storage area in bytes.
+pub const DEFAULT_QUOTA: usize = 10 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebStorageKind {
+  /// `localStorage`, which is expected to persist across runs.
+  Local,
+  /// `sessionStorage`, which only lives as long as the isolate.
+  Session,
+}
+
+/// A single storage area. Also see `SqliteWebStorage`.
+pub trait WebStorage {
+  fn length(&self) -> Result<u32, AnyError>;
+  fn key(&self, index: u32) -> Result<Option<String>, AnyError>;
+  fn get(&self, key: &str) -> Result<Option<String>, AnyError>;
+  fn keys(&self) -> Result<Vec<String>, AnyError>;
+  /// The number of bytes currently used, checked against the quota before
+  /// every write.
+  fn size(&self) -> Result<usize, AnyError>;
+  fn set(&mut self, key: &str, value: &str) -> Result<(), AnyError>;
+  fn remove(&mut self, key: &str) -> Result<(), AnyError>;
+  fn clear(&mut self) -> Result<(), AnyError>;
+}
 
-#[derive(Clone)]
-struct OriginStorageDir(PathBuf);
+/// Opens the storage areas of an isolate. The embedder picks the origin by
+/// passing a provider configured for it.
+pub trait WebStorageProvider: Send + Sync {
+  /// Returns `None` if this kind of storage is not supported, which the ops
+  /// surface as a `NotSupportedError`.
+  fn open(
+    &self,
+    kind: WebStorageKind,
+  ) -> Result<Option<Box<dyn WebStorage>>, AnyError>;
+
+  fn quota(&self) -> usize {
+    DEFAULT_QUOTA
+  }
+}
 
-const MAX_STORAGE_BYTES: usize = 10 * 1024 * 1024;
+struct WebStorageState {
+  provider: Arc<dyn WebStorageProvider>,
+  local: Option<Box<dyn WebStorage>>,
+  session: Option<Box<dyn WebStorage>>,
+}
 
 deno_core::extension!(deno_webstorage,
   deps = [ deno_webidl ],
@@ -32,12 +73,17 @@ deno_core::extension!(deno_webstorage,
   ],
   esm = [ "01_webstorage.js" ],
   options = {
-    origin_storage_dir: Option<PathBuf>
+    provider: Option<Arc<dyn WebStorageProvider>>
   },
   state = |state, options| {
-    if let Some(origin_storage_dir) = options.origin_storage_dir {
-      state.put(OriginStorageDir(origin_storage_dir));
-    }
+    let provider = options
+      .provider
+      .unwrap_or_else(|| Arc::new(SqliteWebStorageProvider::new(None)));
+    state.put(WebStorageState {
+      provider,
+      local: None,
+      session: None,
+    });
   },
 );
 
@@ -45,61 +91,31 @@ pub fn get_declaration() -> PathBuf {
   PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib.deno_webstorage.d.ts")
 }
 
-struct LocalStorage(Connection);
-struct SessionStorage(Connection);
-
 fn get_webstorage(
   state: &mut OpState,
   persistent: bool,
-) -> Result<&Connection, AnyError> {
-  let conn = if persistent {
-    if state.try_borrow::<LocalStorage>().is_none() {
-      let path = state.try_borrow::<OriginStorageDir>().ok_or_else(|| {
-        DomExceptionNotSupportedError::new(
-          "LocalStorage is not supported in this context.",
-        )
-      })?;
-      std::fs::create_dir_all(&path.0)?;
-      let conn = Connection::open(path.0.join("local_storage"))?;
-      // Enable write-ahead-logging and tweak some other stuff.
-      let initial_pragmas = "
-        -- enable write-ahead-logging mode
-        PRAGMA journal_mode=WAL;
-        PRAGMA synchronous=NORMAL;
-        PRAGMA temp_store=memory;
-        PRAGMA page_size=4096;
-        PRAGMA mmap_size=6000000;
-        PRAGMA optimize;
-      ";
-
-      conn.execute_batch(initial_pragmas)?;
-      conn.set_prepared_statement_cache_capacity(128);
-      {
-        let mut stmt = conn.prepare_cached(
-          "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
-        )?;
-        stmt.execute(params![])?;
-      }
-      state.put(LocalStorage(conn));
-    }
-
-    &state.borrow::<LocalStorage>().0
+) -> Result<(&mut dyn WebStorage, usize), AnyError> {
+  let state = state.borrow_mut::<WebStorageState>();
+  let (kind, slot) = if persistent {
+    (WebStorageKind::Local, &mut state.local)
   } else {
-    if state.try_borrow::<SessionStorage>().is_none() {
-      let conn = Connection::open_in_memory()?;
-      {
-        let mut stmt = conn.prepare_cached(
-          "CREATE TABLE data (key VARCHAR UNIQUE, value VARCHAR)",
-        )?;
-        stmt.execute(params![])?;
-      }
-      state.put(SessionStorage(conn));
-    }
-
-    &state.borrow::<SessionStorage>().0
+    (WebStorageKind::Session, &mut state.session)
   };
 
-  Ok(conn)
+  if slot.is_none() {
+    let storage = state.provider.open(kind)?.ok_or_else(|| {
+      let name = match kind {
+        WebStorageKind::Local => "LocalStorage",
+        WebStorageKind::Session => "SessionStorage",
+      };
+      DomExceptionNotSupportedError::new(&format!(
+        "{name} is not supported in this context."
+      ))
+    })?;
+    *slot = Some(storage);
+  }
+
+  Ok((slot.as_deref_mut().unwrap(), state.provider.quota()))
 }
 
 #[op2(fast)]
@@ -107,12 +123,8 @@ pub fn op_webstorage_length(
   state: &mut OpState,
   persistent: bool,
 ) -> Result<u32, AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt = conn.prepare_cached("SELECT COUNT(*) FROM data")?;
-  let length: u32 = stmt.query_row(params![], |row| row.get(0))?;
-
-  Ok(length)
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.length()
 }
 
 #[op2]
@@ -122,21 +134,13 @@ pub fn op_webstorage_key(
   #[smi] index: u32,
   persistent: bool,
 ) -> Result<Option<String>, AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt =
-    conn.prepare_cached("SELECT key FROM data LIMIT 1 OFFSET ?")?;
-
-  let key: Option<String> = stmt
-    .query_row(params![index], |row| row.get(0))
-    .optional()?;
-
-  Ok(key)
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.key(index)
 }
 
 #[inline]
-fn size_check(input: usize) -> Result<(), AnyError> {
-  if input >= MAX_STORAGE_BYTES {
+fn size_check(input: usize, quota: usize) -> Result<(), AnyError> {
+  if input >= quota {
     return Err(
       deno_web::DomExceptionQuotaExceededError::new(
         "Exceeded maximum storage size",
@@ -155,21 +159,12 @@ pub fn op_webstorage_set(
   #[string] value: &str,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
+  let (storage, quota) = get_webstorage(state, persistent)?;
 
-  size_check(key.len() + value.len())?;
+  size_check(key.len() + value.len(), quota)?;
+  size_check(storage.size()?, quota)?;
 
-  let mut stmt = conn
-    .prepare_cached("SELECT SUM(pgsize) FROM dbstat WHERE name = 'data'")?;
-  let size: u32 = stmt.query_row(params![], |row| row.get(0))?;
-
-  size_check(size as usize)?;
-
-  let mut stmt = conn
-    .prepare_cached("INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)")?;
-  stmt.execute(params![key, value])?;
-
-  Ok(())
+  storage.set(key, value)
 }
 
 #[op2]
@@ -179,14 +174,8 @@ pub fn op_webstorage_get(
   #[string] key_name: String,
   persistent: bool,
 ) -> Result<Option<String>, AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt = conn.prepare_cached("SELECT value FROM data WHERE key = ?")?;
-  let val = stmt
-    .query_row(params![key_name], |row| row.get(0))
-    .optional()?;
-
-  Ok(val)
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.get(&key_name)
 }
 
 #[op2(fast)]
@@ -195,12 +184,8 @@ pub fn op_webstorage_remove(
   #[string] key_name: &str,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt = conn.prepare_cached("DELETE FROM data WHERE key = ?")?;
-  stmt.execute(params![key_name])?;
-
-  Ok(())
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.remove(key_name)
 }
 
 #[op2(fast)]
@@ -208,12 +193,8 @@ pub fn op_webstorage_clear(
   state: &mut OpState,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt = conn.prepare_cached("DELETE FROM data")?;
-  stmt.execute(params![])?;
-
-  Ok(())
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.clear()
 }
 
 #[op2]
@@ -222,15 +203,8 @@ pub fn op_webstorage_iterate_keys(
   state: &mut OpState,
   persistent: bool,
 ) -> Result<Vec<String>, AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  let mut stmt = conn.prepare_cached("SELECT key FROM data")?;
-  let keys = stmt
-    .query_map(params![], |row| row.get::<_, String>(0))?
-    .map(|r| r.unwrap())
-    .collect();
-
-  Ok(keys)
+  let (storage, _) = get_webstorage(state, persistent)?;
+  storage.keys()
 }
 
 #[derive(Debug)]
@@ -260,3 +234,214 @@ pub fn get_not_supported_error_class_name(
   e.downcast_ref::<DomExceptionNotSupportedError>()
     .map(|_| "DOMExceptionNotSupportedError")
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_core::JsRuntime;
+  use deno_core::RuntimeOptions;
+  use std::collections::BTreeMap;
+  use std::sync::Mutex;
+
+  type Entries = Arc<Mutex<BTreeMap<String, String>>>;
+
+  struct MemoryWebStorage(Entries);
+
+  impl WebStorage for MemoryWebStorage {
+    fn length(&self) -> Result<u32, AnyError> {
+      Ok(self.0.lock().unwrap().len() as u32)
+    }
+
+    fn key(&self, index: u32) -> Result<Option<String>, AnyError> {
+      Ok(self.0.lock().unwrap().keys().nth(index as usize).cloned())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, AnyError> {
+      Ok(self.0.lock().unwrap().get(key).cloned())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, AnyError> {
+      Ok(self.0.lock().unwrap().keys().cloned().collect())
+    }
+
+    fn size(&self) -> Result<usize, AnyError> {
+      let entries = self.0.lock().unwrap();
+      Ok(
+        entries
+          .iter()
+          .map(|(key, value)| key.len() + value.len())
+          .sum(),
+      )
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), AnyError> {
+      self
+        .0
+        .lock()
+        .unwrap()
+        .insert(key.to_string(), value.to_string());
+      Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), AnyError> {
+      self.0.lock().unwrap().remove(key);
+      Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), AnyError> {
+      self.0.lock().unwrap().clear();
+      Ok(())
+    }
+  }
+
+  /// Only supports `localStorage`.
+  struct MemoryWebStorageProvider {
+    entries: Entries,
+    quota: usize,
+  }
+
+  impl WebStorageProvider for MemoryWebStorageProvider {
+    fn open(
+      &self,
+      kind: WebStorageKind,
+    ) -> Result<Option<Box<dyn WebStorage>>, AnyError> {
+      Ok(match kind {
+        WebStorageKind::Local => {
+          Some(Box::new(MemoryWebStorage(self.entries.clone())))
+        }
+        WebStorageKind::Session => None,
+      })
+    }
+
+    fn quota(&self) -> usize {
+      self.quota
+    }
+  }
+
+  // only the ops are tested, so the JS of deno_webidl isn't needed
+  deno_core::extension!(deno_webidl);
+
+  /// Runs `script` with the webstorage ops of `provider`. It throws to fail
+  /// the test.
+  fn run_with_provider(entries: &Entries, quota: usize, script: &'static str) {
+    let provider = MemoryWebStorageProvider {
+      entries: entries.clone(),
+      quota,
+    };
+    let mut runtime = JsRuntime::new(RuntimeOptions {
+      extensions: vec![
+        deno_webidl::init_ops(),
+        deno_webstorage::init_ops(Some(Arc::new(provider))),
+      ],
+      get_error_class_fn: Some(&|err| {
+        deno_web::get_error_class_name(err)
+          .or_else(|| get_not_supported_error_class_name(err))
+          .unwrap_or("Error")
+      }),
+      ..Default::default()
+    });
+    runtime
+      .execute_script(
+        "register_errors",
+        r#"
+          for (const name of ["QuotaExceededError", "NotSupportedError"]) {
+            Deno.core.registerErrorBuilder(`DOMException${name}`, (msg) => {
+              const err = new Error(msg);
+              err.name = name;
+              return err;
+            });
+          }
+          globalThis.assertThrows = (fn, name) => {
+            try {
+              fn();
+            } catch (err) {
+              if (err.name === name) return;
+              throw err;
+            }
+            throw new Error(`Expected a ${name}`);
+          };
+          globalThis.assertEquals = (actual, expected) => {
+            if (JSON.stringify(actual) !== JSON.stringify(expected)) {
+              throw new Error(`${actual} !== ${expected}`);
+            }
+          };
+        "#,
+      )
+      .unwrap();
+    runtime.execute_script("test", script).unwrap();
+  }
+
+  #[test]
+  fn custom_provider() {
+    let entries = Entries::default();
+    entries
+      .lock()
+      .unwrap()
+      .insert("existing".to_string(), "1".to_string());
+    run_with_provider(
+      &entries,
+      DEFAULT_QUOTA,
+      r#"
+        const {
+          op_webstorage_clear,
+          op_webstorage_get,
+          op_webstorage_iterate_keys,
+          op_webstorage_key,
+          op_webstorage_length,
+          op_webstorage_remove,
+          op_webstorage_set,
+        } = Deno.core.ops;
+        assertEquals(op_webstorage_get("existing", true), "1");
+        op_webstorage_set("a", "2", true);
+        assertEquals(op_webstorage_length(true), 2);
+        assertEquals(op_webstorage_key(0, true), "a");
+        assertEquals(op_webstorage_iterate_keys(true), ["a", "existing"]);
+        op_webstorage_remove("existing", true);
+        assertEquals(op_webstorage_get("existing", true), null);
+        op_webstorage_set("b", "3", true);
+        assertThrows(() => op_webstorage_length(false), "NotSupportedError");
+        op_webstorage_clear(true);
+        op_webstorage_set("c", "4", true);
+      "#,
+    );
+    let entries = entries.lock().unwrap();
+    assert_eq!(
+      *entries,
+      BTreeMap::from([("c".to_string(), "4".to_string())])
+    );
+  }
+
+  #[test]
+  fn quota_exceeded() {
+    let entries = Entries::default();
+    run_with_provider(
+      &entries,
+      8,
+      r#"
+        const { op_webstorage_get, op_webstorage_set } = Deno.core.ops;
+        op_webstorage_set("a", "1234", true);
+        // the entry alone doesn't fit
+        assertThrows(
+          () => op_webstorage_set("b", "12345678", true),
+          "QuotaExceededError",
+        );
+        op_webstorage_set("b", "123", true);
+        // the storage is full
+        assertThrows(
+          () => op_webstorage_set("c", "1", true),
+          "QuotaExceededError",
+        );
+        assertEquals(op_webstorage_get("b", true), "123");
+        assertEquals(op_webstorage_get("c", true), null);
+      "#,
+    );
+    let entries = entries.lock().unwrap();
+    assert_eq!(
+      *entries,
+      BTreeMap::from([
+        ("a".to_string(), "1234".to_string()),
+        ("b".to_string(), "123".to_string()),
+      ])
+    );
+  }
+}
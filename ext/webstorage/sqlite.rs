@@ -0,0 +1,167 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// NOTE to all: use **cached** prepared statements when interfacing with SQLite.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::error::AnyError;
+use rusqlite::params;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+
+use crate::WebStorage;
+use crate::WebStorageKind;
+use crate::WebStorageProvider;
+use crate::DEFAULT_QUOTA;
+
+/// A storage area in a SQLite database.
+pub struct SqliteWebStorage(Connection);
+
+impl SqliteWebStorage {
+  /// Opens the database at `path`, creating it if it doesn't exist.
+  pub fn open(path: &Path) -> Result<Self, AnyError> {
+    if let Some(dir) = path.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+    let conn = Connection::open(path)?;
+    // Enable write-ahead-logging and tweak some other stuff.
+    let initial_pragmas = "
+      -- enable write-ahead-logging mode
+      PRAGMA journal_mode=WAL;
+      PRAGMA synchronous=NORMAL;
+      PRAGMA temp_store=memory;
+      PRAGMA page_size=4096;
+      PRAGMA mmap_size=6000000;
+      PRAGMA optimize;
+    ";
+
+    conn.execute_batch(initial_pragmas)?;
+    Self::init(conn)
+  }
+
+  /// Opens a database that only lives as long as this storage area.
+  pub fn open_in_memory() -> Result<Self, AnyError> {
+    Self::init(Connection::open_in_memory()?)
+  }
+
+  fn init(conn: Connection) -> Result<Self, AnyError> {
+    conn.set_prepared_statement_cache_capacity(128);
+    {
+      let mut stmt = conn.prepare_cached(
+        "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
+      )?;
+      stmt.execute(params![])?;
+    }
+    Ok(Self(conn))
+  }
+}
+
+impl WebStorage for SqliteWebStorage {
+  fn length(&self) -> Result<u32, AnyError> {
+    let mut stmt = self.0.prepare_cached("SELECT COUNT(*) FROM data")?;
+    let length: u32 = stmt.query_row(params![], |row| row.get(0))?;
+
+    Ok(length)
+  }
+
+  fn key(&self, index: u32) -> Result<Option<String>, AnyError> {
+    let mut stmt = self
+      .0
+      .prepare_cached("SELECT key FROM data LIMIT 1 OFFSET ?")?;
+
+    let key: Option<String> = stmt
+      .query_row(params![index], |row| row.get(0))
+      .optional()?;
+
+    Ok(key)
+  }
+
+  fn size(&self) -> Result<usize, AnyError> {
+    let mut stmt = self
+      .0
+      .prepare_cached("SELECT SUM(pgsize) FROM dbstat WHERE name = 'data'")?;
+    let size: u32 = stmt.query_row(params![], |row| row.get(0))?;
+
+    Ok(size as usize)
+  }
+
+  fn get(&self, key: &str) -> Result<Option<String>, AnyError> {
+    let mut stmt = self
+      .0
+      .prepare_cached("SELECT value FROM data WHERE key = ?")?;
+    let val = stmt.query_row(params![key], |row| row.get(0)).optional()?;
+
+    Ok(val)
+  }
+
+  fn set(&mut self, key: &str, value: &str) -> Result<(), AnyError> {
+    let mut stmt = self.0.prepare_cached(
+      "INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)",
+    )?;
+    stmt.execute(params![key, value])?;
+
+    Ok(())
+  }
+
+  fn remove(&mut self, key: &str) -> Result<(), AnyError> {
+    let mut stmt = self.0.prepare_cached("DELETE FROM data WHERE key = ?")?;
+    stmt.execute(params![key])?;
+
+    Ok(())
+  }
+
+  fn clear(&mut self) -> Result<(), AnyError> {
+    let mut stmt = self.0.prepare_cached("DELETE FROM data")?;
+    stmt.execute(params![])?;
+
+    Ok(())
+  }
+
+  fn keys(&self) -> Result<Vec<String>, AnyError> {
+    let mut stmt = self.0.prepare_cached("SELECT key FROM data")?;
+    let keys = stmt
+      .query_map(params![], |row| row.get::<_, String>(0))?
+      .collect::<Result<_, _>>()?;
+
+    Ok(keys)
+  }
+}
+
+/// The default provider, which keeps `localStorage` in a `local_storage`
+/// database in the origin storage directory and `sessionStorage` in memory.
+pub struct SqliteWebStorageProvider {
+  /// `None` means `localStorage` is not supported.
+  pub origin_storage_dir: Option<PathBuf>,
+  /// The maximum size of a storage area in bytes.
+  pub quota: usize,
+}
+
+impl SqliteWebStorageProvider {
+  pub fn new(origin_storage_dir: Option<PathBuf>) -> Self {
+    Self {
+      origin_storage_dir,
+      quota: DEFAULT_QUOTA,
+    }
+  }
+}
+
+impl WebStorageProvider for SqliteWebStorageProvider {
+  fn open(
+    &self,
+    kind: WebStorageKind,
+  ) -> Result<Option<Box<dyn WebStorage>>, AnyError> {
+    let storage = match kind {
+      WebStorageKind::Local => match &self.origin_storage_dir {
+        Some(dir) => SqliteWebStorage::open(&dir.join("local_storage"))?,
+        None => return Ok(None),
+      },
+      WebStorageKind::Session => SqliteWebStorage::open_in_memory()?,
+    };
+    Ok(Some(Box::new(storage)))
+  }
+
+  fn quota(&self) -> usize {
+    self.quota
+  }
+}
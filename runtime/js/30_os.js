@@ -1,16 +1,19 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { primordials } from "ext:core/mod.js";
+import { core, primordials } from "ext:core/mod.js";
 import {
   op_delete_env,
   op_env,
   op_exec_path,
   op_exit,
   op_get_env,
+  op_get_exit_code,
   op_gid,
   op_hostname,
   op_loadavg,
   op_network_interfaces,
+  op_network_interfaces_watch,
+  op_network_interfaces_watch_poll,
   op_os_release,
   op_os_uptime,
   op_set_env,
@@ -18,13 +21,23 @@ import {
   op_system_memory_info,
   op_uid,
 } from "ext:core/ops";
+const {
+  BadResourcePrototype,
+  InterruptedPrototype,
+} = core;
 const {
   Error,
   FunctionPrototypeBind,
+  NumberIsInteger,
+  ObjectPrototypeIsPrototypeOf,
+  RangeError,
+  SymbolAsyncIterator,
   SymbolFor,
+  TypeError,
 } = primordials;
 
 import { Event, EventTarget } from "ext:deno_web/02_event.js";
+import { SymbolDispose } from "ext:deno_web/00_infra.js";
 
 const windowDispatchEvent = FunctionPrototypeBind(
   EventTarget.prototype.dispatchEvent,
@@ -55,6 +68,65 @@ function networkInterfaces() {
   return op_network_interfaces();
 }
 
+class NetworkInterfaceWatcher {
+  #rid = 0;
+  #promise;
+  #unref = false;
+
+  constructor() {
+    this.#rid = op_network_interfaces_watch();
+  }
+
+  unref() {
+    this.#unref = true;
+    if (this.#promise) {
+      core.unrefOpPromise(this.#promise);
+    }
+  }
+
+  ref() {
+    this.#unref = false;
+    if (this.#promise) {
+      core.refOpPromise(this.#promise);
+    }
+  }
+
+  async next() {
+    try {
+      this.#promise = op_network_interfaces_watch_poll(this.#rid);
+      if (this.#unref) {
+        core.unrefOpPromise(this.#promise);
+      }
+      const value = await this.#promise;
+      return { value, done: false };
+    } catch (error) {
+      if (
+        ObjectPrototypeIsPrototypeOf(BadResourcePrototype, error) ||
+        ObjectPrototypeIsPrototypeOf(InterruptedPrototype, error)
+      ) {
+        return { value: undefined, done: true };
+      }
+      throw error;
+    }
+  }
+
+  close() {
+    core.close(this.#rid);
+  }
+
+  [SymbolAsyncIterator]() {
+    return this;
+  }
+
+  [SymbolDispose]() {
+    core.tryClose(this.#rid);
+  }
+}
+
+function watchNetworkInterfaces() {
+  return new NetworkInterfaceWatcher();
+}
+
 function gid() {
   return op_gid();
 }
@@ -70,6 +142,13 @@ function setExitHandler(fn) {
   exitHandler = fn;
 }
 
+// This is an internal only method used by the test harness to record where
+// environment variables are changed when the environment sanitizer is enabled.
+let envChangeHandler = null;
+function setEnvChangeHandler(fn) {
+  envChangeHandler = fn;
+}
+
 function exit(code) {
   // Set exit code first so unload event listeners can override it.
   if (typeof code === "number") {
@@ -94,8 +173,25 @@ function exit(code) {
   throw new Error("Code not reachable");
 }
 
+function getExitCode() {
+  return op_get_exit_code();
+}
+
+function setExitCode(value) {
+  if (typeof value !== "number") {
+    throw new TypeError(
+      `Exit code must be a number, got: ${value} (${typeof value})`,
+    );
+  }
+  if (!NumberIsInteger(value)) {
+    throw new RangeError(`Exit code must be an integer, got: ${value}`);
+  }
+  op_set_exit_code(value);
+}
+
 function setEnv(key, value) {
   op_set_env(key, value);
+  envChangeHandler?.(key);
 }
 
 function getEnv(key) {
@@ -104,6 +200,7 @@ function getEnv(key) {
 
 function deleteEnv(key) {
   op_delete_env(key);
+  envChangeHandler?.(key);
 }
 
 const env = {
@@ -126,13 +223,17 @@ export {
   env,
   execPath,
   exit,
+  getExitCode,
   gid,
   hostname,
   loadavg,
   networkInterfaces,
   osRelease,
   osUptime,
+  setExitCode,
+  setEnvChangeHandler,
   setExitHandler,
   systemMemoryInfo,
   uid,
+  watchNetworkInterfaces,
 };
@@ -7,12 +7,20 @@ import {
   op_run_status,
   op_spawn_child,
   op_spawn_kill,
+  op_spawn_lines,
+  op_spawn_lines_next,
+  op_spawn_pipeline,
+  op_spawn_read_output,
   op_spawn_sync,
   op_spawn_wait,
 } from "ext:core/ops";
 const {
+  ArrayIsArray,
+  ArrayPrototypeEvery,
   ArrayPrototypeMap,
   ArrayPrototypeSlice,
+  NumberIsSafeInteger,
+  RangeError,
   TypeError,
   ObjectEntries,
   SafeArrayIterator,
@@ -37,6 +45,7 @@ import {
   readableStreamForRidUnrefable,
   readableStreamForRidUnrefableRef,
   readableStreamForRidUnrefableUnref,
+  ReadableStreamDefaultReader,
   ReadableStreamPrototype,
   writableStreamForRid,
 } from "ext:deno_web/06_streams.js";
@@ -168,7 +177,7 @@ function run({
 
 const illegalConstructorKey = Symbol("illegalConstructorKey");
 
-function spawnChildInner(opFn, command, apiName, {
+function spawnArgs(command, {
   args = [],
   cwd = undefined,
   clearEnv = false,
@@ -178,11 +187,10 @@ function spawnChildInner(opFn, command, apiName, {
   stdin = "null",
   stdout = "piped",
   stderr = "piped",
-  signal = undefined,
   windowsRawArguments = false,
   ipc = -1,
 } = {}) {
-  const child = opFn({
+  return {
     cmd: pathFromURL(command),
     args: ArrayPrototypeMap(args, String),
     cwd: pathFromURL(cwd),
@@ -195,10 +203,14 @@ function spawnChildInner(opFn, command, apiName, {
     stderr,
     windowsRawArguments,
     ipc,
-  }, apiName);
+  };
+}
+
+function spawnChildInner(opFn, command, apiName, options = {}) {
+  const child = opFn(spawnArgs(command, options), apiName);
   return new ChildProcess(illegalConstructorKey, {
     ...child,
-    signal,
+    signal: options.signal,
   });
 }
 
@@ -221,6 +233,37 @@ function collectOutput(readableStream) {
   return readableStreamCollectIntoUint8Array(readableStream);
 }
 
+// Locks `readableStream` for good, so nothing else reads the resource
+// once it was handed over to an op.
+function lockStream(readableStream) {
+  new ReadableStreamDefaultReader(readableStream);
+}
+
+async function collectCappedOutput(readableStream, rid, maxBytes, truncate) {
+  if (
+    !(ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, readableStream))
+  ) {
+    return null;
+  }
+
+  lockStream(readableStream);
+  return await op_spawn_read_output(rid, maxBytes, truncate);
+}
+
+async function* readLines(rid) {
+  try {
+    while (true) {
+      const lines = await op_spawn_lines_next(rid);
+      if (lines === null) {
+        return;
+      }
+      yield* new SafeArrayIterator(lines);
+    }
+  } finally {
+    core.tryClose(rid);
+  }
+}
+
 const _pipeFd = Symbol("[[pipeFd]]");
 
 internals.getPipeFd = (process) => process[_pipeFd];
@@ -245,6 +288,7 @@ class ChildProcess {
     return this.#stdin;
   }
 
+  #stdoutRid = null;
   #stdout = null;
   get stdout() {
     if (this.#stdout == null) {
@@ -253,6 +297,7 @@ class ChildProcess {
     return this.#stdout;
   }
 
+  #stderrRid = null;
   #stderr = null;
   get stderr() {
     if (this.#stderr == null) {
@@ -283,10 +328,12 @@ class ChildProcess {
     }
 
     if (stdoutRid !== null) {
+      this.#stdoutRid = stdoutRid;
       this.#stdout = readableStreamForRidUnrefable(stdoutRid);
     }
 
     if (stderrRid !== null) {
+      this.#stderrRid = stderrRid;
       this.#stderr = readableStreamForRidUnrefable(stderrRid);
     }
 
@@ -307,7 +354,7 @@ class ChildProcess {
     return this.#status;
   }
 
-  async output() {
+  async output(options = {}) {
     if (this.#stdout?.locked) {
       throw new TypeError(
         "Can't collect output because stdout is locked",
@@ -319,16 +366,32 @@ class ChildProcess {
       );
     }
 
-    const { 0: status, 1: stdout, 2: stderr } = await SafePromiseAll([
-      this.#status,
-      collectOutput(this.#stdout),
-      collectOutput(this.#stderr),
-    ]);
+    const { maxBytes, truncate = false } = options;
+    let status, stdout, stderr, exceeded;
+    if (maxBytes === undefined) {
+      ({ 0: status, 1: stdout, 2: stderr } = await SafePromiseAll([
+        this.#status,
+        collectOutput(this.#stdout),
+        collectOutput(this.#stderr),
+      ]));
+    } else {
+      if (!NumberIsSafeInteger(maxBytes) || maxBytes < 0) {
+        throw new RangeError(
+          `maxBytes must be a non-negative integer, received ${maxBytes}`,
+        );
+      }
+      const capped = await this.#collectCappedOutput(maxBytes, truncate);
+      status = await this.#status;
+      stdout = capped[0]?.data ?? null;
+      stderr = capped[1]?.data ?? null;
+      exceeded = capped[0]?.exceeded || capped[1]?.exceeded;
+    }
 
     return {
       success: status.success,
       code: status.code,
       signal: status.signal,
+      truncated: !!exceeded,
       get stdout() {
         if (stdout == null) {
           throw new TypeError("stdout is not piped");
@@ -344,6 +407,65 @@ class ChildProcess {
     };
   }
 
+  async #collectCappedOutput(maxBytes, truncate) {
+    // Without truncation the child is killed as soon as one of its streams
+    // goes over the limit, so reading the other one doesn't hang.
+    const collect = (readable, rid) =>
+      PromisePrototypeThen(
+        collectCappedOutput(readable, rid, maxBytes, truncate),
+        (output) => {
+          if (output?.exceeded && !truncate && !this.#waitComplete) {
+            try {
+              op_spawn_kill(this.#rid, "SIGKILL");
+            } catch {
+              // the child exited in the meantime
+            }
+          }
+          return output;
+        },
+      );
+    const capped = await SafePromiseAll([
+      collect(this.#stdout, this.#stdoutRid),
+      collect(this.#stderr, this.#stderrRid),
+    ]);
+    if (!truncate) {
+      const { 0: stdout, 1: stderr } = capped;
+      const name = stdout?.exceeded
+        ? "stdout"
+        : stderr?.exceeded
+        ? "stderr"
+        : null;
+      if (name !== null) {
+        await this.#status;
+        throw new RangeError(
+          `Child process ${name} exceeded the limit of ${maxBytes} bytes`,
+        );
+      }
+    }
+    return capped;
+  }
+
+  lines(options = {}) {
+    const { stream = "stdout", encoding = "utf-8" } = options;
+    if (stream !== "stdout" && stream !== "stderr") {
+      throw new TypeError(
+        `stream must be "stdout" or "stderr", received "${stream}"`,
+      );
+    }
+    const readable = stream === "stdout" ? this.#stdout : this.#stderr;
+    if (readable == null) {
+      throw new TypeError(`${stream} is not piped`);
+    }
+    if (readable.locked) {
+      throw new TypeError(`Can't read lines because ${stream} is locked`);
+    }
+
+    const rid = stream === "stdout" ? this.#stdoutRid : this.#stderrRid;
+    const linesRid = op_spawn_lines(rid, String(encoding));
+    lockStream(readable);
+    return readLines(linesRid);
+  }
+
   kill(signo = "SIGTERM") {
     if (this.#waitComplete) {
       throw new TypeError("Child process has already terminated.");
@@ -373,7 +495,7 @@ class ChildProcess {
   }
 }
 
-function spawn(command, options) {
+function spawn(command, options, outputOptions) {
   if (options?.stdin === "piped") {
     throw new TypeError(
       "Piped stdin is not supported for this function, use 'Deno.Command().spawn()' instead",
@@ -385,7 +507,7 @@ function spawn(command, options) {
     "Deno.Command().output()",
     options,
   )
-    .output();
+    .output(outputOptions);
 }
 
 function spawnSync(command, {
@@ -422,6 +544,7 @@ function spawnSync(command, {
     success: result.status.success,
     code: result.status.code,
     signal: result.status.signal,
+    truncated: false,
     get stdout() {
       if (result.stdout == null) {
         throw new TypeError("stdout is not piped");
@@ -437,22 +560,28 @@ function spawnSync(command, {
   };
 }
 
+let getCommandOptions;
+
 class Command {
   #command;
   #options;
 
+  static {
+    getCommandOptions = (command) => [command.#command, command.#options];
+  }
+
   constructor(command, options) {
     this.#command = command;
     this.#options = options;
   }
 
-  output() {
+  output(options) {
     if (this.#options?.stdin === "piped") {
       throw new TypeError(
         "Piped stdin is not supported for this function, use 'Deno.Command.spawn()' instead",
       );
     }
-    return spawn(this.#command, this.#options);
+    return spawn(this.#command, this.#options, options);
   }
 
   outputSync() {
@@ -475,4 +604,50 @@ class Command {
   }
 }
 
-export { ChildProcess, Command, kill, Process, run };
+const CommandPrototype = Command.prototype;
+
+function spawnPipeline(commands) {
+  if (!ArrayIsArray(commands) || commands.length === 0) {
+    throw new TypeError("A pipeline must have at least one command.");
+  }
+  const last = commands.length - 1;
+  const stages = ArrayPrototypeMap(commands, (command, i) => {
+    if (!ObjectPrototypeIsPrototypeOf(CommandPrototype, command)) {
+      throw new TypeError(
+        `Pipeline command at index ${i} is not a Deno.Command.`,
+      );
+    }
+    const { 0: cmd, 1: options = {} } = getCommandOptions(command);
+    // the stdio that connects two commands is set up by the op
+    return {
+      options,
+      args: spawnArgs(cmd, {
+        ...options,
+        stdin: i === 0 ? options.stdin ?? "inherit" : "null",
+        stdout: i === last ? options.stdout ?? "inherit" : "null",
+        stderr: options.stderr ?? "inherit",
+      }),
+    };
+  });
+  const children = op_spawn_pipeline(
+    ArrayPrototypeMap(stages, (stage) => stage.args),
+  );
+  const processes = ArrayPrototypeMap(
+    children,
+    (child, i) =>
+      new ChildProcess(illegalConstructorKey, {
+        ...child,
+        signal: stages[i].options.signal,
+      }),
+  );
+  const status = PromisePrototypeThen(
+    SafePromiseAll(ArrayPrototypeMap(processes, (child) => child.status)),
+    (statuses) => ({
+      success: ArrayPrototypeEvery(statuses, (status) => status.success),
+      statuses,
+    }),
+  );
+  return { children: processes, status };
+}
+
+export { ChildProcess, Command, kill, Process, run, spawnPipeline };
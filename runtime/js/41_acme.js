@@ -0,0 +1,709 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// An ACME (RFC 8555) client provisioning certificates for `Deno.listenTls`
+// and `Deno.serve`, ie: from Let's Encrypt. Certificates are issued on the
+// first handshake for a domain using HTTP-01 challenges, stored on disk and
+// renewed in the background before they expire.
+
+import { primordials } from "ext:core/mod.js";
+import { op_bootstrap_deno_dir } from "ext:core/ops";
+const {
+  ArrayIsArray,
+  ArrayPrototypeFind,
+  ArrayPrototypeIncludes,
+  ArrayPrototypeJoin,
+  ArrayPrototypeMap,
+  ArrayPrototypePush,
+  DateNow,
+  DateUTC,
+  Error,
+  JSONParse,
+  JSONStringify,
+  NumberParseInt,
+  ObjectFreeze,
+  Promise,
+  PromisePrototypeThen,
+  SafeMap,
+  SafeMapIterator,
+  SafeSet,
+  SafeSetIterator,
+  StringPrototypeIndexOf,
+  StringPrototypeReplace,
+  StringPrototypeReplaceAll,
+  StringPrototypeSlice,
+  StringPrototypeSplit,
+  StringPrototypeStartsWith,
+  SymbolFor,
+  TypedArrayPrototypeSet,
+  TypedArrayPrototypeSubarray,
+  TypeError,
+  Uint8Array,
+} = primordials;
+
+import { crypto } from "ext:deno_crypto/00_crypto.js";
+import { createHttpClient } from "ext:deno_fetch/22_http_client.js";
+import { Response } from "ext:deno_fetch/23_response.js";
+import { fetch } from "ext:deno_fetch/26_fetch.js";
+import {
+  mkdir,
+  readTextFile,
+  rename,
+  writeTextFile,
+} from "ext:deno_fs/30_fs.js";
+import { certResolverEvictSymbol } from "ext:deno_net/02_tls.js";
+import {
+  forgivingBase64Decode,
+  forgivingBase64Encode,
+  forgivingBase64UrlEncode,
+} from "ext:deno_web/00_infra.js";
+import { setTimeout, unrefTimer } from "ext:deno_web/02_timers.js";
+import { TextDecoder, TextEncoder } from "ext:deno_web/08_text_encoding.js";
+import { URL } from "ext:deno_url/00_url.js";
+
+const LETS_ENCRYPT_DIRECTORY_URL =
+  "https://acme-v02.api.letsencrypt.org/directory";
+const CHALLENGE_PATH_PREFIX = "/.well-known/acme-challenge/";
+const DAY_MS = 24 * 60 * 60 * 1000;
+const RENEWAL_CHECK_INTERVAL_MS = 12 * 60 * 60 * 1000;
+const POLL_INTERVAL_MS = 1000;
+const POLL_ATTEMPTS = 60;
+
+const ECDSA_P256 = { name: "ECDSA", namedCurve: "P-256" };
+const ECDSA_SHA256 = { name: "ECDSA", hash: "SHA-256" };
+
+const OID_COMMON_NAME = "2.5.4.3";
+const OID_SUBJECT_ALT_NAME = "2.5.29.17";
+const OID_EXTENSION_REQUEST = "1.2.840.113549.1.9.14";
+const OID_ECDSA_WITH_SHA256 = "1.2.840.10045.4.3.2";
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+function concatBytes(chunks) {
+  let length = 0;
+  for (let i = 0; i < chunks.length; ++i) {
+    length += chunks[i].length;
+  }
+  const bytes = new Uint8Array(length);
+  let offset = 0;
+  for (let i = 0; i < chunks.length; ++i) {
+    TypedArrayPrototypeSet(bytes, chunks[i], offset);
+    offset += chunks[i].length;
+  }
+  return bytes;
+}
+
+/** Encodes a DER value from its tag and the encoded children. */
+function der(tag, chunks) {
+  const content = concatBytes(chunks);
+  const length = [];
+  if (content.length < 0x80) {
+    ArrayPrototypePush(length, content.length);
+  } else {
+    const bytes = [];
+    for (let n = content.length; n > 0; n = n >> 8) {
+      ArrayPrototypePush(bytes, n & 0xff);
+    }
+    ArrayPrototypePush(length, 0x80 | bytes.length);
+    for (let i = bytes.length - 1; i >= 0; --i) {
+      ArrayPrototypePush(length, bytes[i]);
+    }
+  }
+  return concatBytes([new Uint8Array([tag]), new Uint8Array(length), content]);
+}
+
+function derOid(oid) {
+  const parts = ArrayPrototypeMap(
+    StringPrototypeSplit(oid, "."),
+    (part) => NumberParseInt(part, 10),
+  );
+  const bytes = [parts[0] * 40 + parts[1]];
+  for (let i = 2; i < parts.length; ++i) {
+    const base128 = [];
+    let n = parts[i];
+    do {
+      ArrayPrototypePush(base128, n & 0x7f);
+      n = n >> 7;
+    } while (n > 0);
+    for (let j = base128.length - 1; j >= 0; --j) {
+      ArrayPrototypePush(bytes, base128[j] | (j > 0 ? 0x80 : 0));
+    }
+  }
+  return der(0x06, [new Uint8Array(bytes)]);
+}
+
+/** Encodes an unsigned big-endian integer. */
+function derUnsignedInteger(bytes) {
+  let start = 0;
+  while (start < bytes.length - 1 && bytes[start] === 0) {
+    ++start;
+  }
+  bytes = TypedArrayPrototypeSubarray(bytes, start);
+  if (bytes[0] & 0x80) {
+    bytes = concatBytes([new Uint8Array([0]), bytes]);
+  }
+  return der(0x02, [bytes]);
+}
+
+/**
+ * Creates a DER encoded PKCS#10 certificate signing request for the domains,
+ * signed with an ECDSA P-256 key pair.
+ */
+async function createCsr(keyPair, domains) {
+  const spki = new Uint8Array(
+    await crypto.subtle.exportKey("spki", keyPair.publicKey),
+  );
+  const subjectAltName = der(
+    0x30,
+    ArrayPrototypeMap(domains, (domain) => der(0x82, [encoder.encode(domain)])),
+  );
+  const info = der(0x30, [
+    der(0x02, [new Uint8Array([0])]),
+    der(0x30, [
+      der(0x31, [
+        der(0x30, [
+          derOid(OID_COMMON_NAME),
+          der(0x0c, [encoder.encode(domains[0])]),
+        ]),
+      ]),
+    ]),
+    spki,
+    der(0xa0, [
+      der(0x30, [
+        derOid(OID_EXTENSION_REQUEST),
+        der(0x31, [
+          der(0x30, [
+            der(0x30, [derOid(OID_SUBJECT_ALT_NAME), der(0x04, [
+              subjectAltName,
+            ])]),
+          ]),
+        ]),
+      ]),
+    ]),
+  ]);
+  // WebCrypto signatures are the raw `r || s` concatenation, while X.509
+  // expects a DER encoded ECDSA-Sig-Value.
+  const signature = new Uint8Array(
+    await crypto.subtle.sign(ECDSA_SHA256, keyPair.privateKey, info),
+  );
+  const ecdsaSigValue = der(0x30, [
+    derUnsignedInteger(TypedArrayPrototypeSubarray(signature, 0, 32)),
+    derUnsignedInteger(TypedArrayPrototypeSubarray(signature, 32)),
+  ]);
+  return der(0x30, [
+    info,
+    der(0x30, [derOid(OID_ECDSA_WITH_SHA256)]),
+    der(0x03, [new Uint8Array([0]), ecdsaSigValue]),
+  ]);
+}
+
+function readDer(bytes, offset) {
+  const tag = bytes[offset];
+  let length = bytes[offset + 1];
+  let start = offset + 2;
+  if (length & 0x80) {
+    const count = length & 0x7f;
+    length = 0;
+    for (let i = 0; i < count; ++i) {
+      length = length * 256 + bytes[start++];
+    }
+  }
+  return { tag, start, end: start + length };
+}
+
+/** Returns the expiration time of a PEM certificate chain's first cert. */
+function certificateNotAfter(pem) {
+  const begin = "-----BEGIN CERTIFICATE-----";
+  const start = StringPrototypeIndexOf(pem, begin);
+  const end = StringPrototypeIndexOf(pem, "-----END CERTIFICATE-----");
+  if (start === -1 || end === -1) {
+    throw new TypeError("Invalid certificate");
+  }
+  const bytes = forgivingBase64Decode(
+    StringPrototypeReplace(
+      StringPrototypeSlice(pem, start + begin.length, end),
+      /\s+/g,
+      "",
+    ),
+  );
+  const certificate = readDer(bytes, 0);
+  const tbsCertificate = readDer(bytes, certificate.start);
+  let field = readDer(bytes, tbsCertificate.start);
+  if (field.tag === 0xa0) {
+    // explicit version, followed by the serial number
+    field = readDer(bytes, field.end);
+  }
+  const signature = readDer(bytes, field.end);
+  const issuer = readDer(bytes, signature.end);
+  const validity = readDer(bytes, issuer.end);
+  const notBefore = readDer(bytes, validity.start);
+  const notAfter = readDer(bytes, notBefore.end);
+  const time = decoder.decode(
+    TypedArrayPrototypeSubarray(bytes, notAfter.start, notAfter.end),
+  );
+  // UTCTime is YYMMDDHHMMSSZ, GeneralizedTime is YYYYMMDDHHMMSSZ
+  const yearLength = notAfter.tag === 0x17 ? 2 : 4;
+  let year = NumberParseInt(StringPrototypeSlice(time, 0, yearLength), 10);
+  if (yearLength === 2) {
+    year += year < 50 ? 2000 : 1900;
+  }
+  const part = (index) => {
+    const start = yearLength + index * 2;
+    return NumberParseInt(StringPrototypeSlice(time, start, start + 2), 10);
+  };
+  return DateUTC(year, part(0) - 1, part(1), part(2), part(3), part(4));
+}
+
+function toPem(label, bytes) {
+  const base64 = forgivingBase64Encode(bytes);
+  const lines = [];
+  for (let i = 0; i < base64.length; i += 64) {
+    ArrayPrototypePush(lines, StringPrototypeSlice(base64, i, i + 64));
+  }
+  return `-----BEGIN ${label}-----\n${
+    ArrayPrototypeJoin(lines, "\n")
+  }\n-----END ${label}-----\n`;
+}
+
+function delay(ms) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+/**
+ * The default storage directory, in `DENO_DIR` and separate for every ACME
+ * server, ie: `$DENO_DIR/acme/acme-v02.api.letsencrypt.org`.
+ */
+function defaultStorageDir(directoryUrl) {
+  const denoDir = op_bootstrap_deno_dir();
+  if (!denoDir) {
+    throw new TypeError("`storageDir` is required without a DENO_DIR");
+  }
+  const host = StringPrototypeReplaceAll(new URL(directoryUrl).host, ":", "_");
+  return `${denoDir}/acme/${host}`;
+}
+
+/** Writes a file atomically, so that a crash never leaves a partial file. */
+async function writeFileAtomic(path, data) {
+  const tempPath = `${path}.tmp`;
+  await writeTextFile(tempPath, data, { mode: 0o600 });
+  await rename(tempPath, path);
+}
+
+class AcmeClient {
+  #directoryUrl;
+  #storageDir;
+  #client;
+  #directory = null;
+  #nonce = null;
+  #accountKey = null;
+  #publicJwk = null;
+  #kid = null;
+  #email;
+
+  constructor(directoryUrl, storageDir, email, caCerts) {
+    this.#directoryUrl = directoryUrl;
+    this.#storageDir = storageDir;
+    this.#email = email;
+    this.#client = caCerts === undefined
+      ? undefined
+      : createHttpClient({ caCerts });
+  }
+
+  #fetch(url, init) {
+    init.client = this.#client;
+    return fetch(url, init);
+  }
+
+  async #getDirectory() {
+    if (this.#directory === null) {
+      const res = await this.#fetch(this.#directoryUrl, {});
+      if (!res.ok) {
+        throw new Error(
+          `Failed to fetch ACME directory ${this.#directoryUrl}: ${res.status}`,
+        );
+      }
+      this.#directory = await res.json();
+    }
+    return this.#directory;
+  }
+
+  async #getNonce() {
+    if (this.#nonce !== null) {
+      const nonce = this.#nonce;
+      this.#nonce = null;
+      return nonce;
+    }
+    const directory = await this.#getDirectory();
+    const res = await this.#fetch(directory.newNonce, { method: "HEAD" });
+    await res.body?.cancel();
+    return res.headers.get("replay-nonce");
+  }
+
+  /**
+   * Sends a JWS signed request. Without a payload, this is a "POST-as-GET"
+   * request.
+   */
+  async #post(url, payload = undefined, retryBadNonce = true) {
+    const header = { alg: "ES256", nonce: await this.#getNonce(), url };
+    if (this.#kid !== null) {
+      header.kid = this.#kid;
+    } else {
+      header.jwk = this.#publicJwk;
+    }
+    const protectedHeader = forgivingBase64UrlEncode(JSONStringify(header));
+    const encodedPayload = payload === undefined
+      ? ""
+      : forgivingBase64UrlEncode(JSONStringify(payload));
+    const signature = await crypto.subtle.sign(
+      ECDSA_SHA256,
+      this.#accountKey,
+      encoder.encode(`${protectedHeader}.${encodedPayload}`),
+    );
+    const res = await this.#fetch(url, {
+      method: "POST",
+      headers: { "content-type": "application/jose+json" },
+      body: JSONStringify({
+        protected: protectedHeader,
+        payload: encodedPayload,
+        signature: forgivingBase64UrlEncode(new Uint8Array(signature)),
+      }),
+    });
+    this.#nonce = res.headers.get("replay-nonce");
+    if (!res.ok) {
+      const problem = await PromisePrototypeThen(
+        res.json(),
+        (problem) => problem,
+        () => ({}),
+      );
+      if (
+        retryBadNonce && problem.type === "urn:ietf:params:acme:error:badNonce"
+      ) {
+        return await this.#post(url, payload, false);
+      }
+      throw new Error(
+        `ACME request to ${url} failed with status ${res.status}: ${
+          problem.detail ?? "unknown error"
+        }`,
+      );
+    }
+    return res;
+  }
+
+  /** Loads the account from the storage directory or registers a new one. */
+  async #ensureAccount() {
+    if (this.#kid !== null) {
+      return;
+    }
+    const accountPath = `${this.#storageDir}/account.json`;
+    let account = null;
+    try {
+      account = JSONParse(await readTextFile(accountPath));
+      if (account.directoryUrl !== this.#directoryUrl) {
+        account = null;
+      }
+    } catch {
+      // no account was registered yet
+    }
+
+    let jwk;
+    if (account !== null) {
+      jwk = account.jwk;
+    } else {
+      const keyPair = await crypto.subtle.generateKey(ECDSA_P256, true, [
+        "sign",
+        "verify",
+      ]);
+      jwk = await crypto.subtle.exportKey("jwk", keyPair.privateKey);
+    }
+    this.#accountKey = await crypto.subtle.importKey(
+      "jwk",
+      jwk,
+      ECDSA_P256,
+      false,
+      ["sign"],
+    );
+    // The members are in lexicographic order, as required for the JWK
+    // thumbprint (RFC 7638).
+    this.#publicJwk = { crv: jwk.crv, kty: jwk.kty, x: jwk.x, y: jwk.y };
+
+    if (account !== null) {
+      this.#kid = account.kid;
+      return;
+    }
+    const directory = await this.#getDirectory();
+    const newAccount = { termsOfServiceAgreed: true };
+    if (this.#email !== undefined) {
+      newAccount.contact = [`mailto:${this.#email}`];
+    }
+    const res = await this.#post(directory.newAccount, newAccount);
+    await res.body?.cancel();
+    this.#kid = res.headers.get("location");
+    await mkdir(this.#storageDir, { recursive: true, mode: 0o700 });
+    await writeFileAtomic(
+      accountPath,
+      JSONStringify({ directoryUrl: this.#directoryUrl, kid: this.#kid, jwk }),
+    );
+  }
+
+  async #thumbprint() {
+    const digest = await crypto.subtle.digest(
+      "SHA-256",
+      encoder.encode(JSONStringify(this.#publicJwk)),
+    );
+    return forgivingBase64UrlEncode(new Uint8Array(digest));
+  }
+
+  async #poll(url, pendingStatuses) {
+    for (let i = 0; i < POLL_ATTEMPTS; ++i) {
+      const resource = await (await this.#post(url)).json();
+      if (!ArrayPrototypeIncludes(pendingStatuses, resource.status)) {
+        return resource;
+      }
+      await delay(POLL_INTERVAL_MS);
+    }
+    throw new Error(`Timed out waiting for ACME resource ${url}`);
+  }
+
+  /**
+   * Issues a certificate for a domain, publishing the HTTP-01 challenge
+   * responses in `challenges` while they are being validated.
+   */
+  async issue(domain, challenges) {
+    await this.#ensureAccount();
+    const directory = await this.#getDirectory();
+    const orderRes = await this.#post(directory.newOrder, {
+      identifiers: [{ type: "dns", value: domain }],
+    });
+    const orderUrl = orderRes.headers.get("location");
+    let order = await orderRes.json();
+
+    const thumbprint = await this.#thumbprint();
+    for (let i = 0; i < order.authorizations.length; ++i) {
+      const authzUrl = order.authorizations[i];
+      const authz = await (await this.#post(authzUrl)).json();
+      if (authz.status === "valid") {
+        continue;
+      }
+      const challenge = ArrayPrototypeFind(
+        authz.challenges,
+        (challenge) => challenge.type === "http-01",
+      );
+      if (challenge === undefined) {
+        throw new Error(`No HTTP-01 challenge offered for '${domain}'`);
+      }
+      challenges.set(challenge.token, `${challenge.token}.${thumbprint}`);
+      try {
+        await (await this.#post(challenge.url, {})).body?.cancel();
+        const result = await this.#poll(authzUrl, ["pending"]);
+        if (result.status !== "valid") {
+          const error = ArrayPrototypeFind(
+            result.challenges,
+            (c) => c.type === "http-01",
+          )?.error;
+          throw new Error(
+            `ACME challenge for '${domain}' failed: ${
+              error?.detail ?? result.status
+            }`,
+          );
+        }
+      } finally {
+        challenges.delete(challenge.token);
+      }
+    }
+
+    const keyPair = await crypto.subtle.generateKey(ECDSA_P256, true, [
+      "sign",
+      "verify",
+    ]);
+    const csr = await createCsr(keyPair, [domain]);
+    await (await this.#post(order.finalize, {
+      csr: forgivingBase64UrlEncode(csr),
+    })).body?.cancel();
+    order = await this.#poll(orderUrl, ["pending", "ready", "processing"]);
+    if (order.status !== "valid") {
+      throw new Error(`ACME order for '${domain}' is ${order.status}`);
+    }
+    const cert = await (await this.#post(order.certificate)).text();
+    const key = toPem(
+      "PRIVATE KEY",
+      new Uint8Array(await crypto.subtle.exportKey("pkcs8", keyPair.privateKey)),
+    );
+    return { cert, key };
+  }
+}
+
+class Acme {
+  #domains;
+  #storageDir;
+  #renewBeforeMs;
+  #client;
+  /** Certificates by domain, with their expiration time. */
+  #certs = new SafeMap();
+  /** Issuances in progress by domain. */
+  #issuing = new SafeMap();
+  /** HTTP-01 key authorizations by challenge token. */
+  #challenges = new SafeMap();
+  /** Evicts a domain from the cache of a listener using `certResolver`. */
+  #evictors = new SafeSet();
+  #renewalTimer = null;
+
+  /** @type {(serverName: string) => Promise<Deno.TlsCertifiedKeyPem | undefined>} */
+  certResolver;
+
+  constructor(options) {
+    const {
+      domains,
+      email = undefined,
+      directoryUrl = LETS_ENCRYPT_DIRECTORY_URL,
+      termsOfServiceAgreed = false,
+      storageDir = defaultStorageDir(directoryUrl),
+      renewBeforeDays = 30,
+      caCerts = undefined,
+    } = options;
+    if (!ArrayIsArray(domains) || domains.length === 0) {
+      throw new TypeError("`domains` must be a non-empty array");
+    }
+    if (typeof storageDir !== "string") {
+      throw new TypeError("`storageDir` must be a string");
+    }
+    if (termsOfServiceAgreed !== true) {
+      throw new TypeError(
+        "The terms of service of the ACME server must be agreed to with `termsOfServiceAgreed: true`",
+      );
+    }
+    const uniqueDomains = [];
+    for (let i = 0; i < domains.length; ++i) {
+      if (typeof domains[i] !== "string") {
+        throw new TypeError("`domains` must only contain strings");
+      }
+      if (!ArrayPrototypeIncludes(uniqueDomains, domains[i])) {
+        ArrayPrototypePush(uniqueDomains, domains[i]);
+      }
+    }
+    this.#domains = ObjectFreeze(uniqueDomains);
+    this.#storageDir = storageDir;
+    this.#renewBeforeMs = renewBeforeDays * DAY_MS;
+    this.#client = new AcmeClient(directoryUrl, storageDir, email, caCerts);
+
+    const certResolver = (serverName) => this.#resolve(serverName);
+    certResolver[certResolverEvictSymbol] = (evict) => {
+      this.#evictors.add(evict);
+      return () => this.#evictors.delete(evict);
+    };
+    this.certResolver = certResolver;
+  }
+
+  /** The domains certificates are provisioned for. */
+  get domains() {
+    return this.#domains;
+  }
+
+  /**
+   * Responds to HTTP-01 challenge requests, returning `null` for any other
+   * request.
+   */
+  handleHttpChallenge(request) {
+    const { pathname } = new URL(request.url);
+    if (!StringPrototypeStartsWith(pathname, CHALLENGE_PATH_PREFIX)) {
+      return null;
+    }
+    const token = StringPrototypeSlice(pathname, CHALLENGE_PATH_PREFIX.length);
+    const keyAuthorization = this.#challenges.get(token);
+    if (keyAuthorization === undefined) {
+      return null;
+    }
+    return new Response(keyAuthorization, {
+      headers: { "content-type": "application/octet-stream" },
+    });
+  }
+
+  async #resolve(serverName) {
+    if (!ArrayPrototypeIncludes(this.#domains, serverName)) {
+      return undefined;
+    }
+    this.#scheduleRenewal();
+    let entry = this.#certs.get(serverName);
+    if (entry === undefined) {
+      entry = await this.#load(serverName);
+    }
+    if (entry === null || entry.notAfter <= DateNow()) {
+      entry = await this.#issue(serverName);
+    } else if (entry.notAfter - this.#renewBeforeMs <= DateNow()) {
+      // Serve the current certificate until the renewed one is available
+      this.#renew(serverName);
+    }
+    return { cert: entry.cert, key: entry.key };
+  }
+
+  async #load(domain) {
+    try {
+      const cert = await readTextFile(`${this.#storageDir}/${domain}.crt`);
+      const key = await readTextFile(`${this.#storageDir}/${domain}.key`);
+      const entry = { cert, key, notAfter: certificateNotAfter(cert) };
+      this.#certs.set(domain, entry);
+      return entry;
+    } catch {
+      return null;
+    }
+  }
+
+  #issue(domain) {
+    let issuing = this.#issuing.get(domain);
+    if (issuing === undefined) {
+      issuing = (async () => {
+        try {
+          const { cert, key } = await this.#client.issue(
+            domain,
+            this.#challenges,
+          );
+          const entry = { cert, key, notAfter: certificateNotAfter(cert) };
+          await mkdir(this.#storageDir, { recursive: true, mode: 0o700 });
+          await writeFileAtomic(`${this.#storageDir}/${domain}.key`, key);
+          await writeFileAtomic(`${this.#storageDir}/${domain}.crt`, cert);
+          this.#certs.set(domain, entry);
+          for (const evict of new SafeSetIterator(this.#evictors)) {
+            evict(domain);
+          }
+          return entry;
+        } finally {
+          this.#issuing.delete(domain);
+        }
+      })();
+      this.#issuing.set(domain, issuing);
+    }
+    return issuing;
+  }
+
+  #renew(domain) {
+    PromisePrototypeThen(this.#issue(domain), undefined, (error) => {
+      console.error(`Failed to renew the certificate for '${domain}':`, error);
+    });
+  }
+
+  #scheduleRenewal() {
+    if (this.#renewalTimer !== null) {
+      return;
+    }
+    this.#renewalTimer = setTimeout(() => {
+      this.#renewalTimer = null;
+      for (const { 0: domain, 1: entry } of new SafeMapIterator(this.#certs)) {
+        if (entry.notAfter - this.#renewBeforeMs <= DateNow()) {
+          this.#renew(domain);
+        }
+      }
+      this.#scheduleRenewal();
+    }, RENEWAL_CHECK_INTERVAL_MS);
+    // The renewal check must not keep the process alive by itself
+    unrefTimer(this.#renewalTimer);
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return `Acme ${inspect({ domains: this.#domains }, inspectOptions)}`;
+  }
+}
+
+function createAcme(options) {
+  return new Acme(options);
+}
+
+export { createAcme };
@@ -0,0 +1,117 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  op_resource_scope_close,
+  op_resource_scope_new,
+  op_resource_scope_track,
+} from "ext:core/ops";
+const {
+  AggregateError,
+  ArrayPrototypePush,
+  FunctionPrototypeCall,
+  ReferenceError,
+  TypeError,
+} = primordials;
+
+import { SymbolAsyncDispose, SymbolDispose } from "ext:deno_web/00_infra.js";
+import { AbortController } from "ext:deno_web/03_abort_signal.js";
+
+let getResourceScopeRid;
+
+class ResourceScope {
+  #rid;
+  #controller = new AbortController();
+  #disposers = [];
+  #disposed = false;
+
+  static {
+    // Lets other runtime modules pass the scope to ops that track the
+    // resources they create.
+    getResourceScopeRid = (scope) => scope.#rid;
+  }
+
+  constructor() {
+    this.#rid = op_resource_scope_new();
+  }
+
+  get signal() {
+    return this.#controller.signal;
+  }
+
+  get disposed() {
+    return this.#disposed;
+  }
+
+  use(value) {
+    this.#assertNotDisposed();
+    if (typeof value === "number") {
+      op_resource_scope_track(this.#rid, value);
+      return value;
+    }
+    if (value === null || value === undefined) {
+      return value;
+    }
+
+    const dispose = value[SymbolAsyncDispose] ?? value[SymbolDispose];
+    if (typeof dispose !== "function") {
+      throw new TypeError(
+        "Value must be a resource id or implement Symbol.asyncDispose or Symbol.dispose",
+      );
+    }
+    ArrayPrototypePush(
+      this.#disposers,
+      () => FunctionPrototypeCall(dispose, value),
+    );
+    return value;
+  }
+
+  defer(onDispose) {
+    this.#assertNotDisposed();
+    if (typeof onDispose !== "function") {
+      throw new TypeError("onDispose must be a function");
+    }
+    ArrayPrototypePush(this.#disposers, onDispose);
+  }
+
+  async [SymbolAsyncDispose]() {
+    if (this.#disposed) {
+      return;
+    }
+    this.#disposed = true;
+
+    // Cancels the pending operations that were passed the signal first, so
+    // that disposing the resources doesn't wait on them.
+    this.#controller.abort();
+
+    const errors = [];
+    const disposers = this.#disposers;
+    this.#disposers = [];
+    for (let i = disposers.length - 1; i >= 0; --i) {
+      try {
+        await disposers[i]();
+      } catch (error) {
+        ArrayPrototypePush(errors, error);
+      }
+    }
+    op_resource_scope_close(this.#rid);
+
+    if (errors.length === 1) {
+      throw errors[0];
+    }
+    if (errors.length > 1) {
+      throw new AggregateError(
+        errors,
+        "Failed to dispose some resources of the scope",
+      );
+    }
+  }
+
+  #assertNotDisposed() {
+    if (this.#disposed) {
+      throw new ReferenceError("The resource scope was disposed");
+    }
+  }
+}
+
+export { getResourceScopeRid, ResourceScope };
@@ -0,0 +1,266 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+import { core, primordials } from "ext:core/mod.js";
+import { op_sync_channel_notify, op_sync_channel_wait } from "ext:core/ops";
+const {
+  isSharedArrayBuffer,
+} = core;
+const {
+  DateNow,
+  Int32Array,
+  MathCeil,
+  NumberIsInteger,
+  NumberIsNaN,
+  ObjectPrototypeIsPrototypeOf,
+  RangeError,
+  TypeError,
+  TypedArrayPrototypeGetByteLength,
+  TypedArrayPrototypeSet,
+  TypedArrayPrototypeSlice,
+  Uint8Array,
+  Uint8ArrayPrototype,
+} = primordials;
+
+import { errors } from "ext:runtime/01_errors.js";
+
+// Atomics and SharedArrayBuffer are not part of the primordials yet
+// deno-lint-ignore prefer-primordials
+const { Atomics, SharedArrayBuffer } = globalThis;
+const {
+  add: AtomicsAdd,
+  compareExchange: AtomicsCompareExchange,
+  exchange: AtomicsExchange,
+  load: AtomicsLoad,
+  store: AtomicsStore,
+} = Atomics;
+
+// Indices of the header fields, which come before the slots in the buffer.
+// Every slot holds the length of its message followed by the message.
+const LOCK = 0; // 0: unlocked, 1: locked, 2: locked with waiters
+const HEAD = 1; // index of the slot of the next message to receive
+const LENGTH = 2; // number of messages in the channel
+const CLOSED = 3;
+const CAPACITY = 4;
+const MESSAGE_SIZE = 5;
+const READABLE = 6; // changes when a message is sent or the channel closes
+const WRITABLE = 7; // changes when a message is received or it closes
+const READERS = 8; // number of receivers waiting for READABLE
+const WRITERS = 9; // number of senders waiting for WRITABLE
+const HEADER_LENGTH = 10;
+const HEADER_SIZE = HEADER_LENGTH * 4;
+
+const MAX_NOTIFY_COUNT = 0x7fffffff;
+
+function slotSize(messageSize) {
+  return 4 + MathCeil(messageSize / 4) * 4;
+}
+
+function validateSize(value, name, min) {
+  if (!NumberIsInteger(value) || value < min || value > 0x7fffffff) {
+    throw new RangeError(
+      `${name} must be an integer between ${min} and ${0x7fffffff}`,
+    );
+  }
+}
+
+function validateTimeout(timeout) {
+  if (typeof timeout !== "number" || NumberIsNaN(timeout)) {
+    throw new TypeError("Timeout must be a number");
+  }
+  return DateNow() + timeout;
+}
+
+class SyncChannel {
+  #buffer;
+  #words;
+  #bytes;
+  #capacity;
+  #messageSize;
+  #slotSize;
+
+  constructor(capacity, messageSize) {
+    let buffer;
+    if (isSharedArrayBuffer(capacity)) {
+      buffer = capacity;
+      if (buffer.byteLength < HEADER_SIZE) {
+        throw new TypeError("Buffer is not the buffer of a SyncChannel");
+      }
+      const words = new Int32Array(buffer, 0, HEADER_LENGTH);
+      capacity = AtomicsLoad(words, CAPACITY);
+      messageSize = AtomicsLoad(words, MESSAGE_SIZE);
+      if (
+        capacity < 1 ||
+        buffer.byteLength !== HEADER_SIZE + capacity * slotSize(messageSize)
+      ) {
+        throw new TypeError("Buffer is not the buffer of a SyncChannel");
+      }
+    } else {
+      validateSize(capacity, "Capacity", 1);
+      validateSize(messageSize, "Message size", 0);
+      const size = HEADER_SIZE + capacity * slotSize(messageSize);
+      if (size > 0x7fffffff) {
+        throw new RangeError("Channel is too large");
+      }
+      buffer = new SharedArrayBuffer(size);
+      const words = new Int32Array(buffer, 0, HEADER_LENGTH);
+      AtomicsStore(words, CAPACITY, capacity);
+      AtomicsStore(words, MESSAGE_SIZE, messageSize);
+    }
+    this.#buffer = buffer;
+    this.#words = new Int32Array(buffer);
+    this.#bytes = new Uint8Array(buffer);
+    this.#capacity = capacity;
+    this.#messageSize = messageSize;
+    this.#slotSize = slotSize(messageSize);
+  }
+
+  get buffer() {
+    return this.#buffer;
+  }
+
+  get capacity() {
+    return this.#capacity;
+  }
+
+  get messageSize() {
+    return this.#messageSize;
+  }
+
+  get closed() {
+    return AtomicsLoad(this.#words, CLOSED) === 1;
+  }
+
+  send(data, timeout = Infinity) {
+    if (!ObjectPrototypeIsPrototypeOf(Uint8ArrayPrototype, data)) {
+      throw new TypeError("Message must be a Uint8Array");
+    }
+    const size = TypedArrayPrototypeGetByteLength(data);
+    if (size > this.#messageSize) {
+      throw new RangeError(
+        `Message of ${size} bytes exceeds the message size of the channel (${this.#messageSize} bytes)`,
+      );
+    }
+    const deadline = validateTimeout(timeout);
+    const words = this.#words;
+    this.#lock();
+    while (true) {
+      if (AtomicsLoad(words, CLOSED) === 1) {
+        this.#unlock();
+        throw new errors.BrokenPipe("Channel is closed");
+      }
+      const length = AtomicsLoad(words, LENGTH);
+      if (length < this.#capacity) {
+        const head = AtomicsLoad(words, HEAD);
+        const slot = (head + length) % this.#capacity;
+        const offset = HEADER_SIZE + slot * this.#slotSize;
+        AtomicsStore(words, offset / 4, size);
+        TypedArrayPrototypeSet(this.#bytes, data, offset + 4);
+        AtomicsStore(words, LENGTH, length + 1);
+        AtomicsAdd(words, READABLE, 1);
+        const readers = AtomicsLoad(words, READERS);
+        this.#unlock();
+        if (readers > 0) {
+          op_sync_channel_notify(this.#bytes, READABLE, 1);
+        }
+        return true;
+      }
+      const remaining = deadline - DateNow();
+      if (remaining <= 0) {
+        this.#unlock();
+        return false;
+      }
+      this.#wait(WRITABLE, WRITERS, remaining);
+    }
+  }
+
+  trySend(data) {
+    return this.send(data, 0);
+  }
+
+  recv(timeout = Infinity) {
+    const deadline = validateTimeout(timeout);
+    const words = this.#words;
+    this.#lock();
+    while (true) {
+      const length = AtomicsLoad(words, LENGTH);
+      if (length > 0) {
+        const head = AtomicsLoad(words, HEAD);
+        const offset = HEADER_SIZE + head * this.#slotSize;
+        const size = AtomicsLoad(words, offset / 4);
+        const data = TypedArrayPrototypeSlice(
+          this.#bytes,
+          offset + 4,
+          offset + 4 + size,
+        );
+        AtomicsStore(words, HEAD, (head + 1) % this.#capacity);
+        AtomicsStore(words, LENGTH, length - 1);
+        AtomicsAdd(words, WRITABLE, 1);
+        const writers = AtomicsLoad(words, WRITERS);
+        this.#unlock();
+        if (writers > 0) {
+          op_sync_channel_notify(this.#bytes, WRITABLE, 1);
+        }
+        return data;
+      }
+      const remaining = deadline - DateNow();
+      if (AtomicsLoad(words, CLOSED) === 1 || remaining <= 0) {
+        this.#unlock();
+        return null;
+      }
+      this.#wait(READABLE, READERS, remaining);
+    }
+  }
+
+  tryRecv() {
+    return this.recv(0);
+  }
+
+  close() {
+    const words = this.#words;
+    this.#lock();
+    if (AtomicsLoad(words, CLOSED) === 1) {
+      this.#unlock();
+      return;
+    }
+    AtomicsStore(words, CLOSED, 1);
+    AtomicsAdd(words, READABLE, 1);
+    AtomicsAdd(words, WRITABLE, 1);
+    this.#unlock();
+    op_sync_channel_notify(this.#bytes, READABLE, MAX_NOTIFY_COUNT);
+    op_sync_channel_notify(this.#bytes, WRITABLE, MAX_NOTIFY_COUNT);
+  }
+
+  /** Waits for a change of `index`, with the lock held. */
+  #wait(index, waiters, timeout) {
+    const words = this.#words;
+    const value = AtomicsLoad(words, index);
+    AtomicsAdd(words, waiters, 1);
+    this.#unlock();
+    op_sync_channel_wait(this.#bytes, index, value, timeout);
+    this.#lock();
+    AtomicsAdd(words, waiters, -1);
+  }
+
+  #lock() {
+    const words = this.#words;
+    let state = AtomicsCompareExchange(words, LOCK, 0, 1);
+    if (state === 0) {
+      return;
+    }
+    if (state !== 2) {
+      state = AtomicsExchange(words, LOCK, 2);
+    }
+    while (state !== 0) {
+      op_sync_channel_wait(this.#bytes, LOCK, 2, Infinity);
+      state = AtomicsExchange(words, LOCK, 2);
+    }
+  }
+
+  #unlock() {
+    if (AtomicsExchange(this.#words, LOCK, 0) === 2) {
+      op_sync_channel_notify(this.#bytes, LOCK, 1);
+    }
+  }
+}
+
+export { SyncChannel };
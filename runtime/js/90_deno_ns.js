@@ -5,6 +5,7 @@ import {
   op_net_listen_udp,
   op_net_listen_unixpacket,
   op_runtime_memory_usage,
+  op_runtime_metrics,
 } from "ext:core/ops";
 
 import * as timers from "ext:deno_web/02_timers.js";
@@ -27,6 +28,9 @@ import * as fsEvents from "ext:runtime/40_fs_events.js";
 import * as process from "ext:runtime/40_process.js";
 import * as signals from "ext:runtime/40_signals.js";
 import * as tty from "ext:runtime/40_tty.js";
+import * as acme from "ext:runtime/41_acme.js";
+import * as resourceScope from "ext:runtime/41_resource_scope.js";
+import * as syncChannel from "ext:runtime/41_sync_channel.js";
 import * as kv from "ext:deno_kv/01_db.ts";
 import * as cron from "ext:deno_cron/01_cron.ts";
 import * as webgpuSurface from "ext:deno_webgpu/02_surface.js";
@@ -247,6 +251,7 @@ const denoNs = {
   Command: process.Command,
   // TODO(bartlomieju): why is this exported?
   ChildProcess: process.ChildProcess,
+  ResourceScope: resourceScope.ResourceScope,
 };
 
 // NOTE(bartlomieju): keep IDs in sync with `cli/main.rs`
@@ -262,6 +267,8 @@ const unstableIds = {
   unsafeProto: 9,
   webgpu: 10,
   workerOptions: 11,
+  process: 12,
+  metrics: 13,
 };
 
 const denoNsUnstableById = {};
@@ -274,6 +281,7 @@ denoNsUnstableById[unstableIds.cron] = {
 
 denoNsUnstableById[unstableIds.ffi] = {
   dlopen: ffi.dlopen,
+  dlopenStatic: ffi.dlopenStatic,
   UnsafeCallback: ffi.UnsafeCallback,
   UnsafePointer: ffi.UnsafePointer,
   UnsafePointerView: ffi.UnsafePointerView,
@@ -301,11 +309,21 @@ denoNsUnstableById[unstableIds.kv] = {
   KvListIterator: kv.KvListIterator,
 };
 
+denoNsUnstableById[unstableIds.metrics] = {
+  runtimeMetrics: () => op_runtime_metrics(),
+};
+
 denoNsUnstableById[unstableIds.net] = {
   listenDatagram: net.createListenDatagram(
     op_net_listen_udp,
     op_net_listen_unixpacket,
   ),
+  createAcme: acme.createAcme,
+  watchNetworkInterfaces: os.watchNetworkInterfaces,
+};
+
+denoNsUnstableById[unstableIds.process] = {
+  spawnPipeline: process.spawnPipeline,
 };
 
 // denoNsUnstableById[unstableIds.unsafeProto] = {}
@@ -314,7 +332,9 @@ denoNsUnstableById[unstableIds.webgpu] = {
   UnsafeWindowSurface: webgpuSurface.UnsafeWindowSurface,
 };
 
-// denoNsUnstableById[unstableIds.workerOptions] = {}
+denoNsUnstableById[unstableIds.workerOptions] = {
+  SyncChannel: syncChannel.SyncChannel,
+};
 
 // when editing this list, also update unstableDenoProps in cli/tsc/99_main_compiler.js
 const denoNsUnstable = {
@@ -326,6 +346,7 @@ const denoNsUnstable = {
   HttpClient: httpClient.HttpClient,
   createHttpClient: httpClient.createHttpClient,
   dlopen: ffi.dlopen,
+  dlopenStatic: ffi.dlopenStatic,
   UnsafeCallback: ffi.UnsafeCallback,
   UnsafePointer: ffi.UnsafePointer,
   UnsafePointerView: ffi.UnsafePointerView,
@@ -341,6 +362,10 @@ const denoNsUnstable = {
   KvU64: kv.KvU64,
   KvListIterator: kv.KvListIterator,
   cron: cron.cron,
+  createAcme: acme.createAcme,
+  watchNetworkInterfaces: os.watchNetworkInterfaces,
+  spawnPipeline: process.spawnPipeline,
+  SyncChannel: syncChannel.SyncChannel,
 };
 
 export { denoNs, denoNsUnstable, denoNsUnstableById, unstableIds };
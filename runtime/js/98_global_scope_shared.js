@@ -31,6 +31,7 @@ import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { DOMException } from "ext:deno_web/01_dom_exception.js";
 import * as abortSignal from "ext:deno_web/03_abort_signal.js";
 import * as imageData from "ext:deno_web/16_image_data.js";
+import * as scheduler from "ext:deno_web/17_scheduler.js";
 import { loadWebGPU } from "ext:deno_webgpu/00_init.js";
 import * as webgpuSurface from "ext:deno_webgpu/02_surface.js";
 import { unstableIds } from "ext:runtime/90_deno_ns.js";
@@ -79,6 +80,7 @@ const windowOrWorkerGlobalScope = {
   ),
   Request: core.propNonEnumerable(request.Request),
   Response: core.propNonEnumerable(response.Response),
+  Scheduler: core.propNonEnumerable(scheduler.Scheduler),
   TextDecoder: core.propNonEnumerable(encoding.TextDecoder),
   TextEncoder: core.propNonEnumerable(encoding.TextEncoder),
   TextDecoderStream: core.propNonEnumerable(encoding.TextDecoderStream),
@@ -138,6 +140,7 @@ const windowOrWorkerGlobalScope = {
   EventSource: core.propWritable(eventSource.EventSource),
   performance: core.propWritable(performance.performance),
   reportError: core.propWritable(event.reportError),
+  scheduler: core.propWritable(scheduler.scheduler),
   setInterval: core.propWritable(timers.setInterval),
   setTimeout: core.propWritable(timers.setTimeout),
   structuredClone: core.propWritable(messagePort.structuredClone),
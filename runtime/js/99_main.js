@@ -14,6 +14,7 @@ import {
   op_bootstrap_pid,
   op_main_module,
   op_ppid,
+  op_runtime_op_metrics,
   op_set_format_exception_callback,
   op_snapshot_options,
   op_worker_close,
@@ -61,6 +62,7 @@ import * as version from "ext:runtime/01_version.ts";
 import * as os from "ext:runtime/30_os.js";
 import * as timers from "ext:deno_web/02_timers.js";
 import {
+  Console,
   customInspect,
   getDefaultInspectOptions,
   getStderrNoColor,
@@ -631,7 +633,11 @@ function removeImportedOps() {
 // FIXME(bartlomieju): temporarily add whole `Deno.core` to
 // `Deno[Deno.internal]` namespace. It should be removed and only necessary
 // methods should be left there.
-ObjectAssign(internals, { core, warnOnDeprecatedApi });
+ObjectAssign(internals, {
+  core,
+  warnOnDeprecatedApi,
+  opMetrics: op_runtime_op_metrics,
+});
 const internalSymbol = Symbol("Deno.internal");
 const finalDenoNs = {
   internal: internalSymbol,
@@ -664,6 +670,17 @@ ObjectDefineProperties(finalDenoNs, {
   noColor: core.propGetterOnly(() => op_bootstrap_no_color()),
   args: core.propGetterOnly(opArgs),
   mainModule: core.propGetterOnly(() => op_main_module()),
+  exitCode: {
+    __proto__: null,
+    get() {
+      return os.getExitCode();
+    },
+    set(value) {
+      os.setExitCode(value);
+    },
+    enumerable: true,
+    configurable: true,
+  },
   // TODO(kt3k): Remove this export at v2
   // See https://github.com/denoland/deno/issues/9294
   customInspect: {
@@ -697,6 +714,20 @@ const executionModes = {
   jupyter: 8,
 };
 
+// Replaces the global `console` with one that prefixes every printed line
+// with a timestamp (`DENO_CONSOLE_TIMESTAMPS`).
+function enableConsoleTimestamps() {
+  ObjectDefineProperty(
+    globalThis,
+    "console",
+    core.propNonEnumerable(
+      new Console((msg, level) => core.print(msg, level > 1), {
+        timestamps: true,
+      }),
+    ),
+  );
+}
+
 function bootstrapMainRuntime(runtimeOptions, warmup = false) {
   if (!warmup) {
     if (hasBootstrapped) {
@@ -717,6 +748,8 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
       11: mode,
       12: servePort,
       13: serveHost,
+      14: preserveSymlinks,
+      15: consoleTimestamps,
     } = runtimeOptions;
 
     if (mode === executionModes.run || mode === executionModes.serve) {
@@ -803,6 +836,10 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
     });
     ObjectSetPrototypeOf(globalThis, Window.prototype);
 
+    if (consoleTimestamps) {
+      enableConsoleTimestamps();
+    }
+
     if (inspectFlag) {
       const consoleFromDeno = globalThis.console;
       core.wrapConsole(consoleFromDeno, core.v8Console);
@@ -866,6 +903,7 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
     if (nodeBootstrap) {
       nodeBootstrap({
         usesLocalNodeModulesDir: hasNodeModulesDir,
+        preserveSymlinks,
         runningOnMainThread: true,
         argv0,
         nodeDebug,
@@ -931,6 +969,8 @@ function bootstrapWorkerRuntime(
       8: shouldDisableDeprecatedApiWarning,
       9: shouldUseVerboseDeprecatedApiWarning,
       10: future,
+      14: preserveSymlinks,
+      15: consoleTimestamps,
     } = runtimeOptions;
 
     // TODO(iuioiua): remove in Deno v2. This allows us to dynamically delete
@@ -968,6 +1008,10 @@ function bootstrapWorkerRuntime(
     }
     ObjectSetPrototypeOf(globalThis, DedicatedWorkerGlobalScope.prototype);
 
+    if (consoleTimestamps) {
+      enableConsoleTimestamps();
+    }
+
     const consoleFromDeno = globalThis.console;
     core.wrapConsole(consoleFromDeno, core.v8Console);
 
@@ -1005,6 +1049,7 @@ function bootstrapWorkerRuntime(
 
     // Not available in workers
     delete finalDenoNs.mainModule;
+    delete finalDenoNs.exitCode;
 
     if (!ArrayPrototypeIncludes(unstableFeatures, unstableIds.unsafeProto)) {
       // Removes the `__proto__` for security reasons.
@@ -1029,6 +1074,7 @@ function bootstrapWorkerRuntime(
     if (nodeBootstrap) {
       nodeBootstrap({
         usesLocalNodeModulesDir: hasNodeModulesDir,
+        preserveSymlinks,
         runningOnMainThread: false,
         argv0,
         workerId,
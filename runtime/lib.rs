@@ -32,6 +32,9 @@ pub mod fmt_errors;
 pub mod fs_util;
 pub mod inspector_server;
 pub mod js;
+pub mod metrics;
+pub mod module_manifest;
+pub mod op_timing;
 pub mod ops;
 pub mod permissions;
 pub mod snapshot;
@@ -86,11 +89,21 @@ pub static UNSTABLE_GRANULAR_FLAGS: &[(
     "Enable unstable Key-Value store APIs",
     6,
   ),
+  (
+    ops::runtime::UNSTABLE_FEATURE_NAME,
+    "Enable unstable runtime metrics API",
+    13,
+  ),
   (
     deno_net::UNSTABLE_FEATURE_NAME,
     "Enable unstable net APIs",
     7,
   ),
+  (
+    ops::process::UNSTABLE_FEATURE_NAME,
+    "Enable unstable process APIs",
+    12,
+  ),
   ("temporal", "Enable unstable Temporal API", 8),
   (
     "unsafe-proto",
@@ -0,0 +1,436 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Runtime metrics returned by `Deno.runtimeMetrics()`, collected when
+//! running with `--unstable-metrics`. The per-op timings come from
+//! [`crate::op_timing::OpTimingTracker`], this module collects the rest:
+//! the bytes moved through resources, the module loads and the event loop
+//! lag. Nothing is hooked in unless the flag is passed.
+
+use crate::op_timing::OpTiming;
+use deno_core::error::AnyError;
+use deno_core::futures::FutureExt;
+use deno_core::op2;
+use deno_core::BufMutView;
+use deno_core::BufView;
+use deno_core::JsBuffer;
+use deno_core::ModuleLoadResponse;
+use deno_core::ModuleLoader;
+use deno_core::ModuleSpecifier;
+use deno_core::OpDecl;
+use deno_core::OpState;
+use deno_core::RequestedModuleType;
+use deno_core::ResolutionKind;
+use deno_core::Resource;
+use deno_core::ResourceId;
+use serde::Serialize;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How often the event loop lag is sampled.
+const EVENT_LOOP_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+/// The number of lag samples kept, the percentiles are computed over the
+/// last ~10 seconds.
+const EVENT_LOOP_SAMPLES: usize = 1024;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceBytes {
+  pub bytes_read: u64,
+  pub bytes_written: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleLoads {
+  /// The number of modules loaded, including the dynamic imports.
+  pub loaded: u64,
+  pub dynamic: u64,
+  pub failed: u64,
+}
+
+/// How late the event loop was to wake up a timer, in microseconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventLoopLag {
+  /// The number of samples the other fields are computed from.
+  pub samples: u64,
+  pub mean_us: u64,
+  pub p50_us: u64,
+  pub p90_us: u64,
+  pub p99_us: u64,
+  pub max_us: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeMetricsSnapshot {
+  pub ops: BTreeMap<&'static str, OpTiming>,
+  /// Keyed by resource name.
+  pub resources: BTreeMap<String, ResourceBytes>,
+  pub modules: ModuleLoads,
+  pub event_loop: EventLoopLag,
+}
+
+#[derive(Default)]
+pub struct RuntimeMetrics {
+  resources: RefCell<HashMap<String, ResourceBytes>>,
+  modules: RefCell<ModuleLoads>,
+  lag_samples: RefCell<VecDeque<u64>>,
+  sampling: Cell<bool>,
+}
+
+impl RuntimeMetrics {
+  fn record_resource(
+    &self,
+    resource: &dyn Resource,
+    read: usize,
+    written: usize,
+  ) {
+    let name = resource.name();
+    let mut resources = self.resources.borrow_mut();
+    let bytes = match resources.get_mut(name.as_ref()) {
+      Some(bytes) => bytes,
+      None => resources.entry(name.into_owned()).or_default(),
+    };
+    bytes.bytes_read += read as u64;
+    bytes.bytes_written += written as u64;
+  }
+
+  fn record_module_load(&self, is_dyn_import: bool, ok: bool) {
+    let mut modules = self.modules.borrow_mut();
+    if ok {
+      modules.loaded += 1;
+      if is_dyn_import {
+        modules.dynamic += 1;
+      }
+    } else {
+      modules.failed += 1;
+    }
+  }
+
+  fn record_lag(&self, lag: Duration) {
+    let us = u64::try_from(lag.as_micros()).unwrap_or(u64::MAX);
+    let mut samples = self.lag_samples.borrow_mut();
+    if samples.len() == EVENT_LOOP_SAMPLES {
+      samples.pop_front();
+    }
+    samples.push_back(us);
+  }
+
+  fn event_loop_lag(&self) -> EventLoopLag {
+    let mut samples = self
+      .lag_samples
+      .borrow()
+      .iter()
+      .copied()
+      .collect::<Vec<_>>();
+    if samples.is_empty() {
+      return EventLoopLag::default();
+    }
+    samples.sort_unstable();
+    // nearest-rank percentile
+    let percentile = |p: usize| {
+      let rank = (p * samples.len()).div_ceil(100);
+      samples[rank.saturating_sub(1)]
+    };
+    EventLoopLag {
+      samples: samples.len() as u64,
+      mean_us: samples.iter().sum::<u64>() / samples.len() as u64,
+      p50_us: percentile(50),
+      p90_us: percentile(90),
+      p99_us: percentile(99),
+      max_us: samples[samples.len() - 1],
+    }
+  }
+
+  pub fn snapshot(
+    &self,
+    ops: BTreeMap<&'static str, OpTiming>,
+  ) -> RuntimeMetricsSnapshot {
+    RuntimeMetricsSnapshot {
+      ops,
+      resources: self
+        .resources
+        .borrow()
+        .iter()
+        .map(|(name, bytes)| (name.clone(), bytes.clone()))
+        .collect(),
+      modules: self.modules.borrow().clone(),
+      event_loop: self.event_loop_lag(),
+    }
+  }
+
+  /// Spawns the task sampling the event loop lag on the current thread. It
+  /// sleeps for a fixed interval in a loop, and the time it wakes up past
+  /// that interval is the time the thread was busy. The task stops once
+  /// the metrics are dropped with their worker.
+  ///
+  /// Must be called from within the tokio runtime of the worker.
+  pub fn start_event_loop_sampler(self: &Rc<Self>) {
+    if self.sampling.replace(true) {
+      return;
+    }
+    let metrics = Rc::downgrade(self);
+    deno_core::unsync::spawn(async move {
+      loop {
+        let start = Instant::now();
+        tokio::time::sleep(EVENT_LOOP_SAMPLE_INTERVAL).await;
+        let Some(metrics) = metrics.upgrade() else {
+          break;
+        };
+        metrics.record_lag(
+          start.elapsed().saturating_sub(EVENT_LOOP_SAMPLE_INTERVAL),
+        );
+      }
+    });
+  }
+
+  /// Wraps a module loader to count the modules it loads.
+  pub fn module_loader(
+    self: &Rc<Self>,
+    inner: Rc<dyn ModuleLoader>,
+  ) -> Rc<dyn ModuleLoader> {
+    Rc::new(MetricsModuleLoader {
+      inner,
+      metrics: self.clone(),
+    })
+  }
+}
+
+/// An op middleware replacing the generic resource read and write ops of
+/// `deno_core` with versions counting the bytes they move. Ops with their
+/// own data path, like the fetch and HTTP body ops, aren't counted.
+pub fn instrument_op(op: OpDecl) -> OpDecl {
+  match op.name {
+    "op_read" => op.with_implementation_from(&op_metrics_read()),
+    "op_write" => op.with_implementation_from(&op_metrics_write()),
+    "op_read_sync" => op.with_implementation_from(&op_metrics_read_sync()),
+    "op_write_sync" => op.with_implementation_from(&op_metrics_write_sync()),
+    "op_write_all" => op.with_implementation_from(&op_metrics_write_all()),
+    _ => op,
+  }
+}
+
+fn record_resource(
+  state: &OpState,
+  resource: &dyn Resource,
+  read: usize,
+  written: usize,
+) {
+  if let Some(metrics) = state.try_borrow::<Rc<RuntimeMetrics>>() {
+    metrics.record_resource(resource, read, written);
+  }
+}
+
+#[op2(async)]
+async fn op_metrics_read(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] buf: JsBuffer,
+) -> Result<u32, AnyError> {
+  let resource = state.borrow().resource_table.get_any(rid)?;
+  let view = BufMutView::from(buf);
+  let (nread, _) = resource.clone().read_byob(view).await?;
+  record_resource(&state.borrow(), &*resource, nread, 0);
+  Ok(nread as u32)
+}
+
+#[op2(async)]
+async fn op_metrics_write(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] buf: JsBuffer,
+) -> Result<u32, AnyError> {
+  let resource = state.borrow().resource_table.get_any(rid)?;
+  let view = BufView::from(buf);
+  let nwritten = resource.clone().write(view).await?.nwritten();
+  record_resource(&state.borrow(), &*resource, 0, nwritten);
+  Ok(nwritten as u32)
+}
+
+#[op2(fast)]
+fn op_metrics_read_sync(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] data: &mut [u8],
+) -> Result<u32, AnyError> {
+  let resource = state.borrow_mut().resource_table.get_any(rid)?;
+  let nread = resource.clone().read_byob_sync(data)?;
+  record_resource(&state.borrow(), &*resource, nread, 0);
+  Ok(nread as u32)
+}
+
+#[op2(fast)]
+fn op_metrics_write_sync(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] data: &[u8],
+) -> Result<u32, AnyError> {
+  let resource = state.borrow_mut().resource_table.get_any(rid)?;
+  let nwritten = resource.clone().write_sync(data)?;
+  record_resource(&state.borrow(), &*resource, 0, nwritten);
+  Ok(nwritten as u32)
+}
+
+#[op2(async)]
+async fn op_metrics_write_all(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[buffer] buf: JsBuffer,
+) -> Result<(), AnyError> {
+  let resource = state.borrow().resource_table.get_any(rid)?;
+  let view = BufView::from(buf);
+  let len = view.len();
+  resource.clone().write_all(view).await?;
+  record_resource(&state.borrow(), &*resource, 0, len);
+  Ok(())
+}
+
+struct MetricsModuleLoader {
+  inner: Rc<dyn ModuleLoader>,
+  metrics: Rc<RuntimeMetrics>,
+}
+
+impl ModuleLoader for MetricsModuleLoader {
+  fn resolve(
+    &self,
+    specifier: &str,
+    referrer: &str,
+    kind: ResolutionKind,
+  ) -> Result<ModuleSpecifier, AnyError> {
+    self.inner.resolve(specifier, referrer, kind)
+  }
+
+  fn load(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    maybe_referrer: Option<&ModuleSpecifier>,
+    is_dyn_import: bool,
+    requested_module_type: RequestedModuleType,
+  ) -> ModuleLoadResponse {
+    let response = self.inner.load(
+      module_specifier,
+      maybe_referrer,
+      is_dyn_import,
+      requested_module_type,
+    );
+    match response {
+      ModuleLoadResponse::Sync(result) => {
+        self
+          .metrics
+          .record_module_load(is_dyn_import, result.is_ok());
+        ModuleLoadResponse::Sync(result)
+      }
+      ModuleLoadResponse::Async(future) => {
+        let metrics = self.metrics.clone();
+        ModuleLoadResponse::Async(
+          future
+            .inspect(move |result| {
+              metrics.record_module_load(is_dyn_import, result.is_ok())
+            })
+            .boxed_local(),
+        )
+      }
+    }
+  }
+
+  fn prepare_load(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    maybe_referrer: Option<String>,
+    is_dyn_import: bool,
+  ) -> Pin<Box<dyn Future<Output = Result<(), AnyError>>>> {
+    self
+      .inner
+      .prepare_load(module_specifier, maybe_referrer, is_dyn_import)
+  }
+
+  fn code_cache_ready(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    code_cache: &[u8],
+  ) -> Pin<Box<dyn Future<Output = ()>>> {
+    self.inner.code_cache_ready(module_specifier, code_cache)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::borrow::Cow;
+
+  struct TestResource;
+
+  impl Resource for TestResource {
+    fn name(&self) -> Cow<str> {
+      "test".into()
+    }
+  }
+
+  #[test]
+  fn resource_bytes() {
+    let metrics = RuntimeMetrics::default();
+    metrics.record_resource(&TestResource, 10, 0);
+    metrics.record_resource(&TestResource, 5, 3);
+    let snapshot = metrics.snapshot(Default::default());
+    assert_eq!(
+      snapshot.resources["test"],
+      ResourceBytes {
+        bytes_read: 15,
+        bytes_written: 3,
+      }
+    );
+  }
+
+  #[test]
+  fn module_loads() {
+    let metrics = RuntimeMetrics::default();
+    metrics.record_module_load(false, true);
+    metrics.record_module_load(true, true);
+    metrics.record_module_load(true, false);
+    assert_eq!(
+      metrics.snapshot(Default::default()).modules,
+      ModuleLoads {
+        loaded: 2,
+        dynamic: 1,
+        failed: 1,
+      }
+    );
+  }
+
+  #[test]
+  fn event_loop_lag_percentiles() {
+    let metrics = RuntimeMetrics::default();
+    assert_eq!(metrics.event_loop_lag(), EventLoopLag::default());
+    for us in (1..=100).rev() {
+      metrics.record_lag(Duration::from_micros(us));
+    }
+    assert_eq!(
+      metrics.event_loop_lag(),
+      EventLoopLag {
+        samples: 100,
+        mean_us: 50,
+        p50_us: 50,
+        p90_us: 90,
+        p99_us: 99,
+        max_us: 100,
+      }
+    );
+
+    // only the latest samples are kept
+    for _ in 0..EVENT_LOOP_SAMPLES {
+      metrics.record_lag(Duration::from_micros(7));
+    }
+    let lag = metrics.event_loop_lag();
+    assert_eq!(lag.samples, EVENT_LOOP_SAMPLES as u64);
+    assert_eq!(lag.max_us, 7);
+  }
+}
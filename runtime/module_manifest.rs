@@ -0,0 +1,143 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! A deterministic description of the modules of a program, which edge
+//! platforms and bundlers can use to preload or package them ahead of time.
+//! It is produced by `deno info --manifest`.
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::ModuleSpecifier;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// The version of the manifest format, bumped on breaking changes.
+pub const MODULE_MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleManifest {
+  pub version: u32,
+  /// The entrypoints of the program.
+  pub roots: Vec<ModuleSpecifier>,
+  /// Every module of the program, keyed and sorted by specifier.
+  pub modules: BTreeMap<ModuleSpecifier, ModuleManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleManifestEntry {
+  /// Hex encoded SHA-256 checksum of the module source.
+  pub hash: String,
+  pub media_type: String,
+  /// The resolved specifiers of the module's dependencies, sorted.
+  pub dependencies: Vec<ModuleSpecifier>,
+  /// Location of the transpiled source, for modules that need to be emitted.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub emit_path: Option<PathBuf>,
+}
+
+impl ModuleManifest {
+  pub fn new(roots: Vec<ModuleSpecifier>) -> Self {
+    Self {
+      version: MODULE_MANIFEST_VERSION,
+      roots,
+      modules: BTreeMap::new(),
+    }
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, AnyError> {
+    let manifest: Self = serde_json::from_str(json)?;
+    if manifest.version != MODULE_MANIFEST_VERSION {
+      bail!(
+        "Unsupported module manifest version {}, expected {}.",
+        manifest.version,
+        MODULE_MANIFEST_VERSION
+      );
+    }
+    Ok(manifest)
+  }
+
+  pub fn to_json(&self) -> Result<String, AnyError> {
+    Ok(serde_json::to_string_pretty(self)?)
+  }
+
+  /// Returns the modules of the manifest so that every module comes after
+  /// its dependencies, which is the order to preload them in.
+  ///
+  /// Dependencies that are not part of the manifest (ex. npm packages or
+  /// built-in node modules) are skipped.
+  pub fn load_order(&self) -> Vec<&ModuleSpecifier> {
+    fn visit<'a>(
+      manifest: &'a ModuleManifest,
+      specifier: &'a ModuleSpecifier,
+      seen: &mut HashSet<&'a ModuleSpecifier>,
+      order: &mut Vec<&'a ModuleSpecifier>,
+    ) {
+      let Some((specifier, entry)) = manifest.modules.get_key_value(specifier)
+      else {
+        return;
+      };
+      if !seen.insert(specifier) {
+        return;
+      }
+      for dependency in &entry.dependencies {
+        visit(manifest, dependency, seen, order);
+      }
+      order.push(specifier);
+    }
+
+    let mut seen = HashSet::new();
+    let mut order = Vec::with_capacity(self.modules.len());
+    for root in &self.roots {
+      visit(self, root, &mut seen, &mut order);
+    }
+    order
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn entry(dependencies: &[&ModuleSpecifier]) -> ModuleManifestEntry {
+    ModuleManifestEntry {
+      hash: "hash".to_string(),
+      media_type: "TypeScript".to_string(),
+      dependencies: dependencies.iter().map(|s| (*s).clone()).collect(),
+      emit_path: None,
+    }
+  }
+
+  #[test]
+  fn load_order() {
+    let main = ModuleSpecifier::parse("file:///main.ts").unwrap();
+    let a = ModuleSpecifier::parse("file:///a.ts").unwrap();
+    let b = ModuleSpecifier::parse("file:///b.ts").unwrap();
+    let npm = ModuleSpecifier::parse("npm:chalk@5").unwrap();
+    let mut manifest = ModuleManifest::new(vec![main.clone()]);
+    manifest.modules.insert(main.clone(), entry(&[&b, &a]));
+    manifest.modules.insert(a.clone(), entry(&[&b, &npm]));
+    manifest.modules.insert(b.clone(), entry(&[&a]));
+    assert_eq!(manifest.load_order(), vec![&a, &b, &main]);
+  }
+
+  #[test]
+  fn json_roundtrip() {
+    let main = ModuleSpecifier::parse("file:///main.ts").unwrap();
+    let mut manifest = ModuleManifest::new(vec![main.clone()]);
+    manifest.modules.insert(main, entry(&[]));
+    let json = manifest.to_json().unwrap();
+    assert_eq!(ModuleManifest::from_json(&json).unwrap(), manifest);
+
+    let json = json.replacen("\"version\": 1", "\"version\": 2", 1);
+    let err = ModuleManifest::from_json(&json).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Unsupported module manifest version 2, expected 1."
+    );
+  }
+}
@@ -0,0 +1,174 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Per-op timing histograms, recorded through the op metrics hooks of
+//! `deno_core` when running with `--op-metrics`. They make it possible to
+//! find which ops dominate latency without external tracing.
+
+use deno_core::OpDecl;
+use deno_core::OpId;
+use deno_core::OpMetricsEvent;
+use deno_core::OpMetricsFactoryFn;
+use deno_core::_ops::OpCtx;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Upper bounds, in microseconds, of the buckets of [`OpTiming::histogram`].
+pub const OP_TIMING_BUCKETS_US: [u64; 22] = [
+  1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 20_000,
+  50_000, 100_000, 200_000, 500_000, 1_000_000, 2_000_000, 5_000_000,
+  10_000_000,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpTiming {
+  /// The number of completed calls.
+  pub count: u64,
+  /// The number of calls that threw.
+  pub errors: u64,
+  pub total_us: u64,
+  pub max_us: u64,
+  /// The number of calls per bucket of [`OP_TIMING_BUCKETS_US`]. The extra
+  /// last bucket counts calls slower than every bound.
+  pub histogram: Vec<u64>,
+}
+
+impl Default for OpTiming {
+  fn default() -> Self {
+    Self {
+      count: 0,
+      errors: 0,
+      total_us: 0,
+      max_us: 0,
+      histogram: vec![0; OP_TIMING_BUCKETS_US.len() + 1],
+    }
+  }
+}
+
+impl OpTiming {
+  fn record(&mut self, elapsed: Duration, error: bool) {
+    let us = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+    self.count += 1;
+    if error {
+      self.errors += 1;
+    }
+    self.total_us = self.total_us.saturating_add(us);
+    self.max_us = self.max_us.max(us);
+    let bucket = OP_TIMING_BUCKETS_US.partition_point(|bound| *bound < us);
+    self.histogram[bucket] += 1;
+  }
+}
+
+#[derive(Default)]
+struct OpTimingState {
+  name: &'static str,
+  timing: OpTiming,
+  /// Start times of the calls that haven't completed yet.
+  pending: VecDeque<Instant>,
+}
+
+#[derive(Default)]
+pub struct OpTimingTracker {
+  ops: RefCell<Vec<OpTimingState>>,
+}
+
+impl OpTimingTracker {
+  /// Returns the timings of every op that completed at least once, keyed by
+  /// op name.
+  pub fn per_op(&self) -> BTreeMap<&'static str, OpTiming> {
+    self
+      .ops
+      .borrow()
+      .iter()
+      .filter(|op| op.timing.count > 0)
+      .map(|op| (op.name, op.timing.clone()))
+      .collect()
+  }
+
+  fn on_event(&self, id: OpId, event: OpMetricsEvent, is_async: bool) {
+    let mut ops = self.ops.borrow_mut();
+    let op = &mut ops[id as usize];
+    let error = match event {
+      OpMetricsEvent::Dispatched => {
+        op.pending.push_back(Instant::now());
+        return;
+      }
+      OpMetricsEvent::Completed | OpMetricsEvent::CompletedAsync => false,
+      OpMetricsEvent::Error | OpMetricsEvent::ErrorAsync => true,
+    };
+    // Completion events don't identify the call they belong to. Sync calls
+    // can only nest, so the latest one completes first. Async calls are
+    // assumed to complete in order, which may attribute a duration to the
+    // wrong call but keeps the total time exact.
+    let start = if is_async {
+      op.pending.pop_front()
+    } else {
+      op.pending.pop_back()
+    };
+    if let Some(start) = start {
+      op.timing.record(start.elapsed(), error);
+    }
+  }
+
+  /// Retrieves the metrics factory function for this tracker.
+  pub fn op_metrics_factory_fn(self: Rc<Self>) -> OpMetricsFactoryFn {
+    Box::new(move |id, total, decl: &OpDecl| {
+      let mut ops = self.ops.borrow_mut();
+      if ops.len() < total {
+        ops.resize_with(total, Default::default);
+      }
+      ops[id as usize].name = decl.name;
+      let tracker = self.clone();
+      Some(Rc::new(move |ctx: &OpCtx, event, _source| {
+        tracker.on_event(ctx.id, event, ctx.decl().is_async)
+      }))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn record_buckets() {
+    let mut timing = OpTiming::default();
+    timing.record(Duration::from_micros(0), false);
+    timing.record(Duration::from_micros(1), false);
+    timing.record(Duration::from_micros(3), true);
+    timing.record(Duration::from_secs(60), false);
+    assert_eq!(timing.count, 4);
+    assert_eq!(timing.errors, 1);
+    assert_eq!(timing.total_us, 60_000_004);
+    assert_eq!(timing.max_us, 60_000_000);
+    assert_eq!(timing.histogram[0], 2);
+    assert_eq!(timing.histogram[2], 1);
+    assert_eq!(timing.histogram[OP_TIMING_BUCKETS_US.len()], 1);
+    assert_eq!(timing.histogram.iter().sum::<u64>(), 4);
+  }
+
+  #[test]
+  fn pending_calls() {
+    let tracker = OpTimingTracker::default();
+    tracker.ops.borrow_mut().push(OpTimingState {
+      name: "op_test",
+      ..Default::default()
+    });
+    tracker.on_event(0, OpMetricsEvent::Dispatched, true);
+    tracker.on_event(0, OpMetricsEvent::Dispatched, true);
+    tracker.on_event(0, OpMetricsEvent::ErrorAsync, true);
+    assert_eq!(tracker.per_op()["op_test"].count, 1);
+    assert_eq!(tracker.ops.borrow()[0].pending.len(), 1);
+    tracker.on_event(0, OpMetricsEvent::CompletedAsync, true);
+    // a completion without a matching dispatch is ignored
+    tracker.on_event(0, OpMetricsEvent::Completed, true);
+    let timing = &tracker.per_op()["op_test"];
+    assert_eq!(timing.count, 2);
+    assert_eq!(timing.errors, 1);
+  }
+}
@@ -19,6 +19,7 @@ deno_core::extension!(
     op_bootstrap_is_stdout_tty,
     op_bootstrap_is_stderr_tty,
     op_bootstrap_unstable_args,
+    op_bootstrap_deno_dir,
     op_snapshot_options,
   ],
   options = {
@@ -99,6 +100,16 @@ pub fn op_bootstrap_unstable_args(state: &mut OpState) -> Vec<String> {
   flags
 }
 
+#[op2]
+#[string]
+pub fn op_bootstrap_deno_dir(state: &mut OpState) -> Option<String> {
+  let options = state.borrow::<BootstrapOptions>();
+  options
+    .deno_dir
+    .as_ref()
+    .map(|path| path.to_string_lossy().into_owned())
+}
+
 #[op2]
 #[string]
 pub fn op_bootstrap_language(state: &mut OpState) -> String {
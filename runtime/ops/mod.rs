@@ -6,8 +6,10 @@ pub mod http;
 pub mod os;
 pub mod permissions;
 pub mod process;
+pub mod resource_scope;
 pub mod runtime;
 pub mod signal;
+pub mod sync_channel;
 pub mod tty;
 mod utils;
 pub mod web_worker;
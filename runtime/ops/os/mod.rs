@@ -13,9 +13,14 @@ use deno_node::NODE_ENV_VAR_ALLOWLIST;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::env;
+use std::io::Write;
 
+mod network_events;
 mod sys_info;
 
+use network_events::op_network_interfaces_watch;
+use network_events::op_network_interfaces_watch_poll;
+
 deno_core::extension!(
   deno_os,
   ops = [
@@ -28,9 +33,12 @@ deno_core::extension!(
     op_hostname,
     op_loadavg,
     op_network_interfaces,
+    op_network_interfaces_watch,
+    op_network_interfaces_watch_poll,
     op_os_release,
     op_os_uptime,
     op_set_env,
+    op_get_exit_code,
     op_set_exit_code,
     op_system_memory_info,
     op_uid,
@@ -56,19 +64,29 @@ deno_core::extension!(
     op_hostname,
     op_loadavg,
     op_network_interfaces,
+    op_network_interfaces_watch,
+    op_network_interfaces_watch_poll,
     op_os_release,
     op_os_uptime,
     op_set_env,
+    op_get_exit_code,
     op_set_exit_code,
     op_system_memory_info,
     op_uid,
     op_runtime_memory_usage,
   ],
+  options = {
+    exit_code: ExitCode,
+  },
+  // `Deno.exit()` closes the worker instead, its exit code is reported to the
+  // host when the worker is closed.
   middleware = |op| match op.name {
-    "op_exit" | "op_set_exit_code" =>
-      op.with_implementation_from(&deno_core::op_void_sync()),
+    "op_exit" => op.with_implementation_from(&deno_core::op_void_sync()),
     _ => op,
   },
+  state = |state, options| {
+    state.put::<ExitCode>(options.exit_code);
+  },
 );
 
 #[op2]
@@ -164,9 +182,20 @@ fn op_set_exit_code(state: &mut OpState, #[smi] code: i32) {
   state.borrow_mut::<ExitCode>().set(code);
 }
 
+#[op2(fast)]
+#[smi]
+fn op_get_exit_code(state: &mut OpState) -> i32 {
+  state.borrow::<ExitCode>().get()
+}
+
 #[op2(fast)]
 fn op_exit(state: &mut OpState) {
   let code = state.borrow::<ExitCode>().get();
+  // Async writes to stdout and stderr happen on blocking threads. Taking the
+  // locks waits for the writes that already started, so that their output
+  // isn't cut off by the exit.
+  let _ = std::io::stdout().lock().flush();
+  let _ = std::io::stderr().lock().flush();
   std::process::exit(code)
 }
 
@@ -208,7 +237,7 @@ fn op_network_interfaces(
   Ok(netif::up()?.map(NetworkInterface::from).collect())
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
 struct NetworkInterface {
   family: &'static str,
   name: String,
@@ -331,8 +360,9 @@ fn op_runtime_memory_usage(scope: &mut v8::HandleScope) -> MemoryUsage {
   }
 }
 
+/// Returns the resident set size of the process, in bytes.
 #[cfg(any(target_os = "android", target_os = "linux"))]
-fn rss() -> usize {
+pub fn rss() -> usize {
   // Inspired by https://github.com/Arc-blroth/memory-stats/blob/5364d0d09143de2a470d33161b2330914228fde9/src/linux.rs
 
   // Extracts a positive integer from a string that
@@ -382,8 +412,9 @@ fn rss() -> usize {
   total_rss_pages * page_size as usize
 }
 
+/// Returns the resident set size of the process, in bytes.
 #[cfg(target_os = "macos")]
-fn rss() -> usize {
+pub fn rss() -> usize {
   // Inspired by https://github.com/Arc-blroth/memory-stats/blob/5364d0d09143de2a470d33161b2330914228fde9/src/darwin.rs
 
   let mut task_info =
@@ -405,8 +436,9 @@ fn rss() -> usize {
   task_info.resident_size as usize
 }
 
+/// Returns the resident set size of the process, in bytes.
 #[cfg(target_os = "openbsd")]
-fn rss() -> usize {
+pub fn rss() -> usize {
   // Uses OpenBSD's KERN_PROC_PID sysctl(2)
   // to retrieve information about the current
   // process, part of which is the RSS (p_vm_rssize)
@@ -452,8 +484,9 @@ fn rss() -> usize {
   }
 }
 
+/// Returns the resident set size of the process, in bytes.
 #[cfg(windows)]
-fn rss() -> usize {
+pub fn rss() -> usize {
   use winapi::shared::minwindef::DWORD;
   use winapi::shared::minwindef::FALSE;
   use winapi::um::processthreadsapi::GetCurrentProcess;
@@ -0,0 +1,495 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The change notifications of the network interfaces, behind
+//! `Deno.watchNetworkInterfaces()`.
+//!
+//! The operating system only tells that something changed: with netlink on
+//! Linux, a routing socket on macOS and the BSDs, and the IP Helper
+//! notifications on Windows. The events are then found by comparing the
+//! interfaces with the ones listed before the change.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::AsyncRefCell;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
+use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
+use deno_core::ResourceId;
+use serde::Serialize;
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+#[cfg(unix)]
+use std::os::fd::FromRawFd;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::HANDLE;
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::NO_ERROR;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::CancelMibChangeNotify2;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::NotifyIpInterfaceChange;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::NotifyUnicastIpAddressChange;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::MIB_IPINTERFACE_ROW;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::MIB_NOTIFICATION_TYPE;
+#[cfg(windows)]
+use windows_sys::Win32::NetworkManagement::IpHelper::MIB_UNICASTIPADDRESS_ROW;
+#[cfg(windows)]
+use windows_sys::Win32::Networking::WinSock::AF_UNSPEC;
+
+use super::NetworkInterface;
+use crate::ops::check_unstable;
+use crate::permissions::PermissionsContainer;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum NetworkInterfaceEvent {
+  AddressAdded { interface: NetworkInterface },
+  AddressRemoved { interface: NetworkInterface },
+  LinkUp { name: String },
+  LinkDown { name: String },
+}
+
+#[derive(Default)]
+struct Snapshot {
+  addresses: Vec<NetworkInterface>,
+  /// The names of the interfaces that are up. Like in
+  /// `Deno.networkInterfaces()`, only the interfaces with an address are
+  /// listed.
+  links: Vec<String>,
+}
+
+impl Snapshot {
+  fn take() -> io::Result<Self> {
+    let mut snapshot = Self::default();
+    for ifa in netif::up()? {
+      if is_running(&ifa) && !snapshot.links.iter().any(|n| n == ifa.name()) {
+        snapshot.links.push(ifa.name().to_owned());
+      }
+      snapshot.addresses.push(NetworkInterface::from(ifa));
+    }
+    Ok(snapshot)
+  }
+}
+
+#[cfg(unix)]
+fn is_running(ifa: &netif::Interface) -> bool {
+  let flags = (libc::IFF_UP | libc::IFF_RUNNING) as u64;
+  ifa.flags() & flags == flags
+}
+
+/// Only the interfaces that are up are listed on Windows.
+#[cfg(windows)]
+fn is_running(_ifa: &netif::Interface) -> bool {
+  true
+}
+
+/// The events that turn `old` into `new`. The removed addresses come first,
+/// so the addresses of a link that went down are removed before it, and the
+/// ones of a link that came up are added after it.
+fn diff(old: &Snapshot, new: &Snapshot) -> Vec<NetworkInterfaceEvent> {
+  let mut events = vec![];
+  for interface in &old.addresses {
+    if !new.addresses.contains(interface) {
+      events.push(NetworkInterfaceEvent::AddressRemoved {
+        interface: interface.clone(),
+      });
+    }
+  }
+  for name in &old.links {
+    if !new.links.contains(name) {
+      events.push(NetworkInterfaceEvent::LinkDown { name: name.clone() });
+    }
+  }
+  for name in &new.links {
+    if !old.links.contains(name) {
+      events.push(NetworkInterfaceEvent::LinkUp { name: name.clone() });
+    }
+  }
+  for interface in &new.addresses {
+    if !old.addresses.contains(interface) {
+      events.push(NetworkInterfaceEvent::AddressAdded {
+        interface: interface.clone(),
+      });
+    }
+  }
+  events
+}
+
+struct Watch {
+  notifier: Notifier,
+  snapshot: Snapshot,
+  /// The events of the last change that weren't polled yet.
+  pending: VecDeque<NetworkInterfaceEvent>,
+}
+
+struct NetworkInterfaceWatcher {
+  watch: AsyncRefCell<Watch>,
+  cancel: CancelHandle,
+}
+
+impl Resource for NetworkInterfaceWatcher {
+  fn name(&self) -> Cow<str> {
+    "networkInterfaceWatcher".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
+
+#[op2(fast)]
+#[smi]
+pub fn op_network_interfaces_watch(
+  state: &mut OpState,
+) -> Result<ResourceId, AnyError> {
+  check_unstable(
+    state,
+    deno_net::UNSTABLE_FEATURE_NAME,
+    "Deno.watchNetworkInterfaces",
+  );
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_sys("networkInterfaces", "Deno.watchNetworkInterfaces()")?;
+  // Subscribe before listing the interfaces, so that no change is missed in
+  // between.
+  let notifier = Notifier::new()?;
+  let snapshot = Snapshot::take()?;
+  Ok(state.resource_table.add(NetworkInterfaceWatcher {
+    watch: AsyncRefCell::new(Watch {
+      notifier,
+      snapshot,
+      pending: VecDeque::new(),
+    }),
+    cancel: Default::default(),
+  }))
+}
+
+#[op2(async)]
+#[serde]
+pub async fn op_network_interfaces_watch_poll(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<NetworkInterfaceEvent, AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<NetworkInterfaceWatcher>(rid)?;
+  let mut watch = RcRef::map(&resource, |r| &r.watch).borrow_mut().await;
+  loop {
+    if let Some(event) = watch.pending.pop_front() {
+      return Ok(event);
+    }
+    let cancel = RcRef::map(&resource, |r| &r.cancel);
+    watch.notifier.changed().or_cancel(cancel).await??;
+    // Most changes, like the ones of the routes, don't change the
+    // interfaces and make no events.
+    let snapshot = Snapshot::take()?;
+    let events = diff(&watch.snapshot, &snapshot);
+    watch.pending.extend(events);
+    watch.snapshot = snapshot;
+  }
+}
+
+/// A non-blocking socket receiving the messages of the kernel about the
+/// network configuration, which are only used as a signal.
+#[cfg(unix)]
+struct Notifier(tokio::io::unix::AsyncFd<OwnedFd>);
+
+#[cfg(unix)]
+impl Notifier {
+  fn new() -> io::Result<Self> {
+    Ok(Self(tokio::io::unix::AsyncFd::new(open_socket()?)?))
+  }
+
+  /// Waits for the next messages, and discards them.
+  async fn changed(&mut self) -> io::Result<()> {
+    let fd = self.0.get_ref().as_raw_fd();
+    let mut guard = self.0.readable().await?;
+    let mut buf = [0u8; 4096];
+    loop {
+      // SAFETY: `buf` is valid for writes of its length.
+      let n = unsafe {
+        libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0)
+      };
+      if n >= 0 {
+        continue;
+      }
+      let err = io::Error::last_os_error();
+      match err.kind() {
+        io::ErrorKind::WouldBlock => {
+          guard.clear_ready();
+          return Ok(());
+        }
+        io::ErrorKind::Interrupted => continue,
+        // The kernel dropped messages, which still means that something
+        // changed.
+        _ if err.raw_os_error() == Some(libc::ENOBUFS) => continue,
+        _ => return Err(err),
+      }
+    }
+  }
+}
+
+/// A netlink socket in the multicast groups of the link and address changes.
+#[cfg(target_os = "linux")]
+fn open_socket() -> io::Result<OwnedFd> {
+  // SAFETY: no pointers are involved.
+  let fd = unsafe {
+    libc::socket(
+      libc::AF_NETLINK,
+      libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+      libc::NETLINK_ROUTE,
+    )
+  };
+  if fd < 0 {
+    return Err(io::Error::last_os_error());
+  }
+  // SAFETY: the socket was just opened and isn't owned by anything else.
+  let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+  // SAFETY: all zeroes is a valid `sockaddr_nl`.
+  let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+  addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+  addr.nl_groups = (libc::RTMGRP_LINK
+    | libc::RTMGRP_IPV4_IFADDR
+    | libc::RTMGRP_IPV6_IFADDR) as u32;
+  // SAFETY: `addr` is a `sockaddr_nl` of the given size.
+  let res = unsafe {
+    libc::bind(
+      fd.as_raw_fd(),
+      &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+      std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+    )
+  };
+  if res < 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(fd)
+}
+
+/// A routing socket, which gets a message for every change of the routes,
+/// the interfaces and their addresses.
+#[cfg(any(
+  target_os = "macos",
+  target_os = "ios",
+  target_os = "freebsd",
+  target_os = "dragonfly",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn open_socket() -> io::Result<OwnedFd> {
+  // SAFETY: no pointers are involved.
+  let fd = unsafe { libc::socket(libc::PF_ROUTE, libc::SOCK_RAW, 0) };
+  if fd < 0 {
+    return Err(io::Error::last_os_error());
+  }
+  // SAFETY: the socket was just opened and isn't owned by anything else.
+  let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+  // SAFETY: `fd` is a valid file descriptor.
+  let res = unsafe {
+    if libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) < 0 {
+      -1
+    } else {
+      libc::fcntl(fd.as_raw_fd(), libc::F_SETFL, libc::O_NONBLOCK)
+    }
+  };
+  if res < 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(fd)
+}
+
+#[cfg(all(
+  unix,
+  not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))
+))]
+fn open_socket() -> io::Result<OwnedFd> {
+  Err(io::Error::new(
+    io::ErrorKind::Unsupported,
+    "Watching the network interfaces is not supported on this platform",
+  ))
+}
+
+/// The IP Helper notifications of the interface and address changes, which
+/// call back on a thread pool.
+#[cfg(windows)]
+struct Notifier {
+  handles: Vec<HANDLE>,
+  receiver: tokio::sync::mpsc::UnboundedReceiver<()>,
+  /// The context of the callbacks, until the notifications are cancelled.
+  sender: Box<tokio::sync::mpsc::UnboundedSender<()>>,
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn on_interface_change(
+  context: *const std::ffi::c_void,
+  _row: *const MIB_IPINTERFACE_ROW,
+  _notification_type: MIB_NOTIFICATION_TYPE,
+) {
+  let sender = &*(context as *const tokio::sync::mpsc::UnboundedSender<()>);
+  let _ = sender.send(());
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn on_address_change(
+  context: *const std::ffi::c_void,
+  _row: *const MIB_UNICASTIPADDRESS_ROW,
+  _notification_type: MIB_NOTIFICATION_TYPE,
+) {
+  let sender = &*(context as *const tokio::sync::mpsc::UnboundedSender<()>);
+  let _ = sender.send(());
+}
+
+#[cfg(windows)]
+impl Notifier {
+  fn new() -> io::Result<Self> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let mut notifier = Self {
+      handles: vec![],
+      receiver,
+      sender: Box::new(sender),
+    };
+    let context = &*notifier.sender
+      as *const tokio::sync::mpsc::UnboundedSender<()>
+      as *const std::ffi::c_void;
+
+    let mut handle = 0;
+    // SAFETY: `context` lives until the notification is cancelled, when the
+    // notifier is dropped.
+    let res = unsafe {
+      NotifyIpInterfaceChange(
+        AF_UNSPEC,
+        Some(on_interface_change),
+        context,
+        0,
+        &mut handle,
+      )
+    };
+    if res != NO_ERROR {
+      return Err(io::Error::from_raw_os_error(res as i32));
+    }
+    notifier.handles.push(handle);
+
+    // SAFETY: as above.
+    let res = unsafe {
+      NotifyUnicastIpAddressChange(
+        AF_UNSPEC,
+        Some(on_address_change),
+        context,
+        0,
+        &mut handle,
+      )
+    };
+    if res != NO_ERROR {
+      return Err(io::Error::from_raw_os_error(res as i32));
+    }
+    notifier.handles.push(handle);
+
+    Ok(notifier)
+  }
+
+  /// Waits for the next notifications, and discards them.
+  async fn changed(&mut self) -> io::Result<()> {
+    self.receiver.recv().await;
+    while self.receiver.try_recv().is_ok() {}
+    Ok(())
+  }
+}
+
+#[cfg(windows)]
+impl Drop for Notifier {
+  fn drop(&mut self) {
+    for handle in &self.handles {
+      // SAFETY: the handle is a registered notification. The cancellation
+      // waits for the running callbacks, so `sender` is dropped after them.
+      unsafe { CancelMibChangeNotify2(*handle) };
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn interface(name: &str, address: &str) -> NetworkInterface {
+    NetworkInterface {
+      family: "IPv4",
+      name: name.to_string(),
+      address: address.to_string(),
+      netmask: "255.255.255.0".to_string(),
+      scopeid: None,
+      cidr: format!("{address}/24"),
+      mac: "00:00:00:00:00:00".to_string(),
+    }
+  }
+
+  #[test]
+  fn diff_snapshots() {
+    let old = Snapshot {
+      addresses: vec![interface("eth0", "10.0.0.2"), interface("lo", "::1")],
+      links: vec!["eth0".to_string(), "lo".to_string()],
+    };
+    assert_eq!(diff(&old, &old), vec![]);
+
+    let new = Snapshot {
+      addresses: vec![interface("lo", "::1"), interface("wlan0", "10.0.1.2")],
+      links: vec!["lo".to_string(), "wlan0".to_string()],
+    };
+    assert_eq!(
+      diff(&old, &new),
+      vec![
+        NetworkInterfaceEvent::AddressRemoved {
+          interface: interface("eth0", "10.0.0.2"),
+        },
+        NetworkInterfaceEvent::LinkDown {
+          name: "eth0".to_string(),
+        },
+        NetworkInterfaceEvent::LinkUp {
+          name: "wlan0".to_string(),
+        },
+        NetworkInterfaceEvent::AddressAdded {
+          interface: interface("wlan0", "10.0.1.2"),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn link_down_keeps_addresses() {
+    let old = Snapshot {
+      addresses: vec![interface("eth0", "10.0.0.2")],
+      links: vec!["eth0".to_string()],
+    };
+    let new = Snapshot {
+      addresses: vec![interface("eth0", "10.0.0.2")],
+      links: vec![],
+    };
+    assert_eq!(
+      diff(&old, &new),
+      vec![NetworkInterfaceEvent::LinkDown {
+        name: "eth0".to_string(),
+      }]
+    );
+  }
+}
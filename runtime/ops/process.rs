@@ -3,6 +3,7 @@
 use super::check_unstable;
 use crate::permissions::PermissionsContainer;
 use deno_core::anyhow::Context;
+use deno_core::error::range_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
@@ -18,9 +19,13 @@ use deno_io::fs::FileResource;
 use deno_io::ChildStderrResource;
 use deno_io::ChildStdinResource;
 use deno_io::ChildStdoutResource;
+use encoding_rs::CoderResult;
+use encoding_rs::Decoder;
+use encoding_rs::Encoding;
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::process::ExitStatus;
 use std::rc::Rc;
@@ -111,6 +116,10 @@ deno_core::extension!(
     op_spawn_wait,
     op_spawn_sync,
     op_spawn_kill,
+    op_spawn_pipeline,
+    op_spawn_read_output,
+    op_spawn_lines,
+    op_spawn_lines_next,
     deprecated::op_run,
     deprecated::op_run_status,
     deprecated::op_kill,
@@ -634,6 +643,79 @@ fn op_spawn_sync(
   })
 }
 
+/// Spawns every command of a pipeline, connecting the stdout of each
+/// command to the stdin of the next one with an OS pipe.
+#[op2]
+#[serde]
+fn op_spawn_pipeline(
+  state: &mut OpState,
+  #[serde] args: Vec<SpawnArgs>,
+) -> Result<Vec<Child>, AnyError> {
+  check_unstable(state, UNSTABLE_FEATURE_NAME, "Deno.spawnPipeline");
+  if args.is_empty() {
+    return Err(type_error("A pipeline must have at least one command."));
+  }
+
+  let len = args.len();
+  let mut children = Vec::with_capacity(len);
+  let mut stdin = None;
+  for (i, args) in args.into_iter().enumerate() {
+    match spawn_pipeline_stage(state, args, stdin.take(), i + 1 < len) {
+      Ok((child, next_stdin)) => {
+        children.push(child);
+        stdin = next_stdin;
+      }
+      Err(err) => {
+        // kills the commands that were already spawned
+        for child in children {
+          close_child(state, child);
+        }
+        return Err(err);
+      }
+    }
+  }
+  Ok(children)
+}
+
+fn spawn_pipeline_stage(
+  state: &mut OpState,
+  args: SpawnArgs,
+  stdin: Option<deno_io::PipeRead>,
+  pipe_stdout: bool,
+) -> Result<(Child, Option<deno_io::PipeRead>), AnyError> {
+  let (mut command, pipe_rid) =
+    create_command(state, args, "Deno.spawnPipeline()")?;
+  if let Some(stdin) = stdin {
+    command.stdin(stdin);
+  }
+  let next_stdin = if pipe_stdout {
+    let (read, write) = deno_io::pipe()?;
+    command.stdout(write);
+    Some(read)
+  } else {
+    None
+  };
+  // The command is dropped once spawned, closing our copies of the pipe
+  // ends, so a command sees EOF as soon as the previous one exits.
+  let child = spawn_child(state, command, pipe_rid)?;
+  Ok((child, next_stdin))
+}
+
+fn close_child(state: &mut OpState, child: Child) {
+  let rids = [
+    Some(child.rid),
+    child.stdin_rid,
+    child.stdout_rid,
+    child.stderr_rid,
+    child.pipe_fd,
+  ];
+  for rid in rids.into_iter().flatten() {
+    if let Ok(resource) = state.resource_table.take_any(rid) {
+      resource.close();
+    }
+  }
+}
+
 #[op2(fast)]
 fn op_spawn_kill(
   state: &mut OpState,
@@ -647,6 +729,161 @@ fn op_spawn_kill(
   Err(type_error("Child process has already terminated."))
 }
 
+/// Size of the chunks read from the stdout or stderr of a child.
+const OUTPUT_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CappedOutput {
+  data: ToJsBuffer,
+  exceeded: bool,
+}
+
+/// Collects at most `max_bytes` of what a child writes to its stdout or
+/// stderr. When the child writes more, reading stops, unless `truncate` is
+/// set: then the rest is read and discarded so the child doesn't block on a
+/// full pipe.
+#[op2(async)]
+#[serde]
+async fn op_spawn_read_output(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[number] max_bytes: usize,
+  truncate: bool,
+) -> Result<CappedOutput, AnyError> {
+  let resource = state.borrow_mut().resource_table.take_any(rid)?;
+  let mut data = Vec::new();
+  let mut exceeded = false;
+  loop {
+    let chunk = resource.clone().read(OUTPUT_CHUNK_SIZE).await?;
+    if chunk.is_empty() {
+      break;
+    }
+    if exceeded {
+      continue;
+    }
+    let remaining = max_bytes - data.len();
+    if chunk.len() > remaining {
+      data.extend_from_slice(&chunk[..remaining]);
+      exceeded = true;
+      if !truncate {
+        break;
+      }
+    } else {
+      data.extend_from_slice(&chunk);
+    }
+  }
+  Ok(CappedOutput {
+    data: data.into(),
+    exceeded,
+  })
+}
+
+struct ChildLinesResource {
+  stream: Rc<dyn Resource>,
+  decoder: RefCell<Decoder>,
+  /// Decoded text after the last line break seen so far.
+  pending: RefCell<String>,
+  done: Cell<bool>,
+}
+
+impl Resource for ChildLinesResource {
+  fn name(&self) -> Cow<str> {
+    "childLines".into()
+  }
+}
+
+impl ChildLinesResource {
+  async fn next_lines(&self) -> Result<Option<Vec<String>>, AnyError> {
+    while !self.done.get() {
+      let chunk = self.stream.clone().read(OUTPUT_CHUNK_SIZE).await?;
+      let last = chunk.is_empty();
+      let mut pending = self.pending.borrow_mut();
+      decode_into(&mut self.decoder.borrow_mut(), &chunk, &mut pending, last);
+      let mut lines = split_lines(&mut pending);
+      if last {
+        self.done.set(true);
+        if !pending.is_empty() {
+          lines.push(std::mem::take(&mut *pending));
+        }
+      }
+      if !lines.is_empty() {
+        return Ok(Some(lines));
+      }
+    }
+    Ok(None)
+  }
+}
+
+fn decode_into(
+  decoder: &mut Decoder,
+  mut input: &[u8],
+  output: &mut String,
+  last: bool,
+) {
+  loop {
+    let additional = decoder
+      .max_utf8_buffer_length(input.len())
+      .unwrap_or(OUTPUT_CHUNK_SIZE);
+    output.reserve(additional);
+    let (result, read, _) = decoder.decode_to_string(input, output, last);
+    input = &input[read..];
+    if let CoderResult::InputEmpty = result {
+      break;
+    }
+  }
+}
+
+/// Removes the complete lines from `pending`, without their "\n" or "\r\n"
+/// terminators.
+fn split_lines(pending: &mut String) -> Vec<String> {
+  let Some(end) = pending.rfind('\n') else {
+    return Vec::new();
+  };
+  let rest = pending.split_off(end + 1);
+  let complete = std::mem::replace(pending, rest);
+  complete[..end]
+    .split('\n')
+    .map(|line| line.strip_suffix('\r').unwrap_or(line).to_owned())
+    .collect()
+}
+
+/// Takes over the stdout or stderr of a child to read it line by line.
+#[op2(fast)]
+#[smi]
+fn op_spawn_lines(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[string] label: &str,
+) -> Result<ResourceId, AnyError> {
+  let encoding = Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+    range_error(format!(
+      "The encoding label provided ('{label}') is invalid."
+    ))
+  })?;
+  let stream = state.resource_table.take_any(rid)?;
+  Ok(state.resource_table.add(ChildLinesResource {
+    stream,
+    decoder: RefCell::new(encoding.new_decoder_with_bom_removal()),
+    pending: RefCell::new(String::new()),
+    done: Cell::new(false),
+  }))
+}
+
+/// Resolves to the next batch of lines, or `null` once the stream ended.
+#[op2(async)]
+#[serde]
+async fn op_spawn_lines_next(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<Option<Vec<String>>, AnyError> {
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<ChildLinesResource>(rid)?;
+  resource.next_lines().await
+}
+
 mod deprecated {
   use super::*;
 
@@ -917,3 +1154,33 @@ mod deprecated {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn split_lines_keeps_partial_line() {
+    let mut pending = String::from("a\nb\r\n\nc");
+    assert_eq!(split_lines(&mut pending), vec!["a", "b", ""]);
+    assert_eq!(pending, "c");
+    assert!(split_lines(&mut pending).is_empty());
+    assert_eq!(pending, "c");
+  }
+
+  #[test]
+  fn decode_into_handles_split_sequences() {
+    let mut decoder = encoding_rs::UTF_8.new_decoder_with_bom_removal();
+    let mut output = String::new();
+    let bytes = "h\u{e9}llo".as_bytes();
+    decode_into(&mut decoder, &bytes[..2], &mut output, false);
+    decode_into(&mut decoder, &bytes[2..], &mut output, false);
+    decode_into(&mut decoder, &[], &mut output, true);
+    assert_eq!(output, "h\u{e9}llo");
+
+    let mut decoder = encoding_rs::UTF_16LE.new_decoder_with_bom_removal();
+    let mut output = String::new();
+    decode_into(&mut decoder, &[0x68, 0, 0x69, 0], &mut output, true);
+    assert_eq!(output, "hi");
+  }
+}
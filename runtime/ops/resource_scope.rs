@@ -0,0 +1,164 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Resource scopes, used by `Deno.ResourceScope`.
+//!
+//! A scope tracks resources of the resource table and closes all of them at
+//! once when it is closed, which also cancels the ops that are still pending
+//! on them. Ops can add the resources they create to a scope passed by rid,
+//! see [ResourceScope::track].
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cell::RefCell;
+
+use deno_core::error::bad_resource_id;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::OpState;
+use deno_core::Resource;
+use deno_core::ResourceId;
+use deno_core::ResourceTable;
+
+deno_core::extension!(
+  deno_resource_scope,
+  ops = [
+    op_resource_scope_new,
+    op_resource_scope_track,
+    op_resource_scope_close,
+  ],
+);
+
+/// A set of resources that are closed together.
+#[derive(Default)]
+pub struct ResourceScope {
+  rids: RefCell<Vec<ResourceId>>,
+  closed: Cell<bool>,
+}
+
+impl Resource for ResourceScope {
+  fn name(&self) -> Cow<str> {
+    "resourceScope".into()
+  }
+}
+
+impl ResourceScope {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds `rid` to the scope. Returns `false` if the scope was closed already,
+  /// in which case the caller stays responsible for closing the resource.
+  pub fn track(&self, rid: ResourceId) -> bool {
+    if self.closed.get() {
+      return false;
+    }
+    let mut rids = self.rids.borrow_mut();
+    if !rids.contains(&rid) {
+      rids.push(rid);
+    }
+    true
+  }
+
+  /// Removes `rid` from the scope, so closing the scope leaves it open.
+  pub fn untrack(&self, rid: ResourceId) {
+    self.rids.borrow_mut().retain(|tracked| *tracked != rid);
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.closed.get()
+  }
+
+  /// Closes the tracked resources that are still open, the most recently
+  /// tracked first, and returns how many were closed.
+  pub fn close_all(&self, resource_table: &mut ResourceTable) -> usize {
+    self.closed.set(true);
+    let rids = self.rids.take();
+    let mut closed = 0;
+    for rid in rids.into_iter().rev() {
+      if let Ok(resource) = resource_table.take_any(rid) {
+        resource.close();
+        closed += 1;
+      }
+    }
+    closed
+  }
+}
+
+#[op2(fast)]
+#[smi]
+fn op_resource_scope_new(state: &mut OpState) -> ResourceId {
+  state.resource_table.add(ResourceScope::new())
+}
+
+#[op2(fast)]
+fn op_resource_scope_track(
+  state: &mut OpState,
+  #[smi] scope_rid: ResourceId,
+  #[smi] rid: ResourceId,
+) -> Result<bool, AnyError> {
+  let scope = state.resource_table.get::<ResourceScope>(scope_rid)?;
+  if rid == scope_rid || !state.resource_table.has(rid) {
+    return Err(bad_resource_id());
+  }
+  Ok(scope.track(rid))
+}
+
+#[op2(fast)]
+#[smi]
+fn op_resource_scope_close(
+  state: &mut OpState,
+  #[smi] scope_rid: ResourceId,
+) -> Result<u32, AnyError> {
+  let scope = state.resource_table.take::<ResourceScope>(scope_rid)?;
+  Ok(scope.close_all(&mut state.resource_table) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::rc::Rc;
+
+  struct Tracked(Rc<RefCell<Vec<&'static str>>>, &'static str);
+
+  impl Resource for Tracked {
+    fn close(self: Rc<Self>) {
+      self.0.borrow_mut().push(self.1);
+    }
+  }
+
+  #[test]
+  fn close_in_reverse_order() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut table = ResourceTable::default();
+    let a = table.add(Tracked(log.clone(), "a"));
+    let b = table.add(Tracked(log.clone(), "b"));
+    let c = table.add(Tracked(log.clone(), "c"));
+
+    let scope = ResourceScope::new();
+    assert!(scope.track(a));
+    assert!(scope.track(b));
+    assert!(scope.track(c));
+    assert!(scope.track(a));
+    scope.untrack(b);
+
+    assert_eq!(scope.close_all(&mut table), 2);
+    assert_eq!(*log.borrow(), vec!["c", "a"]);
+    assert!(table.has(b));
+    assert!(!table.has(a));
+    assert!(scope.is_closed());
+    assert!(!scope.track(b));
+  }
+
+  #[test]
+  fn close_skips_closed_resources() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut table = ResourceTable::default();
+    let a = table.add(Tracked(log.clone(), "a"));
+
+    let scope = ResourceScope::new();
+    scope.track(a);
+    table.take_any(a).unwrap();
+    assert_eq!(scope.close_all(&mut table), 0);
+    assert!(log.borrow().is_empty());
+  }
+}
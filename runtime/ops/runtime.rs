@@ -1,14 +1,28 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::metrics::RuntimeMetrics;
+use crate::metrics::RuntimeMetricsSnapshot;
+use crate::op_timing::OpTiming;
+use crate::op_timing::OpTimingTracker;
 use crate::permissions::PermissionsContainer;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+pub const UNSTABLE_FEATURE_NAME: &str = "metrics";
 
 deno_core::extension!(
   deno_runtime,
-  ops = [op_main_module, op_ppid],
+  ops = [
+    op_main_module,
+    op_ppid,
+    op_runtime_op_metrics,
+    op_runtime_metrics
+  ],
   options = { main_module: ModuleSpecifier },
   state = |state, options| {
     state.put::<ModuleSpecifier>(options.main_module);
@@ -29,6 +43,33 @@ fn op_main_module(state: &mut OpState) -> Result<String, AnyError> {
   Ok(main_path)
 }
 
+#[op2]
+#[serde]
+fn op_runtime_op_metrics(
+  state: &mut OpState,
+) -> Result<BTreeMap<&'static str, OpTiming>, AnyError> {
+  let Some(tracker) = state.try_borrow::<Rc<OpTimingTracker>>() else {
+    return Err(type_error(
+      "Op metrics are not enabled, run with --op-metrics",
+    ));
+  };
+  Ok(tracker.per_op())
+}
+
+#[op2]
+#[serde]
+fn op_runtime_metrics(state: &mut OpState) -> RuntimeMetricsSnapshot {
+  super::check_unstable(state, UNSTABLE_FEATURE_NAME, "Deno.runtimeMetrics");
+  let ops = state
+    .try_borrow::<Rc<OpTimingTracker>>()
+    .map(|tracker| tracker.per_op())
+    .unwrap_or_default();
+  match state.try_borrow::<Rc<RuntimeMetrics>>() {
+    Some(metrics) => metrics.snapshot(ops),
+    None => RuntimeMetrics::default().snapshot(ops),
+  }
+}
+
 /// This is an op instead of being done at initialization time because
 /// it's expensive to retrieve the ppid on Windows.
 #[op2(fast)]
@@ -0,0 +1,217 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Blocking waits on `SharedArrayBuffer` memory, used by `Deno.SyncChannel`.
+//!
+//! They work like `Atomics.wait()` and `Atomics.notify()`, except that a
+//! waiting web worker also wakes up when it gets terminated, so that a
+//! worker blocked on a channel never prevents its termination.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::error::range_error;
+use deno_core::error::AnyError;
+use deno_core::op2;
+use deno_core::OpState;
+use once_cell::sync::Lazy;
+
+use crate::web_worker::WebWorkerInternalHandle;
+
+deno_core::extension!(
+  deno_sync_channel,
+  ops = [op_sync_channel_wait, op_sync_channel_notify],
+);
+
+/// How often a waiting worker checks whether it was terminated.
+const TERMINATION_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+enum WaitResult {
+  Ok = 0,
+  NotEqual = 1,
+  TimedOut = 2,
+}
+
+#[derive(Default)]
+struct WaitQueue {
+  waiters: usize,
+  /// The number of waiters that were notified but didn't wake up yet.
+  pending_wakeups: usize,
+  condvar: Arc<Condvar>,
+}
+
+/// The wait queues, keyed by the address of the value they wait on. Like with
+/// `Atomics.wait()`, waiters of different isolates that share the memory wait
+/// in the same queue.
+static WAIT_QUEUES: Lazy<Mutex<HashMap<usize, WaitQueue>>> =
+  Lazy::new(Default::default);
+
+fn atomic_at(buffer: &[u8], index: u32) -> Result<&AtomicI32, AnyError> {
+  let offset = index as usize * std::mem::size_of::<i32>();
+  if offset + std::mem::size_of::<i32>() > buffer.len() {
+    return Err(range_error("Index out of bounds"));
+  }
+  let ptr = buffer.as_ptr().wrapping_add(offset) as *const AtomicI32;
+  if ptr as usize % std::mem::align_of::<AtomicI32>() != 0 {
+    return Err(range_error("Unaligned index"));
+  }
+  // SAFETY: the pointer is in bounds and aligned, and the channel only
+  // accesses this memory through atomics.
+  Ok(unsafe { &*ptr })
+}
+
+fn wait(
+  value: &AtomicI32,
+  expected: i32,
+  timeout: Option<Duration>,
+  is_terminated: impl Fn() -> bool,
+) -> WaitResult {
+  let address = value as *const AtomicI32 as usize;
+  let deadline = timeout.map(|timeout| Instant::now() + timeout);
+  let mut queues = WAIT_QUEUES.lock().unwrap();
+  // checked while holding the lock, so that a notification sent after
+  // changing the value can't be missed
+  if value.load(Ordering::SeqCst) != expected {
+    return WaitResult::NotEqual;
+  }
+  let queue = queues.entry(address).or_default();
+  queue.waiters += 1;
+  let condvar = queue.condvar.clone();
+  let result = loop {
+    let queue = queues.get_mut(&address).unwrap();
+    if queue.pending_wakeups > 0 {
+      queue.pending_wakeups -= 1;
+      break WaitResult::Ok;
+    }
+    if is_terminated() {
+      break WaitResult::TimedOut;
+    }
+    let now = Instant::now();
+    let interval = match deadline {
+      Some(deadline) if deadline <= now => break WaitResult::TimedOut,
+      Some(deadline) => (deadline - now).min(TERMINATION_CHECK_INTERVAL),
+      None => TERMINATION_CHECK_INTERVAL,
+    };
+    queues = condvar.wait_timeout(queues, interval).unwrap().0;
+  };
+  let queue = queues.get_mut(&address).unwrap();
+  queue.waiters -= 1;
+  if queue.waiters == 0 {
+    queues.remove(&address);
+  } else {
+    // a notification for a waiter that timed out must not wake a later one
+    queue.pending_wakeups = queue.pending_wakeups.min(queue.waiters);
+  }
+  result
+}
+
+fn notify(value: &AtomicI32, count: u32) -> u32 {
+  let address = value as *const AtomicI32 as usize;
+  let mut queues = WAIT_QUEUES.lock().unwrap();
+  let Some(queue) = queues.get_mut(&address) else {
+    return 0;
+  };
+  let woken = (queue.waiters - queue.pending_wakeups).min(count as usize);
+  if woken > 0 {
+    queue.pending_wakeups += woken;
+    queue.condvar.notify_all();
+  }
+  woken as u32
+}
+
+/// Waits until the `i32` at `index` of `buffer` is notified, as long as it
+/// equals `value`. Returns a [`WaitResult`].
+#[op2(fast)]
+fn op_sync_channel_wait(
+  state: &mut OpState,
+  #[buffer] buffer: &[u8],
+  #[smi] index: u32,
+  #[smi] value: i32,
+  timeout: f64,
+) -> Result<u32, AnyError> {
+  let atomic = atomic_at(buffer, index)?;
+  let timeout = if timeout.is_finite() {
+    Some(Duration::from_secs_f64(timeout.max(0.0) / 1000.0))
+  } else {
+    None
+  };
+  let handle = state.try_borrow::<WebWorkerInternalHandle>();
+  let result = wait(atomic, value, timeout, || {
+    handle.is_some_and(|handle| handle.is_terminated())
+  });
+  Ok(result as u32)
+}
+
+/// Wakes up to `count` waiters of the `i32` at `index` of `buffer`, and
+/// returns how many were woken.
+#[op2(fast)]
+fn op_sync_channel_notify(
+  #[buffer] buffer: &[u8],
+  #[smi] index: u32,
+  #[smi] count: u32,
+) -> Result<u32, AnyError> {
+  let atomic = atomic_at(buffer, index)?;
+  Ok(notify(atomic, count))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::sync::atomic::AtomicBool;
+
+  #[test]
+  fn wait_not_equal() {
+    let value = AtomicI32::new(1);
+    let result = wait(&value, 0, None, || false);
+    assert_eq!(result, WaitResult::NotEqual);
+  }
+
+  #[test]
+  fn wait_timed_out() {
+    let value = AtomicI32::new(0);
+    let timeout = Some(Duration::from_millis(10));
+    assert_eq!(wait(&value, 0, timeout, || false), WaitResult::TimedOut);
+    assert_eq!(notify(&value, 1), 0);
+  }
+
+  #[test]
+  fn wait_notify() {
+    let value = Arc::new(AtomicI32::new(0));
+    let waiters = (0..3)
+      .map(|_| {
+        let value = value.clone();
+        std::thread::spawn(move || wait(&value, 0, None, || false))
+      })
+      .collect::<Vec<_>>();
+    let mut woken = 0;
+    while woken < 3 {
+      woken += notify(&value, 1);
+      std::thread::yield_now();
+    }
+    for waiter in waiters {
+      assert_eq!(waiter.join().unwrap(), WaitResult::Ok);
+    }
+  }
+
+  #[test]
+  fn wait_terminated() {
+    let value = Arc::new(AtomicI32::new(0));
+    let terminated = Arc::new(AtomicBool::new(false));
+    let waiter = {
+      let value = value.clone();
+      let terminated = terminated.clone();
+      std::thread::spawn(move || {
+        wait(&value, 0, None, || terminated.load(Ordering::SeqCst))
+      })
+    };
+    terminated.store(true, Ordering::SeqCst);
+    assert_eq!(waiter.join().unwrap(), WaitResult::TimedOut);
+  }
+}
@@ -305,7 +305,7 @@ async fn op_host_recv_ctrl(
       (handle.worker_handle.clone(), handle.cancel_handle.clone())
     } else {
       // If handle was not found it means worker has already shutdown
-      return Ok(WorkerControlEvent::Close);
+      return Ok(WorkerControlEvent::Close(0));
     }
   };
 
@@ -324,12 +324,12 @@ async fn op_host_recv_ctrl(
     Ok(Ok(None)) => {
       // If there was no event from worker it means it has already been closed.
       close_channel(state, id, WorkerChannel::Ctrl);
-      Ok(WorkerControlEvent::Close)
+      Ok(WorkerControlEvent::Close(worker_handle.exit_code()))
     }
     Ok(Err(err)) => Err(err),
     Err(_) => {
       // The worker was terminated.
-      Ok(WorkerControlEvent::Close)
+      Ok(WorkerControlEvent::Close(1))
     }
   }
 }
@@ -44,6 +44,15 @@ impl std::ops::DerefMut for PermissionsContainer {
 }
 
 impl deno_node::NodePermissions for PermissionsContainer {
+  #[inline(always)]
+  fn check_net(
+    &mut self,
+    host: (&str, Option<u16>),
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    self.0.check_net(&host, api_name)
+  }
+
   #[inline(always)]
   fn check_net_url(
     &mut self,
@@ -48,7 +48,10 @@ extension!(runtime,
     "40_process.js",
     "40_signals.js",
     "40_tty.js",
+    "41_acme.js",
     "41_prompt.js",
+    "41_resource_scope.js",
+    "41_sync_channel.js",
     "90_deno_ns.js",
     "98_global_scope_shared.js",
     "98_global_scope_window.js",
@@ -73,6 +73,13 @@ impl deno_napi::NapiPermissions for Permissions {
 }
 
 impl deno_node::NodePermissions for Permissions {
+  fn check_net(
+    &mut self,
+    _host: (&str, Option<u16>),
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
   fn check_net_url(
     &mut self,
     _url: &deno_core::url::Url,
@@ -241,7 +248,7 @@ pub fn create_runtime_snapshot(
     deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
       deno_broadcast_channel::InMemoryBroadcastChannel::default(),
     ),
-    deno_ffi::deno_ffi::init_ops_and_esm::<Permissions>(),
+    deno_ffi::deno_ffi::init_ops_and_esm::<Permissions>(Default::default()),
     deno_net::deno_net::init_ops_and_esm::<Permissions>(None, None),
     deno_tls::deno_tls::init_ops_and_esm(),
     deno_kv::deno_kv::init_ops_and_esm(deno_kv::sqlite::SqliteDbHandler::<
@@ -265,7 +272,9 @@ pub fn create_runtime_snapshot(
     ops::os::deno_os::init_ops(Default::default()),
     ops::permissions::deno_permissions::init_ops(),
     ops::process::deno_process::init_ops(),
+    ops::resource_scope::deno_resource_scope::init_ops(),
     ops::signal::deno_signal::init_ops(),
+    ops::sync_channel::deno_sync_channel::init_ops(),
     ops::tty::deno_tty::init_ops(),
     ops::http::deno_http_runtime::init_ops(),
     ops::bootstrap::deno_bootstrap::init_ops(Some(snapshot_options)),
@@ -1,5 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use crate::inspector_server::InspectorServer;
+use crate::metrics::RuntimeMetrics;
+use crate::op_timing::OpTimingTracker;
 use crate::ops;
 use crate::ops::worker_host::WorkersTable;
 use crate::permissions::PermissionsContainer;
@@ -7,8 +9,10 @@ use crate::shared::maybe_transpile_source;
 use crate::shared::runtime;
 use crate::tokio_util::create_and_run_current_thread;
 use crate::worker::create_op_metrics;
+use crate::worker::custom_module_evaluation_callback;
 use crate::worker::import_meta_resolve_callback;
 use crate::worker::validate_import_attributes_callback;
+use crate::worker::ExitCode;
 use crate::worker::FormatJsErrorFn;
 use crate::BootstrapOptions;
 use deno_broadcast_channel::InMemoryBroadcastChannel;
@@ -103,7 +107,8 @@ pub enum WebWorkerType {
 pub enum WorkerControlEvent {
   Error(AnyError),
   TerminalError(AnyError),
-  Close,
+  /// The worker was closed, with the exit code it set.
+  Close(i32),
 }
 
 use deno_core::serde::Serializer;
@@ -116,7 +121,7 @@ impl Serialize for WorkerControlEvent {
     let type_id = match &self {
       WorkerControlEvent::TerminalError(_) => 1_i32,
       WorkerControlEvent::Error(_) => 2_i32,
-      WorkerControlEvent::Close => 3_i32,
+      WorkerControlEvent::Close(_) => 3_i32,
     };
 
     match self {
@@ -142,7 +147,9 @@ impl Serialize for WorkerControlEvent {
 
         Serialize::serialize(&(type_id, value), serializer)
       }
-      _ => Serialize::serialize(&(type_id, ()), serializer),
+      WorkerControlEvent::Close(exit_code) => {
+        Serialize::serialize(&(type_id, exit_code), serializer)
+      }
     }
   }
 }
@@ -224,6 +231,7 @@ pub struct SendableWebWorkerHandle {
   has_terminated: Arc<AtomicBool>,
   terminate_waker: Arc<AtomicWaker>,
   isolate_handle: v8::IsolateHandle,
+  exit_code: ExitCode,
 }
 
 impl From<SendableWebWorkerHandle> for WebWorkerHandle {
@@ -235,6 +243,7 @@ impl From<SendableWebWorkerHandle> for WebWorkerHandle {
       has_terminated: handle.has_terminated,
       terminate_waker: handle.terminate_waker,
       isolate_handle: handle.isolate_handle,
+      exit_code: handle.exit_code,
     }
   }
 }
@@ -254,6 +263,7 @@ pub struct WebWorkerHandle {
   has_terminated: Arc<AtomicBool>,
   terminate_waker: Arc<AtomicWaker>,
   isolate_handle: v8::IsolateHandle,
+  exit_code: ExitCode,
 }
 
 impl WebWorkerHandle {
@@ -267,6 +277,12 @@ impl WebWorkerHandle {
     Ok(receiver.next().await)
   }
 
+  /// The exit code set by the worker, with `Deno.exit()` or
+  /// `process.exitCode`.
+  pub fn exit_code(&self) -> i32 {
+    self.exit_code.get()
+  }
+
   /// Terminate the worker
   /// This function will set the termination signal, close the message channel,
   /// and schedule to terminate the isolate after two seconds.
@@ -307,6 +323,7 @@ fn create_handles(
   isolate_handle: v8::IsolateHandle,
   name: String,
   worker_type: WebWorkerType,
+  exit_code: ExitCode,
 ) -> (WebWorkerInternalHandle, SendableWebWorkerHandle) {
   let (parent_port, worker_port) = create_entangled_message_port();
   let (ctrl_tx, ctrl_rx) = mpsc::channel::<WorkerControlEvent>(1);
@@ -331,6 +348,7 @@ fn create_handles(
     has_terminated,
     terminate_waker,
     isolate_handle,
+    exit_code,
   };
   (internal_handle, external_handle)
 }
@@ -378,7 +396,9 @@ pub struct WebWorkerOptions {
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub stdio: Stdio,
   pub feature_checker: Arc<FeatureChecker>,
+  pub ffi_static_libraries: deno_ffi::StaticLibraries,
   pub strace_ops: Option<Vec<String>>,
+  pub op_metrics: bool,
   pub close_on_idle: bool,
   pub maybe_worker_metadata: Option<WorkerMetadata>,
 }
@@ -424,6 +444,25 @@ impl WebWorker {
       CreateCache(Arc::new(create_cache_fn))
     });
 
+    // Get our op metrics
+    let runtime_metrics = options
+      .feature_checker
+      .check(ops::runtime::UNSTABLE_FEATURE_NAME)
+      .then(|| Rc::new(RuntimeMetrics::default()));
+    let op_timing_tracker = (options.op_metrics || runtime_metrics.is_some())
+      .then(|| Rc::new(OpTimingTracker::default()));
+
+    // With --unstable-metrics, count the bytes moved by the resource ops.
+    // The middleware of an extension applies to the ops of every extension.
+    let mut runtime_ext =
+      ops::runtime::deno_runtime::init_ops_and_esm(main_module.clone());
+    if runtime_metrics.is_some() {
+      runtime_ext.middleware_fn = Some(Box::new(crate::metrics::instrument_op));
+    }
+
+    // Reported to the host when the worker is closed.
+    let exit_code = ExitCode::default();
+
     // NOTE(bartlomieju): ordering is important here, keep it in sync with
     // `runtime/worker.rs` and `runtime/snapshot.rs`!
 
@@ -462,7 +501,9 @@ impl WebWorker {
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
         options.broadcast_channel.clone(),
       ),
-      deno_ffi::deno_ffi::init_ops_and_esm::<PermissionsContainer>(),
+      deno_ffi::deno_ffi::init_ops_and_esm::<PermissionsContainer>(
+        options.ffi_static_libraries.clone(),
+      ),
       deno_net::deno_net::init_ops_and_esm::<PermissionsContainer>(
         options.root_cert_store_provider.clone(),
         options.unsafely_ignore_certificate_errors.clone(),
@@ -495,16 +536,18 @@ impl WebWorker {
         options.fs,
       ),
       // Runtime ops that are always initialized for WebWorkers
-      ops::runtime::deno_runtime::init_ops_and_esm(main_module.clone()),
+      runtime_ext,
       ops::worker_host::deno_worker_host::init_ops_and_esm(
         options.create_web_worker_cb.clone(),
         options.format_js_error_fn.clone(),
       ),
       ops::fs_events::deno_fs_events::init_ops_and_esm(),
-      ops::os::deno_os_worker::init_ops_and_esm(),
+      ops::os::deno_os_worker::init_ops_and_esm(exit_code.clone()),
       ops::permissions::deno_permissions::init_ops_and_esm(),
       ops::process::deno_process::init_ops_and_esm(),
+      ops::resource_scope::deno_resource_scope::init_ops_and_esm(),
       ops::signal::deno_signal::init_ops_and_esm(),
+      ops::sync_channel::deno_sync_channel::init_ops_and_esm(),
       ops::tty::deno_tty::init_ops_and_esm(),
       ops::http::deno_http_runtime::init_ops_and_esm(),
       ops::bootstrap::deno_bootstrap::init_ops_and_esm(None),
@@ -532,14 +575,17 @@ impl WebWorker {
     #[cfg(feature = "only_snapshotted_js_sources")]
     options.startup_snapshot.as_ref().expect("A user snapshot was not provided, even though 'only_snapshotted_js_sources' is used.");
 
-    // Get our op metrics
     let (op_summary_metrics, op_metrics_factory_fn) = create_op_metrics(
       options.bootstrap.enable_op_summary_metrics,
+      op_timing_tracker.clone(),
       options.strace_ops,
     );
 
     let mut js_runtime = JsRuntime::new(RuntimeOptions {
-      module_loader: Some(options.module_loader.clone()),
+      module_loader: Some(match &runtime_metrics {
+        Some(metrics) => metrics.module_loader(options.module_loader.clone()),
+        None => options.module_loader.clone(),
+      }),
       startup_snapshot: options.startup_snapshot,
       source_map_getter: options.source_map_getter,
       get_error_class_fn: options.get_error_class_fn,
@@ -558,12 +604,21 @@ impl WebWorker {
       validate_import_attributes_cb: Some(Box::new(
         validate_import_attributes_callback,
       )),
+      custom_module_evaluation_cb: Some(Box::new(
+        custom_module_evaluation_callback,
+      )),
       ..Default::default()
     });
 
     if let Some(op_summary_metrics) = op_summary_metrics {
       js_runtime.op_state().borrow_mut().put(op_summary_metrics);
     }
+    if let Some(op_timing_tracker) = op_timing_tracker {
+      js_runtime.op_state().borrow_mut().put(op_timing_tracker);
+    }
+    if let Some(metrics) = runtime_metrics {
+      js_runtime.op_state().borrow_mut().put(metrics);
+    }
 
     if let Some(server) = options.maybe_inspector_server.clone() {
       server.register_inspector(
@@ -582,7 +637,7 @@ impl WebWorker {
     let (internal_handle, external_handle) = {
       let handle = js_runtime.v8_isolate().thread_safe_handle();
       let (internal_handle, external_handle) =
-        create_handles(handle, name.clone(), options.worker_type);
+        create_handles(handle, name.clone(), options.worker_type, exit_code);
       let op_state = js_runtime.op_state();
       let mut op_state = op_state.borrow_mut();
       op_state.put(internal_handle.clone());
@@ -917,6 +972,18 @@ pub fn run_web_worker(
   let fut = async move {
     let internal_handle = worker.internal_handle.clone();
 
+    // The worker is created outside of its tokio runtime, so the event loop
+    // lag can only be sampled from here.
+    let runtime_metrics = worker
+      .js_runtime
+      .op_state()
+      .borrow()
+      .try_borrow::<Rc<RuntimeMetrics>>()
+      .cloned();
+    if let Some(metrics) = runtime_metrics {
+      metrics.start_event_loop_sampler();
+    }
+
     // Execute provided source code immediately
     let result = if let Some(source_code) = maybe_source_code.take() {
       let r = worker.execute_script(located_script_name!(), source_code.into());
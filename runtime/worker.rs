@@ -12,12 +12,15 @@ use std::time::Instant;
 use deno_broadcast_channel::InMemoryBroadcastChannel;
 use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
 use deno_core::merge_op_metrics;
 use deno_core::v8;
 use deno_core::CompiledWasmModuleStore;
+use deno_core::CustomModuleEvaluationKind;
 use deno_core::Extension;
+use deno_core::FastString;
 use deno_core::FeatureChecker;
 use deno_core::FsModuleLoader;
 use deno_core::GetErrorClassFn;
@@ -26,6 +29,7 @@ use deno_core::LocalInspectorSession;
 use deno_core::ModuleCodeString;
 use deno_core::ModuleId;
 use deno_core::ModuleLoader;
+use deno_core::ModuleSourceCode;
 use deno_core::ModuleSpecifier;
 use deno_core::OpMetricsFactoryFn;
 use deno_core::OpMetricsSummaryTracker;
@@ -41,12 +45,16 @@ use deno_kv::dynamic::MultiBackendDbHandler;
 use deno_tls::RootCertStoreProvider;
 use deno_tls::TlsKeys;
 use deno_web::BlobStore;
+use deno_webstorage::SqliteWebStorageProvider;
+use deno_webstorage::WebStorageProvider;
 use log::debug;
 
 use crate::code_cache::CodeCache;
 use crate::code_cache::CodeCacheType;
 use crate::fs_util::code_timestamp;
 use crate::inspector_server::InspectorServer;
+use crate::metrics::RuntimeMetrics;
+use crate::op_timing::OpTimingTracker;
 use crate::ops;
 use crate::permissions::PermissionsContainer;
 use crate::shared::maybe_transpile_source;
@@ -76,7 +84,7 @@ pub fn validate_import_attributes_callback(
   for (key, value) in attributes {
     let msg = if key != "type" {
       Some(format!("\"{key}\" attribute is not supported."))
-    } else if value != "json" {
+    } else if !matches!(value.as_str(), "json" | "bytes" | "text") {
       Some(format!("\"{value}\" is not a valid module type."))
     } else {
       None
@@ -93,6 +101,45 @@ pub fn validate_import_attributes_callback(
   }
 }
 
+/// Evaluates modules imported `with { type: "bytes" }` to a `Uint8Array` and
+/// modules imported `with { type: "text" }` to a string.
+pub fn custom_module_evaluation_callback(
+  scope: &mut v8::HandleScope,
+  module_type: Cow<'_, str>,
+  _module_name: &FastString,
+  code: ModuleSourceCode,
+) -> Result<CustomModuleEvaluationKind, AnyError> {
+  let bytes = match &code {
+    ModuleSourceCode::String(code) => code.as_bytes(),
+    ModuleSourceCode::Bytes(code) => code.as_bytes(),
+  };
+  let value: v8::Local<v8::Value> = match module_type.as_ref() {
+    "bytes" => {
+      let len = bytes.len();
+      let backing_store =
+        v8::ArrayBuffer::new_backing_store_from_vec(bytes.to_vec())
+          .make_shared();
+      let buffer = v8::ArrayBuffer::with_backing_store(scope, &backing_store);
+      v8::Uint8Array::new(scope, buffer, 0, len).unwrap().into()
+    }
+    "text" => {
+      let text = String::from_utf8_lossy(bytes);
+      let text = text.strip_prefix('\u{FEFF}').unwrap_or(&text);
+      v8::String::new(scope, text)
+        .ok_or_else(|| type_error("Text module is too large"))?
+        .into()
+    }
+    _ => {
+      return Err(type_error(format!(
+        "Importing '{module_type}' modules is not supported"
+      )))
+    }
+  };
+  Ok(CustomModuleEvaluationKind::Synthetic(v8::Global::new(
+    scope, value,
+  )))
+}
+
 #[derive(Clone, Default)]
 pub struct ExitCode(Arc<AtomicI32>);
 
@@ -173,12 +220,18 @@ pub struct WorkerOptions {
   pub should_wait_for_inspector_session: bool,
   /// If Some, print a low-level trace output for ops matching the given patterns.
   pub strace_ops: Option<Vec<String>>,
+  /// If true, record timing histograms for every op, which can be retrieved
+  /// from JavaScript with `Deno[Deno.internal].opMetrics()`.
+  pub op_metrics: bool,
 
   /// Allows to map error type to a string "class" used to represent
   /// error in JavaScript.
   pub get_error_class_fn: Option<GetErrorClassFn>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub origin_storage_dir: Option<std::path::PathBuf>,
+  /// Backs `localStorage` and `sessionStorage`. When unset, they are kept in
+  /// SQLite, with `localStorage` under `origin_storage_dir`.
+  pub web_storage_provider: Option<Arc<dyn WebStorageProvider>>,
   pub blob_store: Arc<BlobStore>,
   pub broadcast_channel: InMemoryBroadcastChannel,
 
@@ -198,6 +251,9 @@ pub struct WorkerOptions {
   pub compiled_wasm_module_store: Option<CompiledWasmModuleStore>,
   pub stdio: Stdio,
   pub feature_checker: Arc<FeatureChecker>,
+  /// Native symbol tables linked into the binary, which can be opened with
+  /// `Deno.dlopenStatic()`.
+  pub ffi_static_libraries: deno_ffi::StaticLibraries,
 
   /// V8 code cache for module and script source code.
   pub v8_code_cache: Option<Arc<dyn CodeCache>>,
@@ -217,12 +273,14 @@ impl Default for WorkerOptions {
       should_break_on_first_statement: Default::default(),
       should_wait_for_inspector_session: Default::default(),
       strace_ops: Default::default(),
+      op_metrics: Default::default(),
       compiled_wasm_module_store: Default::default(),
       shared_array_buffer_store: Default::default(),
       maybe_inspector_server: Default::default(),
       format_js_error_fn: Default::default(),
       get_error_class_fn: Default::default(),
       origin_storage_dir: Default::default(),
+      web_storage_provider: Default::default(),
       cache_storage_dir: Default::default(),
       broadcast_channel: Default::default(),
       source_map_getter: Default::default(),
@@ -235,6 +293,7 @@ impl Default for WorkerOptions {
       bootstrap: Default::default(),
       stdio: Default::default(),
       feature_checker: Default::default(),
+      ffi_static_libraries: Default::default(),
       v8_code_cache: Default::default(),
     }
   }
@@ -242,6 +301,7 @@ impl Default for WorkerOptions {
 
 pub fn create_op_metrics(
   enable_op_summary_metrics: bool,
+  op_timing_tracker: Option<Rc<OpTimingTracker>>,
   strace_ops: Option<Vec<String>>,
 ) -> (
   Option<Rc<OpMetricsSummaryTracker>>,
@@ -303,6 +363,14 @@ pub fn create_op_metrics(
     op_summary_metrics = Some(summary);
   }
 
+  if let Some(tracker) = op_timing_tracker {
+    let timing_metrics = tracker.op_metrics_factory_fn();
+    op_metrics_factory_fn = Some(match op_metrics_factory_fn {
+      Some(f) => merge_op_metrics(f, timing_metrics),
+      None => timing_metrics,
+    });
+  }
+
   (op_summary_metrics, op_metrics_factory_fn)
 }
 
@@ -383,8 +451,15 @@ impl MainWorker {
     );
 
     // Get our op metrics
+    let runtime_metrics = options
+      .feature_checker
+      .check(ops::runtime::UNSTABLE_FEATURE_NAME)
+      .then(|| Rc::new(RuntimeMetrics::default()));
+    let op_timing_tracker = (options.op_metrics || runtime_metrics.is_some())
+      .then(|| Rc::new(OpTimingTracker::default()));
     let (op_summary_metrics, op_metrics_factory_fn) = create_op_metrics(
       options.bootstrap.enable_op_summary_metrics,
+      op_timing_tracker.clone(),
       options.strace_ops,
     );
 
@@ -396,6 +471,14 @@ impl MainWorker {
       CreateCache(Arc::new(create_cache_fn))
     });
 
+    // With --unstable-metrics, count the bytes moved by the resource ops.
+    // The middleware of an extension applies to the ops of every extension.
+    let mut runtime_ext =
+      ops::runtime::deno_runtime::init_ops_and_esm(main_module.clone());
+    if runtime_metrics.is_some() {
+      runtime_ext.middleware_fn = Some(Box::new(crate::metrics::instrument_op));
+    }
+
     // NOTE(bartlomieju): ordering is important here, keep it in sync with
     // `runtime/web_worker.rs` and `runtime/snapshot.rs`!
     let mut extensions = vec![
@@ -428,14 +511,20 @@ impl MainWorker {
         options.root_cert_store_provider.clone(),
         options.unsafely_ignore_certificate_errors.clone(),
       ),
-      deno_webstorage::deno_webstorage::init_ops_and_esm(
-        options.origin_storage_dir.clone(),
-      ),
+      deno_webstorage::deno_webstorage::init_ops_and_esm(Some(
+        options.web_storage_provider.clone().unwrap_or_else(|| {
+          Arc::new(SqliteWebStorageProvider::new(
+            options.origin_storage_dir.clone(),
+          ))
+        }),
+      )),
       deno_crypto::deno_crypto::init_ops_and_esm(options.seed),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
         options.broadcast_channel.clone(),
       ),
-      deno_ffi::deno_ffi::init_ops_and_esm::<PermissionsContainer>(),
+      deno_ffi::deno_ffi::init_ops_and_esm::<PermissionsContainer>(
+        options.ffi_static_libraries.clone(),
+      ),
       deno_net::deno_net::init_ops_and_esm::<PermissionsContainer>(
         options.root_cert_store_provider.clone(),
         options.unsafely_ignore_certificate_errors.clone(),
@@ -468,7 +557,7 @@ impl MainWorker {
         options.fs,
       ),
       // Ops from this crate
-      ops::runtime::deno_runtime::init_ops_and_esm(main_module.clone()),
+      runtime_ext,
       ops::worker_host::deno_worker_host::init_ops_and_esm(
         options.create_web_worker_cb.clone(),
         options.format_js_error_fn.clone(),
@@ -477,7 +566,9 @@ impl MainWorker {
       ops::os::deno_os::init_ops_and_esm(exit_code.clone()),
       ops::permissions::deno_permissions::init_ops_and_esm(),
       ops::process::deno_process::init_ops_and_esm(),
+      ops::resource_scope::deno_resource_scope::init_ops_and_esm(),
       ops::signal::deno_signal::init_ops_and_esm(),
+      ops::sync_channel::deno_sync_channel::init_ops_and_esm(),
       ops::tty::deno_tty::init_ops_and_esm(),
       ops::http::deno_http_runtime::init_ops_and_esm(),
       ops::bootstrap::deno_bootstrap::init_ops_and_esm(
@@ -525,7 +616,10 @@ impl MainWorker {
     });
 
     let mut js_runtime = JsRuntime::new(RuntimeOptions {
-      module_loader: Some(options.module_loader.clone()),
+      module_loader: Some(match &runtime_metrics {
+        Some(metrics) => metrics.module_loader(options.module_loader.clone()),
+        None => options.module_loader.clone(),
+      }),
       startup_snapshot: options.startup_snapshot,
       create_params: options.create_params,
       source_map_getter: options.source_map_getter,
@@ -550,6 +644,9 @@ impl MainWorker {
       validate_import_attributes_cb: Some(Box::new(
         validate_import_attributes_callback,
       )),
+      custom_module_evaluation_cb: Some(Box::new(
+        custom_module_evaluation_callback,
+      )),
       enable_code_cache: options.v8_code_cache.is_some(),
       eval_context_code_cache_cbs: options.v8_code_cache.map(|cache| {
         let cache_clone = cache.clone();
@@ -568,6 +665,13 @@ impl MainWorker {
     if let Some(op_summary_metrics) = op_summary_metrics {
       js_runtime.op_state().borrow_mut().put(op_summary_metrics);
     }
+    if let Some(op_timing_tracker) = op_timing_tracker {
+      js_runtime.op_state().borrow_mut().put(op_timing_tracker);
+    }
+    if let Some(metrics) = runtime_metrics {
+      metrics.start_event_loop_sampler();
+      js_runtime.op_state().borrow_mut().put(metrics);
+    }
     extern "C" fn message_handler(
       _msg: v8::Local<v8::Message>,
       _exception: v8::Local<v8::Value>,
@@ -4,6 +4,7 @@ use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use serde::Serialize;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::thread;
 
 use deno_terminal::colors;
@@ -84,6 +85,14 @@ pub struct BootstrapOptions {
   pub user_agent: String,
   pub inspect: bool,
   pub has_node_modules_dir: bool,
+  /// Resolve CommonJS modules by the path they were required through
+  /// instead of their real path (`--preserve-symlinks`).
+  pub preserve_symlinks: bool,
+  /// Prefix the lines printed by the global `console` with a timestamp.
+  pub console_timestamps: bool,
+  /// The root of `DENO_DIR`, under which `Deno.createAcme()` stores its
+  /// certificates by default.
+  pub deno_dir: Option<PathBuf>,
   pub argv0: Option<String>,
   pub node_debug: Option<String>,
   pub node_ipc_fd: Option<i64>,
@@ -121,6 +130,9 @@ impl Default for BootstrapOptions {
       inspect: Default::default(),
       args: Default::default(),
       has_node_modules_dir: Default::default(),
+      preserve_symlinks: false,
+      console_timestamps: false,
+      deno_dir: None,
       argv0: None,
       node_debug: None,
       node_ipc_fd: None,
@@ -173,6 +185,10 @@ struct BootstrapV8<'a>(
   u16,
   // serve host
   Option<&'a str>,
+  // preserve_symlinks
+  bool,
+  // console_timestamps
+  bool,
 );
 
 impl BootstrapOptions {
@@ -199,6 +215,8 @@ impl BootstrapOptions {
       self.mode as u8 as _,
       self.serve_port.unwrap_or_default(),
       self.serve_host.as_deref(),
+      self.preserve_symlinks,
+      self.console_timestamps,
     );
 
     bootstrap.serialize(ser).unwrap()
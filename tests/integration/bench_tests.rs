@@ -36,6 +36,28 @@ fn file_protocol() {
     .assert_matches_file("bench/file_protocol.out");
 }
 
+#[test]
+fn output_jsonl() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main_bench.js", "Deno.bench('noop', () => {});");
+  let output = context
+    .new_command()
+    .args("bench --output=sub_dir/output.jsonl main_bench.js")
+    .run();
+  output.skip_output_check();
+  output.assert_exit_code(0);
+  temp_dir
+    .path()
+    .join("sub_dir/output.jsonl")
+    .assert_matches_text(
+      r#"[WILDCARD]{"type":"plan",[WILDCARD]"usedOnly":false}
+[WILDCARD]{"type":"result",[WILDCARD]"stats":{"n":[WILDCARD]}}
+{"type":"end",[WILDCARD]"failed":0,"rss":[WILDCARD]}
+"#,
+    );
+}
+
 #[test]
 fn conditionally_loads_type_graph() {
   let context = TestContext::default();
@@ -361,6 +361,90 @@ fn standalone_runtime_flags() {
     .assert_exit_code(1);
 }
 
+#[test]
+fn standalone_permission_override_never() {
+  let context = TestContextBuilder::new().build();
+  let dir = context.temp_dir();
+  let exe = if cfg!(windows) {
+    dir.path().join("never.exe")
+  } else {
+    dir.path().join("never")
+  };
+  context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--allow-read",
+      "--permission-override=never",
+      "--output",
+      &exe.to_string_lossy(),
+      "./compile/permission_override.ts",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let out_dir = dir.path().join("with space");
+  out_dir.create_dir_all();
+  let out_file = out_dir.join("out.txt");
+  context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .env(
+      "DENO_STANDALONE_PERMISSIONS",
+      serde_json::json!([format!("--allow-write={}", out_dir)]).to_string(),
+    )
+    .name(&exe)
+    .args_vec([out_file.to_string_lossy().to_string()])
+    .split_output()
+    .run()
+    .assert_stdout_matches_text("granted\n")
+    .assert_stderr_matches_text(
+      "[WILDCARD]PermissionDenied: Requires write access to[WILDCARD]",
+    )
+    .assert_exit_code(1);
+  assert!(!out_file.exists());
+}
+
+#[test]
+fn standalone_permission_override_allow() {
+  let context = TestContextBuilder::new().build();
+  let dir = context.temp_dir();
+  let exe = if cfg!(windows) {
+    dir.path().join("allow.exe")
+  } else {
+    dir.path().join("allow")
+  };
+  context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--allow-read",
+      "--permission-override=allow",
+      "--output",
+      &exe.to_string_lossy(),
+      "./compile/permission_override.ts",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let out_dir = dir.path().join("with space");
+  out_dir.create_dir_all();
+  let out_file = out_dir.join("out.txt");
+  context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .env(
+      "DENO_STANDALONE_PERMISSIONS",
+      serde_json::json!([format!("--allow-write={}", out_dir)]).to_string(),
+    )
+    .name(&exe)
+    .args_vec([out_file.to_string_lossy().to_string()])
+    .run()
+    .assert_matches_text("granted\nwritten\n")
+    .assert_exit_code(0);
+  assert_eq!(out_file.read_to_string(), "Hello");
+}
+
 #[test]
 fn standalone_ext_flag_ts() {
   let context = TestContextBuilder::new().build();
@@ -556,6 +556,64 @@ File         | Branch % | Line % |
   );
 }
 
+#[test]
+fn merge_shards() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir().path();
+
+  for shard in ["shard1", "shard2"] {
+    let output = context
+      .new_command()
+      .args_vec(vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        format!("--coverage={}", tempdir.join(shard)),
+        "coverage/multisource".to_string(),
+      ])
+      .run();
+
+    output.assert_exit_code(0);
+    output.skip_output_check();
+  }
+
+  let merged = tempdir.join("merged");
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      format!("--merge={}", merged),
+      format!("{}/", tempdir.join("shard1")),
+      format!("{}/", tempdir.join("shard2")),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "Merged the coverage into 4 profiles in [WILDCARD]merged\n",
+  );
+  assert_eq!(std::fs::read_dir(merged.as_path()).unwrap().count(), 4);
+
+  let output = context
+    .new_command()
+    .args_vec(vec!["coverage".to_string(), format!("{}/", merged)])
+    .run();
+
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "----------------------------------
+File         | Branch % | Line % |
+----------------------------------
+ bar.ts      |      0.0 |   57.1 |
+ baz/quux.ts |      0.0 |   28.6 |
+ baz/qux.ts  |    100.0 |  100.0 |
+ foo.ts      |     50.0 |   76.9 |
+----------------------------------
+ All files   |     40.0 |   61.0 |
+----------------------------------
+",
+  );
+}
+
 #[test]
 fn test_collect_summary_with_no_matches() {
   let context: TestContext = TestContext::default();
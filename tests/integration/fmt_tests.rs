@@ -188,6 +188,18 @@ itest!(fmt_check_parse_error {
   exit_code: 1,
 });
 
+itest!(fmt_check_diff_format_patch {
+  args: "fmt --check --diff-format=patch fmt/badly_formatted.mjs",
+  output: "fmt/fmt_check_diff_format_patch.out",
+  exit_code: 1,
+});
+
+itest!(fmt_check_diff_format_json {
+  args: "fmt --check --diff-format=json fmt/badly_formatted.mjs",
+  output: "fmt/fmt_check_diff_format_json.out",
+  exit_code: 1,
+});
+
 itest!(fmt_check_invalid_data {
   args: "fmt --check fmt/invalid_data.json",
   output: "fmt/invalid_data.out",
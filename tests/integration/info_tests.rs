@@ -90,6 +90,12 @@ itest!(json_file {
   exit_code: 0,
 });
 
+itest!(manifest_file {
+  args: "info --quiet --manifest info/json_output/main.ts",
+  output: "info/json_output/main_manifest.out",
+  exit_code: 0,
+});
+
 itest!(info_json_deps_order {
   args: "info --json info/076_info_json_deps_order.ts",
   output: "info/076_info_json_deps_order.out",
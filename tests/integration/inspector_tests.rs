@@ -571,6 +571,69 @@ async fn inspector_does_not_hang() {
   assert!(tester.child.wait().unwrap().success());
 }
 
+#[tokio::test]
+async fn inspector_console_group() {
+  let script = util::testdata_path().join("inspector/console_group.js");
+  let child = util::deno_cmd()
+    .arg("run")
+    .arg(inspect_flag_with_unique_port("--inspect-brk"))
+    .env("NO_COLOR", "1")
+    .arg(script)
+    .piped_output()
+    .spawn()
+    .unwrap();
+
+  let mut tester = InspectorTester::create(child, ignore_script_parsed).await;
+
+  tester.assert_stderr_for_inspect_brk();
+
+  tester.send(json!({"id":1,"method":"Runtime.enable"})).await;
+  tester.assert_received_messages(
+      &[r#"{"id":1,"result":{}}"#],
+      &[
+        r#"{"method":"Runtime.executionContextCreated","params":{"context":{"id":1,"#
+      ],
+    )
+    .await;
+
+  tester
+    .send(json!({"id":2,"method":"Runtime.runIfWaitingForDebugger"}))
+    .await;
+  tester
+    .assert_received_messages(&[r#"{"id":2,"result":{}}"#], &[])
+    .await;
+
+  // The terminal indents the messages of a group...
+  assert_eq!(&tester.stdout_line(), "outer");
+  assert_eq!(&tester.stdout_line(), "    inner");
+  assert_eq!(&tester.stdout_line(), "after");
+
+  // ...while CDP clients get the group structure and do their own indenting.
+  let mut calls = vec![];
+  while calls.len() < 4 {
+    let msg = tester.recv_as_json().await;
+    if msg["method"] == "Runtime.consoleAPICalled" {
+      let params = &msg["params"];
+      calls.push((
+        params["type"].as_str().unwrap().to_string(),
+        params["args"][0]["value"].as_str().map(ToString::to_string),
+      ));
+    }
+  }
+  assert_eq!(
+    calls,
+    vec![
+      ("startGroup".to_string(), Some("outer".to_string())),
+      ("log".to_string(), Some("inner".to_string())),
+      ("endGroup".to_string(), None),
+      ("log".to_string(), Some("after".to_string())),
+    ]
+  );
+
+  tester.child.kill().unwrap();
+  tester.child.wait().unwrap();
+}
+
 #[tokio::test]
 async fn inspector_without_brk_runs_code() {
   let script = util::testdata_path().join("inspector/inspector4.js");
@@ -12,6 +12,7 @@ util::unit_test_factory!(
   "*.ts",
   [
     abort_controller_test,
+    acme_test,
     blob_test,
     body_test,
     broadcast_channel_test,
@@ -78,8 +79,10 @@ util::unit_test_factory!(
     remove_test,
     rename_test,
     request_test,
+    resource_scope_test,
     resources_test,
     response_test,
+    scheduler_test,
     serve_test,
     signal_test,
     stat_test,
@@ -88,6 +91,7 @@ util::unit_test_factory!(
     structured_clone_test,
     symbol_test,
     symlink_test,
+    sync_channel_test,
     sync_test,
     test_util,
     testing_test,
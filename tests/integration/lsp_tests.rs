@@ -9238,6 +9238,51 @@ struct PerformanceAverages {
   averages: Vec<PerformanceAverage>,
 }
 
+#[test]
+fn lsp_cache_status_and_recache() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "import * as a from \"http://127.0.0.1:4545/subdir/mod1.ts\";\n\nconsole.log(a);\n"
+    }
+  }));
+  client.write_request(
+    "workspace/executeCommand",
+    json!({
+      "command": "deno.cache",
+      "arguments": [[], "file:///a/file.ts"],
+    }),
+  );
+  let res = client.write_request("deno/cacheStatus", json!(null));
+  let remote_modules = res["remoteModules"].as_array().unwrap();
+  let module = remote_modules
+    .iter()
+    .find(|m| m["specifier"] == "http://127.0.0.1:4545/subdir/mod1.ts")
+    .unwrap();
+  assert_eq!(module["uri"], "deno:/http/127.0.0.1%3A4545/subdir/mod1.ts");
+  assert_eq!(module["mediaType"], "TypeScript");
+  assert!(module["size"].as_u64().unwrap() > 0);
+  assert_eq!(res["npmPackages"], json!([]));
+
+  let res = client.write_request(
+    "deno/recache",
+    json!({
+      "specifiers": ["http://127.0.0.1:4545/subdir/mod1.ts"],
+      "referrer": "file:///a/file.ts",
+    }),
+  );
+  assert_eq!(res, json!(true));
+  client.shutdown();
+}
+
 #[test]
 fn lsp_performance() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -59,11 +59,14 @@ util::unit_test_factory!(
     console_test,
     crypto_cipher_test = crypto / crypto_cipher_test,
     crypto_cipher_gcm_test = crypto / crypto_cipher_gcm_test,
+    crypto_dh_test = crypto / crypto_dh_test,
     crypto_hash_test = crypto / crypto_hash_test,
     crypto_key_test = crypto / crypto_key_test,
     crypto_sign_test = crypto / crypto_sign_test,
+    crypto_x509_test = crypto / crypto_x509_test,
     events_test,
     dgram_test,
+    dns_test,
     domain_test,
     fs_test,
     http_test,
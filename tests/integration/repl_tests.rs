@@ -1131,3 +1131,37 @@ fn eval_file_promise_error() {
   assert_contains!(out, "Uncaught undefined");
   assert!(err.is_empty());
 }
+
+#[test]
+fn pty_debugger_breakpoint() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  context.temp_dir().write(
+    "add.js",
+    "export function add(a, b) {\n  const sum = a + b;\n  return sum;\n}\n",
+  );
+  context
+    .new_command()
+    .args_vec(["repl"])
+    .with_pty(|mut console| {
+      console.write_line("import { add } from './add.js';");
+      console.expect("undefined");
+      console.write_line(".break ./add.js:2");
+      console.expect("Breakpoint 1 set at");
+      console.write_line(".breakpoints");
+      console.expect("add.js:2");
+      console.write_line(".debug add(1, 2)");
+      console.expect_all(&["Paused at add", "const sum = a + b;", "debug>"]);
+      console.write_line(".scope");
+      console.expect_all(&["a: 1", "b: 2"]);
+      console.write_line(".next");
+      console.expect("return sum;");
+      console.write_line("sum * 2");
+      console.expect("6");
+      console.write_line(".continue");
+      console.expect("3");
+      console.write_line(".delete 1");
+      console.expect("Breakpoint 1 deleted");
+      console.write_line(".debug add(2, 2)");
+      console.expect("4");
+    });
+}
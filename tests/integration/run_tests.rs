@@ -1003,6 +1003,61 @@ fn lock_redirects() {
   );
 }
 
+#[test]
+fn lock_bytes_import() {
+  let context = TestContextBuilder::new()
+    .use_temp_cwd()
+    .use_http_server()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", "{}"); // cause a lockfile to be created
+  temp_dir.write(
+    "main.ts",
+    r#"import bytes from "http://localhost:4545/image/1x1-white.png" with {
+  type: "bytes",
+};
+console.log(bytes.length);
+"#,
+  );
+  // the image isn't valid UTF-8, so its raw bytes are hashed
+  let lockfile_text = r#"{
+  "version": "3",
+  "remote": {
+    "http://localhost:4545/image/1x1-white.png": "b31782b0ecaa71394f1bccf3cc4647ba70b7208464244546b48521a71e1f1dd0"
+  }
+}
+"#;
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text(
+      "Download http://localhost:4545/image/1x1-white.png\n109\n",
+    )
+    .assert_exit_code(0);
+  assert_eq!(temp_dir.read_to_string("deno.lock"), lockfile_text);
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text("109\n")
+    .assert_exit_code(0);
+  assert_eq!(temp_dir.read_to_string("deno.lock"), lockfile_text);
+
+  temp_dir.write("deno.lock", lockfile_text.replace("b31782b0", "00000000"));
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text(
+      r#"error: The source code is invalid, as it does not match the expected hash in the lock file.
+  Specifier: http://localhost:4545/image/1x1-white.png
+  Lock file: [WILDCARD]deno.lock
+"#,
+    )
+    .assert_exit_code(10);
+}
+
 #[test]
 fn lock_deno_json_package_json_deps() {
   let context = TestContextBuilder::new()
@@ -1575,6 +1630,12 @@ itest!(set_exit_code_2 {
   exit_code: 42,
 });
 
+itest!(exit_code {
+  args: "run --no-check run/exit_code.ts",
+  output: "run/exit_code.ts.out",
+  exit_code: 42,
+});
+
 itest!(op_exit_op_set_exit_code_in_worker {
   args: "run --no-check --allow-read run/op_exit_op_set_exit_code_in_worker.ts",
   exit_code: 21,
@@ -1750,6 +1811,21 @@ itest!(v8_flags_env_run {
   output: "run/v8_flags.js.out",
 });
 
+itest!(op_metrics {
+  args: "run --quiet --allow-read --op-metrics run/op_metrics.ts",
+  output: "run/op_metrics.ts.out",
+});
+
+itest!(op_metrics_disabled {
+  args: "run --quiet run/op_metrics.ts",
+  output: "run/op_metrics_disabled.out",
+});
+
+itest!(runtime_metrics {
+  args: "run --quiet --allow-read --unstable-metrics run/runtime_metrics.ts",
+  output: "run/runtime_metrics.ts.out",
+});
+
 itest!(v8_flags_unrecognized {
   args: "repl --v8-flags=--foo,bar,--trace-gc,-baz",
   output: "run/v8_flags_unrecognized.out",
@@ -3412,6 +3488,17 @@ itest!(import_attributes_type_check {
   exit_code: 1,
 });
 
+itest!(import_attributes_bytes_text_import {
+  args: "run --allow-read --check import_attributes/bytes_text_import.ts",
+  output: "import_attributes/bytes_text_import.out",
+});
+
+itest!(import_attributes_bytes_text_type_check {
+  args: "run --allow-read --check import_attributes/bytes_text_type_check.ts",
+  output: "import_attributes/bytes_text_type_check.out",
+  exit_code: 1,
+});
+
 itest!(delete_window {
   args: "run run/delete_window.js",
   output_str: Some("true\n"),
@@ -1,8 +1,16 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::future::poll_fn;
+use std::io::BufRead;
 use std::io::Read;
+use std::sync::Arc;
+use std::time::Duration;
 
+use bytes::Buf;
+use bytes::Bytes;
 use deno_fetch::reqwest;
+use deno_tls::rustls;
+use deno_tls::rustls_pemfile;
 use pretty_assertions::assert_eq;
 use regex::Regex;
 use test_util as util;
@@ -92,3 +100,95 @@ async fn deno_serve_no_args() {
   child.kill().unwrap();
   child.wait().unwrap();
 }
+
+type H3RequestStream =
+  h3::client::RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+
+async fn read_h3_body(stream: &mut H3RequestStream) -> String {
+  let mut body = vec![];
+  while let Some(mut chunk) = stream.recv_data().await.unwrap() {
+    body.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
+  }
+  String::from_utf8(body).unwrap()
+}
+
+#[tokio::test]
+async fn deno_serve_h3() {
+  let mut child = util::deno_cmd()
+    .current_dir(util::testdata_path())
+    .arg("run")
+    .arg("--unstable-http")
+    .arg("--allow-net")
+    .arg("--allow-read")
+    .arg("./serve/h3.ts")
+    .stdout_piped()
+    .spawn()
+    .unwrap();
+  let stdout = child.stdout.as_mut().unwrap();
+  let mut stdout_lines =
+    std::io::BufReader::new(stdout).lines().map(|r| r.unwrap());
+  let msg = stdout_lines.next().unwrap();
+  let port_regex = Regex::new(r"(\d+)").unwrap();
+  let port = port_regex.find(&msg).unwrap().as_str();
+
+  let mut roots = rustls::RootCertStore::empty();
+  let ca_certs = rustls_pemfile::certs(
+    &mut &include_bytes!("../testdata/tls/RootCA.pem")[..],
+  )
+  .unwrap();
+  for cert in ca_certs {
+    roots.add(&rustls::Certificate(cert)).unwrap();
+  }
+  let mut tls_config = rustls::ClientConfig::builder()
+    .with_safe_defaults()
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+  tls_config.alpn_protocols = vec![b"h3".to_vec()];
+
+  let mut endpoint =
+    quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+  endpoint
+    .set_default_client_config(quinn::ClientConfig::new(Arc::new(tls_config)));
+  let connection = endpoint
+    .connect(format!("127.0.0.1:{port}").parse().unwrap(), "localhost")
+    .unwrap()
+    .await
+    .unwrap();
+  let (mut driver, mut send_request) =
+    h3::client::new(h3_quinn::Connection::new(connection))
+      .await
+      .unwrap();
+  let driver = tokio::spawn(async move {
+    let _ = poll_fn(|cx| driver.poll_close(cx)).await;
+  });
+
+  let request = http::Request::get(format!("https://localhost:{port}/foo"))
+    .body(())
+    .unwrap();
+  let mut stream = send_request.send_request(request).await.unwrap();
+  stream.finish().await.unwrap();
+  let response = stream.recv_response().await.unwrap();
+  assert_eq!(response.status(), 200);
+  assert_eq!(read_h3_body(&mut stream).await, "GET /foo");
+
+  // The body is sent in several frames, and only finished after the server
+  // already started to read it
+  let request = http::Request::post(format!("https://localhost:{port}/echo"))
+    .body(())
+    .unwrap();
+  let mut stream = send_request.send_request(request).await.unwrap();
+  for chunk in ["Hello", ", ", "World"] {
+    stream.send_data(Bytes::from(chunk)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+  stream.finish().await.unwrap();
+  let response = stream.recv_response().await.unwrap();
+  assert_eq!(response.status(), 200);
+  assert_eq!(read_h3_body(&mut stream).await, "Hello, World");
+
+  drop(send_request);
+  endpoint.close(0u32.into(), b"done");
+  driver.abort();
+  child.kill().unwrap();
+  child.wait().unwrap();
+}
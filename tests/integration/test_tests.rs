@@ -266,6 +266,12 @@ itest!(resource_sanitizer {
   output: "test/sanitizer/resource_sanitizer.out",
 });
 
+itest!(resource_sanitizer_trace_steps {
+  args: "test --allow-read --trace-leaks test/sanitizer/resource_sanitizer_trace_steps.ts",
+  exit_code: 1,
+  output: "test/sanitizer/resource_sanitizer_trace_steps.out",
+});
+
 itest!(ops_sanitizer_tcp {
   args: "test --allow-net --trace-leaks test/sanitizer/ops_sanitizer_tcp.ts",
   exit_code: 1,
@@ -278,6 +284,19 @@ itest!(exit_sanitizer {
   exit_code: 1,
 });
 
+itest!(child_process_sanitizer {
+  args:
+    "test --allow-read --allow-run test/sanitizer/child_process_sanitizer.ts",
+  output: "test/sanitizer/child_process_sanitizer.out",
+  exit_code: 1,
+});
+
+itest!(env_sanitizer {
+  args: "test --allow-env test/sanitizer/env_sanitizer.ts",
+  output: "test/sanitizer/env_sanitizer.out",
+  exit_code: 1,
+});
+
 itest!(junit {
   args: "test --reporter junit test/pass.ts",
   output: "test/pass.junit.out",
@@ -312,6 +331,33 @@ fn junit_path() {
     .assert_matches_text("<?xml [WILDCARD]");
 }
 
+#[test]
+fn output_jsonl() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "test.js",
+    "Deno.test('passes', () => {});\nDeno.test('fails', () => { throw new Error('boom'); });",
+  );
+  let output = context
+    .new_command()
+    .args("test --output=sub_dir/output.jsonl test.js")
+    .run();
+  output.skip_output_check();
+  output.assert_exit_code(1);
+  temp_dir.path().join("sub_dir/output.jsonl").assert_matches_text(
+    r#"{"type":"register",[WILDCARD]"name":"passes",[WILDCARD]
+{"type":"register",[WILDCARD]"name":"fails",[WILDCARD]
+{"type":"plan",[WILDCARD]"total":2,"filteredOut":0,"usedOnly":false}
+{"type":"wait",[WILDCARD]
+{"type":"result",[WILDCARD]"status":"ok","elapsed":[WILDCARD],"rss":[WILDCARD]}
+{"type":"wait",[WILDCARD]
+{"type":"result",[WILDCARD]"status":"failed",[WILDCARD]"failure":{"message":"[WILDCARD]boom","detail":"[WILDCARD]"}}
+{"type":"summary","passed":1,"failed":1,"ignored":0,"passedSteps":0,"failedSteps":0,"ignoredSteps":0,"elapsed":[WILDCARD],"rss":[WILDCARD]}
+"#,
+  );
+}
+
 itest!(clear_timeout {
   args: "test test/clear_timeout.ts",
   exit_code: 0,
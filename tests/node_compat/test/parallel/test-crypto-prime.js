@@ -160,118 +160,118 @@ generatePrime(80, {}, common.mustSucceed((prime) => {
 
 assert(checkPrimeSync(generatePrimeSync(80, {})));
 
-// generatePrime(32, { safe: true }, common.mustSucceed((prime) => {
-//   assert(checkPrimeSync(prime));
-//   const buf = Buffer.from(prime);
-//   const val = buf.readUInt32BE();
-//   const check = (val - 1) / 2;
-//   buf.writeUInt32BE(check);
-//   assert(checkPrimeSync(buf));
-// }));
-
-// {
-//   const prime = generatePrimeSync(32, { safe: true });
-//   assert(checkPrimeSync(prime));
-//   const buf = Buffer.from(prime);
-//   const val = buf.readUInt32BE();
-//   const check = (val - 1) / 2;
-//   buf.writeUInt32BE(check);
-//   assert(checkPrimeSync(buf));
-// }
-
-// const add = 12;
-// const rem = 11;
-// const add_buf = Buffer.from([add]);
-// const rem_buf = Buffer.from([rem]);
-// generatePrime(
-//   32,
-//   { add: add_buf, rem: rem_buf },
-//   common.mustSucceed((prime) => {
-//     assert(checkPrimeSync(prime));
-//     const buf = Buffer.from(prime);
-//     const val = buf.readUInt32BE();
-//     assert.strictEqual(val % add, rem);
-//   }));
-
-// {
-//   const prime = generatePrimeSync(32, { add: add_buf, rem: rem_buf });
-//   assert(checkPrimeSync(prime));
-//   const buf = Buffer.from(prime);
-//   const val = buf.readUInt32BE();
-//   assert.strictEqual(val % add, rem);
-// }
-
-// {
-//   const prime = generatePrimeSync(32, { add: BigInt(add), rem: BigInt(rem) });
-//   assert(checkPrimeSync(prime));
-//   const buf = Buffer.from(prime);
-//   const val = buf.readUInt32BE();
-//   assert.strictEqual(val % add, rem);
-// }
-
-// {
-//   // The behavior when specifying only add without rem should depend on the
-//   // safe option.
-
-//   if (process.versions.openssl >= '1.1.1f') {
-//     generatePrime(128, {
-//       bigint: true,
-//       add: 5n
-//     }, common.mustSucceed((prime) => {
-//       assert(checkPrimeSync(prime));
-//       assert.strictEqual(prime % 5n, 1n);
-//     }));
-
-//     generatePrime(128, {
-//       bigint: true,
-//       safe: true,
-//       add: 5n
-//     }, common.mustSucceed((prime) => {
-//       assert(checkPrimeSync(prime));
-//       assert.strictEqual(prime % 5n, 3n);
-//     }));
-//   }
-// }
-
-// {
-//   // This is impossible because it implies (prime % 2**64) == 1 and
-//   // prime < 2**64, meaning prime = 1, but 1 is not prime.
-//   for (const add of [2n ** 64n, 2n ** 65n]) {
-//     assert.throws(() => {
-//       generatePrimeSync(64, { add });
-//     }, {
-//       code: 'ERR_OUT_OF_RANGE',
-//       message: 'invalid options.add'
-//     });
-//   }
-
-//   // Any parameters with rem >= add lead to an impossible condition.
-//   for (const rem of [7n, 8n, 3000n]) {
-//     assert.throws(() => {
-//       generatePrimeSync(64, { add: 7n, rem });
-//     }, {
-//       code: 'ERR_OUT_OF_RANGE',
-//       message: 'invalid options.rem'
-//     });
-//   }
-
-//   // This is possible, but not allowed. It implies prime == 7, which means that
-//   // we did not actually generate a random prime.
-//   assert.throws(() => {
-//     generatePrimeSync(3, { add: 8n, rem: 7n });
-//   }, {
-//     code: 'ERR_OUT_OF_RANGE'
-//   });
-
-//   if (process.versions.openssl >= '1.1.1f') {
-//     // This is possible and allowed (but makes little sense).
-//     assert.strictEqual(generatePrimeSync(4, {
-//       add: 15n,
-//       rem: 13n,
-//       bigint: true
-//     }), 13n);
-//   }
-// }
+generatePrime(32, { safe: true }, common.mustSucceed((prime) => {
+  assert(checkPrimeSync(prime));
+  const buf = Buffer.from(prime);
+  const val = buf.readUInt32BE();
+  const check = (val - 1) / 2;
+  buf.writeUInt32BE(check);
+  assert(checkPrimeSync(buf));
+}));
+
+{
+  const prime = generatePrimeSync(32, { safe: true });
+  assert(checkPrimeSync(prime));
+  const buf = Buffer.from(prime);
+  const val = buf.readUInt32BE();
+  const check = (val - 1) / 2;
+  buf.writeUInt32BE(check);
+  assert(checkPrimeSync(buf));
+}
+
+const add = 12;
+const rem = 11;
+const add_buf = Buffer.from([add]);
+const rem_buf = Buffer.from([rem]);
+generatePrime(
+  32,
+  { add: add_buf, rem: rem_buf },
+  common.mustSucceed((prime) => {
+    assert(checkPrimeSync(prime));
+    const buf = Buffer.from(prime);
+    const val = buf.readUInt32BE();
+    assert.strictEqual(val % add, rem);
+  }));
+
+{
+  const prime = generatePrimeSync(32, { add: add_buf, rem: rem_buf });
+  assert(checkPrimeSync(prime));
+  const buf = Buffer.from(prime);
+  const val = buf.readUInt32BE();
+  assert.strictEqual(val % add, rem);
+}
+
+{
+  const prime = generatePrimeSync(32, { add: BigInt(add), rem: BigInt(rem) });
+  assert(checkPrimeSync(prime));
+  const buf = Buffer.from(prime);
+  const val = buf.readUInt32BE();
+  assert.strictEqual(val % add, rem);
+}
+
+{
+  // The behavior when specifying only add without rem should depend on the
+  // safe option.
+
+  if (process.versions.openssl >= '1.1.1f') {
+    generatePrime(128, {
+      bigint: true,
+      add: 5n
+    }, common.mustSucceed((prime) => {
+      assert(checkPrimeSync(prime));
+      assert.strictEqual(prime % 5n, 1n);
+    }));
+
+    generatePrime(128, {
+      bigint: true,
+      safe: true,
+      add: 5n
+    }, common.mustSucceed((prime) => {
+      assert(checkPrimeSync(prime));
+      assert.strictEqual(prime % 5n, 3n);
+    }));
+  }
+}
+
+{
+  // This is impossible because it implies (prime % 2**64) == 1 and
+  // prime < 2**64, meaning prime = 1, but 1 is not prime.
+  for (const add of [2n ** 64n, 2n ** 65n]) {
+    assert.throws(() => {
+      generatePrimeSync(64, { add });
+    }, {
+      code: 'ERR_OUT_OF_RANGE',
+      message: 'invalid options.add'
+    });
+  }
+
+  // Any parameters with rem >= add lead to an impossible condition.
+  for (const rem of [7n, 8n, 3000n]) {
+    assert.throws(() => {
+      generatePrimeSync(64, { add: 7n, rem });
+    }, {
+      code: 'ERR_OUT_OF_RANGE',
+      message: 'invalid options.rem'
+    });
+  }
+
+  // This is possible, but not allowed. It implies prime == 7, which means that
+  // we did not actually generate a random prime.
+  assert.throws(() => {
+    generatePrimeSync(3, { add: 8n, rem: 7n });
+  }, {
+    code: 'ERR_OUT_OF_RANGE'
+  });
+
+  if (process.versions.openssl >= '1.1.1f') {
+    // This is possible and allowed (but makes little sense).
+    assert.strictEqual(generatePrimeSync(4, {
+      add: 15n,
+      rem: 13n,
+      bigint: true
+    }), 13n);
+  }
+}
 
 [1, 'hello', {}, []].forEach((i) => {
   assert.throws(() => checkPrime(i), {
@@ -359,8 +359,8 @@ dns.lookup('', {
     {
       method: "resolveAny",
       answers: [
-        { type: "A", address: "1.2.3.4" /*ttl: 3333333333*/ },
-        { type: "AAAA", address: "::42" /*ttl: 3333333333*/ },
+        { type: "A", address: "1.2.3.4", ttl: 3333333333 },
+        { type: "AAAA", address: "::42", ttl: 3333333333 },
         { type: "MX", priority: 42, exchange: "foobar.com", ttl: 3333333333 },
         { type: "NS", value: "foobar.org", ttl: 3333333333 },
         { type: "PTR", value: "baz.org", ttl: 3333333333 },
@@ -0,0 +1,2 @@
+import { value } from "@deno-test/foo";
+export const doubled = value * 2;
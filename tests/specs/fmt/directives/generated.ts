@@ -0,0 +1,10 @@
+// deno-fmt-options lineWidth=120
+export const message = "this line is longer than the default line width and fits within the one of this file";
+
+// deno-fmt-ignore-start
+export const identity = [
+  1, 0, 0,
+  0, 1, 0,
+  0, 0, 1,
+];
+// deno-fmt-ignore-end
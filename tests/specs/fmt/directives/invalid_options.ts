@@ -0,0 +1,2 @@
+// deno-fmt-options lineWidth=wide
+export const a = 1;
@@ -0,0 +1,2 @@
+import "../infra/db.ts";
+import "./b.ts";
@@ -0,0 +1 @@
+export const db = 1;
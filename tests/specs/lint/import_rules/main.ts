@@ -0,0 +1,2 @@
+import "http://localhost:4545/echo.ts";
+import "./domain/a.ts";
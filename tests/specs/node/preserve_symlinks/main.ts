@@ -0,0 +1,3 @@
+import { url } from "package";
+
+console.log(url.includes("/node_modules/"));
@@ -0,0 +1,7 @@
+console.log("hello");
+console.error("multiple\nlines");
+
+const worker = new Worker(import.meta.resolve("./worker.ts"), {
+  type: "module",
+});
+worker.onmessage = () => worker.terminate();
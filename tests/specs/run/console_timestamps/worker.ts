@@ -0,0 +1,2 @@
+console.log("from worker");
+self.postMessage("done");
@@ -0,0 +1,14 @@
+/**
+ * ```ts test
+ * const sum: number = add(1, 2);
+ * if (sum !== 3) throw new Error("unexpected sum");
+ * ```
+ *
+ * ```ts
+ * import { add as plus } from "./main.ts";
+ * plus(1, 2);
+ * ```
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
@@ -0,0 +1,3 @@
+console.log(Deno.permissions.querySync({ name: "read" }).state);
+Deno.writeTextFileSync(Deno.args[0], "Hello");
+console.log("written");
@@ -0,0 +1,7 @@
+import bytes from "./data.txt" with { type: "bytes" };
+import text from "./data.txt" with { type: "text" };
+
+const decoded: string = new TextDecoder().decode(bytes);
+console.log(bytes instanceof Uint8Array);
+console.log(decoded === text);
+console.log(text);
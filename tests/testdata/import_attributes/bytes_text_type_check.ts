@@ -0,0 +1,4 @@
+import text from "./data.txt" with { type: "text" };
+
+const length: number = text.byteLength;
+console.log(length);
@@ -0,0 +1,16 @@
+console.log(Deno.exitCode);
+Deno.exitCode = 42;
+console.log(Deno.exitCode);
+
+for (const value of ["1", 1.5]) {
+  try {
+    Deno.exitCode = value;
+  } catch (e) {
+    console.log(`${e.name}: ${e.message}`);
+  }
+}
+
+globalThis.addEventListener("unload", () => {
+  console.log(Deno.exitCode);
+});
+// Exits naturally.
@@ -0,0 +1,20 @@
+// @ts-ignore internal API
+const { opMetrics } = Deno[Deno.internal];
+
+try {
+  opMetrics();
+} catch (err) {
+  console.log(err.message);
+  Deno.exit(0);
+}
+
+Deno.cwd();
+Deno.cwd();
+await Deno.stat(".");
+await Deno.stat("./does_not_exist").catch(() => {});
+const metrics = opMetrics();
+const cwd = metrics["op_fs_cwd"];
+console.log(cwd.count, cwd.errors);
+console.log(cwd.histogram.reduce((a: number, b: number) => a + b));
+const stat = metrics["op_fs_stat_async"];
+console.log(stat.count, stat.errors);
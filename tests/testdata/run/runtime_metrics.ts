@@ -0,0 +1,18 @@
+const path = new URL("../subdir/json_1.json", import.meta.url);
+using file = await Deno.open(path);
+const buf = new Uint8Array(1024);
+const nread = await file.read(buf);
+
+await import("../subdir/mod4.js");
+
+// block the event loop
+const start = Date.now();
+while (Date.now() - start < 100);
+await new Promise((resolve) => setTimeout(resolve, 50));
+
+const metrics = Deno.runtimeMetrics();
+console.log(metrics.ops["op_read"].count);
+console.log(metrics.resources["fsFile"].bytesRead === nread);
+console.log(metrics.modules.loaded, metrics.modules.dynamic);
+console.log(metrics.eventLoop.samples > 0);
+console.log(metrics.eventLoop.maxUs >= 50_000);
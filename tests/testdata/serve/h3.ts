@@ -0,0 +1,17 @@
+Deno.serve({
+  hostname: "127.0.0.1",
+  port: 0,
+  cert: Deno.readTextFileSync("./tls/localhost.crt"),
+  key: Deno.readTextFileSync("./tls/localhost.key"),
+  h3: true,
+  onListen({ port }) {
+    console.log(`Listening on port ${port}`);
+  },
+  handler(req) {
+    if (req.method === "POST") {
+      // Echo the request body as it is received
+      return new Response(req.body);
+    }
+    return new Response(`${req.method} ${new URL(req.url).pathname}`);
+  },
+});
@@ -0,0 +1,28 @@
+function spawn() {
+  new Deno.Command(Deno.execPath(), {
+    args: ["eval", "setTimeout(() => {}, 1000)"],
+    stdin: "null",
+    stdout: "null",
+    stderr: "null",
+  }).spawn();
+}
+
+Deno.test({
+  name: "leak",
+  sanitizeOps: false,
+  sanitizeResources: false,
+  sanitizeChildProcesses: true,
+  fn: spawn,
+});
+
+Deno.test({
+  name: "opted out",
+  sanitizeChildProcesses: false,
+  fn: spawn,
+});
+
+Deno.test({
+  name: "follows sanitizeOps",
+  sanitizeOps: false,
+  fn: spawn,
+});
@@ -0,0 +1,20 @@
+Deno.test({
+  name: "leak",
+  sanitizeEnv: true,
+  fn() {
+    Deno.env.set("DENO_TEST_ENV_SANITIZER_LEAK", "1");
+  },
+});
+
+Deno.test({
+  name: "restored",
+  sanitizeEnv: true,
+  fn() {
+    Deno.env.set("DENO_TEST_ENV_SANITIZER_RESTORED", "1");
+    Deno.env.delete("DENO_TEST_ENV_SANITIZER_RESTORED");
+  },
+});
+
+Deno.test("disabled", function () {
+  Deno.env.set("DENO_TEST_ENV_SANITIZER_DISABLED", "1");
+});
@@ -0,0 +1,6 @@
+Deno.test("leak", async (t) => {
+  await t.step("open", () => {
+    Deno.openSync("run/001_hello.js");
+  });
+  await t.step("close", () => {});
+});
@@ -0,0 +1,17 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Echoes every message of the input channel with its bytes doubled, then
+// sends the number of messages and closes the output channel.
+addEventListener("message", (e) => {
+  const { data } = e as MessageEvent;
+  const input = new Deno.SyncChannel(data.input);
+  const output = new Deno.SyncChannel(data.output);
+  let count = 0;
+  let message;
+  while ((message = input.recv()) !== null) {
+    count++;
+    output.send(message.map((byte) => byte * 2));
+  }
+  output.send(new Uint8Array([count]));
+  output.close();
+});
@@ -977,3 +977,185 @@ Deno.test(
     );
   },
 );
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function spawnPipeline() {
+    const { children, status } = Deno.spawnPipeline([
+      new Deno.Command(Deno.execPath(), {
+        args: ["eval", "console.log('hello world')"],
+      }),
+      new Deno.Command(Deno.execPath(), {
+        args: [
+          "eval",
+          "await Deno.stdin.readable.pipeThrough(new TextDecoderStream()).pipeThrough(new TransformStream({ transform(chunk, controller) { controller.enqueue(chunk.toUpperCase()); } })).pipeThrough(new TextEncoderStream()).pipeTo(Deno.stdout.writable);",
+        ],
+      }),
+      new Deno.Command(Deno.execPath(), {
+        args: [
+          "eval",
+          "await Deno.stdin.readable.pipeTo(Deno.stdout.writable);",
+        ],
+        stdout: "piped",
+      }),
+    ]);
+    assertEquals(children.length, 3);
+    assertThrows(() => children[0].stdout, TypeError, "stdout is not piped");
+
+    const output = await children[2].output();
+    assertEquals(new TextDecoder().decode(output.stdout), "HELLO WORLD\n");
+    const { success, statuses } = await status;
+    assert(success);
+    assertEquals(statuses.map((status) => status.code), [0, 0, 0]);
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function spawnPipelineStatus() {
+    const { status } = Deno.spawnPipeline([
+      new Deno.Command(Deno.execPath(), {
+        args: ["eval", "Deno.exit(3)"],
+      }),
+      new Deno.Command(Deno.execPath(), {
+        args: [
+          "eval",
+          "await Deno.stdin.readable.pipeTo(Deno.stdout.writable);",
+        ],
+        stdout: "null",
+      }),
+    ]);
+    const { success, statuses } = await status;
+    assertEquals(success, false);
+    assertEquals(statuses.map((status) => status.code), [3, 0]);
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function spawnPipelineInvalid() {
+    assertThrows(
+      () => Deno.spawnPipeline([]),
+      TypeError,
+      "A pipeline must have at least one command.",
+    );
+    assertThrows(
+      // @ts-expect-error testing invalid input
+      () => Deno.spawnPipeline([Deno.execPath()]),
+      TypeError,
+      "Pipeline command at index 0 is not a Deno.Command.",
+    );
+    assertThrows(
+      () =>
+        Deno.spawnPipeline([
+          new Deno.Command(Deno.execPath(), { args: ["eval", ""] }),
+          new Deno.Command("this_command_does_not_exist"),
+        ]),
+      Deno.errors.NotFound,
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandOutputMaxBytes() {
+    const command = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "await Deno.stdout.write(new Uint8Array(1024).fill(97))",
+      ],
+    });
+
+    const output = await command.output({ maxBytes: 1024 });
+    assertEquals(output.stdout.length, 1024);
+    assertEquals(output.truncated, false);
+
+    await assertRejects(
+      () => command.output({ maxBytes: 100 }),
+      RangeError,
+      "Child process stdout exceeded the limit of 100 bytes",
+    );
+
+    const truncated = await command.output({ maxBytes: 100, truncate: true });
+    assert(truncated.success);
+    assertEquals(truncated.stdout, new Uint8Array(100).fill(97));
+    assertEquals(truncated.truncated, true);
+
+    await assertRejects(
+      () => command.output({ maxBytes: -1 }),
+      RangeError,
+      "maxBytes must be a non-negative integer",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandOutputMaxBytesKillsChild() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "while (true) await Deno.stdout.write(new Uint8Array(4096))",
+      ],
+      stdout: "piped",
+      stderr: "null",
+    }).spawn();
+
+    await assertRejects(
+      () => child.output({ maxBytes: 10000 }),
+      RangeError,
+      "Child process stdout exceeded the limit of 10000 bytes",
+    );
+    const status = await child.status;
+    assertEquals(status.success, false);
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function childProcessLines() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "console.log('one\\ntwo\\r\\n'); console.error('err'); " +
+        "await Deno.stdout.write(new TextEncoder().encode('last'))",
+      ],
+      stdout: "piped",
+      stderr: "piped",
+    }).spawn();
+
+    const stderr = Array.fromAsync(child.lines({ stream: "stderr" }));
+    assertThrows(
+      () => child.lines({ stream: "stderr" }),
+      TypeError,
+      "Can't read lines because stderr is locked",
+    );
+    const lines = await Array.fromAsync(child.lines());
+    assertEquals(lines, ["one", "two", "", "last"]);
+    assertEquals(await stderr, ["err"]);
+    assert(child.stdout.locked);
+    await child.status;
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function childProcessLinesEncoding() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "await Deno.stdout.write(new Uint8Array([0x68, 0, 0x69, 0, 0x0a, 0]))",
+      ],
+      stdout: "piped",
+    }).spawn();
+
+    assertThrows(
+      () => child.lines({ encoding: "not-an-encoding" }),
+      RangeError,
+      "The encoding label provided ('not-an-encoding') is invalid.",
+    );
+    const lines = await Array.fromAsync(child.lines({ encoding: "utf-16le" }));
+    assertEquals(lines, ["hi"]);
+    await child.status;
+  },
+);
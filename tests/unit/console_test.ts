@@ -11,6 +11,7 @@
 import {
   assert,
   assertEquals,
+  assertMatch,
   assertStringIncludes,
   assertThrows,
 } from "./test_util.ts";
@@ -1239,6 +1240,11 @@ Deno.test(function consoleParseCss() {
     parseCss("font-weight: bold"),
     { ...DEFAULT_CSS, fontWeight: "bold" },
   );
+  assertEquals(
+    parseCss("font-weight: 700"),
+    { ...DEFAULT_CSS, fontWeight: "bold" },
+  );
+  assertEquals(parseCss("font-weight: 400"), DEFAULT_CSS);
   assertEquals(
     parseCss("font-style: italic"),
     { ...DEFAULT_CSS, fontStyle: "italic" },
@@ -1527,6 +1533,26 @@ Deno.test(function consoleGroupWarn() {
   });
 });
 
+// console with timestamps test
+Deno.test(function consoleTimestamps() {
+  let out = "";
+  const csl = new Console(
+    (x: string) => {
+      out += x;
+    },
+    { timestamps: true },
+  );
+  csl.group("a");
+  csl.log("b\nc");
+  csl.groupEnd();
+
+  const timestamp = String.raw`\[\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}Z\] `;
+  assertMatch(
+    out,
+    new RegExp(`^${timestamp}a\n${timestamp}    b\n${timestamp}    c\n$`),
+  );
+});
+
 // console.table test
 Deno.test(function consoleTable() {
   mockConsole((console, out) => {
@@ -1804,6 +1830,54 @@ Deno.test(function consoleTable() {
 `,
     );
   });
+  mockConsole((console, out) => {
+    console.table([{ toString: 1 }, Object.create({ a: 2 })]);
+    assertEquals(
+      stripColor(out.toString()),
+      `\
+┌───────┬──────────┐
+│ (idx) │ toString │
+├───────┼──────────┤
+│     0 │        1 │
+│     1 │          │
+└───────┴──────────┘
+`,
+    );
+  });
+  mockConsole((console, out) => {
+    console.table([Object.create({ a: 2 }), { a: 3 }], ["a"]);
+    assertEquals(
+      stripColor(out.toString()),
+      `\
+┌───────┬───┐
+│ (idx) │ a │
+├───────┼───┤
+│     0 │   │
+│     1 │ 3 │
+└───────┴───┘
+`,
+    );
+  });
+  mockConsole((console, out) => {
+    console.table(new Map([[1, "one"]]), ["Key"]);
+    assertEquals(
+      stripColor(out.toString()),
+      `\
+┌────────────┬─────┬────────┐
+│ (iter idx) │ Key │ Values │
+├────────────┼─────┼────────┤
+│          0 │   1 │ "one"  │
+└────────────┴─────┴────────┘
+`,
+    );
+  });
+  mockConsole((console) => {
+    assertThrows(
+      () => console.table([], "a" as unknown as string[]),
+      TypeError,
+      "The 'properties' argument must be of type Array",
+    );
+  });
 });
 
 // console.log(Error) test
@@ -2082,6 +2156,45 @@ Deno.test(function inspectIterableLimit() {
   );
 });
 
+Deno.test(function inspectPropertyLimit() {
+  assertEquals(
+    stripColor(Deno.inspect({ a: 1, b: 2, c: 3 }, { propertyLimit: 2 })),
+    `{ a: 1, b: 2, ... 1 more property }`,
+  );
+  assertEquals(
+    stripColor(Deno.inspect({ a: 1, b: 2, c: 3 }, { propertyLimit: 0 })),
+    `{ ... 3 more properties }`,
+  );
+});
+
+Deno.test(function inspectOutputLimit() {
+  const value = { a: { x: 1 }, b: { x: 2 }, c: { x: 3 } };
+  assertEquals(
+    stripColor(Deno.inspect(value, { outputLimit: 10 })),
+    `{ a: { x: 1 }, b: { x: 2 }, c: [Object] }`,
+  );
+  assertEquals(
+    stripColor(Deno.inspect(value)),
+    `{ a: { x: 1 }, b: { x: 2 }, c: { x: 3 } }`,
+  );
+});
+
+Deno.test(function inspectCustomInspectReceivesOptions() {
+  class Wrapper {
+    constructor(public inner: unknown) {}
+    [Symbol.for("Deno.customInspect")](
+      inspect: typeof Deno.inspect,
+      options: Deno.InspectOptions,
+    ) {
+      return `Wrapper(${inspect(this.inner, options)})`;
+    }
+  }
+  assertEquals(
+    stripColor(Deno.inspect(new Wrapper({ a: 1, b: 2 }), { propertyLimit: 1 })),
+    `Wrapper({ a: 1, ... 1 more property })`,
+  );
+});
+
 Deno.test(function inspectProxy() {
   assertEquals(
     stripColor(Deno.inspect(
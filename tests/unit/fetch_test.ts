@@ -250,6 +250,38 @@ Deno.test({ permissions: { net: true } }, async function responseClone() {
   }
 });
 
+Deno.test(
+  { permissions: { net: true } },
+  async function responseCloneBigBody() {
+    const data = "a".repeat(1 << 20); // 1mb
+    const response = await fetch("http://localhost:4545/echo_server", {
+      method: "POST",
+      body: data,
+    });
+    const response1 = response.clone();
+    // the second branch buffers what the first one reads
+    assertEquals(await response.text(), data);
+    assertEquals(await response1.text(), data);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function responseCloneAbort() {
+    const abortController = new AbortController();
+    const abortReason = new Error();
+    const response = await fetch("http://localhost:4545/echo_server", {
+      method: "POST",
+      body: "a".repeat(1 << 20),
+      signal: abortController.signal,
+    });
+    const response1 = response.clone();
+    abortController.abort(abortReason);
+    await assertRejects(() => response.text(), Error);
+    await assertRejects(() => response1.text(), Error);
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function fetchMultipartFormDataSuccess() {
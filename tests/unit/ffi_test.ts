@@ -28,6 +28,10 @@ Deno.test({ permissions: { ffi: false } }, function ffiPermissionDenied() {
   assertThrows(() => {
     Deno.dlopen("/usr/lib/libc.so.6", {});
   }, Deno.errors.PermissionDenied);
+  // Checked before looking the library up, whether it's registered or not
+  assertThrows(() => {
+    Deno.dlopenStatic("math", {});
+  }, Deno.errors.PermissionDenied);
   const fnptr = new Deno.UnsafeFnPointer(
     // @ts-expect-error: Not NonNullable but null check is after permissions check.
     null,
@@ -1,6 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { assert } from "./test_util.ts";
+import { assert, assertEquals, assertThrows } from "./test_util.ts";
 
 Deno.test(
   {
@@ -28,3 +28,42 @@ Deno.test(
     }
   },
 );
+
+Deno.test(
+  {
+    name: "Deno.watchNetworkInterfaces",
+    permissions: { sys: ["networkInterfaces"] },
+  },
+  async () => {
+    const watcher = Deno.watchNetworkInterfaces();
+    const next = watcher.next();
+    watcher.close();
+    assertEquals(await next, { value: undefined, done: true });
+  },
+);
+
+Deno.test(
+  {
+    name: "Deno.watchNetworkInterfaces unref",
+    permissions: { sys: ["networkInterfaces"] },
+  },
+  async () => {
+    using watcher = Deno.watchNetworkInterfaces();
+    // applies to the polls made after it
+    watcher.unref();
+    const next = watcher.next();
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    watcher.close();
+    assertEquals(await next, { value: undefined, done: true });
+  },
+);
+
+Deno.test(
+  { permissions: { sys: false } },
+  function watchNetworkInterfacesPerm() {
+    assertThrows(
+      () => Deno.watchNetworkInterfaces(),
+      Deno.errors.PermissionDenied,
+    );
+  },
+);
@@ -111,3 +111,61 @@ Deno.test({ permissions: { read: true } }, async function readDirNotFound() {
     `readdir 'bad_dir_name'`,
   );
 });
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function readDirManyEntries() {
+    // more entries than are read per batch
+    const dir = Deno.makeTempDirSync();
+    for (let i = 0; i < 300; i++) {
+      Deno.writeTextFileSync(`${dir}/${i}.txt`, "");
+    }
+    const names = new Set<string>();
+    for await (const entry of Deno.readDir(dir)) {
+      assert(entry.isFile);
+      names.add(entry.name);
+    }
+    assertEquals(names.size, 300);
+    Deno.removeSync(dir, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function readDirBreak() {
+    const dir = Deno.makeTempDirSync();
+    for (let i = 0; i < 300; i++) {
+      Deno.writeTextFileSync(`${dir}/${i}.txt`, "");
+    }
+    let count = 0;
+    for await (const _ of Deno.readDir(dir)) {
+      if (++count === 10) {
+        // closes the directory, which the resource sanitizer checks
+        break;
+      }
+    }
+    assertEquals(count, 10);
+    Deno.removeSync(dir, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function readDirStat() {
+    const dir = Deno.makeTempDirSync();
+    Deno.writeTextFileSync(`${dir}/file.txt`, "hello");
+    Deno.mkdirSync(`${dir}/subdir`);
+    const entries: Record<string, Deno.DirEntryWithStat> = {};
+    for await (const entry of Deno.readDir(dir, { stat: true })) {
+      entries[entry.name] = entry;
+    }
+    assertEquals(Object.keys(entries).sort(), ["file.txt", "subdir"]);
+    const file = entries["file.txt"];
+    assert(file.isFile);
+    assert(file.stat.isFile);
+    assertEquals(file.stat.size, 5);
+    assert(file.stat.mtime instanceof Date);
+    assert(entries["subdir"].stat.isDirectory);
+    Deno.removeSync(dir, { recursive: true });
+  },
+);
@@ -136,6 +136,22 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true } },
+  async function readFileWithAbortSignalAny() {
+    const ac = new AbortController();
+    const abortReason = new Error();
+    const signal = AbortSignal.any([ac.signal]);
+    queueMicrotask(() => ac.abort(abortReason));
+    const error = await assertRejects(
+      async () => {
+        await Deno.readFile("tests/testdata/assets/fixture.json", { signal });
+      },
+    );
+    assertEquals(error, abortReason);
+  },
+);
+
 // Test that AbortController's cancel handle is cleaned-up correctly, and do not leak resources.
 Deno.test(
   { permissions: { read: true } },
@@ -0,0 +1,93 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "./test_util.ts";
+
+Deno.test(async function resourceScopeDisposesInReverseOrder() {
+  const order: string[] = [];
+  {
+    await using scope = new Deno.ResourceScope();
+    scope.defer(() => {
+      order.push("first");
+    });
+    scope.use({ [Symbol.dispose]: () => order.push("second") });
+    scope.use({
+      [Symbol.asyncDispose]: async () => {
+        await Promise.resolve();
+        order.push("third");
+      },
+    });
+    assertEquals(scope.use(null), null);
+    assert(!scope.disposed);
+  }
+  assertEquals(order, ["third", "second", "first"]);
+});
+
+Deno.test(
+  { permissions: { read: true } },
+  async function resourceScopeClosesResources() {
+    const scope = new Deno.ResourceScope();
+    const file = scope.use(await Deno.open("tests/testdata/assets/hello.txt"));
+    const rid = scope.use(
+      // deno-lint-ignore no-deprecated-deno-api
+      Deno.openSync("tests/testdata/assets/hello.txt").rid,
+    );
+    const { signal } = scope;
+
+    await scope[Symbol.asyncDispose]();
+    assert(scope.disposed);
+    assert(signal.aborted);
+    await assertRejects(
+      () => file.read(new Uint8Array(1)),
+      Deno.errors.BadResource,
+    );
+    assertThrows(() => Deno.close(rid), Deno.errors.BadResource);
+
+    // disposing again is a no-op
+    await scope[Symbol.asyncDispose]();
+  },
+);
+
+Deno.test(function resourceScopeInvalidValues() {
+  const scope = new Deno.ResourceScope();
+  assertThrows(() => scope.use(123456), Deno.errors.BadResource);
+  // @ts-expect-error testing invalid input
+  assertThrows(() => scope.use({}), TypeError);
+  // @ts-expect-error testing invalid input
+  assertThrows(() => scope.defer("callback"), TypeError);
+});
+
+Deno.test(async function resourceScopeUseAfterDispose() {
+  const scope = new Deno.ResourceScope();
+  await scope[Symbol.asyncDispose]();
+  assertThrows(
+    () => scope.defer(() => {}),
+    ReferenceError,
+    "The resource scope was disposed",
+  );
+});
+
+Deno.test(async function resourceScopeCollectsErrors() {
+  let disposed = false;
+  const scope = new Deno.ResourceScope();
+  scope.defer(() => {
+    disposed = true;
+  });
+  scope.defer(() => {
+    throw new Error("one");
+  });
+  scope.defer(() => {
+    throw new Error("two");
+  });
+
+  const error = await assertRejects(
+    () => scope[Symbol.asyncDispose](),
+    AggregateError,
+    "Failed to dispose some resources of the scope",
+  );
+  assertEquals(error.errors.map((e: Error) => e.message), ["two", "one"]);
+  assert(disposed);
+});
@@ -0,0 +1,81 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+import { assertEquals, assertRejects, assertThrows } from "./test_util.ts";
+
+Deno.test(function schedulerIllegalConstructor() {
+  assertThrows(() => new Scheduler(), TypeError, "Illegal constructor");
+});
+
+Deno.test(async function schedulerPostTaskResolvesWithResult() {
+  assertEquals(await scheduler.postTask(() => 42), 42);
+  assertEquals(await scheduler.postTask(() => Promise.resolve("a")), "a");
+  await assertRejects(
+    () =>
+      scheduler.postTask(() => {
+        throw new Error("boom");
+      }),
+    Error,
+    "boom",
+  );
+});
+
+Deno.test(async function schedulerPostTaskPriorityOrder() {
+  const order: string[] = [];
+  await Promise.all([
+    scheduler.postTask(() => order.push("background"), {
+      priority: "background",
+    }),
+    scheduler.postTask(() => order.push("user-visible")),
+    scheduler.postTask(() => order.push("user-blocking"), {
+      priority: "user-blocking",
+    }),
+  ]);
+  assertEquals(order, ["user-blocking", "user-visible", "background"]);
+});
+
+Deno.test(async function schedulerPostTaskDelay() {
+  const order: string[] = [];
+  await Promise.all([
+    scheduler.postTask(() => order.push("delayed"), {
+      priority: "user-blocking",
+      delay: 20,
+    }),
+    scheduler.postTask(() => order.push("background"), {
+      priority: "background",
+    }),
+  ]);
+  assertEquals(order, ["background", "delayed"]);
+});
+
+Deno.test(async function schedulerPostTaskAbort() {
+  const controller = new AbortController();
+  let ran = false;
+  const task = scheduler.postTask(() => {
+    ran = true;
+  }, { signal: controller.signal });
+  controller.abort(new Error("aborted"));
+  await assertRejects(() => task, Error, "aborted");
+  assertEquals(ran, false);
+
+  await assertRejects(
+    () => scheduler.postTask(() => {}, { signal: controller.signal }),
+    Error,
+    "aborted",
+  );
+});
+
+Deno.test(function schedulerPostTaskInvalidPriority() {
+  assertThrows(
+    // @ts-expect-error testing invalid input
+    () => scheduler.postTask(() => {}, { priority: "urgent" }),
+    TypeError,
+  );
+});
+
+Deno.test(async function schedulerYield() {
+  const order: string[] = [];
+  const task = scheduler.postTask(() => order.push("task"));
+  await scheduler.yield();
+  order.push("yield");
+  await task;
+  assertEquals(order, ["yield", "task"]);
+});
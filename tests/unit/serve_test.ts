@@ -2984,6 +2984,58 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function httpServerWithH3AdvertisesAltSvc() {
+    const ac = new AbortController();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    const hostname = "127.0.0.1";
+
+    const server = Deno.serve({
+      handler: (req) =>
+        new Response("Hello World", {
+          headers: req.url.endsWith("/own")
+            ? { "alt-svc": "clear" }
+            : undefined,
+        }),
+      hostname,
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(resolve),
+      onError: createOnErrorCb(ac),
+      cert: Deno.readTextFileSync("tests/testdata/tls/localhost.crt"),
+      key: Deno.readTextFileSync("tests/testdata/tls/localhost.key"),
+      h3: true,
+    });
+
+    await promise;
+    const caCert = Deno.readTextFileSync("tests/testdata/tls/RootCA.pem");
+    const client = Deno.createHttpClient({ caCerts: [caCert] });
+    let resp = await fetch(`https://localhost:${servePort}/`, { client });
+    assertEquals(resp.headers.get("alt-svc"), `h3=":${servePort}"; ma=86400`);
+    assertEquals(await resp.text(), "Hello World");
+
+    resp = await fetch(`https://localhost:${servePort}/own`, { client });
+    assertEquals(resp.headers.get("alt-svc"), "clear");
+    assertEquals(await resp.text(), "Hello World");
+
+    client.close();
+    ac.abort();
+    await server.finished;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  function httpServerH3RequiresCertAndKey() {
+    assertThrows(
+      () => Deno.serve({ port: servePort, h3: true }, () => new Response()),
+      TypeError,
+      "Both cert and key must be provided to enable HTTP/3.",
+    );
+  },
+);
+
 Deno.test(
   { permissions: { net: true, write: true, read: true } },
   async function httpServerRequestCLTE() {
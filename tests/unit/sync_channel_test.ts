@@ -0,0 +1,95 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+import { assert, assertEquals, assertThrows } from "./test_util.ts";
+
+Deno.test(function syncChannelSendRecv() {
+  const channel = new Deno.SyncChannel(2, 4);
+  assertEquals(channel.capacity, 2);
+  assertEquals(channel.messageSize, 4);
+  assert(channel.buffer instanceof SharedArrayBuffer);
+
+  assertEquals(channel.tryRecv(), null);
+  assert(channel.trySend(new Uint8Array([1, 2, 3, 4])));
+  assert(channel.send(new Uint8Array([])));
+  assert(!channel.trySend(new Uint8Array([5])));
+  assert(!channel.send(new Uint8Array([5]), 10));
+
+  assertEquals(channel.recv(), new Uint8Array([1, 2, 3, 4]));
+  assert(channel.trySend(new Uint8Array([5])));
+  assertEquals(channel.recv(), new Uint8Array([]));
+  assertEquals(channel.recv(), new Uint8Array([5]));
+  assertEquals(channel.recv(10), null);
+
+  assertThrows(
+    () => channel.send(new Uint8Array(5)),
+    RangeError,
+    "exceeds the message size",
+  );
+});
+
+Deno.test(function syncChannelClose() {
+  const channel = new Deno.SyncChannel(4, 1);
+  channel.send(new Uint8Array([1]));
+  channel.close();
+  assert(channel.closed);
+  assertThrows(
+    () => channel.send(new Uint8Array([2])),
+    Deno.errors.BrokenPipe,
+  );
+  assertEquals(channel.recv(), new Uint8Array([1]));
+  assertEquals(channel.recv(), null);
+});
+
+Deno.test(function syncChannelFromBuffer() {
+  const channel = new Deno.SyncChannel(3, 8);
+  const other = new Deno.SyncChannel(channel.buffer);
+  assertEquals(other.capacity, 3);
+  assertEquals(other.messageSize, 8);
+  channel.send(new Uint8Array([42]));
+  assertEquals(other.recv(), new Uint8Array([42]));
+
+  assertThrows(
+    () => new Deno.SyncChannel(new SharedArrayBuffer(8)),
+    TypeError,
+    "Buffer is not the buffer of a SyncChannel",
+  );
+  assertThrows(() => new Deno.SyncChannel(0, 8), RangeError);
+  assertThrows(() => new Deno.SyncChannel(1.5, 8), RangeError);
+});
+
+Deno.test(async function syncChannelWorker() {
+  const input = new Deno.SyncChannel(2, 1);
+  const output = new Deno.SyncChannel(2, 1);
+  const worker = new Worker(
+    import.meta.resolve("../testdata/workers/sync_channel_worker.ts"),
+    { type: "module" },
+  );
+  worker.postMessage({ input: input.buffer, output: output.buffer });
+
+  // blocks whenever the worker is behind
+  for (let i = 0; i < 50; i++) {
+    input.send(new Uint8Array([i]));
+    assertEquals(output.recv(), new Uint8Array([i * 2]));
+  }
+  input.close();
+  assertEquals(output.recv(), new Uint8Array([50]));
+  assertEquals(output.recv(), null);
+  worker.terminate();
+  // let the worker shut down
+  await new Promise((resolve) => setTimeout(resolve, 0));
+});
+
+Deno.test(async function syncChannelTerminateBlockedWorker() {
+  const input = new Deno.SyncChannel(1, 1);
+  const output = new Deno.SyncChannel(1, 1);
+  const worker = new Worker(
+    import.meta.resolve("../testdata/workers/sync_channel_worker.ts"),
+    { type: "module" },
+  );
+  worker.postMessage({ input: input.buffer, output: output.buffer });
+  // the worker blocks on the full output channel
+  input.send(new Uint8Array([1]));
+  input.send(new Uint8Array([2]));
+  assert(output.recv(1000) !== null);
+  worker.terminate();
+  await new Promise((resolve) => setTimeout(resolve, 100));
+});
@@ -1,7 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import { assertEquals, assertRejects } from "./test_util.ts";
 // @ts-expect-error TypeScript (as of 3.7) does not support indexing namespaces by symbol
-const { resolverSymbol, serverNameSymbol } = Deno[Deno.internal];
+const { certResolverEvictSymbol, resolverSymbol, serverNameSymbol } =
+  Deno[Deno.internal];
 
 const cert = Deno.readTextFileSync("tests/testdata/tls/localhost.crt");
 const key = Deno.readTextFileSync("tests/testdata/tls/localhost.key");
@@ -58,3 +59,115 @@ Deno.test(
     listener.close();
   },
 );
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function listenCertResolver() {
+    let attempts = 0;
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port: 0,
+      certResolver: (serverName) => {
+        if (serverName === "retry") {
+          return ++attempts === 1 ? Promise.reject(new Error("boom")) : {
+            cert: certEcc,
+            key: keyEcc,
+          };
+        }
+        return serverName === "server" ? { cert, key } : undefined;
+      },
+      cert,
+      key,
+    });
+
+    for (
+      const [server, ok] of [
+        ["server", true],
+        ["other", true],
+        ["retry", false],
+        ["retry", true],
+        ["retry", true],
+      ] as const
+    ) {
+      const conn = await Deno.connectTls({
+        hostname: "localhost",
+        [serverNameSymbol]: server,
+        port: listener.addr.port,
+      });
+      const serverConn = await listener.accept();
+      if (!ok) {
+        await assertRejects(async () => await conn.handshake());
+        await assertRejects(async () => await serverConn.handshake());
+      } else {
+        await conn.handshake();
+        await serverConn.handshake();
+      }
+      conn.close();
+      serverConn.close();
+    }
+
+    // the failed lookup is retried, the successful one is cached
+    assertEquals(attempts, 2);
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function listenCertResolverMap() {
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port: 0,
+      certResolver: {
+        "example.com": { cert, key },
+        "*.example.com": { cert: certEcc, key: keyEcc },
+      },
+    });
+
+    for (
+      const server of ["example.com", "www.example.com", "a.b.example.com"]
+    ) {
+      const conn = await Deno.connectTls({
+        hostname: "localhost",
+        [serverNameSymbol]: server,
+        port: listener.addr.port,
+      });
+      const serverConn = await listener.accept();
+      if (server === "a.b.example.com") {
+        await assertRejects(async () => await conn.handshake());
+        await assertRejects(async () => await serverConn.handshake());
+      } else {
+        await conn.handshake();
+        await serverConn.handshake();
+      }
+      conn.close();
+      serverConn.close();
+    }
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function listenCertResolverEvictClosed() {
+    const { promise: registered, resolve: register } = Promise
+      .withResolvers<unknown>();
+    const { promise: closed, resolve: close } = Promise.withResolvers<void>();
+    const certResolver = Object.assign(() => ({ cert, key }), {
+      [certResolverEvictSymbol]: (evict: unknown) => {
+        register(evict);
+        return close;
+      },
+    });
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port: 0,
+      certResolver,
+    });
+    assertEquals(typeof await registered, "function");
+
+    // the callback returned by the listener's registration is called on close
+    listener.close();
+    await closed;
+  },
+);
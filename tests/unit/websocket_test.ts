@@ -406,6 +406,82 @@ Deno.test(
   },
 );
 
+Deno.test(
+  async function websocketServerMaxBufferedAmount() {
+    const ac = new AbortController();
+    const listeningDeferred = Promise.withResolvers<void>();
+    const drainDeferred = Promise.withResolvers<void>();
+
+    const server = Deno.serve({
+      handler: (req) => {
+        const { socket, response } = Deno.upgradeWebSocket(req, {
+          idleTimeout: 0,
+          maxBufferedAmount: 16,
+        });
+        socket.addEventListener("drain", () => drainDeferred.resolve());
+        socket.onopen = async function () {
+          assertThrows(
+            () => socket.send("x".repeat(17)),
+            DOMException,
+            "The WebSocket outbound queue is full",
+          );
+          assertEquals(socket.bufferedAmount, 0);
+          await drainDeferred.promise;
+          socket.send("x".repeat(16));
+          socket.close();
+        };
+        socket.onclose = () => ac.abort();
+        return response;
+      },
+      signal: ac.signal,
+      onListen: () => listeningDeferred.resolve(),
+      hostname: "localhost",
+      port: servePort,
+    });
+
+    await listeningDeferred.promise;
+    const messageDeferred = Promise.withResolvers<string>();
+    const closeDeferred = Promise.withResolvers<void>();
+    const ws = new WebSocket(serveUrl);
+    ws.onerror = () => fail();
+    ws.onmessage = (e) => messageDeferred.resolve(e.data);
+    ws.onclose = () => closeDeferred.resolve();
+
+    assertEquals(await messageDeferred.promise, "x".repeat(16));
+    await Promise.all([closeDeferred.promise, server.finished]);
+  },
+);
+
+Deno.test(
+  async function websocketServerMaxBufferedAmountInvalid() {
+    const ac = new AbortController();
+    const listeningDeferred = Promise.withResolvers<void>();
+
+    const server = Deno.serve({
+      handler: (req) => {
+        assertThrows(
+          () => Deno.upgradeWebSocket(req, { maxBufferedAmount: -1 }),
+          TypeError,
+        );
+        ac.abort();
+        return new Response(null, { status: 400 });
+      },
+      signal: ac.signal,
+      onListen: () => listeningDeferred.resolve(),
+      hostname: "localhost",
+      port: servePort,
+    });
+
+    await listeningDeferred.promise;
+    const ws = new WebSocket(serveUrl);
+    const closeDeferred = Promise.withResolvers<void>();
+    ws.onerror = () => {};
+    ws.onclose = () => closeDeferred.resolve();
+
+    await Promise.all([closeDeferred.promise, server.finished]);
+  },
+);
+
 Deno.test(
   { sanitizeOps: false },
   async function websocketServerGetsGhosted() {
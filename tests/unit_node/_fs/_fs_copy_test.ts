@@ -1,8 +1,14 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import * as path from "@std/path/mod.ts";
-import { assert } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 import { assertCallbackErrorUncaught } from "../_test_utils.ts";
-import { copyFile, copyFileSync, cpSync, existsSync } from "node:fs";
+import {
+  copyFile,
+  copyFileSync,
+  cpSync,
+  existsSync,
+  promises as fsPromises,
+} from "node:fs";
 
 const destFile = "./destination.txt";
 
@@ -60,3 +66,93 @@ Deno.test("[std/node/fs] cp creates destination directory", async () => {
   assert(existsSync(tempFile2));
   await Deno.remove(tempDir, { recursive: true });
 });
+
+Deno.test("[std/node/fs] cp copies directories recursively with a filter", async () => {
+  const tempDir = await Deno.makeTempDir();
+  const src = path.join(tempDir, "src");
+  const dest = path.join(tempDir, "dest");
+  await Deno.mkdir(path.join(src, "nested", "skipped"), { recursive: true });
+  await Deno.writeTextFile(path.join(src, "a.txt"), "a");
+  await Deno.writeTextFile(path.join(src, "b.log"), "b");
+  await Deno.writeTextFile(path.join(src, "nested", "c.txt"), "c");
+  await Deno.writeTextFile(path.join(src, "nested", "skipped", "d.txt"), "d");
+
+  await fsPromises.cp(src, dest, {
+    recursive: true,
+    filter: (source: string) =>
+      Promise.resolve(
+        !source.endsWith(".log") && path.basename(source) !== "skipped",
+      ),
+  });
+  assertEquals(await Deno.readTextFile(path.join(dest, "a.txt")), "a");
+  assertEquals(
+    await Deno.readTextFile(path.join(dest, "nested", "c.txt")),
+    "c",
+  );
+  assert(!existsSync(path.join(dest, "b.log")));
+  assert(!existsSync(path.join(dest, "nested", "skipped")));
+
+  assertThrows(
+    () =>
+      cpSync(src, path.join(tempDir, "other"), {
+        recursive: true,
+        filter: () => Promise.resolve(true),
+      }),
+    Error,
+    "Use `fs.promises.cp` instead",
+  );
+  await Deno.remove(tempDir, { recursive: true });
+});
+
+Deno.test("[std/node/fs] cp reports errors like Node", async () => {
+  const tempDir = await Deno.makeTempDir();
+  const src = path.join(tempDir, "src");
+  const file = path.join(tempDir, "file.txt");
+  await Deno.mkdir(src);
+  await Deno.writeTextFile(file, "hello world");
+  await Deno.writeTextFile(path.join(tempDir, "existing.txt"), "existing");
+
+  assertThrows(() => cpSync(src, path.join(tempDir, "dest")), Error, "EISDIR");
+  assertThrows(
+    () => cpSync(src, path.join(src, "inner"), { recursive: true }),
+    Error,
+    "cannot copy",
+  );
+  // deno-lint-ignore no-explicit-any
+  const err: any = assertThrows(() =>
+    cpSync(file, path.join(tempDir, "existing.txt"), {
+      force: false,
+      errorOnExist: true,
+    })
+  );
+  assertEquals(err.code, "ERR_FS_CP_EEXIST");
+  assertThrows(
+    () =>
+      cpSync(file, path.join(tempDir, "x"), {
+        dereference: true,
+        verbatimSymlinks: true,
+      }),
+    Error,
+    "dereference",
+  );
+  // not overwritten without `force`
+  cpSync(file, path.join(tempDir, "existing.txt"), { force: false });
+  assertEquals(
+    await Deno.readTextFile(path.join(tempDir, "existing.txt")),
+    "existing",
+  );
+  await Deno.remove(tempDir, { recursive: true });
+});
+
+Deno.test("[std/node/fs] cp preserves timestamps", async () => {
+  const tempDir = await Deno.makeTempDir();
+  const src = path.join(tempDir, "file.txt");
+  const dest = path.join(tempDir, "copy.txt");
+  await Deno.writeTextFile(src, "hello world");
+  const mtime = new Date("2020-01-01T00:00:00Z");
+  await Deno.utime(src, mtime, mtime);
+
+  cpSync(src, dest, { preserveTimestamps: true });
+  assertEquals((await Deno.stat(dest)).mtime, mtime);
+  await Deno.remove(tempDir, { recursive: true });
+});
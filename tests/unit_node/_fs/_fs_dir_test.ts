@@ -1,5 +1,5 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
-import { assert, assertEquals, fail } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows, fail } from "@std/assert/mod.ts";
 import { assertCallbackErrorUncaught } from "../_test_utils.ts";
 import { Dir as DirOrig, type Dirent } from "node:fs";
 
@@ -203,3 +203,53 @@ Deno.test(
     });
   },
 );
+
+Deno.test({
+  name: "Dir reads the entries in batches of bufferSize",
+  async fn() {
+    const testDir: string = Deno.makeTempDirSync();
+    for (let i = 0; i < 5; i++) {
+      Deno.writeTextFileSync(`${testDir}/${i}.txt`, "");
+    }
+
+    try {
+      const names = [];
+      for await (const file of new Dir(testDir, { bufferSize: 2 })) {
+        names.push(file.name);
+      }
+      assertEquals(names.sort(), [
+        "0.txt",
+        "1.txt",
+        "2.txt",
+        "3.txt",
+        "4.txt",
+      ]);
+
+      const dir = new Dir(testDir, { bufferSize: 2 });
+      const first = dir.readSync();
+      const second = await dir.read();
+      assert(first && second && first.name !== second.name);
+      dir.closeSync();
+    } finally {
+      Deno.removeSync(testDir, { recursive: true });
+    }
+  },
+});
+
+Deno.test({
+  name: "Dir can't be used once closed",
+  async fn() {
+    const dir = new Dir(".");
+    dir.closeSync();
+    assertThrows(() => dir.readSync(), Error, "Directory handle was closed");
+    assertThrows(() => dir.read(), Error, "Directory handle was closed");
+    assertThrows(() => dir.closeSync(), Error, "Directory handle was closed");
+    await new Promise<void>((resolve) => {
+      // deno-lint-ignore no-explicit-any
+      dir.close((err: any) => {
+        assertEquals(err.code, "ERR_DIR_CLOSED");
+        resolve();
+      });
+    });
+  },
+});
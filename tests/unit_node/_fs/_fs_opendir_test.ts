@@ -62,6 +62,7 @@ Deno.test("[node/fs] opendir()", async (t) => {
         (err, dir) => {
           assertEquals(err, null);
           assert(dir);
+          dir.closeSync();
         },
       ),
   );
@@ -74,6 +75,7 @@ Deno.test("[node/fs] opendir()", async (t) => {
         (err, dir) => {
           assertFalse(err);
           assert(dir);
+          dir.closeSync();
         },
       ),
   );
@@ -86,6 +88,7 @@ Deno.test("[node/fs] opendir()", async (t) => {
         (err, dir) => {
           assertFalse(err);
           assert(dir);
+          dir.closeSync();
         },
       ),
   );
@@ -130,15 +133,21 @@ Deno.test("[node/fs] opendirSync()", async (t) => {
   });
 
   await t.step("passes if path is a string", () => {
-    assert(opendirSync(path));
+    const dir = opendirSync(path);
+    assert(dir);
+    dir.closeSync();
   });
 
   await t.step("passes if path is a Buffer", () => {
-    assert(opendirSync(Buffer.from(path)));
+    const dir = opendirSync(Buffer.from(path));
+    assert(dir);
+    dir.closeSync();
   });
 
   await t.step("passes if path is a URL", () => {
-    assert(opendirSync(new URL(`file://` + path)));
+    const dir = opendirSync(new URL(`file://` + path));
+    assert(dir);
+    dir.closeSync();
   });
 
   await Deno.remove(path);
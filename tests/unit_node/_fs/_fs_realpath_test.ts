@@ -1,7 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import * as path from "@std/path/mod.ts";
-import { assertEquals } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 import { assertCallbackErrorUncaught } from "../_test_utils.ts";
+import { Buffer } from "node:buffer";
 import { realpath, realpathSync } from "node:fs";
 
 Deno.test("realpath", async function () {
@@ -53,3 +54,38 @@ Deno.test("[std/node/fs] realpath callback isn't called twice if error is thrown
     },
   });
 });
+
+Deno.test("realpath.native", async function () {
+  const tempDir = Deno.realPathSync(await Deno.makeTempDir());
+  const tempFile = path.join(tempDir, "file.txt");
+  const linkFile = path.join(tempDir, "link.txt");
+  await Deno.writeTextFile(tempFile, "hello world");
+  await Deno.symlink(tempFile, linkFile);
+
+  assert(realpath.native !== realpath);
+  assertEquals(realpathSync.native(linkFile), tempFile);
+  const buffer = realpathSync.native(linkFile, { encoding: "buffer" });
+  assert(Buffer.isBuffer(buffer));
+  assertEquals(buffer.toString(), tempFile);
+  const resolved = await new Promise<string | Buffer | undefined>(
+    (resolve, reject) => {
+      realpath.native(linkFile, "buffer", (err, path) => {
+        if (err) {
+          reject(err);
+          return;
+        }
+        resolve(path);
+      });
+    },
+  );
+  assert(Buffer.isBuffer(resolved));
+  assertEquals(resolved.toString(), tempFile);
+
+  const missing = path.join(tempDir, "missing.txt");
+  // deno-lint-ignore no-explicit-any
+  const err: any = assertThrows(() => realpathSync.native(missing));
+  assertEquals(err.code, "ENOENT");
+  assertEquals(err.syscall, "realpath");
+  assertEquals(err.path, missing);
+  await Deno.remove(tempDir, { recursive: true });
+});
@@ -855,3 +855,77 @@ Deno.test(
     assertEquals(output.stderr, null);
   },
 );
+
+function forkIpc(options: { serialization?: "json" | "advanced" } = {}) {
+  const testdataDir = path.join(
+    path.dirname(path.fromFileUrl(import.meta.url)),
+    "testdata",
+  );
+  const script = path.join(testdataDir, "child_process_ipc.js");
+  return CP.fork(script, [], { cwd: testdataDir, ...options });
+}
+
+Deno.test("[node/child_process fork] sends json messages", async () => {
+  const cp = forkIpc();
+  const message = withTimeout<unknown>();
+  cp.on("message", (msg) => message.resolve(msg));
+  assertEquals(cp.send({ a: [1, "b"] }), true);
+  assertEquals(await message.promise, {
+    echo: { a: [1, "b"] },
+    connected: true,
+  });
+
+  const disconnected = withTimeout<void>();
+  const exited = withTimeout<number | null>();
+  cp.on("disconnect", () => disconnected.resolve());
+  cp.on("exit", (code) => exited.resolve(code));
+  cp.send("disconnect");
+  await disconnected.promise;
+  assertEquals(cp.connected, false);
+  assertEquals(await exited.promise, 0);
+});
+
+Deno.test("[node/child_process fork] sends advanced messages", async () => {
+  const cp = forkIpc({ serialization: "advanced" });
+  const message = withTimeout<unknown>();
+  cp.on("message", (msg) => message.resolve(msg));
+  const value = new Map<string, unknown>([
+    ["big", 1n << 70n],
+    ["bytes", new Uint8Array([1, 2, 3])],
+    ["date", new Date(0)],
+  ]);
+  cp.send(value);
+  assertEquals(await message.promise, { echo: value, connected: true });
+
+  const exited = withTimeout<void>();
+  cp.on("exit", () => exited.resolve());
+  cp.disconnect();
+  await exited.promise;
+});
+
+Deno.test("[node/child_process fork] send after disconnect", async () => {
+  const cp = forkIpc();
+  const exited = withTimeout<void>();
+  cp.on("exit", () => exited.resolve());
+  cp.disconnect();
+
+  const error = withTimeout<Error & { code?: string }>();
+  assertEquals(cp.send("foo", (err) => error.resolve(err!)), false);
+  assertEquals((await error.promise).code, "ERR_IPC_CHANNEL_CLOSED");
+  await exited.promise;
+});
+
+Deno.test(
+  "[node/child_process fork] child exits without message listeners",
+  async () => {
+    const testdataDir = path.join(
+      path.dirname(path.fromFileUrl(import.meta.url)),
+      "testdata",
+    );
+    const script = path.join(testdataDir, "node_modules", "foo", "index.js");
+    const cp = CP.fork(script, [], { cwd: testdataDir, stdio: "ignore" });
+    const exited = withTimeout<number | null>();
+    cp.on("exit", (code) => exited.resolve(code));
+    assertEquals(await exited.promise, 0);
+  },
+);
@@ -1,10 +1,15 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 import crypto from "node:crypto";
+import fs from "node:fs";
 import { Buffer } from "node:buffer";
 import testVectors128 from "./gcmEncryptExtIV128.json" assert { type: "json" };
 import testVectors256 from "./gcmEncryptExtIV256.json" assert { type: "json" };
-import { assertEquals } from "@std/assert/mod.ts";
+import {
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "@std/assert/mod.ts";
 
 const aesGcm = (bits: string, key: Uint8Array) => {
   const ALGO = bits == "128" ? `aes-128-gcm` : `aes-256-gcm`;
@@ -101,3 +106,159 @@ for (
     });
   }
 }
+
+// Test case 4 of the GCM specification
+const key = Buffer.from("feffe9928665731c6d6a8f9467308308", "hex");
+const iv = Buffer.from("cafebabefacedbaddecaf888", "hex");
+const aad = Buffer.from("feedfacedeadbeeffeedfacedeadbeefabaddad2", "hex");
+const plaintext = Buffer.from(
+  "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+  "hex",
+);
+const ciphertext =
+  "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091";
+const tag = "5bc94fbc3221a5db94fae95ae7121a47";
+
+Deno.test({
+  name: "aes-128-gcm with chunks that don't fill a block",
+  fn() {
+    const cipher = crypto.createCipheriv("aes-128-gcm", key, iv);
+    cipher.setAAD(aad);
+    let enc = "";
+    for (let i = 0; i < plaintext.length; i += 7) {
+      enc += cipher.update(plaintext.subarray(i, i + 7), undefined, "hex");
+    }
+    enc += cipher.final("hex");
+    assertEquals(enc, ciphertext);
+    assertEquals(cipher.getAuthTag().toString("hex"), tag);
+
+    const decipher = crypto.createDecipheriv("aes-128-gcm", key, iv);
+    decipher.setAAD(aad);
+    decipher.setAuthTag(Buffer.from(tag, "hex"));
+    let dec = decipher.update(ciphertext.slice(0, 22), "hex", "hex");
+    dec += decipher.update(ciphertext.slice(22), "hex", "hex");
+    dec += decipher.final("hex");
+    assertEquals(dec, plaintext.toString("hex"));
+  },
+});
+
+Deno.test({
+  name: "aes-128-gcm with authTagLength",
+  fn() {
+    const cipher = crypto.createCipheriv("aes-128-gcm", key, iv, {
+      authTagLength: 12,
+    });
+    cipher.setAAD(aad);
+    cipher.update(plaintext);
+    cipher.final();
+    assertEquals(cipher.getAuthTag().toString("hex"), tag.slice(0, 24));
+
+    const decipher = crypto.createDecipheriv("aes-128-gcm", key, iv, {
+      authTagLength: 16,
+    });
+    assertThrows(
+      () => decipher.setAuthTag(cipher.getAuthTag()),
+      TypeError,
+      "Invalid authentication tag length: 12",
+    );
+    assertThrows(
+      () =>
+        crypto.createCipheriv("aes-128-gcm", key, iv, { authTagLength: 5 }),
+      TypeError,
+      "Invalid authentication tag length: 5",
+    );
+  },
+});
+
+Deno.test({
+  name: "aes-128-gcm fails to authenticate a wrong tag",
+  fn() {
+    const decipher = crypto.createDecipheriv("aes-128-gcm", key, iv);
+    decipher.setAAD(aad);
+    decipher.setAuthTag(Buffer.alloc(16));
+    decipher.update(ciphertext, "hex");
+    assertThrows(() => decipher.final(), TypeError, "authenticate");
+  },
+});
+
+Deno.test({
+  name: "aes-128-gcm pipeFd writes nothing for a wrong tag",
+  async fn() {
+    const dir = Deno.makeTempDirSync();
+    Deno.writeFileSync(`${dir}/encrypted`, Buffer.from(ciphertext, "hex"));
+    const decipher = crypto.createDecipheriv("aes-128-gcm", key, iv);
+    decipher.setAAD(aad);
+    decipher.setAuthTag(Buffer.alloc(16));
+
+    const srcFd = fs.openSync(`${dir}/encrypted`, "r");
+    const dstFd = fs.openSync(`${dir}/decrypted`, "w");
+    try {
+      await assertRejects(
+        // @ts-ignore: pipeFd is Deno only
+        () => decipher.pipeFd(srcFd, dstFd),
+        TypeError,
+        "authenticate",
+      );
+    } finally {
+      fs.closeSync(srcFd);
+      fs.closeSync(dstFd);
+    }
+    assertEquals(Deno.readFileSync(`${dir}/decrypted`).length, 0);
+    Deno.removeSync(dir, { recursive: true });
+  },
+});
+
+Deno.test({
+  name: "aes-128-gcm pipeFd rejects input over the buffering limit",
+  async fn() {
+    const dir = Deno.makeTempDirSync();
+    Deno.writeFileSync(
+      `${dir}/encrypted`,
+      new Uint8Array(64 * 1024 * 1024 + 1),
+    );
+    const decipher = crypto.createDecipheriv("aes-128-gcm", key, iv);
+    decipher.setAuthTag(Buffer.alloc(16));
+
+    const srcFd = fs.openSync(`${dir}/encrypted`, "r");
+    const dstFd = fs.openSync(`${dir}/decrypted`, "w");
+    try {
+      await assertRejects(
+        // @ts-ignore: pipeFd is Deno only
+        () => decipher.pipeFd(srcFd, dstFd),
+        RangeError,
+        "too large to pipe",
+      );
+    } finally {
+      fs.closeSync(srcFd);
+      fs.closeSync(dstFd);
+    }
+    assertEquals(Deno.readFileSync(`${dir}/decrypted`).length, 0);
+    Deno.removeSync(dir, { recursive: true });
+  },
+});
+
+Deno.test({
+  name: "aes-192-gcm round trip",
+  fn() {
+    const key192 = Buffer.alloc(24, 1);
+    const cipher = crypto.createCipheriv("aes-192-gcm", key192, iv);
+    const enc = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+
+    const decipher = crypto.createDecipheriv("aes-192-gcm", key192, iv);
+    decipher.setAuthTag(cipher.getAuthTag());
+    const dec = Buffer.concat([decipher.update(enc), decipher.final()]);
+    assertEquals(dec, plaintext);
+  },
+});
+
+Deno.test({
+  name: "getAuthTag before final",
+  fn() {
+    const cipher = crypto.createCipheriv("aes-128-gcm", key, iv);
+    assertThrows(
+      () => cipher.getAuthTag(),
+      Error,
+      "Invalid state for operation getAuthTag",
+    );
+  },
+});
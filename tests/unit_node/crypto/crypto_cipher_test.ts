@@ -1,16 +1,12 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import crypto from "node:crypto";
+import fs from "node:fs";
 import { Buffer } from "node:buffer";
 import { Readable } from "node:stream";
 import { buffer, text } from "node:stream/consumers";
 import { assertEquals, assertThrows } from "@std/assert/mod.ts";
-
-const rsaPrivateKey = Deno.readTextFileSync(
-  new URL("../testdata/rsa_private.pem", import.meta.url),
-);
-const rsaPublicKey = Deno.readTextFileSync(
-  new URL("../testdata/rsa_public.pem", import.meta.url),
-);
+import rsaPrivateKey from "../testdata/rsa_private.pem" with { type: "text" };
+import rsaPublicKey from "../testdata/rsa_public.pem" with { type: "text" };
 
 const input = new TextEncoder().encode("hello world");
 
@@ -46,14 +42,110 @@ Deno.test({
 });
 
 Deno.test({
-  name: "rsa private encrypt and private decrypt",
+  name: "rsa private encrypt and public decrypt",
   fn() {
     const encrypted = crypto.privateEncrypt(rsaPrivateKey, input);
+    assertEquals(
+      Buffer.from(encrypted).toString("base64"),
+      "gkJOHR/8Ka+rG59AzZciXfZ+rYlF1ChcsJlhQe9yx6MVdTeXsrmw8nB7hTZSfLD6yL/RoH22wqbhENO4yHoGbWrltReJgRtGlZ/22dRBghogGNmD13yr4Ho3r2Me6pWUjyyQQVgs6NQpJZYFBoRclAsUamDiRxK6P01Gbh0fzPY6VVXbx13VCKMrE3eIYacJneCP4izmV3XNkeJw5Ia7ulwiDDUesy5f/20hP6pDind2q/+5+83MhLvsBTi39lC0YDYxEItBygvNUTCPng3ZFis8+VEYwsT6QBSE+UuUzLBC1R6ueeC9ceVsCwkQaZlFwQ/LgDUG5CHxJQfia1lXoA==",
+    );
+    const decrypted = crypto.publicDecrypt(
+      rsaPublicKey,
+      Buffer.from(encrypted),
+    );
+    assertEquals(decrypted, input);
+  },
+});
+
+Deno.test({
+  name: "rsa oaep hash and label",
+  fn() {
+    const options = {
+      padding: crypto.constants.RSA_PKCS1_OAEP_PADDING,
+      oaepHash: "sha256",
+      oaepLabel: Buffer.from("label"),
+    };
+    const encrypted = crypto.publicEncrypt(
+      { key: rsaPublicKey, ...options },
+      input,
+    );
     const decrypted = crypto.privateDecrypt(
-      rsaPrivateKey,
+      { key: rsaPrivateKey, ...options },
       Buffer.from(encrypted),
     );
     assertEquals(decrypted, input);
+
+    assertThrows(() =>
+      crypto.privateDecrypt(
+        { key: rsaPrivateKey, ...options, oaepHash: "sha1" },
+        Buffer.from(encrypted),
+      )
+    );
+    assertThrows(() =>
+      crypto.publicEncrypt(
+        { key: rsaPublicKey, ...options, oaepHash: "md55" },
+        input,
+      )
+    );
+  },
+});
+
+Deno.test({
+  name: "rsa no padding",
+  fn() {
+    const { RSA_NO_PADDING } = crypto.constants;
+    // a PKCS#1 v1.5 signature is the padded input raised to the private
+    // exponent, so publicDecrypt without padding recovers the padded block
+    const signature = crypto.privateEncrypt(rsaPrivateKey, input);
+    const block = crypto.publicDecrypt(
+      { key: rsaPublicKey, padding: RSA_NO_PADDING },
+      signature,
+    );
+    assertEquals(block.length, signature.length);
+    assertEquals([...block.subarray(0, 3)], [0, 1, 0xff]);
+    assertEquals([...block.subarray(block.length - input.length)], [...input]);
+    assertEquals(
+      [
+        ...crypto.privateEncrypt(
+          { key: rsaPrivateKey, padding: RSA_NO_PADDING },
+          block,
+        ),
+      ],
+      [...signature],
+    );
+
+    const encrypted = crypto.publicEncrypt(
+      { key: rsaPublicKey, padding: RSA_NO_PADDING },
+      block,
+    );
+    assertEquals(
+      [
+        ...crypto.privateDecrypt(
+          { key: rsaPrivateKey, padding: RSA_NO_PADDING },
+          encrypted,
+        ),
+      ],
+      [...block],
+    );
+
+    assertThrows(
+      () =>
+        crypto.publicEncrypt(
+          { key: rsaPublicKey, padding: RSA_NO_PADDING },
+          input,
+        ),
+      TypeError,
+      "Data must be as long as the key modulus",
+    );
+    assertThrows(
+      () =>
+        crypto.publicEncrypt(
+          { key: rsaPublicKey, padding: RSA_NO_PADDING },
+          Buffer.alloc(block.length, 0xff),
+        ),
+      TypeError,
+      "Data must be as long as the key modulus",
+    );
   },
 });
 
@@ -233,6 +325,35 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "aes-128-cbc update returns the whole blocks so far",
+  fn() {
+    const key = Buffer.alloc(16, 1);
+    const iv = Buffer.alloc(16, 2);
+    const plaintext = Buffer.alloc(21, "a");
+
+    const cipher = crypto.createCipheriv("aes-128-cbc", key, iv);
+    assertEquals(cipher.update(plaintext.subarray(0, 10)).length, 0);
+    const first = cipher.update(plaintext.subarray(10));
+    assertEquals(first.length, 16);
+    const last = cipher.final();
+    assertEquals(last.length, 16);
+    const ciphertext = Buffer.concat([first, last]);
+    assertEquals(
+      ciphertext.toString("hex"),
+      "8191529c09e02d51535221189b1b303fd342edb9e95def0d2e16f0170d7254ea",
+    );
+
+    const decipher = crypto.createDecipheriv("aes-128-cbc", key, iv);
+    assertEquals(decipher.update(ciphertext.subarray(0, 16)).length, 0);
+    assertEquals(
+      decipher.update(ciphertext.subarray(16)),
+      plaintext.subarray(0, 16),
+    );
+    assertEquals(decipher.final(), plaintext.subarray(16));
+  },
+});
+
 Deno.test({
   name: "createCipheriv - invalid algorithm",
   fn() {
@@ -257,6 +378,51 @@ Deno.test({
   },
 });
 
+// Packet vector #1 of RFC 3610
+Deno.test({
+  name: "aes-128-ccm",
+  fn() {
+    const key = Buffer.from("c0c1c2c3c4c5c6c7c8c9cacbcccdcecf", "hex");
+    const nonce = Buffer.from("00000003020100a0a1a2a3a4a5", "hex");
+    const aad = Buffer.from("0001020304050607", "hex");
+    const plaintext = Buffer.from(
+      "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+      "hex",
+    );
+    const ciphertext = "588c979a61c663d2f066d0c2c0f989806d5f6b61dac384";
+
+    const cipher = crypto.createCipheriv("aes-128-ccm", key, nonce, {
+      authTagLength: 8,
+    });
+    assertThrows(
+      () => cipher.setAAD(aad),
+      TypeError,
+      "options.plaintextLength required for CCM mode with AAD",
+    );
+    cipher.setAAD(aad, { plaintextLength: plaintext.length });
+    let enc = cipher.update(plaintext, undefined, "hex");
+    enc += cipher.final("hex");
+    assertEquals(enc, ciphertext);
+    const tag = cipher.getAuthTag();
+    assertEquals(tag.toString("hex"), "17e8d12cfdf926e0");
+
+    const decipher = crypto.createDecipheriv("aes-128-ccm", key, nonce, {
+      authTagLength: 8,
+    });
+    decipher.setAuthTag(tag);
+    decipher.setAAD(aad, { plaintextLength: plaintext.length });
+    const dec = decipher.update(ciphertext, "hex");
+    decipher.final();
+    assertEquals(dec, plaintext);
+
+    assertThrows(
+      () => crypto.createCipheriv("aes-128-ccm", key, nonce),
+      TypeError,
+      "authTagLength required for aes-128-ccm",
+    );
+  },
+});
+
 Deno.test({
   name: "getCiphers",
   fn() {
@@ -264,6 +430,28 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "getCiphers lists the implemented ciphers",
+  fn() {
+    for (const name of crypto.getCiphers()) {
+      const info = crypto.getCipherInfo(name)!;
+      if (info.mode !== "cbc" && info.mode !== "ecb") {
+        continue;
+      }
+      const key = zeros(info.keyLength);
+      const iv = zeros(info.mode === "cbc" ? info.ivLength : 0);
+      const cipher = crypto.createCipheriv(name, key, iv);
+      const encrypted = Buffer.concat([cipher.update(input), cipher.final()]);
+      const decipher = crypto.createDecipheriv(name, key, iv);
+      assertEquals(
+        Buffer.concat([decipher.update(encrypted), decipher.final()]),
+        Buffer.from(input),
+      );
+    }
+    assertEquals(crypto.getCiphers().includes("aes-128-ctr"), false);
+  },
+});
+
 Deno.test({
   name: "getCipherInfo",
   fn() {
@@ -278,3 +466,79 @@ Deno.test({
     assertEquals(info2.ivLength, 16);
   },
 });
+
+Deno.test({
+  name: "Cipheriv and Decipheriv updateFromStream",
+  async fn() {
+    const key = zeros(16);
+    const iv = zeros(16);
+    const chunks = [new Uint8Array(70_000).fill(1), new Uint8Array(5)];
+    const plaintext = Buffer.concat(chunks);
+
+    const cipher = crypto.createCipheriv("aes-128-cbc", key, iv);
+    const encrypted = Buffer.concat([
+      // @ts-ignore: updateFromStream is Deno only
+      await buffer(cipher.updateFromStream(ReadableStream.from(chunks))),
+      cipher.final(),
+    ]);
+    const expected = crypto.createCipheriv("aes-128-cbc", key, iv);
+    assertEquals(
+      encrypted,
+      Buffer.concat([expected.update(plaintext), expected.final()]),
+    );
+
+    const decipher = crypto.createDecipheriv("aes-128-cbc", key, iv);
+    const decrypted = Buffer.concat([
+      // @ts-ignore: updateFromStream is Deno only
+      await buffer(decipher.updateFromStream(ReadableStream.from([encrypted]))),
+      decipher.final(),
+    ]);
+    assertEquals(decrypted, plaintext);
+  },
+});
+
+Deno.test({
+  name: "Cipheriv and Decipheriv pipeFd",
+  async fn() {
+    const key = zeros(16);
+    const iv = zeros(16);
+    const dir = Deno.makeTempDirSync();
+    const plaintext = new Uint8Array(200_003).map((_, i) => i % 251);
+    Deno.writeFileSync(`${dir}/plain`, plaintext);
+
+    const pipeFile = async (
+      cipher: crypto.Cipher | crypto.Decipher,
+      src: string,
+      dst: string,
+    ) => {
+      const srcFd = fs.openSync(src, "r");
+      const dstFd = fs.openSync(dst, "w");
+      try {
+        // @ts-ignore: pipeFd is Deno only
+        await cipher.pipeFd(srcFd, dstFd);
+      } finally {
+        fs.closeSync(srcFd);
+        fs.closeSync(dstFd);
+      }
+    };
+
+    await pipeFile(
+      crypto.createCipheriv("aes-128-cbc", key, iv),
+      `${dir}/plain`,
+      `${dir}/encrypted`,
+    );
+    const expected = crypto.createCipheriv("aes-128-cbc", key, iv);
+    assertEquals(
+      Buffer.from(Deno.readFileSync(`${dir}/encrypted`)),
+      Buffer.concat([expected.update(plaintext), expected.final()]),
+    );
+
+    await pipeFile(
+      crypto.createDecipheriv("aes-128-cbc", key, iv),
+      `${dir}/encrypted`,
+      `${dir}/decrypted`,
+    );
+    assertEquals(Deno.readFileSync(`${dir}/decrypted`), plaintext);
+    Deno.removeSync(dir, { recursive: true });
+  },
+});
@@ -0,0 +1,151 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+import {
+  createDiffieHellman,
+  createECDH,
+  ECDH,
+  getCurves,
+  getDiffieHellman,
+} from "node:crypto";
+import { Buffer } from "node:buffer";
+import { assertEquals, assertThrows } from "@std/assert/mod.ts";
+
+Deno.test({
+  name: "[node/crypto] DiffieHellman computes the same secret",
+  fn() {
+    const alice = createDiffieHellman(Buffer.from([23]), 5);
+    alice.setPrivateKey(Buffer.from([6]));
+    assertEquals(alice.generateKeys("hex"), "08");
+
+    const bob = createDiffieHellman(alice.getPrime(), alice.getGenerator());
+    bob.setPrivateKey(Buffer.from([15]));
+    assertEquals(bob.generateKeys("hex"), "13");
+
+    assertEquals(alice.computeSecret(bob.getPublicKey()), Buffer.from([2]));
+    assertEquals(bob.computeSecret("08", "hex", "hex"), "02");
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] DiffieHellman without keys",
+  fn() {
+    const dh = createDiffieHellman(Buffer.from([23]), 5);
+    assertThrows(
+      () => dh.getPublicKey(),
+      Error,
+      "No public key - did you forget to generate one?",
+    );
+    assertThrows(
+      () => dh.computeSecret(Buffer.from([8])),
+      TypeError,
+      "No private key - did you forget to generate one?",
+    );
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] getDiffieHellman",
+  fn() {
+    const alice = getDiffieHellman("modp14");
+    const bob = getDiffieHellman("modp14");
+    assertEquals(alice.getPrime().length, 256);
+    assertEquals(
+      alice.getPrime("hex").slice(0, 24),
+      "ffffffffffffffffc90fdaa2",
+    );
+    assertEquals(alice.getGenerator(), Buffer.from([2]));
+
+    const alicePublicKey = alice.generateKeys();
+    const bobPublicKey = bob.generateKeys();
+    assertEquals(alicePublicKey.length, 256);
+    assertEquals(
+      alice.computeSecret(bobPublicKey),
+      bob.computeSecret(alicePublicKey),
+    );
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] getDiffieHellman with an unknown group",
+  fn() {
+    assertThrows(() => getDiffieHellman("modp1"), Error, "Unknown DH group");
+  },
+});
+
+for (const curve of ["prime256v1", "secp256k1", "secp384r1", "secp521r1"]) {
+  Deno.test({
+    name: `[node/crypto] ECDH ${curve} computes the same secret`,
+    fn() {
+      const alice = createECDH(curve);
+      const bob = createECDH(curve);
+      const alicePublicKey = alice.generateKeys();
+      const bobPublicKey = bob.generateKeys("hex", "compressed");
+      assertEquals(
+        alice.computeSecret(bobPublicKey, "hex", "hex"),
+        bob.computeSecret(alicePublicKey, undefined, "hex"),
+      );
+    },
+  });
+}
+
+Deno.test({
+  name: "[node/crypto] getCurves lists the curves supported by ECDH",
+  fn() {
+    const curves = getCurves();
+    assertEquals(curves.includes("secp256k1"), true);
+    for (const curve of curves) {
+      const ecdh = createECDH(curve);
+      assertEquals(ecdh.generateKeys().length > 0, true);
+    }
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] ECDH.convertKey",
+  fn() {
+    const ecdh = createECDH("secp521r1");
+    const uncompressed = ecdh.generateKeys();
+    assertEquals(uncompressed.length, 133);
+
+    const compressed = ECDH.convertKey(
+      uncompressed,
+      "secp521r1",
+      undefined,
+      undefined,
+      "compressed",
+    ) as Buffer;
+    assertEquals(compressed.length, 67);
+    assertEquals(ecdh.getPublicKey(undefined, "compressed"), compressed);
+
+    const hybrid = ECDH.convertKey(
+      compressed.toString("hex"),
+      "secp521r1",
+      "hex",
+      "hex",
+      "hybrid",
+    ) as string;
+    assertEquals(hybrid.slice(2), uncompressed.toString("hex").slice(2));
+    assertEquals(parseInt(hybrid.slice(0, 2), 16) & 0xfe, 0x06);
+    assertEquals(
+      ECDH.convertKey(hybrid, "secp521r1", "hex"),
+      uncompressed,
+    );
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] ECDH with an invalid public key",
+  fn() {
+    const ecdh = createECDH("prime256v1");
+    ecdh.generateKeys();
+    assertThrows(
+      () => ecdh.computeSecret(Buffer.from([4, 1, 2, 3])),
+      Error,
+      "Public key is not valid for specified curve",
+    );
+    assertThrows(
+      () => ECDH.convertKey(Buffer.alloc(65), "prime256v1"),
+      Error,
+      "Public key is not valid for specified curve",
+    );
+  },
+});
@@ -5,10 +5,17 @@ import {
   getHashes,
   randomFillSync,
   randomUUID,
+  // @ts-ignore: randomUUIDv7 is not in @types/node
+  randomUUIDv7,
 } from "node:crypto";
 import { Buffer } from "node:buffer";
 import { Readable } from "node:stream";
-import { assert, assertEquals } from "@std/assert/mod.ts";
+import {
+  assert,
+  assertEquals,
+  assertMatch,
+  assertThrows,
+} from "@std/assert/mod.ts";
 
 // https://github.com/denoland/deno/issues/18140
 Deno.test({
@@ -21,6 +28,30 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "[node/crypto] createHmac with a key longer than the block size",
+  fn() {
+    // RFC 4231, test case 6
+    assertEquals(
+      createHmac("sha256", Buffer.alloc(131, 0xaa))
+        .update("Test Using Larger Than Block-Size Key - Hash Key First")
+        .digest("hex"),
+      "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+    );
+  },
+});
+
+Deno.test({
+  name: "[node/crypto] createHmac with an unknown digest",
+  fn() {
+    assertThrows(
+      () => createHmac("sha0", "secret"),
+      TypeError,
+      "Invalid digest: sha0",
+    );
+  },
+});
+
 Deno.test({
   name: "[node/crypto] createHash digest",
   fn() {
@@ -115,6 +146,53 @@ Deno.test("[node/crypto.Hash] streaming usage", async () => {
   );
 });
 
+Deno.test("[node/crypto.createHash] shake128 and shake256", () => {
+  assertEquals(
+    createHash("shake128").update("abc").digest("hex"),
+    "5881092dd818bf5cf8a3ddb793fbcba7",
+  );
+  assertEquals(
+    createHash("shake256", { outputLength: 8 }).update("abc").digest("hex"),
+    "483366601360a877",
+  );
+  assertEquals(
+    createHash("shake256", { outputLength: 0 }).update("abc").digest().length,
+    0,
+  );
+
+  const hash = createHash("shake128").update("ab");
+  const copy = hash.copy({ outputLength: 32 });
+  assertEquals(
+    copy.update("c").digest("hex"),
+    "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8",
+  );
+});
+
+Deno.test("[node/crypto.createHash] outputLength of non-XOF hashes", () => {
+  assertEquals(
+    createHash("sha256", { outputLength: 32 }).update("abc").digest().length,
+    32,
+  );
+  assertThrows(
+    () => createHash("sha256", { outputLength: 16 }).update("abc").digest(),
+    TypeError,
+    "Output length 16 is invalid for sha256, which does not support XOF",
+  );
+  assertThrows(() => createHash("shake256", { outputLength: -1 }), RangeError);
+});
+
+Deno.test("[node/crypto.createHash] blake2b512 and blake2s256", () => {
+  assertEquals(
+    createHash("blake2b512").update("abc").digest("hex"),
+    "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1" +
+      "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+  );
+  assertEquals(
+    createHash("blake2s256").update("abc").digest("hex"),
+    "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+  );
+});
+
 Deno.test("[node/crypto.getHashes]", () => {
   for (const algorithm of getHashes()) {
     const d = createHash(algorithm).update("abc").digest();
@@ -128,6 +206,29 @@ Deno.test("[node/crypto.getRandomUUID] works the same way as Web Crypto API", ()
   assertEquals(typeof randomUUID(), typeof crypto.randomUUID());
 });
 
+Deno.test("[node/crypto.randomUUID] options", () => {
+  assertMatch(
+    randomUUID({ disableEntropyCache: true }),
+    /^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/,
+  );
+  // @ts-ignore: invalid options
+  assertThrows(() => randomUUID(null), TypeError);
+  // @ts-ignore: invalid options
+  assertThrows(() => randomUUID({ disableEntropyCache: 1 }), TypeError);
+});
+
+Deno.test("[node/crypto.randomUUIDv7]", () => {
+  const before = Date.now();
+  // @ts-ignore: randomUUIDv7 is not in @types/node
+  const uuid: string = randomUUIDv7();
+  assertMatch(
+    uuid,
+    /^[0-9a-f]{8}-[0-9a-f]{4}-7[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/,
+  );
+  const time = parseInt(uuid.replace("-", "").slice(0, 12), 16);
+  assert(time >= before && time <= Date.now());
+});
+
 Deno.test("[node/crypto.randomFillSync] supported arguments", () => {
   const buf = new Uint8Array(10);
 
@@ -137,3 +238,30 @@ Deno.test("[node/crypto.randomFillSync] supported arguments", () => {
   assert(randomFillSync(buf.buffer));
   assert(randomFillSync(new DataView(buf.buffer)));
 });
+
+Deno.test("[node/crypto.Hash] updateFromStream", async () => {
+  const chunks = [new Uint8Array(100_000).fill(1), new Uint8Array(7)];
+  const expected = createHash("sha256").update(chunks[0]).update(chunks[1])
+    .digest("hex");
+
+  const hash = createHash("sha256");
+  // @ts-ignore: updateFromStream is Deno only
+  await hash.updateFromStream(ReadableStream.from(chunks));
+  assertEquals(hash.digest("hex"), expected);
+
+  const tempFile = await Deno.makeTempFile();
+  try {
+    await Deno.writeFile(tempFile, Buffer.concat(chunks));
+    using file = await Deno.open(tempFile);
+    const hmac = createHmac("sha256", "secret");
+    // @ts-ignore: updateFromStream is Deno only
+    await hmac.updateFromStream(file.readable);
+    assertEquals(
+      hmac.digest("hex"),
+      createHmac("sha256", "secret").update(Buffer.concat(chunks))
+        .digest("hex"),
+    );
+  } finally {
+    await Deno.remove(tempFile);
+  }
+});
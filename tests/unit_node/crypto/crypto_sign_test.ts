@@ -1,24 +1,31 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { assert, assertEquals } from "@std/testing/asserts.ts";
-import { createSign, createVerify, sign, verify } from "node:crypto";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "@std/testing/asserts.ts";
+import {
+  constants,
+  createPublicKey,
+  createSign,
+  createVerify,
+  generateKeyPairSync,
+  sign,
+  verify,
+} from "node:crypto";
 import { Buffer } from "node:buffer";
+import rsaPrivateBytes from "../testdata/rsa_private.pem" with {
+  type: "bytes",
+};
+import rsaPrivatePkcs1Bytes from "../testdata/rsa_private_pkcs1.pem" with {
+  type: "bytes",
+};
+import rsaPublicBytes from "../testdata/rsa_public.pem" with { type: "bytes" };
 
-const rsaPrivatePem = Buffer.from(
-  await Deno.readFile(
-    new URL("../testdata/rsa_private.pem", import.meta.url),
-  ),
-);
-const rsaPrivatePkcs1Pem = Buffer.from(
-  await Deno.readFile(
-    new URL("../testdata/rsa_private_pkcs1.pem", import.meta.url),
-  ),
-);
-const rsaPublicPem = Buffer.from(
-  await Deno.readFile(
-    new URL("../testdata/rsa_public.pem", import.meta.url),
-  ),
-);
+const rsaPrivatePem = Buffer.from(rsaPrivateBytes);
+const rsaPrivatePkcs1Pem = Buffer.from(rsaPrivatePkcs1Bytes);
+const rsaPublicPem = Buffer.from(rsaPublicBytes);
 
 const table = [
   {
@@ -138,3 +145,134 @@ AwEH
     createSign("SHA256").update("test").sign(pem, "base64");
   },
 });
+
+Deno.test({
+  name: "crypto.createVerify|verify - RSA public KeyObject",
+  fn() {
+    const publicKey = createPublicKey(rsaPublicPem);
+    for (const testCase of table) {
+      for (const algorithm of testCase.algorithms) {
+        assert(
+          createVerify(algorithm).update(data).verify(
+            { key: publicKey },
+            testCase.signature,
+            "hex",
+          ),
+        );
+        assert(
+          verify(
+            algorithm,
+            data,
+            publicKey,
+            Buffer.from(testCase.signature, "hex"),
+          ),
+        );
+      }
+    }
+  },
+});
+
+Deno.test({
+  name: "crypto.createSign|sign - RSA-PSS padding",
+  fn() {
+    const key = {
+      key: rsaPrivatePem,
+      padding: constants.RSA_PKCS1_PSS_PADDING,
+    };
+    const publicKey = {
+      key: rsaPublicPem,
+      padding: constants.RSA_PKCS1_PSS_PADDING,
+    };
+    for (
+      const saltLength of [
+        undefined,
+        0,
+        16,
+        constants.RSA_PSS_SALTLEN_DIGEST,
+        constants.RSA_PSS_SALTLEN_MAX_SIGN,
+      ]
+    ) {
+      const signature = sign("sha256", data, { ...key, saltLength });
+      assertEquals(signature.length, 256);
+      assert(verify("sha256", data, publicKey, signature));
+      assert(verify("sha256", data, { ...publicKey, saltLength }, signature));
+      // PKCS#1 v1.5 verification rejects PSS signatures
+      assert(!verify("sha256", data, rsaPublicPem, signature));
+    }
+
+    const signature = sign("sha256", data, { ...key, saltLength: 16 });
+    assert(
+      !verify("sha256", data, { ...publicKey, saltLength: 20 }, signature),
+    );
+  },
+});
+
+Deno.test({
+  name: "crypto.createSign|sign - ECDSA with generated keys",
+  fn() {
+    for (
+      const [namedCurve, size] of [
+        ["prime256v1", 64],
+        ["secp384r1", 96],
+        ["secp256k1", 64],
+      ] as const
+    ) {
+      const { privateKey, publicKey } = generateKeyPairSync("ec", {
+        namedCurve,
+      });
+
+      const der = createSign("sha256").update(data).sign(privateKey);
+      assert(createVerify("sha256").update(data).verify(publicKey, der));
+      assert(
+        !createVerify("sha256").update("other data").verify(publicKey, der),
+      );
+
+      const p1363 = sign("sha256", data, {
+        key: privateKey,
+        dsaEncoding: "ieee-p1363",
+      });
+      assertEquals(p1363.length, size);
+      assert(
+        verify("sha256", data, {
+          key: publicKey,
+          dsaEncoding: "ieee-p1363",
+        }, p1363),
+      );
+      assert(!verify("sha256", data, publicKey, p1363));
+    }
+  },
+});
+
+Deno.test({
+  name: "crypto.createSign|sign - invalid dsaEncoding",
+  fn() {
+    const { privateKey } = generateKeyPairSync("ec", {
+      namedCurve: "prime256v1",
+    });
+    assertThrows(
+      () =>
+        sign("sha256", data, {
+          key: privateKey,
+          // deno-lint-ignore no-explicit-any
+          dsaEncoding: "raw" as any,
+        }),
+      TypeError,
+    );
+  },
+});
+
+Deno.test({
+  name: "crypto.Sign|Verify updateFromStream",
+  async fn() {
+    const chunks = [data.subarray(0, 4), data.subarray(4)];
+    const signer = createSign("sha256");
+    // @ts-ignore: updateFromStream is Deno only
+    await signer.updateFromStream(ReadableStream.from(chunks));
+    assertEquals(signer.sign(rsaPrivatePem, "hex"), table[1].signature);
+
+    const verifier = createVerify("sha256");
+    // @ts-ignore: updateFromStream is Deno only
+    await verifier.updateFromStream(ReadableStream.from(chunks));
+    assert(verifier.verify(rsaPublicPem, table[1].signature, "hex"));
+  },
+});
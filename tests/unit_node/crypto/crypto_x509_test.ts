@@ -0,0 +1,71 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+import { X509Certificate } from "node:crypto";
+import { Buffer } from "node:buffer";
+import { assertEquals, assertThrows } from "@std/assert/mod.ts";
+import certPem from "../../testdata/tls/localhost.crt" with { type: "text" };
+
+const certDer = Buffer.from(
+  certPem.replace(/-----[A-Z ]+-----/g, "").replace(/\s/g, ""),
+  "base64",
+);
+
+Deno.test("X509Certificate parses DER like PEM", function () {
+  const pem = new X509Certificate(certPem);
+  const der = new X509Certificate(certDer);
+  assertEquals(der.subject, pem.subject);
+  assertEquals(
+    der.fingerprint256,
+    "66:AF:D1:4C:CF:73:A1:E2:5E:90:4C:FA:64:97:85:86:CB:38:51:B1:FB:9B:4A:02:DB:79:36:EB:4D:44:CC:78",
+  );
+  assertEquals(pem.fingerprint256, der.fingerprint256);
+  assertEquals(pem.raw, certDer);
+  assertEquals(pem.toString(), certPem);
+  assertEquals(pem.toJSON(), der.toString());
+});
+
+Deno.test("X509Certificate subjectAltName and publicKey", function () {
+  const cert = new X509Certificate(certPem);
+  assertEquals(cert.subjectAltName, "DNS:localhost");
+  assertEquals(cert.publicKey.type, "public");
+  assertEquals(cert.publicKey.asymmetricKeyType, "rsa");
+  assertEquals(cert.publicKey.asymmetricKeyDetails?.modulusLength, 2048);
+});
+
+Deno.test("X509Certificate checkHost", function () {
+  const cert = new X509Certificate(certPem);
+  assertEquals(cert.checkHost("localhost"), "localhost");
+  assertEquals(cert.checkHost("LOCALHOST"), "localhost");
+  assertEquals(cert.checkHost("example.com"), undefined);
+  // The subject is only checked if there are no DNS names.
+  assertEquals(cert.checkHost("localhost.local"), undefined);
+  assertEquals(
+    cert.checkHost("localhost.local", { subject: "always" }),
+    "localhost.local",
+  );
+  assertEquals(
+    cert.checkHost("localhost", { subject: "never" }),
+    "localhost",
+  );
+
+  assertThrows(() => cert.checkHost("local\x00host"), TypeError);
+  // deno-lint-ignore no-explicit-any
+  assertThrows(() => cert.checkHost("localhost", null as any), TypeError);
+  assertThrows(
+    // deno-lint-ignore no-explicit-any
+    () => cert.checkHost("localhost", { subject: "sometimes" as any }),
+    TypeError,
+  );
+  assertThrows(
+    // deno-lint-ignore no-explicit-any
+    () => cert.checkHost("localhost", { wildcards: 1 as any }),
+    TypeError,
+  );
+});
+
+Deno.test("X509Certificate checkIP", function () {
+  const cert = new X509Certificate(certPem);
+  assertEquals(cert.checkIP("127.0.0.1"), undefined);
+  assertEquals(cert.checkIP("::1"), undefined);
+  assertThrows(() => cert.checkIP("[::]"), TypeError);
+  assertThrows(() => cert.checkIP("localhost"), TypeError);
+});
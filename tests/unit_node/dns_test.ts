@@ -0,0 +1,161 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+import { assertEquals, assertThrows } from "@std/assert/mod.ts";
+import dns, { Resolver } from "node:dns";
+import { createSocket, type Socket } from "node:dgram";
+import { Buffer } from "node:buffer";
+
+function encodeName(name: string): Buffer {
+  const labels = name.split(".").map((label) =>
+    Buffer.concat([Buffer.from([label.length]), Buffer.from(label)])
+  );
+  return Buffer.concat([...labels, Buffer.from([0])]);
+}
+
+// A name server answering every question with the records of `answer`, or
+// never answering when it returns null.
+async function nameServer(
+  answer: (name: string, type: number) => [number, Buffer][] | null,
+): Promise<{ socket: Socket; address: string; questions: string[] }> {
+  const socket = createSocket("udp4");
+  const questions: string[] = [];
+
+  socket.on("message", (msg, { address, port }) => {
+    const labels = [];
+    let offset = 12;
+    while (msg[offset] !== 0) {
+      const length = msg[offset];
+      labels.push(msg.subarray(offset + 1, offset + 1 + length).toString());
+      offset += length + 1;
+    }
+    const name = labels.join(".");
+    const type = msg.readUInt16BE(offset + 1);
+    const question = msg.subarray(12, offset + 5);
+    questions.push(name);
+
+    const records = answer(name, type);
+    if (records === null) {
+      return;
+    }
+
+    const header = Buffer.alloc(12);
+    header.writeUInt16BE(msg.readUInt16BE(0), 0);
+    header.writeUInt16BE(0x8180, 2);
+    header.writeUInt16BE(1, 4);
+    header.writeUInt16BE(records.length, 6);
+    const answers = records.map(([ttl, data]) => {
+      const record = Buffer.alloc(12);
+      // a pointer to the name of the question
+      record.writeUInt16BE(0xc00c, 0);
+      record.writeUInt16BE(type, 2);
+      record.writeUInt16BE(1, 4);
+      record.writeUInt32BE(ttl, 6);
+      record.writeUInt16BE(data.length, 10);
+      return Buffer.concat([record, data]);
+    });
+    socket.send(Buffer.concat([header, question, ...answers]), port, address);
+  });
+
+  await new Promise<void>((resolve) => socket.bind(0, "127.0.0.1", resolve));
+  return {
+    socket,
+    address: `127.0.0.1:${socket.address().port}`,
+    questions,
+  };
+}
+
+Deno.test("[node/dns] resolve4 with the ttl option", {
+  sanitizeResources: false,
+}, async () => {
+  const server = await nameServer(() => [
+    [600, Buffer.from([1, 2, 3, 4])],
+    [60, Buffer.from([5, 6, 7, 8])],
+  ]);
+  const resolver = new Resolver();
+  resolver.setServers([server.address]);
+
+  const addresses = await new Promise((resolve, reject) =>
+    resolver.resolve4(
+      "example.org",
+      { ttl: true },
+      (err, addresses) => err ? reject(err) : resolve(addresses),
+    )
+  );
+  assertEquals(addresses, [
+    { address: "1.2.3.4", ttl: 600 },
+    { address: "5.6.7.8", ttl: 60 },
+  ]);
+
+  const promises = new dns.promises.Resolver();
+  promises.setServers([server.address]);
+  assertEquals(await promises.resolve4("example.org"), [
+    "1.2.3.4",
+    "5.6.7.8",
+  ]);
+
+  server.socket.close();
+});
+
+Deno.test("[node/dns] reverse", { sanitizeResources: false }, async () => {
+  const server = await nameServer(() => [[600, encodeName("example.org")]]);
+  const resolver = new dns.promises.Resolver();
+  resolver.setServers([server.address]);
+
+  assertEquals(await resolver.reverse("1.2.3.4"), ["example.org"]);
+  assertEquals(server.questions, ["4.3.2.1.in-addr.arpa"]);
+
+  server.socket.close();
+});
+
+Deno.test("[node/dns] reverse an invalid ip", () => {
+  assertThrows(
+    () => dns.reverse("foo", () => {}),
+    Error,
+    "getHostByAddr EINVAL foo",
+  );
+});
+
+Deno.test("[node/dns] resolver reports the errors of the queries", {
+  sanitizeResources: false,
+}, async () => {
+  const server = await nameServer(() => []);
+  const resolver = new dns.promises.Resolver();
+  resolver.setServers([server.address]);
+
+  const err = await resolver.resolveMx("example.org").catch((err) => err);
+  assertEquals(err.code, "ENODATA");
+  assertEquals(err.syscall, "queryMx");
+  assertEquals(err.hostname, "example.org");
+
+  server.socket.close();
+});
+
+Deno.test("[node/dns] resolver cancel", {
+  sanitizeResources: false,
+}, async () => {
+  const server = await nameServer(() => null);
+  const resolver = new Resolver();
+  resolver.setServers([server.address]);
+
+  const { promise, resolve } = Promise.withResolvers<
+    Error & { code?: string }
+  >();
+  resolver.resolve4("example.org", (err) => resolve(err!));
+  resolver.cancel();
+  const err = await promise;
+  assertEquals(err.code, "ECANCELLED");
+  assertEquals(err.message, "queryA ECANCELLED example.org");
+
+  server.socket.close();
+});
+
+Deno.test("[node/dns] resolver servers", () => {
+  const resolver = new Resolver();
+  assertEquals(Array.isArray(resolver.getServers()), true);
+
+  resolver.setServers(["127.0.0.1", "[::1]:5353", "8.8.8.8:53"]);
+  assertEquals(resolver.getServers(), ["127.0.0.1", "[::1]:5353", "8.8.8.8"]);
+
+  resolver.setServers([]);
+  assertEquals(resolver.getServers(), []);
+});
@@ -2,7 +2,8 @@
 
 import * as http2 from "node:http2";
 import * as net from "node:net";
-import { assert, assertEquals } from "@std/assert/mod.ts";
+import { Buffer } from "node:buffer";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 import { curlRequest } from "../unit/test_util.ts";
 
 for (const url of ["http://127.0.0.1:4246", "https://127.0.0.1:4247"]) {
@@ -164,3 +165,174 @@ Deno.test("[node/http2.createServer()]", {
   // Issue: https://github.com/denoland/deno/issues/22764
   await new Promise<void>((resolve) => server.on("close", resolve));
 });
+
+// Connects a client to the server, closing them waits for the session of
+// the server to be closed too.
+async function connect(server: http2.Http2Server) {
+  const sessionClosed = Promise.withResolvers<void>();
+  server.once("session", (session) => {
+    session.on("close", () => sessionClosed.resolve());
+  });
+  const listening = Promise.withResolvers<void>();
+  server.listen(0, () => listening.resolve());
+  await listening.promise;
+  const { port } = <net.AddressInfo> server.address();
+  const client = http2.connect(`http://127.0.0.1:${port}`);
+
+  const close = async () => {
+    client.close();
+    await sessionClosed.promise;
+    const serverClosed = Promise.withResolvers<void>();
+    server.close(() => serverClosed.resolve());
+    await serverClosed.promise;
+  };
+  return { client, close };
+}
+
+Deno.test("[node/http2 server] respond with trailers", {
+  ignore: Deno.build.os === "windows",
+}, async () => {
+  const server = http2.createServer();
+  server.on("stream", (stream, headers) => {
+    let body = "";
+    stream.setEncoding("utf8");
+    stream.on("data", (chunk) => body += chunk);
+    stream.on("end", () => {
+      stream.respond({ ":status": 201, "x-path": headers[":path"] }, {
+        waitForTrailers: true,
+      });
+      stream.on("wantTrailers", () => stream.sendTrailers({ "x-done": "1" }));
+      stream.end(`${body} world`);
+    });
+  });
+  const { client, close } = await connect(server);
+
+  const req = client.request({ ":method": "POST", ":path": "/foo" });
+  req.setEncoding("utf8");
+  let data = "";
+  req.on("data", (chunk) => data += chunk);
+  const response = new Promise<http2.IncomingHttpHeaders>((resolve) =>
+    req.on("response", resolve)
+  );
+  const trailers = new Promise<http2.IncomingHttpHeaders>((resolve) =>
+    req.on("trailers", resolve)
+  );
+  const end = new Promise((resolve) => req.on("end", resolve));
+  req.end("hello");
+
+  const headers = await response;
+  assertEquals(headers[":status"], 201);
+  assertEquals(headers["x-path"], "/foo");
+  assertEquals(await trailers, { "x-done": "1" });
+  await end;
+  assertEquals(data, "hello world");
+
+  await close();
+});
+
+Deno.test("[node/http2 session] ping and settings", {
+  ignore: Deno.build.os === "windows",
+}, async () => {
+  const server = http2.createServer();
+  const { client, close } = await connect(server);
+
+  const ping = Promise.withResolvers<[number, Buffer]>();
+  client.ping(Buffer.from("abcdefgh"), (err, duration, payload) => {
+    if (err) {
+      ping.reject(err);
+    } else {
+      ping.resolve([duration, payload]);
+    }
+  });
+  const [duration, payload] = await ping.promise;
+  assert(duration >= 0);
+  assertEquals(payload.toString(), "abcdefgh");
+  assertThrows(
+    () => client.ping(Buffer.from("abc"), () => {}),
+    RangeError,
+    "HTTP2 ping payload must be 8 bytes",
+  );
+
+  const applied = Promise.withResolvers<http2.Settings>();
+  client.settings({ initialWindowSize: 1 << 20 }, (err, settings) => {
+    if (err) {
+      applied.reject(err);
+    } else {
+      applied.resolve(settings);
+    }
+  });
+  assertEquals((await applied.promise).initialWindowSize, 1 << 20);
+  assertEquals(client.localSettings.initialWindowSize, 1 << 20);
+  assertEquals(client.localSettings.enablePush, false);
+  assertThrows(
+    () => client.settings({ maxFrameSize: 1 }),
+    RangeError,
+    'Invalid value for setting "maxFrameSize": 1',
+  );
+
+  await close();
+});
+
+Deno.test("[node/http2 server] pushStream to a client without push", {
+  ignore: Deno.build.os === "windows",
+}, async () => {
+  const server = http2.createServer();
+  const pushError = Promise.withResolvers<Error & { code?: string }>();
+  server.on("stream", (stream) => {
+    assert(stream.pushAllowed);
+    stream.pushStream({ ":path": "/style.css" }, (err) => {
+      pushError.resolve(err!);
+      stream.respond({ ":status": 200 }, { endStream: true });
+    });
+  });
+  const { client, close } = await connect(server);
+
+  const req = client.request({ ":path": "/" });
+  req.resume();
+  const end = new Promise((resolve) => req.on("end", resolve));
+
+  assertEquals((await pushError.promise).code, "ERR_HTTP2_PUSH_DISABLED");
+  await end;
+
+  await close();
+});
+
+Deno.test("[node/http2] packed settings", () => {
+  const packed = http2.getPackedSettings({
+    headerTableSize: 100,
+    initialWindowSize: 100,
+    maxFrameSize: 20000,
+    maxConcurrentStreams: 200,
+    maxHeaderListSize: 100,
+    enablePush: true,
+    enableConnectProtocol: false,
+  });
+  assertEquals(
+    [...packed],
+    [
+      [0x00, 0x01, 0x00, 0x00, 0x00, 0x64],
+      [0x00, 0x03, 0x00, 0x00, 0x00, 0xc8],
+      [0x00, 0x05, 0x00, 0x00, 0x4e, 0x20],
+      [0x00, 0x04, 0x00, 0x00, 0x00, 0x64],
+      [0x00, 0x06, 0x00, 0x00, 0x00, 0x64],
+      [0x00, 0x02, 0x00, 0x00, 0x00, 0x01],
+      [0x00, 0x08, 0x00, 0x00, 0x00, 0x00],
+    ].flat(),
+  );
+  assertEquals(http2.getUnpackedSettings(packed), {
+    headerTableSize: 100,
+    initialWindowSize: 100,
+    maxFrameSize: 20000,
+    maxConcurrentStreams: 200,
+    maxHeaderListSize: 100,
+    maxHeaderSize: 100,
+    enablePush: true,
+    enableConnectProtocol: false,
+  });
+  assertEquals(http2.getDefaultSettings().initialWindowSize, 65535);
+  assertThrows(
+    () => http2.getUnpackedSettings(Buffer.alloc(5)),
+    RangeError,
+    "Packed settings length must be a multiple of six",
+  );
+});
@@ -1007,3 +1007,41 @@ Deno.test("[node/http] ServerResponse getHeaders", () => {
 Deno.test("[node/http] maxHeaderSize is defined", () => {
   assertEquals(http.maxHeaderSize, 16_384);
 });
+
+Deno.test("[node/http] Agent limits the connections", async () => {
+  let active = 0;
+  let maxActive = 0;
+  const server = Deno.serve(
+    { port: 0, onListen: () => {} },
+    async () => {
+      maxActive = Math.max(maxActive, ++active);
+      await new Promise((resolve) => setTimeout(resolve, 10));
+      active--;
+      return new Response("ok");
+    },
+  );
+  const agent = new http.Agent({ keepAlive: true, maxSockets: 1 });
+  const get = () =>
+    new Promise<string>((resolve, reject) => {
+      const req = http.get(
+        `http://localhost:${server.addr.port}/`,
+        { agent },
+        (res: http.IncomingMessage) => {
+          let body = "";
+          res.on("data", (chunk: string) => body += chunk);
+          res.on("end", () => resolve(body));
+        },
+      );
+      req.once("error", reject);
+    });
+
+  assertEquals(await Promise.all([get(), get(), get()]), ["ok", "ok", "ok"]);
+  assertEquals(maxActive, 1);
+  // @ts-ignore not in the types of node:http
+  assertEquals(agent.getPoolStats(), {
+    [`localhost:${server.addr.port}`]: { active: 0, pending: 0, requests: 3 },
+  });
+
+  agent.destroy();
+  await server.shutdown();
+});
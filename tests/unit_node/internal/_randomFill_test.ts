@@ -63,3 +63,39 @@ Deno.test("[node/crypto.randomFillSync] Fill beginning, explicit offset+size", (
 Deno.test("[node/crypto.randomFillSync] Invalid offst/size", () => {
   assertThrows(() => randomFillSync(Buffer.alloc(10), 1, 10));
 });
+
+Deno.test("[node/crypto.randomFillSync] Offset and size are in elements", () => {
+  const buf = new Uint32Array(4);
+  randomFillSync(buf, 1, 2);
+  assertEquals(buf[0], 0);
+  assertEquals(buf[3], 0);
+  assertNotEquals(buf[1] | buf[2], 0);
+
+  assertThrows(() => randomFillSync(buf, 2, 3), RangeError);
+});
+
+Deno.test("[node/crypto.randomFillSync] Respects the byte offset of views", () => {
+  const buf = Buffer.alloc(10);
+  randomFillSync(buf.subarray(5));
+  validateZero(buf.subarray(0, 5));
+  validateNonZero(buf.subarray(5));
+});
+
+Deno.test("[node/crypto.randomFill] ArrayBuffer", async () => {
+  const { promise, resolve } = Promise.withResolvers<ArrayBuffer>();
+  const buf = new ArrayBuffer(10);
+
+  randomFill(buf, 5, (err, filled) => {
+    assertEquals(err, null);
+    resolve(filled);
+  });
+
+  const filled = new Uint8Array(await promise);
+  validateZero(Buffer.from(filled.subarray(0, 5)));
+  validateNonZero(Buffer.from(filled.subarray(5)));
+});
+
+Deno.test("[node/crypto.randomFill] Invalid callback", () => {
+  // @ts-ignore: invalid callback
+  assertThrows(() => randomFill(Buffer.alloc(10), 0, 5, "cb"), TypeError);
+});
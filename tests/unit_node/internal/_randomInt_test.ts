@@ -30,3 +30,18 @@ Deno.test("[node/crypto.randomInt] Min, Max and Callback", () => {
 Deno.test("[node/crypto.randomInt] Min is bigger than Max", () => {
   assertThrows(() => randomInt(45, 34));
 });
+
+Deno.test("[node/crypto.randomInt] Range larger than 32 bits", () => {
+  const min = 2 ** 40;
+  assert(between(randomInt(min, min + 2 ** 47), min, min + 2 ** 47));
+  assert(between(randomInt(-(2 ** 47), 2 ** 47), -(2 ** 47), 2 ** 47));
+});
+
+Deno.test("[node/crypto.randomInt] Invalid arguments", () => {
+  assertThrows(() => randomInt(2 ** 48 + 1), RangeError);
+  assertThrows(() => randomInt(0, 2 ** 53), TypeError);
+  assertThrows(() => randomInt(1.5, 3), TypeError);
+  assertThrows(() => randomInt(5, 5), RangeError);
+  // @ts-ignore: invalid callback
+  assertThrows(() => randomInt(1, 5, "cb"), TypeError);
+});
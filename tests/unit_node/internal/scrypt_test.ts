@@ -1,7 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 import { scrypt, scryptSync } from "node:crypto";
 import { Buffer } from "node:buffer";
-import { assertEquals } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 
 Deno.test("scrypt works correctly", async () => {
   const { promise, resolve } = Promise.withResolvers<boolean>();
@@ -188,3 +188,39 @@ Deno.test("scryptSync with options works correctly", () => {
     ]),
   );
 });
+
+Deno.test("scrypt derives keys longer than 64 bytes", async () => {
+  const expected = Buffer.from(
+    "16dbc8906763c7f048977a68f9d305f7710e068ca2cd95dab372125bb3f19608175003c79f9cdee65d2e45fc1f169afde0a6806f5d4f2ba0584249d2e66c2c96cee540dfeaecc8a9e9100148da804068",
+    "hex",
+  );
+  assertEquals(scryptSync("password", "salt", 80, { N: 1024 }), expected);
+
+  const { promise, resolve, reject } = Promise.withResolvers<Buffer>();
+  scrypt("password", "salt", 80, { N: 1024 }, (err, key) => {
+    if (err) reject(err);
+    else resolve(key!);
+  });
+  assertEquals(await promise, expected);
+});
+
+Deno.test("scryptSync derives short and empty keys", () => {
+  assertEquals(
+    scryptSync("password", "salt", 4, { N: 1024 }),
+    Buffer.from("16dbc890", "hex"),
+  );
+  assertEquals(scryptSync("password", "salt", 0), Buffer.alloc(0));
+});
+
+Deno.test("scrypt rejects invalid parameters", async () => {
+  assertThrows(
+    () => scryptSync("password", "salt", 32, { N: 1000 }),
+    TypeError,
+    "N must be a power of 2",
+  );
+
+  const { promise, resolve } = Promise.withResolvers<unknown>();
+  scrypt("password", "salt", 32, { N: 1000 }, (err) => resolve(err));
+  const err = await promise;
+  assert(err instanceof TypeError);
+});
@@ -1,7 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 import * as net from "node:net";
-import { assert, assertEquals } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 import * as path from "@std/path/mod.ts";
 import * as http from "node:http";
 
@@ -200,3 +200,51 @@ Deno.test("[node/net] multiple Sockets should get correct server data", async ()
     assertEquals(sockets[i].events, [`${i}`.repeat(3), `${i}`.repeat(3)]);
   }
 });
+
+Deno.test("[node/net] BlockList", () => {
+  const blockList = new net.BlockList();
+  blockList.addAddress("123.123.123.123");
+  blockList.addRange("10.0.0.1", "10.0.0.10");
+  blockList.addSubnet("8592:757c:efae:4e45::", 64, "ipv6");
+
+  assert(blockList.check("123.123.123.123"));
+  assert(!blockList.check("10.0.0.11"));
+  assert(blockList.check("10.0.0.3"));
+  assert(blockList.check("::ffff:7b7b:7b7b", "ipv6"));
+  assert(blockList.check("8592:757c:efae:4e45:f::", "ipv6"));
+  assert(!blockList.check("not an address"));
+  assert(blockList.check(new net.SocketAddress({ address: "10.0.0.5" })));
+
+  assertEquals(blockList.rules, [
+    "Subnet: IPv6 8592:757c:efae:4e45::/64",
+    "Range: IPv4 10.0.0.1-10.0.0.10",
+    "Address: IPv4 123.123.123.123",
+  ]);
+
+  assertThrows(() => blockList.addRange("10.0.0.10", "10.0.0.1"), TypeError);
+  assertThrows(() => blockList.addSubnet("10.0.0.0", 33), RangeError);
+});
+
+Deno.test("[node/net] SocketAddress", () => {
+  const address = new net.SocketAddress({
+    address: "::1",
+    family: "ipv6",
+    port: 8080,
+  });
+  assertEquals(address.toJSON(), {
+    address: "::1",
+    port: 8080,
+    family: "ipv6",
+    flowlabel: 0,
+  });
+  assert(net.SocketAddress.isSocketAddress(address));
+  assert(!net.SocketAddress.isSocketAddress({}));
+
+  assertEquals(new net.SocketAddress().address, "127.0.0.1");
+  assertThrows(
+    () => new net.SocketAddress({ address: "::1" }),
+    Error,
+    "Invalid socket address",
+  );
+  assertThrows(() => new net.SocketAddress({ family: "ipv5" }), TypeError);
+});
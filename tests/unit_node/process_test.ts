@@ -738,6 +738,19 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "process.hrtime.bigint is monotonic and matches process.hrtime",
+  fn() {
+    const before = process.hrtime.bigint();
+    const [sec, nano] = process.hrtime();
+    const after = process.hrtime.bigint();
+    const time = BigInt(sec) * 1_000_000_000n + BigInt(nano);
+    assert(before <= time);
+    assert(time <= after);
+    assert(Number.isInteger(nano) && nano < 1_000_000_000);
+  },
+});
+
 Deno.test("process.on, process.off, process.removeListener doesn't throw on unimplemented events", () => {
   const events = [
     "beforeExit",
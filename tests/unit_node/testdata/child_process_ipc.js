@@ -0,0 +1,8 @@
+// Echoes the messages of the parent until it's asked to disconnect.
+process.on("message", (message) => {
+  if (message === "disconnect") {
+    process.disconnect();
+    return;
+  }
+  process.send({ echo: message, connected: process.connected });
+});
@@ -2,11 +2,18 @@
 import {
   cachedDataVersionTag,
   deserialize,
+  Deserializer,
   getHeapStatistics,
   serialize,
+  Serializer,
   setFlagsFromString,
 } from "node:v8";
-import { assertEquals } from "@std/assert/mod.ts";
+import { Buffer } from "node:buffer";
+import {
+  assertEquals,
+  assertStrictEquals,
+  assertThrows,
+} from "@std/assert/mod.ts";
 
 // https://github.com/nodejs/node/blob/a2bbe5ff216bc28f8dac1c36a8750025a93c3827/test/parallel/test-v8-version-tag.js#L6
 Deno.test({
@@ -64,3 +71,76 @@ Deno.test({
     assertEquals(d, { a: 1 });
   },
 });
+
+Deno.test({
+  name: "serialize deserialize builtins",
+  fn() {
+    const value = {
+      map: new Map([[1, "a"]]),
+      set: new Set([2n]),
+      date: new Date(0),
+      bytes: new Uint16Array([1, 2, 3]),
+      nested: [null, undefined, 1.5, "b"],
+    };
+    assertEquals(deserialize(serialize(value)), value);
+    assertThrows(() => serialize(() => {}), Error, "could not be cloned");
+  },
+});
+
+Deno.test({
+  name: "Serializer Deserializer",
+  fn() {
+    const ser = new Serializer();
+    ser.writeHeader();
+    ser.writeUint32(300);
+    ser.writeValue({ a: [1, "x"] });
+    ser.writeUint64(1, 2);
+    ser.writeDouble(-0.5);
+    ser.writeRawBytes(new Uint8Array([7, 8, 9]));
+    const buffer = ser.releaseBuffer();
+    assertEquals(buffer instanceof Buffer, true);
+
+    const der = new Deserializer(buffer);
+    assertEquals(der.readHeader(), true);
+    assertEquals(der.getWireFormatVersion() > 0, true);
+    assertEquals(der.readUint32(), 300);
+    assertEquals(der.readValue(), { a: [1, "x"] });
+    assertEquals(der.readUint64(), [1, 2]);
+    assertEquals(der.readDouble(), -0.5);
+    assertEquals([...der.readRawBytes(3)], [7, 8, 9]);
+    assertThrows(() => der.readUint32(), Error, "ReadUint32() failed");
+  },
+});
+
+Deno.test({
+  name: "Serializer Deserializer shared array buffers",
+  fn() {
+    const sab = new SharedArrayBuffer(4);
+    assertThrows(() => serialize(sab), Error, "could not be cloned");
+
+    const ser = new Serializer();
+    ser._getSharedArrayBufferId = () => 3;
+    ser.writeHeader();
+    ser.writeValue({ sab });
+
+    const der = new Deserializer(ser.releaseBuffer());
+    der.transferArrayBuffer(3, sab);
+    der.readHeader();
+    assertStrictEquals(der.readValue().sab, sab);
+  },
+});
+
+// The bytes of `v8.serialize(Buffer.from([1, 2, 3]))` in Node.
+Deno.test({
+  name: "deserialize array buffer views written by Node",
+  fn() {
+    const data = Buffer.from([0xff, 0x0f, 0x5c, 0x0a, 0x03, 1, 2, 3]);
+    const value = deserialize(data);
+    assertEquals(Buffer.isBuffer(value), true);
+    assertEquals([...value], [1, 2, 3]);
+
+    const der = new Deserializer(data);
+    der.readHeader();
+    assertThrows(() => der.readValue(), Error);
+  },
+});
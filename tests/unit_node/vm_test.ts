@@ -1,6 +1,11 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
-import { assertEquals, assertThrows } from "@std/assert/mod.ts";
 import {
+  assertEquals,
+  assertStringIncludes,
+  assertThrows,
+} from "@std/assert/mod.ts";
+import {
+  compileFunction,
   createContext,
   isContext,
   runInContext,
@@ -148,3 +153,66 @@ reject().catch(() => {})
     script.runInNewContext();
   },
 });
+
+Deno.test({
+  name: "vm runInNewContext timeout",
+  fn() {
+    const err = assertThrows(() =>
+      runInNewContext("while (true) {}", {}, { timeout: 10 })
+    ) as Error & { code?: string };
+    assertEquals(err.code, "ERR_SCRIPT_EXECUTION_TIMEOUT");
+    assertEquals(err.message, "Script execution timed out after 10ms");
+  },
+});
+
+Deno.test({
+  name: "vm createContext microtaskMode afterEvaluate",
+  fn() {
+    const context = createContext({}, { microtaskMode: "afterEvaluate" });
+    const result = runInContext(
+      "const r = []; Promise.resolve().then(() => r.push(1)); r",
+      context,
+    );
+    assertEquals(result.length, 1);
+  },
+});
+
+Deno.test({
+  name: "vm createContext codeGeneration strings",
+  fn() {
+    const context = createContext({}, { codeGeneration: { strings: false } });
+    const err = assertThrows(() => runInContext("eval('1')", context));
+    assertEquals((err as Error).name, "EvalError");
+  },
+});
+
+Deno.test({
+  name: "vm Script filename and lineOffset",
+  fn() {
+    const script = new Script("throw new Error('error')", {
+      filename: "foo.js",
+      lineOffset: 2,
+    });
+    const err = assertThrows(() => script.runInThisContext()) as Error;
+    assertStringIncludes(err.stack!, "foo.js:3");
+  },
+});
+
+Deno.test({
+  name: "vm compileFunction",
+  fn() {
+    const add = compileFunction("return a + b + c", ["a", "b"], {
+      contextExtensions: [{ c: 3 }],
+    });
+    assertEquals(add(1, 2), 6);
+
+    const context = createContext({ d: 4 });
+    const get = compileFunction("return d", [], { parsingContext: context });
+    assertEquals(get(), 4);
+
+    assertThrows(
+      () => compileFunction("return d", [], { parsingContext: {} }),
+      TypeError,
+    );
+  },
+});
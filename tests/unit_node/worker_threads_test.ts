@@ -538,7 +538,130 @@ Deno.test({
     await deferred.promise;
     const promise = worker.terminate();
     assertEquals(typeof promise.then, "function");
-    assertEquals(await promise, 0);
+    assertEquals(await promise, 1);
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] Worker exit event",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      import process from "node:process";
+      parentPort.postMessage("ok");
+      process.exit(3);
+      `,
+      { eval: true },
+    );
+    const messages: string[] = [];
+    worker.on("message", (data) => messages.push(data));
+
+    assertEquals(await once(worker, "exit"), [3]);
+    // the messages are received before "exit"
+    assertEquals(messages, ["ok"]);
+    // it has already exited
+    assertEquals(await worker.terminate(), 3);
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] Worker exit event with process.exitCode",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import process from "node:process";
+      process.exitCode = 2;
+      `,
+      { eval: true },
+    );
+    assertEquals(await once(worker, "exit"), [2]);
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] Worker terminate emits exit",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      "setInterval(() => {}, 1000)",
+      { eval: true },
+    );
+    const exit = once(worker, "exit");
+    assertEquals(await worker.terminate(), 1);
+    assertEquals(await exit, [1]);
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] MessagePort transferred with postMessage",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      parentPort.once("message", ({ port }) => {
+        port.on("message", (message) => {
+          port.postMessage(message + " pong");
+          port.close();
+        });
+      });
+      `,
+      { eval: true },
+    );
+    const { port1, port2 } = new workerThreads.MessageChannel();
+    worker.postMessage({ port: port2 }, [port2]);
+    port1.postMessage("ping");
+
+    const deferred = Promise.withResolvers<string>();
+    port1.on("message", (message) => deferred.resolve(message));
+    assertEquals(await deferred.promise, "ping pong");
+    port1.close();
+    await worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] MessagePort transferred from the worker",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { MessageChannel, parentPort } from "node:worker_threads";
+      const { port1, port2 } = new MessageChannel();
+      parentPort.postMessage({ port: port2 }, [port2]);
+      port1.postMessage("hello from the worker's channel");
+      port1.close();
+      `,
+      { eval: true },
+    );
+    const [{ port }] = await once(worker, "message");
+    const deferred = Promise.withResolvers<string>();
+    port.on("message", (message: string) => deferred.resolve(message));
+    assertEquals(await deferred.promise, "hello from the worker's channel");
+    port.close();
+    await worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] SharedArrayBuffer with postMessage",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      parentPort.once("message", (sab) => {
+        const counter = new Int32Array(sab);
+        Atomics.add(counter, 0, 41);
+        parentPort.postMessage("done");
+      });
+      `,
+      { eval: true },
+    );
+    const sab = new SharedArrayBuffer(Int32Array.BYTES_PER_ELEMENT);
+    const counter = new Int32Array(sab);
+    counter[0] = 1;
+    worker.postMessage(sab);
+    assertEquals((await once(worker, "message"))[0], "done");
+    assertEquals(Atomics.load(counter, 0), 42);
+    await worker.terminate();
   },
 });
 
@@ -1,15 +1,24 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { assert, assertEquals } from "@std/assert/mod.ts";
+import { assert, assertEquals, assertThrows } from "@std/assert/mod.ts";
 import { fromFileUrl, relative } from "@std/path/mod.ts";
 import {
   brotliCompress,
+  BrotliCompress,
   brotliCompressSync,
+  brotliDecompress,
+  BrotliDecompress,
   brotliDecompressSync,
+  constants,
   createBrotliCompress,
   createBrotliDecompress,
   createDeflate,
+  createInflate,
+  deflateRawSync,
+  deflateSync,
   gzipSync,
+  inflateRawSync,
+  inflateSync,
   unzipSync,
 } from "node:zlib";
 import { Buffer } from "node:buffer";
@@ -183,3 +192,102 @@ Deno.test("brotli decompress flush restore size", async () => {
   );
   assertEquals(output.length, input.length);
 });
+
+Deno.test("brotli decompression async", async () => {
+  const compressed = brotliCompressSync("hello world");
+  const decompressed: Buffer = await new Promise((resolve, reject) =>
+    brotliDecompress(compressed, (err, res) => err ? reject(err) : resolve(res))
+  );
+  assertEquals(decompressed.toString(), "hello world");
+});
+
+Deno.test("brotli stream with params", async () => {
+  const input = "hello world ".repeat(1000);
+  const compress = new BrotliCompress({
+    params: {
+      [constants.BROTLI_PARAM_QUALITY]: 4,
+      [constants.BROTLI_PARAM_SIZE_HINT]: input.length,
+    },
+  });
+  assert(compress instanceof BrotliCompress);
+  const compressed = await buffer(Readable.from([input]).pipe(compress));
+  assert(compressed.length < input.length);
+  assertEquals(brotliDecompressSync(compressed).toString(), input);
+
+  const output = await buffer(
+    Readable.from([compressed]).pipe(new BrotliDecompress()),
+  );
+  assertEquals(output.toString(), input);
+
+  assertThrows(
+    () => new BrotliCompress({ params: { 100: 1 } }),
+    RangeError,
+    "100 is not a valid Brotli parameter",
+  );
+});
+
+Deno.test("brotli stream reports the errors of the decoder", async () => {
+  const compressed = brotliCompressSync("hello world ".repeat(100));
+  const truncated = await new Promise<Error>((resolve) => {
+    const decompress = createBrotliDecompress();
+    decompress.on("error", resolve);
+    decompress.resume();
+    decompress.end(compressed.subarray(0, compressed.length - 4));
+  });
+  assertEquals(truncated.message, "unexpected end of file");
+  // deno-lint-ignore no-explicit-any
+  assertEquals((truncated as any).code, "Z_BUF_ERROR");
+
+  const invalid = await new Promise<Error>((resolve) => {
+    const decompress = createBrotliDecompress();
+    decompress.on("error", resolve);
+    decompress.resume();
+    decompress.end(Buffer.from("not brotli at all"));
+  });
+  assertEquals(invalid.message, "Decompression failed");
+  // deno-lint-ignore no-explicit-any
+  assert((invalid as any).code.startsWith("ERR__ERROR_FORMAT"));
+});
+
+Deno.test(
+  "zlib deflate with dictionary",
+  { sanitizeResources: false },
+  () => {
+    const dictionary = Buffer.from("hello world");
+    const input = "hello hello hello world";
+
+    const compressed = deflateSync(input, { dictionary });
+    assertEquals(inflateSync(compressed, { dictionary }).toString(), input);
+
+    const raw = deflateRawSync(input, { dictionary });
+    assertEquals(inflateRawSync(raw, { dictionary }).toString(), input);
+  },
+);
+
+Deno.test(
+  "zlib params changes the level of a stream",
+  { sanitizeResources: false },
+  async () => {
+    const input = Buffer.from("hello world ".repeat(1000));
+    const deflate = createDeflate({ level: 0 });
+    const chunks: Buffer[] = [];
+    deflate.on("data", (chunk) => chunks.push(chunk));
+    const ended = new Promise((resolve) => deflate.on("end", resolve));
+
+    deflate.write(input);
+    await new Promise<void>((resolve) =>
+      deflate.params(9, constants.Z_DEFAULT_STRATEGY, resolve)
+    );
+    deflate.end(input);
+    await ended;
+
+    const compressed = Buffer.concat(chunks);
+    // the first half is stored, the second one compressed
+    assert(compressed.length > input.length);
+    assert(compressed.length < 2 * input.length);
+    const inflated = await buffer(
+      Readable.from([compressed]).pipe(createInflate()),
+    );
+    assertEquals(inflated.toString(), input.toString().repeat(2));
+  },
+);